@@ -1,51 +1,128 @@
+mod alerts;
+mod avatars;
+mod device_flow;
+mod diagnostics;
+mod direction;
+mod events;
 mod fonts;
+mod idle;
+mod incremental;
 mod notification_state;
+mod plugin_sections;
+mod power;
+mod release_radar;
+mod reminders;
 mod repo_paths;
 mod repo_state;
+mod report;
 mod review;
+mod review_wait;
+mod rules;
+mod saved_searches;
 mod scheduler;
 mod search;
+mod snooze;
+mod stale_branches;
 mod state;
+mod sync_runner;
 mod time;
+mod token_check;
+mod tray;
 mod ui;
+mod viewer_check;
+mod watchdog;
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, mpsc::Receiver},
+    time::{Duration, Instant},
+};
 
-use std::{collections::BTreeMap, fs, time::Duration};
-
+use chrono::{Local, Utc};
 use eframe::{
     App, CreationContext, Frame,
     egui::{self, Color32, Context},
 };
 
 use self::{
-    fonts::install_international_fonts,
+    alerts::{AlertThrottle, DesktopAlert},
+    avatars::{AVATARS_DIR, AvatarLoader},
+    device_flow::{DeviceFlowJob, oauth_client_id},
+    diagnostics::{StoreUsage, format_bytes},
+    direction::{accounts_panel_side, content_layout, preview_panel_side, uses_right_to_left},
+    events::{AppEvent, EventBus},
+    fonts::{apply_ui_scale, install_international_fonts},
+    idle::IdleMonitor,
+    notification_state::{
+        is_discussion, is_mention, is_other_notification, is_review_request, section_stats,
+    },
+    plugin_sections::PluginSectionsState,
+    power::PowerMonitor,
+    release_radar::ReleaseRadarState,
+    reminders::{DUE_INPUT_HINT, ReminderAction, ReminderEditor},
     repo_paths::{
         canonical_repo_key, normalize_hydrated_repo_path_accounts, normalize_hydrated_repo_paths,
     },
     repo_state::RepoState,
+    report::ReportAccount,
     review::{
         ReviewWindowAction, custom_review_command_available,
         default_pr_description_prompt_md_path_display, default_review_prompt_md_path_display,
-        render_review_window, review_prompt_command_available,
+        render_review_window, review_output_len, review_prompt_command_available,
     },
+    rules::{RuleSet, rule_problem},
+    saved_searches::saved_search_problem,
     scheduler::BatchRefreshScheduler,
-    state::AccountState,
+    state::{AccountState, BulkAction},
+    sync_runner::SyncRunner,
+    token_check::PendingTokenCheck,
+    tray::{Tray, TrayCommand},
     ui::{
-        account_overview, render_account_card, render_repository_card,
-        render_tracked_account_badges, responsive_accounts_panel_width, tracked_account_heading,
-        uses_compact_account_rows,
+        HistoryView, PreviewAction, StatusColors, WhatsNew, account_overview, apply_accent,
+        consume_refresh_shortcut, render_account_card, render_combined_inbox, render_due_section,
+        render_history_window, render_plugin_sections, render_preview_pane, render_release_radar,
+        render_repository_card, render_stale_branches, render_thread_timeline,
+        render_tracked_account_badges, render_whats_new_window, responsive_accounts_panel_width,
+        status_colors, tracked_account_heading, uses_compact_account_rows,
     },
 };
 
 use crate::{
+    deep_link::{self, DeepLink},
     domain::{
-        GitHubAccount, PullRequestReviewer, PullRequestReviewerStatus, ReviewCommandSettings,
+        ActivityHistory, AppSettings, DoubleClickAction, Experiment, Forge, GitHubAccount,
+        InboxBackend, LayoutDirection, NetworkSettings, NotificationChips, NotificationItem,
+        NotificationRule, PullRequestReviewer, PullRequestReviewerStatus, ReleaseRadar,
+        ReminderList, RepoTier, ReviewCommandSettings, RuleAction, SavedSearch, SavedSearchKind,
+        SearchMode, SectionAlertThresholds, StatusPalette, SwipeAction, SyncBackend, SyncSettings,
+        ThreadStage, TimestampStyle, WorkingHours, normalize_api_base_url,
     },
-    storage::AccountStore,
+    github, plugins,
+    replay::ReplayServer,
+    settings::{AccountUiState, UiState},
+    storage::{self, AccountStore, Backup},
+    sync::{SyncDocument, SyncRemote, TriageSettings},
 };
 
 pub const APP_NAME: &str = "Reminder";
 
+/// Window title carrying the profile name and the aggregate unread count, so
+/// the taskbar entry doubles as an unread badge.
+pub fn window_title(profile: Option<&str>, unread: usize) -> String {
+    let mut title = match profile {
+        Some(name) => format!("{APP_NAME} ({name})"),
+        None => APP_NAME.to_owned(),
+    };
+    if unread > 0 {
+        title = format!("[{unread}] {title}");
+    }
+    title
+}
+
 pub const CJK_FONT_NAME: &str = "CJK_Fallback_Font";
+const RTL_FONT_NAME: &str = "RTL_Fallback_Font";
 const ACCOUNTS_PANEL_MIN_WIDTH: f32 = 140.0;
 const ACCOUNTS_PANEL_MAX_WIDTH: f32 = 240.0;
 const ACCOUNTS_PANEL_WIDTH_RATIO: f32 = 0.24;
@@ -59,6 +136,19 @@ const MAX_REVIEW_OUTPUT_CHARS: usize = 20_000;
 const ACTIVE_REVIEW_REPAINT_MS: u64 = 50;
 const REVIEW_REQUEST_REASON: &str = "review_requested";
 const MENTION_REASONS: &[&str] = &["mention", "team_mention"];
+const KNOWN_NOTIFICATION_REASONS: &[&str] = &[
+    "review_requested",
+    "mention",
+    "team_mention",
+    "assign",
+    "author",
+    "comment",
+    "ci_activity",
+    "state_change",
+    "security_alert",
+    "subscribed",
+    "manual",
+];
 const APPROVED_REVIEW_LABEL_COLOR: Color32 = Color32::from_rgb(80, 170, 90);
 const PENDING_REVIEW_LABEL_COLOR: Color32 = Color32::from_rgb(120, 200, 255);
 
@@ -85,14 +175,78 @@ const SYSTEM_FONT_CANDIDATES: &[&str] = &[
 
 #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
 const SYSTEM_FONT_CANDIDATES: &[&str] = &[];
+
+#[cfg(target_os = "macos")]
+const RTL_FONT_CANDIDATES: &[&str] = &["/System/Library/Fonts/Supplemental/Arial Unicode.ttf"];
+
+#[cfg(target_os = "windows")]
+const RTL_FONT_CANDIDATES: &[&str] = &["C:\\Windows\\Fonts\\segoeui.ttf"];
+
+#[cfg(target_os = "linux")]
+const RTL_FONT_CANDIDATES: &[&str] = &[
+    "/usr/share/fonts/truetype/noto/NotoSansArabic-Regular.ttf",
+    "/usr/share/fonts/truetype/noto/NotoSansHebrew-Regular.ttf",
+    "/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf",
+];
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+const RTL_FONT_CANDIDATES: &[&str] = &[];
 const AUTO_REFRESH_INTERVAL_SECS: u64 = 180;
+/// Automatic refreshes pause until the quota resets once fewer requests remain.
+const RATE_LIMIT_BACKOFF_REMAINING: u32 = 200;
+const MIN_PASSPHRASE_LEN: usize = 8;
+/// How often long-lived caches and ledgers are trimmed.
+const STORE_COMPACTION_INTERVAL: Duration = Duration::from_secs(60 * 60);
 
 pub struct ReminderApp {
+    profile: Option<String>,
     account_form: AccountForm,
     repo_path_form: RepoPathForm,
     account_delete_confirmation: Option<AccountDeleteConfirmation>,
     review_settings_editor: Option<AccountReviewSettingsEditor>,
+    device_flow: Option<DeviceFlowJob>,
+    power: PowerMonitor,
+    idle: IdleMonitor,
+    deep_links: Option<Receiver<DeepLink>>,
+    pending_deep_link: Option<DeepLink>,
+    events: EventBus,
     repo_path_account_editor: Option<RepoPathAccountEditor>,
+    settings_editor: Option<AppSettingsEditor>,
+    settings: AppSettings,
+    /// Accent the egui visuals were last rebuilt with.
+    applied_accent: Option<[u8; 3]>,
+    /// UI and text scale last applied to the context, in percent.
+    applied_ui_scale: Option<(u16, u16)>,
+    /// Custom font file the installed fonts were built with.
+    applied_font_path: String,
+    /// Rules [`Self::rules`] was compiled from.
+    applied_rules: Vec<NotificationRule>,
+    rules: RuleSet,
+    history: ActivityHistory,
+    /// The summary shown instead of closing on a day with handled threads.
+    exit_summary: bool,
+    /// Set once the user chose to quit from the summary.
+    close_confirmed: bool,
+    tray: Option<Tray>,
+    /// Set by Quit in the tray so the next close exits instead of hiding.
+    quit_requested: bool,
+    reminders: ReminderList,
+    reminder_editor: Option<ReminderEditor>,
+    history_view: Option<HistoryView>,
+    whats_new: Option<WhatsNew>,
+    token_update: Option<TokenUpdateForm>,
+    /// Section picked in the counts bar; the all-accounts view then lists
+    /// only that section for every account.
+    section_focus: Option<SectionKind>,
+    alert_throttle: AlertThrottle,
+    last_compaction: Instant,
+    /// When the newest backup of the local triage state was taken.
+    last_backup: Option<chrono::DateTime<Local>>,
+    sync: SyncRunner,
+    release_radar: ReleaseRadarState,
+    plugin_sections: PluginSectionsState,
+    avatars: Arc<AvatarLoader>,
+    window_title: String,
     accounts: Vec<AccountState>,
     repo_paths: BTreeMap<String, String>,
     repo_path_accounts: BTreeMap<String, String>,
@@ -101,22 +255,77 @@ pub struct ReminderApp {
     selected_repo: Option<String>,
     repo_path_filter_login: Option<String>,
     show_all_accounts: bool,
+    /// The all-accounts view shows one merged table instead of a card per
+    /// account.
+    combined_inbox: bool,
+    combined_chips: NotificationChips,
     secret_store: Option<AccountStore>,
+    unlock_form: Option<UnlockForm>,
+    ui_state: UiState,
     storage_warning: Option<String>,
     global_error: Option<String>,
     auto_refresh: BatchRefreshScheduler,
+    /// Set by `--replay`; the only account then reads from this server.
+    replay: Option<ReplayServer>,
 }
 
 impl ReminderApp {
-    pub fn new(cc: &CreationContext<'_>) -> Self {
-        install_international_fonts(&cc.egui_ctx);
+    pub fn new(
+        cc: &CreationContext<'_>,
+        profile: Option<String>,
+        deep_link: Option<DeepLink>,
+        replay: Option<ReplayServer>,
+    ) -> Self {
+        // No custom font yet; `update` reinstalls once the settings load.
+        let _ = install_international_fonts(&cc.egui_ctx, None);
+        let ui_state = UiState::load(cc.storage);
+        cc.egui_ctx.set_theme(ui_state.theme);
+        // Replays never touch local data, so their avatars stay in memory.
+        let avatar_dir = storage::data_dir(profile.as_deref())
+            .ok()
+            .filter(|_| replay.is_none())
+            .map(|dir| dir.join(AVATARS_DIR));
+        let avatars = AvatarLoader::install(&cc.egui_ctx, avatar_dir);
 
         let mut app = Self {
             account_form: AccountForm::default(),
             repo_path_form: RepoPathForm::default(),
             account_delete_confirmation: None,
             review_settings_editor: None,
+            device_flow: None,
+            power: PowerMonitor::default(),
+            idle: IdleMonitor::default(),
+            deep_links: None,
+            pending_deep_link: None,
+            events: EventBus::default(),
             repo_path_account_editor: None,
+            settings_editor: None,
+            settings: AppSettings::default(),
+            applied_accent: None,
+            applied_ui_scale: None,
+            applied_font_path: String::new(),
+            applied_rules: Vec::new(),
+            rules: RuleSet::default(),
+            history: ActivityHistory::default(),
+            exit_summary: false,
+            close_confirmed: false,
+            tray: Tray::spawn(&cc.egui_ctx),
+            quit_requested: false,
+            reminders: ReminderList::default(),
+            reminder_editor: None,
+            history_view: None,
+            whats_new: None,
+            token_update: None,
+            section_focus: None,
+            alert_throttle: AlertThrottle::default(),
+            last_compaction: Instant::now(),
+            last_backup: None,
+            sync: SyncRunner::default(),
+            release_radar: ReleaseRadarState::default(),
+            plugin_sections: PluginSectionsState::default(),
+            avatars,
+            window_title: window_title(profile.as_deref(), 0),
+            profile,
             accounts: Vec::new(),
             repo_paths: BTreeMap::new(),
             repo_path_accounts: BTreeMap::new(),
@@ -125,510 +334,2019 @@ impl ReminderApp {
             selected_repo: None,
             repo_path_filter_login: None,
             show_all_accounts: true,
+            combined_inbox: false,
+            combined_chips: NotificationChips::default(),
             secret_store: None,
+            unlock_form: None,
+            ui_state,
             storage_warning: None,
             global_error: None,
             auto_refresh: BatchRefreshScheduler::new(Duration::from_secs(
                 AUTO_REFRESH_INTERVAL_SECS,
             )),
+            replay,
         };
 
-        match AccountStore::initialize() {
-            Ok(store) => {
-                match store.hydrate() {
-                    Ok(outcome) => {
-                        for profile in outcome.profiles {
-                            let mut state = AccountState::new(profile);
-                            state.start_refresh();
-                            app.accounts.push(state);
-                        }
-                        let (repo_paths, dropped_repo_paths) =
-                            normalize_hydrated_repo_paths(outcome.repo_paths);
-                        app.repo_paths = repo_paths;
-                        let account_logins: Vec<_> = app
-                            .accounts
-                            .iter()
-                            .map(|account| account.profile.login.clone())
-                            .collect();
-                        let (repo_path_accounts, dropped_repo_path_accounts) =
-                            normalize_hydrated_repo_path_accounts(
-                                outcome.repo_path_accounts,
-                                &app.repo_paths,
-                                &account_logins,
-                            );
-                        app.repo_path_accounts = repo_path_accounts;
-                        if dropped_repo_paths > 0 || dropped_repo_path_accounts > 0 {
-                            app.storage_warning = Some(format!(
-                                "Skipped {} invalid local repo path setting(s) while restoring settings.",
-                                dropped_repo_paths + dropped_repo_path_accounts
-                            ));
-                        }
-                    }
-                    Err(err) => {
-                        app.storage_warning =
-                            Some(format!("Failed to restore saved accounts: {err}"))
-                    }
+        // Replays run on default settings and never read or write local data.
+        if let Some(server) = &app.replay {
+            let client = match github::build_client() {
+                Ok(client) => client,
+                Err(err) => {
+                    app.global_error = Some(format!("Unable to start the replay: {err}"));
+                    return app;
                 }
+            };
+            let mut state = AccountState::new(
+                GitHubAccount {
+                    login: server.login().to_owned(),
+                    token: String::from("replay"),
+                    review_settings: ReviewCommandSettings::default(),
+                    hide_bot_notifications: None,
+                    api_base_url: Some(server.base_url().to_owned()),
+                    working_hours: None,
+                    token_command: None,
+                    group: None,
+                    section_alerts: None,
+                    inbox_backend: InboxBackend::Rest,
+                    forge: Forge::GitHub,
+                },
+                client,
+                app.events.publisher(),
+            );
+            state.start_refresh(&app.settings);
+            app.accounts.push(state);
+            app.ensure_selected_account();
+            app.auto_refresh.mark_triggered();
+            return app;
+        }
+
+        match AccountStore::initialize(app.profile.as_deref()) {
+            Ok(store) => {
+                app.last_backup = store
+                    .backups()
+                    .ok()
+                    .and_then(|backups| backups.first().map(|backup| backup.created_at));
+                let locked = store.is_locked();
                 app.secret_store = Some(store);
+                app.load_local_state();
+                if locked {
+                    app.unlock_form = Some(UnlockForm::default());
+                } else {
+                    app.restore_accounts();
+                }
             }
             Err(err) => {
                 app.storage_warning = Some(format!(
-                    "Local token storage is unavailable; tokens cannot be persisted ({err})."
+                    "Local account storage is unavailable; accounts cannot be persisted ({err})."
                 ));
             }
         }
 
+        if let Ok(dir) = storage::data_dir(app.profile.as_deref()) {
+            app.deep_links = deep_link::listen(&dir).ok();
+            app.plugin_sections = PluginSectionsState::load(&dir.join(plugins::PLUGINS_DIR));
+        }
+        app.pending_deep_link = deep_link;
+
         app.ensure_selected_account();
         app.auto_refresh.mark_triggered();
+        app.whats_new = WhatsNew::since(app.ui_state.last_seen_version.as_deref());
 
         app
     }
 
-    fn add_account(&mut self) {
-        if self.account_form.login.trim().is_empty() || self.account_form.token.trim().is_empty() {
-            self.account_form.form_error =
-                Some("Both the login and a Personal Access Token are required.".to_owned());
+    /// Loads the activity history and reminders saved next to the registry.
+    fn load_local_state(&mut self) {
+        let Some(store) = &self.secret_store else {
             return;
+        };
+        match store.load_history() {
+            Ok(history) => self.history = history,
+            Err(err) => self.storage_warning = Some(format!("Failed to restore history: {err}")),
+        }
+        match store.load_reminders() {
+            Ok(reminders) => self.reminders = reminders,
+            Err(err) => self.storage_warning = Some(format!("Failed to restore reminders: {err}")),
+        }
+        match store.load_sync_state() {
+            Ok(baseline) => self.sync.baseline = baseline,
+            Err(err) => self.storage_warning = Some(format!("Ignoring the sync state: {err}")),
         }
+    }
 
-        if self.accounts.iter().any(|account| {
-            account
-                .profile
-                .login
-                .eq_ignore_ascii_case(self.account_form.login.trim())
-        }) {
-            self.account_form.form_error =
-                Some("This GitHub login is already being tracked.".to_owned());
+    /// Takes a scheduled backup once the newest one is older than the
+    /// configured interval. A failure is reported once per interval.
+    fn run_scheduled_backup(&mut self) {
+        let hours = self.settings.backup_interval_hours;
+        let Some(store) = self
+            .secret_store
+            .as_ref()
+            .filter(|store| !store.is_locked())
+        else {
+            return;
+        };
+        let now = Local::now();
+        if hours == 0
+            || self
+                .last_backup
+                .is_some_and(|at| now - at < chrono::Duration::hours(i64::from(hours)))
+        {
             return;
         }
+        if let Err(err) = store.create_backup() {
+            self.storage_warning = Some(format!("Unable to back up local state: {err}"));
+        }
+        self.last_backup = Some(now);
+    }
 
-        let profile = GitHubAccount {
-            login: self.account_form.login.trim().to_owned(),
-            token: self.account_form.token.trim().to_owned(),
-            review_settings: ReviewCommandSettings::default(),
+    fn create_backup_now(&mut self) {
+        let (Some(store), Some(editor)) = (&self.secret_store, self.settings_editor.as_mut())
+        else {
+            return;
         };
-        let selected_login = profile.login.clone();
-
-        if let Some(store) = &self.secret_store {
-            if let Err(err) = store.persist_profile(&profile) {
-                self.account_form.form_error =
-                    Some(format!("Unable to persist credentials locally: {err}"));
-                return;
+        match store.create_backup().and_then(|_| store.backups()) {
+            Ok(backups) => {
+                self.last_backup = backups.first().map(|backup| backup.created_at);
+                editor.backups = backups;
+                editor.form_error = None;
             }
-        } else {
-            self.account_form.form_error = Some(
-                "Local token storage is not available; cannot add new accounts right now."
-                    .to_owned(),
-            );
-            return;
+            Err(err) => editor.form_error = Some(format!("Unable to back up local state: {err}")),
         }
-
-        let mut state = AccountState::new(profile);
-        state.start_refresh();
-        self.auto_refresh.mark_triggered();
-        self.accounts.push(state);
-        self.selected_account_login = Some(selected_login);
-        self.account_form = AccountForm::default();
     }
 
-    fn remove_account_at(&mut self, idx: usize) {
-        if idx >= self.accounts.len() {
+    /// Swaps in the triage state and settings of backup `name` and reloads
+    /// every account with them.
+    fn restore_backup(&mut self, name: &str) {
+        let (Some(store), Some(editor)) = (&self.secret_store, self.settings_editor.as_mut())
+        else {
             return;
+        };
+        if let Err(err) = store.restore_backup(name) {
+            editor.form_error = Some(format!("Unable to restore the backup: {err}"));
+            return;
+        }
+        self.settings_editor = None;
+        self.last_backup = Some(Local::now());
+        for account in &mut self.accounts {
+            account.cancel_all_jobs();
         }
+        self.accounts.clear();
+        self.load_local_state();
+        self.restore_accounts();
+        self.ensure_selected_account();
+    }
 
-        let login = self.accounts[idx].profile.login.clone();
-        let linked_repos: Vec<_> = self
-            .repo_path_accounts
-            .iter()
-            .filter(|(_, linked_login)| *linked_login == &login)
-            .map(|(repo, _)| repo.clone())
-            .collect();
-        if let Some(store) = &self.secret_store
-            && let Err(err) = store.forget(&login)
+    fn triage_settings(&self) -> TriageSettings {
+        TriageSettings {
+            rules: self.settings.rules.clone(),
+            repo_tiers: self.settings.repo_tiers.clone(),
+            auto_snooze_hours: self.settings.auto_snooze_hours.clone(),
+        }
+    }
+
+    /// Starts a sync when one is configured and due.
+    fn run_sync(&mut self) {
+        let config = &self.settings.sync;
+        if config.backend == SyncBackend::Off
+            || !self.sync.is_due()
+            || self
+                .secret_store
+                .as_ref()
+                .is_none_or(AccountStore::is_locked)
         {
-            self.global_error = Some(format!("Failed to remove credentials for {login}: {err}"));
             return;
         }
-        if let Some(store) = &self.secret_store {
-            for repo in &linked_repos {
-                if let Err(err) = store.clear_repo_path_account(repo) {
-                    self.global_error = Some(format!(
-                        "Failed to clear repo path account for {repo}: {err}"
-                    ));
+        let remote = match config.backend {
+            SyncBackend::Off => return,
+            SyncBackend::Gist => {
+                let Some(account) = self.accounts.iter().find(|account| {
+                    account.profile.forge == Forge::GitHub
+                        && account.profile.login.eq_ignore_ascii_case(&config.account)
+                }) else {
+                    self.sync
+                        .skip("Pick the GitHub account whose token owns the sync gist.");
                     return;
+                };
+                SyncRemote::Gist {
+                    client: account.client.clone(),
+                    profile: Box::new(account.profile.clone()),
+                    gist_id: Some(config.gist_id.trim().to_owned())
+                        .filter(|gist_id| !gist_id.is_empty()),
                 }
             }
-        }
+            SyncBackend::GitRepo => {
+                let path = config.repo_path.trim();
+                if path.is_empty() {
+                    self.sync
+                        .skip("Enter the path of a local clone to sync through.");
+                    return;
+                }
+                SyncRemote::GitRepo(PathBuf::from(path))
+            }
+        };
+        let now = Utc::now();
+        let local = SyncDocument::local(
+            &self.sync.baseline,
+            now,
+            self.triage_settings(),
+            self.accounts.iter().map(|account| {
+                (
+                    account.profile.login.as_str(),
+                    &account.done_threads.entries,
+                    &account.chips,
+                )
+            }),
+        );
+        self.sync
+            .start(remote, local, now - state::done_tombstone_max_age());
+    }
 
-        if self
-            .review_settings_editor
-            .as_ref()
-            .is_some_and(|editor| editor.login == login)
-        {
-            self.review_settings_editor = None;
-        }
-        if self
-            .account_delete_confirmation
-            .as_ref()
-            .is_some_and(|confirmation| confirmation.login == login)
-        {
-            self.account_delete_confirmation = None;
+    /// Applies what another machine shared and remembers the merged
+    /// document for the next round.
+    fn poll_sync(&mut self) {
+        let Some(outcome) = self.sync.poll() else {
+            return;
+        };
+        let document = &outcome.document;
+        for account in &mut self.accounts {
+            if let Some(done) = document.done.get(&account.profile.login) {
+                for (thread_id, at) in done {
+                    if account
+                        .done_threads
+                        .entries
+                        .get(thread_id)
+                        .is_none_or(|local| local < at)
+                    {
+                        account.done_threads.mark(thread_id.clone(), *at);
+                        account.done_threads_dirty = true;
+                    }
+                }
+            }
+            if let Some(chips) = document.chips.get(&account.profile.login) {
+                account.chips = chips.value.clone();
+            }
         }
-        if self
-            .repo_path_account_editor
-            .as_ref()
-            .is_some_and(|editor| editor.repo_login == login)
-        {
-            self.repo_path_account_editor = None;
+        let mut settings = self.settings.clone();
+        let shared = &document.settings.value;
+        settings.rules = shared.rules.clone();
+        settings.repo_tiers = shared.repo_tiers.clone();
+        settings.auto_snooze_hours = shared.auto_snooze_hours.clone();
+        if let Some(gist_id) = outcome.gist_id {
+            settings.sync.gist_id = gist_id;
         }
-
-        self.accounts.remove(idx);
-        for repo in linked_repos {
-            self.repo_path_accounts.remove(&repo);
+        let Some(store) = &self.secret_store else {
+            return;
+        };
+        if settings != self.settings {
+            match store.persist_settings(&settings) {
+                Ok(()) => self.settings = settings,
+                Err(err) => {
+                    self.storage_warning = Some(format!("Unable to save synced preferences: {err}"))
+                }
+            }
         }
-        if self.repo_path_filter_login.as_deref() == Some(login.as_str()) {
-            self.repo_path_filter_login = None;
+        if let Err(err) = store.persist_sync_state(document) {
+            self.storage_warning = Some(format!("Unable to save the sync state: {err}"));
         }
-        self.ensure_selected_account();
-    }
-
-    fn open_account_delete_confirmation(&mut self, login: &str) {
-        self.account_delete_confirmation = Some(AccountDeleteConfirmation {
-            login: login.to_owned(),
-            typed_login: String::new(),
-        });
     }
 
-    fn open_repo_path_account_editor(&mut self, repo: &str) {
-        if !self.repo_paths.contains_key(repo) {
-            self.global_error = Some(format!("Cannot find repo path for {repo}."));
+    fn restore_accounts(&mut self) {
+        let Some(store) = &mut self.secret_store else {
             return;
+        };
+        match store.hydrate() {
+            Ok(outcome) => {
+                self.settings = outcome.settings;
+                if let Err(err) = github::configure_network(&self.settings.network) {
+                    self.global_error = Some(format!("Ignoring the network preferences: {err}"));
+                }
+                if let Some(warning) = outcome.secret_warning.or(outcome.provisioning_warning) {
+                    self.storage_warning = Some(warning);
+                }
+                for profile in outcome.profiles {
+                    let client = match github::build_client() {
+                        Ok(client) => client,
+                        Err(err) => {
+                            self.global_error =
+                                Some(format!("Unable to load {}: {err}", profile.login));
+                            continue;
+                        }
+                    };
+                    let key = profile.storage_key();
+                    let mut state = AccountState::new(profile, client, self.events.publisher());
+                    match store.load_inbox_cache(&key) {
+                        Ok(mut inbox) => {
+                            if let Some(inbox) = &mut inbox {
+                                inbox.tag_provider(state.profile.forge);
+                            }
+                            state.inbox_is_cached = inbox.is_some();
+                            state.inbox = inbox;
+                        }
+                        Err(err) => {
+                            self.storage_warning = Some(format!(
+                                "Ignoring the offline inbox of {}: {err}",
+                                state.profile.login
+                            ));
+                        }
+                    }
+                    match store.load_done_threads(&key) {
+                        Ok(done) => state.done_threads = done,
+                        Err(err) => {
+                            self.storage_warning = Some(format!(
+                                "Ignoring the threads {} marked done: {err}",
+                                state.profile.login
+                            ));
+                        }
+                    }
+                    match store.load_thread_stages(&key) {
+                        Ok(stages) => state.thread_stages = stages,
+                        Err(err) => {
+                            self.storage_warning = Some(format!(
+                                "Ignoring the thread stages of {}: {err}",
+                                state.profile.login
+                            ));
+                        }
+                    }
+                    match store.load_archive(&key) {
+                        Ok(archive) => state.archive = archive,
+                        Err(err) => {
+                            self.storage_warning = Some(format!(
+                                "Ignoring the notification history of {}: {err}",
+                                state.profile.login
+                            ));
+                        }
+                    }
+                    match store.load_enrichment_cache(&key) {
+                        Ok(cache) => state.enrichment_cache = cache,
+                        Err(err) => {
+                            self.storage_warning = Some(format!(
+                                "Ignoring the cached pull request details of {}: {err}",
+                                state.profile.login
+                            ));
+                        }
+                    }
+                    state.start_refresh(&self.settings);
+                    self.accounts.push(state);
+                }
+                let (repo_paths, dropped_repo_paths) =
+                    normalize_hydrated_repo_paths(outcome.repo_paths);
+                self.repo_paths = repo_paths;
+                let account_logins: Vec<_> = self
+                    .accounts
+                    .iter()
+                    .map(|account| account.profile.login.clone())
+                    .collect();
+                let (repo_path_accounts, dropped_repo_path_accounts) =
+                    normalize_hydrated_repo_path_accounts(
+                        outcome.repo_path_accounts,
+                        &self.repo_paths,
+                        &account_logins,
+                    );
+                self.repo_path_accounts = repo_path_accounts;
+                if dropped_repo_paths > 0 || dropped_repo_path_accounts > 0 {
+                    self.storage_warning = Some(format!(
+                        "Skipped {} invalid local repo path setting(s) while restoring settings.",
+                        dropped_repo_paths + dropped_repo_path_accounts
+                    ));
+                }
+                self.apply_ui_state();
+            }
+            Err(err) => {
+                self.storage_warning = Some(format!("Failed to restore saved accounts: {err}"))
+            }
         }
+    }
 
-        self.repo_path_account_editor = Some(RepoPathAccountEditor {
-            repo: repo.to_owned(),
-            repo_login: self
-                .repo_path_accounts
-                .get(repo)
-                .cloned()
-                .unwrap_or_default(),
-            form_error: None,
-        });
+    /// Restores the saved dashboard layout onto freshly loaded accounts.
+    fn apply_ui_state(&mut self) {
+        for account in &mut self.accounts {
+            let Some(saved) = self.ui_state.accounts.get(&account.profile.login) else {
+                continue;
+            };
+            account.expanded = saved.expanded;
+            account.view_mode = if saved.board_view {
+                AccountViewMode::Board
+            } else if saved.grouped_view {
+                AccountViewMode::Grouped
+            } else {
+                AccountViewMode::Inbox
+            };
+            account.search_query = saved.search_query.clone();
+            account.search_mode = saved.search_mode;
+            account.chips = saved.chips.clone();
+            account.group_by_repo = saved.group_by_repo;
+            account.group_by_day = saved.group_by_day;
+            account.collapsed_repos = saved.collapsed_repos.clone();
+        }
+        for (repo, query) in &self.ui_state.repo_search_queries {
+            if self.repo_paths.contains_key(repo) {
+                self.repo_views
+                    .entry(repo.clone())
+                    .or_insert_with(|| RepoState::new(repo.clone()))
+                    .search_query = query.clone();
+            }
+        }
+        for (repo, mode) in &self.ui_state.repo_search_modes {
+            if self.repo_paths.contains_key(repo) {
+                self.repo_views
+                    .entry(repo.clone())
+                    .or_insert_with(|| RepoState::new(repo.clone()))
+                    .search_mode = *mode;
+            }
+        }
+        self.selected_account_login = self.ui_state.selected_account.clone();
+        self.show_all_accounts = self.ui_state.show_all_accounts;
+        self.combined_inbox = self.ui_state.combined_inbox;
+        self.combined_chips = self.ui_state.combined_chips.clone();
     }
 
-    fn assign_repo_path_account(&mut self, repo: &str, login: &str) {
-        if !self.repo_paths.contains_key(repo) {
-            self.global_error = Some(format!("Cannot find repo path for {repo}."));
+    /// Snapshot of the dashboard layout for [`App::save`]. Accounts are left
+    /// as saved while the store is locked, since none are loaded yet.
+    fn capture_ui_state(&mut self) {
+        if self.unlock_form.is_some() {
             return;
         }
-        let Some(account_login) = self
+        self.ui_state.accounts = self
             .accounts
             .iter()
-            .find(|account| account.profile.login == login)
-            .map(|account| account.profile.login.clone())
+            .map(|account| {
+                let state = AccountUiState {
+                    expanded: account.expanded,
+                    grouped_view: account.view_mode == AccountViewMode::Grouped,
+                    board_view: account.view_mode == AccountViewMode::Board,
+                    search_query: account.search_query.clone(),
+                    search_mode: account.search_mode,
+                    chips: account.chips.clone(),
+                    group_by_repo: account.group_by_repo,
+                    group_by_day: account.group_by_day,
+                    collapsed_repos: account.collapsed_repos.clone(),
+                };
+                (account.profile.login.clone(), state)
+            })
+            .collect();
+        self.ui_state.repo_search_queries = self
+            .repo_views
+            .iter()
+            .filter(|(_, view)| !view.search_query.is_empty())
+            .map(|(repo, view)| (repo.clone(), view.search_query.clone()))
+            .collect();
+        self.ui_state.repo_search_modes = self
+            .repo_views
+            .iter()
+            .filter(|(_, view)| view.search_mode != SearchMode::Substring)
+            .map(|(repo, view)| (repo.clone(), view.search_mode))
+            .collect();
+        self.ui_state.selected_account = self.selected_account_login.clone();
+        self.ui_state.show_all_accounts = self.show_all_accounts;
+        self.ui_state.combined_inbox = self.combined_inbox;
+        self.ui_state.combined_chips = self.combined_chips.clone();
+    }
+
+    fn unlock_store(&mut self) {
+        let (Some(store), Some(form)) = (self.secret_store.as_mut(), self.unlock_form.as_mut())
         else {
-            if let Some(editor) = &mut self.repo_path_account_editor {
-                editor.form_error = Some(format!("Cannot find tracked account {login}."));
-            }
             return;
         };
-
-        if let Some(store) = &self.secret_store {
-            if let Err(err) = store.persist_repo_path_account(repo, &account_login) {
-                if let Some(editor) = &mut self.repo_path_account_editor {
-                    editor.form_error = Some(format!("Unable to update repo path account: {err}"));
-                }
-                return;
+        match store.unlock(&form.passphrase) {
+            Ok(()) => {
+                self.unlock_form = None;
+                self.restore_accounts();
+                self.ensure_selected_account();
+            }
+            Err(err) => {
+                form.passphrase.clear();
+                form.error = Some(err.to_string());
             }
-        } else if let Some(editor) = &mut self.repo_path_account_editor {
-            editor.form_error = Some(
-                "Local storage is not available; cannot update repo path accounts right now."
-                    .to_owned(),
-            );
-            return;
-        }
-
-        self.repo_path_accounts
-            .insert(repo.to_owned(), account_login.clone());
-        self.repo_path_account_editor = None;
-
-        if self.selected_repo.as_deref() == Some(repo) {
-            self.selected_account_login = Some(account_login.clone());
-            let repo = repo.to_owned();
-            self.select_repo(repo);
         }
     }
 
-    fn confirm_account_delete_matches(expected_login: &str, typed_login: &str) -> bool {
-        typed_login.trim() == expected_login
-    }
-
-    fn remove_account_by_login(&mut self, login: &str) {
-        if let Some(idx) = self
-            .accounts
-            .iter()
-            .position(|account| account.profile.login == login)
-        {
-            self.remove_account_at(idx);
-        } else {
-            self.account_delete_confirmation = None;
-            self.global_error = Some(format!("Cannot find tracked account {login} to remove."));
+    fn render_unlock_window(&mut self, ctx: &Context) {
+        let Some(form) = self.unlock_form.as_mut() else {
+            return;
+        };
+        let mut unlock_requested = false;
+        egui::Window::new("Unlock accounts")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label(
+                    "Saved tokens are encrypted. Enter your passphrase to load your accounts.",
+                );
+                ui.add_space(8.0);
+                let response = ui.add(
+                    egui::TextEdit::singleline(&mut form.passphrase)
+                        .password(true)
+                        .hint_text("Passphrase"),
+                );
+                if response.lost_focus() && ui.input(|input| input.key_pressed(egui::Key::Enter)) {
+                    unlock_requested = true;
+                }
+                if let Some(error) = &form.error {
+                    ui.colored_label(ui.visuals().error_fg_color, error);
+                }
+                ui.add_space(8.0);
+                if ui.button("Unlock").clicked() {
+                    unlock_requested = true;
+                }
+            });
+        if unlock_requested {
+            self.unlock_store();
         }
     }
 
-    fn save_repo_path(&mut self) {
-        let Some(repo) = canonical_repo_key(&self.repo_path_form.repo) else {
-            self.repo_path_form.form_error =
-                Some("Repository must be in the form owner/repo.".to_owned());
+    fn enable_token_encryption(&mut self) {
+        let profiles: Vec<_> = self
+            .accounts
+            .iter()
+            .map(|account| account.profile.clone())
+            .collect();
+        let (Some(store), Some(editor)) =
+            (self.secret_store.as_mut(), self.settings_editor.as_mut())
+        else {
             return;
         };
-
-        let raw_path = self.repo_path_form.path.trim();
-        if raw_path.is_empty() {
-            self.repo_path_form.form_error = Some("A local checkout path is required.".to_owned());
+        if editor.passphrase.chars().count() < MIN_PASSPHRASE_LEN {
+            editor.form_error = Some(format!(
+                "Use a passphrase of at least {MIN_PASSPHRASE_LEN} characters."
+            ));
             return;
         }
-
-        let canonical_path = match fs::canonicalize(raw_path) {
-            Ok(path) => path,
+        if editor.passphrase != editor.passphrase_confirmation {
+            editor.form_error = Some("The passphrases do not match.".to_owned());
+            return;
+        }
+        match store.enable_encryption(&editor.passphrase, &profiles) {
+            Ok(()) => {
+                editor.passphrase.clear();
+                editor.passphrase_confirmation.clear();
+                editor.form_error = None;
+            }
             Err(err) => {
-                self.repo_path_form.form_error =
-                    Some(format!("Unable to resolve checkout path: {err}"));
-                return;
+                editor.form_error = Some(format!("Unable to encrypt tokens: {err}"));
             }
-        };
-
-        if !canonical_path.is_dir() {
-            self.repo_path_form.form_error =
-                Some("Local checkout path must point to a directory.".to_owned());
-            return;
         }
+    }
 
-        let canonical_path = canonical_path.display().to_string();
-        let linked_login = self.selected_account_login.clone().or_else(|| {
-            self.accounts
-                .first()
-                .map(|account| account.profile.login.clone())
-        });
-        if linked_login.is_none() {
-            self.repo_path_form.form_error =
-                Some("Add at least one tracked account before saving a repo path.".to_owned());
+    fn add_account(&mut self) {
+        if self.account_form.login.trim().is_empty() || self.account_form.token.trim().is_empty() {
+            self.account_form.form_error =
+                Some("Both the login and a Personal Access Token are required.".to_owned());
             return;
         }
 
-        if let Some(store) = &self.secret_store {
-            if let Err(err) =
-                store.persist_repo_path(&repo, &canonical_path, linked_login.as_deref())
-            {
-                self.repo_path_form.form_error =
-                    Some(format!("Unable to persist local repo path: {err}"));
-                return;
-            }
-        } else {
-            self.repo_path_form.form_error = Some(
-                "Local storage is not available; cannot save repo paths right now.".to_owned(),
-            );
+        let profile = GitHubAccount {
+            login: self.account_form.login.trim().to_owned(),
+            token: self.account_form.token.trim().to_owned(),
+            review_settings: ReviewCommandSettings::default(),
+            hide_bot_notifications: None,
+            api_base_url: normalize_api_base_url(
+                self.account_form.forge,
+                &self.account_form.api_host,
+            ),
+            working_hours: None,
+            token_command: None,
+            group: None,
+            section_alerts: None,
+            inbox_backend: InboxBackend::Rest,
+            forge: self.account_form.forge,
+        };
+        if self.is_tracked(&profile) {
+            self.offer_token_update(profile);
             return;
         }
-
-        self.repo_paths.insert(repo.clone(), canonical_path);
-        if let Some(login) = linked_login {
-            self.repo_path_accounts.insert(repo, login);
-        }
-        self.repo_path_form = RepoPathForm::default();
+        self.account_form.form_error = None;
+        self.account_form.duplicate = None;
+        self.account_form.pending_check = Some(PendingTokenCheck::spawn(profile));
     }
 
-    fn open_review_settings_editor(&mut self, login: &str) {
-        let Some(account) = self
+    /// A second card for the same account would poll twice, so adding a
+    /// tracked one offers to replace the existing account's token instead.
+    fn offer_token_update(&mut self, profile: GitHubAccount) {
+        let Some(existing) = self
             .accounts
             .iter()
-            .find(|account| account.profile.login == login)
+            .find(|account| account.profile.same_identity(&profile))
         else {
-            self.global_error = Some(format!("Cannot find account settings for {login}."));
             return;
         };
-
-        self.review_settings_editor = Some(AccountReviewSettingsEditor {
-            login: account.profile.login.clone(),
-            env_vars_text: format_review_env_vars(&account.profile.review_settings),
-            additional_args_text: format_review_additional_args(&account.profile.review_settings),
-            review_prompt_md_path_text: account
-                .profile
-                .review_settings
-                .review_prompt_md_path
-                .clone()
-                .unwrap_or_default(),
-            pr_description_md_path_text: account
-                .profile
-                .review_settings
-                .pr_description_md_path
-                .clone()
-                .unwrap_or_default(),
-            form_error: None,
-        });
+        self.account_form.form_error = Some(format!(
+            "{} is already being tracked.",
+            existing.profile.login
+        ));
+        self.account_form.duplicate = Some((existing.profile.storage_key(), profile.token));
     }
 
-    fn save_review_settings(&mut self) {
-        let Some(editor) = self.review_settings_editor.as_ref() else {
+    fn accept_token_update_offer(&mut self) {
+        let Some((key, token)) = self.account_form.duplicate.take() else {
             return;
         };
-
-        let env_vars = match parse_review_env_vars(&editor.env_vars_text) {
-            Ok(env_vars) => env_vars,
-            Err(err) => {
-                if let Some(editor) = &mut self.review_settings_editor {
-                    editor.form_error = Some(err);
-                }
-                return;
-            }
-        };
-
-        let additional_args = parse_review_additional_args(&editor.additional_args_text);
-        let login = editor.login.clone();
-        let review_settings = ReviewCommandSettings {
-            env_vars,
-            additional_args,
-            review_prompt_md_path: normalize_optional_path(&editor.review_prompt_md_path_text),
-            pr_description_md_path: normalize_optional_path(&editor.pr_description_md_path_text),
-        };
-
-        let Some(account_idx) = self
+        let Some(account) = self
             .accounts
             .iter()
-            .position(|account| account.profile.login == login)
+            .find(|account| account.profile.storage_key() == key)
         else {
-            self.review_settings_editor = None;
-            self.global_error = Some(format!("Cannot find account settings for {login}."));
             return;
         };
+        let mut profile = account.profile.clone();
+        profile.token = token.clone();
+        profile.token_command = None;
+        let mut form = TokenUpdateForm::new(&profile);
+        form.token = token;
+        form.pending_check = Some(PendingTokenCheck::spawn(profile));
+        self.token_update = Some(form);
+        self.account_form = AccountForm::default();
+    }
 
-        let mut profile = self.accounts[account_idx].profile.clone();
-        profile.review_settings = review_settings.clone();
-
-        if let Some(store) = &self.secret_store {
-            if let Err(err) = store.persist_profile(&profile) {
-                if let Some(editor) = &mut self.review_settings_editor {
-                    editor.form_error = Some(format!("Unable to save review settings: {err}"));
-                }
-                return;
-            }
-        } else if let Some(editor) = &mut self.review_settings_editor {
-            editor.form_error = Some(
-                "Local storage is not available; cannot save review settings right now.".to_owned(),
-            );
+    fn poll_token_check(&mut self) {
+        let Some(outcome) = self
+            .account_form
+            .pending_check
+            .as_ref()
+            .and_then(PendingTokenCheck::try_take)
+        else {
             return;
+        };
+        self.account_form.pending_check = None;
+        match outcome
+            .map_err(|err| err.to_string())
+            .and_then(|profile| self.track_account(profile))
+        {
+            Ok(()) => self.account_form = AccountForm::default(),
+            Err(err) => self.account_form.form_error = Some(err),
         }
+    }
 
-        self.accounts[account_idx].profile.review_settings = review_settings;
-        self.review_settings_editor = None;
+    fn open_token_update_requests(&mut self) {
+        for account in &mut self.accounts {
+            if std::mem::take(&mut account.token_update_requested) {
+                self.token_update = Some(TokenUpdateForm::new(&account.profile));
+            }
+        }
     }
 
-    fn render_review_settings_window(&mut self, ctx: &Context) {
-        let Some(editor) = self.review_settings_editor.as_mut() else {
+    fn render_token_update_window(&mut self, ctx: &Context) {
+        self.open_token_update_requests();
+        let Some(form) = self.token_update.as_mut() else {
             return;
         };
 
         let mut open = true;
         let mut save_requested = false;
         let mut cancel_requested = false;
-        let title = format!("Review settings: {}", editor.login);
-        egui::Window::new(title)
+        let checking = form.pending_check.is_some();
+        egui::Window::new(format!("Update token · {}", form.login))
+            .id(egui::Id::new("token-update"))
             .open(&mut open)
             .collapsible(false)
-            .resizable(true)
-            .default_size(egui::vec2(520.0, 360.0))
+            .resizable(false)
             .show(ctx, |ui| {
-                ui.label("Environment variables (one KEY=VALUE per line)");
-                ui.add(
-                    egui::TextEdit::multiline(&mut editor.env_vars_text)
-                        .desired_rows(8)
-                        .desired_width(f32::INFINITY),
-                );
-                ui.add_space(8.0);
-                ui.label("Additional args (split on whitespace)");
-                ui.add(
-                    egui::TextEdit::multiline(&mut editor.additional_args_text)
-                        .desired_rows(3)
-                        .desired_width(f32::INFINITY)
-                        .hint_text("--lang korean"),
-                );
-                ui.add_space(8.0);
-                ui.label("Review prompt md path");
-                ui.add(
-                    egui::TextEdit::singleline(&mut editor.review_prompt_md_path_text)
-                        .desired_width(f32::INFINITY)
-                        .hint_text(default_review_prompt_md_path_display()),
-                );
-                ui.add_space(8.0);
-                ui.label("PR Description md path");
+                ui.label("New personal access token");
                 ui.add(
-                    egui::TextEdit::singleline(&mut editor.pr_description_md_path_text)
-                        .desired_width(f32::INFINITY)
-                        .hint_text(default_pr_description_prompt_md_path_display()),
+                    egui::TextEdit::singleline(&mut form.token)
+                        .password(true)
+                        .hint_text("ghp_..."),
                 );
-
-                if let Some(error) = &editor.form_error {
-                    ui.add_space(8.0);
+                if let Some(error) = &form.form_error {
                     ui.colored_label(ui.visuals().error_fg_color, error);
                 }
-
-                ui.add_space(12.0);
+                ui.add_space(8.0);
                 ui.horizontal(|row| {
-                    if row.button("Save").clicked() {
+                    if row
+                        .add_enabled(
+                            !checking && !form.token.trim().is_empty(),
+                            egui::Button::new("Save"),
+                        )
+                        .clicked()
+                    {
                         save_requested = true;
                     }
                     if row.button("Cancel").clicked() {
                         cancel_requested = true;
                     }
+                    if checking {
+                        row.spinner();
+                        row.label("Checking token...");
+                    }
                 });
             });
 
-        if save_requested {
-            self.save_review_settings();
-        } else if cancel_requested || !open {
-            self.review_settings_editor = None;
+        if cancel_requested || !open {
+            self.token_update = None;
+            return;
+        }
+        if save_requested
+            && let Some(account) = self
+                .accounts
+                .iter()
+                .find(|account| account.profile.storage_key() == form.key)
+        {
+            let mut profile = account.profile.clone();
+            profile.token = form.token.trim().to_owned();
+            profile.token_command = None;
+            form.form_error = None;
+            form.pending_check = Some(PendingTokenCheck::spawn(profile));
         }
     }
 
-    fn render_account_delete_confirmation_window(&mut self, ctx: &Context) {
-        let Some(confirmation) = self.account_delete_confirmation.as_mut() else {
+    fn poll_token_update(&mut self) {
+        let Some(outcome) = self
+            .token_update
+            .as_ref()
+            .and_then(|form| form.pending_check.as_ref())
+            .and_then(PendingTokenCheck::try_take)
+        else {
             return;
         };
-
-        let mut open = true;
-        let mut delete_requested = false;
-        let mut cancel_requested = false;
-        let typed_matches =
-            Self::confirm_account_delete_matches(&confirmation.login, &confirmation.typed_login);
-        let title = format!("Delete account: {}", confirmation.login);
-        egui::Window::new(title)
-            .open(&mut open)
-            .collapsible(false)
-            .resizable(false)
-            .default_size(egui::vec2(420.0, 180.0))
-            .show(ctx, |ui| {
-                ui.label("Type the exact account name to confirm deletion.");
-                ui.monospace(&confirmation.login);
-                ui.add_space(8.0);
-                ui.label("Account name");
-                ui.add(
-                    egui::TextEdit::singleline(&mut confirmation.typed_login)
-                        .desired_width(f32::INFINITY),
-                );
-                if !typed_matches && !confirmation.typed_login.is_empty() {
-                    ui.add_space(8.0);
-                    ui.colored_label(
-                        ui.visuals().warn_fg_color,
-                        "Typed account name does not match.",
-                    );
+        let Some(form) = self.token_update.as_mut() else {
+            return;
+        };
+        form.pending_check = None;
+        let result = outcome.map_err(|err| err.to_string()).and_then(|profile| {
+            let Some(store) = &self.secret_store else {
+                return Err("Local token storage is not available right now.".to_owned());
+            };
+            store
+                .persist_profile(&profile)
+                .map_err(|err| format!("Unable to persist credentials locally: {err}"))?;
+            Ok(profile)
+        });
+        match result {
+            Ok(profile) => {
+                self.token_update = None;
+                if let Some(account) = self
+                    .accounts
+                    .iter_mut()
+                    .find(|account| account.profile.same_identity(&profile))
+                {
+                    account.profile = profile;
+                    account.last_error = None;
+                    account.viewer.recheck();
+                    account.start_refresh(&self.settings);
                 }
+            }
+            Err(err) => form.form_error = Some(err),
+        }
+    }
+
+    fn is_tracked(&self, profile: &GitHubAccount) -> bool {
+        self.accounts
+            .iter()
+            .any(|account| account.profile.same_identity(profile))
+    }
+
+    fn track_account(&mut self, profile: GitHubAccount) -> Result<(), String> {
+        if self.is_tracked(&profile) {
+            return Err("This account is already being tracked.".to_owned());
+        }
+
+        let selected_login = profile.login.clone();
+        let client = github::build_client().map_err(|err| err.to_string())?;
+        let Some(store) = &self.secret_store else {
+            return Err(
+                "Local token storage is not available; cannot add new accounts right now."
+                    .to_owned(),
+            );
+        };
+        store
+            .persist_profile(&profile)
+            .map_err(|err| format!("Unable to persist credentials locally: {err}"))?;
+
+        let mut state = AccountState::new(profile, client, self.events.publisher());
+        state.start_refresh(&self.settings);
+        self.auto_refresh.mark_triggered();
+        self.accounts.push(state);
+        self.selected_account_login = Some(selected_login);
+        Ok(())
+    }
+
+    fn start_device_flow(&mut self) {
+        let Some(client_id) = oauth_client_id() else {
+            self.account_form.form_error =
+                Some("No OAuth client id is configured for GitHub sign-in.".to_owned());
+            return;
+        };
+        self.account_form.form_error = None;
+        self.device_flow = Some(DeviceFlowJob::spawn(
+            client_id,
+            normalize_api_base_url(Forge::GitHub, &self.account_form.api_host),
+        ));
+    }
+
+    fn poll_device_flow(&mut self) {
+        let Some(outcome) = self.device_flow.as_mut().and_then(DeviceFlowJob::poll) else {
+            return;
+        };
+        self.device_flow = None;
+        if let Ok(profile) = &outcome
+            && self.is_tracked(profile)
+        {
+            self.offer_token_update(profile.clone());
+            return;
+        }
+        match outcome
+            .map_err(|err| err.to_string())
+            .and_then(|profile| self.track_account(profile))
+        {
+            Ok(()) => self.account_form = AccountForm::default(),
+            Err(err) => self.account_form.form_error = Some(err),
+        }
+    }
+
+    fn remove_account_at(&mut self, idx: usize) {
+        if idx >= self.accounts.len() {
+            return;
+        }
+
+        let login = self.accounts[idx].profile.login.clone();
+        let linked_repos: Vec<_> = self
+            .repo_path_accounts
+            .iter()
+            .filter(|(_, linked_login)| *linked_login == &login)
+            .map(|(repo, _)| repo.clone())
+            .collect();
+        if let Some(store) = &self.secret_store
+            && let Err(err) = store.forget(&self.accounts[idx].profile)
+        {
+            self.global_error = Some(format!("Failed to remove credentials for {login}: {err}"));
+            return;
+        }
+        if let Some(store) = &self.secret_store {
+            for repo in &linked_repos {
+                if let Err(err) = store.clear_repo_path_account(repo) {
+                    self.global_error = Some(format!(
+                        "Failed to clear repo path account for {repo}: {err}"
+                    ));
+                    return;
+                }
+            }
+        }
+
+        if self
+            .review_settings_editor
+            .as_ref()
+            .is_some_and(|editor| editor.login == login)
+        {
+            self.review_settings_editor = None;
+        }
+        if self
+            .account_delete_confirmation
+            .as_ref()
+            .is_some_and(|confirmation| confirmation.login == login)
+        {
+            self.account_delete_confirmation = None;
+        }
+        if self
+            .repo_path_account_editor
+            .as_ref()
+            .is_some_and(|editor| editor.repo_login == login)
+        {
+            self.repo_path_account_editor = None;
+        }
+
+        let mut removed = self.accounts.remove(idx);
+        removed.cancel_all_jobs();
+        for repo_view in self.repo_views.values_mut() {
+            repo_view.cancel_jobs_for(&login);
+        }
+        for repo in linked_repos {
+            self.repo_path_accounts.remove(&repo);
+        }
+        if self.repo_path_filter_login.as_deref() == Some(login.as_str()) {
+            self.repo_path_filter_login = None;
+        }
+        self.ensure_selected_account();
+    }
+
+    fn open_account_delete_confirmation(&mut self, login: &str) {
+        self.account_delete_confirmation = Some(AccountDeleteConfirmation {
+            login: login.to_owned(),
+            typed_login: String::new(),
+        });
+    }
+
+    fn open_repo_path_account_editor(&mut self, repo: &str) {
+        if !self.repo_paths.contains_key(repo) {
+            self.global_error = Some(format!("Cannot find repo path for {repo}."));
+            return;
+        }
+
+        self.repo_path_account_editor = Some(RepoPathAccountEditor {
+            repo: repo.to_owned(),
+            repo_login: self
+                .repo_path_accounts
+                .get(repo)
+                .cloned()
+                .unwrap_or_default(),
+            form_error: None,
+        });
+    }
+
+    fn assign_repo_path_account(&mut self, repo: &str, login: &str) {
+        if !self.repo_paths.contains_key(repo) {
+            self.global_error = Some(format!("Cannot find repo path for {repo}."));
+            return;
+        }
+        let Some(account_login) = self
+            .accounts
+            .iter()
+            .find(|account| account.profile.login == login)
+            .map(|account| account.profile.login.clone())
+        else {
+            if let Some(editor) = &mut self.repo_path_account_editor {
+                editor.form_error = Some(format!("Cannot find tracked account {login}."));
+            }
+            return;
+        };
+
+        if let Some(store) = &self.secret_store {
+            if let Err(err) = store.persist_repo_path_account(repo, &account_login) {
+                if let Some(editor) = &mut self.repo_path_account_editor {
+                    editor.form_error = Some(format!("Unable to update repo path account: {err}"));
+                }
+                return;
+            }
+        } else if let Some(editor) = &mut self.repo_path_account_editor {
+            editor.form_error = Some(
+                "Local storage is not available; cannot update repo path accounts right now."
+                    .to_owned(),
+            );
+            return;
+        }
+
+        self.repo_path_accounts
+            .insert(repo.to_owned(), account_login.clone());
+        self.repo_path_account_editor = None;
+
+        if self.selected_repo.as_deref() == Some(repo) {
+            self.selected_account_login = Some(account_login.clone());
+            let repo = repo.to_owned();
+            self.select_repo(repo);
+        }
+    }
+
+    fn confirm_account_delete_matches(expected_login: &str, typed_login: &str) -> bool {
+        typed_login.trim() == expected_login
+    }
+
+    fn remove_account_by_login(&mut self, login: &str) {
+        if let Some(idx) = self
+            .accounts
+            .iter()
+            .position(|account| account.profile.login == login)
+        {
+            self.remove_account_at(idx);
+        } else {
+            self.account_delete_confirmation = None;
+            self.global_error = Some(format!("Cannot find tracked account {login} to remove."));
+        }
+    }
+
+    fn save_repo_path(&mut self) {
+        let Some(repo) = canonical_repo_key(&self.repo_path_form.repo) else {
+            self.repo_path_form.form_error =
+                Some("Repository must be in the form owner/repo.".to_owned());
+            return;
+        };
+
+        let raw_path = self.repo_path_form.path.trim();
+        if raw_path.is_empty() {
+            self.repo_path_form.form_error = Some("A local checkout path is required.".to_owned());
+            return;
+        }
+
+        let canonical_path = match fs::canonicalize(raw_path) {
+            Ok(path) => path,
+            Err(err) => {
+                self.repo_path_form.form_error =
+                    Some(format!("Unable to resolve checkout path: {err}"));
+                return;
+            }
+        };
+
+        if !canonical_path.is_dir() {
+            self.repo_path_form.form_error =
+                Some("Local checkout path must point to a directory.".to_owned());
+            return;
+        }
+
+        let canonical_path = canonical_path.display().to_string();
+        let linked_login = self.selected_account_login.clone().or_else(|| {
+            self.accounts
+                .first()
+                .map(|account| account.profile.login.clone())
+        });
+        if linked_login.is_none() {
+            self.repo_path_form.form_error =
+                Some("Add at least one tracked account before saving a repo path.".to_owned());
+            return;
+        }
+
+        if let Some(store) = &self.secret_store {
+            if let Err(err) =
+                store.persist_repo_path(&repo, &canonical_path, linked_login.as_deref())
+            {
+                self.repo_path_form.form_error =
+                    Some(format!("Unable to persist local repo path: {err}"));
+                return;
+            }
+        } else {
+            self.repo_path_form.form_error = Some(
+                "Local storage is not available; cannot save repo paths right now.".to_owned(),
+            );
+            return;
+        }
+
+        self.repo_paths.insert(repo.clone(), canonical_path);
+        if let Some(login) = linked_login {
+            self.repo_path_accounts.insert(repo, login);
+        }
+        self.repo_path_form = RepoPathForm::default();
+    }
+
+    fn open_review_settings_editor(&mut self, login: &str) {
+        let Some(account) = self
+            .accounts
+            .iter()
+            .find(|account| account.profile.login == login)
+        else {
+            self.global_error = Some(format!("Cannot find account settings for {login}."));
+            return;
+        };
+
+        self.review_settings_editor = Some(AccountReviewSettingsEditor {
+            login: account.profile.login.clone(),
+            env_vars_text: format_review_env_vars(&account.profile.review_settings),
+            additional_args_text: format_review_additional_args(&account.profile.review_settings),
+            review_prompt_md_path_text: account
+                .profile
+                .review_settings
+                .review_prompt_md_path
+                .clone()
+                .unwrap_or_default(),
+            pr_description_md_path_text: account
+                .profile
+                .review_settings
+                .pr_description_md_path
+                .clone()
+                .unwrap_or_default(),
+            hide_bot_notifications: account.profile.hide_bot_notifications,
+            working_hours: account.profile.working_hours,
+            token_command_text: account.profile.token_command.clone().unwrap_or_default(),
+            group_text: account.profile.group.clone().unwrap_or_default(),
+            section_alerts: account.profile.section_alerts,
+            inbox_backend: account.profile.inbox_backend,
+            form_error: None,
+        });
+    }
+
+    fn save_review_settings(&mut self) {
+        let Some(editor) = self.review_settings_editor.as_ref() else {
+            return;
+        };
+
+        let env_vars = match parse_review_env_vars(&editor.env_vars_text) {
+            Ok(env_vars) => env_vars,
+            Err(err) => {
+                if let Some(editor) = &mut self.review_settings_editor {
+                    editor.form_error = Some(err);
+                }
+                return;
+            }
+        };
+
+        let additional_args = parse_review_additional_args(&editor.additional_args_text);
+        let login = editor.login.clone();
+        let hide_bot_notifications = editor.hide_bot_notifications;
+        let working_hours = editor.working_hours;
+        let section_alerts = editor.section_alerts;
+        let inbox_backend = editor.inbox_backend;
+        let token_command = Some(editor.token_command_text.trim())
+            .filter(|command| !command.is_empty())
+            .map(str::to_owned);
+        let group = Some(editor.group_text.trim())
+            .filter(|group| !group.is_empty())
+            .map(str::to_owned);
+        let review_settings = ReviewCommandSettings {
+            env_vars,
+            additional_args,
+            review_prompt_md_path: normalize_optional_path(&editor.review_prompt_md_path_text),
+            pr_description_md_path: normalize_optional_path(&editor.pr_description_md_path_text),
+        };
+
+        let Some(account_idx) = self
+            .accounts
+            .iter()
+            .position(|account| account.profile.login == login)
+        else {
+            self.review_settings_editor = None;
+            self.global_error = Some(format!("Cannot find account settings for {login}."));
+            return;
+        };
+
+        let mut profile = self.accounts[account_idx].profile.clone();
+        profile.review_settings = review_settings.clone();
+        profile.hide_bot_notifications = hide_bot_notifications;
+        profile.working_hours = working_hours;
+        profile.group = group;
+        profile.section_alerts = section_alerts;
+        profile.inbox_backend = inbox_backend;
+        if token_command != profile.token_command
+            && let Some(command) = &token_command
+        {
+            match storage::run_token_command(command) {
+                Ok(token) => profile.token = token,
+                Err(err) => {
+                    if let Some(editor) = &mut self.review_settings_editor {
+                        editor.form_error = Some(err.to_string());
+                    }
+                    return;
+                }
+            }
+        }
+        profile.token_command = token_command;
+
+        if let Some(store) = &self.secret_store {
+            if let Err(err) = store.persist_profile(&profile) {
+                if let Some(editor) = &mut self.review_settings_editor {
+                    editor.form_error = Some(format!("Unable to save review settings: {err}"));
+                }
+                return;
+            }
+        } else if let Some(editor) = &mut self.review_settings_editor {
+            editor.form_error = Some(
+                "Local storage is not available; cannot save review settings right now.".to_owned(),
+            );
+            return;
+        }
+
+        self.accounts[account_idx].profile = profile;
+        self.accounts[account_idx].viewer.recheck();
+        self.review_settings_editor = None;
+    }
+
+    fn render_review_settings_window(&mut self, ctx: &Context) {
+        let app_section_alerts = self.settings.section_alerts;
+        let Some(editor) = self.review_settings_editor.as_mut() else {
+            return;
+        };
+
+        let mut open = true;
+        let mut save_requested = false;
+        let mut cancel_requested = false;
+        let title = format!("Review settings: {}", editor.login);
+        egui::Window::new(title)
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(true)
+            .default_size(egui::vec2(520.0, 360.0))
+            .show(ctx, |ui| {
+                ui.label("Environment variables (one KEY=VALUE per line)");
+                ui.add(
+                    egui::TextEdit::multiline(&mut editor.env_vars_text)
+                        .desired_rows(8)
+                        .desired_width(f32::INFINITY),
+                );
+                ui.add_space(8.0);
+                ui.label("Additional args (split on whitespace)");
+                ui.add(
+                    egui::TextEdit::multiline(&mut editor.additional_args_text)
+                        .desired_rows(3)
+                        .desired_width(f32::INFINITY)
+                        .hint_text("--lang korean"),
+                );
+                ui.add_space(8.0);
+                ui.label("Review prompt md path");
+                ui.add(
+                    egui::TextEdit::singleline(&mut editor.review_prompt_md_path_text)
+                        .desired_width(f32::INFINITY)
+                        .hint_text(default_review_prompt_md_path_display()),
+                );
+                ui.add_space(8.0);
+                ui.label("PR Description md path");
+                ui.add(
+                    egui::TextEdit::singleline(&mut editor.pr_description_md_path_text)
+                        .desired_width(f32::INFINITY)
+                        .hint_text(default_pr_description_prompt_md_path_display()),
+                );
+                ui.add_space(8.0);
+                ui.horizontal(|row| {
+                    row.label("Bot notifications");
+                    hide_bot_notifications_combo(row, &mut editor.hide_bot_notifications);
+                });
+                ui.add_space(8.0);
+                working_hours_editor(
+                    ui,
+                    &mut editor.working_hours,
+                    "Only auto-refresh during working hours",
+                );
+                ui.add_space(8.0);
+                let mut override_alerts = editor.section_alerts.is_some();
+                if ui
+                    .checkbox(&mut override_alerts, "Use its own section alerts")
+                    .changed()
+                {
+                    editor.section_alerts = override_alerts.then_some(app_section_alerts);
+                }
+                if let Some(thresholds) = &mut editor.section_alerts {
+                    section_alerts_editor(ui, thresholds);
+                }
+                ui.add_space(8.0);
+                ui.horizontal(|row| {
+                    row.label("Fetch lists over");
+                    for backend in InboxBackend::ALL {
+                        row.selectable_value(&mut editor.inbox_backend, backend, backend.label());
+                    }
+                })
+                .response
+                .on_hover_text(
+                    "GraphQL fetches review requests, mentions, assigned issues and pull request badges in one query, falling back to REST if it fails.",
+                );
+                ui.add_space(8.0);
+                ui.label("Dashboard group (optional)");
+                ui.add(
+                    egui::TextEdit::singleline(&mut editor.group_text)
+                        .desired_width(f32::INFINITY)
+                        .hint_text("Work"),
+                );
+                ui.add_space(8.0);
+                ui.label("Token command (optional)");
+                ui.add(
+                    egui::TextEdit::singleline(&mut editor.token_command_text)
+                        .desired_width(f32::INFINITY)
+                        .hint_text("op read op://Private/GitHub/token"),
+                )
+                .on_hover_text(
+                    "Runs at startup and its first output line becomes the token, which is then not stored by the app.",
+                );
+
+                if let Some(error) = &editor.form_error {
+                    ui.add_space(8.0);
+                    ui.colored_label(ui.visuals().error_fg_color, error);
+                }
+
+                ui.add_space(12.0);
+                ui.horizontal(|row| {
+                    if row.button("Save").clicked() {
+                        save_requested = true;
+                    }
+                    if row.button("Cancel").clicked() {
+                        cancel_requested = true;
+                    }
+                });
+            });
+
+        if save_requested {
+            self.save_review_settings();
+        } else if cancel_requested || !open {
+            self.review_settings_editor = None;
+        }
+    }
+
+    fn render_account_delete_confirmation_window(&mut self, ctx: &Context) {
+        let Some(confirmation) = self.account_delete_confirmation.as_mut() else {
+            return;
+        };
+
+        let mut open = true;
+        let mut delete_requested = false;
+        let mut cancel_requested = false;
+        let typed_matches =
+            Self::confirm_account_delete_matches(&confirmation.login, &confirmation.typed_login);
+        let title = format!("Delete account: {}", confirmation.login);
+        egui::Window::new(title)
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .default_size(egui::vec2(420.0, 180.0))
+            .show(ctx, |ui| {
+                ui.label("Type the exact account name to confirm deletion.");
+                ui.monospace(&confirmation.login);
+                ui.add_space(8.0);
+                ui.label("Account name");
+                ui.add(
+                    egui::TextEdit::singleline(&mut confirmation.typed_login)
+                        .desired_width(f32::INFINITY),
+                );
+                if !typed_matches && !confirmation.typed_login.is_empty() {
+                    ui.add_space(8.0);
+                    ui.colored_label(
+                        ui.visuals().warn_fg_color,
+                        "Typed account name does not match.",
+                    );
+                }
+                ui.add_space(12.0);
+                ui.horizontal(|row| {
+                    if row
+                        .add_enabled(typed_matches, egui::Button::new("Delete account"))
+                        .clicked()
+                    {
+                        delete_requested = true;
+                    }
+                    if row.button("Cancel").clicked() {
+                        cancel_requested = true;
+                    }
+                });
+            });
+
+        if delete_requested {
+            let login = confirmation.login.clone();
+            self.remove_account_by_login(&login);
+        } else if cancel_requested || !open {
+            self.account_delete_confirmation = None;
+        }
+    }
+
+    fn render_repo_path_account_editor_window(&mut self, ctx: &Context) {
+        let Some(editor) = self.repo_path_account_editor.as_mut() else {
+            return;
+        };
+
+        let mut open = true;
+        let mut selected_login = None;
+        let mut cancel_requested = false;
+        let title = format!("Repo path account: {}", editor.repo);
+        egui::Window::new(title)
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .default_size(egui::vec2(360.0, 220.0))
+            .show(ctx, |ui| {
+                ui.label("Choose which tracked account this local repo path depends on.");
+                if editor.repo_login.is_empty() {
+                    ui.small("Current account: unassigned");
+                } else {
+                    ui.small(format!("Current account: {}", editor.repo_login));
+                }
+                ui.add_space(8.0);
+
+                if self.accounts.is_empty() {
+                    ui.weak("No tracked accounts available.");
+                } else {
+                    for account in &self.accounts {
+                        if ui.button(&account.profile.login).clicked() {
+                            selected_login = Some(account.profile.login.clone());
+                        }
+                    }
+                }
+
+                if let Some(error) = &editor.form_error {
+                    ui.add_space(8.0);
+                    ui.colored_label(ui.visuals().error_fg_color, error);
+                }
+
+                ui.add_space(12.0);
+                if ui.button("Cancel").clicked() {
+                    cancel_requested = true;
+                }
+            });
+
+        if let Some(login) = selected_login {
+            let repo = editor.repo.clone();
+            self.assign_repo_path_account(&repo, &login);
+        } else if cancel_requested || !open {
+            self.repo_path_account_editor = None;
+        }
+    }
+
+    fn open_settings_editor(&mut self) {
+        self.settings_editor = Some(AppSettingsEditor {
+            draft: self.settings.clone(),
+            form_error: None,
+            passphrase: String::new(),
+            passphrase_confirmation: String::new(),
+            tab: PreferencesTab::General,
+            backups: self
+                .secret_store
+                .as_ref()
+                .and_then(|store| store.backups().ok())
+                .unwrap_or_default(),
+            restore_candidate: None,
+        });
+    }
+
+    fn render_whats_new_window(&mut self, ctx: &egui::Context) {
+        if self.whats_new.is_none() {
+            return;
+        }
+        let before = self.settings.clone();
+        let outcome = render_whats_new_window(
+            ctx,
+            &mut self.whats_new,
+            &mut self.settings,
+            &mut self.combined_inbox,
+        );
+        if outcome.settings_changed
+            && let Some(store) = &self.secret_store
+            && let Err(err) = store.persist_settings(&self.settings)
+        {
+            self.settings = before;
+            self.storage_warning = Some(format!("Unable to save preferences: {err}"));
+        }
+        if outcome.closed {
+            self.ui_state.last_seen_version = Some(env!("CARGO_PKG_VERSION").to_owned());
+        }
+    }
+
+    fn save_settings(&mut self) {
+        let Some(editor) = self.settings_editor.as_mut() else {
+            return;
+        };
+
+        let network_changed = editor.draft.network != self.settings.network;
+        if network_changed && let Err(err) = github::configure_network(&editor.draft.network) {
+            editor.form_error = Some(err.to_string());
+            return;
+        }
+        if let Some(store) = &self.secret_store {
+            if let Err(err) = store.persist_settings(&editor.draft) {
+                editor.form_error = Some(format!("Unable to save preferences: {err}"));
+                return;
+            }
+        } else {
+            editor.form_error = Some(
+                "Local storage is not available; cannot save preferences right now.".to_owned(),
+            );
+            return;
+        }
+
+        self.settings = editor.draft.clone();
+        self.settings_editor = None;
+        if network_changed {
+            for account in &mut self.accounts {
+                match github::build_client() {
+                    Ok(client) => account.client = client,
+                    Err(err) => {
+                        self.global_error = Some(format!(
+                            "{} keeps its old network settings: {err}",
+                            account.profile.login
+                        ));
+                    }
+                }
+            }
+            self.plugin_sections.reset_client();
+            self.avatars.reset_client();
+        }
+    }
+
+    /// Repositories seen in any account's feed or configured locally, plus any
+    /// that already carry a tier.
+    fn known_repos(&self) -> BTreeSet<String> {
+        let mut repos: BTreeSet<_> = self
+            .accounts
+            .iter()
+            .filter_map(|account| account.inbox.as_ref())
+            .flat_map(|inbox| &inbox.notifications)
+            .map(|item| item.repo.clone())
+            .collect();
+        repos.extend(self.repo_paths.keys().cloned());
+        repos.extend(self.settings.repo_tiers.keys().cloned());
+        repos
+    }
+
+    fn render_settings_window(&mut self, ctx: &Context) {
+        if self.settings_editor.is_none() {
+            return;
+        }
+        let known_repos = self.known_repos();
+        let store_usage = self.store_usage();
+        let worker_restarts: Vec<_> = self
+            .accounts
+            .iter()
+            .flat_map(|account| {
+                account
+                    .watchdog
+                    .restarts
+                    .iter()
+                    .map(|(at, stall)| (account.profile.login.as_str(), *at, *stall))
+            })
+            .collect();
+        let tokens_encrypted = self
+            .secret_store
+            .as_ref()
+            .is_some_and(AccountStore::is_encrypted);
+        let github_logins: Vec<_> = self
+            .accounts
+            .iter()
+            .filter(|account| account.profile.forge == Forge::GitHub)
+            .map(|account| account.profile.login.clone())
+            .collect();
+        let sync_status = if self.sync.is_running() {
+            String::from("Syncing…")
+        } else if let Some(error) = &self.sync.error {
+            format!("Last sync failed: {error}")
+        } else if let Some(at) = self.sync.last_synced {
+            format!("Last synced {}", time::format_local_timestamp(at, "%H:%M"))
+        } else {
+            String::from("Not synced this session.")
+        };
+        let Some(editor) = self.settings_editor.as_mut() else {
+            return;
+        };
+
+        let mut open = true;
+        let mut save_requested = false;
+        let mut cancel_requested = false;
+        let mut encrypt_requested = false;
+        let mut compact_requested = false;
+        let mut backup_requested = false;
+        let mut sync_requested = false;
+        let mut restore_requested = None;
+        egui::Window::new("Preferences")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(true)
+            .default_size(egui::vec2(420.0, 360.0))
+            .show(ctx, |ui| {
+                ui.horizontal(|row| {
+                    row.selectable_value(&mut editor.tab, PreferencesTab::General, "General");
+                    row.selectable_value(&mut editor.tab, PreferencesTab::Rules, "Rules");
+                    row.selectable_value(&mut editor.tab, PreferencesTab::Sections, "Sections");
+                    row.selectable_value(&mut editor.tab, PreferencesTab::Labs, "Labs");
+                });
+                ui.separator();
+                egui::ScrollArea::vertical()
+                    .max_height(420.0)
+                    .show(ui, |ui| {
+                        if editor.tab == PreferencesTab::Labs {
+                            render_labs_settings(ui, &mut editor.draft);
+                            return;
+                        }
+                        if editor.tab == PreferencesTab::Rules {
+                            render_rules_settings(ui, &mut editor.draft);
+                            return;
+                        }
+                        if editor.tab == PreferencesTab::Sections {
+                            render_saved_search_settings(ui, &mut editor.draft);
+                            return;
+                        }
+                        ui.strong("Double-click action");
+                        ui.small("Choose what double-clicking a notification row does for each reason.");
+                        ui.add_space(8.0);
+                        egui::Grid::new("double-click-actions")
+                            .num_columns(2)
+                            .striped(true)
+                            .show(ui, |grid| {
+                                for reason in KNOWN_NOTIFICATION_REASONS {
+                                    grid.label(*reason);
+                                    let mut action = editor.draft.double_click_action(reason);
+                                    double_click_action_combo(grid, reason, &mut action);
+                                    if action != editor.draft.double_click_action(reason) {
+                                        editor
+                                            .draft
+                                            .double_click_actions
+                                            .insert((*reason).to_owned(), action);
+                                    }
+                                    grid.end_row();
+                                }
+                                grid.label("Other reasons");
+                                double_click_action_combo(
+                                    grid,
+                                    "fallback",
+                                    &mut editor.draft.double_click_fallback,
+                                );
+                                grid.end_row();
+                            });
+                        ui.checkbox(
+                            &mut editor.draft.preview_pane,
+                            "Preview a notification in a side pane when its row is clicked",
+                        );
+
+                        ui.add_space(12.0);
+                        ui.strong("Swipe actions");
+                        ui.small("On touch screens, swiping a notification row sideways runs these.");
+                        egui::Grid::new("swipe-actions")
+                            .num_columns(2)
+                            .show(ui, |grid| {
+                                for (label, action) in [
+                                    ("Swipe right", &mut editor.draft.swipe_right),
+                                    ("Swipe left", &mut editor.draft.swipe_left),
+                                ] {
+                                    grid.label(label);
+                                    egui::ComboBox::from_id_salt(("swipe-action", label))
+                                        .selected_text(action.label())
+                                        .show_ui(grid, |menu| {
+                                            for candidate in SwipeAction::ALL {
+                                                menu.selectable_value(
+                                                    action,
+                                                    candidate,
+                                                    candidate.label(),
+                                                );
+                                            }
+                                        });
+                                    grid.end_row();
+                                }
+                            });
+
+                        ui.add_space(12.0);
+                        ui.strong("Filtering");
+                        ui.checkbox(
+                            &mut editor.draft.hide_bot_notifications,
+                            "Hide notifications from bots (dependabot, renovate, github-actions)",
+                        );
+                        ui.small("Accounts can override this in their own settings.");
+                        ui.checkbox(
+                            &mut editor.draft.show_done_section,
+                            "Show threads marked done in a Done section",
+                        );
+
+                        ui.add_space(12.0);
+                        ui.strong("Scale");
+                        egui::Grid::new("ui-scale-grid").show(ui, |grid| {
+                            grid.label("Interface zoom");
+                            grid.add(
+                                egui::Slider::new(&mut editor.draft.ui_scale_percent, 50..=300)
+                                    .suffix("%"),
+                            );
+                            grid.end_row();
+                            grid.label("Text size");
+                            grid.add(
+                                egui::Slider::new(&mut editor.draft.text_size_percent, 70..=200)
+                                    .suffix("%"),
+                            );
+                            grid.end_row();
+                        });
+                        ui.small("Ctrl/Cmd + and − zoom too, until the next restart.");
+                        ui.horizontal(|row| {
+                            row.label("Font file");
+                            row.add(
+                                egui::TextEdit::singleline(&mut editor.draft.custom_font_path)
+                                    .hint_text("/path/to/font.ttf"),
+                            );
+                        });
+                        ui.small("Used before the built-in and CJK fonts; leave empty for none.");
+
+                        ui.add_space(12.0);
+                        ui.strong("Status colors");
+                        egui::ComboBox::from_id_salt("status-palette")
+                            .selected_text(editor.draft.status_palette.label())
+                            .show_ui(ui, |menu| {
+                                for palette in StatusPalette::ALL {
+                                    menu.selectable_value(
+                                        &mut editor.draft.status_palette,
+                                        palette,
+                                        palette.label(),
+                                    );
+                                }
+                            });
+                        ui.checkbox(
+                            &mut editor.draft.status_icons,
+                            "Mark unread (●) and updated (↻) titles with a shape",
+                        );
+                        optional_color_picker(
+                            ui,
+                            &mut editor.draft.updated_color,
+                            "Custom color for updated threads",
+                        );
+                        optional_color_picker(
+                            ui,
+                            &mut editor.draft.accent_color,
+                            "Custom accent for selections and links",
+                        );
+
+                        ui.add_space(12.0);
+                        ui.strong("Update times");
+                        egui::ComboBox::from_id_salt("timestamp-style")
+                            .selected_text(editor.draft.timestamp_style.label())
+                            .show_ui(ui, |menu| {
+                                for style in TimestampStyle::ALL {
+                                    menu.selectable_value(
+                                        &mut editor.draft.timestamp_style,
+                                        style,
+                                        style.label(),
+                                    );
+                                }
+                            });
+
+                        ui.add_space(12.0);
+                        ui.strong("Layout direction");
+                        egui::ComboBox::from_id_salt("layout-direction")
+                            .selected_text(editor.draft.layout_direction.label())
+                            .show_ui(ui, |menu| {
+                                for direction in LayoutDirection::ALL {
+                                    menu.selectable_value(
+                                        &mut editor.draft.layout_direction,
+                                        direction,
+                                        direction.label(),
+                                    );
+                                }
+                            });
+
                 ui.add_space(12.0);
+                ui.strong("Alerts");
+                ui.checkbox(
+                    &mut editor.draft.desktop_alerts,
+                    "Show desktop alerts for new notifications",
+                );
+                working_hours_editor(
+                    ui,
+                    &mut editor.draft.alert_hours,
+                    "Only alert and update the title badge during these hours",
+                );
                 ui.horizontal(|row| {
-                    if row
-                        .add_enabled(typed_matches, egui::Button::new("Delete account"))
-                        .clicked()
-                    {
-                        delete_requested = true;
+                    row.label("Group a repository after");
+                    row.add(egui::DragValue::new(&mut editor.draft.storm_threshold).range(0..=500));
+                    row.label("updates in one refresh (0 = never)");
+                });
+                section_alerts_editor(ui, &mut editor.draft.section_alerts);
+                ui.horizontal(|row| {
+                    row.label("Alert about the same thread at most once every");
+                    row.add(
+                        egui::DragValue::new(&mut editor.draft.alert_dedup_minutes)
+                            .range(0..=1440)
+                            .suffix(" min"),
+                    );
+                });
+                ui.checkbox(
+                    &mut editor.draft.summary_on_exit,
+                    "Show what I handled today when closing the window",
+                );
+                ui.checkbox(
+                    &mut editor.draft.close_to_tray,
+                    "Hide to the tray when closing the window",
+                );
+                ui.horizontal(|row| {
+                    row.label("Remind me of my branches quiet for");
+                    row.add(
+                        egui::DragValue::new(&mut editor.draft.stale_branch_days)
+                            .range(0..=365)
+                            .suffix(" days"),
+                    );
+                    row.label("(0 = never)");
+                });
+                ui.horizontal(|row| {
+                    row.label("Flag review requests waiting longer than");
+                    row.add(
+                        egui::DragValue::new(&mut editor.draft.review_wait_hours)
+                            .range(0..=720)
+                            .suffix(" h"),
+                    );
+                    row.label("(0 = never)");
+                });
+                ui.add_enabled(
+                    editor.draft.review_wait_hours > 0,
+                    egui::Checkbox::new(
+                        &mut editor.draft.review_wait_reminders,
+                        "Remind me when they become overdue and each time the wait doubles",
+                    ),
+                );
+
+                ui.add_space(12.0);
+                ui.strong("Fetching");
+                ui.horizontal(|row| {
+                    row.label("Read at most");
+                    row.add(egui::DragValue::new(&mut editor.draft.max_fetch_pages).range(1..=50));
+                    row.label("pages of notifications and saved searches per refresh");
+                });
+                ui.horizontal(|row| {
+                    row.label("Try each request up to");
+                    row.add(egui::DragValue::new(&mut editor.draft.fetch_attempts).range(1..=10));
+                    row.label("times on timeouts and server errors");
+                });
+                ui.horizontal(|row| {
+                    row.label("Give up on a refresh after");
+                    row.add(
+                        egui::DragValue::new(&mut editor.draft.fetch_timeout_secs)
+                            .range(30..=1800)
+                            .suffix(" s"),
+                    );
+                });
+                ui.horizontal(|row| {
+                    row.label("Keep threads I marked read for");
+                    row.add(
+                        egui::DragValue::new(&mut editor.draft.action_settle_secs)
+                            .range(0..=600)
+                            .suffix(" s"),
+                    );
+                })
+                .response
+                .on_hover_text(
+                    "Refreshes right after marking threads read can still report them unread. 0 s trusts every refresh.",
+                );
+                ui.horizontal(|row| {
+                    row.label("Save power on battery at or below");
+                    row.add(
+                        egui::DragValue::new(&mut editor.draft.battery_saver_percent)
+                            .range(0..=100)
+                            .suffix("%"),
+                    );
+                })
+                .response
+                .on_hover_text("Slows refreshes and skips pull request detail lookups. 0% disables it.");
+                ui.horizontal(|row| {
+                    row.label("Pause polling after");
+                    row.add(
+                        egui::DragValue::new(&mut editor.draft.idle_pause_minutes)
+                            .range(0..=240)
+                            .suffix(" min"),
+                    );
+                    row.label("idle or while the screen is locked");
+                })
+                .response
+                .on_hover_text("Alerts pause too, and a refresh runs as soon as you are back. 0 keeps polling.");
+                ui.checkbox(&mut editor.draft.low_data_mode, "Low-data mode")
+                    .on_hover_text(
+                        "For tethered or metered connections: refresh less often, fetch unread notifications only, and skip pull request detail lookups.",
+                    );
+                ui.checkbox(
+                    &mut editor.draft.transliterate_search,
+                    "Match romanized search input",
+                )
+                .on_hover_text("Lets Latin input find Korean, Japanese, Chinese or accented titles, e.g. \"hangugeo\" finds \"한국어\".");
+
+                ui.add_space(12.0);
+                egui::CollapsingHeader::new(egui::RichText::new("Repository tiers").strong())
+                    .default_open(false)
+                    .show(ui, |section| {
+                        section.small(
+                            "Critical repositories sort first and get a red stripe; low ones sort last and never raise desktop alerts.",
+                        );
+                        if known_repos.is_empty() {
+                            section.weak("No repositories seen yet.");
+                            return;
+                        }
+                        egui::Grid::new("repo-tiers")
+                            .num_columns(2)
+                            .striped(true)
+                            .show(section, |grid| {
+                                for repo in &known_repos {
+                                    grid.label(repo);
+                                    let mut tier = editor.draft.repo_tier(repo);
+                                    egui::ComboBox::from_id_salt(("repo-tier", repo))
+                                        .selected_text(tier.label())
+                                        .show_ui(grid, |menu| {
+                                            for candidate in RepoTier::ALL {
+                                                menu.selectable_value(
+                                                    &mut tier,
+                                                    candidate,
+                                                    candidate.label(),
+                                                );
+                                            }
+                                        });
+                                    if tier == RepoTier::Normal {
+                                        editor.draft.repo_tiers.remove(repo);
+                                    } else {
+                                        editor.draft.repo_tiers.insert(repo.clone(), tier);
+                                    }
+                                    grid.end_row();
+                                }
+                            });
+                    });
+
+                        ui.add_space(12.0);
+                        ui.strong("Auto-snooze");
+                        ui.small(
+                            "Hold back notifications for a number of hours; they surface afterwards only if still unread. 0 disables snoozing.",
+                        );
+                        egui::Grid::new("auto-snooze-hours")
+                            .num_columns(2)
+                            .striped(true)
+                            .show(ui, |grid| {
+                                for reason in KNOWN_NOTIFICATION_REASONS {
+                                    grid.label(*reason);
+                                    let mut hours = editor
+                                        .draft
+                                        .auto_snooze_hours
+                                        .get(*reason)
+                                        .copied()
+                                        .unwrap_or(0);
+                                    grid.add(egui::DragValue::new(&mut hours).range(0..=168).suffix(" h"));
+                                    if hours == 0 {
+                                        editor.draft.auto_snooze_hours.remove(*reason);
+                                    } else {
+                                        editor
+                                            .draft
+                                            .auto_snooze_hours
+                                            .insert((*reason).to_owned(), hours);
+                                    }
+                                    grid.end_row();
+                                }
+                            });
+
+                        ui.add_space(12.0);
+                        ui.strong("Security");
+                        ui.small(if tokens_encrypted {
+                            "Tokens are encrypted with your passphrase. Enter a new one to change it."
+                        } else {
+                            "Tokens are kept in the OS keychain. Set a passphrase to encrypt them in the registry file instead."
+                        });
+                        egui::Grid::new("token-passphrase")
+                            .num_columns(2)
+                            .show(ui, |grid| {
+                                grid.label("Passphrase");
+                                grid.add(
+                                    egui::TextEdit::singleline(&mut editor.passphrase)
+                                        .password(true),
+                                );
+                                grid.end_row();
+                                grid.label("Confirm");
+                                grid.add(
+                                    egui::TextEdit::singleline(&mut editor.passphrase_confirmation)
+                                        .password(true),
+                                );
+                                grid.end_row();
+                            });
+                        let label = if tokens_encrypted {
+                            "Change passphrase"
+                        } else {
+                            "Encrypt tokens"
+                        };
+                        if ui
+                            .add_enabled(!editor.passphrase.is_empty(), egui::Button::new(label))
+                            .clicked()
+                        {
+                            encrypt_requested = true;
+                        }
+
+                        ui.add_space(12.0);
+                        egui::CollapsingHeader::new(egui::RichText::new("Release radar").strong())
+                            .default_open(false)
+                            .show(ui, |section| {
+                                render_release_radar_settings(
+                                    section,
+                                    &mut editor.draft.release_radar,
+                                    &github_logins,
+                                );
+                            });
+
+                        ui.add_space(12.0);
+                        egui::CollapsingHeader::new(egui::RichText::new("Sync").strong())
+                            .default_open(false)
+                            .show(ui, |section| {
+                                render_sync_settings(
+                                    section,
+                                    &mut editor.draft.sync,
+                                    &github_logins,
+                                    &sync_status,
+                                    &mut sync_requested,
+                                );
+                            });
+
+                        ui.add_space(12.0);
+                        egui::CollapsingHeader::new(egui::RichText::new("Network").strong())
+                            .default_open(false)
+                            .show(ui, |section| {
+                                render_network_settings(section, &mut editor.draft.network);
+                            });
+
+                        ui.add_space(12.0);
+                        egui::CollapsingHeader::new(egui::RichText::new("Backups").strong())
+                            .default_open(false)
+                            .show(ui, |section| {
+                                render_backup_settings(
+                                    section,
+                                    editor,
+                                    &mut backup_requested,
+                                    &mut restore_requested,
+                                );
+                            });
+
+                        ui.add_space(12.0);
+                        egui::CollapsingHeader::new(egui::RichText::new("Diagnostics").strong())
+                            .default_open(false)
+                            .show(ui, |section| {
+                                section.small(
+                                    "Approximate memory held by long-lived stores. They are trimmed every hour.",
+                                );
+                                egui::Grid::new("store-usage")
+                                    .num_columns(3)
+                                    .striped(true)
+                                    .show(section, |grid| {
+                                        for store in &store_usage {
+                                            grid.label(&store.name);
+                                            grid.label(format!("{} entries", store.entries));
+                                            grid.label(format_bytes(store.bytes));
+                                            grid.end_row();
+                                        }
+                                    });
+                                if section.button("Compact now").clicked() {
+                                    compact_requested = true;
+                                }
+                                section.add_space(8.0);
+                                if worker_restarts.is_empty() {
+                                    section.small("No background workers restarted this session.");
+                                }
+                                for (login, at, stall) in &worker_restarts {
+                                    section.small(format!(
+                                        "{} {login}: worker restarted, {stall}",
+                                        time::format_local_timestamp(*at, "%H:%M:%S")
+                                    ));
+                                }
+                            });
+                    });
+
+                if let Some(error) = &editor.form_error {
+                    ui.add_space(8.0);
+                    ui.colored_label(ui.visuals().error_fg_color, error);
+                }
+
+                ui.add_space(12.0);
+                ui.horizontal(|row| {
+                    if row.button("Save").clicked() {
+                        save_requested = true;
+                    }
+                    if row.button("Reset to defaults").clicked() {
+                        editor.draft = AppSettings::default();
                     }
                     if row.button("Cancel").clicked() {
                         cancel_requested = true;
@@ -636,67 +2354,248 @@ impl ReminderApp {
                 });
             });
 
-        if delete_requested {
-            let login = confirmation.login.clone();
-            self.remove_account_by_login(&login);
+        if encrypt_requested {
+            self.enable_token_encryption();
+        }
+        if compact_requested {
+            self.compact_stores();
+        }
+        if backup_requested {
+            self.create_backup_now();
+        }
+        if sync_requested {
+            self.sync.request();
+        }
+        if let Some(name) = restore_requested {
+            self.restore_backup(&name);
+            return;
+        }
+        if save_requested {
+            self.save_settings();
         } else if cancel_requested || !open {
-            self.account_delete_confirmation = None;
+            self.settings_editor = None;
         }
     }
 
-    fn render_repo_path_account_editor_window(&mut self, ctx: &Context) {
-        let Some(editor) = self.repo_path_account_editor.as_mut() else {
-            return;
-        };
+    fn render_bulk_action_confirmation_windows(&mut self, ctx: &Context) {
+        for account in &mut self.accounts {
+            let Some(action) = account.bulk_confirmation.as_ref() else {
+                continue;
+            };
 
-        let mut open = true;
-        let mut selected_login = None;
-        let mut cancel_requested = false;
-        let title = format!("Repo path account: {}", editor.repo);
-        egui::Window::new(title)
-            .open(&mut open)
-            .collapsible(false)
-            .resizable(false)
-            .default_size(egui::vec2(360.0, 220.0))
-            .show(ctx, |ui| {
-                ui.label("Choose which tracked account this local repo path depends on.");
-                if editor.repo_login.is_empty() {
-                    ui.small("Current account: unassigned");
-                } else {
-                    ui.small(format!("Current account: {}", editor.repo_login));
+            let mut open = true;
+            let mut confirm_requested = false;
+            let mut cancel_requested = false;
+            let count = action.thread_ids.len();
+            egui::Window::new(format!("{} · {}", action.label(), action.section))
+                .id(egui::Id::new(("bulk-action", &account.profile.login)))
+                .open(&mut open)
+                .collapsible(false)
+                .resizable(false)
+                .show(ctx, |ui| {
+                    ui.label(if action.done {
+                        format!("Mark {count} visible notification(s) as done?")
+                    } else {
+                        format!("Mark {count} visible notification(s) as read?")
+                    });
+                    ui.weak(format!("Account: {}", account.profile.login));
+                    ui.add_space(8.0);
+                    ui.horizontal(|row| {
+                        if row.button(action.label()).clicked() {
+                            confirm_requested = true;
+                        }
+                        if row.button("Cancel").clicked() {
+                            cancel_requested = true;
+                        }
+                    });
+                });
+
+            if confirm_requested {
+                if let Some(action) = account.bulk_confirmation.take() {
+                    account.request_bulk_action(action);
                 }
-                ui.add_space(8.0);
+            } else if cancel_requested || !open {
+                account.bulk_confirmation = None;
+            }
+        }
+    }
 
-                if self.accounts.is_empty() {
-                    ui.weak("No tracked accounts available.");
-                } else {
-                    for account in &self.accounts {
-                        if ui.button(&account.profile.login).clicked() {
-                            selected_login = Some(account.profile.login.clone());
+    fn render_notification_detail_windows(&mut self, ctx: &Context) {
+        let show_timeline = self.settings.experiment_enabled(Experiment::ThreadTimeline);
+        for account in &mut self.accounts {
+            let Some(thread_id) = account.detail_thread_id.clone() else {
+                continue;
+            };
+            let Some(item) = account.inbox.as_ref().and_then(|inbox| {
+                inbox
+                    .notifications
+                    .iter()
+                    .find(|item| item.thread_id == thread_id)
+                    .cloned()
+            }) else {
+                account.detail_thread_id = None;
+                account.timeline = None;
+                continue;
+            };
+            if show_timeline {
+                account.load_timeline(&item, false);
+            } else {
+                account.timeline = None;
+            }
+
+            let mut open = true;
+            let mut mark_read_requested = false;
+            let mut open_requested = false;
+            let mut remind_requested = false;
+            let mut reload_timeline = false;
+            let timeline = account.timeline.as_ref();
+            let title = format!("{} · {}", account.profile.login, item.display_title());
+            egui::Window::new(title)
+                .id(egui::Id::new((
+                    "notification-detail",
+                    &account.profile.login,
+                )))
+                .open(&mut open)
+                .collapsible(false)
+                .resizable(true)
+                .default_size(egui::vec2(420.0, 360.0))
+                .show(ctx, |ui| {
+                    ui.label(format!("Repository: {}", item.repo));
+                    ui.label(format!("Reason: {}", item.reason));
+                    if let Some(direction) = item.merge_direction_text() {
+                        ui.label(format!("Branches: {direction}"));
+                    }
+                    ui.label(format!(
+                        "Updated: {}",
+                        time::format_local_timestamp(item.updated_at, "%Y-%m-%d %H:%M")
+                    ));
+                    ui.label(if item.unread { "Unread" } else { "Read" });
+                    ui.add_space(12.0);
+                    ui.horizontal(|row| {
+                        if row
+                            .add_enabled(item.url.is_some(), egui::Button::new("Open in browser"))
+                            .clicked()
+                        {
+                            open_requested = true;
                         }
+                        if row
+                            .add_enabled(item.unread, egui::Button::new("Mark read"))
+                            .clicked()
+                        {
+                            mark_read_requested = true;
+                        }
+                        if row.button("Remind me…").clicked() {
+                            remind_requested = true;
+                        }
+                    });
+                    if let Some(timeline) = timeline {
+                        ui.separator();
+                        reload_timeline = render_thread_timeline(ui, timeline);
                     }
-                }
+                });
 
-                if let Some(error) = &editor.form_error {
-                    ui.add_space(8.0);
-                    ui.colored_label(ui.visuals().error_fg_color, error);
-                }
+            if open_requested && let Some(url) = &item.url {
+                ctx.open_url(egui::OpenUrl::new_tab(url));
+                account.mark_notification_seen(&item.thread_id);
+            }
+            if mark_read_requested {
+                account.request_mark_read(item.thread_id.clone());
+            }
+            if remind_requested {
+                account.remind_thread_id = Some(item.thread_id.clone());
+            }
+            if reload_timeline {
+                account.load_timeline(&item, true);
+            }
+            if !open {
+                account.detail_thread_id = None;
+                account.timeline = None;
+            }
+        }
+    }
 
-                ui.add_space(12.0);
-                if ui.button("Cancel").clicked() {
-                    cancel_requested = true;
+    /// Moves the preview pane to the row clicked last, in whichever account
+    /// it belongs to.
+    fn open_preview_requests(&mut self) {
+        let Some(index) = self
+            .accounts
+            .iter()
+            .position(|account| account.preview_request.is_some())
+        else {
+            return;
+        };
+        for (other, account) in self.accounts.iter_mut().enumerate() {
+            if other != index {
+                account.preview_request = None;
+                account.preview = None;
+            }
+        }
+        let account = &mut self.accounts[index];
+        let Some(thread_id) = account.preview_request.take() else {
+            return;
+        };
+        if let Some(item) = account.inbox.as_ref().and_then(|inbox| {
+            inbox
+                .notifications
+                .iter()
+                .find(|item| item.thread_id == thread_id)
+                .cloned()
+        }) {
+            account.load_preview(&item, false);
+        }
+    }
+
+    fn render_preview_panel(&mut self, ctx: &Context, right_to_left: bool) {
+        if !self.settings.preview_pane {
+            return;
+        }
+        let timestamp_style = self.settings.timestamp_style;
+        let Some(account) = self
+            .accounts
+            .iter_mut()
+            .find(|account| account.preview.is_some())
+        else {
+            return;
+        };
+        let Some(item) = account.preview.as_ref().and_then(|preview| {
+            account.inbox.as_ref().and_then(|inbox| {
+                inbox
+                    .notifications
+                    .iter()
+                    .find(|item| item.thread_id == preview.thread_id)
+                    .cloned()
+            })
+        }) else {
+            account.preview = None;
+            return;
+        };
+        let Some(preview) = &mut account.preview else {
+            return;
+        };
+        let action = egui::SidePanel::new(preview_panel_side(right_to_left), "preview_pane")
+            .resizable(true)
+            .default_width(380.0)
+            .show(ctx, |ui| {
+                render_preview_pane(ui, &item, preview, timestamp_style)
+            })
+            .inner;
+        match action {
+            Some(PreviewAction::Open) => {
+                if let Some(url) = &item.url {
+                    ctx.open_url(egui::OpenUrl::new_tab(url));
+                    account.mark_notification_seen(&item.thread_id);
                 }
-            });
-
-        if let Some(login) = selected_login {
-            let repo = editor.repo.clone();
-            self.assign_repo_path_account(&repo, &login);
-        } else if cancel_requested || !open {
-            self.repo_path_account_editor = None;
+            }
+            Some(PreviewAction::MarkRead) => account.request_mark_read(item.thread_id.clone()),
+            Some(PreviewAction::Reload) => account.load_preview(&item, true),
+            Some(PreviewAction::Close) => account.preview = None,
+            Some(PreviewAction::Reply(reply)) => account.request_reply(&item, reply),
+            None => {}
         }
     }
 
     fn render_review_request_windows(&mut self, ctx: &Context) {
+        let palette = self.settings.status_palette;
         for idx in 0..self.accounts.len() {
             let mut request_login = None;
             let mut renotify_login = None;
@@ -771,7 +2670,10 @@ impl ReminderApp {
                             row.label("Current reviewers");
                             if let Some(summary) = summarize_current_reviewers(&current_reviewers) {
                                 row.colored_label(
-                                    summary.color(row.visuals()),
+                                    summary.color(
+                                        status_colors(palette, row.visuals()),
+                                        row.visuals(),
+                                    ),
                                     format!("• {}", summary.label()),
                                 );
                             }
@@ -860,93 +2762,777 @@ impl ReminderApp {
                         });
                     });
 
-                if !open {
-                    cancel_requested = true;
+                if !open {
+                    cancel_requested = true;
+                }
+            }
+
+            let account = &mut self.accounts[idx];
+            if let Some(login) = remove_login {
+                account.remove_pull_request_review(login);
+            } else if let Some(login) = request_login {
+                account.request_pull_request_review(login);
+            } else if let Some(login) = renotify_login {
+                account.renotify_pull_request_review(login);
+            } else if cancel_requested {
+                account.close_review_request_editor();
+            }
+        }
+    }
+
+    fn remove_repo_path(&mut self, repo: &str) {
+        if let Some(store) = &self.secret_store
+            && let Err(err) = store.forget_repo_path(repo)
+        {
+            self.global_error = Some(format!("Failed to remove repo path for {repo}: {err}"));
+            return;
+        }
+
+        self.repo_paths.remove(repo);
+        self.repo_path_accounts.remove(repo);
+        self.repo_views.remove(repo);
+        if self.selected_repo.as_deref() == Some(repo) {
+            self.selected_repo = None;
+        }
+        if self
+            .repo_path_account_editor
+            .as_ref()
+            .is_some_and(|editor| editor.repo == repo)
+        {
+            self.repo_path_account_editor = None;
+        }
+    }
+
+    fn ensure_selected_account(&mut self) {
+        let Some(selected_login) = self.selected_account_login.as_deref() else {
+            self.selected_account_login = self
+                .accounts
+                .first()
+                .map(|account| account.profile.login.clone());
+            return;
+        };
+
+        if self
+            .accounts
+            .iter()
+            .any(|account| account.profile.login == selected_login)
+        {
+            return;
+        }
+
+        self.selected_account_login = self
+            .accounts
+            .first()
+            .map(|account| account.profile.login.clone());
+    }
+
+    fn ensure_selected_repo(&mut self) {
+        let Some(selected_repo) = self.selected_repo.as_deref() else {
+            return;
+        };
+
+        if self.repo_paths.contains_key(selected_repo) {
+            return;
+        }
+
+        self.selected_repo = None;
+    }
+
+    fn power_saving(&self) -> bool {
+        self.power.is_saving(self.settings.battery_saver_percent)
+    }
+
+    fn poll_jobs(&mut self) {
+        self.poll_device_flow();
+        self.poll_token_check();
+        self.poll_token_update();
+        self.power.refresh();
+        let enrich = !self.settings.low_data_mode && !self.power_saving();
+        let away = self.idle.is_away();
+        for account in &mut self.accounts {
+            account.viewer.poll(&account.profile.login);
+            if !away {
+                account
+                    .viewer
+                    .maybe_start(&account.client, &account.profile);
+            }
+            account.poll_job(&self.settings, &self.rules, enrich);
+            account.poll_notification_metadata_job();
+            account.poll_pull_request_status_job();
+            account.poll_action_jobs();
+            account.poll_review_job();
+            account.poll_review_request_jobs();
+            account.poll_timeline_job();
+            account.poll_preview_job();
+            account.poll_reply_jobs();
+            account.poll_more_results_job();
+            account.check_workers(Duration::from_secs(self.settings.fetch_timeout_secs));
+            account.saved_searches.poll();
+            if let Some(inbox) = &account.inbox {
+                account.saved_searches.observe(
+                    inbox,
+                    &self.settings.saved_searches,
+                    &account.client,
+                    &account.profile,
+                    self.settings.max_fetch_pages,
+                );
+            }
+            let alerts = account.stale_branches.poll();
+            account.publish_alerts(alerts);
+            if !away
+                && account.profile.forge == Forge::GitHub
+                && let Some(inbox) = &account.inbox
+            {
+                // Repositories I have open pull requests in or a checkout of.
+                let mut repos: BTreeSet<_> = self.repo_paths.keys().cloned().collect();
+                repos.extend(
+                    inbox
+                        .authored_pull_requests
+                        .iter()
+                        .map(|pull_request| pull_request.repo.clone()),
+                );
+                account.stale_branches.maybe_start(
+                    &account.client,
+                    &account.profile,
+                    repos.into_iter().collect(),
+                    self.settings.stale_branch_days,
+                    self.settings.max_fetch_pages,
+                );
+            }
+        }
+        for repo_view in self.repo_views.values_mut() {
+            repo_view.poll_job();
+        }
+        self.poll_release_radar(away);
+        self.plugin_sections.poll(away);
+    }
+
+    /// Searches the configured org for release blockers every few minutes
+    /// with the radar's account, or the first GitHub account.
+    fn poll_release_radar(&mut self, away: bool) {
+        self.release_radar.poll();
+        let radar = &self.settings.release_radar;
+        let Some(query) = radar.search_query() else {
+            self.release_radar.reset();
+            return;
+        };
+        let account = self
+            .accounts
+            .iter()
+            .filter(|account| account.profile.forge == Forge::GitHub)
+            .find(|account| {
+                radar.account.trim().is_empty()
+                    || account
+                        .profile
+                        .login
+                        .eq_ignore_ascii_case(radar.account.trim())
+            });
+        if let Some(account) = account
+            && !away
+        {
+            self.release_radar.maybe_start(
+                &account.client,
+                &account.profile,
+                query,
+                self.settings.max_fetch_pages,
+            );
+        }
+    }
+
+    fn persist_inbox_caches(&mut self) {
+        let Some(store) = &self.secret_store else {
+            return;
+        };
+        for account in &mut self.accounts {
+            if std::mem::take(&mut account.enrichment_cache_dirty)
+                && let Err(err) = store.persist_enrichment_cache(
+                    &account.profile.storage_key(),
+                    &account.enrichment_cache,
+                )
+            {
+                self.storage_warning = Some(format!(
+                    "Unable to save the cached pull request details of {}: {err}",
+                    account.profile.login
+                ));
+            }
+            if std::mem::take(&mut account.done_threads_dirty)
+                && let Err(err) = store
+                    .persist_done_threads(&account.profile.storage_key(), &account.done_threads)
+            {
+                self.storage_warning = Some(format!(
+                    "Unable to save the threads {} marked done: {err}",
+                    account.profile.login
+                ));
+            }
+            if std::mem::take(&mut account.thread_stages_dirty)
+                && let Err(err) = store
+                    .persist_thread_stages(&account.profile.storage_key(), &account.thread_stages)
+            {
+                self.storage_warning = Some(format!(
+                    "Unable to save the thread stages of {}: {err}",
+                    account.profile.login
+                ));
+            }
+            if std::mem::take(&mut account.archive_dirty)
+                && let Err(err) =
+                    store.persist_archive(&account.profile.storage_key(), &account.archive)
+            {
+                self.storage_warning = Some(format!(
+                    "Unable to save the notification history of {}: {err}",
+                    account.profile.login
+                ));
+            }
+            if !std::mem::take(&mut account.inbox_cache_dirty) {
+                continue;
+            }
+            if let Some(inbox) = &account.inbox
+                && let Err(err) = store.persist_inbox_cache(&account.profile.storage_key(), inbox)
+            {
+                self.storage_warning = Some(format!(
+                    "Unable to save the offline inbox of {}: {err}",
+                    account.profile.login
+                ));
+            }
+        }
+    }
+
+    /// Trims every long-lived store so a session running for weeks does not
+    /// keep growing.
+    fn compact_stores(&mut self) {
+        self.last_compaction = Instant::now();
+        let now = Utc::now();
+        self.alert_throttle
+            .compact(now, self.settings.alert_dedup_window());
+        for account in &mut self.accounts {
+            account.compact_stores(now);
+        }
+        let repo_paths = &self.repo_paths;
+        self.repo_views
+            .retain(|repo, view| repo_paths.contains_key(repo) || view.pending_job.is_some());
+    }
+
+    fn store_usage(&self) -> Vec<StoreUsage> {
+        let mut usage = Vec::new();
+        for account in &self.accounts {
+            let login = &account.profile.login;
+            if let Some(inbox) = &account.inbox {
+                usage.push(StoreUsage::serialized(
+                    format!("Inbox · {login}"),
+                    inbox.notifications.len()
+                        + inbox.review_requests.len()
+                        + inbox.mentions.len()
+                        + inbox.recent_reviews.len(),
+                    inbox,
+                ));
+            }
+            usage.push(StoreUsage::serialized(
+                format!("Pull request details · {login}"),
+                account.enrichment_cache.entries.len(),
+                &account.enrichment_cache,
+            ));
+            usage.push(StoreUsage::serialized(
+                format!("Done threads · {login}"),
+                account.done_threads.entries.len(),
+                &account.done_threads,
+            ));
+            usage.push(StoreUsage::serialized(
+                format!("Notification history · {login}"),
+                account.archive.entries.len(),
+                &account.archive,
+            ));
+            usage.push(StoreUsage::new(
+                format!("Review output · {login}"),
+                account.review_outputs.len(),
+                account.review_outputs.values().map(review_output_len).sum(),
+            ));
+        }
+        usage.push(StoreUsage::new(
+            "Alert dedup",
+            self.alert_throttle.len(),
+            self.alert_throttle.approximate_bytes(),
+        ));
+        usage.push(StoreUsage::serialized(
+            "Reminders",
+            self.reminders.reminders.len(),
+            &self.reminders,
+        ));
+        usage.push(StoreUsage::serialized("History", 1, &self.history));
+        usage
+    }
+
+    fn total_unread_count(&self) -> usize {
+        self.accounts
+            .iter()
+            .filter_map(AccountState::unread_count)
+            .sum()
+    }
+
+    fn track_inbox_zero(&mut self) {
+        let reached = !self.accounts.is_empty()
+            && self
+                .accounts
+                .iter()
+                .all(|account| account.unread_count() == Some(0));
+        if !reached || !self.history.inbox_zero.record(Local::now().date_naive()) {
+            return;
+        }
+        if let Some(store) = &self.secret_store
+            && let Err(err) = store.persist_history(&self.history)
+        {
+            self.storage_warning = Some(format!("Unable to save inbox zero streak: {err}"));
+        }
+    }
+
+    /// Hides the window to the tray unless Quit was picked there. Otherwise
+    /// holds the first close request of a day with handled threads to show
+    /// the summary; closing again, or Quit in the summary, lets it through.
+    fn intercept_close(&mut self, ctx: &Context) {
+        if self.close_confirmed || !ctx.input(|input| input.viewport().close_requested()) {
+            return;
+        }
+        if self.tray.is_some() && self.settings.close_to_tray && !self.quit_requested {
+            ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+            ctx.send_viewport_cmd(egui::ViewportCommand::Visible(false));
+            return;
+        }
+        if self.exit_summary || !self.settings.summary_on_exit {
+            return;
+        }
+        if self.history.handled.on(Local::now().date_naive()).total() > 0 {
+            ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+            self.exit_summary = true;
+        }
+    }
+
+    fn render_exit_summary_window(&mut self, ctx: &Context) {
+        if !self.exit_summary {
+            return;
+        }
+        let today = Local::now().date_naive();
+        let counts = self.history.handled.on(today);
+        let streak = self.history.inbox_zero.current(today);
+        let mut show_again = self.settings.summary_on_exit;
+        let mut open = true;
+        let mut quit = false;
+        egui::Window::new("Before you go")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                let items = if counts.total() == 1 { "item" } else { "items" };
+                ui.heading(format!("You handled {} {items} today", counts.total()));
+                ui.label(format!(
+                    "{} marked read, {} marked done.",
+                    counts.read, counts.done
+                ));
+                if streak > 0 {
+                    let days = if streak == 1 { "day" } else { "days" };
+                    ui.label(format!("Inbox zero streak: {streak} {days}."));
+                }
+                ui.add_space(8.0);
+                ui.checkbox(&mut show_again, "Show this when closing");
+                ui.horizontal(|row| {
+                    quit = row.button("Quit").clicked();
+                    if row.button("Keep open").clicked() {
+                        self.exit_summary = false;
+                    }
+                });
+            });
+        if !open {
+            self.exit_summary = false;
+        }
+        if !self.exit_summary {
+            self.quit_requested = false;
+        }
+        if show_again != self.settings.summary_on_exit {
+            self.settings.summary_on_exit = show_again;
+            if let Some(store) = &self.secret_store
+                && let Err(err) = store.persist_settings(&self.settings)
+            {
+                self.storage_warning = Some(format!("Unable to save preferences: {err}"));
+            }
+        }
+        if quit {
+            self.close_confirmed = true;
+            ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+        }
+    }
+
+    /// Publishes each reminder that came due. Held back while away so they
+    /// fire when the user returns.
+    fn check_reminders(&mut self) {
+        if self.idle.is_away() {
+            return;
+        }
+        let fired = self.reminders.take_newly_due(Utc::now());
+        if fired.is_empty() {
+            return;
+        }
+        for reminder in fired {
+            self.events.publish(AppEvent::ReminderDue(reminder));
+        }
+        self.persist_reminders();
+    }
+
+    fn persist_reminders(&mut self) {
+        if let Some(store) = &self.secret_store
+            && let Err(err) = store.persist_reminders(&self.reminders)
+        {
+            self.storage_warning = Some(format!("Unable to save reminders: {err}"));
+        }
+    }
+
+    fn apply_reminder_actions(&mut self, ctx: &Context, actions: Vec<ReminderAction>) {
+        if actions.is_empty() {
+            return;
+        }
+        for action in actions {
+            match action {
+                ReminderAction::Open(id) => {
+                    if let Some(reminder) = self.reminders.get(id) {
+                        ctx.open_url(egui::OpenUrl::new_tab(&reminder.url));
+                    }
+                }
+                ReminderAction::Snooze(id, due) => match reminders::due_at(due) {
+                    Ok(due_at) => self.reminders.reschedule(id, due_at),
+                    Err(err) => self.global_error = Some(err),
+                },
+                ReminderAction::Done(id) => self.reminders.remove(id),
+            }
+        }
+        self.persist_reminders();
+    }
+
+    /// Turns "Remind" clicks on notifications into a prefilled editor.
+    /// Refreshes accounts whose card asked for it, or the selected account
+    /// when the refresh shortcut was pressed outside any card.
+    fn start_requested_refreshes(&mut self, ctx: &Context) {
+        let shortcut_target = if consume_refresh_shortcut(ctx) {
+            self.selected_account_index()
+        } else {
+            None
+        };
+        let mut triggered = false;
+        for (idx, account) in self.accounts.iter_mut().enumerate() {
+            let requested =
+                std::mem::take(&mut account.refresh_requested) || shortcut_target == Some(idx);
+            if requested && account.pending_job.is_none() {
+                account.start_refresh(&self.settings);
+                triggered = true;
+            }
+        }
+        if triggered {
+            self.auto_refresh.mark_triggered();
+        }
+    }
+
+    fn open_reminder_requests(&mut self) {
+        for account in &mut self.accounts {
+            let Some(thread_id) = account.remind_thread_id.take() else {
+                continue;
+            };
+            let Some(item) = account.inbox.as_ref().and_then(|inbox| {
+                inbox
+                    .notifications
+                    .iter()
+                    .find(|item| item.thread_id == thread_id)
+            }) else {
+                continue;
+            };
+            self.reminder_editor = Some(ReminderEditor::new(
+                item.display_title(),
+                item.url.clone().unwrap_or_default(),
+                Some(account.profile.login.clone()),
+                Some(thread_id),
+            ));
+        }
+    }
+
+    fn render_reminder_editor_window(&mut self, ctx: &Context) {
+        let Some(editor) = self.reminder_editor.as_mut() else {
+            return;
+        };
+
+        let mut open = true;
+        let mut save_requested = false;
+        let mut cancel_requested = false;
+        egui::Window::new("New reminder")
+            .id(egui::Id::new("reminder-editor"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                if let Some(login) = &editor.login {
+                    ui.small(format!("Account: {login}"));
+                }
+                ui.label("Title");
+                ui.add(
+                    egui::TextEdit::singleline(&mut editor.title).hint_text("Follow up on review"),
+                );
+                ui.label("GitHub URL");
+                ui.add(
+                    egui::TextEdit::singleline(&mut editor.url)
+                        .hint_text("https://github.com/owner/repo/pull/1"),
+                );
+                ui.label("Due");
+                ui.add(egui::TextEdit::singleline(&mut editor.due_input).hint_text(DUE_INPUT_HINT));
+
+                if let Some(error) = &editor.form_error {
+                    ui.colored_label(ui.visuals().error_fg_color, error);
                 }
-            }
+                ui.add_space(8.0);
+                ui.horizontal(|row| {
+                    if row.button("Save").clicked() {
+                        save_requested = true;
+                    }
+                    if row.button("Cancel").clicked() {
+                        cancel_requested = true;
+                    }
+                });
+            });
 
-            let account = &mut self.accounts[idx];
-            if let Some(login) = remove_login {
-                account.remove_pull_request_review(login);
-            } else if let Some(login) = request_login {
-                account.request_pull_request_review(login);
-            } else if let Some(login) = renotify_login {
-                account.renotify_pull_request_review(login);
-            } else if cancel_requested {
-                account.close_review_request_editor();
+        if save_requested {
+            let url = editor.url.trim();
+            if !url.starts_with("http://") && !url.starts_with("https://") {
+                editor.form_error = Some(String::from("Enter the URL to open when it is due."));
+                return;
             }
+            let due_at = match reminders::due_at(&editor.due_input) {
+                Ok(due_at) => due_at,
+                Err(err) => {
+                    editor.form_error = Some(err);
+                    return;
+                }
+            };
+            let title = match editor.title.trim() {
+                "" => url.to_owned(),
+                title => title.to_owned(),
+            };
+            let Some(editor) = self.reminder_editor.take() else {
+                return;
+            };
+            self.reminders.add(
+                title,
+                editor.url.trim().to_owned(),
+                editor.login,
+                editor.thread_id,
+                due_at,
+            );
+            self.persist_reminders();
+            return;
+        }
+        if cancel_requested || !open {
+            self.reminder_editor = None;
         }
     }
 
-    fn remove_repo_path(&mut self, repo: &str) {
-        if let Some(store) = &self.secret_store
-            && let Err(err) = store.forget_repo_path(repo)
-        {
-            self.global_error = Some(format!("Failed to remove repo path for {repo}: {err}"));
-            return;
+    /// Writes an HTML snapshot of every account's sections under the data
+    /// directory and opens it in the browser.
+    fn export_report(&mut self, ctx: &Context) {
+        let now = Local::now();
+        let html = report::render_report(
+            self.accounts.iter().filter_map(|account| {
+                Some(ReportAccount {
+                    login: &account.profile.login,
+                    inbox: account.inbox.as_ref()?,
+                    done: &account.done_threads,
+                })
+            }),
+            &self.rules,
+            now,
+        );
+        let written = storage::data_dir(self.profile.as_deref())
+            .map_err(|err| err.to_string())
+            .and_then(|dir| report::write_report(&dir, &html, now).map_err(|err| err.to_string()));
+        match written {
+            Ok(path) => ctx.open_url(egui::OpenUrl::new_tab(format!("file://{}", path.display()))),
+            Err(err) => self.global_error = Some(format!("Failed to export the report: {err}")),
         }
+    }
 
-        self.repo_paths.remove(repo);
-        self.repo_path_accounts.remove(repo);
-        self.repo_views.remove(repo);
-        if self.selected_repo.as_deref() == Some(repo) {
-            self.selected_repo = None;
+    /// Do-not-disturb is on or it is outside the alert hours.
+    fn is_quiet(&self) -> bool {
+        alerts::is_quiet(
+            self.ui_state.do_not_disturb,
+            self.settings.alert_hours,
+            Local::now().naive_local(),
+        )
+    }
+
+    fn sync_window_title(&mut self, ctx: &Context) {
+        if let Some(tray) = &mut self.tray {
+            tray.set_muted(self.ui_state.do_not_disturb);
         }
-        if self
-            .repo_path_account_editor
-            .as_ref()
-            .is_some_and(|editor| editor.repo == repo)
-        {
-            self.repo_path_account_editor = None;
+        if self.is_quiet() {
+            return;
+        }
+        let title = window_title(self.profile.as_deref(), self.total_unread_count());
+        if title != self.window_title {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Title(title.clone()));
+            self.window_title = title;
+        }
+        let unread = self.total_unread_count();
+        if let Some(tray) = &mut self.tray {
+            tray.set_unread(unread);
         }
     }
 
-    fn ensure_selected_account(&mut self) {
-        let Some(selected_login) = self.selected_account_login.as_deref() else {
-            self.selected_account_login = self
-                .accounts
-                .first()
-                .map(|account| account.profile.login.clone());
+    /// Acts on what was picked in the tray menu since the last frame.
+    fn handle_tray_commands(&mut self, ctx: &Context) {
+        let Some(tray) = &self.tray else {
             return;
         };
+        for command in tray.commands() {
+            match command {
+                TrayCommand::Open => {
+                    ctx.send_viewport_cmd(egui::ViewportCommand::Visible(true));
+                    ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
+                }
+                TrayCommand::RefreshAll => {
+                    for account in &mut self.accounts {
+                        account.refresh_requested = true;
+                    }
+                }
+                TrayCommand::ToggleMute => {
+                    self.ui_state.do_not_disturb = !self.ui_state.do_not_disturb;
+                }
+                TrayCommand::Quit => {
+                    // Shown again in case the summary holds the close.
+                    self.quit_requested = true;
+                    ctx.send_viewport_cmd(egui::ViewportCommand::Visible(true));
+                    ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+                }
+            }
+        }
+    }
 
-        if self
-            .accounts
-            .iter()
-            .any(|account| account.profile.login == selected_login)
+    /// Reacts to everything published on the event bus since the last
+    /// frame. Alerts that arrive while away or in quiet hours are dropped.
+    fn handle_events(&mut self, ctx: &Context) {
+        let now = Utc::now();
+        let window = self.settings.alert_dedup_window();
+        let alerting = self.settings.desktop_alerts && !self.idle.is_away() && !self.is_quiet();
+        let mut inbox_changed = false;
+        let mut handled_changed = false;
+        for event in self.events.drain() {
+            let alert = match event {
+                AppEvent::SnapshotReady { .. } => {
+                    inbox_changed = true;
+                    continue;
+                }
+                AppEvent::ActionCompleted { handled, .. } => {
+                    if let Some(kind) = handled {
+                        self.history.handled.record(kind, Local::now().date_naive());
+                        handled_changed = true;
+                    }
+                    inbox_changed = true;
+                    continue;
+                }
+                // Each reminder alerts once per due time, so skip the throttle.
+                AppEvent::ReminderDue(reminder) => {
+                    if alerting {
+                        alerts::deliver(
+                            ctx,
+                            &DesktopAlert {
+                                key: format!("reminder-{}", reminder.id),
+                                title: String::from("Reminder"),
+                                body: reminder.title,
+                            },
+                        );
+                    }
+                    continue;
+                }
+                AppEvent::RateLimited { login, reset_at } => DesktopAlert {
+                    key: format!("{login}/rate-limit"),
+                    title: String::from("Rate limited"),
+                    body: format!(
+                        "Requests for @{login} are paused until {}.",
+                        time::format_local_timestamp(reset_at, "%H:%M")
+                    ),
+                },
+                AppEvent::Alert(alert) => alert,
+            };
+            if alerting && self.alert_throttle.admit(&alert.key, now, window) {
+                alerts::deliver(ctx, &alert);
+            }
+        }
+        if handled_changed
+            && let Some(store) = &self.secret_store
+            && let Err(err) = store.persist_history(&self.history)
         {
-            return;
+            self.storage_warning = Some(format!("Unable to save today's handled count: {err}"));
+        }
+        if inbox_changed {
+            self.track_inbox_zero();
         }
-
-        self.selected_account_login = self
-            .accounts
-            .first()
-            .map(|account| account.profile.login.clone());
     }
 
-    fn ensure_selected_repo(&mut self) {
-        let Some(selected_repo) = self.selected_repo.as_deref() else {
+    fn receive_deep_links(&mut self, ctx: &Context) {
+        if let Some(links) = &self.deep_links
+            && let Some(link) = links.try_iter().last()
+        {
+            self.pending_deep_link = Some(link);
+        }
+        let Some(link) = self.pending_deep_link.take() else {
             return;
         };
 
-        if self.repo_paths.contains_key(selected_repo) {
-            return;
-        }
-
-        self.selected_repo = None;
-    }
-
-    fn poll_jobs(&mut self) {
-        for account in &mut self.accounts {
-            account.poll_job();
-            account.poll_notification_metadata_job();
-            account.poll_action_jobs();
-            account.poll_review_job();
-            account.poll_review_request_jobs();
-        }
-        for repo_view in self.repo_views.values_mut() {
-            repo_view.poll_job();
+        match link {
+            DeepLink::Account { login } => {
+                let Some(account) = self
+                    .accounts
+                    .iter()
+                    .find(|account| account.profile.login.eq_ignore_ascii_case(&login))
+                else {
+                    self.global_error = Some(format!("No tracked account named {login}."));
+                    return;
+                };
+                self.select_account(account.profile.login.clone());
+            }
+            DeepLink::Thread { thread_id, login } => {
+                let candidates = || {
+                    self.accounts.iter().filter(|account| {
+                        login
+                            .as_deref()
+                            .is_none_or(|login| account.profile.login.eq_ignore_ascii_case(login))
+                    })
+                };
+                let found = candidates().position(|account| {
+                    account.inbox.as_ref().is_some_and(|inbox| {
+                        inbox
+                            .notifications
+                            .iter()
+                            .any(|item| item.thread_id == thread_id)
+                    })
+                });
+                let Some(found) = found else {
+                    // The first refresh after launch may still be running.
+                    if candidates().any(|account| account.inbox.is_none()) {
+                        self.pending_deep_link = Some(DeepLink::Thread { thread_id, login });
+                    } else {
+                        self.global_error =
+                            Some(format!("Thread {thread_id} is not in the inbox."));
+                    }
+                    return;
+                };
+                let login = candidates()
+                    .nth(found)
+                    .map(|account| account.profile.login.clone())
+                    .unwrap_or_default();
+                if let Some(account) = self
+                    .accounts
+                    .iter_mut()
+                    .find(|account| account.profile.login == login)
+                {
+                    account.expanded = true;
+                    account.detail_thread_id = Some(thread_id);
+                }
+                self.select_account(login);
+            }
         }
+        ctx.send_viewport_cmd(egui::ViewportCommand::Minimized(false));
+        ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
     }
 
     fn selected_account_index(&self) -> Option<usize> {
@@ -956,19 +3542,79 @@ impl ReminderApp {
             .position(|account| account.profile.login == selected_login)
     }
 
+    /// Pauses polling while the user is away and catches up once they are
+    /// back, muting alerts for that first refresh.
+    fn track_presence(&mut self) {
+        if !self.idle.refresh(self.settings.idle_pause_threshold()) {
+            return;
+        }
+        let local_now = Local::now().naive_local();
+        for account in &mut self.accounts {
+            if account.pending_job.is_none() && account.profile.polls_at(local_now) {
+                account.mute_next_alerts = true;
+                account.start_refresh(&self.settings);
+            }
+        }
+        self.auto_refresh.mark_triggered();
+    }
+
     fn maybe_auto_refresh(&mut self) {
-        if !self.auto_refresh.should_trigger() {
+        if self.idle.is_away() {
             return;
         }
+        let stale_after = self.settings.refresh_interval(
+            Duration::from_secs(AUTO_REFRESH_INTERVAL_SECS),
+            self.power_saving(),
+        );
+        let stale_after = self
+            .replay
+            .as_ref()
+            .map_or(stale_after, |server| server.accelerate(stale_after));
+        self.auto_refresh.set_interval(stale_after);
+        let batch_due = self.auto_refresh.should_trigger();
 
         let mut triggered = false;
-        let stale_after = Duration::from_secs(AUTO_REFRESH_INTERVAL_SECS);
+        let now = Utc::now();
+        let local_now = Local::now().naive_local();
+        let account_count = self.accounts.len();
+        let mut running = self
+            .accounts
+            .iter()
+            .filter(|account| account.pending_job.is_some())
+            .count();
         for account in &mut self.accounts {
-            if account.pending_job.is_some() {
+            if account.pending_job.is_some()
+                || !account.profile.polls_at(local_now)
+                || account.viewer.mismatch.is_some()
+            {
+                continue;
+            }
+            if let Some(until) = account.rate_limit_backoff(RATE_LIMIT_BACKOFF_REMAINING, now) {
+                let wait = (until - now).to_std().unwrap_or_default();
+                self.auto_refresh
+                    .back_off(&account.profile.login, Instant::now() + wait);
+            }
+            if self
+                .auto_refresh
+                .is_backing_off(&account.profile.login, Instant::now())
+            {
                 continue;
             }
-            if account.needs_refresh(stale_after) {
-                account.start_refresh();
+            let first_fetch = account.inbox.is_none() || account.inbox_is_cached;
+            let login = account.profile.login.as_str();
+            if account.needs_refresh(stale_after)
+                && self.auto_refresh.may_start(
+                    login,
+                    running,
+                    account_count,
+                    first_fetch,
+                    Instant::now(),
+                )
+            {
+                self.auto_refresh
+                    .mark_started(login, first_fetch, Instant::now());
+                account.start_refresh(&self.settings);
+                running += 1;
                 triggered = true;
             }
         }
@@ -977,6 +3623,9 @@ impl ReminderApp {
             self.auto_refresh.mark_triggered();
         }
 
+        if !batch_due {
+            return;
+        }
         let Some(selected_repo) = self.selected_repo.clone() else {
             return;
         };
@@ -984,16 +3633,14 @@ impl ReminderApp {
             return;
         };
 
+        let client = self.accounts[selected_idx].client.clone();
         let profile = self.accounts[selected_idx].profile.clone();
         let repo_view = self
             .repo_views
             .entry(selected_repo.clone())
             .or_insert_with(|| RepoState::new(selected_repo));
-        if repo_view.should_refresh_with(
-            &profile.login,
-            Duration::from_secs(AUTO_REFRESH_INTERVAL_SECS),
-        ) {
-            repo_view.start_refresh(profile);
+        if repo_view.should_refresh_with(&profile.login, stale_after) {
+            repo_view.start_refresh(client, profile);
             self.auto_refresh.mark_triggered();
         }
     }
@@ -1003,12 +3650,70 @@ impl ReminderApp {
         self.selected_repo = None;
         self.repo_path_filter_login = self.selected_account_login.clone();
         self.show_all_accounts = false;
+        self.section_focus = None;
     }
 
     fn show_all_repo_paths(&mut self) {
         self.repo_path_filter_login = None;
         self.selected_repo = None;
         self.show_all_accounts = true;
+        self.section_focus = None;
+    }
+
+    /// Clicking a count shows that section across every account; clicking
+    /// the focused count again brings back the full view.
+    fn focus_section(&mut self, kind: SectionKind) {
+        let refocus = self.section_focus != Some(kind);
+        self.show_all_repo_paths();
+        self.section_focus = refocus.then_some(kind);
+    }
+
+    fn render_counts_bar(&mut self, ui: &mut egui::Ui) {
+        let (mut review_requests, mut mentions, mut notifications) = (0, 0, 0);
+        for stats in self
+            .accounts
+            .iter()
+            .filter_map(|account| account.inbox.as_ref())
+            .map(|inbox| section_stats(inbox, &self.rules))
+        {
+            review_requests += stats.review_requests.unseen;
+            mentions += stats.mentions.unseen;
+            notifications += stats.notifications.unseen;
+        }
+
+        let mut clicked = None;
+        let mut toggle_history = false;
+        ui.horizontal(|row| {
+            for (kind, count) in [
+                (SectionKind::ReviewRequests, review_requests),
+                (SectionKind::Mentions, mentions),
+                (SectionKind::Notifications, notifications),
+            ] {
+                let text = egui::RichText::new(format!("{} {count}", kind.title()));
+                let text = if count > 0 { text.strong() } else { text };
+                if row
+                    .selectable_label(self.section_focus == Some(kind), text)
+                    .on_hover_text("Unseen across all accounts. Click to list them together.")
+                    .clicked()
+                {
+                    clicked = Some(kind);
+                }
+            }
+            row.separator();
+            toggle_history = row
+                .selectable_label(self.history_view.is_some(), "History")
+                .on_hover_text("Every thread seen so far, including ones marked done.")
+                .clicked();
+        });
+        if let Some(kind) = clicked {
+            self.focus_section(kind);
+        }
+        if toggle_history {
+            self.history_view = match self.history_view {
+                Some(_) => None,
+                None => Some(HistoryView::default()),
+            };
+        }
     }
 
     fn select_repo(&mut self, repo: String) {
@@ -1032,6 +3737,7 @@ impl ReminderApp {
             return;
         };
 
+        let client = self.accounts[selected_idx].client.clone();
         let profile = self.accounts[selected_idx].profile.clone();
         let repo_view = self
             .repo_views
@@ -1041,12 +3747,123 @@ impl ReminderApp {
             &profile.login,
             Duration::from_secs(AUTO_REFRESH_INTERVAL_SECS),
         ) {
-            repo_view.start_refresh(profile);
+            repo_view.start_refresh(client, profile);
+        }
+    }
+
+    fn render_device_flow_controls(&mut self, ui: &mut egui::Ui) {
+        let Some(job) = &self.device_flow else {
+            if ui
+                .add_enabled(
+                    oauth_client_id().is_some(),
+                    egui::Button::new("Sign in with GitHub"),
+                )
+                .on_disabled_hover_text(
+                    "Set REMINDER_OAUTH_CLIENT_ID to an OAuth app client id to enable browser sign-in.",
+                )
+                .clicked()
+            {
+                self.start_device_flow();
+            }
+            return;
+        };
+
+        match &job.code {
+            Some(code) => {
+                ui.label("Enter this code on GitHub to finish signing in:");
+                ui.horizontal(|row| {
+                    row.monospace(egui::RichText::new(&code.user_code).strong());
+                    if row.small_button("Copy").clicked() {
+                        row.ctx().copy_text(code.user_code.clone());
+                    }
+                });
+                if ui.link(&code.verification_uri).clicked() {
+                    ui.ctx()
+                        .open_url(egui::OpenUrl::new_tab(code.verification_uri.clone()));
+                }
+            }
+            None => {
+                ui.label("Contacting GitHub...");
+            }
+        }
+        if ui.small_button("Cancel sign-in").clicked() {
+            self.device_flow = None;
         }
     }
 
     fn render_side_panel(&mut self, ui: &mut egui::Ui) {
-        ui.heading("Accounts");
+        ui.horizontal(|row| {
+            row.heading("Accounts");
+            if row.small_button("Preferences").clicked() {
+                self.open_settings_editor();
+            }
+            if row
+                .small_button("Export report")
+                .on_hover_text("Save the current sections as an HTML page to share.")
+                .clicked()
+            {
+                self.export_report(row.ctx());
+            }
+            if row.small_button("Reminder…").clicked() {
+                self.reminder_editor = Some(ReminderEditor::new(
+                    String::new(),
+                    String::new(),
+                    None,
+                    None,
+                ));
+            }
+            egui::widgets::global_theme_preference_switch(row);
+        });
+        if let Some(profile) = &self.profile {
+            ui.small(format!("Profile: {profile}"));
+        }
+        let handled = self.history.handled.on(Local::now().date_naive());
+        if handled.total() > 0 {
+            ui.small(format!("Handled today: {}", handled.total()))
+                .on_hover_text(format!(
+                    "{} marked read, {} marked done",
+                    handled.read, handled.done
+                ));
+        }
+        if self.settings.low_data_mode {
+            ui.small(
+                egui::RichText::new("Low-data mode: slower refresh, unread only")
+                    .color(ui.visuals().warn_fg_color),
+            );
+        }
+        ui.checkbox(&mut self.ui_state.do_not_disturb, "Do not disturb")
+            .on_hover_text(
+                "Hold back desktop alerts and the title and tray badges; refreshing continues.",
+            );
+        if !self.ui_state.do_not_disturb
+            && let Some(hours) = self.settings.alert_hours
+            && self.is_quiet()
+        {
+            ui.small(
+                egui::RichText::new(format!(
+                    "Outside alert hours ({}): alerts muted",
+                    hours.label()
+                ))
+                .color(ui.visuals().weak_text_color()),
+            );
+        }
+        if self.idle.is_away() {
+            ui.small(
+                egui::RichText::new("Away: polling and alerts paused")
+                    .color(ui.visuals().weak_text_color()),
+            );
+        }
+        if self.power_saving()
+            && let Some(status) = self.power.status()
+        {
+            ui.small(
+                egui::RichText::new(format!(
+                    "Power saving ({}% battery): slower refresh, no PR details",
+                    status.percent
+                ))
+                .color(ui.visuals().warn_fg_color),
+            );
+        }
         ui.separator();
 
         if let Some(warning) = &self.storage_warning {
@@ -1054,28 +3871,64 @@ impl ReminderApp {
             ui.separator();
         }
 
-        ui.label("GitHub username");
+        ui.horizontal(|row| {
+            row.label("Provider");
+            for forge in Forge::ALL {
+                row.selectable_value(&mut self.account_form.forge, forge, forge.label());
+            }
+        });
+        let forge = self.account_form.forge;
+        let (host_label, host_hint) = match forge {
+            Forge::GitHub => ("GitHub Enterprise host (optional)", "github.mycorp.com"),
+            Forge::GitLab => ("Self-hosted GitLab host (optional)", "gitlab.mycorp.com"),
+            Forge::Gitea => ("Gitea host (optional)", "git.mycorp.com"),
+        };
+        ui.label(host_label);
+        ui.add(egui::TextEdit::singleline(&mut self.account_form.api_host).hint_text(host_hint));
+
+        if forge == Forge::GitHub {
+            self.render_device_flow_controls(ui);
+            ui.add_space(4.0);
+            ui.small("Or add a personal access token:");
+        }
+        ui.label(format!("{} username", forge.label()));
         ui.text_edit_singleline(&mut self.account_form.login);
 
         ui.label("Personal access token");
         ui.add(
             egui::TextEdit::singleline(&mut self.account_form.token)
                 .password(true)
-                .hint_text("ghp_..."),
+                .hint_text(match forge {
+                    Forge::GitHub => "ghp_...",
+                    Forge::GitLab => "glpat-...",
+                    Forge::Gitea => "",
+                }),
         );
 
-        let add_enabled = !self.account_form.login.trim().is_empty()
+        let checking = self.account_form.pending_check.is_some();
+        let add_enabled = !checking
+            && !self.account_form.login.trim().is_empty()
             && !self.account_form.token.trim().is_empty();
-        if ui
-            .add_enabled(add_enabled, egui::Button::new("Add account"))
-            .clicked()
-        {
-            self.add_account();
-        }
+        ui.horizontal(|row| {
+            if row
+                .add_enabled(add_enabled, egui::Button::new("Add account"))
+                .clicked()
+            {
+                self.add_account();
+            }
+            if checking {
+                row.spinner();
+                row.label("Checking token...");
+            }
+        });
 
         if let Some(error) = &self.account_form.form_error {
             ui.colored_label(ui.visuals().error_fg_color, error);
         }
+        if self.account_form.duplicate.is_some() && ui.button("Update its token instead").clicked()
+        {
+            self.accept_token_update_offer();
+        }
 
         ui.separator();
         ui.horizontal(|row| {
@@ -1094,7 +3947,7 @@ impl ReminderApp {
             let mut remove_login = None;
             let mut settings_login = None;
             for (idx, account) in self.accounts.iter_mut().enumerate() {
-                let overview = account_overview(account);
+                let overview = account_overview(account, &self.rules);
                 let pending = account.pending_job.is_some();
                 let has_error = account.last_error.is_some();
                 let is_selected =
@@ -1147,7 +4000,7 @@ impl ReminderApp {
             if let Some(idx) = refresh_idx
                 && let Some(account) = self.accounts.get_mut(idx)
             {
-                account.start_refresh();
+                account.start_refresh(&self.settings);
                 self.auto_refresh.mark_triggered();
             }
         }
@@ -1294,6 +4147,7 @@ impl ReminderApp {
 
             let repo_key = selected_repo.clone();
             let repo_paths = &self.repo_paths;
+            let transliterate_search = self.settings.transliterate_search;
             let account = &mut self.accounts[selected_idx];
             let custom_review_command =
                 review_prompt_command_available(&account.profile.review_settings);
@@ -1310,12 +4164,26 @@ impl ReminderApp {
                         repo_state,
                         repo_paths,
                         custom_review_command,
+                        transliterate_search,
                     );
                 });
             });
             return;
         }
 
+        if self.settings.release_radar.search_query().is_some() {
+            render_release_radar(
+                ui,
+                self.settings.release_radar.label.trim(),
+                &self.release_radar,
+            );
+        }
+        render_plugin_sections(ui, &self.plugin_sections, self.settings.timestamp_style);
+        let reminder_actions = render_due_section(ui, &self.reminders, Utc::now());
+        self.apply_reminder_actions(ui.ctx(), reminder_actions);
+        render_stale_branches(ui, &mut self.accounts);
+
+        let inbox_zero_streak = self.history.inbox_zero.current(Local::now().date_naive());
         if self.show_all_accounts {
             if self.accounts.is_empty() {
                 ui.centered_and_justified(|center| {
@@ -1328,28 +4196,84 @@ impl ReminderApp {
 
             egui::ScrollArea::vertical().show(ui, |area| {
                 area.push_id("all-accounts-dashboard", |ui| {
+                    let mut clear_focus = false;
                     ui.group(|group| {
                         group.heading("Account: All");
                         group.small(format!(
                             "Showing notifications from {} tracked account(s).",
                             self.accounts.len()
                         ));
+                        group.horizontal(|row| {
+                            row.selectable_value(&mut self.combined_inbox, false, "Cards");
+                            row.selectable_value(&mut self.combined_inbox, true, "Combined")
+                                .on_hover_text(
+                                    "One table of every account's notifications, newest first.",
+                                );
+                        });
+                        if let Some(kind) = self.section_focus {
+                            group.horizontal(|row| {
+                                row.label(format!("Only {}", kind.title().to_lowercase()));
+                                clear_focus = row.small_button("Show all sections").clicked();
+                            });
+                        }
                     });
+                    if clear_focus {
+                        self.section_focus = None;
+                    }
                     ui.add_space(12.0);
-
-                    for account in &mut self.accounts {
-                        account.clear_new_notifications();
-                        let account_id = account.profile.login.clone();
-                        let custom_review_command =
-                            review_prompt_command_available(&account.profile.review_settings);
-                        ui.push_id(account_id, |ui| {
-                            render_account_card(
-                                ui,
-                                account,
-                                &self.repo_paths,
-                                custom_review_command,
-                            );
-                        });
+
+                    if self.combined_inbox {
+                        render_combined_inbox(
+                            ui,
+                            &mut self.accounts,
+                            &mut self.combined_chips,
+                            &self.settings,
+                            &self.rules,
+                        );
+                        return;
+                    }
+                    for (group, indices) in account_groups(&self.accounts) {
+                        let Some(group) = group else {
+                            for idx in indices {
+                                render_dashboard_account(
+                                    ui,
+                                    &mut self.accounts[idx],
+                                    &self.repo_paths,
+                                    &self.settings,
+                                    &self.rules,
+                                    inbox_zero_streak,
+                                    self.section_focus,
+                                );
+                            }
+                            continue;
+                        };
+                        let (unseen, updated) = indices
+                            .iter()
+                            .filter_map(|idx| account_overview(&self.accounts[*idx], &self.rules))
+                            .fold((0, 0), |(unseen, updated), overview| {
+                                (unseen + overview.unseen, updated + overview.updated)
+                            });
+                        let heading = egui::RichText::new(format!(
+                            "{group} · {} account(s) · {unseen} unseen · {updated} updated",
+                            indices.len()
+                        ))
+                        .strong();
+                        egui::CollapsingHeader::new(heading)
+                            .id_salt(("account-group", &group))
+                            .default_open(true)
+                            .show(ui, |ui| {
+                                for idx in indices {
+                                    render_dashboard_account(
+                                        ui,
+                                        &mut self.accounts[idx],
+                                        &self.repo_paths,
+                                        &self.settings,
+                                        &self.rules,
+                                        inbox_zero_streak,
+                                        self.section_focus,
+                                    );
+                                }
+                            });
                     }
                 });
             });
@@ -1384,7 +4308,16 @@ impl ReminderApp {
             let custom_review_command =
                 review_prompt_command_available(&account.profile.review_settings);
             area.push_id(account_id, |ui| {
-                render_account_card(ui, account, &self.repo_paths, custom_review_command);
+                render_account_card(
+                    ui,
+                    account,
+                    &self.repo_paths,
+                    custom_review_command,
+                    &self.settings,
+                    &self.rules,
+                    inbox_zero_streak,
+                    None,
+                );
             });
         });
     }
@@ -1399,27 +4332,91 @@ impl ReminderApp {
 
 impl App for ReminderApp {
     fn update(&mut self, ctx: &Context, _frame: &mut Frame) {
+        self.ui_state.theme = ctx.options(|options| options.theme_preference);
+        if self.applied_accent != self.settings.accent_color {
+            apply_accent(ctx, self.settings.accent_color);
+            self.applied_accent = self.settings.accent_color;
+        }
+        let ui_scale = (
+            self.settings.ui_scale_percent,
+            self.settings.text_size_percent,
+        );
+        if self.applied_ui_scale != Some(ui_scale) {
+            apply_ui_scale(ctx, ui_scale.0, ui_scale.1);
+            self.applied_ui_scale = Some(ui_scale);
+        }
+        if self.applied_rules != self.settings.rules {
+            self.applied_rules = self.settings.rules.clone();
+            self.rules = RuleSet::compile(&self.applied_rules);
+        }
+        if self.applied_font_path != self.settings.custom_font_path {
+            self.applied_font_path = self.settings.custom_font_path.clone();
+            let path = self.applied_font_path.trim();
+            let custom = (!path.is_empty()).then(|| Path::new(path));
+            if let Err(err) = install_international_fonts(ctx, custom) {
+                self.global_error = Some(format!("Unable to load the font {path}: {err}"));
+            }
+        }
         self.poll_jobs();
+        self.avatars.forget_evicted(ctx);
+        if self.last_compaction.elapsed() >= STORE_COMPACTION_INTERVAL {
+            self.compact_stores();
+        }
+        self.run_scheduled_backup();
+        self.poll_sync();
+        self.run_sync();
+        self.persist_inbox_caches();
+        self.receive_deep_links(ctx);
+        self.check_reminders();
+        self.handle_events(ctx);
+        self.handle_tray_commands(ctx);
+        self.intercept_close(ctx);
+        self.open_reminder_requests();
+        self.open_preview_requests();
+        self.sync_window_title(ctx);
         self.ensure_selected_account();
         self.ensure_selected_repo();
+        self.track_presence();
         self.maybe_auto_refresh();
 
+        egui::TopBottomPanel::top("counts_bar").show(ctx, |ui| {
+            self.render_counts_bar(ui);
+        });
+
         let accounts_panel_width = responsive_accounts_panel_width(ctx.available_rect().width());
 
-        egui::SidePanel::left("accounts_panel")
+        let right_to_left = uses_right_to_left(self.settings.layout_direction);
+        egui::SidePanel::new(accounts_panel_side(right_to_left), "accounts_panel")
             .exact_width(accounts_panel_width)
             .show(ctx, |ui| {
-                egui::ScrollArea::vertical().show(ui, |ui| self.render_side_panel(ui));
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    ui.with_layout(content_layout(right_to_left), |ui| {
+                        self.render_side_panel(ui)
+                    });
+                });
             });
+        self.render_preview_panel(ctx, right_to_left);
 
         egui::CentralPanel::default().show(ctx, |ui| {
-            self.render_dashboard(ui);
+            ui.with_layout(content_layout(right_to_left), |ui| {
+                self.render_dashboard(ui);
+            });
         });
+        self.start_requested_refreshes(ctx);
 
         self.render_account_delete_confirmation_window(ctx);
         self.render_review_settings_window(ctx);
         self.render_repo_path_account_editor_window(ctx);
         self.render_review_request_windows(ctx);
+        self.render_settings_window(ctx);
+        self.render_unlock_window(ctx);
+        self.render_notification_detail_windows(ctx);
+        self.render_exit_summary_window(ctx);
+        self.render_bulk_action_confirmation_windows(ctx);
+        self.render_reminder_editor_window(ctx);
+        render_history_window(ctx, &mut self.history_view, &self.accounts, &self.settings);
+        self.render_whats_new_window(ctx);
+        self.render_token_update_window(ctx);
 
         for account in &mut self.accounts {
             let mut review_window_actions = Vec::new();
@@ -1447,6 +4444,21 @@ impl App for ReminderApp {
         };
         ctx.request_repaint_after(Duration::from_millis(repaint_ms));
     }
+
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        self.capture_ui_state();
+        self.ui_state.save(storage);
+    }
+
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        for account in &mut self.accounts {
+            account.cancel_all_jobs();
+        }
+        for repo_view in self.repo_views.values_mut() {
+            repo_view.pending_job = None;
+            repo_view.pending_batch_approve = None;
+        }
+    }
 }
 
 // -----------------------------------------------------------------------------
@@ -1477,29 +4489,106 @@ enum AccountAction {
     ToggleReviewWindow(String),
     Seen(String),
     Read(String),
+    Open {
+        thread_id: String,
+        url: String,
+    },
+    ShowDetails(String),
+    /// Opens the thread in the preview pane.
+    Preview(String),
+    Remind(String),
+    Unsubscribe(String),
+    Bulk(BulkAction),
+    ToggleRepoGroup(String),
+    SetStage {
+        thread_id: String,
+        stage: ThreadStage,
+    },
 }
 
-#[derive(Clone, Copy, Hash, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
 enum SectionKind {
     Inbox,
     ReviewRequests,
     Mentions,
     Notifications,
+    /// Discussion threads, a subset of `Notifications`.
+    Discussions,
+    /// Search-backed: open issues assigned to the user.
+    Assigned,
+    /// Search-backed: open pull requests the user authored.
+    AuthoredPullRequests,
+}
+
+impl SectionKind {
+    fn title(self) -> &'static str {
+        match self {
+            Self::Inbox => "Inbox",
+            Self::ReviewRequests => "Review requests",
+            Self::Mentions => "Mentions",
+            Self::Notifications => "Notifications",
+            Self::Discussions => "Discussions",
+            Self::Assigned => "Assigned to me",
+            Self::AuthoredPullRequests => "My open PRs",
+        }
+    }
+
+    /// Search-backed sections hold no notification threads.
+    fn contains(self, item: &NotificationItem) -> bool {
+        match self {
+            Self::Inbox => true,
+            Self::ReviewRequests => is_review_request(item),
+            Self::Mentions => is_mention(item),
+            Self::Notifications => is_other_notification(item),
+            Self::Discussions => is_discussion(item),
+            Self::Assigned | Self::AuthoredPullRequests => false,
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum AccountViewMode {
     Inbox,
     Grouped,
+    /// One column per personal stage.
+    Board,
 }
 
 #[derive(Default)]
 struct AccountForm {
     login: String,
     token: String,
+    forge: Forge,
+    api_host: String,
+    pending_check: Option<PendingTokenCheck>,
+    form_error: Option<String>,
+    /// Storage key of the tracked account the entered credentials
+    /// duplicate, and the token that can replace its current one.
+    duplicate: Option<(String, String)>,
+}
+
+/// Replaces the token of a tracked account after GitHub rejected it.
+struct TokenUpdateForm {
+    login: String,
+    /// See [`GitHubAccount::storage_key`].
+    key: String,
+    token: String,
+    pending_check: Option<PendingTokenCheck>,
     form_error: Option<String>,
 }
 
+impl TokenUpdateForm {
+    fn new(profile: &GitHubAccount) -> Self {
+        Self {
+            login: profile.login.clone(),
+            key: profile.storage_key(),
+            token: String::new(),
+            pending_check: None,
+            form_error: None,
+        }
+    }
+}
+
 #[derive(Default)]
 struct RepoPathForm {
     repo: String,
@@ -1527,6 +4616,12 @@ struct AccountReviewSettingsEditor {
     additional_args_text: String,
     review_prompt_md_path_text: String,
     pr_description_md_path_text: String,
+    hide_bot_notifications: Option<bool>,
+    working_hours: Option<WorkingHours>,
+    token_command_text: String,
+    group_text: String,
+    section_alerts: Option<SectionAlertThresholds>,
+    inbox_backend: InboxBackend,
     form_error: Option<String>,
 }
 
@@ -1541,6 +4636,496 @@ struct AccountDeleteConfirmation {
     typed_login: String,
 }
 
+struct AppSettingsEditor {
+    draft: AppSettings,
+    form_error: Option<String>,
+    passphrase: String,
+    passphrase_confirmation: String,
+    tab: PreferencesTab,
+    /// Saved backups, newest first, read when the window opens.
+    backups: Vec<Backup>,
+    /// Backup whose restore awaits confirmation.
+    restore_candidate: Option<String>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum PreferencesTab {
+    General,
+    Rules,
+    Sections,
+    Labs,
+}
+
+#[derive(Default)]
+struct UnlockForm {
+    passphrase: String,
+    error: Option<String>,
+}
+
+/// Accounts without a group come first, then one entry per group in
+/// alphabetical order. Group names match ignoring surrounding whitespace.
+fn account_groups(accounts: &[AccountState]) -> Vec<(Option<String>, Vec<usize>)> {
+    let mut ungrouped = Vec::new();
+    let mut groups = BTreeMap::<String, Vec<usize>>::new();
+    for (idx, account) in accounts.iter().enumerate() {
+        match account
+            .profile
+            .group
+            .as_deref()
+            .map(str::trim)
+            .filter(|group| !group.is_empty())
+        {
+            Some(group) => groups.entry(group.to_owned()).or_default().push(idx),
+            None => ungrouped.push(idx),
+        }
+    }
+    let mut ordered = Vec::with_capacity(groups.len() + 1);
+    if !ungrouped.is_empty() {
+        ordered.push((None, ungrouped));
+    }
+    ordered.extend(
+        groups
+            .into_iter()
+            .map(|(group, indices)| (Some(group), indices)),
+    );
+    ordered
+}
+
+fn render_dashboard_account(
+    ui: &mut egui::Ui,
+    account: &mut AccountState,
+    repo_paths: &BTreeMap<String, String>,
+    settings: &AppSettings,
+    rules: &RuleSet,
+    inbox_zero_streak: u32,
+    section_focus: Option<SectionKind>,
+) {
+    account.clear_new_notifications();
+    let account_id = account.profile.login.clone();
+    let custom_review_command = review_prompt_command_available(&account.profile.review_settings);
+    ui.push_id(account_id, |ui| {
+        render_account_card(
+            ui,
+            account,
+            repo_paths,
+            custom_review_command,
+            settings,
+            rules,
+            inbox_zero_streak,
+            section_focus,
+        );
+    });
+}
+
+fn render_rules_settings(ui: &mut egui::Ui, draft: &mut AppSettings) {
+    ui.strong("Rules");
+    ui.small(
+        "Mute, silence or highlight threads by repository glob (acme/* or payments-*), exact reason, title regex or provider. Empty fields match anything.",
+    );
+    ui.add_space(8.0);
+    let mut remove = None;
+    for (index, rule) in draft.rules.iter_mut().enumerate() {
+        ui.group(|group| {
+            egui::Grid::new(("notification-rule", index))
+                .num_columns(2)
+                .show(group, |grid| {
+                    grid.label("Repository");
+                    grid.add(
+                        egui::TextEdit::singleline(&mut rule.repo).hint_text("acme/noisy-bot"),
+                    );
+                    grid.end_row();
+                    grid.label("Reason");
+                    grid.add(egui::TextEdit::singleline(&mut rule.reason).hint_text("ci_activity"));
+                    grid.end_row();
+                    grid.label("Title");
+                    grid.add(
+                        egui::TextEdit::singleline(&mut rule.title).hint_text("^chore\\(deps\\)"),
+                    );
+                    grid.end_row();
+                    grid.label("Provider");
+                    egui::ComboBox::from_id_salt(("notification-rule-provider", index))
+                        .selected_text(rule.provider.map_or("Any", Forge::label))
+                        .show_ui(grid, |menu| {
+                            menu.selectable_value(&mut rule.provider, None, "Any");
+                            for forge in Forge::ALL {
+                                menu.selectable_value(
+                                    &mut rule.provider,
+                                    Some(forge),
+                                    forge.label(),
+                                );
+                            }
+                        });
+                    grid.end_row();
+                    grid.label("Action");
+                    grid.horizontal(|row| {
+                        egui::ComboBox::from_id_salt(("notification-rule-action", index))
+                            .selected_text(rule.action.label())
+                            .show_ui(row, |menu| {
+                                for action in RuleAction::ALL {
+                                    menu.selectable_value(&mut rule.action, action, action.label());
+                                }
+                            });
+                        if row.small_button("Remove").clicked() {
+                            remove = Some(index);
+                        }
+                    });
+                    grid.end_row();
+                });
+            if let Some(problem) = rule_problem(rule) {
+                group.colored_label(group.visuals().warn_fg_color, problem);
+            }
+        });
+    }
+    if let Some(index) = remove {
+        draft.rules.remove(index);
+    }
+    if ui.button("Add rule").clicked() {
+        draft.rules.push(NotificationRule::default());
+    }
+}
+
+fn render_saved_search_settings(ui: &mut egui::Ui, draft: &mut AppSettings) {
+    ui.strong("Sections");
+    ui.small(
+        "Extra sections on every account. A notification filter combines repo:, reason:, type:, provider: and is:unread/read/pr/issue terms with title words (prefix - to exclude); a GitHub search runs its query on each refresh.",
+    );
+    ui.add_space(8.0);
+    let mut remove = None;
+    for (index, search) in draft.saved_searches.iter_mut().enumerate() {
+        ui.group(|group| {
+            egui::Grid::new(("saved-search", index))
+                .num_columns(2)
+                .show(group, |grid| {
+                    grid.label("Name");
+                    grid.add(
+                        egui::TextEdit::singleline(&mut search.name).hint_text("Infra mentions"),
+                    );
+                    grid.end_row();
+                    grid.label("Source");
+                    grid.horizontal(|row| {
+                        egui::ComboBox::from_id_salt(("saved-search-kind", index))
+                            .selected_text(search.kind.label())
+                            .show_ui(row, |menu| {
+                                for kind in SavedSearchKind::ALL {
+                                    menu.selectable_value(&mut search.kind, kind, kind.label());
+                                }
+                            });
+                        if row.small_button("Remove").clicked() {
+                            remove = Some(index);
+                        }
+                    });
+                    grid.end_row();
+                    grid.label("Query");
+                    let hint = match search.kind {
+                        SavedSearchKind::Filter => "repo:acme/infra-* reason:mention",
+                        SavedSearchKind::GitHubSearch => "is:pr is:open label:urgent org:acme",
+                    };
+                    grid.add(egui::TextEdit::singleline(&mut search.query).hint_text(hint));
+                    grid.end_row();
+                });
+            if let Some(problem) = saved_search_problem(search) {
+                group.colored_label(group.visuals().warn_fg_color, problem);
+            }
+        });
+    }
+    if let Some(index) = remove {
+        draft.saved_searches.remove(index);
+    }
+    if ui.button("Add section").clicked() {
+        draft.saved_searches.push(SavedSearch::default());
+    }
+}
+
+fn render_labs_settings(ui: &mut egui::Ui, draft: &mut AppSettings) {
+    ui.strong("Labs");
+    ui.small("Experimental features. They may change or disappear between releases.");
+    ui.add_space(8.0);
+    for experiment in Experiment::ALL {
+        let mut enabled = draft.experiment_enabled(experiment);
+        if ui
+            .checkbox(&mut enabled, experiment.label())
+            .on_hover_text(experiment.description())
+            .changed()
+        {
+            draft.set_experiment(experiment, enabled);
+        }
+    }
+}
+
+fn render_release_radar_settings(
+    ui: &mut egui::Ui,
+    radar: &mut ReleaseRadar,
+    github_logins: &[String],
+) {
+    ui.small(
+        "Pins open issues and pull requests with this label anywhere in the organization in a red banner above the dashboard, checked every 5 minutes. Leave a field empty to turn it off.",
+    );
+    egui::Grid::new("release-radar")
+        .num_columns(2)
+        .show(ui, |grid| {
+            grid.label("Organization");
+            grid.add(egui::TextEdit::singleline(&mut radar.org).hint_text("acme"));
+            grid.end_row();
+            grid.label("Label");
+            grid.add(egui::TextEdit::singleline(&mut radar.label).hint_text("release-blocker"));
+            grid.end_row();
+            grid.label("Search as");
+            egui::ComboBox::from_id_salt("release-radar-account")
+                .selected_text(if radar.account.is_empty() {
+                    "First GitHub account"
+                } else {
+                    radar.account.as_str()
+                })
+                .show_ui(grid, |menu| {
+                    menu.selectable_value(
+                        &mut radar.account,
+                        String::new(),
+                        "First GitHub account",
+                    );
+                    for login in github_logins {
+                        menu.selectable_value(&mut radar.account, login.clone(), login);
+                    }
+                });
+            grid.end_row();
+        });
+}
+
+fn render_sync_settings(
+    ui: &mut egui::Ui,
+    sync: &mut SyncSettings,
+    github_logins: &[String],
+    status: &str,
+    sync_requested: &mut bool,
+) {
+    ui.small(
+        "Shares rules, repository tiers, auto-snooze hours, done threads and filter chips with your other machines. Tokens are never synced.",
+    );
+    egui::ComboBox::from_id_salt("sync-backend")
+        .selected_text(sync.backend.label())
+        .show_ui(ui, |menu| {
+            for backend in SyncBackend::ALL {
+                menu.selectable_value(&mut sync.backend, backend, backend.label());
+            }
+        });
+    match sync.backend {
+        SyncBackend::Off => return,
+        SyncBackend::Gist => {
+            egui::ComboBox::from_id_salt("sync-account")
+                .selected_text(if sync.account.is_empty() {
+                    "Pick an account"
+                } else {
+                    sync.account.as_str()
+                })
+                .show_ui(ui, |menu| {
+                    for login in github_logins {
+                        menu.selectable_value(&mut sync.account, login.clone(), login);
+                    }
+                });
+            ui.horizontal(|row| {
+                row.label("Gist id");
+                row.add(
+                    egui::TextEdit::singleline(&mut sync.gist_id)
+                        .hint_text("created on first sync")
+                        .desired_width(220.0),
+                );
+            })
+            .response
+            .on_hover_text(
+                "Use the same gist id on every machine. The token needs the gist scope.",
+            );
+        }
+        SyncBackend::GitRepo => {
+            ui.horizontal(|row| {
+                row.label("Local clone");
+                row.add(
+                    egui::TextEdit::singleline(&mut sync.repo_path)
+                        .hint_text("/path/to/clone")
+                        .desired_width(220.0),
+                );
+            })
+            .response
+            .on_hover_text("A checkout with an upstream branch; each sync pulls, commits and pushes reminder-sync.json.");
+        }
+    }
+    ui.horizontal(|row| {
+        if row.button("Sync now").clicked() {
+            *sync_requested = true;
+        }
+        row.small(status);
+    });
+    ui.small("Save first; syncing uses the saved settings.");
+}
+
+fn render_network_settings(ui: &mut egui::Ui, network: &mut NetworkSettings) {
+    egui::Grid::new("network-settings")
+        .num_columns(2)
+        .show(ui, |grid| {
+            grid.label("Proxy");
+            grid.add(
+                egui::TextEdit::singleline(&mut network.proxy)
+                    .hint_text("http://proxy.corp:3128 or socks5://…"),
+            );
+            grid.end_row();
+            grid.label("CA bundle");
+            grid.add(
+                egui::TextEdit::singleline(&mut network.ca_bundle)
+                    .hint_text("/etc/ssl/corp-root.pem"),
+            );
+            grid.end_row();
+            for (label, value) in [
+                ("Connect timeout", &mut network.connect_timeout_secs),
+                ("Read timeout", &mut network.read_timeout_secs),
+                ("TCP keep-alive", &mut network.keep_alive_secs),
+                ("Idle connections kept", &mut network.pool_idle_timeout_secs),
+            ] {
+                grid.label(label);
+                grid.add(egui::DragValue::new(value).range(0..=600).suffix(" s"));
+                grid.end_row();
+            }
+        });
+    ui.small("0 turns a timeout or keep-alive off.");
+    ui.small("Without a proxy, HTTPS_PROXY, HTTP_PROXY, ALL_PROXY and NO_PROXY are honored.");
+    ui.checkbox(
+        &mut network.skip_tls_verification,
+        "Skip TLS certificate verification",
+    );
+    if network.skip_tls_verification {
+        ui.colored_label(
+            ui.visuals().warn_fg_color,
+            "Anyone on the network path can read your tokens. Prefer a CA bundle.",
+        );
+    }
+    ui.small("Applies to new connections once saved.");
+}
+
+fn render_backup_settings(
+    ui: &mut egui::Ui,
+    editor: &mut AppSettingsEditor,
+    backup_requested: &mut bool,
+    restore_requested: &mut Option<String>,
+) {
+    ui.small(
+        "Copies of settings, rules, history, reminders and done threads are kept under backups/ next to accounts.json; the newest 10 are kept. Accounts and tokens are not included.",
+    );
+    ui.horizontal(|row| {
+        row.label("Back up every");
+        row.add(
+            egui::DragValue::new(&mut editor.draft.backup_interval_hours)
+                .range(0..=720)
+                .suffix(" h"),
+        );
+        row.label("(0 = never)");
+    });
+    if ui.button("Back up now").clicked() {
+        *backup_requested = true;
+    }
+    if editor.backups.is_empty() {
+        ui.weak("No backups yet.");
+    }
+    let mut candidate = editor.restore_candidate.take();
+    for backup in &editor.backups {
+        ui.horizontal(|row| {
+            row.label(backup.created_at.format("%Y-%m-%d %H:%M:%S").to_string());
+            if candidate.as_deref() == Some(backup.name.as_str()) {
+                if row.button("Confirm restore").clicked() {
+                    *restore_requested = Some(backup.name.clone());
+                }
+                if row.button("Cancel").clicked() {
+                    candidate = None;
+                }
+            } else if row.button("Restore…").clicked() {
+                candidate = Some(backup.name.clone());
+            }
+        });
+    }
+    editor.restore_candidate = candidate;
+    if editor.restore_candidate.is_some() {
+        ui.small("Restoring replaces the current state and reloads every account; the current state is backed up first.");
+    }
+}
+
+fn double_click_action_combo(ui: &mut egui::Ui, id_salt: &str, action: &mut DoubleClickAction) {
+    egui::ComboBox::from_id_salt(("double-click-action", id_salt))
+        .selected_text(action.label())
+        .show_ui(ui, |menu| {
+            for candidate in DoubleClickAction::ALL {
+                menu.selectable_value(action, candidate, candidate.label());
+            }
+        });
+}
+
+fn hide_bot_notifications_combo(ui: &mut egui::Ui, value: &mut Option<bool>) {
+    let label = |value: Option<bool>| match value {
+        None => "Use global preference",
+        Some(false) => "Show",
+        Some(true) => "Hide",
+    };
+    egui::ComboBox::from_id_salt("hide-bot-notifications")
+        .selected_text(label(*value))
+        .show_ui(ui, |menu| {
+            for candidate in [None, Some(false), Some(true)] {
+                menu.selectable_value(value, candidate, label(candidate));
+            }
+        });
+}
+
+/// A checkbox that turns a custom sRGB color on, with its picker beside it.
+fn optional_color_picker(ui: &mut egui::Ui, value: &mut Option<[u8; 3]>, label: &str) {
+    ui.horizontal(|row| {
+        let mut enabled = value.is_some();
+        if row.checkbox(&mut enabled, label).changed() {
+            *value = enabled.then_some([230, 159, 0]);
+        }
+        if let Some(color) = value {
+            row.color_edit_button_srgb(color);
+        }
+    });
+}
+
+fn working_hours_editor(ui: &mut egui::Ui, value: &mut Option<WorkingHours>, label: &str) {
+    let mut enabled = value.is_some();
+    if ui.checkbox(&mut enabled, label).changed() {
+        *value = enabled.then(WorkingHours::default);
+    }
+    let Some(working_hours) = value else {
+        return;
+    };
+    ui.horizontal(|row| {
+        row.label("From");
+        row.add(egui::DragValue::new(&mut working_hours.start_hour).range(0..=23));
+        row.label("to");
+        row.add(egui::DragValue::new(&mut working_hours.end_hour).range(0..=24));
+        row.checkbox(&mut working_hours.weekdays_only, "Mon–Fri only");
+    });
+}
+
+/// Rows of "alert when unread <section> exceed N"; a section left unchecked
+/// alerts per thread instead.
+fn section_alerts_editor(ui: &mut egui::Ui, thresholds: &mut SectionAlertThresholds) {
+    for (kind, threshold) in [
+        (SectionKind::ReviewRequests, &mut thresholds.review_requests),
+        (SectionKind::Mentions, &mut thresholds.mentions),
+        (SectionKind::Notifications, &mut thresholds.notifications),
+    ] {
+        ui.horizontal(|row| {
+            let mut enabled = threshold.is_some();
+            if row
+                .checkbox(
+                    &mut enabled,
+                    format!("Alert when unread {}", kind.title().to_lowercase()),
+                )
+                .changed()
+            {
+                *threshold = enabled.then_some(0);
+            }
+            if let Some(value) = threshold {
+                row.label("exceed");
+                row.add(egui::DragValue::new(value).range(0..=500));
+            }
+        });
+    }
+}
+
 fn format_review_env_vars(settings: &ReviewCommandSettings) -> String {
     settings
         .env_vars
@@ -1620,10 +5205,10 @@ impl CurrentReviewerSummary {
         }
     }
 
-    fn color(self, visuals: &egui::Visuals) -> Color32 {
+    fn color(self, colors: StatusColors, visuals: &egui::Visuals) -> Color32 {
         match self {
-            Self::Pending => PENDING_REVIEW_LABEL_COLOR,
-            Self::Approved => APPROVED_REVIEW_LABEL_COLOR,
+            Self::Pending => colors.pending_review,
+            Self::Approved => colors.approved,
             Self::ChangesRequested => visuals.error_fg_color,
         }
     }
@@ -1664,13 +5249,23 @@ mod tests {
     use chrono::{DateTime, NaiveDateTime, Utc};
     use eframe::egui;
     use eframe::egui::collapsing_header::CollapsingState;
-    use std::{collections::HashSet, time::Instant};
+    use std::{
+        collections::{HashMap, HashSet},
+        time::Instant,
+    };
 
-    use crate::domain::{InboxSnapshot, NotificationItem};
+    use crate::{
+        domain::{
+            InboxSnapshot, NotificationChips, NotificationItem, NotificationRule, RuleAction,
+            SearchMode, SearchTotals, ThreadStages, test_support,
+        },
+        github::PullRequestStatusQueue,
+    };
 
     use super::{
+        events::EventPublisher,
         notification_state::{
-            SectionCounts, base_notification_state, collect_new_notification_ids,
+            SectionCounts, base_notification_state, collect_new_notification_ids, has_new_activity,
             pending_review_request_ids, section_stats,
         },
         review::{
@@ -1678,6 +5273,7 @@ mod tests {
             format_review_success_output, initial_review_output_state, resolve_review_launch,
             review_output_plain_text, review_summary_text, truncate_review_output,
         },
+        rules::RuleSet,
         search::SearchFilter,
         ui::{
             NotificationRenderState, notification_state, render_bucket_sections,
@@ -1686,6 +5282,34 @@ mod tests {
         },
     };
 
+    #[test]
+    fn refresh_shortcut_is_consumed_once_and_ignores_other_keys() {
+        let ctx = egui::Context::default();
+        let key = |key, modifiers| egui::Event::Key {
+            key,
+            physical_key: None,
+            pressed: true,
+            repeat: false,
+            modifiers,
+        };
+        let mut input = egui::RawInput::default();
+        input.events.push(key(egui::Key::F5, egui::Modifiers::NONE));
+        input.events.push(key(egui::Key::R, egui::Modifiers::NONE));
+        ctx.begin_pass(input);
+
+        assert!(consume_refresh_shortcut(&ctx));
+        assert!(!consume_refresh_shortcut(&ctx));
+        let _ = ctx.end_pass();
+
+        let mut input = egui::RawInput::default();
+        input
+            .events
+            .push(key(egui::Key::R, egui::Modifiers::COMMAND));
+        ctx.begin_pass(input);
+        assert!(consume_refresh_shortcut(&ctx));
+        let _ = ctx.end_pass();
+    }
+
     fn parse_utc(ts: &str) -> DateTime<Utc> {
         NaiveDateTime::parse_from_str(ts, "%Y-%m-%d %H:%M:%S")
             .unwrap()
@@ -1694,17 +5318,11 @@ mod tests {
 
     fn notif(thread_id: &str, reason: &str, unread: bool, updated: &str) -> NotificationItem {
         NotificationItem {
-            thread_id: thread_id.to_string(),
             repo: "acme/repo".into(),
-            title: "Title".into(),
-            url: None,
-            head_ref: None,
-            base_ref: None,
-            my_review_status: None,
             reason: reason.into(),
             updated_at: parse_utc(updated),
-            last_read_at: None,
             unread,
+            ..test_support::notification(thread_id)
         }
     }
 
@@ -1714,7 +5332,13 @@ mod tests {
             review_requests: Vec::new(),
             mentions: Vec::new(),
             recent_reviews: Vec::new(),
+            assigned_issues: Vec::new(),
+            authored_pull_requests: Vec::new(),
+            pull_request_statuses: Vec::new(),
             fetched_at: Utc::now(),
+            rate_limit: None,
+            search_totals: SearchTotals::default(),
+            unread_threads: None,
         }
     }
 
@@ -1739,6 +5363,7 @@ mod tests {
             url: url.into(),
             updated_at: parse_utc("2024-01-01 00:00:00"),
             requested_by: Some("octocat".into()),
+            requested_at: None,
         }
     }
 
@@ -1758,24 +5383,77 @@ mod tests {
             login: "user".into(),
             token: "token".into(),
             review_settings: ReviewCommandSettings::default(),
+            hide_bot_notifications: None,
+            api_base_url: None,
+            working_hours: None,
+            token_command: None,
+            group: None,
+            section_alerts: None,
+            inbox_backend: InboxBackend::Rest,
+            forge: Forge::GitHub,
         }
     }
 
     fn make_account(login: &str) -> AccountState {
-        AccountState::new(GitHubAccount {
-            login: login.into(),
-            token: "token".into(),
-            review_settings: ReviewCommandSettings::default(),
-        })
+        AccountState::new(
+            GitHubAccount {
+                login: login.into(),
+                token: "token".into(),
+                review_settings: ReviewCommandSettings::default(),
+                hide_bot_notifications: None,
+                api_base_url: None,
+                working_hours: None,
+                token_command: None,
+                group: None,
+                section_alerts: None,
+                inbox_backend: InboxBackend::Rest,
+                forge: Forge::GitHub,
+            },
+            reqwest::Client::new(),
+            EventPublisher::detached(),
+        )
     }
 
     fn app_with_accounts(logins: &[&str]) -> ReminderApp {
         ReminderApp {
+            profile: None,
             account_form: AccountForm::default(),
             repo_path_form: RepoPathForm::default(),
             account_delete_confirmation: None,
             review_settings_editor: None,
+            device_flow: None,
+            power: PowerMonitor::default(),
+            idle: IdleMonitor::default(),
+            deep_links: None,
+            pending_deep_link: None,
+            events: EventBus::default(),
             repo_path_account_editor: None,
+            settings_editor: None,
+            settings: AppSettings::default(),
+            applied_accent: None,
+            applied_ui_scale: None,
+            applied_font_path: String::new(),
+            applied_rules: Vec::new(),
+            rules: RuleSet::default(),
+            history: ActivityHistory::default(),
+            exit_summary: false,
+            close_confirmed: false,
+            tray: None,
+            quit_requested: false,
+            reminders: ReminderList::default(),
+            reminder_editor: None,
+            history_view: None,
+            whats_new: None,
+            token_update: None,
+            section_focus: None,
+            alert_throttle: AlertThrottle::default(),
+            last_compaction: Instant::now(),
+            last_backup: None,
+            sync: SyncRunner::default(),
+            release_radar: ReleaseRadarState::default(),
+            plugin_sections: PluginSectionsState::default(),
+            avatars: Arc::new(AvatarLoader::new(None)),
+            window_title: String::new(),
             accounts: logins.iter().map(|login| make_account(login)).collect(),
             repo_paths: BTreeMap::new(),
             repo_path_accounts: BTreeMap::new(),
@@ -1784,13 +5462,67 @@ mod tests {
             selected_repo: None,
             repo_path_filter_login: None,
             show_all_accounts: true,
+            combined_inbox: false,
+            combined_chips: NotificationChips::default(),
             secret_store: None,
+            unlock_form: None,
+            ui_state: UiState::default(),
             storage_warning: None,
             global_error: None,
             auto_refresh: BatchRefreshScheduler::new(Duration::from_secs(1)),
+            replay: None,
         }
     }
 
+    #[test]
+    fn adding_a_tracked_login_offers_a_token_update() {
+        let mut app = app_with_accounts(&["neo"]);
+        app.account_form.login = String::from("NEO");
+        app.account_form.token = String::from("ghp_new");
+
+        app.add_account();
+
+        assert!(app.account_form.pending_check.is_none());
+        assert_eq!(
+            app.account_form.duplicate,
+            Some((String::from("neo"), String::from("ghp_new")))
+        );
+        assert_eq!(app.accounts.len(), 1);
+    }
+
+    #[test]
+    fn focusing_a_section_shows_all_accounts_and_toggles_off() {
+        let mut app = app_with_accounts(&["neo", "trinity"]);
+        app.select_account(String::from("neo"));
+
+        app.focus_section(SectionKind::Mentions);
+        assert!(app.show_all_accounts);
+        assert_eq!(app.section_focus, Some(SectionKind::Mentions));
+
+        app.focus_section(SectionKind::ReviewRequests);
+        assert_eq!(app.section_focus, Some(SectionKind::ReviewRequests));
+
+        app.focus_section(SectionKind::ReviewRequests);
+        assert_eq!(app.section_focus, None);
+    }
+
+    #[test]
+    fn account_groups_list_ungrouped_accounts_first() {
+        let mut app = app_with_accounts(&["neo", "trinity", "morpheus", "oracle"]);
+        app.accounts[0].profile.group = Some(String::from("Work"));
+        app.accounts[1].profile.group = Some(String::from(" Open source "));
+        app.accounts[3].profile.group = Some(String::from("Work"));
+
+        assert_eq!(
+            account_groups(&app.accounts),
+            vec![
+                (None, vec![2]),
+                (Some(String::from("Open source")), vec![1]),
+                (Some(String::from("Work")), vec![0, 3]),
+            ]
+        );
+    }
+
     fn reviewer(login: &str, status: PullRequestReviewerStatus) -> PullRequestReviewer {
         PullRequestReviewer {
             login: login.into(),
@@ -1805,16 +5537,52 @@ mod tests {
             notif("2", "mention", true, "2024-01-01 00:00:00"),
             notif("3", "subscribed", false, "2024-01-01 00:00:00"),
         ]);
-        let stats = section_stats(&inbox);
+        let stats = section_stats(&inbox, &RuleSet::default());
         assert_eq!(stats.inbox.unseen, 2);
         assert_eq!(stats.review_requests.unseen, 1);
         assert_eq!(stats.mentions.unseen, 1);
         assert_eq!(stats.notifications.unseen, 0);
+
+        let rules = RuleSet::compile(&[NotificationRule {
+            reason: String::from("mention"),
+            action: RuleAction::Mute,
+            ..NotificationRule::default()
+        }]);
+        let stats = section_stats(&inbox, &rules);
+        assert_eq!(stats.inbox.unseen, 1);
+        assert_eq!(stats.mentions.unseen, 0);
+    }
+
+    #[test]
+    fn window_title_badges_unread_count_and_profile() {
+        assert_eq!(window_title(None, 0), "Reminder");
+        assert_eq!(window_title(Some("work"), 3), "[3] Reminder (work)");
+    }
+
+    #[test]
+    fn total_unread_count_aggregates_accounts() {
+        let mut app = app_with_accounts(&["neo", "trinity"]);
+        app.accounts[0].inbox = Some(inbox_with_notifications(vec![
+            notif("1", "mention", true, "2024-01-01 00:00:00"),
+            notif("2", "mention", false, "2024-01-01 00:00:00"),
+        ]));
+        app.accounts[1].inbox = Some(inbox_with_notifications(vec![notif(
+            "3",
+            "subscribed",
+            true,
+            "2024-01-01 00:00:00",
+        )]));
+
+        assert_eq!(app.total_unread_count(), 2);
     }
 
     #[test]
     fn new_accounts_start_in_inbox_view() {
-        let account = AccountState::new(dummy_profile());
+        let account = AccountState::new(
+            dummy_profile(),
+            reqwest::Client::new(),
+            EventPublisher::detached(),
+        );
         assert_eq!(account.view_mode, AccountViewMode::Inbox);
     }
 
@@ -1832,9 +5600,27 @@ mod tests {
         assert!(!new.bumped_since(&old));
     }
 
+    #[test]
+    fn search_sections_highlight_on_new_or_updated_entries() {
+        let earlier = Utc::now() - chrono::Duration::hours(1);
+        let now = Utc::now();
+        let previous = [("https://github.com/acme/app/issues/1", earlier)];
+
+        assert!(!has_new_activity(previous, previous));
+        assert!(!has_new_activity(previous, []));
+        assert!(has_new_activity(
+            previous,
+            [("https://github.com/acme/app/issues/1", now)]
+        ));
+        assert!(has_new_activity(
+            previous,
+            [("https://github.com/acme/app/issues/2", earlier)]
+        ));
+    }
+
     #[test]
     fn search_filter_matches_case_insensitive() {
-        let filter = SearchFilter::new("Repo");
+        let filter = SearchFilter::new("Repo", SearchMode::Substring, false);
         assert!(filter.matches_any(&["my/repo"]));
         assert!(!filter.matches_any(&["other/project"]));
     }
@@ -1882,6 +5668,45 @@ mod tests {
         assert_eq!(app.selected_account_login.as_deref(), Some("alpha"));
     }
 
+    #[test]
+    fn cached_inbox_round_trips_through_json() {
+        let inbox = inbox_with_notifications(vec![notif(
+            "1",
+            "review_requested",
+            true,
+            "2024-01-01 00:00:00",
+        )]);
+
+        let restored: InboxSnapshot =
+            serde_json::from_str(&serde_json::to_string(&inbox).expect("json")).expect("inbox");
+
+        assert_eq!(restored.fetched_at, inbox.fetched_at);
+        assert_eq!(restored.notifications.len(), 1);
+        assert_eq!(restored.notifications[0].reason, "review_requested");
+        assert!(restored.notifications[0].unread);
+    }
+
+    #[test]
+    fn ui_state_round_trips_account_layout() {
+        let mut app = app_with_accounts(&["alpha", "beta"]);
+        app.accounts[1].expanded = false;
+        app.accounts[1].view_mode = AccountViewMode::Grouped;
+        app.accounts[1].search_query = String::from("infra");
+        app.select_account(String::from("beta"));
+        app.capture_ui_state();
+
+        let mut restored = app_with_accounts(&["alpha", "beta"]);
+        restored.ui_state = app.ui_state.clone();
+        restored.apply_ui_state();
+
+        assert!(restored.accounts[0].expanded);
+        assert!(!restored.accounts[1].expanded);
+        assert_eq!(restored.accounts[1].view_mode, AccountViewMode::Grouped);
+        assert_eq!(restored.accounts[1].search_query, "infra");
+        assert_eq!(restored.selected_account_login.as_deref(), Some("beta"));
+        assert!(!restored.show_all_accounts);
+    }
+
     #[test]
     fn ensure_selected_repo_clears_missing_repo() {
         let mut app = app_with_accounts(&["alpha"]);
@@ -1960,6 +5785,26 @@ mod tests {
         assert!(app.account_delete_confirmation.is_none());
     }
 
+    #[test]
+    fn cancelled_refresh_discards_its_result() {
+        let mut account = AccountState::new(
+            dummy_profile(),
+            reqwest::Client::new(),
+            EventPublisher::detached(),
+        );
+        account.pending_job = Some(crate::task::Task::finished(Ok(inbox_with_notifications(
+            vec![notif("1", "subscribed", true, "2024-01-01 00:00:00")],
+        ))));
+        account.cancel_refresh();
+        assert!(account.is_cancelling_refresh());
+
+        account.poll_job(&AppSettings::default(), &RuleSet::default(), false);
+
+        assert!(account.pending_job.is_none());
+        assert!(account.inbox.is_none());
+        assert!(account.last_error.is_none());
+    }
+
     #[test]
     fn collect_new_notification_ids_ignores_initial_sync() {
         let next =
@@ -1997,7 +5842,8 @@ mod tests {
             inbox.notifications[1].last_read_at = Some(parse_utc("2024-01-01 00:00:00"));
         }
 
-        let overview = account_overview(&account).expect("overview should exist");
+        let overview =
+            account_overview(&account, &RuleSet::default()).expect("overview should exist");
 
         assert_eq!(overview.new_notifications, 2);
         assert_eq!(overview.unseen, 1);
@@ -2265,7 +6111,13 @@ mod tests {
             review_requests: vec![review_request("acme/repo", pr_url)],
             mentions: Vec::new(),
             recent_reviews: Vec::new(),
+            assigned_issues: Vec::new(),
+            authored_pull_requests: Vec::new(),
+            pull_request_statuses: Vec::new(),
             fetched_at: Utc::now(),
+            rate_limit: None,
+            search_totals: SearchTotals::default(),
+            unread_threads: None,
         };
 
         let pending = pending_review_request_ids(&inbox);
@@ -2328,7 +6180,13 @@ mod tests {
             review_requests: vec![review_request("acme/repo", pr_url)],
             mentions: Vec::new(),
             recent_reviews: Vec::new(),
+            assigned_issues: Vec::new(),
+            authored_pull_requests: Vec::new(),
+            pull_request_statuses: Vec::new(),
             fetched_at: Utc::now(),
+            rate_limit: None,
+            search_totals: SearchTotals::default(),
+            unread_threads: None,
         };
 
         let pending = pending_review_request_ids(&inbox);
@@ -2350,7 +6208,13 @@ mod tests {
             review_requests: vec![review_request("acme/repo", pr_url)],
             mentions: Vec::new(),
             recent_reviews: vec![review_summary("acme/repo", pr_url)],
+            assigned_issues: Vec::new(),
+            authored_pull_requests: Vec::new(),
+            pull_request_statuses: Vec::new(),
             fetched_at: Utc::now(),
+            rate_limit: None,
+            search_totals: SearchTotals::default(),
+            unread_threads: None,
         };
 
         let pending = pending_review_request_ids(&inbox);
@@ -2374,12 +6238,23 @@ mod tests {
         let open_review_window_thread_ids = HashSet::new();
         let render_state = NotificationRenderState {
             inflight_done: &inflight_done,
+            unsubscribed_thread_ids: &HashSet::new(),
+            group_by_repo: false,
+            group_by_day: false,
+            collapsed_repos: &BTreeSet::new(),
             pending_review_ids: &pending_review_ids,
+            re_requested_ids: &HashSet::new(),
+            review_waits: &HashMap::new(),
             active_review_thread_ids: &active_review_thread_ids,
             review_output_thread_ids: &review_output_thread_ids,
             open_review_window_thread_ids: &open_review_window_thread_ids,
             custom_review_command: false,
             repo_paths: &BTreeMap::new(),
+            settings: &AppSettings::default(),
+            rules: &RuleSet::default(),
+            pull_request_statuses: &PullRequestStatusQueue::default(),
+            bulk_progress: None,
+            stages: &ThreadStages::default(),
         };
 
         let visual = notification_state(&item, &render_state);
@@ -2390,7 +6265,11 @@ mod tests {
     #[test]
     fn highlight_clears_after_rendering_section() {
         let ctx = egui::Context::default();
-        let mut account = AccountState::new(dummy_profile());
+        let mut account = AccountState::new(
+            dummy_profile(),
+            reqwest::Client::new(),
+            EventPublisher::detached(),
+        );
         account.inbox = Some(inbox_with_notifications(vec![notif(
             "t1",
             "subscribed",
@@ -2398,11 +6277,19 @@ mod tests {
             "2024-01-01 00:00:00",
         )]));
         account.highlights.insert(SectionKind::Notifications);
-        let filter = SearchFilter::new("");
+        let filter = SearchFilter::new("", SearchMode::Substring, false);
 
         ctx.begin_pass(Default::default());
         egui::CentralPanel::default().show(&ctx, |ui| {
-            let _ = render_bucket_sections(ui, &mut account, &filter, &BTreeMap::new(), false);
+            let _ = render_bucket_sections(
+                ui,
+                &mut account,
+                &filter,
+                &BTreeMap::new(),
+                false,
+                &AppSettings::default(),
+                &RuleSet::default(),
+            );
         });
         let _ = ctx.end_pass();
 
@@ -2415,19 +6302,31 @@ mod tests {
     #[test]
     fn collapsing_header_state_persists_across_frames() {
         let ctx = egui::Context::default();
-        let mut account = AccountState::new(dummy_profile());
+        let mut account = AccountState::new(
+            dummy_profile(),
+            reqwest::Client::new(),
+            EventPublisher::detached(),
+        );
         account.inbox = Some(inbox_with_notifications(vec![notif(
             "t1",
             "subscribed",
             true,
             "2024-01-01 00:00:00",
         )]));
-        let filter = SearchFilter::new("");
+        let filter = SearchFilter::new("", SearchMode::Substring, false);
 
         // Frame 1: render and manually collapse the notifications section.
         ctx.begin_pass(Default::default());
         egui::CentralPanel::default().show(&ctx, |ui| {
-            let _ = render_bucket_sections(ui, &mut account, &filter, &BTreeMap::new(), false);
+            let _ = render_bucket_sections(
+                ui,
+                &mut account,
+                &filter,
+                &BTreeMap::new(),
+                false,
+                &AppSettings::default(),
+                &RuleSet::default(),
+            );
         });
         let id = egui::Id::new("notification-section-Notifications");
         let mut state = CollapsingState::load_with_default_open(&ctx, id, true);
@@ -2439,8 +6338,15 @@ mod tests {
         ctx.begin_pass(Default::default());
         let mut stayed_collapsed = true;
         egui::CentralPanel::default().show(&ctx, |ui| {
-            let response =
-                render_bucket_sections(ui, &mut account, &filter, &BTreeMap::new(), false);
+            let response = render_bucket_sections(
+                ui,
+                &mut account,
+                &filter,
+                &BTreeMap::new(),
+                false,
+                &AppSettings::default(),
+                &RuleSet::default(),
+            );
             let state = CollapsingState::load_with_default_open(ui.ctx(), id, true);
             stayed_collapsed = !state.is_open();
             assert!(response.is_empty(), "Rendering should not trigger actions");
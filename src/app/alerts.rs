@@ -0,0 +1,558 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    process::{Command, Stdio},
+    thread,
+};
+
+use chrono::{DateTime, Duration, NaiveDateTime, Utc};
+use eframe::egui::{self, Context};
+
+use crate::domain::{
+    AppSettings, InboxSnapshot, NotificationItem, SectionAlertThresholds, WorkingHours,
+};
+
+use super::{
+    SectionKind,
+    notification_state::{SectionCounts, SectionStats, requested_reviews},
+    rules::RuleSet,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) struct DesktopAlert {
+    /// Identifies what the alert is about so repeats can be suppressed.
+    pub(super) key: String,
+    pub(super) title: String,
+    pub(super) body: String,
+}
+
+/// Remembers when each alert key last fired so a thread that keeps bumping
+/// (for example CI retries) alerts at most once per window.
+#[derive(Debug, Default)]
+pub(super) struct AlertThrottle {
+    last_delivered: HashMap<String, DateTime<Utc>>,
+}
+
+impl AlertThrottle {
+    pub(super) fn admit(&mut self, key: &str, now: DateTime<Utc>, window: Duration) -> bool {
+        self.last_delivered
+            .retain(|_, delivered_at| now - *delivered_at < window);
+        if self.last_delivered.contains_key(key) {
+            return false;
+        }
+        self.last_delivered.insert(key.to_owned(), now);
+        true
+    }
+
+    /// Forgets keys whose window has passed; `admit` only does this for the
+    /// alerts it sees, so a quiet session would otherwise keep them all.
+    pub(super) fn compact(&mut self, now: DateTime<Utc>, window: Duration) -> usize {
+        let before = self.last_delivered.len();
+        self.last_delivered
+            .retain(|_, delivered_at| now - *delivered_at < window);
+        before - self.last_delivered.len()
+    }
+
+    pub(super) fn len(&self) -> usize {
+        self.last_delivered.len()
+    }
+
+    /// Rough heap use of the remembered keys and timestamps.
+    pub(super) fn approximate_bytes(&self) -> usize {
+        self.last_delivered
+            .keys()
+            .map(|key| key.len() + std::mem::size_of::<(String, DateTime<Utc>)>())
+            .sum()
+    }
+}
+
+/// Whether alerts and the title badge are held back: do-not-disturb is on,
+/// or `local_time` falls outside the alert hours.
+pub(super) fn is_quiet(
+    do_not_disturb: bool,
+    alert_hours: Option<WorkingHours>,
+    local_time: NaiveDateTime,
+) -> bool {
+    do_not_disturb || alert_hours.is_some_and(|hours| !hours.contains(local_time))
+}
+
+/// Unread threads that are new or were bumped since the previous snapshot.
+/// The first snapshot for an account never produces arrivals.
+pub(super) fn collect_arrived_notifications<'a>(
+    previous: Option<&InboxSnapshot>,
+    next: &'a InboxSnapshot,
+) -> Vec<&'a NotificationItem> {
+    let Some(previous) = previous else {
+        return Vec::new();
+    };
+
+    let previous_updates: BTreeMap<_, _> = previous
+        .notifications
+        .iter()
+        .map(|item| (item.thread_id.as_str(), item.updated_at))
+        .collect();
+
+    next.notifications
+        .iter()
+        .filter(|item| item.unread)
+        .filter(|item| {
+            previous_updates
+                .get(item.thread_id.as_str())
+                .is_none_or(|updated_at| item.updated_at > *updated_at)
+        })
+        .collect()
+}
+
+/// Groups arrivals by repository and keeps only repositories that received
+/// more than `threshold` threads in a single refresh. A threshold of zero
+/// disables storm detection.
+pub(super) fn detect_storms(
+    arrived: &[&NotificationItem],
+    threshold: usize,
+) -> BTreeMap<String, Vec<String>> {
+    if threshold == 0 {
+        return BTreeMap::new();
+    }
+
+    let mut by_repo = BTreeMap::<String, Vec<String>>::new();
+    for item in arrived {
+        by_repo
+            .entry(item.repo.clone())
+            .or_default()
+            .push(item.thread_id.clone());
+    }
+    by_repo.retain(|_, thread_ids| thread_ids.len() > threshold);
+    by_repo
+}
+
+/// Threads in a section with a threshold are left to [`build_section_alerts`]
+/// rather than alerting one by one.
+pub(super) fn build_alerts(
+    login: &str,
+    arrived: &[&NotificationItem],
+    storms: &BTreeMap<String, Vec<String>>,
+    settings: &AppSettings,
+    rules: &RuleSet,
+    thresholds: SectionAlertThresholds,
+) -> Vec<DesktopAlert> {
+    let mut alerts: Vec<_> = storms
+        .iter()
+        .filter(|(repo, _)| settings.repo_tier(repo).alerts())
+        .filter(|(repo, _)| {
+            arrived
+                .iter()
+                .any(|item| &item.repo == *repo && rules.alerts(item))
+        })
+        .map(|(repo, thread_ids)| DesktopAlert {
+            key: format!("{login}/storm/{repo}"),
+            title: format!("{login}: {repo}"),
+            body: storm_summary_text(repo, thread_ids.len()),
+        })
+        .collect();
+    alerts.extend(
+        arrived
+            .iter()
+            .filter(|item| !storms.contains_key(&item.repo))
+            .filter(|item| settings.repo_tier(&item.repo).alerts() && rules.alerts(item))
+            .filter(|item| section_threshold(item, thresholds).is_none())
+            .map(|item| DesktopAlert {
+                key: format!("{login}/thread/{}", item.source_key()),
+                title: format!("{login}: {}", item.repo),
+                body: item.display_title(),
+            }),
+    );
+    alerts
+}
+
+/// Alerts for pull requests that ask again for a review I already gave,
+/// regardless of repository tier or section thresholds.
+pub(super) fn build_re_request_alerts(
+    login: &str,
+    previous: &InboxSnapshot,
+    next: &InboxSnapshot,
+) -> Vec<DesktopAlert> {
+    let before = requested_reviews(previous, true);
+    let mut keys: Vec<_> = requested_reviews(next, true)
+        .into_iter()
+        .filter(|key| !before.contains(key))
+        .collect();
+    keys.sort();
+    keys.into_iter()
+        .map(|(repo, number)| {
+            let title = next
+                .review_requests
+                .iter()
+                .find(|request| {
+                    request.repo == repo && request.pull_request_number() == Some(number)
+                })
+                .map_or_else(|| format!("#{number}"), |request| request.title.clone());
+            DesktopAlert {
+                key: format!("{login}/re-requested/{repo}#{number}"),
+                title: format!("{login}: review re-requested in {repo}"),
+                body: title,
+            }
+        })
+        .collect()
+}
+
+fn section_threshold(item: &NotificationItem, thresholds: SectionAlertThresholds) -> Option<usize> {
+    if SectionKind::ReviewRequests.contains(item) {
+        thresholds.review_requests
+    } else if SectionKind::Mentions.contains(item) {
+        thresholds.mentions
+    } else {
+        thresholds.notifications
+    }
+}
+
+/// One alert per section whose counts bumped since `previous` and whose
+/// unread count now exceeds its threshold.
+pub(super) fn build_section_alerts(
+    login: &str,
+    previous: &SectionStats,
+    next: &SectionStats,
+    thresholds: SectionAlertThresholds,
+) -> Vec<DesktopAlert> {
+    let sections: [(SectionKind, Option<usize>, &SectionCounts, &SectionCounts); 3] = [
+        (
+            SectionKind::ReviewRequests,
+            thresholds.review_requests,
+            &previous.review_requests,
+            &next.review_requests,
+        ),
+        (
+            SectionKind::Mentions,
+            thresholds.mentions,
+            &previous.mentions,
+            &next.mentions,
+        ),
+        (
+            SectionKind::Notifications,
+            thresholds.notifications,
+            &previous.notifications,
+            &next.notifications,
+        ),
+    ];
+    sections
+        .into_iter()
+        .filter_map(|(kind, threshold, old, new)| {
+            let threshold = threshold?;
+            (new.bumped_since(old) && new.unseen > threshold).then(|| DesktopAlert {
+                key: format!("{login}/section/{kind:?}"),
+                title: format!("{login}: {}", kind.title()),
+                body: format!("{} unread", new.unseen),
+            })
+        })
+        .collect()
+}
+
+pub(super) fn storm_summary_text(repo: &str, count: usize) -> String {
+    format!("{count} updates in {repo}")
+}
+
+pub(super) fn deliver(ctx: &Context, alert: &DesktopAlert) {
+    ctx.send_viewport_cmd(egui::ViewportCommand::RequestUserAttention(
+        egui::UserAttentionType::Informational,
+    ));
+
+    let Some(mut command) = platform_alert_command(alert) else {
+        return;
+    };
+    command.stdin(Stdio::null());
+    command.stdout(Stdio::null());
+    command.stderr(Stdio::null());
+    // Alerts are best-effort: a missing notifier binary must not disturb the UI.
+    thread::spawn(move || {
+        let _ = command.status();
+    });
+}
+
+#[cfg(target_os = "macos")]
+fn platform_alert_command(alert: &DesktopAlert) -> Option<Command> {
+    let mut command = Command::new("osascript");
+    command
+        .arg("-e")
+        .arg("on run argv")
+        .arg("-e")
+        .arg("display notification (item 2 of argv) with title (item 1 of argv)")
+        .arg("-e")
+        .arg("end run")
+        .arg(&alert.title)
+        .arg(&alert.body);
+    Some(command)
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+fn platform_alert_command(alert: &DesktopAlert) -> Option<Command> {
+    let mut command = Command::new("notify-send");
+    command.arg(&alert.title).arg(&alert.body);
+    Some(command)
+}
+
+#[cfg(not(unix))]
+fn platform_alert_command(_alert: &DesktopAlert) -> Option<Command> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use chrono::{Duration, Utc};
+
+    use super::{
+        AlertThrottle, build_alerts, build_re_request_alerts, build_section_alerts,
+        collect_arrived_notifications, detect_storms, is_quiet,
+    };
+    use crate::{
+        app::{notification_state::section_stats, rules::RuleSet},
+        domain::{
+            AppSettings, InboxSnapshot, NotificationItem, NotificationRule, RepoTier,
+            ReviewRequest, ReviewSummary, RuleAction, SearchTotals, SectionAlertThresholds,
+            WorkingHours, test_support,
+        },
+    };
+
+    fn notification(thread_id: &str, repo: &str) -> NotificationItem {
+        NotificationItem {
+            repo: repo.to_owned(),
+            ..test_support::notification(thread_id)
+        }
+    }
+
+    fn snapshot(notifications: Vec<NotificationItem>) -> InboxSnapshot {
+        InboxSnapshot {
+            notifications,
+            review_requests: Vec::new(),
+            mentions: Vec::new(),
+            recent_reviews: Vec::new(),
+            assigned_issues: Vec::new(),
+            authored_pull_requests: Vec::new(),
+            pull_request_statuses: Vec::new(),
+            fetched_at: Utc::now(),
+            rate_limit: None,
+            search_totals: SearchTotals::default(),
+            unread_threads: None,
+        }
+    }
+
+    #[test]
+    fn arrivals_include_new_and_bumped_unread_threads() {
+        let mut stale = notification("1", "acme/app");
+        stale.updated_at = Utc::now() - Duration::hours(1);
+        let previous = snapshot(vec![stale.clone(), notification("2", "acme/app")]);
+
+        let mut bumped = stale;
+        bumped.updated_at = Utc::now();
+        let mut read = notification("3", "acme/app");
+        read.unread = false;
+        let next = snapshot(vec![
+            bumped,
+            previous.notifications[1].clone(),
+            read,
+            notification("4", "acme/app"),
+        ]);
+
+        let arrived: Vec<_> = collect_arrived_notifications(Some(&previous), &next)
+            .into_iter()
+            .map(|item| item.thread_id.as_str())
+            .collect();
+        assert_eq!(arrived, vec!["1", "4"]);
+        assert!(collect_arrived_notifications(None, &next).is_empty());
+    }
+
+    #[test]
+    fn storms_collapse_to_a_single_alert_per_repo() {
+        let items: Vec<_> = (0..4)
+            .map(|index| notification(&index.to_string(), "acme/infra"))
+            .chain([notification("quiet", "acme/app")])
+            .collect();
+        let arrived: Vec<_> = items.iter().collect();
+
+        let storms = detect_storms(&arrived, 3);
+        assert_eq!(storms.len(), 1);
+        assert_eq!(storms["acme/infra"].len(), 4);
+
+        let alerts = build_alerts(
+            "neo",
+            &arrived,
+            &storms,
+            &AppSettings::default(),
+            &RuleSet::default(),
+            SectionAlertThresholds::default(),
+        );
+        assert_eq!(alerts.len(), 2);
+        assert_eq!(alerts[0].body, "4 updates in acme/infra");
+        assert_eq!(alerts[1].body, "Thread quiet");
+    }
+
+    #[test]
+    fn low_tier_repositories_do_not_alert() {
+        let items = [
+            notification("1", "acme/infra"),
+            notification("2", "acme/app"),
+        ];
+        let arrived: Vec<_> = items.iter().collect();
+        let mut settings = AppSettings::default();
+        settings
+            .repo_tiers
+            .insert(String::from("acme/infra"), RepoTier::Low);
+
+        let alerts = build_alerts(
+            "neo",
+            &arrived,
+            &BTreeMap::new(),
+            &settings,
+            &RuleSet::default(),
+            SectionAlertThresholds::default(),
+        );
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].title, "neo: acme/app");
+    }
+
+    #[test]
+    fn silenced_threads_do_not_alert() {
+        let mut ci = notification("1", "acme/app");
+        ci.reason = String::from("ci_activity");
+        let items = [ci, notification("2", "acme/app")];
+        let arrived: Vec<_> = items.iter().collect();
+        let rules = RuleSet::compile(&[NotificationRule {
+            reason: String::from("ci_activity"),
+            action: RuleAction::Silence,
+            ..NotificationRule::default()
+        }]);
+
+        let alerts = build_alerts(
+            "neo",
+            &arrived,
+            &BTreeMap::new(),
+            &AppSettings::default(),
+            &rules,
+            SectionAlertThresholds::default(),
+        );
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].key, "neo/thread/github/2");
+    }
+
+    #[test]
+    fn section_thresholds_replace_thread_alerts_for_their_section() {
+        let mut review = notification("1", "acme/app");
+        review.reason = String::from("review_requested");
+        let previous = snapshot(vec![review.clone()]);
+        let mut second = notification("2", "acme/app");
+        second.reason = String::from("review_requested");
+        let next = snapshot(vec![review, second, notification("3", "acme/app")]);
+        let thresholds = SectionAlertThresholds {
+            review_requests: Some(1),
+            ..SectionAlertThresholds::default()
+        };
+
+        let arrived = collect_arrived_notifications(Some(&previous), &next);
+        let alerts = build_alerts(
+            "neo",
+            &arrived,
+            &BTreeMap::new(),
+            &AppSettings::default(),
+            &RuleSet::default(),
+            thresholds,
+        );
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].key, "neo/thread/github/3");
+
+        let rules = RuleSet::default();
+        let old = section_stats(&previous, &rules);
+        let new = section_stats(&next, &rules);
+        let section_alerts = build_section_alerts("neo", &old, &new, thresholds);
+        assert_eq!(section_alerts.len(), 1);
+        assert_eq!(section_alerts[0].title, "neo: Review requests");
+        assert_eq!(section_alerts[0].body, "2 unread");
+
+        let stricter = SectionAlertThresholds {
+            review_requests: Some(2),
+            ..thresholds
+        };
+        assert!(build_section_alerts("neo", &old, &new, stricter).is_empty());
+    }
+
+    #[test]
+    fn re_requested_reviews_alert_once() {
+        let url = "https://github.com/acme/app/pull/7";
+        let request = ReviewRequest {
+            _id: 1,
+            repo: String::from("acme/app"),
+            title: String::from("#7 Retry uploads"),
+            url: url.to_owned(),
+            updated_at: Utc::now(),
+            requested_by: Some(String::from("trinity")),
+            requested_at: None,
+        };
+        let review = ReviewSummary {
+            _id: 2,
+            repo: String::from("acme/app"),
+            title: String::from("#7 Retry uploads"),
+            url: url.to_owned(),
+            updated_at: Utc::now(),
+            state: String::from("open"),
+        };
+        let mut first_request = snapshot(Vec::new());
+        first_request.review_requests = vec![request.clone()];
+        let mut reviewed = snapshot(Vec::new());
+        reviewed.recent_reviews = vec![review.clone()];
+        let mut re_requested = snapshot(Vec::new());
+        re_requested.review_requests = vec![request];
+        re_requested.recent_reviews = vec![review];
+
+        assert!(build_re_request_alerts("neo", &snapshot(Vec::new()), &first_request).is_empty());
+        let alerts = build_re_request_alerts("neo", &reviewed, &re_requested);
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].key, "neo/re-requested/acme/app#7");
+        assert_eq!(alerts[0].body, "#7 Retry uploads");
+        assert!(build_re_request_alerts("neo", &re_requested, &re_requested).is_empty());
+    }
+
+    #[test]
+    fn zero_threshold_disables_storm_detection() {
+        let items = [
+            notification("1", "acme/infra"),
+            notification("2", "acme/infra"),
+        ];
+        let arrived: Vec<_> = items.iter().collect();
+
+        assert!(detect_storms(&arrived, 0).is_empty());
+    }
+
+    #[test]
+    fn throttle_suppresses_repeats_within_the_window() {
+        let mut throttle = AlertThrottle::default();
+        let window = Duration::minutes(30);
+        let start = Utc::now();
+
+        assert!(throttle.admit("neo/thread/1", start, window));
+        assert!(!throttle.admit("neo/thread/1", start + Duration::minutes(10), window));
+        assert!(throttle.admit("neo/thread/2", start + Duration::minutes(10), window));
+        assert!(throttle.admit("neo/thread/1", start + Duration::minutes(31), window));
+    }
+
+    #[test]
+    fn zero_window_never_suppresses() {
+        let mut throttle = AlertThrottle::default();
+        let now = Utc::now();
+
+        assert!(throttle.admit("neo/thread/1", now, Duration::zero()));
+        assert!(throttle.admit("neo/thread/1", now, Duration::zero()));
+    }
+
+    #[test]
+    fn alerts_are_quiet_outside_alert_hours_or_under_do_not_disturb() {
+        let at = |text: &str| text.parse::<chrono::NaiveDateTime>().unwrap();
+        // 2026-03-02 is a Monday.
+        let monday_noon = at("2026-03-02T12:00:00");
+        let monday_night = at("2026-03-02T21:00:00");
+        let hours = Some(WorkingHours::default());
+
+        assert!(!is_quiet(false, None, monday_night));
+        assert!(!is_quiet(false, hours, monday_noon));
+        assert!(is_quiet(false, hours, monday_night));
+        assert!(is_quiet(false, hours, at("2026-03-07T12:00:00")));
+        assert!(is_quiet(true, None, monday_noon));
+    }
+}
@@ -0,0 +1,344 @@
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, PoisonError},
+    time::SystemTime,
+};
+
+use eframe::egui::{
+    Context,
+    load::{BytesLoadResult, BytesLoader, BytesPoll, LoadError},
+};
+use reqwest::Client;
+
+use crate::{
+    github,
+    task::{Task, TaskLost},
+};
+
+/// Folder under the data directory that downloaded avatars are kept in.
+pub(super) const AVATARS_DIR: &str = "avatars";
+
+const AVATAR_URI_PREFIX: &str = "avatar:";
+/// Twice the drawn size so avatars stay sharp on high-DPI screens.
+const AVATAR_FETCH_SIZE_PX: u32 = 40;
+/// Decoded avatars kept as textures; the least recently drawn go first.
+const AVATAR_MEMORY_LIMIT: usize = 256;
+const AVATAR_DISK_LIMIT: usize = 1024;
+
+/// `avatar:` URI of `login`'s picture on the host that serves `page_url`,
+/// for `egui::Image`. Users and organizations share the same endpoint.
+pub(super) fn avatar_uri(page_url: &str, login: &str) -> Option<String> {
+    let (scheme, rest) = page_url.split_once("://")?;
+    let host = rest.split('/').next().filter(|host| !host.is_empty())?;
+    let login = login.trim();
+    (!login.is_empty()).then(|| {
+        format!("{AVATAR_URI_PREFIX}{scheme}://{host}/{login}.png?size={AVATAR_FETCH_SIZE_PX}")
+    })
+}
+
+type AvatarResult = Result<Arc<[u8]>, String>;
+
+enum AvatarEntry {
+    Pending(Task<AvatarResult>),
+    Ready(Arc<[u8]>),
+    Failed(String),
+}
+
+struct AvatarSlot {
+    entry: AvatarEntry,
+    last_used: u64,
+}
+
+#[derive(Default)]
+struct AvatarSlots {
+    slots: HashMap<String, AvatarSlot>,
+    clock: u64,
+    /// Dropped URIs whose decoded textures the app still has to forget.
+    evicted: Vec<String>,
+    client: Option<Client>,
+}
+
+impl AvatarSlots {
+    fn tick(&mut self) -> u64 {
+        self.clock += 1;
+        self.clock
+    }
+
+    /// Drops the least recently used slots until at most `limit` remain.
+    fn evict_over(&mut self, limit: usize) {
+        while self.slots.len() > limit {
+            let Some(oldest) = self
+                .slots
+                .iter()
+                .min_by_key(|(_, slot)| slot.last_used)
+                .map(|(uri, _)| uri.clone())
+            else {
+                return;
+            };
+            self.slots.remove(&oldest);
+            self.evicted.push(oldest);
+        }
+    }
+}
+
+/// Serves `avatar:` URIs to egui's image loaders: fetched on the shared
+/// runtime, kept on disk between runs and in memory while drawn.
+pub(super) struct AvatarLoader {
+    dir: Option<PathBuf>,
+    slots: Mutex<AvatarSlots>,
+}
+
+impl AvatarLoader {
+    pub(super) fn new(dir: Option<PathBuf>) -> Self {
+        Self {
+            dir,
+            slots: Mutex::default(),
+        }
+    }
+
+    /// Registers the loader, and the PNG decoder it relies on, with `ctx`.
+    pub(super) fn install(ctx: &Context, dir: Option<PathBuf>) -> Arc<Self> {
+        egui_extras::install_image_loaders(ctx);
+        let loader = Arc::new(Self::new(dir));
+        ctx.add_bytes_loader(loader.clone());
+        loader
+    }
+
+    /// Frees the textures of avatars evicted since the last call. Runs from
+    /// `update` rather than `load` so egui's loaders are never re-entered.
+    pub(super) fn forget_evicted(&self, ctx: &Context) {
+        let evicted = std::mem::take(&mut self.lock().evicted);
+        for uri in evicted {
+            ctx.forget_image(&uri);
+        }
+    }
+
+    /// Picks up changed network preferences on the next download.
+    pub(super) fn reset_client(&self) {
+        self.lock().client = None;
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, AvatarSlots> {
+        self.slots.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl BytesLoader for AvatarLoader {
+    fn id(&self) -> &str {
+        eframe::egui::load::generate_loader_id!(AvatarLoader)
+    }
+
+    fn load(&self, ctx: &Context, uri: &str) -> BytesLoadResult {
+        let Some(url) = uri.strip_prefix(AVATAR_URI_PREFIX) else {
+            return Err(LoadError::NotSupported);
+        };
+        let mut slots = self.lock();
+        let now = slots.tick();
+        if let Some(slot) = slots.slots.get_mut(uri) {
+            slot.last_used = now;
+            if let AvatarEntry::Pending(task) = &slot.entry
+                && let Some(result) = task.try_take()
+            {
+                slot.entry = match result
+                    .unwrap_or_else(|TaskLost| Err(String::from("the avatar download stopped")))
+                {
+                    Ok(bytes) => AvatarEntry::Ready(bytes),
+                    Err(err) => AvatarEntry::Failed(err),
+                };
+            }
+            return match &slot.entry {
+                AvatarEntry::Pending(_) => Ok(BytesPoll::Pending { size: None }),
+                AvatarEntry::Ready(bytes) => Ok(BytesPoll::Ready {
+                    size: None,
+                    bytes: bytes.clone().into(),
+                    mime: Some(String::from("image/png")),
+                }),
+                AvatarEntry::Failed(err) => Err(LoadError::Loading(err.clone())),
+            };
+        }
+
+        let client = match &slots.client {
+            Some(client) => client.clone(),
+            None => {
+                let client =
+                    github::build_client().map_err(|err| LoadError::Loading(err.to_string()))?;
+                slots.client.insert(client).clone()
+            }
+        };
+        let path = self.dir.clone();
+        let url = url.to_owned();
+        let ctx = ctx.clone();
+        let task = Task::spawn(move |_| async move {
+            let result = fetch_avatar(&client, &url, path.as_deref()).await;
+            ctx.request_repaint();
+            result
+        });
+        slots.slots.insert(
+            uri.to_owned(),
+            AvatarSlot {
+                entry: AvatarEntry::Pending(task),
+                last_used: now,
+            },
+        );
+        slots.evict_over(AVATAR_MEMORY_LIMIT);
+        Ok(BytesPoll::Pending { size: None })
+    }
+
+    fn forget(&self, uri: &str) {
+        self.lock().slots.remove(uri);
+    }
+
+    fn forget_all(&self) {
+        self.lock().slots.clear();
+    }
+
+    fn byte_size(&self) -> usize {
+        self.lock()
+            .slots
+            .values()
+            .map(|slot| match &slot.entry {
+                AvatarEntry::Ready(bytes) => bytes.len(),
+                AvatarEntry::Pending(_) | AvatarEntry::Failed(_) => 0,
+            })
+            .sum()
+    }
+
+    fn has_pending(&self) -> bool {
+        self.lock()
+            .slots
+            .values()
+            .any(|slot| matches!(slot.entry, AvatarEntry::Pending(_)))
+    }
+}
+
+/// The disk copy when there is one, otherwise a download that is then
+/// written to `dir`.
+async fn fetch_avatar(client: &Client, url: &str, dir: Option<&Path>) -> AvatarResult {
+    let path = dir.map(|dir| dir.join(avatar_file_name(url)));
+    if let Some(path) = &path
+        && let Ok(bytes) = fs::read(path)
+    {
+        // The modification time doubles as the disk copy's last use.
+        let _ = fs::File::options()
+            .append(true)
+            .open(path)
+            .and_then(|file| file.set_modified(SystemTime::now()));
+        return Ok(bytes.into());
+    }
+    let bytes = client
+        .get(url)
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .map_err(|err| err.to_string())?
+        .bytes()
+        .await
+        .map_err(|err| err.to_string())?;
+    if let (Some(dir), Some(path)) = (dir, &path) {
+        let _ = fs::create_dir_all(dir)
+            .and_then(|()| fs::write(path, &bytes))
+            .and_then(|()| prune_avatar_dir(dir, AVATAR_DISK_LIMIT));
+    }
+    Ok(bytes.to_vec().into())
+}
+
+fn avatar_file_name(url: &str) -> String {
+    let name: String = url
+        .split_once("://")
+        .map_or(url, |(_, rest)| rest)
+        .chars()
+        .map(|ch| {
+            if ch.is_ascii_alphanumeric() || ch == '-' || ch == '.' {
+                ch
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("{name}.png")
+}
+
+/// Deletes the least recently used files until at most `limit` remain.
+fn prune_avatar_dir(dir: &Path, limit: usize) -> io::Result<()> {
+    let mut files: Vec<_> = fs::read_dir(dir)?
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            let modified = entry.metadata().ok()?.modified().ok()?;
+            Some((modified, entry.path()))
+        })
+        .collect();
+    if files.len() <= limit {
+        return Ok(());
+    }
+    files.sort();
+    for (_, path) in &files[..files.len() - limit] {
+        fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        time::{Duration, SystemTime},
+    };
+
+    use super::{
+        AvatarEntry, AvatarSlot, AvatarSlots, avatar_file_name, avatar_uri, prune_avatar_dir,
+    };
+
+    #[test]
+    fn avatar_uris_use_the_page_host() {
+        assert_eq!(
+            avatar_uri("https://github.mycorp.com/acme/app/pull/7", "acme").as_deref(),
+            Some("avatar:https://github.mycorp.com/acme.png?size=40")
+        );
+        assert_eq!(avatar_uri("not a url", "acme"), None);
+        assert_eq!(avatar_uri("https://github.com/acme/app", " "), None);
+        assert_eq!(
+            avatar_file_name("https://github.com/acme.png?size=40"),
+            "github.com_acme.png_size_40.png"
+        );
+    }
+
+    #[test]
+    fn least_recently_used_avatars_are_evicted_from_memory_and_disk() {
+        let mut slots = AvatarSlots::default();
+        for (uri, last_used) in [("avatar:a", 3), ("avatar:b", 1), ("avatar:c", 2)] {
+            slots.slots.insert(
+                uri.to_owned(),
+                AvatarSlot {
+                    entry: AvatarEntry::Failed(String::new()),
+                    last_used,
+                },
+            );
+        }
+        slots.evict_over(1);
+        assert_eq!(slots.evicted, ["avatar:b", "avatar:c"]);
+        assert!(slots.slots.contains_key("avatar:a"));
+
+        let dir = std::env::temp_dir().join(format!("reminder-avatars-{}", std::process::id()));
+        fs::create_dir_all(&dir).expect("avatar dir");
+        let base = SystemTime::now() - Duration::from_secs(60);
+        for (index, name) in ["old.png", "newer.png", "newest.png"].iter().enumerate() {
+            let path = dir.join(name);
+            fs::write(&path, b"png").expect("avatar file");
+            fs::File::options()
+                .append(true)
+                .open(&path)
+                .and_then(|file| file.set_modified(base + Duration::from_secs(index as u64)))
+                .expect("set modified");
+        }
+        prune_avatar_dir(&dir, 2).expect("prune");
+        let mut left: Vec<_> = fs::read_dir(&dir)
+            .expect("read dir")
+            .map(|entry| entry.expect("entry").file_name())
+            .collect();
+        left.sort();
+        let _ = fs::remove_dir_all(&dir);
+        assert_eq!(left, ["newer.png", "newest.png"]);
+    }
+}
@@ -0,0 +1,103 @@
+use std::{
+    sync::mpsc::{self, Receiver, Sender},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    domain::{Forge, GitHubAccount, InboxBackend, ReviewCommandSettings},
+    github::{self, DeviceCode, DeviceTokenPoll, FetchError},
+    task::{Task, TaskLost},
+};
+
+const CLIENT_ID_ENV: &str = "REMINDER_OAUTH_CLIENT_ID";
+/// GitHub adds this many seconds to the polling interval on `slow_down`.
+const SLOW_DOWN_STEP_SECS: u64 = 5;
+
+/// OAuth app client id, read at runtime first so packagers and users can
+/// supply their own app without rebuilding.
+pub(super) fn oauth_client_id() -> Option<String> {
+    std::env::var(CLIENT_ID_ENV)
+        .ok()
+        .or_else(|| option_env!("REMINDER_OAUTH_CLIENT_ID").map(str::to_owned))
+        .filter(|client_id| !client_id.trim().is_empty())
+}
+
+/// Runs "Sign in with GitHub" in the background: request a user code, then
+/// poll until the user authorizes it in the browser. Dropping the job
+/// cancels the sign-in.
+pub(super) struct DeviceFlowJob {
+    task: Task<Result<GitHubAccount, FetchError>>,
+    codes: Receiver<DeviceCode>,
+    pub(super) code: Option<DeviceCode>,
+}
+
+impl DeviceFlowJob {
+    pub(super) fn spawn(client_id: String, api_base_url: Option<String>) -> Self {
+        let (tx, rx) = mpsc::channel();
+        let task =
+            Task::spawn(
+                move |_| async move { run_device_flow(&client_id, api_base_url, &tx).await },
+            );
+        Self {
+            task,
+            codes: rx,
+            code: None,
+        }
+    }
+
+    /// Picks up the user code once it arrives and returns the signed-in
+    /// account (or the failure) when the flow ends.
+    pub(super) fn poll(&mut self) -> Option<Result<GitHubAccount, FetchError>> {
+        if let Some(code) = self.codes.try_iter().last() {
+            self.code = Some(code);
+        }
+        self.task
+            .try_take()
+            .map(|result| result.unwrap_or_else(|TaskLost| Err(FetchError::BackgroundWorkerGone)))
+    }
+}
+
+async fn run_device_flow(
+    client_id: &str,
+    api_base_url: Option<String>,
+    codes: &Sender<DeviceCode>,
+) -> Result<GitHubAccount, FetchError> {
+    let mut profile = GitHubAccount {
+        login: String::new(),
+        token: String::new(),
+        review_settings: ReviewCommandSettings::default(),
+        hide_bot_notifications: None,
+        api_base_url,
+        working_hours: None,
+        token_command: None,
+        group: None,
+        section_alerts: None,
+        inbox_backend: InboxBackend::Rest,
+        forge: Forge::GitHub,
+    };
+    let client = github::build_client()?;
+    let web_base = profile.web_base().to_owned();
+    let code = github::request_device_code(&client, &web_base, client_id).await?;
+    let device_code = code.device_code.clone();
+    let expires_at = Instant::now() + Duration::from_secs(code.expires_in);
+    let mut interval = code.interval.max(1);
+    let _ = codes.send(code);
+
+    profile.token = loop {
+        tokio::time::sleep(Duration::from_secs(interval)).await;
+        if Instant::now() >= expires_at {
+            return Err(FetchError::DeviceFlow(String::from(
+                "the code expired before it was authorized",
+            )));
+        }
+        match github::poll_device_token(&client, &web_base, client_id, &device_code).await? {
+            DeviceTokenPoll::Pending => {}
+            DeviceTokenPoll::SlowDown { interval: next } => {
+                interval = next.unwrap_or(interval + SLOW_DOWN_STEP_SECS);
+            }
+            DeviceTokenPoll::Granted(token) => break token,
+        }
+    };
+    profile.login = github::fetch_authenticated_login(&client, &profile).await?;
+    Ok(profile)
+}
@@ -0,0 +1,54 @@
+use serde::Serialize;
+
+/// Approximate footprint of one long-lived store, listed under
+/// Preferences → Diagnostics.
+pub(super) struct StoreUsage {
+    pub(super) name: String,
+    pub(super) entries: usize,
+    pub(super) bytes: usize,
+}
+
+impl StoreUsage {
+    pub(super) fn new(name: impl Into<String>, entries: usize, bytes: usize) -> Self {
+        Self {
+            name: name.into(),
+            entries,
+            bytes,
+        }
+    }
+
+    /// Sizes `value` by its JSON encoding, which tracks heap use closely
+    /// enough to spot a store that keeps growing.
+    pub(super) fn serialized(
+        name: impl Into<String>,
+        entries: usize,
+        value: &impl Serialize,
+    ) -> Self {
+        let bytes = serde_json::to_vec(value).map_or(0, |json| json.len());
+        Self::new(name, entries, bytes)
+    }
+}
+
+pub(super) fn format_bytes(bytes: usize) -> String {
+    const KIB: f64 = 1024.0;
+    let bytes_f = bytes as f64;
+    if bytes_f < KIB {
+        format!("{bytes} B")
+    } else if bytes_f < KIB * KIB {
+        format!("{:.1} KiB", bytes_f / KIB)
+    } else {
+        format!("{:.1} MiB", bytes_f / (KIB * KIB))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::format_bytes;
+
+    #[test]
+    fn bytes_are_shown_in_the_largest_fitting_unit() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+}
@@ -0,0 +1,78 @@
+use std::{env, sync::OnceLock};
+
+use eframe::egui::{Align, Layout, panel::Side};
+
+use crate::domain::LayoutDirection;
+
+/// Languages written right to left, by ISO 639 code. `iw` is the legacy
+/// code for Hebrew that some systems still report.
+const RTL_LANGUAGES: &[&str] = &[
+    "ar", "arc", "ckb", "dv", "fa", "he", "iw", "ps", "sd", "ug", "ur", "yi",
+];
+
+pub(super) fn uses_right_to_left(direction: LayoutDirection) -> bool {
+    match direction {
+        LayoutDirection::Auto => system_locale_is_rtl(),
+        LayoutDirection::LeftToRight => false,
+        LayoutDirection::RightToLeft => true,
+    }
+}
+
+/// Rows added with `ui.horizontal` inside this layout run right to left.
+pub(super) fn content_layout(right_to_left: bool) -> Layout {
+    Layout::top_down(if right_to_left {
+        Align::Max
+    } else {
+        Align::Min
+    })
+}
+
+/// The accounts panel sits at the reading start of the window.
+pub(super) fn accounts_panel_side(right_to_left: bool) -> Side {
+    if right_to_left {
+        Side::Right
+    } else {
+        Side::Left
+    }
+}
+
+/// The preview pane sits opposite the accounts panel.
+pub(super) fn preview_panel_side(right_to_left: bool) -> Side {
+    accounts_panel_side(!right_to_left)
+}
+
+fn system_locale_is_rtl() -> bool {
+    static RTL: OnceLock<bool> = OnceLock::new();
+    *RTL.get_or_init(|| {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .into_iter()
+            .filter_map(|name| env::var(name).ok())
+            .find(|value| !value.is_empty())
+            .is_some_and(|locale| locale_is_rtl(&locale))
+    })
+}
+
+/// Accepts POSIX (`he_IL.UTF-8`) and BCP 47 (`ar-EG`) locale names.
+fn locale_is_rtl(locale: &str) -> bool {
+    let language = locale
+        .split(['_', '-', '.', '@'])
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    RTL_LANGUAGES.contains(&language.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::locale_is_rtl;
+
+    #[test]
+    fn recognizes_rtl_locales() {
+        assert!(locale_is_rtl("he_IL.UTF-8"));
+        assert!(locale_is_rtl("ar-EG"));
+        assert!(locale_is_rtl("fa"));
+        assert!(!locale_is_rtl("en_US.UTF-8"));
+        assert!(!locale_is_rtl("C"));
+        assert!(!locale_is_rtl(""));
+    }
+}
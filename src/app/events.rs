@@ -0,0 +1,117 @@
+use std::sync::mpsc::{self, Receiver, Sender};
+
+use chrono::{DateTime, Utc};
+
+use crate::domain::{HandledKind, Reminder};
+
+use super::alerts::DesktopAlert;
+
+/// Something a subsystem wants the app to react to, published on the
+/// [`EventBus`] and handled once per frame in `update`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) enum AppEvent {
+    /// A refresh replaced the account's inbox.
+    SnapshotReady {
+        login: String,
+    },
+    /// A mark read, done or unsubscribe request finished.
+    ActionCompleted {
+        login: String,
+        thread_id: Option<String>,
+        succeeded: bool,
+        /// Set when the thread was marked read or done.
+        handled: Option<HandledKind>,
+    },
+    /// The account's API refused requests until `reset_at`.
+    RateLimited {
+        login: String,
+        reset_at: DateTime<Utc>,
+    },
+    ReminderDue(Reminder),
+    Alert(DesktopAlert),
+}
+
+/// Cloneable handle for publishing [`AppEvent`]s; safe to move into
+/// background tasks.
+#[derive(Clone)]
+pub(super) struct EventPublisher(Sender<AppEvent>);
+
+impl EventPublisher {
+    /// Dropped silently once the bus is gone, e.g. while the app shuts down.
+    pub(super) fn publish(&self, event: AppEvent) {
+        let _ = self.0.send(event);
+    }
+
+    /// A publisher nobody listens to, for state built outside an app.
+    #[cfg(test)]
+    pub(super) fn detached() -> Self {
+        EventBus::default().publisher()
+    }
+}
+
+/// Single queue every subsystem publishes to, so new kinds of work need a
+/// variant rather than another channel for the app to poll.
+pub(super) struct EventBus {
+    sender: Sender<AppEvent>,
+    receiver: Receiver<AppEvent>,
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Self { sender, receiver }
+    }
+}
+
+impl EventBus {
+    pub(super) fn publisher(&self) -> EventPublisher {
+        EventPublisher(self.sender.clone())
+    }
+
+    pub(super) fn publish(&self, event: AppEvent) {
+        let _ = self.sender.send(event);
+    }
+
+    /// Everything published since the last call, oldest first.
+    pub(super) fn drain(&self) -> Vec<AppEvent> {
+        self.receiver.try_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AppEvent, EventBus};
+
+    #[test]
+    fn events_from_every_publisher_drain_in_order() {
+        let bus = EventBus::default();
+        let first = bus.publisher();
+        let second = first.clone();
+        let worker = bus.publisher();
+
+        first.publish(AppEvent::SnapshotReady {
+            login: String::from("neo"),
+        });
+        std::thread::spawn(move || {
+            worker.publish(AppEvent::ActionCompleted {
+                login: String::from("neo"),
+                thread_id: Some(String::from("7")),
+                succeeded: true,
+                handled: None,
+            });
+        })
+        .join()
+        .expect("worker thread");
+        second.publish(AppEvent::SnapshotReady {
+            login: String::from("trinity"),
+        });
+
+        let events = bus.drain();
+        assert_eq!(events.len(), 3);
+        assert!(
+            matches!(&events[1], AppEvent::ActionCompleted { thread_id: Some(id), .. } if id == "7")
+        );
+        assert!(matches!(&events[2], AppEvent::SnapshotReady { login } if login == "trinity"));
+        assert!(bus.drain().is_empty());
+    }
+}
@@ -1,33 +1,148 @@
-use std::fs;
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
 
-use eframe::egui::{Context, FontData, FontDefinitions, FontFamily};
+use eframe::egui::{Context, FontData, FontDefinitions, FontFamily, style::default_text_styles};
 
-use super::{CJK_FONT_NAME, SYSTEM_FONT_CANDIDATES};
+use super::{CJK_FONT_NAME, RTL_FONT_CANDIDATES, RTL_FONT_NAME, SYSTEM_FONT_CANDIDATES};
 
-pub(super) fn install_international_fonts(ctx: &Context) {
-    let Some(font_data) = resolve_cjk_font_data() else {
-        eprintln!("Warning: no CJK-capable font found; Some glyphs may fail to render.");
-        return;
-    };
+const CUSTOM_FONT_NAME: &str = "Custom_Font";
+
+/// Noto Sans KR, embedded with the `bundled-fonts` feature for systems
+/// without any CJK font. Emoji already come with egui's default fonts.
+#[cfg(feature = "bundled-fonts")]
+const BUNDLED_CJK_FONT: &[u8] = include_bytes!("../../assets/NotoSansKR-Regular.ttf");
 
+/// Installs the CJK and RTL fallbacks, with `custom_font` ahead of every
+/// other font when set. The fallbacks are installed even when the custom
+/// font cannot be read.
+pub(super) fn install_international_fonts(
+    ctx: &Context,
+    custom_font: Option<&Path>,
+) -> io::Result<()> {
     let mut definitions = FontDefinitions::default();
-    definitions
-        .font_data
-        .insert(CJK_FONT_NAME.to_owned(), font_data.into());
 
-    for family in [FontFamily::Proportional, FontFamily::Monospace] {
+    let custom = custom_font.map(fs::read).transpose();
+    if let Ok(Some(bytes)) = &custom {
+        definitions.font_data.insert(
+            CUSTOM_FONT_NAME.to_owned(),
+            FontData::from_owned(bytes.clone()).into(),
+        );
+        for family in [FontFamily::Proportional, FontFamily::Monospace] {
+            definitions
+                .families
+                .entry(family)
+                .or_default()
+                .insert(0, CUSTOM_FONT_NAME.to_owned());
+        }
+    }
+
+    if let Some(font_data) = resolve_cjk_font_data() {
         definitions
-            .families
-            .entry(family)
-            .or_default()
-            .insert(0, CJK_FONT_NAME.to_owned());
+            .font_data
+            .insert(CJK_FONT_NAME.to_owned(), font_data.into());
+        // After the custom font, before egui's own.
+        let position = usize::from(matches!(custom, Ok(Some(_))));
+        for family in [FontFamily::Proportional, FontFamily::Monospace] {
+            definitions
+                .families
+                .entry(family)
+                .or_default()
+                .insert(position, CJK_FONT_NAME.to_owned());
+        }
+    } else {
+        eprintln!("Warning: no CJK-capable font found; Some glyphs may fail to render.");
+    }
+
+    // Arabic and Hebrew fonts go last so they only supply glyphs the
+    // default and CJK fonts lack.
+    for (index, font_data) in load_system_rtl_fonts().into_iter().enumerate() {
+        let name = format!("{RTL_FONT_NAME}_{index}");
+        definitions.font_data.insert(name.clone(), font_data.into());
+        for family in [FontFamily::Proportional, FontFamily::Monospace] {
+            definitions
+                .families
+                .entry(family)
+                .or_default()
+                .push(name.clone());
+        }
     }
 
     ctx.set_fonts(definitions);
+    custom.map(drop)
 }
 
+/// Applies the zoom and scales every text style from egui's defaults, in
+/// both themes.
+pub(super) fn apply_ui_scale(ctx: &Context, ui_scale_percent: u16, text_size_percent: u16) {
+    ctx.set_zoom_factor(f32::from(ui_scale_percent) / 100.0);
+    let factor = f32::from(text_size_percent) / 100.0;
+    ctx.all_styles_mut(|style| {
+        style.text_styles = default_text_styles()
+            .into_iter()
+            .map(|(text_style, mut font)| {
+                font.size *= factor;
+                (text_style, font)
+            })
+            .collect();
+    });
+}
+
+/// Known system paths first, then whatever fontconfig knows about, then the
+/// bundled font when compiled in.
 fn resolve_cjk_font_data() -> Option<FontData> {
     load_system_cjk_font()
+        .or_else(load_fontconfig_cjk_font)
+        .or_else(bundled_cjk_font)
+}
+
+#[cfg(feature = "bundled-fonts")]
+fn bundled_cjk_font() -> Option<FontData> {
+    Some(FontData::from_static(BUNDLED_CJK_FONT))
+}
+
+#[cfg(not(feature = "bundled-fonts"))]
+fn bundled_cjk_font() -> Option<FontData> {
+    None
+}
+
+/// Asks `fc-list` for fonts covering Korean, Japanese or Chinese, on
+/// systems that have fontconfig.
+#[cfg(all(unix, not(target_os = "macos")))]
+fn load_fontconfig_cjk_font() -> Option<FontData> {
+    ["ko", "ja", "zh-cn"].iter().find_map(|lang| {
+        let output = std::process::Command::new("fc-list")
+            .arg("--format=%{file}\\n")
+            .arg(format!(":lang={lang}"))
+            .output()
+            .ok()?;
+        let path = pick_fontconfig_file(&String::from_utf8_lossy(&output.stdout))?;
+        fs::read(path).ok().map(FontData::from_owned)
+    })
+}
+
+#[cfg(not(all(unix, not(target_os = "macos"))))]
+fn load_fontconfig_cjk_font() -> Option<FontData> {
+    None
+}
+
+/// The first listed file that egui can load, preferring Noto/Source CJK
+/// families over whatever else claims the language.
+#[cfg_attr(not(all(unix, not(target_os = "macos"))), allow(dead_code))]
+fn pick_fontconfig_file(listing: &str) -> Option<PathBuf> {
+    let mut files: Vec<&str> = listing
+        .lines()
+        .map(str::trim)
+        .filter(|line| {
+            let lower = line.to_ascii_lowercase();
+            [".ttf", ".otf", ".ttc"]
+                .iter()
+                .any(|ext| lower.ends_with(ext))
+        })
+        .collect();
+    files.sort_by_key(|file| (!file.contains("CJK"), *file));
+    files.first().map(PathBuf::from)
 }
 
 fn load_system_cjk_font() -> Option<FontData> {
@@ -38,3 +153,54 @@ fn load_system_cjk_font() -> Option<FontData> {
     }
     None
 }
+
+/// Every available candidate, since Arabic and Hebrew often ship as
+/// separate files.
+fn load_system_rtl_fonts() -> Vec<FontData> {
+    RTL_FONT_CANDIDATES
+        .iter()
+        .filter_map(|candidate| fs::read(candidate).ok())
+        .map(FontData::from_owned)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use eframe::egui::{Context, TextStyle, Theme, style::default_text_styles};
+
+    use std::path::PathBuf;
+
+    use super::{apply_ui_scale, pick_fontconfig_file};
+
+    #[test]
+    fn fontconfig_listing_prefers_cjk_families() {
+        let listing = "/usr/share/fonts/droid/DroidSansFallbackFull.ttf\n\
+/usr/share/fonts/x/font.pcf.gz\n\
+/usr/share/fonts/opentype/noto/NotoSansCJK-Regular.ttc\n";
+
+        assert_eq!(
+            pick_fontconfig_file(listing),
+            Some(PathBuf::from(
+                "/usr/share/fonts/opentype/noto/NotoSansCJK-Regular.ttc"
+            ))
+        );
+        assert_eq!(pick_fontconfig_file("/fonts/bitmap.pcf.gz\n"), None);
+    }
+
+    #[test]
+    fn ui_scale_sets_zoom_and_scales_text_from_defaults() {
+        let ctx = Context::default();
+        let body = default_text_styles()[&TextStyle::Body].size;
+
+        apply_ui_scale(&ctx, 150, 120);
+        apply_ui_scale(&ctx, 150, 120);
+        // The zoom takes effect at the end of a pass.
+        let _ = ctx.run(Default::default(), |_| {});
+
+        assert_eq!(ctx.zoom_factor(), 1.5);
+        for theme in [Theme::Dark, Theme::Light] {
+            let size = ctx.style_of(theme).text_styles[&TextStyle::Body].size;
+            assert!((size - body * 1.2).abs() < 0.01);
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use std::time::{Duration, Instant};
+
+const IDLE_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Tracks whether the user has stepped away (screen locked or no input for a
+/// while) so polling and alerts can pause overnight.
+#[derive(Default)]
+pub(super) struct IdleMonitor {
+    last_checked: Option<Instant>,
+    away: bool,
+}
+
+impl IdleMonitor {
+    /// Re-samples the session at most once per [`IDLE_CHECK_INTERVAL`] and
+    /// returns true on the check where the user comes back. `threshold` of
+    /// `None` disables pausing.
+    pub(super) fn refresh(&mut self, threshold: Option<Duration>) -> bool {
+        let Some(threshold) = threshold else {
+            let resumed = self.away;
+            self.away = false;
+            return resumed;
+        };
+        if self
+            .last_checked
+            .is_some_and(|checked| checked.elapsed() < IDLE_CHECK_INTERVAL)
+        {
+            return false;
+        }
+        self.last_checked = Some(Instant::now());
+
+        let away = screen_locked() || system_idle_time().is_some_and(|idle| idle >= threshold);
+        let resumed = self.away && !away;
+        self.away = away;
+        resumed
+    }
+
+    pub(super) fn is_away(&self) -> bool {
+        self.away
+    }
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+fn screen_locked() -> bool {
+    let Ok(session) = std::env::var("XDG_SESSION_ID") else {
+        return false;
+    };
+    command_stdout(
+        "loginctl",
+        &["show-session", &session, "-p", "LockedHint", "--value"],
+    )
+    .is_some_and(|output| output.trim() == "yes")
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+fn system_idle_time() -> Option<Duration> {
+    parse_xprintidle(&command_stdout("xprintidle", &[])?)
+}
+
+// macOS locks after the idle timeout in practice, so the HID idle time
+// covers both cases there.
+#[cfg(target_os = "macos")]
+fn screen_locked() -> bool {
+    false
+}
+
+#[cfg(target_os = "macos")]
+fn system_idle_time() -> Option<Duration> {
+    parse_hid_idle_time(&command_stdout("ioreg", &["-c", "IOHIDSystem", "-d", "4"])?)
+}
+
+#[cfg(not(unix))]
+fn screen_locked() -> bool {
+    false
+}
+
+#[cfg(not(unix))]
+fn system_idle_time() -> Option<Duration> {
+    None
+}
+
+#[cfg(unix)]
+fn command_stdout(program: &str, args: &[&str]) -> Option<String> {
+    let output = std::process::Command::new(program)
+        .args(args)
+        .stdin(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg_attr(not(all(unix, not(target_os = "macos"))), allow(dead_code))]
+fn parse_xprintidle(output: &str) -> Option<Duration> {
+    output.trim().parse().ok().map(Duration::from_millis)
+}
+
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn parse_hid_idle_time(output: &str) -> Option<Duration> {
+    output.lines().find_map(|line| {
+        let (_, value) = line.split_once("\"HIDIdleTime\" = ")?;
+        value.trim().parse().ok().map(Duration::from_nanos)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{IdleMonitor, parse_hid_idle_time, parse_xprintidle};
+
+    #[test]
+    fn idle_time_parsers_read_tool_output() {
+        assert_eq!(parse_xprintidle("125000\n"), Some(Duration::from_secs(125)));
+        assert_eq!(parse_xprintidle("error"), None);
+        assert_eq!(
+            parse_hid_idle_time("    | |   \"HIDIdleTime\" = 90000000000\n"),
+            Some(Duration::from_secs(90))
+        );
+    }
+
+    #[test]
+    fn disabling_the_threshold_resumes_immediately() {
+        let mut monitor = IdleMonitor {
+            last_checked: None,
+            away: true,
+        };
+
+        assert!(monitor.refresh(None));
+        assert!(!monitor.is_away());
+        assert!(!monitor.refresh(None));
+    }
+}
@@ -0,0 +1,231 @@
+//! Incremental refreshes: between full fetches, only threads updated since
+//! the last fetch are asked for and merged into the snapshot on hand.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::domain::{InboxSnapshot, NotificationItem, UnreadThreads};
+
+/// How far `since` reaches back before the last fetch, so clock skew
+/// between this machine and the server cannot drop an update.
+const SINCE_OVERLAP_MINUTES: i64 = 5;
+/// Threads marked done or unsubscribed elsewhere only drop out of a delta
+/// at the next full fetch; threads read elsewhere are caught by
+/// [`settle_read_elsewhere`] on every delta.
+const FULL_REFRESH_INTERVAL_MINUTES: i64 = 30;
+
+/// `since` for the next refresh, or `None` when a full fetch is due: there
+/// is no fetched snapshot yet, or the last full one is too old.
+pub(super) fn delta_since(
+    inbox: Option<&InboxSnapshot>,
+    inbox_is_cached: bool,
+    last_full_refresh: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+) -> Option<DateTime<Utc>> {
+    let inbox = inbox.filter(|_| !inbox_is_cached)?;
+    let last_full_refresh = last_full_refresh?;
+    if now - last_full_refresh >= Duration::minutes(FULL_REFRESH_INTERVAL_MINUTES) {
+        return None;
+    }
+    Some(inbox.fetched_at - Duration::minutes(SINCE_OVERLAP_MINUTES))
+}
+
+/// Folds the threads of a delta fetch into `previous`: changed threads are
+/// replaced, new ones inserted and the rest kept as they were. A thread
+/// read here stays read unless the delta shows activity after that.
+pub(super) fn merge_delta(
+    previous: Vec<NotificationItem>,
+    delta: Vec<NotificationItem>,
+) -> Vec<NotificationItem> {
+    let mut merged = previous;
+    let positions: HashMap<_, _> = merged
+        .iter()
+        .enumerate()
+        .map(|(index, item)| (item.thread_id.clone(), index))
+        .collect();
+    for mut item in delta {
+        let Some(&index) = positions.get(&item.thread_id) else {
+            merged.push(item);
+            continue;
+        };
+        let known = &merged[index];
+        if item.updated_at <= known.updated_at {
+            if !known.unread {
+                item.unread = false;
+                item.last_read_at = item.last_read_at.max(known.last_read_at);
+            }
+            // Details filled in by enrichment still hold for an unchanged
+            // thread.
+            item.head_ref = item.head_ref.or_else(|| known.head_ref.clone());
+            item.base_ref = item.base_ref.or_else(|| known.base_ref.clone());
+            item.latest_actor = item.latest_actor.or_else(|| known.latest_actor.clone());
+            item.my_review_status = item.my_review_status.or(known.my_review_status);
+        }
+        merged[index] = item;
+    }
+    merged.sort_by_key(|item| std::cmp::Reverse(item.updated_at));
+    merged
+}
+
+/// Marks threads read that are unread here but missing from `unread`, as
+/// far back as that page reaches. Reading a thread elsewhere leaves its
+/// update time alone, so no delta reports it.
+pub(super) fn settle_read_elsewhere(
+    notifications: &mut [NotificationItem],
+    unread: &UnreadThreads,
+) {
+    for item in notifications.iter_mut().filter(|item| item.unread) {
+        if !unread.thread_ids.contains(&item.thread_id)
+            && unread
+                .oldest_update
+                .is_none_or(|oldest| item.updated_at > oldest)
+        {
+            item.unread = false;
+        }
+    }
+}
+
+/// Keeps threads marked read within `window` of `now` read when a fetch
+/// still reports them unread without newer activity, and forgets marks
+/// older than the window.
+pub(super) fn settle_recent_reads(
+    notifications: &mut [NotificationItem],
+    recent_reads: &mut HashMap<String, DateTime<Utc>>,
+    now: DateTime<Utc>,
+    window: Duration,
+) {
+    recent_reads.retain(|_, marked_at| now - *marked_at < window);
+    for item in notifications.iter_mut().filter(|item| item.unread) {
+        if let Some(&marked_at) = recent_reads.get(&item.thread_id)
+            && item.updated_at <= marked_at
+        {
+            item.unread = false;
+            item.last_read_at = Some(marked_at);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeSet, HashMap};
+
+    use chrono::{Duration, Utc};
+
+    use super::{delta_since, merge_delta, settle_read_elsewhere, settle_recent_reads};
+    use crate::domain::{
+        InboxSnapshot, NotificationItem, SearchTotals, UnreadThreads, test_support,
+    };
+
+    fn item(thread_id: &str, minutes_ago: i64, unread: bool) -> NotificationItem {
+        NotificationItem {
+            subject_type: String::from("Issue"),
+            reason: String::from("mention"),
+            updated_at: Utc::now() - Duration::minutes(minutes_ago),
+            unread,
+            ..test_support::notification(thread_id)
+        }
+    }
+
+    #[test]
+    fn delta_updates_inserts_and_keeps_local_read_state() {
+        let mut read = item("1", 30, false);
+        read.head_ref = Some(String::from("feature"));
+        let stale = item("2", 60, true);
+        let untouched = item("3", 90, false);
+        let previous = vec![read.clone(), stale, untouched];
+
+        let mut replay = read.clone();
+        replay.unread = true;
+        replay.head_ref = None;
+        let mut bumped = item("2", 1, true);
+        bumped.title = String::from("Renamed");
+        let merged = merge_delta(previous, vec![replay, bumped, item("4", 5, true)]);
+
+        let ids: Vec<_> = merged.iter().map(|item| item.thread_id.as_str()).collect();
+        assert_eq!(ids, ["2", "4", "1", "3"]);
+        assert_eq!(merged[0].title, "Renamed");
+        assert!(!merged[2].unread);
+        assert_eq!(merged[2].head_ref.as_deref(), Some("feature"));
+    }
+
+    #[test]
+    fn threads_read_elsewhere_are_settled_as_far_as_the_unread_page_reaches() {
+        let notifications = vec![
+            item("1", 10, true),
+            item("2", 20, true),
+            item("3", 120, true),
+        ];
+        let unread = |oldest_update| UnreadThreads {
+            thread_ids: BTreeSet::from([String::from("1")]),
+            oldest_update,
+        };
+
+        let mut partial = notifications.clone();
+        settle_read_elsewhere(
+            &mut partial,
+            &unread(Some(Utc::now() - Duration::minutes(60))),
+        );
+        let states: Vec<_> = partial.iter().map(|item| item.unread).collect();
+        assert_eq!(states, [true, false, true]);
+
+        let mut complete = notifications;
+        settle_read_elsewhere(&mut complete, &unread(None));
+        let states: Vec<_> = complete.iter().map(|item| item.unread).collect();
+        assert_eq!(states, [true, false, false]);
+    }
+
+    #[test]
+    fn recently_read_threads_stay_read_until_new_activity_or_the_window_ends() {
+        let now = Utc::now();
+        let mut notifications = vec![item("1", 10, true), item("2", 10, true), item("3", 0, true)];
+        let mut recent_reads = HashMap::from([
+            (String::from("1"), now - Duration::seconds(5)),
+            (String::from("2"), now - Duration::minutes(5)),
+            (String::from("3"), now - Duration::seconds(5)),
+        ]);
+
+        settle_recent_reads(
+            &mut notifications,
+            &mut recent_reads,
+            now,
+            Duration::seconds(60),
+        );
+
+        assert!(!notifications[0].unread);
+        // Marked too long ago, and bumped after the mark.
+        assert!(notifications[1].unread && notifications[2].unread);
+        assert!(!recent_reads.contains_key("2"));
+    }
+
+    #[test]
+    fn full_fetch_is_due_without_a_live_snapshot_or_after_the_interval() {
+        let now = Utc::now();
+        let inbox = InboxSnapshot {
+            notifications: Vec::new(),
+            review_requests: Vec::new(),
+            mentions: Vec::new(),
+            recent_reviews: Vec::new(),
+            assigned_issues: Vec::new(),
+            authored_pull_requests: Vec::new(),
+            pull_request_statuses: Vec::new(),
+            fetched_at: now - Duration::minutes(2),
+            rate_limit: None,
+            search_totals: SearchTotals::default(),
+            unread_threads: None,
+        };
+        let recent = Some(now - Duration::minutes(10));
+
+        assert_eq!(
+            delta_since(Some(&inbox), false, recent, now),
+            Some(now - Duration::minutes(7))
+        );
+        assert_eq!(delta_since(None, false, recent, now), None);
+        assert_eq!(delta_since(Some(&inbox), true, recent, now), None);
+        assert_eq!(delta_since(Some(&inbox), false, None, now), None);
+        assert_eq!(
+            delta_since(Some(&inbox), false, Some(now - Duration::hours(1)), now),
+            None
+        );
+    }
+}
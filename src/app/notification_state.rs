@@ -1,8 +1,10 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
-use crate::domain::{InboxSnapshot, NotificationItem};
+use chrono::{DateTime, Utc};
 
-use super::{MENTION_REASONS, REVIEW_REQUEST_REASON};
+use crate::domain::{InboxSnapshot, NotificationItem, PullRequestKey};
+
+use super::{MENTION_REASONS, REVIEW_REQUEST_REASON, rules::RuleSet};
 
 pub(super) fn is_review_request(item: &NotificationItem) -> bool {
     item.reason == REVIEW_REQUEST_REASON
@@ -16,6 +18,12 @@ pub(super) fn is_other_notification(item: &NotificationItem) -> bool {
     !is_review_request(item) && !is_mention(item)
 }
 
+/// Discussions outside the review request and mention buckets get their
+/// own section, though they still count as notifications.
+pub(super) fn is_discussion(item: &NotificationItem) -> bool {
+    is_other_notification(item) && item.is_discussion()
+}
+
 pub(super) fn collect_new_notification_ids(
     previous: Option<&InboxSnapshot>,
     next: &InboxSnapshot,
@@ -37,6 +45,20 @@ pub(super) fn collect_new_notification_ids(
         .collect()
 }
 
+/// True when `next` lists a `(url, updated_at)` entry that `previous`
+/// lacked or had with an older update.
+pub(super) fn has_new_activity<'a>(
+    previous: impl IntoIterator<Item = (&'a str, DateTime<Utc>)>,
+    next: impl IntoIterator<Item = (&'a str, DateTime<Utc>)>,
+) -> bool {
+    let previous: HashMap<_, _> = previous.into_iter().collect();
+    next.into_iter().any(|(url, updated_at)| {
+        previous
+            .get(url)
+            .is_none_or(|seen_at| updated_at > *seen_at)
+    })
+}
+
 pub(super) struct SectionCounts {
     pub(super) unseen: usize,
     pub(super) updated: usize,
@@ -59,21 +81,26 @@ pub(super) struct SectionStats {
     pub(super) notifications: SectionCounts,
 }
 
-pub(super) fn section_stats(inbox: &InboxSnapshot) -> SectionStats {
-    let all_notifications: Vec<_> = inbox.notifications.iter().collect();
-    let review_requests: Vec<_> = inbox
+/// Counts per section, leaving out threads muted by a rule.
+pub(super) fn section_stats(inbox: &InboxSnapshot, rules: &RuleSet) -> SectionStats {
+    let all_notifications: Vec<_> = inbox
         .notifications
         .iter()
+        .filter(|item| !rules.mutes(item))
+        .collect();
+    let review_requests: Vec<_> = all_notifications
+        .iter()
+        .copied()
         .filter(|item| is_review_request(item))
         .collect();
-    let mentions: Vec<_> = inbox
-        .notifications
+    let mentions: Vec<_> = all_notifications
         .iter()
+        .copied()
         .filter(|item| is_mention(item))
         .collect();
-    let other: Vec<_> = inbox
-        .notifications
+    let other: Vec<_> = all_notifications
         .iter()
+        .copied()
         .filter(|item| is_other_notification(item))
         .collect();
 
@@ -91,32 +118,45 @@ pub(super) fn section_stats(inbox: &InboxSnapshot) -> SectionStats {
 }
 
 pub(super) fn pending_review_request_ids(inbox: &InboxSnapshot) -> HashSet<String> {
-    let requested_prs: HashSet<_> = inbox
-        .review_requests
-        .iter()
-        .filter_map(|request| request.pull_request_key())
-        .collect();
-    if requested_prs.is_empty() {
-        return HashSet::new();
-    }
+    requested_review_ids(inbox, false)
+}
+
+/// Threads of pull requests I already reviewed that ask for my review
+/// again.
+pub(super) fn re_requested_review_ids(inbox: &InboxSnapshot) -> HashSet<String> {
+    requested_review_ids(inbox, true)
+}
 
+/// Pull requests with an open request for my review, split by whether I
+/// reviewed them before; a review clears the request, so a request after
+/// one means the author asked again.
+pub(super) fn requested_reviews(inbox: &InboxSnapshot, reviewed: bool) -> HashSet<PullRequestKey> {
     let reviewed_prs: HashSet<_> = inbox
         .recent_reviews
         .iter()
         .filter_map(|review| review.pull_request_key())
         .collect();
+    inbox
+        .review_requests
+        .iter()
+        .filter_map(|request| request.pull_request_key())
+        .filter(|key| reviewed_prs.contains(key) == reviewed)
+        .collect()
+}
 
+fn requested_review_ids(inbox: &InboxSnapshot, reviewed: bool) -> HashSet<String> {
+    let requested_prs = requested_reviews(inbox, reviewed);
+    if requested_prs.is_empty() {
+        return HashSet::new();
+    }
     inbox
         .notifications
         .iter()
-        .filter_map(|item| {
-            let pr_key = item.pull_request_key()?;
-            if requested_prs.contains(&pr_key) && !reviewed_prs.contains(&pr_key) {
-                Some(item.thread_id.clone())
-            } else {
-                None
-            }
+        .filter(|item| {
+            item.pull_request_key()
+                .is_some_and(|key| requested_prs.contains(&key))
         })
+        .map(|item| item.thread_id.clone())
         .collect()
 }
 
@@ -125,6 +165,10 @@ pub(super) struct NotificationVisualState {
     pub(super) seen: bool,
     pub(super) needs_revisit: bool,
     pub(super) pending_review: bool,
+    /// My review was asked for again after I gave one.
+    pub(super) re_requested: bool,
+    /// Matched by a highlight rule.
+    pub(super) highlighted: bool,
 }
 
 pub(super) fn base_notification_state(item: &NotificationItem) -> NotificationVisualState {
@@ -137,6 +181,8 @@ pub(super) fn base_notification_state(item: &NotificationItem) -> NotificationVi
         seen: !item.unread && !needs_revisit,
         needs_revisit,
         pending_review: false,
+        re_requested: false,
+        highlighted: false,
     }
 }
 
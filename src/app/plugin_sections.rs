@@ -0,0 +1,129 @@
+use std::{
+    cmp::Reverse,
+    path::Path,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use chrono::{DateTime, Utc};
+use reqwest::Client;
+
+use crate::{
+    plugins::{self, Plugin, PluginError, PluginRow},
+    task::Task,
+};
+
+const PLUGIN_REFRESH_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// One plugin's section and the fetch in flight for it.
+pub(super) struct PluginSection {
+    plugin: Arc<Plugin>,
+    job: Option<Task<Result<Vec<PluginRow>, PluginError>>>,
+    last_started: Option<Instant>,
+    pub(super) rows: Vec<PluginRow>,
+    pub(super) checked_at: Option<DateTime<Utc>>,
+    pub(super) error: Option<String>,
+}
+
+impl PluginSection {
+    pub(super) fn name(&self) -> &str {
+        &self.plugin.manifest.name
+    }
+
+    fn maybe_start(&mut self, client: &Client) {
+        if self.job.is_some()
+            || self
+                .last_started
+                .is_some_and(|started| started.elapsed() < PLUGIN_REFRESH_INTERVAL)
+        {
+            return;
+        }
+        self.last_started = Some(Instant::now());
+        let client = client.clone();
+        let plugin = Arc::clone(&self.plugin);
+        self.job = Some(Task::spawn(move |_| async move {
+            plugins::fetch_rows(&client, plugin).await
+        }));
+    }
+
+    fn poll(&mut self) {
+        let Some(job) = &self.job else {
+            return;
+        };
+        let Some(result) = job.try_take() else {
+            return;
+        };
+        self.job = None;
+        match result {
+            Ok(Ok(mut rows)) => {
+                rows.sort_by_key(|row| Reverse(row.updated_at));
+                self.rows = rows;
+                self.checked_at = Some(Utc::now());
+                self.error = None;
+            }
+            Ok(Err(err)) => self.error = Some(err.to_string()),
+            Err(_) => self.error = Some(String::from("The plugin's background task stopped.")),
+        }
+    }
+}
+
+/// Sections added by WebAssembly plugins from the data directory.
+#[derive(Default)]
+pub(super) struct PluginSectionsState {
+    client: Option<Client>,
+    pub(super) sections: Vec<PluginSection>,
+    /// Plugins that failed to load, shown once above the sections.
+    pub(super) load_errors: Vec<String>,
+}
+
+impl PluginSectionsState {
+    pub(super) fn load(dir: &Path) -> Self {
+        let (plugins, errors) = plugins::load_plugins(dir);
+        Self {
+            client: None,
+            sections: plugins
+                .into_iter()
+                .map(|plugin| PluginSection {
+                    plugin: Arc::new(plugin),
+                    job: None,
+                    last_started: None,
+                    rows: Vec::new(),
+                    checked_at: None,
+                    error: None,
+                })
+                .collect(),
+            load_errors: errors.iter().map(ToString::to_string).collect(),
+        }
+    }
+
+    /// Picks up changed network preferences on the next fetch.
+    pub(super) fn reset_client(&mut self) {
+        self.client = None;
+    }
+
+    /// Collects finished fetches and, unless the user is away, starts the
+    /// ones that are due.
+    pub(super) fn poll(&mut self, away: bool) {
+        for section in &mut self.sections {
+            section.poll();
+        }
+        if away || self.sections.is_empty() {
+            return;
+        }
+        let client = match &mut self.client {
+            Some(client) => client,
+            None => match crate::github::build_client() {
+                Ok(client) => self.client.insert(client),
+                Err(err) => {
+                    for section in &mut self.sections {
+                        section.error = Some(err.to_string());
+                    }
+                    return;
+                }
+            },
+        };
+        for section in &mut self.sections {
+            section.maybe_start(client);
+        }
+    }
+}
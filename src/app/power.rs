@@ -0,0 +1,149 @@
+use std::time::{Duration, Instant};
+
+const POWER_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) struct PowerStatus {
+    pub(super) on_battery: bool,
+    pub(super) percent: u8,
+}
+
+/// Samples the battery at most once per [`POWER_CHECK_INTERVAL`]. Desktops
+/// and unsupported platforms simply never report a status.
+#[derive(Default)]
+pub(super) struct PowerMonitor {
+    last_checked: Option<Instant>,
+    status: Option<PowerStatus>,
+}
+
+impl PowerMonitor {
+    pub(super) fn refresh(&mut self) {
+        if self
+            .last_checked
+            .is_some_and(|checked| checked.elapsed() < POWER_CHECK_INTERVAL)
+        {
+            return;
+        }
+        self.last_checked = Some(Instant::now());
+        self.status = read_power_status();
+    }
+
+    pub(super) fn status(&self) -> Option<PowerStatus> {
+        self.status
+    }
+
+    /// True while running on battery at or below `threshold` percent. A
+    /// threshold of zero turns power saving off.
+    pub(super) fn is_saving(&self, threshold: u8) -> bool {
+        threshold > 0
+            && self
+                .status
+                .is_some_and(|status| status.on_battery && status.percent <= threshold)
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn read_power_status() -> Option<PowerStatus> {
+    let supplies = std::fs::read_dir("/sys/class/power_supply").ok()?;
+    supplies.flatten().find_map(|entry| {
+        let path = entry.path();
+        let kind = std::fs::read_to_string(path.join("type")).ok()?;
+        if kind.trim() != "Battery" {
+            return None;
+        }
+        parse_sysfs_battery(
+            &std::fs::read_to_string(path.join("status")).ok()?,
+            &std::fs::read_to_string(path.join("capacity")).ok()?,
+        )
+    })
+}
+
+#[cfg(target_os = "macos")]
+fn read_power_status() -> Option<PowerStatus> {
+    let output = std::process::Command::new("pmset")
+        .args(["-g", "batt"])
+        .output()
+        .ok()?;
+    parse_pmset_battery(&String::from_utf8_lossy(&output.stdout))
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn read_power_status() -> Option<PowerStatus> {
+    None
+}
+
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_sysfs_battery(status: &str, capacity: &str) -> Option<PowerStatus> {
+    Some(PowerStatus {
+        on_battery: status.trim() == "Discharging",
+        percent: capacity.trim().parse().ok()?,
+    })
+}
+
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn parse_pmset_battery(output: &str) -> Option<PowerStatus> {
+    let on_battery = output.contains("'Battery Power'");
+    let percent = output
+        .split_whitespace()
+        .find_map(|word| word.trim_end_matches(';').strip_suffix('%'))?
+        .parse()
+        .ok()?;
+    Some(PowerStatus {
+        on_battery,
+        percent,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PowerMonitor, PowerStatus, parse_pmset_battery, parse_sysfs_battery};
+
+    #[test]
+    fn sysfs_battery_reports_discharging_capacity() {
+        assert_eq!(
+            parse_sysfs_battery("Discharging\n", "18\n"),
+            Some(PowerStatus {
+                on_battery: true,
+                percent: 18,
+            })
+        );
+        assert_eq!(
+            parse_sysfs_battery("Charging\n", "18\n").map(|status| status.on_battery),
+            Some(false)
+        );
+    }
+
+    #[test]
+    fn pmset_battery_reports_power_source_and_percent() {
+        let output = "Now drawing from 'Battery Power'\n -InternalBattery-0 (id=1234)\t42%; discharging; 2:10 remaining present: true\n";
+
+        assert_eq!(
+            parse_pmset_battery(output),
+            Some(PowerStatus {
+                on_battery: true,
+                percent: 42,
+            })
+        );
+    }
+
+    #[test]
+    fn saving_applies_on_low_battery_only() {
+        let mut monitor = PowerMonitor {
+            last_checked: None,
+            status: Some(PowerStatus {
+                on_battery: true,
+                percent: 20,
+            }),
+        };
+
+        assert!(monitor.is_saving(30));
+        assert!(!monitor.is_saving(10));
+        assert!(!monitor.is_saving(0));
+
+        monitor.status = Some(PowerStatus {
+            on_battery: false,
+            percent: 20,
+        });
+        assert!(!monitor.is_saving(30));
+    }
+}
@@ -0,0 +1,80 @@
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use reqwest::Client;
+
+use crate::{
+    domain::{GitHubAccount, SearchHit},
+    github::{self, FetchError},
+    task::Task,
+};
+
+const RADAR_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// Polls the configured org for release blockers, independent of any
+/// account's notification feed.
+#[derive(Default)]
+pub(super) struct ReleaseRadarState {
+    job: Option<Task<Result<Vec<SearchHit>, FetchError>>>,
+    last_started: Option<Instant>,
+    /// Query of the last search, so a changed org or label searches again.
+    query: Option<String>,
+    pub(super) hits: Vec<SearchHit>,
+    pub(super) checked_at: Option<DateTime<Utc>>,
+    pub(super) error: Option<String>,
+}
+
+impl ReleaseRadarState {
+    pub(super) fn maybe_start(
+        &mut self,
+        client: &Client,
+        profile: &GitHubAccount,
+        query: String,
+        max_pages: usize,
+    ) {
+        let changed = self.query.as_ref() != Some(&query);
+        if self.job.is_some()
+            || (!changed
+                && self
+                    .last_started
+                    .is_some_and(|started| started.elapsed() < RADAR_INTERVAL))
+        {
+            return;
+        }
+        if changed {
+            self.hits.clear();
+            self.error = None;
+        }
+        self.last_started = Some(Instant::now());
+        self.query = Some(query.clone());
+        let client = client.clone();
+        let profile = profile.clone();
+        self.job = Some(Task::spawn(move |_| async move {
+            github::fetch_saved_search(&client, &profile, &query, max_pages).await
+        }));
+    }
+
+    /// Forgets results once the radar is switched off.
+    pub(super) fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    pub(super) fn poll(&mut self) {
+        let Some(job) = &self.job else {
+            return;
+        };
+        let Some(result) = job.try_take() else {
+            return;
+        };
+        self.job = None;
+        match result {
+            Ok(Ok(hits)) => {
+                self.hits = hits;
+                self.checked_at = Some(Utc::now());
+                self.error = None;
+            }
+            Ok(Err(err)) => self.error = Some(err.to_string()),
+            Err(_) => self.error = Some(FetchError::BackgroundWorkerGone.to_string()),
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use chrono::{
+    DateTime, Datelike, Duration, Local, NaiveDateTime, NaiveTime, TimeZone, Utc, Weekday,
+};
+
+pub(super) const DEFAULT_DUE_INPUT: &str = "tomorrow 09:00";
+pub(super) const DUE_INPUT_HINT: &str = "tomorrow 09:00, fri 10:00, 2026-03-06 14:30, 16:00, in 2h";
+
+pub(super) enum ReminderAction {
+    Open(u64),
+    /// Pushes the reminder back; the string is a due expression for
+    /// [`due_at`].
+    Snooze(u64, &'static str),
+    Done(u64),
+}
+
+/// Form for a new reminder, prefilled when started from a notification.
+pub(super) struct ReminderEditor {
+    pub(super) title: String,
+    pub(super) url: String,
+    pub(super) due_input: String,
+    pub(super) login: Option<String>,
+    pub(super) thread_id: Option<String>,
+    pub(super) form_error: Option<String>,
+}
+
+impl ReminderEditor {
+    pub(super) fn new(
+        title: String,
+        url: String,
+        login: Option<String>,
+        thread_id: Option<String>,
+    ) -> Self {
+        Self {
+            title,
+            url,
+            due_input: DEFAULT_DUE_INPUT.to_owned(),
+            login,
+            thread_id,
+            form_error: None,
+        }
+    }
+}
+
+/// Resolves a due expression typed by the user against the local clock.
+pub(super) fn due_at(input: &str) -> Result<DateTime<Utc>, String> {
+    parse_due(input, Local::now().naive_local())
+        .and_then(|due| Local.from_local_datetime(&due).earliest())
+        .map(|due| due.with_timezone(&Utc))
+        .ok_or_else(|| format!("Could not read {input:?}; try {DUE_INPUT_HINT}."))
+}
+
+/// Accepts `2026-03-06 10:00`, `10:00` (today, or tomorrow once passed),
+/// `today 10:00`, `tomorrow 10:00`, `friday 10:00` / `fri 10:00` (the next
+/// such day), and `in 30m` / `in 2h` / `in 3d`.
+fn parse_due(input: &str, now: NaiveDateTime) -> Option<NaiveDateTime> {
+    let input = input.trim().to_ascii_lowercase();
+    if let Some(offset) = input.strip_prefix("in ") {
+        return parse_offset(offset.trim()).map(|offset| now + offset);
+    }
+    if let Ok(due) = NaiveDateTime::parse_from_str(&input, "%Y-%m-%d %H:%M") {
+        return Some(due);
+    }
+
+    let (day, time) = match input.rsplit_once(' ') {
+        Some((day, time)) => (Some(day.trim()), time),
+        None => (None, input.as_str()),
+    };
+    let time = NaiveTime::parse_from_str(time, "%H:%M").ok()?;
+    let today = now.date();
+    let date = match day {
+        None => {
+            let due = today.and_time(time);
+            return Some(if due > now {
+                due
+            } else {
+                due + Duration::days(1)
+            });
+        }
+        Some("today") => today,
+        Some("tomorrow") => today.succ_opt()?,
+        Some(name) => {
+            let weekday: Weekday = name.parse().ok()?;
+            let days_ahead =
+                (7 + weekday.num_days_from_monday() - today.weekday().num_days_from_monday()) % 7;
+            let date = today + Duration::days(i64::from(days_ahead));
+            if date.and_time(time) > now {
+                date
+            } else {
+                date + Duration::days(7)
+            }
+        }
+    };
+    Some(date.and_time(time))
+}
+
+fn parse_offset(offset: &str) -> Option<Duration> {
+    let unit = offset.chars().last()?;
+    let amount: i64 = offset[..offset.len() - unit.len_utf8()]
+        .trim()
+        .parse()
+        .ok()?;
+    match unit {
+        'm' => Some(Duration::minutes(amount)),
+        'h' => Some(Duration::hours(amount)),
+        'd' => Some(Duration::days(amount)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDateTime;
+
+    use super::parse_due;
+
+    fn at(value: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    // 2026-03-04 is a Wednesday.
+    const NOW: &str = "2026-03-04 12:00";
+
+    #[test]
+    fn parses_absolute_and_relative_days() {
+        let now = at(NOW);
+
+        assert_eq!(
+            parse_due("2026-03-06 10:00", now),
+            Some(at("2026-03-06 10:00"))
+        );
+        assert_eq!(
+            parse_due("tomorrow 09:00", now),
+            Some(at("2026-03-05 09:00"))
+        );
+        assert_eq!(parse_due("Friday 10:00", now), Some(at("2026-03-06 10:00")));
+        assert_eq!(parse_due("wed 10:00", now), Some(at("2026-03-11 10:00")));
+        assert_eq!(parse_due("wed 13:00", now), Some(at("2026-03-04 13:00")));
+    }
+
+    #[test]
+    fn bare_times_roll_over_to_tomorrow() {
+        let now = at(NOW);
+
+        assert_eq!(parse_due("16:30", now), Some(at("2026-03-04 16:30")));
+        assert_eq!(parse_due("08:00", now), Some(at("2026-03-05 08:00")));
+    }
+
+    #[test]
+    fn parses_offsets_and_rejects_garbage() {
+        let now = at(NOW);
+
+        assert_eq!(parse_due("in 2h", now), Some(at("2026-03-04 14:00")));
+        assert_eq!(parse_due("in 3d", now), Some(at("2026-03-07 12:00")));
+        assert_eq!(parse_due("someday", now), None);
+        assert_eq!(parse_due("in 2 weeks", now), None);
+    }
+}
@@ -1,14 +1,12 @@
-use std::{
-    sync::mpsc::{self, Receiver, TryRecvError},
-    thread,
-    time::Duration,
-};
+use std::time::Duration;
 
 use chrono::Utc;
+use reqwest::Client;
 
 use crate::{
-    domain::{GitHubAccount, RepoPullRequestSnapshot},
+    domain::{GitHubAccount, RepoPullRequestSnapshot, SearchMode},
     github::{self, FetchError},
+    task::{Task, TaskLost},
 };
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
@@ -23,10 +21,13 @@ pub(super) struct RepoState {
     pub(super) repo: String,
     pub(super) snapshot: Option<RepoPullRequestSnapshot>,
     pub(super) last_error: Option<String>,
-    pub(super) pending_job: Option<PendingRepoJob>,
+    pub(super) pending_job: Option<Task<github::RepoFetchOutcome>>,
     pub(super) search_query: String,
+    pub(super) search_mode: SearchMode,
     pub(super) sort_mode: RepoSortMode,
     pub(super) loaded_by_login: Option<String>,
+    pub(super) pending_batch_approve: Option<PendingBatchApproveJob>,
+    pub(super) batch_approve_status: Option<String>,
     pending_login: Option<String>,
 }
 
@@ -38,16 +39,62 @@ impl RepoState {
             last_error: None,
             pending_job: None,
             search_query: String::new(),
+            search_mode: SearchMode::default(),
             sort_mode: RepoSortMode::Default,
             loaded_by_login: None,
+            pending_batch_approve: None,
+            batch_approve_status: None,
             pending_login: None,
         }
     }
 
-    pub(super) fn start_refresh(&mut self, profile: GitHubAccount) {
+    pub(super) fn start_batch_approve(
+        &mut self,
+        client: Client,
+        profile: GitHubAccount,
+        pr_numbers: Vec<u64>,
+    ) {
+        if pr_numbers.is_empty() || self.pending_batch_approve.is_some() {
+            return;
+        }
+        self.batch_approve_status = None;
+        let task = {
+            let client = client.clone();
+            let profile = profile.clone();
+            let repo = self.repo.clone();
+            Task::spawn(move |_| async move {
+                github::approve_passing_pull_requests(&client, &profile, &repo, &pr_numbers).await
+            })
+        };
+        self.pending_batch_approve = Some(PendingBatchApproveJob {
+            client,
+            profile,
+            task,
+        });
+    }
+
+    pub(super) fn start_refresh(&mut self, client: Client, profile: GitHubAccount) {
         self.last_error = None;
         self.pending_login = Some(profile.login.clone());
-        self.pending_job = Some(PendingRepoJob::spawn(profile, self.repo.clone()));
+        let repo = self.repo.clone();
+        self.pending_job = Some(Task::spawn(move |_| async move {
+            github::fetch_repo_pull_requests(&client, &profile, &repo).await
+        }));
+    }
+
+    /// Aborts work started with `login`'s token; dropping a task cancels it.
+    pub(super) fn cancel_jobs_for(&mut self, login: &str) {
+        if self.pending_login.as_deref() == Some(login) {
+            self.pending_job = None;
+            self.pending_login = None;
+        }
+        if self
+            .pending_batch_approve
+            .as_ref()
+            .is_some_and(|job| job.profile.login == login)
+        {
+            self.pending_batch_approve = None;
+        }
     }
 
     pub(super) fn poll_job(&mut self) {
@@ -55,7 +102,7 @@ impl RepoState {
             && let Some(result) = job.try_take()
         {
             self.pending_job = None;
-            match result {
+            match result.unwrap_or_else(|TaskLost| Err(FetchError::BackgroundWorkerGone)) {
                 Ok(snapshot) => {
                     self.snapshot = Some(snapshot);
                     self.loaded_by_login = self.pending_login.take();
@@ -67,6 +114,19 @@ impl RepoState {
                 }
             }
         }
+
+        if let Some(job) = &self.pending_batch_approve
+            && let Some(result) = job.task.try_take()
+            && let Some(job) = self.pending_batch_approve.take()
+        {
+            self.batch_approve_status = Some(
+                match result.unwrap_or_else(|TaskLost| Err(FetchError::BackgroundWorkerGone)) {
+                    Ok(summary) => batch_approve_status_text(&summary),
+                    Err(err) => format!("Batch approve failed: {err}"),
+                },
+            );
+            self.start_refresh(job.client, job.profile);
+        }
     }
 
     pub(super) fn needs_refresh(&self, threshold: Duration) -> bool {
@@ -85,36 +145,32 @@ impl RepoState {
     }
 }
 
-pub(super) struct PendingRepoJob {
-    receiver: Receiver<github::RepoFetchOutcome>,
+/// Batch approval in flight, with the account that reloads the list after.
+pub(super) struct PendingBatchApproveJob {
+    client: Client,
+    profile: GitHubAccount,
+    task: Task<github::BatchApproveOutcome>,
 }
 
-impl PendingRepoJob {
-    fn spawn(profile: GitHubAccount, repo: String) -> Self {
-        let (tx, rx) = mpsc::channel();
-        thread::spawn(move || {
-            let outcome = (|| -> github::RepoFetchOutcome {
-                let client = github::build_client()?;
-                github::fetch_repo_pull_requests(&client, &profile, &repo)
-            })();
-            let _ = tx.send(outcome);
-        });
-        Self { receiver: rx }
-    }
-
-    fn try_take(&self) -> Option<github::RepoFetchOutcome> {
-        match self.receiver.try_recv() {
-            Ok(result) => Some(result),
-            Err(TryRecvError::Empty) => None,
-            Err(TryRecvError::Disconnected) => Some(Err(FetchError::BackgroundWorkerGone)),
-        }
+fn batch_approve_status_text(summary: &github::BatchApproveSummary) -> String {
+    let mut text = format!("Approved {} pull request(s).", summary.approved.len());
+    if !summary.skipped.is_empty() {
+        let skipped = summary
+            .skipped
+            .iter()
+            .map(|number| format!("#{number}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        text.push_str(&format!(" Skipped without passing checks: {skipped}."));
     }
+    text
 }
 
 #[cfg(test)]
 mod tests {
-    use super::RepoState;
+    use super::{RepoState, batch_approve_status_text};
     use crate::domain::RepoPullRequestSnapshot;
+    use crate::github::BatchApproveSummary;
     use chrono::Utc;
     use std::time::Duration;
 
@@ -130,4 +186,17 @@ mod tests {
         assert!(state.should_refresh_with("trinity", Duration::from_secs(60)));
         assert!(!state.should_refresh_with("neo", Duration::from_secs(60)));
     }
+
+    #[test]
+    fn batch_approve_status_lists_skipped_pull_requests() {
+        let summary = BatchApproveSummary {
+            approved: vec![1, 2],
+            skipped: vec![7],
+        };
+
+        assert_eq!(
+            batch_approve_status_text(&summary),
+            "Approved 2 pull request(s). Skipped without passing checks: #7."
+        );
+    }
 }
@@ -0,0 +1,251 @@
+use std::{
+    fmt::Write as _,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Local};
+
+use crate::domain::{DoneThreads, InboxSnapshot, NotificationItem};
+
+use super::{SectionKind, rules::RuleSet, time::format_local_timestamp};
+
+/// Folder under the data directory that exported reports go to.
+const REPORTS_DIR: &str = "reports";
+
+const REPORT_STYLE: &str = "body{font-family:system-ui,sans-serif;max-width:960px;margin:2em auto;padding:0 1em}\
+table{border-collapse:collapse;width:100%;margin-bottom:1.5em}\
+th,td{text-align:left;padding:4px 8px;border-bottom:1px solid #ddd}\
+.unread{font-weight:600}.muted{color:#777}";
+
+/// What one account contributes to a report. Tokens, reminders and thread
+/// stages stay out of it on purpose.
+pub(super) struct ReportAccount<'a> {
+    pub(super) login: &'a str,
+    pub(super) inbox: &'a InboxSnapshot,
+    pub(super) done: &'a DoneThreads,
+}
+
+/// A self-contained HTML page with each account's sections, their counts
+/// and links, ready to paste into a wiki.
+pub(super) fn render_report<'a>(
+    accounts: impl IntoIterator<Item = ReportAccount<'a>>,
+    rules: &RuleSet,
+    generated_at: DateTime<Local>,
+) -> String {
+    let mut html = String::new();
+    let generated = generated_at.format("%Y-%m-%d %H:%M");
+    let _ = write!(
+        html,
+        "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>Reminder report {generated}</title>\
+<style>{REPORT_STYLE}</style></head><body>\n<h1>Reminder report</h1>\n<p class=\"muted\">Generated {generated}</p>\n"
+    );
+    for account in accounts {
+        let _ = writeln!(html, "<h2>@{}</h2>", escape(account.login));
+        let visible: Vec<_> = account
+            .inbox
+            .notifications
+            .iter()
+            .filter(|item| !rules.mutes(item) && !account.done.hides(item))
+            .collect();
+        for kind in [
+            SectionKind::ReviewRequests,
+            SectionKind::Mentions,
+            SectionKind::Notifications,
+        ] {
+            let items: Vec<_> = visible
+                .iter()
+                .copied()
+                .filter(|item| kind.contains(item))
+                .collect();
+            write_notification_section(&mut html, kind.title(), &items);
+        }
+        write_link_section(
+            &mut html,
+            SectionKind::AuthoredPullRequests.title(),
+            account.inbox.authored_pull_requests.iter().map(|pr| {
+                (
+                    pr.repo.as_str(),
+                    format!("#{} {}", pr.number, pr.title),
+                    pr.url.as_str(),
+                )
+            }),
+        );
+        write_link_section(
+            &mut html,
+            SectionKind::Assigned.title(),
+            account.inbox.assigned_issues.iter().map(|issue| {
+                (
+                    issue.repo.as_str(),
+                    format!("#{} {}", issue.number, issue.title),
+                    issue.url.as_str(),
+                )
+            }),
+        );
+    }
+    html.push_str("</body></html>\n");
+    html
+}
+
+fn write_notification_section(html: &mut String, title: &str, items: &[&NotificationItem]) {
+    let unread = items.iter().filter(|item| item.unread).count();
+    let _ = writeln!(
+        html,
+        "<h3>{} <span class=\"muted\">({} · {unread} unread)</span></h3>",
+        escape(title),
+        items.len()
+    );
+    if items.is_empty() {
+        html.push_str("<p class=\"muted\">Nothing here.</p>\n");
+        return;
+    }
+    html.push_str(
+        "<table><tr><th>Repository</th><th>Subject</th><th>Reason</th><th>Updated</th></tr>\n",
+    );
+    for item in items {
+        let subject = match &item.url {
+            Some(url) => link(url, &item.title),
+            None => escape(&item.title),
+        };
+        let _ = writeln!(
+            html,
+            "<tr{}><td>{}</td><td>{subject}</td><td>{}</td><td>{}</td></tr>",
+            if item.unread { " class=\"unread\"" } else { "" },
+            escape(&item.repo),
+            escape(&item.reason),
+            format_local_timestamp(item.updated_at, "%Y-%m-%d %H:%M"),
+        );
+    }
+    html.push_str("</table>\n");
+}
+
+fn write_link_section<'a>(
+    html: &mut String,
+    title: &str,
+    rows: impl ExactSizeIterator<Item = (&'a str, String, &'a str)>,
+) {
+    let _ = writeln!(
+        html,
+        "<h3>{} <span class=\"muted\">({})</span></h3>",
+        escape(title),
+        rows.len()
+    );
+    if rows.len() == 0 {
+        html.push_str("<p class=\"muted\">Nothing here.</p>\n");
+        return;
+    }
+    html.push_str("<table><tr><th>Repository</th><th>Title</th></tr>\n");
+    for (repo, title, url) in rows {
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td>{}</td></tr>",
+            escape(repo),
+            link(url, &title)
+        );
+    }
+    html.push_str("</table>\n");
+}
+
+fn link(url: &str, text: &str) -> String {
+    format!("<a href=\"{}\">{}</a>", escape(url), escape(text))
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            ch => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+/// Writes `html` to a timestamped file under `reports/` in `data_dir`.
+pub(super) fn write_report(
+    data_dir: &Path,
+    html: &str,
+    at: DateTime<Local>,
+) -> io::Result<PathBuf> {
+    let dir = data_dir.join(REPORTS_DIR);
+    fs::create_dir_all(&dir)?;
+    let path = dir.join(format!("report-{}.html", at.format("%Y%m%d-%H%M%S")));
+    fs::write(&path, html)?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Local, Utc};
+
+    use super::{ReportAccount, render_report};
+    use crate::{
+        app::rules::RuleSet,
+        domain::{
+            AuthoredPullRequest, DoneThreads, InboxSnapshot, NotificationItem, SearchTotals,
+            test_support,
+        },
+    };
+
+    fn notification(thread_id: &str, reason: &str, title: &str) -> NotificationItem {
+        NotificationItem {
+            repo: String::from("acme/api"),
+            title: title.to_owned(),
+            url: Some(format!("https://github.com/acme/api/pull/{thread_id}")),
+            subject_type: String::from("PullRequest"),
+            reason: reason.to_owned(),
+            ..test_support::notification(thread_id)
+        }
+    }
+
+    #[test]
+    fn report_lists_sections_with_escaped_links_and_skips_done_threads() {
+        let inbox = InboxSnapshot {
+            notifications: vec![
+                notification("1", "review_requested", "Fix <script> & stuff"),
+                notification("2", "mention", "Old thread"),
+            ],
+            review_requests: Vec::new(),
+            mentions: Vec::new(),
+            recent_reviews: Vec::new(),
+            assigned_issues: Vec::new(),
+            authored_pull_requests: vec![AuthoredPullRequest {
+                repo: String::from("acme/web"),
+                number: 7,
+                title: String::from("Add dark mode"),
+                url: String::from("https://github.com/acme/web/pull/7"),
+                updated_at: Utc::now(),
+                draft: false,
+                labels: Vec::new(),
+            }],
+            pull_request_statuses: Vec::new(),
+            fetched_at: Utc::now(),
+            rate_limit: None,
+            search_totals: SearchTotals::default(),
+            unread_threads: None,
+        };
+        let mut done = DoneThreads::default();
+        done.mark(String::from("2"), Utc::now());
+
+        let html = render_report(
+            [ReportAccount {
+                login: "neo",
+                inbox: &inbox,
+                done: &done,
+            }],
+            &RuleSet::default(),
+            Local::now(),
+        );
+
+        assert!(html.contains("<h2>@neo</h2>"));
+        assert!(html.contains("Review requests <span class=\"muted\">(1 · 1 unread)"));
+        assert!(html.contains("Fix &lt;script&gt; &amp; stuff"));
+        assert!(html.contains("href=\"https://github.com/acme/api/pull/1\""));
+        assert!(!html.contains("Old thread"));
+        assert!(html.contains("#7 Add dark mode"));
+    }
+}
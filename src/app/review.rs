@@ -1089,6 +1089,15 @@ fn review_output_layout_job(review_output: &ReviewOutputState, ui: &egui::Ui) ->
     job
 }
 
+/// Bytes of captured output text, for the diagnostics readout.
+pub(super) fn review_output_len(review_output: &ReviewOutputState) -> usize {
+    review_output
+        .styled_spans
+        .iter()
+        .map(|span| span.text.len())
+        .sum()
+}
+
 #[cfg(test)]
 pub(super) fn review_output_plain_text(review_output: &ReviewOutputState) -> String {
     review_output
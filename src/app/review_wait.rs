@@ -0,0 +1,152 @@
+//! How long review requests have been waiting on the user, counted from
+//! when the pending request was made.
+
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::domain::{InboxSnapshot, PullRequestKey};
+
+use super::alerts::DesktopAlert;
+
+/// When each review-request notification started waiting, by thread id.
+pub(super) fn review_wait_starts(inbox: &InboxSnapshot) -> HashMap<String, DateTime<Utc>> {
+    let requested: HashMap<_, _> = inbox
+        .review_requests
+        .iter()
+        .filter_map(|request| Some((request.pull_request_key()?, request.requested_at?)))
+        .collect();
+    inbox
+        .notifications
+        .iter()
+        .filter_map(|item| {
+            let since = requested.get(&item.pull_request_key()?)?;
+            Some((item.thread_id.clone(), *since))
+        })
+        .collect()
+}
+
+/// 0 within `threshold_hours`, then 1, 2, 3… each time the wait doubles
+/// past it. A zero threshold never counts as overdue.
+pub(super) fn overdue_level(waited: Duration, threshold_hours: u32) -> u32 {
+    let threshold = i64::from(threshold_hours) * 3600;
+    let waited = waited.num_seconds();
+    if threshold == 0 || waited < threshold {
+        return 0;
+    }
+    let mut level = 1;
+    let mut bound = threshold * 2;
+    while waited >= bound {
+        level += 1;
+        bound = bound.saturating_mul(2);
+    }
+    level
+}
+
+/// Hours up to two days, then days and hours: `5h`, `47h`, `3d 4h`.
+pub(super) fn format_wait(waited: Duration) -> String {
+    let hours = waited.num_hours().max(0);
+    if hours < 48 {
+        format!("{hours}h")
+    } else {
+        format!("{}d {}h", hours / 24, hours % 24)
+    }
+}
+
+/// How overdue each review request was when the user was last reminded.
+#[derive(Debug, Default)]
+pub(super) struct ReviewWaitReminders {
+    levels: HashMap<PullRequestKey, u32>,
+}
+
+impl ReviewWaitReminders {
+    /// One alert per review request that became overdue, or saw its wait
+    /// double, since the last check. Answered requests are forgotten.
+    pub(super) fn check(
+        &mut self,
+        login: &str,
+        inbox: &InboxSnapshot,
+        threshold_hours: u32,
+        now: DateTime<Utc>,
+    ) -> Vec<DesktopAlert> {
+        let mut alerts = Vec::new();
+        let mut pending = HashSet::new();
+        for request in &inbox.review_requests {
+            let (Some(key), Some(since)) = (request.pull_request_key(), request.requested_at)
+            else {
+                continue;
+            };
+            let level = overdue_level(now - since, threshold_hours);
+            pending.insert(key.clone());
+            let reminded = self.levels.entry(key.clone()).or_default();
+            if level > *reminded {
+                let (repo, number) = &key;
+                alerts.push(DesktopAlert {
+                    key: format!("{login}/review-wait/{repo}#{number}/{level}"),
+                    title: format!(
+                        "{login}: review waiting {} in {repo}",
+                        format_wait(now - since)
+                    ),
+                    body: request.title.clone(),
+                });
+            }
+            *reminded = level;
+        }
+        self.levels.retain(|key, _| pending.contains(key));
+        alerts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+
+    use super::{ReviewWaitReminders, format_wait, overdue_level};
+    use crate::domain::{InboxSnapshot, ReviewRequest, SearchTotals};
+
+    #[test]
+    fn overdue_levels_rise_each_time_the_wait_doubles() {
+        assert_eq!(overdue_level(Duration::hours(23), 24), 0);
+        assert_eq!(overdue_level(Duration::hours(24), 24), 1);
+        assert_eq!(overdue_level(Duration::hours(47), 24), 1);
+        assert_eq!(overdue_level(Duration::hours(48), 24), 2);
+        assert_eq!(overdue_level(Duration::hours(100), 24), 3);
+        assert_eq!(overdue_level(Duration::hours(1000), 0), 0);
+        assert_eq!(format_wait(Duration::hours(47)), "47h");
+        assert_eq!(format_wait(Duration::hours(76)), "3d 4h");
+    }
+
+    #[test]
+    fn reminders_escalate_once_per_level() {
+        let now = Utc::now();
+        let inbox = InboxSnapshot {
+            notifications: Vec::new(),
+            review_requests: vec![ReviewRequest {
+                _id: 1,
+                repo: String::from("acme/app"),
+                title: String::from("#7 Retry uploads"),
+                url: String::from("https://github.com/acme/app/pull/7"),
+                updated_at: now,
+                requested_by: None,
+                requested_at: Some(now - Duration::hours(30)),
+            }],
+            mentions: Vec::new(),
+            recent_reviews: Vec::new(),
+            assigned_issues: Vec::new(),
+            authored_pull_requests: Vec::new(),
+            pull_request_statuses: Vec::new(),
+            fetched_at: now,
+            rate_limit: None,
+            search_totals: SearchTotals::default(),
+            unread_threads: None,
+        };
+        let mut reminders = ReviewWaitReminders::default();
+
+        let first = reminders.check("neo", &inbox, 24, now);
+        assert_eq!(first.len(), 1);
+        assert_eq!(first[0].key, "neo/review-wait/acme/app#7/1");
+        assert!(reminders.check("neo", &inbox, 24, now).is_empty());
+        let later = reminders.check("neo", &inbox, 24, now + Duration::hours(20));
+        assert_eq!(later[0].key, "neo/review-wait/acme/app#7/2");
+    }
+}
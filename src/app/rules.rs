@@ -0,0 +1,212 @@
+use regex::{Regex, RegexBuilder};
+
+use crate::domain::{Forge, NotificationItem, NotificationRule, RuleAction};
+
+/// [`NotificationRule`]s with their title patterns compiled. Rules without
+/// any condition or with an invalid title pattern are skipped.
+#[derive(Debug, Default)]
+pub(super) struct RuleSet {
+    rules: Vec<CompiledRule>,
+}
+
+#[derive(Debug)]
+struct CompiledRule {
+    repo: String,
+    reason: String,
+    title: Option<Regex>,
+    provider: Option<Forge>,
+    action: RuleAction,
+}
+
+impl CompiledRule {
+    fn matches(&self, item: &NotificationItem) -> bool {
+        (self.repo.is_empty() || repo_matches(&self.repo, &item.repo))
+            && (self.reason.is_empty() || self.reason == item.reason)
+            && self
+                .provider
+                .is_none_or(|provider| provider == item.provider)
+            && self
+                .title
+                .as_ref()
+                .is_none_or(|title| title.is_match(&item.title))
+    }
+}
+
+impl RuleSet {
+    pub(super) fn compile(rules: &[NotificationRule]) -> Self {
+        let rules = rules
+            .iter()
+            .filter(|rule| rule_problem(rule).is_none())
+            .map(|rule| CompiledRule {
+                repo: rule.repo.trim().to_owned(),
+                reason: rule.reason.trim().to_owned(),
+                title: compile_title(&rule.title).and_then(Result::ok),
+                provider: rule.provider,
+                action: rule.action,
+            })
+            .collect();
+        Self { rules }
+    }
+
+    fn applies(&self, item: &NotificationItem, action: RuleAction) -> bool {
+        self.rules
+            .iter()
+            .any(|rule| rule.action == action && rule.matches(item))
+    }
+
+    pub(super) fn mutes(&self, item: &NotificationItem) -> bool {
+        self.applies(item, RuleAction::Mute)
+    }
+
+    pub(super) fn highlights(&self, item: &NotificationItem) -> bool {
+        self.applies(item, RuleAction::Highlight)
+    }
+
+    /// Whether a desktop alert may be raised for the thread.
+    pub(super) fn alerts(&self, item: &NotificationItem) -> bool {
+        !self.mutes(item) && !self.applies(item, RuleAction::Silence)
+    }
+}
+
+/// Why a rule would be ignored, for display in the rules editor.
+pub(super) fn rule_problem(rule: &NotificationRule) -> Option<String> {
+    if rule.repo.trim().is_empty()
+        && rule.reason.trim().is_empty()
+        && rule.title.trim().is_empty()
+        && rule.provider.is_none()
+    {
+        return Some(String::from(
+            "Add a repository, reason, title or provider condition.",
+        ));
+    }
+    match compile_title(&rule.title) {
+        Some(Err(err)) => Some(format!("Invalid title pattern: {err}")),
+        _ => None,
+    }
+}
+
+fn compile_title(pattern: &str) -> Option<Result<Regex, regex::Error>> {
+    let pattern = pattern.trim();
+    (!pattern.is_empty()).then(|| RegexBuilder::new(pattern).case_insensitive(true).build())
+}
+
+/// Patterns without a `/` only look at the repository name, so `payments-*`
+/// matches `acme/payments-api`.
+pub(super) fn repo_matches(pattern: &str, repo: &str) -> bool {
+    let target = if pattern.contains('/') {
+        repo
+    } else {
+        repo.rsplit_once('/').map_or(repo, |(_, name)| name)
+    };
+    glob_matches(&pattern.to_ascii_lowercase(), &target.to_ascii_lowercase())
+}
+
+/// `*` matches any run of characters and `?` exactly one.
+fn glob_matches(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                Some((star, matched)) => {
+                    p = star + 1;
+                    t = matched + 1;
+                    backtrack = Some((star, matched + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RuleSet, glob_matches, rule_problem};
+    use crate::domain::{Forge, NotificationItem, NotificationRule, RuleAction, test_support};
+
+    fn notification(repo: &str, reason: &str, title: &str) -> NotificationItem {
+        NotificationItem {
+            repo: repo.to_owned(),
+            title: title.to_owned(),
+            reason: reason.to_owned(),
+            ..test_support::notification("thread-1")
+        }
+    }
+
+    fn rule(repo: &str, reason: &str, title: &str, action: RuleAction) -> NotificationRule {
+        NotificationRule {
+            repo: repo.to_owned(),
+            reason: reason.to_owned(),
+            title: title.to_owned(),
+            provider: None,
+            action,
+        }
+    }
+
+    #[test]
+    fn glob_supports_wildcards() {
+        assert!(glob_matches("acme/*", "acme/noisy-bot"));
+        assert!(glob_matches("payments-?pi", "payments-api"));
+        assert!(glob_matches("*-bot", "noisy-bot"));
+        assert!(!glob_matches("acme/*", "other/noisy-bot"));
+        assert!(!glob_matches("payments-*", "legacy-payments"));
+    }
+
+    #[test]
+    fn rules_mute_silence_and_highlight_matching_threads() {
+        let rules = RuleSet::compile(&[
+            rule("acme/noisy-bot", "", "", RuleAction::Mute),
+            rule("", "ci_activity", "", RuleAction::Silence),
+            rule("payments-*", "", "", RuleAction::Highlight),
+            rule("", "", "^release v\\d+", RuleAction::Highlight),
+        ]);
+
+        let noisy = notification("acme/noisy-bot", "subscribed", "Bump deps");
+        let ci = notification("acme/app", "ci_activity", "Build failed");
+        let payments = notification("acme/payments-api", "mention", "Refund flow");
+        let release = notification("acme/app", "subscribed", "Release v2 notes");
+
+        assert!(rules.mutes(&noisy) && !rules.alerts(&noisy));
+        assert!(!rules.mutes(&ci) && !rules.alerts(&ci));
+        assert!(rules.highlights(&payments) && rules.alerts(&payments));
+        assert!(rules.highlights(&release));
+        assert!(!rules.highlights(&ci));
+    }
+
+    #[test]
+    fn empty_and_invalid_rules_are_ignored() {
+        let empty = rule("", " ", "", RuleAction::Mute);
+        let broken = rule("", "", "fix(", RuleAction::Mute);
+        let rules = RuleSet::compile(&[empty.clone(), broken.clone()]);
+
+        assert!(rule_problem(&empty).is_some());
+        assert!(rule_problem(&broken).is_some_and(|problem| problem.contains("title pattern")));
+        assert!(!rules.mutes(&notification("acme/app", "mention", "fix(parser)")));
+    }
+
+    #[test]
+    fn provider_rules_only_match_their_provider() {
+        let gitea_only = NotificationRule {
+            provider: Some(Forge::Gitea),
+            ..rule("", "", "", RuleAction::Mute)
+        };
+        let rules = RuleSet::compile(std::slice::from_ref(&gitea_only));
+        let mut gitea = notification("acme/app", "subscribed", "Bump");
+        gitea.provider = Forge::Gitea;
+
+        assert!(rule_problem(&gitea_only).is_none());
+        assert!(rules.mutes(&gitea));
+        assert!(!rules.mutes(&notification("acme/app", "subscribed", "Bump")));
+    }
+}
@@ -0,0 +1,287 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use chrono::{DateTime, Utc};
+use reqwest::Client;
+
+use super::{notification_state::has_new_activity, rules::repo_matches};
+use crate::{
+    domain::{
+        Forge, GitHubAccount, InboxSnapshot, NotificationItem, SavedSearch, SavedSearchKind,
+        SearchHit,
+    },
+    github::{self, FetchError},
+    task::Task,
+};
+
+/// A parsed local filter such as `repo:acme/* reason:mention -is:read`.
+/// Every term must match; `-` negates a term and bare words search titles.
+#[derive(Debug, PartialEq, Eq)]
+pub(super) struct FilterQuery {
+    terms: Vec<(bool, Term)>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Term {
+    /// Glob over `owner/name`, or over the name alone without a `/`.
+    Repo(String),
+    Reason(String),
+    /// Subject type such as `PullRequest` or `Issue`.
+    Type(String),
+    /// [`Forge::key`] of the thread's provider.
+    Provider(String),
+    Unread,
+    Text(String),
+}
+
+impl FilterQuery {
+    pub(super) fn parse(input: &str) -> Result<Self, String> {
+        let mut terms = Vec::new();
+        for token in tokenize(input) {
+            let (negated, token) = match token.strip_prefix('-') {
+                Some(rest) if !rest.is_empty() => (true, rest.to_owned()),
+                _ => (false, token),
+            };
+            let term = match token.split_once(':') {
+                Some((key, value)) if !value.is_empty() => {
+                    match key.to_ascii_lowercase().as_str() {
+                        "repo" => Term::Repo(value.to_owned()),
+                        "reason" => Term::Reason(value.to_ascii_lowercase()),
+                        "type" => Term::Type(value.to_ascii_lowercase()),
+                        "provider" => Term::Provider(value.to_ascii_lowercase()),
+                        "is" => match value.to_ascii_lowercase().as_str() {
+                            "unread" => Term::Unread,
+                            "read" => {
+                                terms.push((!negated, Term::Unread));
+                                continue;
+                            }
+                            "pr" => Term::Type(String::from("pullrequest")),
+                            "issue" => Term::Type(String::from("issue")),
+                            "discussion" => Term::Type(String::from("discussion")),
+                            other => {
+                                return Err(format!(
+                                    "Unknown is:{other}; use unread, read, pr, issue or discussion."
+                                ));
+                            }
+                        },
+                        other => {
+                            return Err(format!(
+                                "Unknown filter {other}:; use repo:, reason:, type:, provider: or is:."
+                            ));
+                        }
+                    }
+                }
+                _ => Term::Text(token.to_lowercase()),
+            };
+            terms.push((negated, term));
+        }
+        if terms.is_empty() {
+            return Err(String::from("Enter at least one condition."));
+        }
+        Ok(Self { terms })
+    }
+
+    pub(super) fn matches(&self, item: &NotificationItem) -> bool {
+        self.terms.iter().all(|(negated, term)| {
+            let hit = match term {
+                Term::Repo(pattern) => repo_matches(pattern, &item.repo),
+                Term::Reason(reason) => item.reason.eq_ignore_ascii_case(reason),
+                Term::Type(kind) => item.subject_type.eq_ignore_ascii_case(kind),
+                Term::Provider(provider) => item.provider.key() == provider,
+                Term::Unread => item.unread,
+                Term::Text(text) => item.title.to_lowercase().contains(text),
+            };
+            hit != *negated
+        })
+    }
+}
+
+/// Splits on whitespace, keeping double-quoted runs such as
+/// `repo:"acme/my app"` or `"flaky test"` together.
+fn tokenize(input: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    for ch in input.chars() {
+        match ch {
+            '"' => quoted = !quoted,
+            ch if ch.is_whitespace() && !quoted => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            ch => current.push(ch),
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    tokens
+}
+
+/// Why a saved search would show nothing, for display in its editor.
+pub(super) fn saved_search_problem(search: &SavedSearch) -> Option<String> {
+    if search.name.trim().is_empty() {
+        return Some(String::from("Give the section a name."));
+    }
+    match search.kind {
+        SavedSearchKind::Filter => FilterQuery::parse(&search.query).err(),
+        SavedSearchKind::GitHubSearch if search.query.trim().is_empty() => {
+            Some(String::from("Enter a GitHub search query."))
+        }
+        SavedSearchKind::GitHubSearch => None,
+    }
+}
+
+type SavedSearchJob = Task<Vec<(String, Result<Vec<SearchHit>, FetchError>)>>;
+
+/// Per-account results and highlights of the saved searches, refreshed
+/// after each inbox refresh.
+#[derive(Default)]
+pub(super) struct SavedSearchState {
+    job: Option<SavedSearchJob>,
+    /// `fetched_at` of the inbox last looked at.
+    observed: Option<DateTime<Utc>>,
+    /// Matching threads of each filter section at the last refresh.
+    filter_matches: BTreeMap<String, Vec<(String, DateTime<Utc>)>>,
+    pub(super) hits: BTreeMap<String, Vec<SearchHit>>,
+    pub(super) errors: BTreeMap<String, String>,
+    /// Sections with new activity since the user last opened them.
+    pub(super) highlights: BTreeSet<String>,
+}
+
+impl SavedSearchState {
+    /// Looks at a freshly fetched inbox once: highlights filter sections
+    /// with new matches and starts the GitHub searches.
+    pub(super) fn observe(
+        &mut self,
+        inbox: &InboxSnapshot,
+        searches: &[SavedSearch],
+        client: &Client,
+        profile: &GitHubAccount,
+        max_pages: usize,
+    ) {
+        if self.observed == Some(inbox.fetched_at) {
+            return;
+        }
+        self.observed = Some(inbox.fetched_at);
+
+        let mut filter_matches = BTreeMap::new();
+        for search in searches {
+            if search.kind != SavedSearchKind::Filter {
+                continue;
+            }
+            let Ok(query) = FilterQuery::parse(&search.query) else {
+                continue;
+            };
+            let matches: Vec<_> = inbox
+                .notifications
+                .iter()
+                .filter(|item| query.matches(item))
+                .map(|item| (item.thread_id.clone(), item.updated_at))
+                .collect();
+            if let Some(previous) = self.filter_matches.get(&search.name)
+                && has_new_activity(
+                    previous.iter().map(|(id, at)| (id.as_str(), *at)),
+                    matches.iter().map(|(id, at)| (id.as_str(), *at)),
+                )
+            {
+                self.highlights.insert(search.name.clone());
+            }
+            filter_matches.insert(search.name.clone(), matches);
+        }
+        self.filter_matches = filter_matches;
+
+        let queries: Vec<_> = searches
+            .iter()
+            .filter(|search| {
+                search.kind == SavedSearchKind::GitHubSearch && !search.query.trim().is_empty()
+            })
+            .map(|search| (search.name.clone(), search.query.trim().to_owned()))
+            .collect();
+        if queries.is_empty() || profile.forge != Forge::GitHub || self.job.is_some() {
+            return;
+        }
+        let client = client.clone();
+        let profile = profile.clone();
+        self.job = Some(Task::spawn(move |_| async move {
+            let mut results = Vec::new();
+            for (name, query) in queries {
+                let hits = github::fetch_saved_search(&client, &profile, &query, max_pages).await;
+                results.push((name, hits));
+            }
+            results
+        }));
+    }
+
+    pub(super) fn poll(&mut self) {
+        let Some(job) = &self.job else {
+            return;
+        };
+        let Some(result) = job.try_take() else {
+            return;
+        };
+        self.job = None;
+        let Ok(results) = result else {
+            return;
+        };
+        self.errors.clear();
+        for (name, result) in results {
+            match result {
+                Ok(hits) => {
+                    if let Some(previous) = self.hits.get(&name)
+                        && has_new_activity(
+                            previous
+                                .iter()
+                                .map(|hit| (hit.url.as_str(), hit.updated_at)),
+                            hits.iter().map(|hit| (hit.url.as_str(), hit.updated_at)),
+                        )
+                    {
+                        self.highlights.insert(name.clone());
+                    }
+                    self.hits.insert(name, hits);
+                }
+                Err(err) => {
+                    self.errors.insert(name, err.to_string());
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FilterQuery, tokenize};
+    use crate::domain::{NotificationItem, test_support};
+
+    fn notification(repo: &str, reason: &str, title: &str, unread: bool) -> NotificationItem {
+        NotificationItem {
+            repo: repo.to_owned(),
+            title: title.to_owned(),
+            subject_type: String::from("PullRequest"),
+            reason: reason.to_owned(),
+            unread,
+            ..test_support::notification("1")
+        }
+    }
+
+    #[test]
+    fn filter_queries_combine_negated_and_quoted_terms() {
+        assert_eq!(
+            tokenize(r#"repo:"acme/my app" "flaky test""#),
+            ["repo:acme/my app", "flaky test"]
+        );
+        let query = FilterQuery::parse("repo:acme/* reason:MENTION is:pr -is:read flaky")
+            .expect("valid query");
+
+        assert!(query.matches(&notification("acme/api", "mention", "Flaky login", true)));
+        assert!(!query.matches(&notification("acme/api", "mention", "Flaky login", false)));
+        assert!(!query.matches(&notification("other/api", "mention", "Flaky login", true)));
+        assert!(!query.matches(&notification("acme/api", "author", "Flaky login", true)));
+        assert!(
+            FilterQuery::parse("-repo:acme/* label:bug")
+                .unwrap_err()
+                .contains("label:")
+        );
+        assert!(FilterQuery::parse("  ").is_err());
+    }
+}
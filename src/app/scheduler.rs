@@ -1,8 +1,20 @@
-use std::time::{Duration, Instant};
+use std::{
+    collections::BTreeMap,
+    time::{Duration, Instant},
+};
+
+/// Most automatic account refreshes in flight at once.
+const MAX_CONCURRENT_REFRESHES: usize = 2;
 
 pub(super) struct BatchRefreshScheduler {
     interval: Duration,
     pub(super) last_run: Option<Instant>,
+    backoffs: BTreeMap<String, Instant>,
+    /// When an account that had already been fetched was last started.
+    last_staggered: Option<Instant>,
+    /// When each account was last started, so a failing refresh is retried
+    /// once per interval rather than every frame.
+    started: BTreeMap<String, Instant>,
 }
 
 impl BatchRefreshScheduler {
@@ -10,6 +22,9 @@ impl BatchRefreshScheduler {
         Self {
             interval,
             last_run: None,
+            backoffs: BTreeMap::new(),
+            last_staggered: None,
+            started: BTreeMap::new(),
         }
     }
 
@@ -20,7 +35,99 @@ impl BatchRefreshScheduler {
         }
     }
 
+    pub(super) fn set_interval(&mut self, interval: Duration) {
+        self.interval = interval;
+    }
+
     pub(super) fn mark_triggered(&mut self) {
         self.last_run = Some(Instant::now());
     }
+
+    /// Whether `login`, one of `accounts`, may start an automatic refresh
+    /// while `running` are in flight. Starts are spread `interval / accounts`
+    /// apart so accounts do not all refresh at the same moment; an account
+    /// that has never been fetched only waits for a free slot.
+    pub(super) fn may_start(
+        &self,
+        login: &str,
+        running: usize,
+        accounts: usize,
+        first_fetch: bool,
+        now: Instant,
+    ) -> bool {
+        if running >= MAX_CONCURRENT_REFRESHES
+            || self
+                .started
+                .get(login)
+                .is_some_and(|started| now.saturating_duration_since(*started) < self.interval)
+        {
+            return false;
+        }
+        let gap = self.interval / u32::try_from(accounts.max(1)).unwrap_or(u32::MAX);
+        first_fetch
+            || self
+                .last_staggered
+                .is_none_or(|started| now.saturating_duration_since(started) >= gap)
+    }
+
+    pub(super) fn mark_started(&mut self, login: &str, first_fetch: bool, now: Instant) {
+        self.started.insert(login.to_owned(), now);
+        if !first_fetch {
+            self.last_staggered = Some(now);
+        }
+    }
+
+    /// Skips automatic refreshes for `login` until `until`, keeping the later
+    /// deadline when one is already set.
+    pub(super) fn back_off(&mut self, login: &str, until: Instant) {
+        let deadline = self.backoffs.entry(login.to_owned()).or_insert(until);
+        *deadline = (*deadline).max(until);
+    }
+
+    pub(super) fn is_backing_off(&mut self, login: &str, now: Instant) -> bool {
+        match self.backoffs.get(login) {
+            Some(until) if now < *until => true,
+            Some(_) => {
+                self.backoffs.remove(login);
+                false
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::BatchRefreshScheduler;
+
+    #[test]
+    fn back_off_skips_account_until_deadline() {
+        let mut scheduler = BatchRefreshScheduler::new(Duration::from_secs(180));
+        let now = Instant::now();
+        scheduler.back_off("neo", now + Duration::from_secs(60));
+        scheduler.back_off("neo", now + Duration::from_secs(30));
+
+        assert!(scheduler.is_backing_off("neo", now + Duration::from_secs(45)));
+        assert!(!scheduler.is_backing_off("trinity", now));
+        assert!(!scheduler.is_backing_off("neo", now + Duration::from_secs(61)));
+    }
+
+    #[test]
+    fn starts_are_spread_over_the_interval_and_capped() {
+        let mut scheduler = BatchRefreshScheduler::new(Duration::from_secs(180));
+        let now = Instant::now();
+        assert!(scheduler.may_start("neo", 0, 3, false, now));
+        scheduler.mark_started("neo", false, now);
+
+        let soon = now + Duration::from_secs(30);
+        assert!(!scheduler.may_start("trinity", 1, 3, false, soon));
+        assert!(scheduler.may_start("trinity", 1, 3, true, soon));
+        assert!(!scheduler.may_start("trinity", 2, 3, true, soon));
+        let later = now + Duration::from_secs(60);
+        assert!(scheduler.may_start("trinity", 1, 3, false, later));
+        // A refresh that failed is not retried before the interval is up.
+        assert!(!scheduler.may_start("neo", 0, 3, true, later));
+    }
 }
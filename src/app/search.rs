@@ -1,24 +1,231 @@
+use regex::{Regex, RegexBuilder};
+use unicode_normalization::UnicodeNormalization;
+
+use crate::domain::{SearchField, SearchMode};
+
+/// Spelled-out terms and the abbreviation titles commonly use instead, both
+/// in normalized form.
+const ABBREVIATIONS: &[(&str, &str)] = &[("pullrequest", "pr")];
+const REGEX_SIZE_LIMIT: usize = 1 << 20;
+
 pub(super) struct SearchFilter {
-    needle: Option<String>,
+    matcher: Matcher,
+    transliterate: bool,
+}
+
+enum Matcher {
+    Everything,
+    /// The normalized query plus its abbreviated spellings.
+    Substring(Vec<String>),
+    Fuzzy(String),
+    Regex(Regex),
+    /// An unparsable pattern matches nothing until it is fixed.
+    InvalidRegex(String),
 }
 
 impl SearchFilter {
-    pub(super) fn new(raw: &str) -> Self {
-        let trimmed = raw.trim();
-        let needle = if trimmed.is_empty() {
-            None
+    /// With `transliterate`, fields also match through their romanized
+    /// form, so `hangug` finds `한국`.
+    pub(super) fn new(raw: &str, mode: SearchMode, transliterate: bool) -> Self {
+        let matcher = if raw.trim().is_empty() {
+            Matcher::Everything
         } else {
-            Some(trimmed.to_lowercase())
+            match mode {
+                SearchMode::Substring => Matcher::Substring(substring_needles(raw)),
+                SearchMode::Fuzzy => Matcher::Fuzzy(normalize(raw)),
+                SearchMode::Regex => match RegexBuilder::new(raw.trim())
+                    .case_insensitive(true)
+                    .size_limit(REGEX_SIZE_LIMIT)
+                    .build()
+                {
+                    Ok(regex) => Matcher::Regex(regex),
+                    Err(err) => Matcher::InvalidRegex(err.to_string()),
+                },
+            }
         };
-        Self { needle }
+        Self {
+            matcher,
+            transliterate,
+        }
     }
 
     pub(super) fn matches_any(&self, fields: &[&str]) -> bool {
-        match &self.needle {
-            None => true,
-            Some(needle) => fields
-                .iter()
-                .any(|field| field.to_lowercase().contains(needle)),
+        if matches!(self.matcher, Matcher::Everything) {
+            return true;
+        }
+        fields.iter().any(|field| {
+            self.matches(field)
+                || (self.transliterate && self.matches(&deunicode::deunicode(field)))
+        })
+    }
+
+    /// Like [`Self::matches_any`] over fields already folded by
+    /// [`search_fields`].
+    pub(super) fn matches_prepared(&self, fields: &[SearchField]) -> bool {
+        if matches!(self.matcher, Matcher::Everything) {
+            return true;
+        }
+        fields.iter().any(|field| {
+            self.matches_folded(&field.raw, &field.folded)
+                || (self.transliterate
+                    && field
+                        .romanized
+                        .as_ref()
+                        .is_some_and(|(raw, folded)| self.matches_folded(raw, folded)))
+        })
+    }
+
+    /// Why a regex query cannot be used, for display under the search box.
+    pub(super) fn error(&self) -> Option<&str> {
+        match &self.matcher {
+            Matcher::InvalidRegex(error) => Some(error),
+            _ => None,
+        }
+    }
+
+    fn matches(&self, field: &str) -> bool {
+        match &self.matcher {
+            Matcher::Substring(_) | Matcher::Fuzzy(_) => {
+                self.matches_folded(field, &normalize(field))
+            }
+            _ => self.matches_folded(field, ""),
+        }
+    }
+
+    /// Regexes see `raw`; substring and fuzzy queries see `folded`.
+    fn matches_folded(&self, raw: &str, folded: &str) -> bool {
+        match &self.matcher {
+            Matcher::Everything => true,
+            Matcher::Substring(needles) => needles.iter().any(|needle| folded.contains(needle)),
+            Matcher::Fuzzy(needle) => is_subsequence(needle, folded),
+            Matcher::Regex(regex) => regex.is_match(raw),
+            Matcher::InvalidRegex(_) => false,
+        }
+    }
+}
+
+/// Folds `terms` once for [`SearchFilter::matches_prepared`].
+pub(super) fn search_fields(terms: Vec<String>) -> Vec<SearchField> {
+    terms
+        .into_iter()
+        .map(|raw| {
+            let romanized = (!raw.is_ascii()).then(|| {
+                let romanized = deunicode::deunicode(&raw);
+                let folded = normalize(&romanized);
+                (romanized, folded)
+            });
+            SearchField {
+                folded: normalize(&raw),
+                raw,
+                romanized,
+            }
+        })
+        .collect()
+}
+
+fn substring_needles(raw: &str) -> Vec<String> {
+    let needle = normalize(raw);
+    let mut needles = vec![needle.clone()];
+    for (term, abbreviation) in ABBREVIATIONS {
+        if needle.contains(term) {
+            needles.push(needle.replace(term, abbreviation));
+        }
+    }
+    needles
+}
+
+/// Skim-style matching: every query character appears in the field, in
+/// order, with anything in between.
+fn is_subsequence(needle: &str, haystack: &str) -> bool {
+    let mut haystack = haystack.chars();
+    needle
+        .chars()
+        .all(|wanted| haystack.any(|candidate| candidate == wanted))
+}
+
+/// NFKC plus lowercasing, so full-width and compatibility forms match their
+/// plain spelling, with spaces, `-` and `_` dropped so `pull request`,
+/// `pull-request` and `pullrequest` are the same query.
+fn normalize(text: &str) -> String {
+    text.nfkc()
+        .flat_map(char::to_lowercase)
+        .filter(|ch| !ch.is_whitespace() && *ch != '-' && *ch != '_')
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SearchFilter, search_fields};
+    use crate::domain::SearchMode;
+
+    fn substring(raw: &str) -> SearchFilter {
+        SearchFilter::new(raw, SearchMode::Substring, false)
+    }
+
+    #[test]
+    fn spelled_out_terms_match_abbreviations() {
+        let filter = substring("pull request");
+
+        assert!(filter.matches_any(&["Fix PR template"]));
+        assert!(filter.matches_any(&["feature/pull-request-cache"]));
+        assert!(!filter.matches_any(&["Fix issue template"]));
+    }
+
+    #[test]
+    fn compatibility_forms_match_plain_text() {
+        let filter = substring("ＡＰＩ");
+
+        assert!(filter.matches_any(&["Update api client"]));
+        assert!(substring("리뷰").matches_any(&["코드 리뷰 요청"]));
+    }
+
+    #[test]
+    fn transliteration_is_opt_in() {
+        let title = ["한국어 번역 추가"];
+
+        assert!(!substring("hangugeo").matches_any(&title));
+        assert!(SearchFilter::new("hangugeo", SearchMode::Substring, true).matches_any(&title));
+    }
+
+    #[test]
+    fn prepared_fields_match_like_raw_fields() {
+        let fields = search_fields(vec![
+            String::from("한국어 번역"),
+            String::from("Pull-Request"),
+        ]);
+        for (query, mode) in [
+            ("hangugeo", SearchMode::Substring),
+            ("pull request", SearchMode::Substring),
+            ("pr", SearchMode::Fuzzy),
+            ("^pull", SearchMode::Regex),
+            ("nothing", SearchMode::Substring),
+        ] {
+            let filter = SearchFilter::new(query, mode, true);
+            assert_eq!(
+                filter.matches_prepared(&fields),
+                filter.matches_any(&["한국어 번역", "Pull-Request"]),
+                "{query}"
+            );
         }
     }
+
+    #[test]
+    fn fuzzy_matches_characters_in_order() {
+        let filter = SearchFilter::new("frc", SearchMode::Fuzzy, false);
+
+        assert!(filter.matches_any(&["Fix refresh cache"]));
+        assert!(!filter.matches_any(&["Cache refresh fix"]));
+    }
+
+    #[test]
+    fn regex_mode_matches_patterns_and_reports_errors() {
+        let filter = SearchFilter::new(r"^release/v\d+", SearchMode::Regex, false);
+        assert!(filter.matches_any(&["Release/v2 branch"]));
+        assert!(!filter.matches_any(&["prep release/v2"]));
+        assert!(filter.error().is_none());
+
+        let broken = SearchFilter::new("fix(", SearchMode::Regex, false);
+        assert!(!broken.matches_any(&["fix(ui): spacing"]));
+        assert!(broken.error().is_some());
+    }
 }
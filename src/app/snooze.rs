@@ -0,0 +1,90 @@
+use chrono::{DateTime, Utc};
+
+use crate::domain::{AppSettings, NotificationItem};
+
+/// Returns true while an auto-snooze rule for the item's reason holds it back.
+///
+/// A snoozed thread surfaces once its window elapses, unless it was read
+/// during the window, in which case it stays hidden until GitHub bumps it
+/// again.
+pub(super) fn is_auto_snoozed(
+    item: &NotificationItem,
+    settings: &AppSettings,
+    now: DateTime<Utc>,
+) -> bool {
+    let Some(window) = settings.auto_snooze_duration(&item.reason) else {
+        return false;
+    };
+    let surface_at = item.updated_at + window;
+    if now < surface_at {
+        return true;
+    }
+    !item.unread
+        && item
+            .last_read_at
+            .is_some_and(|read_at| read_at < surface_at)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+
+    use super::is_auto_snoozed;
+    use crate::domain::{AppSettings, NotificationItem, test_support};
+
+    fn subscribed(updated_hours_ago: i64) -> NotificationItem {
+        NotificationItem {
+            title: String::from("Bump deps"),
+            updated_at: Utc::now() - Duration::hours(updated_hours_ago),
+            ..test_support::notification("thread-1")
+        }
+    }
+
+    fn settings_with_subscribed_snooze(hours: u32) -> AppSettings {
+        let mut settings = AppSettings::default();
+        settings
+            .auto_snooze_hours
+            .insert(String::from("subscribed"), hours);
+        settings
+    }
+
+    #[test]
+    fn items_without_a_rule_are_never_snoozed() {
+        assert!(!is_auto_snoozed(
+            &subscribed(0),
+            &AppSettings::default(),
+            Utc::now()
+        ));
+        assert!(!is_auto_snoozed(
+            &subscribed(0),
+            &settings_with_subscribed_snooze(0),
+            Utc::now()
+        ));
+    }
+
+    #[test]
+    fn unread_items_surface_after_the_window() {
+        let settings = settings_with_subscribed_snooze(4);
+
+        assert!(is_auto_snoozed(&subscribed(1), &settings, Utc::now()));
+        assert!(!is_auto_snoozed(&subscribed(5), &settings, Utc::now()));
+    }
+
+    #[test]
+    fn items_read_during_the_window_stay_hidden() {
+        let settings = settings_with_subscribed_snooze(4);
+        let mut read_early = subscribed(5);
+        read_early.unread = false;
+        read_early.last_read_at = Some(read_early.updated_at + Duration::hours(1));
+        let mut read_after_surfacing = subscribed(6);
+        read_after_surfacing.unread = false;
+        read_after_surfacing.last_read_at = Some(Utc::now());
+
+        assert!(is_auto_snoozed(&read_early, &settings, Utc::now()));
+        assert!(!is_auto_snoozed(
+            &read_after_surfacing,
+            &settings,
+            Utc::now()
+        ));
+    }
+}
@@ -0,0 +1,157 @@
+use std::{
+    collections::HashSet,
+    time::{Duration, Instant},
+};
+
+use chrono::{Duration as ChronoDuration, Utc};
+use reqwest::Client;
+
+use super::alerts::DesktopAlert;
+use crate::{
+    domain::{GitHubAccount, StaleBranch},
+    github::{self, FetchError},
+    task::Task,
+};
+
+/// Branches age in days, so a few scans a day are plenty.
+const SCAN_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// My quiet pull requests and unopened branches for one account, rescanned
+/// every few hours.
+#[derive(Default)]
+pub(super) struct StaleBranchState {
+    job: Option<Task<Result<Vec<StaleBranch>, FetchError>>>,
+    last_started: Option<Instant>,
+    days: u32,
+    pub(super) branches: Vec<StaleBranch>,
+    pub(super) error: Option<String>,
+    /// Branches already alerted about, so each reminds once per session.
+    reported: HashSet<String>,
+    /// Branches hidden from the list for this session.
+    pub(super) dismissed: HashSet<String>,
+}
+
+impl StaleBranchState {
+    /// Scans `repos` plus the search results when due; changing `days`
+    /// rescans right away and zero clears the list.
+    pub(super) fn maybe_start(
+        &mut self,
+        client: &Client,
+        profile: &GitHubAccount,
+        repos: Vec<String>,
+        days: u32,
+        max_pages: usize,
+    ) {
+        if days == 0 {
+            self.branches.clear();
+            self.days = 0;
+            return;
+        }
+        let changed = self.days != days;
+        if self.job.is_some()
+            || (!changed
+                && self
+                    .last_started
+                    .is_some_and(|started| started.elapsed() < SCAN_INTERVAL))
+        {
+            return;
+        }
+        self.days = days;
+        self.last_started = Some(Instant::now());
+        let cutoff = Utc::now() - ChronoDuration::days(i64::from(days));
+        let client = client.clone();
+        let profile = profile.clone();
+        self.job = Some(Task::spawn(move |_| async move {
+            github::fetch_stale_branches(&client, &profile, repos, cutoff, max_pages).await
+        }));
+    }
+
+    /// Takes a finished scan; returns alerts for branches seen for the
+    /// first time.
+    pub(super) fn poll(&mut self) -> Vec<DesktopAlert> {
+        let Some(job) = &self.job else {
+            return Vec::new();
+        };
+        let Some(result) = job.try_take() else {
+            return Vec::new();
+        };
+        self.job = None;
+        match result {
+            Ok(Ok(branches)) => {
+                self.error = None;
+                self.branches = branches;
+                self.new_alerts()
+            }
+            Ok(Err(err)) => {
+                self.error = Some(err.to_string());
+                Vec::new()
+            }
+            Err(_) => {
+                self.error = Some(FetchError::BackgroundWorkerGone.to_string());
+                Vec::new()
+            }
+        }
+    }
+
+    pub(super) fn visible(&self) -> impl Iterator<Item = &StaleBranch> {
+        self.branches
+            .iter()
+            .filter(|branch| !self.dismissed.contains(&branch.url))
+    }
+
+    fn new_alerts(&mut self) -> Vec<DesktopAlert> {
+        let days = self.days;
+        self.branches
+            .iter()
+            .filter(|branch| self.reported.insert(branch.url.clone()))
+            .map(|branch| DesktopAlert {
+                key: format!("stale-branch:{}", branch.url),
+                title: format!("Stale branch in {}", branch.repo),
+                body: if branch.has_pull_request {
+                    format!("{} has been quiet for over {days} days.", branch.title)
+                } else {
+                    format!(
+                        "{} was pushed over {days} days ago and never got a pull request.",
+                        branch.title
+                    )
+                },
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::StaleBranchState;
+    use crate::domain::StaleBranch;
+
+    #[test]
+    fn each_stale_branch_alerts_once() {
+        let branch = |title: &str, has_pull_request| StaleBranch {
+            repo: String::from("acme/app"),
+            title: title.to_owned(),
+            url: format!("https://github.com/acme/app/{title}"),
+            has_pull_request,
+            last_activity: Utc::now(),
+        };
+        let mut state = StaleBranchState {
+            days: 14,
+            branches: vec![branch("#3 Retry uploads", true), branch("spike", false)],
+            ..StaleBranchState::default()
+        };
+
+        let alerts = state.new_alerts();
+        assert_eq!(alerts.len(), 2);
+        assert!(alerts[1].body.contains("never got a pull request"));
+
+        state.branches.push(branch("cleanup", false));
+        let alerts = state.new_alerts();
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(
+            alerts[0].key,
+            "stale-branch:https://github.com/acme/app/cleanup"
+        );
+    }
+}
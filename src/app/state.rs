@@ -1,59 +1,354 @@
 use std::{
-    collections::{BTreeMap, HashSet},
-    sync::mpsc::{self, Receiver, TryRecvError},
-    thread,
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque},
+    fmt,
     time::Duration,
 };
 
-use chrono::Utc;
+use chrono::{DateTime, Utc};
+use reqwest::Client;
 
 use crate::{
-    domain::{GitHubAccount, InboxSnapshot, NotificationItem, PullRequestReviewers},
-    github::{self, FetchError},
+    domain::{
+        AppSettings, CachedEnrichment, DoneThreads, EnrichmentCache, Forge, GitHubAccount,
+        HandledKind, InboxSnapshot, MentionThread, NotificationArchive, NotificationChips,
+        NotificationItem, PullRequestKey, PullRequestReviewerStatus, PullRequestReviewers,
+        RateLimitStatus, ReviewRequest, ReviewVerdict, SearchMode, ThreadComment, ThreadDetails,
+        ThreadStages, TimelineEvent,
+    },
+    forge,
+    github::{
+        self, ExtraSearchPages, FetchError, InboxFetchOptions, PullRequestStatusQueue, SearchPage,
+    },
+    task::{Task, TaskLost},
 };
 
 use super::{
     AccountViewMode, ReviewRequestEditor, SectionKind,
-    notification_state::{collect_new_notification_ids, section_stats},
+    alerts::{
+        DesktopAlert, build_alerts, build_re_request_alerts, build_section_alerts,
+        collect_arrived_notifications, detect_storms,
+    },
+    events::{AppEvent, EventPublisher},
+    incremental::{delta_since, merge_delta, settle_read_elsewhere, settle_recent_reads},
+    notification_state::{
+        collect_new_notification_ids, has_new_activity, is_discussion, section_stats,
+    },
     review::{
         ReviewJob, ReviewJobMessage, ReviewLaunchPlan, ReviewOutputState, ReviewServer,
         ReviewServerHealth, ReviewStatus, append_review_chunk, append_review_follow_up_prompt,
         initial_review_output_state, review_process_active, review_session_ready,
     },
+    review_wait::ReviewWaitReminders,
+    rules::RuleSet,
+    saved_searches::SavedSearchState,
+    stale_branches::StaleBranchState,
+    viewer_check::ViewerCheck,
+    watchdog::{Stall, Watchdog},
 };
 
+/// How long fetched enrichment is trusted for a thread with no new activity.
+const ENRICHMENT_CACHE_TTL_HOURS: i64 = 12;
+/// Upper bound on cached threads per account; the least recently used go
+/// first.
+const ENRICHMENT_CACHE_CAPACITY: usize = 2_000;
+/// How long pull request status badges are trusted when the thread is quiet;
+/// checks and reviews move on without bumping the notification.
+const PULL_REQUEST_STATUS_TTL_MINUTES: i64 = 15;
+/// How many recent events the detail window's timeline shows.
+const TIMELINE_EVENT_LIMIT: usize = 10;
+/// How many of the latest comments the preview pane shows.
+const PREVIEW_COMMENT_LIMIT: usize = 5;
+/// Done tombstones outlive the threads' stay in GitHub's feed by a margin.
+const DONE_TOMBSTONE_MAX_AGE_DAYS: i64 = 30;
+/// Bulk section actions run at most this many mark requests at once.
+const MAX_CONCURRENT_ACTIONS: usize = 6;
+
+/// "Mark all read" or "Done all" for the visible threads of one section.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) struct BulkAction {
+    pub(super) section: String,
+    pub(super) thread_ids: Vec<String>,
+    pub(super) done: bool,
+}
+
+impl BulkAction {
+    pub(super) fn label(&self) -> &'static str {
+        if self.done {
+            "Done all"
+        } else {
+            "Mark all read"
+        }
+    }
+}
+
+/// How far the running [`BulkAction`] of an account has got.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) struct BulkProgress {
+    pub(super) section: String,
+    pub(super) done: bool,
+    pub(super) total: usize,
+    pub(super) completed: usize,
+}
+
+/// What last went wrong for an account. Fetch errors stay typed so the
+/// account card can offer a matching way out.
+#[derive(Debug)]
+pub(super) enum AccountError {
+    Fetch(FetchError),
+    /// Local failures, e.g. a review command that could not start.
+    Other(String),
+}
+
+/// The way out the account card offers for an [`AccountError`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum Recovery {
+    UpdateToken,
+    RetryAt(DateTime<Utc>),
+    Retry,
+    Dismiss,
+}
+
+impl AccountError {
+    pub(super) fn recovery(&self) -> Recovery {
+        match self {
+            Self::Fetch(
+                FetchError::Unauthorized
+                | FetchError::TokenRejected
+                | FetchError::MissingToken
+                | FetchError::MissingNotificationsScope
+                | FetchError::LoginMismatch { .. },
+            ) => Recovery::UpdateToken,
+            Self::Fetch(FetchError::RateLimited { reset_at }) => Recovery::RetryAt(*reset_at),
+            Self::Fetch(_) => Recovery::Retry,
+            Self::Other(_) => Recovery::Dismiss,
+        }
+    }
+}
+
+impl fmt::Display for AccountError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Fetch(err) => err.fmt(f),
+            Self::Other(message) => f.write_str(message),
+        }
+    }
+}
+
+impl From<FetchError> for AccountError {
+    fn from(err: FetchError) -> Self {
+        Self::Fetch(err)
+    }
+}
+
+impl From<String> for AccountError {
+    fn from(message: String) -> Self {
+        Self::Other(message)
+    }
+}
+
+/// Recent activity of the thread open in the detail window.
+pub(super) struct ThreadTimeline {
+    pub(super) thread_id: String,
+    /// `None` while the first load is running.
+    pub(super) events: Option<Result<Vec<TimelineEvent>, String>>,
+    job: Option<Task<Result<Vec<TimelineEvent>, FetchError>>>,
+}
+
+impl ThreadTimeline {
+    pub(super) fn is_loading(&self) -> bool {
+        self.job.is_some()
+    }
+}
+
+/// The thread open in the preview pane.
+pub(super) struct ThreadPreview {
+    pub(super) thread_id: String,
+    /// `None` while the first load is running.
+    pub(super) details: Option<Result<ThreadDetails, String>>,
+    job: Option<Task<Result<ThreadDetails, FetchError>>>,
+    /// Text of the reply box.
+    pub(super) draft: String,
+    /// True while a reply to this thread is being posted.
+    pub(super) sending: bool,
+    pub(super) reply_error: Option<String>,
+}
+
+impl ThreadPreview {
+    fn new(thread_id: String) -> Self {
+        Self {
+            thread_id,
+            details: None,
+            job: None,
+            draft: String::new(),
+            sending: false,
+            reply_error: None,
+        }
+    }
+
+    pub(super) fn is_loading(&self) -> bool {
+        self.job.is_some()
+    }
+
+    fn comments_mut(&mut self) -> Option<&mut Vec<ThreadComment>> {
+        match &mut self.details {
+            Some(Ok(details)) => Some(&mut details.comments),
+            _ => None,
+        }
+    }
+}
+
+/// A comment or review posted from the preview pane.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) enum ThreadReply {
+    Comment(String),
+    Review {
+        verdict: ReviewVerdict,
+        body: String,
+    },
+}
+
+/// A reply already shown in the pane and the inbox before GitHub confirms
+/// it, with what to undo if it fails.
+struct PendingReply {
+    thread_id: String,
+    reply: ThreadReply,
+    /// The comment shown optimistically, replaced by GitHub's copy.
+    comment: Option<ThreadComment>,
+    previous_review_status: Option<PullRequestReviewerStatus>,
+}
+
+struct ReplyJob {
+    pending: PendingReply,
+    task: Task<Result<Option<ThreadComment>, FetchError>>,
+}
+
+/// Search hits past the pages a refresh fetched.
+enum MoreSearchResults {
+    ReviewRequests(SearchPage<ReviewRequest>),
+    Mentions(SearchPage<MentionThread>),
+}
+
 pub(super) struct AccountState {
     pub(super) profile: GitHubAccount,
     pub(super) inbox: Option<InboxSnapshot>,
     pub(super) new_notification_ids: HashSet<String>,
     pub(super) review_outputs: BTreeMap<String, ReviewOutputState>,
-    pub(super) last_error: Option<String>,
-    pub(super) pending_job: Option<PendingJob>,
-    pending_notification_metadata_job: Option<PendingNotificationMetadataJob>,
-    pending_actions: Vec<NotificationActionJob>,
+    pub(super) last_error: Option<AccountError>,
+    /// Set by the "Update token" recovery button; picked up by the app.
+    pub(super) token_update_requested: bool,
+    /// Set by the refresh shortcut or a click on "Last synced"; picked up by
+    /// the app.
+    pub(super) refresh_requested: bool,
+    /// Pooled connections shared by every request made for this account.
+    pub(super) client: Client,
+    pub(super) pending_job: Option<Task<github::FetchOutcome>>,
+    /// `since` of the running refresh when it only fetches a delta.
+    pending_since: Option<DateTime<Utc>>,
+    /// When the last refresh that fetched the whole feed finished.
+    last_full_refresh: Option<DateTime<Utc>>,
+    pending_notification_metadata_job: Option<Task<github::NotificationMetadataOutcome>>,
+    /// Draft, check and review badges for threads that point at a PR.
+    pub(super) pull_request_statuses: PullRequestStatusQueue,
+    /// The batch being looked up and its job.
+    pending_pull_request_status_job:
+        Option<(Vec<PullRequestKey>, Task<github::PullRequestStatusOutcome>)>,
+    pending_actions: Vec<Task<NotificationActionResult>>,
+    /// Comments and reviews posted from the preview pane.
+    reply_jobs: Vec<ReplyJob>,
+    /// Pages the user loaded past the refresh's, fetched again by later
+    /// refreshes.
+    extra_search_pages: ExtraSearchPages,
+    pending_more_results: Option<(SectionKind, Task<Result<MoreSearchResults, FetchError>>)>,
+    review_wait_reminders: ReviewWaitReminders,
+    /// Bulk-action threads waiting for a free slot: (thread id, done).
+    queued_actions: VecDeque<(String, bool)>,
+    bulk_thread_ids: HashSet<String>,
+    pub(super) bulk_progress: Option<BulkProgress>,
+    /// Bulk action awaiting the user's confirmation; picked up by the app.
+    pub(super) bulk_confirmation: Option<BulkAction>,
     pending_review_jobs: BTreeMap<String, ReviewJob>,
     review_servers: BTreeMap<String, ReviewServer>,
     pub(super) review_request_editor: Option<ReviewRequestEditor>,
-    pending_review_request_load: Option<ReviewRequestLoadJob>,
-    pending_review_request_action: Option<ReviewRequestActionJob>,
+    pending_review_request_load: Option<Task<ReviewRequestLoadResult>>,
+    pending_review_request_action: Option<Task<ReviewRequestActionResult>>,
     pub(super) expanded: bool,
     pub(super) view_mode: AccountViewMode,
     pub(super) search_query: String,
+    pub(super) search_mode: SearchMode,
+    pub(super) chips: NotificationChips,
+    pub(super) group_by_repo: bool,
+    pub(super) group_by_day: bool,
+    pub(super) collapsed_repos: BTreeSet<String>,
     pub(super) inflight_done: HashSet<String>,
+    /// When each thread was last marked read or done here, so a refresh
+    /// racing the API's propagation cannot flip it back to unread.
+    recent_reads: HashMap<String, DateTime<Utc>>,
+    /// Threads unsubscribed from this session.
+    pub(super) unsubscribed_thread_ids: HashSet<String>,
     pub(super) highlights: HashSet<SectionKind>,
+    pub(super) saved_searches: SavedSearchState,
+    pub(super) stale_branches: StaleBranchState,
+    pub(super) detail_thread_id: Option<String>,
+    pub(super) timeline: Option<ThreadTimeline>,
+    /// Row clicked for the preview pane; picked up by the app.
+    pub(super) preview_request: Option<String>,
+    pub(super) preview: Option<ThreadPreview>,
+    /// Notification the user asked to be reminded about; picked up by the app.
+    pub(super) remind_thread_id: Option<String>,
+    /// Repo -> thread ids collapsed into a single summarized row.
+    pub(super) storms: BTreeMap<String, Vec<String>>,
+    events: EventPublisher,
+    /// Set for the catch-up refresh after the user returns, so everything
+    /// that piled up while away does not fire as desktop alerts.
+    pub(super) mute_next_alerts: bool,
+    pub(super) show_read_at_inbox_zero: bool,
+    /// True while `inbox` is the copy restored from disk rather than a fetch
+    /// made this session.
+    pub(super) inbox_is_cached: bool,
+    /// Set whenever `inbox` changes so the app writes the offline copy.
+    pub(super) inbox_cache_dirty: bool,
+    pub(super) enrichment_cache: EnrichmentCache,
+    pub(super) enrichment_cache_dirty: bool,
+    /// Threads marked done, hidden until they see new activity.
+    pub(super) done_threads: DoneThreads,
+    pub(super) done_threads_dirty: bool,
+    pub(super) thread_stages: ThreadStages,
+    pub(super) thread_stages_dirty: bool,
+    /// Every thread seen so far, listed by the history view.
+    pub(super) archive: NotificationArchive,
+    pub(super) archive_dirty: bool,
+    pub(super) viewer: ViewerCheck,
+    pub(super) watchdog: Watchdog,
+    pub(super) rate_limit: Option<RateLimitStatus>,
+    pub(super) rate_limited_until: Option<DateTime<Utc>>,
 }
 
 impl AccountState {
-    pub(super) fn new(profile: GitHubAccount) -> Self {
+    pub(super) fn new(profile: GitHubAccount, client: Client, events: EventPublisher) -> Self {
         Self {
             profile,
             inbox: None,
             new_notification_ids: HashSet::new(),
             review_outputs: BTreeMap::new(),
             last_error: None,
+            token_update_requested: false,
+            refresh_requested: false,
+            client,
             pending_job: None,
+            pending_since: None,
+            last_full_refresh: None,
             pending_notification_metadata_job: None,
+            pull_request_statuses: PullRequestStatusQueue::default(),
+            pending_pull_request_status_job: None,
             pending_actions: Vec::new(),
+            reply_jobs: Vec::new(),
+            extra_search_pages: ExtraSearchPages::default(),
+            pending_more_results: None,
+            review_wait_reminders: ReviewWaitReminders::default(),
+            queued_actions: VecDeque::new(),
+            bulk_thread_ids: HashSet::new(),
+            bulk_progress: None,
+            bulk_confirmation: None,
             pending_review_jobs: BTreeMap::new(),
             review_servers: BTreeMap::new(),
             review_request_editor: None,
@@ -62,25 +357,561 @@ impl AccountState {
             expanded: true,
             view_mode: AccountViewMode::Inbox,
             search_query: String::new(),
+            search_mode: SearchMode::default(),
+            chips: NotificationChips::default(),
+            group_by_repo: false,
+            group_by_day: false,
+            collapsed_repos: BTreeSet::new(),
             inflight_done: HashSet::new(),
+            recent_reads: HashMap::new(),
+            unsubscribed_thread_ids: HashSet::new(),
             highlights: HashSet::new(),
+            saved_searches: SavedSearchState::default(),
+            stale_branches: StaleBranchState::default(),
+            detail_thread_id: None,
+            timeline: None,
+            preview_request: None,
+            preview: None,
+            remind_thread_id: None,
+            storms: BTreeMap::new(),
+            events,
+            mute_next_alerts: false,
+            show_read_at_inbox_zero: false,
+            inbox_is_cached: false,
+            inbox_cache_dirty: false,
+            enrichment_cache: EnrichmentCache::default(),
+            enrichment_cache_dirty: false,
+            done_threads: DoneThreads::default(),
+            done_threads_dirty: false,
+            thread_stages: ThreadStages::default(),
+            thread_stages_dirty: false,
+            archive: NotificationArchive::default(),
+            archive_dirty: false,
+            viewer: ViewerCheck::default(),
+            watchdog: Watchdog::default(),
+            rate_limit: None,
+            rate_limited_until: None,
+        }
+    }
+
+    pub(super) fn publish_alerts(&self, alerts: impl IntoIterator<Item = DesktopAlert>) {
+        for alert in alerts {
+            self.events.publish(AppEvent::Alert(alert));
         }
     }
 
-    pub(super) fn start_refresh(&mut self) {
+    pub(super) fn start_refresh(&mut self, settings: &AppSettings) {
+        let client = self.client.clone();
         let profile = self.profile.clone();
         self.last_error = None;
         self.pending_notification_metadata_job = None;
-        self.pending_job = Some(PendingJob::spawn(profile));
+        // Only GitHub's feed can be asked for what changed since a time.
+        self.pending_since = if self.profile.forge == Forge::GitHub {
+            delta_since(
+                self.inbox.as_ref(),
+                self.inbox_is_cached,
+                self.last_full_refresh,
+                Utc::now(),
+            )
+        } else {
+            None
+        };
+        let options = InboxFetchOptions {
+            max_pages: settings.max_fetch_pages,
+            unread_only: settings.low_data_mode,
+            since: self.pending_since,
+            max_attempts: settings.fetch_attempts,
+            extra_search_pages: self.extra_search_pages,
+        };
+        let seconds = settings.fetch_timeout_secs;
+        self.pending_job = Some(Task::spawn_with_deadline(
+            Duration::from_secs(seconds),
+            move || {
+                Err(FetchError::Timeout {
+                    seconds: Some(seconds),
+                })
+            },
+            move |progress| async move {
+                forge::fetch_inbox(&client, &profile, options, &progress).await
+            },
+        ));
+    }
+
+    /// Aborts the running refresh. The job stays around as "cancelling"
+    /// until the runtime confirms it stopped, and its result is discarded.
+    pub(super) fn cancel_refresh(&mut self) {
+        if let Some(job) = &mut self.pending_job {
+            job.cancel();
+        }
+    }
+
+    /// Loads the timeline for `item` unless it is already shown; `reload`
+    /// fetches it again anyway. Threads without a number (releases,
+    /// discussions) have no timeline.
+    pub(super) fn load_timeline(&mut self, item: &NotificationItem, reload: bool) {
+        let current = self
+            .timeline
+            .as_ref()
+            .is_some_and(|timeline| timeline.thread_id == item.thread_id);
+        if (current && !reload) || self.profile.forge != Forge::GitHub {
+            return;
+        }
+        let Some(number) = item.thread_number() else {
+            self.timeline = None;
+            return;
+        };
+
+        let client = self.client.clone();
+        let profile = self.profile.clone();
+        let repo = item.repo.clone();
+        let job = Task::spawn(move |_| async move {
+            github::fetch_thread_timeline(&client, &profile, &repo, number, TIMELINE_EVENT_LIMIT)
+                .await
+        });
+        match &mut self.timeline {
+            // Keep showing the old events while the reload runs.
+            Some(timeline) if current => timeline.job = Some(job),
+            _ => {
+                self.timeline = Some(ThreadTimeline {
+                    thread_id: item.thread_id.clone(),
+                    events: None,
+                    job: Some(job),
+                });
+            }
+        }
     }
 
-    pub(super) fn poll_job(&mut self) {
-        if let Some(job) = &mut self.pending_job
+    pub(super) fn poll_timeline_job(&mut self) {
+        let Some(timeline) = &mut self.timeline else {
+            return;
+        };
+        if let Some(job) = &timeline.job
+            && let Some(result) = job.try_take()
+        {
+            timeline.job = None;
+            timeline.events = Some(
+                result
+                    .unwrap_or_else(|TaskLost| Err(FetchError::BackgroundWorkerGone))
+                    .map_err(|err| err.to_string()),
+            );
+        }
+    }
+
+    /// Loads `item` into the preview pane unless it is already there;
+    /// `reload` fetches it again anyway.
+    pub(super) fn load_preview(&mut self, item: &NotificationItem, reload: bool) {
+        let current = self
+            .preview
+            .as_ref()
+            .is_some_and(|preview| preview.thread_id == item.thread_id);
+        if current && !reload {
+            return;
+        }
+        let number = item
+            .thread_number()
+            .filter(|_| self.profile.forge == Forge::GitHub);
+        let Some(number) = number else {
+            self.preview = Some(ThreadPreview {
+                details: Some(Err(String::from(
+                    "Previews are only available for GitHub issues and pull requests.",
+                ))),
+                ..ThreadPreview::new(item.thread_id.clone())
+            });
+            return;
+        };
+
+        let client = self.client.clone();
+        let profile = self.profile.clone();
+        let repo = item.repo.clone();
+        let job = Task::spawn(move |_| async move {
+            github::fetch_thread_details(&client, &profile, &repo, number, PREVIEW_COMMENT_LIMIT)
+                .await
+        });
+        match &mut self.preview {
+            // Keep showing the old details while the reload runs.
+            Some(preview) if current => preview.job = Some(job),
+            _ => {
+                self.preview = Some(ThreadPreview {
+                    job: Some(job),
+                    sending: self
+                        .reply_jobs
+                        .iter()
+                        .any(|job| job.pending.thread_id == item.thread_id),
+                    ..ThreadPreview::new(item.thread_id.clone())
+                });
+            }
+        }
+    }
+
+    /// Posts `reply` to `item`'s thread, showing it right away: a comment
+    /// joins the preview's comments and a review sets the row's review
+    /// badge. Both are undone if GitHub refuses it.
+    pub(super) fn request_reply(&mut self, item: &NotificationItem, reply: ThreadReply) {
+        let Some(number) = item
+            .thread_number()
+            .filter(|_| self.profile.forge == Forge::GitHub)
+        else {
+            return;
+        };
+        if self
+            .reply_jobs
+            .iter()
+            .any(|job| job.pending.thread_id == item.thread_id)
+        {
+            return;
+        }
+
+        let mut pending = PendingReply {
+            thread_id: item.thread_id.clone(),
+            reply: reply.clone(),
+            comment: None,
+            previous_review_status: None,
+        };
+        match &reply {
+            ThreadReply::Comment(body) => {
+                let comment = ThreadComment {
+                    author: Some(self.profile.login.clone()),
+                    body: body.clone(),
+                    created_at: Utc::now(),
+                };
+                if let Some(comments) = self
+                    .preview_for(&item.thread_id)
+                    .and_then(ThreadPreview::comments_mut)
+                {
+                    comments.push(comment.clone());
+                }
+                pending.comment = Some(comment);
+            }
+            ThreadReply::Review { verdict, .. } => {
+                if let Some(item) = self.inbox_item_mut(&item.thread_id) {
+                    pending.previous_review_status = item.my_review_status;
+                    item.my_review_status = Some(verdict.reviewer_status());
+                }
+            }
+        }
+        if let Some(preview) = self.preview_for(&item.thread_id) {
+            preview.draft.clear();
+            preview.sending = true;
+            preview.reply_error = None;
+        }
+
+        let client = self.client.clone();
+        let profile = self.profile.clone();
+        let repo = item.repo.clone();
+        let task = Task::spawn(move |_| async move {
+            match reply {
+                ThreadReply::Comment(body) => {
+                    github::post_issue_comment(&client, &profile, &repo, number, &body)
+                        .await
+                        .map(Some)
+                }
+                ThreadReply::Review { verdict, body } => github::submit_pull_request_review(
+                    &client, &profile, &repo, number, verdict, &body,
+                )
+                .await
+                .map(|()| None),
+            }
+        });
+        self.reply_jobs.push(ReplyJob { pending, task });
+    }
+
+    pub(super) fn poll_reply_jobs(&mut self) {
+        let mut index = 0;
+        while index < self.reply_jobs.len() {
+            let Some(result) = self.reply_jobs[index].task.try_take() else {
+                index += 1;
+                continue;
+            };
+            let job = self.reply_jobs.swap_remove(index);
+            self.watchdog.record_result(result.is_ok());
+            self.finish_reply(
+                job.pending,
+                result.unwrap_or_else(|TaskLost| Err(FetchError::BackgroundWorkerGone)),
+            );
+        }
+    }
+
+    /// Swaps the optimistic comment for GitHub's copy, or rolls the reply
+    /// back and puts its text back in the reply box.
+    fn finish_reply(
+        &mut self,
+        pending: PendingReply,
+        result: Result<Option<ThreadComment>, FetchError>,
+    ) {
+        let PendingReply {
+            thread_id,
+            reply,
+            comment,
+            previous_review_status,
+        } = pending;
+        if let Some(preview) = self.preview_for(&thread_id) {
+            preview.sending = false;
+        }
+        match result {
+            Ok(posted) => {
+                if let (Some(posted), Some(comment)) = (posted, comment)
+                    && let Some(comments) = self
+                        .preview_for(&thread_id)
+                        .and_then(ThreadPreview::comments_mut)
+                    && let Some(slot) = comments.iter_mut().find(|shown| **shown == comment)
+                {
+                    *slot = posted;
+                }
+            }
+            Err(err) => {
+                match &reply {
+                    ThreadReply::Comment(_) => {
+                        if let Some(comments) = self
+                            .preview_for(&thread_id)
+                            .and_then(ThreadPreview::comments_mut)
+                        {
+                            comments.retain(|shown| Some(shown) != comment.as_ref());
+                        }
+                    }
+                    ThreadReply::Review { .. } => {
+                        if let Some(item) = self.inbox_item_mut(&thread_id) {
+                            item.my_review_status = previous_review_status;
+                        }
+                    }
+                }
+                let text = match reply {
+                    ThreadReply::Comment(body) | ThreadReply::Review { body, .. } => body,
+                };
+                match self.preview_for(&thread_id) {
+                    Some(preview) => {
+                        if preview.draft.is_empty() {
+                            preview.draft = text;
+                        }
+                        preview.reply_error = Some(err.to_string());
+                    }
+                    None => self.last_error = Some(err.into()),
+                }
+            }
+        }
+    }
+
+    /// Fetches the next page of the review request or mention search.
+    pub(super) fn request_more_search_results(&mut self, kind: SectionKind) {
+        if self.pending_more_results.is_some() || self.profile.forge != Forge::GitHub {
+            return;
+        }
+        let Some(inbox) = &self.inbox else {
+            return;
+        };
+        let client = self.client.clone();
+        let profile = self.profile.clone();
+        let task = match kind {
+            SectionKind::ReviewRequests => {
+                let loaded = inbox.review_requests.len();
+                Task::spawn(move |_| async move {
+                    github::fetch_more_review_requests(&client, &profile, loaded)
+                        .await
+                        .map(MoreSearchResults::ReviewRequests)
+                })
+            }
+            SectionKind::Mentions => {
+                let loaded = inbox.mentions.len();
+                Task::spawn(move |_| async move {
+                    github::fetch_more_mentions(&client, &profile, loaded)
+                        .await
+                        .map(MoreSearchResults::Mentions)
+                })
+            }
+            _ => return,
+        };
+        self.pending_more_results = Some((kind, task));
+    }
+
+    pub(super) fn is_loading_more(&self, kind: SectionKind) -> bool {
+        self.pending_more_results
+            .as_ref()
+            .is_some_and(|(loading, _)| *loading == kind)
+    }
+
+    pub(super) fn poll_more_results_job(&mut self) {
+        let Some((_, job)) = &self.pending_more_results else {
+            return;
+        };
+        let Some(result) = job.try_take() else {
+            return;
+        };
+        self.pending_more_results = None;
+        let more = match result.unwrap_or_else(|TaskLost| Err(FetchError::BackgroundWorkerGone)) {
+            Ok(more) => more,
+            Err(err) => {
+                self.last_error = Some(err.into());
+                return;
+            }
+        };
+        let Some(inbox) = &mut self.inbox else {
+            return;
+        };
+        match more {
+            MoreSearchResults::ReviewRequests(page) => {
+                github::append_new_hits(&mut inbox.review_requests, page.items, |request| {
+                    &request.url
+                });
+                inbox.search_totals.review_requests = page.total_count;
+                self.extra_search_pages.review_requests += 1;
+            }
+            MoreSearchResults::Mentions(page) => {
+                github::append_new_hits(&mut inbox.mentions, page.items, |mention| &mention.url);
+                inbox.search_totals.mentions = page.total_count;
+                self.extra_search_pages.mentions += 1;
+            }
+        }
+        self.inbox_cache_dirty = true;
+    }
+
+    fn preview_for(&mut self, thread_id: &str) -> Option<&mut ThreadPreview> {
+        self.preview
+            .as_mut()
+            .filter(|preview| preview.thread_id == thread_id)
+    }
+
+    fn inbox_item_mut(&mut self, thread_id: &str) -> Option<&mut NotificationItem> {
+        self.inbox
+            .as_mut()?
+            .notifications
+            .iter_mut()
+            .find(|item| item.thread_id == thread_id)
+    }
+
+    pub(super) fn poll_preview_job(&mut self) {
+        let Some(preview) = &mut self.preview else {
+            return;
+        };
+        if let Some(job) = &preview.job
+            && let Some(result) = job.try_take()
+        {
+            preview.job = None;
+            preview.details = Some(
+                result
+                    .unwrap_or_else(|TaskLost| Err(FetchError::BackgroundWorkerGone))
+                    .map_err(|err| err.to_string()),
+            );
+        }
+    }
+
+    /// Aborts all network work for this account, e.g. on shutdown.
+    pub(super) fn cancel_all_jobs(&mut self) {
+        self.cancel_refresh();
+        if let Some(job) = &mut self.pending_notification_metadata_job {
+            job.cancel();
+        }
+        if let Some((_, job)) = &mut self.pending_pull_request_status_job {
+            job.cancel();
+        }
+        for job in &mut self.pending_actions {
+            job.cancel();
+        }
+        for job in &mut self.reply_jobs {
+            job.task.cancel();
+        }
+        if let Some((_, job)) = &mut self.pending_more_results {
+            job.cancel();
+        }
+        self.queued_actions.clear();
+        self.bulk_thread_ids.clear();
+        self.bulk_progress = None;
+        if let Some(job) = &mut self.pending_review_request_load {
+            job.cancel();
+        }
+        if let Some(job) = &mut self.pending_review_request_action {
+            job.cancel();
+        }
+        self.timeline = None;
+        self.preview = None;
+    }
+
+    /// Restarts this account's workers when the watchdog finds them stalled.
+    pub(super) fn check_workers(&mut self, fetch_timeout: Duration) {
+        let oldest_job = self
+            .pending_job
+            .iter()
+            .map(Task::elapsed)
+            .chain(
+                self.pending_notification_metadata_job
+                    .iter()
+                    .map(Task::elapsed),
+            )
+            .chain(
+                self.pending_pull_request_status_job
+                    .iter()
+                    .map(|(_, job)| job.elapsed()),
+            )
+            .chain(self.pending_actions.iter().map(Task::elapsed))
+            .max();
+        if let Some(stall) = self.watchdog.check(oldest_job, fetch_timeout) {
+            self.restart_workers(stall);
+        }
+    }
+
+    /// Drops every network job, even ones that would never confirm a
+    /// cancel, and starts over with a fresh connection pool.
+    fn restart_workers(&mut self, stall: Stall) {
+        self.cancel_all_jobs();
+        self.pending_job = None;
+        self.pending_notification_metadata_job = None;
+        self.pending_pull_request_status_job = None;
+        self.pending_actions.clear();
+        self.inflight_done.clear();
+        self.pending_review_request_load = None;
+        self.pending_review_request_action = None;
+        // A client that cannot be rebuilt keeps its old pool rather than
+        // dropping the proxy or CA settings.
+        let rebuilt = github::build_client().map(|client| self.client = client);
+        self.watchdog.record_restart(stall, Utc::now());
+        self.last_error = Some(AccountError::Other(match rebuilt {
+            Ok(()) => format!("Background worker restarted: {stall}"),
+            Err(err) => {
+                format!("Background worker restarted on the old connections: {stall}; {err}")
+            }
+        }));
+        self.refresh_requested = true;
+    }
+
+    pub(super) fn is_cancelling_refresh(&self) -> bool {
+        self.pending_job.as_ref().is_some_and(Task::is_cancelled)
+    }
+
+    /// `enrich` allows the follow-up pull request metadata fetch; low-data
+    /// and power-saving modes turn it off.
+    pub(super) fn poll_job(&mut self, settings: &AppSettings, rules: &RuleSet, enrich: bool) {
+        if let Some(job) = &self.pending_job
+            && job.is_cancelled()
+        {
+            if job.try_take().is_some() {
+                self.pending_job = None;
+            }
+        } else if let Some(job) = &mut self.pending_job
             && let Some(result) = job.try_take()
         {
             self.pending_job = None;
-            match result {
-                Ok(inbox) => {
+            self.watchdog.record_result(result.is_ok());
+            match result.unwrap_or_else(|TaskLost| Err(FetchError::BackgroundWorkerGone)) {
+                Ok(mut inbox) => {
+                    match (self.pending_since.take(), &self.inbox) {
+                        (Some(_), Some(previous)) => {
+                            inbox.notifications = merge_delta(
+                                previous.notifications.clone(),
+                                std::mem::take(&mut inbox.notifications),
+                            );
+                            if let Some(unread) = inbox.unread_threads.take() {
+                                settle_read_elsewhere(&mut inbox.notifications, &unread);
+                            }
+                        }
+                        _ => self.last_full_refresh = Some(inbox.fetched_at),
+                    }
+                    settle_recent_reads(
+                        &mut inbox.notifications,
+                        &mut self.recent_reads,
+                        Utc::now(),
+                        i64::try_from(settings.action_settle_secs)
+                            .ok()
+                            .and_then(chrono::Duration::try_seconds)
+                            .unwrap_or(chrono::Duration::MAX),
+                    );
                     let new_notification_ids =
                         collect_new_notification_ids(self.inbox.as_ref(), &inbox);
                     let current_ids: HashSet<_> = inbox
@@ -91,9 +922,102 @@ impl AccountState {
                     self.new_notification_ids
                         .retain(|thread_id| current_ids.contains(thread_id.as_str()));
                     self.new_notification_ids.extend(new_notification_ids);
-                    let previous_stats = self.inbox.as_ref().map(section_stats);
-                    let next_stats = section_stats(&inbox);
+                    self.storms.retain(|_, thread_ids| {
+                        thread_ids.retain(|thread_id| current_ids.contains(thread_id.as_str()));
+                        !thread_ids.is_empty()
+                    });
+                    let arrived: Vec<_> =
+                        collect_arrived_notifications(self.inbox.as_ref(), &inbox)
+                            .into_iter()
+                            .filter(|item| !rules.mutes(item))
+                            .collect();
+                    let storms = detect_storms(&arrived, settings.storm_threshold);
+                    let thresholds = self.profile.section_alerts(settings);
+                    let mute_alerts = std::mem::take(&mut self.mute_next_alerts);
+                    if let Some(previous) = &self.inbox {
+                        self.publish_alerts(build_re_request_alerts(
+                            &self.profile.login,
+                            previous,
+                            &inbox,
+                        ));
+                    }
+                    if !mute_alerts {
+                        self.publish_alerts(build_alerts(
+                            &self.profile.login,
+                            &arrived,
+                            &storms,
+                            settings,
+                            rules,
+                            thresholds,
+                        ));
+                    }
+                    if settings.review_wait_reminders {
+                        let alerts = self.review_wait_reminders.check(
+                            &self.profile.login,
+                            &inbox,
+                            settings.review_wait_hours,
+                            Utc::now(),
+                        );
+                        self.publish_alerts(alerts);
+                    }
+                    for (repo, thread_ids) in storms {
+                        let group = self.storms.entry(repo).or_default();
+                        for thread_id in thread_ids {
+                            if !group.contains(&thread_id) {
+                                group.push(thread_id);
+                            }
+                        }
+                    }
+                    let previous_stats =
+                        self.inbox.as_ref().map(|inbox| section_stats(inbox, rules));
+                    let next_stats = section_stats(&inbox, rules);
+                    if let Some(previous) = &self.inbox {
+                        if has_new_activity(
+                            previous
+                                .assigned_issues
+                                .iter()
+                                .map(|issue| (issue.url.as_str(), issue.updated_at)),
+                            inbox
+                                .assigned_issues
+                                .iter()
+                                .map(|issue| (issue.url.as_str(), issue.updated_at)),
+                        ) {
+                            self.highlights.insert(SectionKind::Assigned);
+                        }
+                        if has_new_activity(
+                            previous.authored_pull_requests.iter().map(|pull_request| {
+                                (pull_request.url.as_str(), pull_request.updated_at)
+                            }),
+                            inbox.authored_pull_requests.iter().map(|pull_request| {
+                                (pull_request.url.as_str(), pull_request.updated_at)
+                            }),
+                        ) {
+                            self.highlights.insert(SectionKind::AuthoredPullRequests);
+                        }
+                        if has_new_activity(
+                            previous
+                                .notifications
+                                .iter()
+                                .filter(|item| is_discussion(item))
+                                .map(|item| (item.thread_id.as_str(), item.updated_at)),
+                            inbox
+                                .notifications
+                                .iter()
+                                .filter(|item| is_discussion(item))
+                                .map(|item| (item.thread_id.as_str(), item.updated_at)),
+                        ) {
+                            self.highlights.insert(SectionKind::Discussions);
+                        }
+                    }
                     if let Some(old) = previous_stats {
+                        if !mute_alerts {
+                            self.publish_alerts(build_section_alerts(
+                                &self.profile.login,
+                                &old,
+                                &next_stats,
+                                thresholds,
+                            ));
+                        }
                         if next_stats.inbox.bumped_since(&old.inbox) {
                             self.highlights.insert(SectionKind::Inbox);
                         }
@@ -111,23 +1035,119 @@ impl AccountState {
                         }
                     }
 
+                    self.rate_limit = inbox.rate_limit.or(self.rate_limit);
+                    self.rate_limited_until = None;
+                    if self.done_threads.prune(
+                        &inbox.notifications,
+                        Utc::now(),
+                        done_tombstone_max_age(),
+                    ) > 0
+                    {
+                        self.done_threads_dirty = true;
+                    }
+                    self.pull_request_statuses
+                        .seed(&inbox.pull_request_statuses, Utc::now());
+                    if self.archive.record(&inbox.notifications, Utc::now()) {
+                        self.archive_dirty = true;
+                    }
                     self.inbox = Some(inbox);
-                    self.start_notification_metadata_refresh();
+                    self.inbox_is_cached = false;
+                    self.inbox_cache_dirty = true;
+                    self.events.publish(AppEvent::SnapshotReady {
+                        login: self.profile.login.clone(),
+                    });
+                    if enrich && self.profile.forge == Forge::GitHub {
+                        self.start_notification_metadata_refresh();
+                        self.queue_pull_request_statuses();
+                    }
                     self.last_error = None;
                 }
                 Err(err) => {
-                    self.last_error = Some(err.to_string());
+                    if let FetchError::RateLimited { reset_at } = &err {
+                        self.rate_limited_until = Some(*reset_at);
+                        self.events.publish(AppEvent::RateLimited {
+                            login: self.profile.login.clone(),
+                            reset_at: *reset_at,
+                        });
+                    }
+                    self.last_error = Some(err.into());
                 }
             }
         }
     }
 
+    /// Asks for badges on every pull request in the inbox; only stale ones
+    /// are actually fetched.
+    fn queue_pull_request_statuses(&mut self) {
+        let Some(inbox) = &self.inbox else {
+            return;
+        };
+        let now = Utc::now();
+        let ttl = chrono::Duration::minutes(PULL_REQUEST_STATUS_TTL_MINUTES);
+        let notifications = inbox
+            .notifications
+            .iter()
+            .filter_map(|item| Some((item.pull_request_key()?, item.updated_at)));
+        let review_requests = inbox
+            .review_requests
+            .iter()
+            .filter_map(|request| Some((request.pull_request_key()?, request.updated_at)));
+        let authored = inbox
+            .authored_pull_requests
+            .iter()
+            .map(|pull_request| (pull_request.pull_request_key(), pull_request.updated_at));
+        for (key, updated_at) in notifications.chain(review_requests).chain(authored) {
+            self.pull_request_statuses
+                .request(key, updated_at, now, ttl);
+        }
+        self.start_pull_request_status_batch();
+    }
+
+    fn start_pull_request_status_batch(&mut self) {
+        if self.pending_pull_request_status_job.is_some()
+            || !self.pull_request_statuses.has_pending()
+        {
+            return;
+        }
+        let batch = self.pull_request_statuses.next_batch();
+        let client = self.client.clone();
+        let profile = self.profile.clone();
+        let keys = batch.clone();
+        let job = Task::spawn(move |_| async move {
+            github::fetch_pull_request_statuses(&client, &profile, &keys).await
+        });
+        self.pending_pull_request_status_job = Some((batch, job));
+    }
+
+    /// Records a finished batch and starts the next one. A failed batch is
+    /// dropped; its pull requests are queued again on the next refresh.
+    pub(super) fn poll_pull_request_status_job(&mut self) {
+        let Some((batch, job)) = &self.pending_pull_request_status_job else {
+            return;
+        };
+        let Some(result) = job.try_take() else {
+            return;
+        };
+        let batch = batch.clone();
+        self.pending_pull_request_status_job = None;
+        let Ok(Ok(statuses)) = result else {
+            return;
+        };
+        self.pull_request_statuses.record(
+            &batch,
+            statuses,
+            Utc::now(),
+            chrono::Duration::minutes(PULL_REQUEST_STATUS_TTL_MINUTES),
+        );
+        self.start_pull_request_status_batch();
+    }
+
     pub(super) fn poll_notification_metadata_job(&mut self) {
         if let Some(job) = &self.pending_notification_metadata_job
             && let Some(result) = job.try_take()
         {
             self.pending_notification_metadata_job = None;
-            let Ok(updates) = result else {
+            let Ok(Ok(updates)) = result else {
                 return;
             };
             let Some(inbox) = &mut self.inbox else {
@@ -141,14 +1161,44 @@ impl AccountState {
                     (thread_id, update)
                 })
                 .collect();
+            let now = Utc::now();
+            let ttl = enrichment_cache_ttl();
             for item in &mut inbox.notifications {
-                let Some(update) = updates_by_thread.get(&item.thread_id) else {
-                    continue;
-                };
-                item.head_ref = update.head_ref.clone();
-                item.base_ref = update.base_ref.clone();
-                item.my_review_status = update.my_review_status;
+                if let Some(update) = updates_by_thread.get(&item.thread_id) {
+                    item.head_ref = update.head_ref.clone();
+                    item.base_ref = update.base_ref.clone();
+                    item.my_review_status = update.my_review_status;
+                    item.latest_actor = update.latest_actor.clone();
+                    if let Some(html_url) = &update.html_url {
+                        item.url = Some(html_url.clone());
+                    }
+                    item.search_cache.clear();
+                }
+                // Threads that were fetched but had nothing to add are cached
+                // too, so they are not asked about again after a restart.
+                if needs_enrichment(item) && self.enrichment_cache.lookup(item, now, ttl).is_none()
+                {
+                    self.enrichment_cache.insert(
+                        item.thread_id.clone(),
+                        CachedEnrichment {
+                            updated_at: item.updated_at,
+                            fetched_at: now,
+                            head_ref: item.head_ref.clone(),
+                            base_ref: item.base_ref.clone(),
+                            my_review_status: item.my_review_status,
+                            latest_actor: item.latest_actor.clone(),
+                            html_url: updates_by_thread
+                                .get(&item.thread_id)
+                                .and_then(|update| update.html_url.clone()),
+                            last_used_at: None,
+                        },
+                        ttl,
+                        ENRICHMENT_CACHE_CAPACITY,
+                    );
+                    self.enrichment_cache_dirty = true;
+                }
             }
+            self.inbox_cache_dirty = true;
         }
     }
 
@@ -219,7 +1269,7 @@ impl AccountState {
                     message,
                 } => {
                     let session_ready = session_id.is_some();
-                    self.last_error = Some(message.clone());
+                    self.last_error = Some(message.clone().into());
                     if let Some(review_output) = self.review_outputs.get_mut(&thread_id) {
                         review_output.status = ReviewStatus::Failed;
                         review_output.captured_at = Some(captured_at);
@@ -239,25 +1289,102 @@ impl AccountState {
         self.pending_actions.retain(|job| match job.try_take() {
             None => true,
             Some(result) => {
-                finished.push(result);
+                self.watchdog.record_result(result.is_ok());
+                finished.push(
+                    result.unwrap_or_else(|TaskLost| Err((None, FetchError::BackgroundWorkerGone))),
+                );
                 false
             }
         });
 
         for outcome in finished {
-            match outcome {
-                Ok(NotificationActionOutcome::Done(thread_id))
-                | Ok(NotificationActionOutcome::Read(thread_id)) => {
-                    self.handle_action_success(&thread_id)
+            let succeeded = outcome.is_ok();
+            let mut handled = None;
+            let thread_id = match outcome {
+                Ok(NotificationActionOutcome::Done(thread_id)) => {
+                    self.done_threads.mark(thread_id.clone(), Utc::now());
+                    self.done_threads_dirty = true;
+                    if self.archive.mark_done(&thread_id, Utc::now()) {
+                        self.archive_dirty = true;
+                    }
+                    self.handle_action_success(&thread_id);
+                    handled = Some(HandledKind::Done);
+                    Some(thread_id)
+                }
+                Ok(NotificationActionOutcome::Read(thread_id)) => {
+                    self.handle_action_success(&thread_id);
+                    handled = Some(HandledKind::Read);
+                    Some(thread_id)
+                }
+                Ok(NotificationActionOutcome::Unsubscribed(thread_id)) => {
+                    self.inflight_done.remove(&thread_id);
+                    self.unsubscribed_thread_ids.insert(thread_id.clone());
+                    Some(thread_id)
                 }
                 Err((thread_id, err)) => {
-                    self.last_error = Some(err);
-                    if let Some(id) = thread_id {
-                        self.inflight_done.remove(&id);
+                    self.last_error = Some(err.into());
+                    if let Some(id) = &thread_id {
+                        self.inflight_done.remove(id);
                     }
+                    thread_id
                 }
+            };
+            self.events.publish(AppEvent::ActionCompleted {
+                login: self.profile.login.clone(),
+                thread_id: thread_id.clone(),
+                succeeded,
+                handled,
+            });
+            if let Some(id) = thread_id
+                && self.bulk_thread_ids.remove(&id)
+                && let Some(progress) = &mut self.bulk_progress
+            {
+                progress.completed += 1;
             }
         }
+        if self.bulk_thread_ids.is_empty() {
+            self.bulk_progress = None;
+        }
+        self.start_queued_actions();
+    }
+
+    /// Queues a mark request for every listed thread not already in flight
+    /// and starts as many as the concurrency cap allows.
+    pub(super) fn request_bulk_action(&mut self, action: BulkAction) {
+        let thread_ids: Vec<_> = action
+            .thread_ids
+            .into_iter()
+            .filter(|thread_id| !self.inflight_done.contains(thread_id))
+            .collect();
+        if thread_ids.is_empty() {
+            return;
+        }
+        let progress = self.bulk_progress.get_or_insert(BulkProgress {
+            section: action.section,
+            done: action.done,
+            total: 0,
+            completed: 0,
+        });
+        progress.total += thread_ids.len();
+        for thread_id in thread_ids {
+            self.inflight_done.insert(thread_id.clone());
+            self.bulk_thread_ids.insert(thread_id.clone());
+            self.queued_actions.push_back((thread_id, action.done));
+        }
+        self.start_queued_actions();
+    }
+
+    fn start_queued_actions(&mut self) {
+        while self.pending_actions.len() < MAX_CONCURRENT_ACTIONS
+            && let Some((thread_id, done)) = self.queued_actions.pop_front()
+        {
+            self.pending_actions.push(mark_notification(
+                self.client.clone(),
+                self.profile.clone(),
+                thread_id,
+                done,
+            ));
+        }
     }
 
     pub(super) fn poll_review_request_jobs(&mut self) {
@@ -265,7 +1392,7 @@ impl AccountState {
             && let Some(result) = job.try_take()
         {
             self.pending_review_request_load = None;
-            match result {
+            match result.unwrap_or_else(|TaskLost| Err(review_request_worker_gone("load"))) {
                 Ok(outcome) => {
                     let PullRequestReviewers {
                         requested_reviewers,
@@ -291,7 +1418,7 @@ impl AccountState {
                         editor.pending_load = false;
                         editor.form_error = Some(err);
                     } else {
-                        self.last_error = Some(err);
+                        self.last_error = Some(err.into());
                     }
                 }
             }
@@ -301,7 +1428,7 @@ impl AccountState {
             && let Some(result) = job.try_take()
         {
             self.pending_review_request_action = None;
-            match result {
+            match result.unwrap_or_else(|TaskLost| Err(review_request_worker_gone("action"))) {
                 Ok(outcome) => {
                     let mut should_reload = false;
                     if let Some(editor) = &mut self.review_request_editor
@@ -325,7 +1452,7 @@ impl AccountState {
                         editor.pending_action = false;
                         editor.form_error = Some(err);
                     } else {
-                        self.last_error = Some(err);
+                        self.last_error = Some(err.into());
                     }
                 }
             }
@@ -342,6 +1469,7 @@ impl AccountState {
             item.unread = false;
             item.last_read_at = Some(Utc::now());
         }
+        self.recent_reads.insert(thread_id.to_owned(), Utc::now());
         self.inflight_done.remove(thread_id);
     }
 
@@ -361,8 +1489,12 @@ impl AccountState {
         if self.inflight_done.contains(&thread_id) {
             return;
         }
-        let profile = self.profile.clone();
-        let job = NotificationActionJob::mark_read(profile, thread_id.clone());
+        let job = mark_notification(
+            self.client.clone(),
+            self.profile.clone(),
+            thread_id.clone(),
+            false,
+        );
         self.pending_actions.push(job);
         self.inflight_done.insert(thread_id);
     }
@@ -371,8 +1503,29 @@ impl AccountState {
         if self.inflight_done.contains(&thread_id) {
             return;
         }
-        let profile = self.profile.clone();
-        let job = NotificationActionJob::mark_done(profile, thread_id.clone());
+        let job = mark_notification(
+            self.client.clone(),
+            self.profile.clone(),
+            thread_id.clone(),
+            true,
+        );
+        self.pending_actions.push(job);
+        self.inflight_done.insert(thread_id);
+    }
+
+    pub(super) fn toggle_repo_group(&mut self, repo: String) {
+        if !self.collapsed_repos.remove(&repo) {
+            self.collapsed_repos.insert(repo);
+        }
+    }
+
+    pub(super) fn request_unsubscribe(&mut self, thread_id: String) {
+        if self.inflight_done.contains(&thread_id)
+            || self.unsubscribed_thread_ids.contains(&thread_id)
+        {
+            return;
+        }
+        let job = unsubscribe_thread(self.client.clone(), self.profile.clone(), thread_id.clone());
         self.pending_actions.push(job);
         self.inflight_done.insert(thread_id);
     }
@@ -398,7 +1551,7 @@ impl AccountState {
         };
 
         if let Err(err) = job.cancel() {
-            self.last_error = Some(err);
+            self.last_error = Some(err.into());
         }
     }
 
@@ -521,6 +1674,84 @@ impl AccountState {
         }
     }
 
+    /// When automatic refreshes should pause: after a rate-limit rejection, or
+    /// once the remaining quota drops below `min_remaining` until it resets.
+    pub(super) fn rate_limit_backoff(
+        &self,
+        min_remaining: u32,
+        now: DateTime<Utc>,
+    ) -> Option<DateTime<Utc>> {
+        let low_quota_reset = self
+            .rate_limit
+            .filter(|rate_limit| rate_limit.remaining < min_remaining)
+            .map(|rate_limit| rate_limit.reset_at);
+        self.rate_limited_until
+            .into_iter()
+            .chain(low_quota_reset)
+            .max()
+            .filter(|until| *until > now)
+    }
+
+    /// Unread threads in the latest snapshot, or `None` before the first fetch.
+    pub(super) fn unread_count(&self) -> Option<usize> {
+        self.inbox.as_ref().map(|inbox| {
+            inbox
+                .notifications
+                .iter()
+                .filter(|item| item.unread && !self.done_threads.hides(item))
+                .count()
+        })
+    }
+
+    /// Drops state for threads that left the inbox and trims the
+    /// enrichment cache, so a session running for weeks stays bounded.
+    /// Returns how many entries were dropped.
+    pub(super) fn compact_stores(&mut self, now: DateTime<Utc>) -> usize {
+        let mut dropped =
+            self.enrichment_cache
+                .compact(now, enrichment_cache_ttl(), ENRICHMENT_CACHE_CAPACITY);
+        if dropped > 0 {
+            self.enrichment_cache_dirty = true;
+        }
+        let notifications = self
+            .inbox
+            .as_ref()
+            .map_or(&[][..], |inbox| &inbox.notifications);
+        let dropped_done = self
+            .done_threads
+            .prune(notifications, now, done_tombstone_max_age());
+        if dropped_done > 0 {
+            self.done_threads_dirty = true;
+            dropped += dropped_done;
+        }
+
+        let current: HashSet<String> = self
+            .inbox
+            .iter()
+            .flat_map(|inbox| &inbox.notifications)
+            .map(|item| item.thread_id.clone())
+            .collect();
+        let before = self.review_outputs.len()
+            + self.inflight_done.len()
+            + self.unsubscribed_thread_ids.len();
+        let pending_reviews = &self.pending_review_jobs;
+        self.review_outputs.retain(|thread_id, output| {
+            current.contains(thread_id)
+                || output.open
+                || output.status == ReviewStatus::Running
+                || pending_reviews.contains_key(thread_id)
+        });
+        self.inflight_done
+            .retain(|thread_id| current.contains(thread_id));
+        self.unsubscribed_thread_ids
+            .retain(|thread_id| current.contains(thread_id));
+        dropped += before
+            - self.review_outputs.len()
+            - self.inflight_done.len()
+            - self.unsubscribed_thread_ids.len();
+        dropped
+    }
+
     pub(super) fn clear_new_notifications(&mut self) {
         self.new_notification_ids.clear();
     }
@@ -563,7 +1794,8 @@ impl AccountState {
             status_message: None,
         });
         self.pending_review_request_action = None;
-        self.pending_review_request_load = Some(ReviewRequestLoadJob::spawn(
+        self.pending_review_request_load = Some(load_review_requests(
+            self.client.clone(),
             self.profile.clone(),
             ReviewRequestTarget { repo, pr_number },
         ));
@@ -582,7 +1814,8 @@ impl AccountState {
             return;
         };
 
-        self.pending_review_request_action = Some(ReviewRequestActionJob::spawn(
+        self.pending_review_request_action = Some(mutate_review_request(
+            self.client.clone(),
             self.profile.clone(),
             target,
             reviewer_login,
@@ -601,7 +1834,8 @@ impl AccountState {
             reviewer_list_contains(&editor.requested_reviewers, &reviewer_login)
         });
 
-        self.pending_review_request_action = Some(ReviewRequestActionJob::spawn(
+        self.pending_review_request_action = Some(mutate_review_request(
+            self.client.clone(),
             self.profile.clone(),
             target,
             reviewer_login,
@@ -630,10 +1864,9 @@ impl AccountState {
             repo: editor.repo.clone(),
             pr_number: editor.pr_number,
         };
-        let profile = self.profile.clone();
-
-        self.pending_review_request_action = Some(ReviewRequestActionJob::spawn(
-            profile,
+        self.pending_review_request_action = Some(mutate_review_request(
+            self.client.clone(),
+            self.profile.clone(),
             target,
             reviewer_login,
             ReviewRequestMutationKind::Remove,
@@ -651,8 +1884,11 @@ impl AccountState {
             repo: editor.repo.clone(),
             pr_number: editor.pr_number,
         };
-        let profile = self.profile.clone();
-        self.pending_review_request_load = Some(ReviewRequestLoadJob::spawn(profile, target));
+        self.pending_review_request_load = Some(load_review_requests(
+            self.client.clone(),
+            self.profile.clone(),
+            target,
+        ));
     }
 
     fn prepare_review_request_action(
@@ -694,89 +1930,67 @@ impl AccountState {
     }
 
     fn start_notification_metadata_refresh(&mut self) {
-        let Some(inbox) = &self.inbox else {
+        let Some(inbox) = &mut self.inbox else {
             self.pending_notification_metadata_job = None;
             return;
         };
-        let notifications: Vec<NotificationItem> = inbox
+        let now = Utc::now();
+        let ttl = enrichment_cache_ttl();
+        let mut notifications = Vec::new();
+        for item in inbox
             .notifications
-            .iter()
-            .filter(|item| item.pull_request_url().is_some())
-            .cloned()
-            .collect();
+            .iter_mut()
+            .filter(|item| needs_enrichment(item))
+        {
+            match self.enrichment_cache.lookup(item, now, ttl) {
+                Some(cached) => {
+                    item.head_ref = cached.head_ref.clone();
+                    item.base_ref = cached.base_ref.clone();
+                    item.my_review_status = cached.my_review_status;
+                    item.latest_actor = cached.latest_actor.clone();
+                    if let Some(html_url) = &cached.html_url {
+                        item.url = Some(html_url.clone());
+                    }
+                    item.search_cache.clear();
+                    self.inbox_cache_dirty = true;
+                }
+                None => notifications.push(item.clone()),
+            }
+        }
         if notifications.is_empty() {
             self.pending_notification_metadata_job = None;
             return;
         }
-        self.pending_notification_metadata_job = Some(PendingNotificationMetadataJob::spawn(
-            self.profile.clone(),
-            notifications,
-        ));
+        let client = self.client.clone();
+        let profile = self.profile.clone();
+        self.pending_notification_metadata_job = Some(Task::spawn(move |_| async move {
+            github::fetch_notification_metadata_updates(&client, &profile, &notifications).await
+        }));
     }
 }
 
-pub(super) struct PendingJob {
-    receiver: Receiver<github::FetchOutcome>,
+fn needs_enrichment(item: &NotificationItem) -> bool {
+    item.pull_request_url().is_some()
+        || item.latest_comment_url.is_some()
+        || item.html_url_source().is_some()
+        || item.is_discussion()
 }
 
-struct PendingNotificationMetadataJob {
-    receiver: Receiver<github::NotificationMetadataOutcome>,
+fn enrichment_cache_ttl() -> chrono::Duration {
+    chrono::Duration::hours(ENRICHMENT_CACHE_TTL_HOURS)
 }
 
-impl PendingJob {
-    fn spawn(profile: GitHubAccount) -> Self {
-        let (tx, rx) = mpsc::channel();
-        thread::spawn(move || {
-            let outcome = (|| -> github::FetchOutcome {
-                let client = github::build_client()?;
-                github::fetch_inbox(&client, &profile)
-            })();
-            let _ = tx.send(outcome);
-        });
-        Self { receiver: rx }
-    }
-
-    fn try_take(&self) -> Option<github::FetchOutcome> {
-        match self.receiver.try_recv() {
-            Ok(result) => Some(result),
-            Err(TryRecvError::Empty) => None,
-            Err(TryRecvError::Disconnected) => Some(Err(FetchError::BackgroundWorkerGone)),
-        }
-    }
-}
-
-impl PendingNotificationMetadataJob {
-    fn spawn(profile: GitHubAccount, notifications: Vec<NotificationItem>) -> Self {
-        let (tx, rx) = mpsc::channel();
-        thread::spawn(move || {
-            let outcome = (|| -> github::NotificationMetadataOutcome {
-                let client = github::build_client()?;
-                github::fetch_notification_metadata_updates(&client, &profile, &notifications)
-            })();
-            let _ = tx.send(outcome);
-        });
-        Self { receiver: rx }
-    }
-
-    fn try_take(&self) -> Option<github::NotificationMetadataOutcome> {
-        match self.receiver.try_recv() {
-            Ok(result) => Some(result),
-            Err(TryRecvError::Empty) => None,
-            Err(TryRecvError::Disconnected) => Some(Err(FetchError::BackgroundWorkerGone)),
-        }
-    }
+pub(super) fn done_tombstone_max_age() -> chrono::Duration {
+    chrono::Duration::days(DONE_TOMBSTONE_MAX_AGE_DAYS)
 }
 
 enum NotificationActionOutcome {
     Done(String),
     Read(String),
+    Unsubscribed(String),
 }
 
-type NotificationActionResult = Result<NotificationActionOutcome, (Option<String>, String)>;
-
-struct NotificationActionJob {
-    receiver: Receiver<NotificationActionResult>,
-}
+type NotificationActionResult = Result<NotificationActionOutcome, (Option<String>, FetchError)>;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 struct ReviewRequestTarget {
@@ -791,41 +2005,18 @@ struct ReviewRequestLoadOutcome {
 
 type ReviewRequestLoadResult = Result<ReviewRequestLoadOutcome, (ReviewRequestTarget, String)>;
 
-struct ReviewRequestLoadJob {
-    receiver: Receiver<ReviewRequestLoadResult>,
-}
-
-impl ReviewRequestLoadJob {
-    fn spawn(profile: GitHubAccount, target: ReviewRequestTarget) -> Self {
-        let (tx, rx) = mpsc::channel();
-        thread::spawn(move || {
-            let outcome = Self::load_worker(profile, target);
-            let _ = tx.send(outcome);
-        });
-        Self { receiver: rx }
-    }
-
-    fn load_worker(profile: GitHubAccount, target: ReviewRequestTarget) -> ReviewRequestLoadResult {
-        let client = github::build_client().map_err(|err| (target.clone(), err.to_string()))?;
+fn load_review_requests(
+    client: Client,
+    profile: GitHubAccount,
+    target: ReviewRequestTarget,
+) -> Task<ReviewRequestLoadResult> {
+    Task::spawn(move |_| async move {
         let reviewers =
             github::fetch_pull_request_reviewers(&client, &profile, &target.repo, target.pr_number)
+                .await
                 .map_err(|err| (target.clone(), err.to_string()))?;
         Ok(ReviewRequestLoadOutcome { target, reviewers })
-    }
-
-    fn try_take(&self) -> Option<ReviewRequestLoadResult> {
-        match self.receiver.try_recv() {
-            Ok(result) => Some(result),
-            Err(TryRecvError::Empty) => None,
-            Err(TryRecvError::Disconnected) => Some(Err((
-                ReviewRequestTarget {
-                    repo: String::new(),
-                    pr_number: 0,
-                },
-                "Review request worker disconnected".to_owned(),
-            ))),
-        }
-    }
+    })
 }
 
 #[derive(Clone, Copy)]
@@ -842,104 +2033,81 @@ struct ReviewRequestActionOutcome {
 
 type ReviewRequestActionResult = Result<ReviewRequestActionOutcome, (ReviewRequestTarget, String)>;
 
-struct ReviewRequestActionJob {
-    receiver: Receiver<ReviewRequestActionResult>,
-}
-
-impl ReviewRequestActionJob {
-    fn spawn(
-        profile: GitHubAccount,
-        target: ReviewRequestTarget,
-        reviewer_login: String,
-        action: ReviewRequestMutationKind,
-        already_requested: bool,
-    ) -> Self {
-        let (tx, rx) = mpsc::channel();
-        thread::spawn(move || {
-            let outcome =
-                Self::action_worker(profile, target, reviewer_login, action, already_requested);
-            let _ = tx.send(outcome);
-        });
-        Self { receiver: rx }
-    }
-
-    fn action_worker(
-        profile: GitHubAccount,
-        target: ReviewRequestTarget,
-        reviewer_login: String,
-        action: ReviewRequestMutationKind,
-        already_requested: bool,
-    ) -> ReviewRequestActionResult {
-        let client = github::build_client().map_err(|err| (target.clone(), err.to_string()))?;
-        match action {
+fn mutate_review_request(
+    client: Client,
+    profile: GitHubAccount,
+    target: ReviewRequestTarget,
+    reviewer_login: String,
+    action: ReviewRequestMutationKind,
+    already_requested: bool,
+) -> Task<ReviewRequestActionResult> {
+    Task::spawn(move |_| async move {
+        let fail = |err: FetchError| (target.clone(), err.to_string());
+        let (repo, pr_number) = (target.repo.as_str(), target.pr_number);
+        let message = match action {
             ReviewRequestMutationKind::Request => {
                 github::request_pull_request_reviewer(
                     &client,
                     &profile,
-                    &target.repo,
-                    target.pr_number,
+                    repo,
+                    pr_number,
                     &reviewer_login,
                 )
-                .map_err(|err| (target.clone(), err.to_string()))?;
-                Ok(ReviewRequestActionOutcome {
-                    target,
-                    message: format!("Requested review from {reviewer_login}."),
-                })
+                .await
+                .map_err(fail)?;
+                format!("Requested review from {reviewer_login}.")
             }
             ReviewRequestMutationKind::Remove => {
                 github::remove_pull_request_reviewer(
                     &client,
                     &profile,
-                    &target.repo,
-                    target.pr_number,
+                    repo,
+                    pr_number,
                     &reviewer_login,
                 )
-                .map_err(|err| (target.clone(), err.to_string()))?;
-                Ok(ReviewRequestActionOutcome {
-                    target,
-                    message: format!("Removed review request for {reviewer_login}."),
-                })
+                .await
+                .map_err(fail)?;
+                format!("Removed review request for {reviewer_login}.")
             }
             ReviewRequestMutationKind::ReNotify => {
                 if already_requested {
                     github::remove_pull_request_reviewer(
                         &client,
                         &profile,
-                        &target.repo,
-                        target.pr_number,
+                        repo,
+                        pr_number,
                         &reviewer_login,
                     )
-                    .map_err(|err| (target.clone(), err.to_string()))?;
+                    .await
+                    .map_err(fail)?;
                 }
                 github::request_pull_request_reviewer(
                     &client,
                     &profile,
-                    &target.repo,
-                    target.pr_number,
+                    repo,
+                    pr_number,
                     &reviewer_login,
                 )
-                .map_err(|err| (target.clone(), err.to_string()))?;
-                Ok(ReviewRequestActionOutcome {
-                    target,
-                    message: format!("Re-notified {reviewer_login}."),
-                })
+                .await
+                .map_err(fail)?;
+                format!("Re-notified {reviewer_login}.")
             }
-        }
-    }
+        };
+        Ok(ReviewRequestActionOutcome {
+            target: target.clone(),
+            message,
+        })
+    })
+}
 
-    fn try_take(&self) -> Option<ReviewRequestActionResult> {
-        match self.receiver.try_recv() {
-            Ok(result) => Some(result),
-            Err(TryRecvError::Empty) => None,
-            Err(TryRecvError::Disconnected) => Some(Err((
-                ReviewRequestTarget {
-                    repo: String::new(),
-                    pr_number: 0,
-                },
-                "Review request action worker disconnected".to_owned(),
-            ))),
-        }
-    }
+fn review_request_worker_gone(what: &str) -> (ReviewRequestTarget, String) {
+    (
+        ReviewRequestTarget {
+            repo: String::new(),
+            pr_number: 0,
+        },
+        format!("Review request {what} was interrupted"),
+    )
 }
 
 fn reviewer_list_contains(reviewers: &[String], reviewer_login: &str) -> bool {
@@ -948,72 +2116,84 @@ fn reviewer_list_contains(reviewers: &[String], reviewer_login: &str) -> bool {
         .any(|login| login.eq_ignore_ascii_case(reviewer_login))
 }
 
-impl NotificationActionJob {
-    fn mark_done(profile: GitHubAccount, thread_id: String) -> Self {
-        let (tx, rx) = mpsc::channel();
-        thread::spawn(move || {
-            let outcome = Self::mark_done_worker(profile, thread_id);
-            let _ = tx.send(outcome);
-        });
-        Self { receiver: rx }
-    }
-
-    fn mark_done_worker(profile: GitHubAccount, thread_id: String) -> NotificationActionResult {
-        let client =
-            github::build_client().map_err(|err| (Some(thread_id.clone()), err.to_string()))?;
-        github::mark_notification_done(&client, &profile, &thread_id)
-            .map_err(|err| (Some(thread_id.clone()), err.to_string()))?;
-        Ok(NotificationActionOutcome::Done(thread_id))
-    }
-
-    fn mark_read(profile: GitHubAccount, thread_id: String) -> Self {
-        let (tx, rx) = mpsc::channel();
-        thread::spawn(move || {
-            let outcome = Self::mark_read_worker(profile, thread_id);
-            let _ = tx.send(outcome);
-        });
-        Self { receiver: rx }
-    }
-
-    fn mark_read_worker(profile: GitHubAccount, thread_id: String) -> NotificationActionResult {
-        let client =
-            github::build_client().map_err(|err| (Some(thread_id.clone()), err.to_string()))?;
-        github::mark_notification_read(&client, &profile, &thread_id)
-            .map_err(|err| (Some(thread_id.clone()), err.to_string()))?;
-        Ok(NotificationActionOutcome::Read(thread_id))
-    }
+fn mark_notification(
+    client: Client,
+    profile: GitHubAccount,
+    thread_id: String,
+    done: bool,
+) -> Task<NotificationActionResult> {
+    Task::spawn(move |_| async move {
+        let result = if done {
+            forge::mark_done(&client, &profile, &thread_id).await
+        } else {
+            forge::mark_read(&client, &profile, &thread_id).await
+        };
+        if let Err(err) = result {
+            return Err((Some(thread_id), err));
+        }
+        Ok(if done {
+            NotificationActionOutcome::Done(thread_id)
+        } else {
+            NotificationActionOutcome::Read(thread_id)
+        })
+    })
+}
 
-    fn try_take(&self) -> Option<NotificationActionResult> {
-        match self.receiver.try_recv() {
-            Ok(result) => Some(result),
-            Err(TryRecvError::Empty) => None,
-            Err(TryRecvError::Disconnected) => Some(Err((
-                None,
-                "Notification action worker disconnected".to_owned(),
-            ))),
+fn unsubscribe_thread(
+    client: Client,
+    profile: GitHubAccount,
+    thread_id: String,
+) -> Task<NotificationActionResult> {
+    Task::spawn(move |_| async move {
+        match github::unsubscribe_notification_thread(&client, &profile, &thread_id).await {
+            Ok(()) => Ok(NotificationActionOutcome::Unsubscribed(thread_id)),
+            Err(err) => Err((Some(thread_id), err)),
         }
-    }
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use chrono::Utc;
 
-    use super::AccountState;
+    use super::{
+        AccountError, AccountState, BulkAction, MAX_CONCURRENT_ACTIONS, Recovery, ThreadPreview,
+        ThreadReply,
+    };
     use crate::{
-        app::review::{
-            ReviewLaunchPlan, ReviewStatus, append_review_chunk, append_review_follow_up_prompt,
-            initial_review_output_state, test_review_server,
+        app::{
+            events::EventPublisher,
+            review::{
+                ReviewLaunchPlan, ReviewStatus, append_review_chunk,
+                append_review_follow_up_prompt, initial_review_output_state, test_review_server,
+            },
+        },
+        domain::{
+            Forge, GitHubAccount, InboxBackend, InboxSnapshot, NotificationItem,
+            PullRequestReviewerStatus, ReviewCommandSettings, ReviewVerdict, SearchTotals,
+            ThreadDetails, test_support,
         },
-        domain::{GitHubAccount, ReviewCommandSettings},
+        github::FetchError,
     };
 
     fn account_state() -> AccountState {
-        AccountState::new(GitHubAccount {
-            login: String::from("neo"),
-            token: String::from("token"),
-            review_settings: ReviewCommandSettings::default(),
-        })
+        AccountState::new(
+            GitHubAccount {
+                login: String::from("neo"),
+                token: String::from("token"),
+                review_settings: ReviewCommandSettings::default(),
+                hide_bot_notifications: None,
+                api_base_url: None,
+                working_hours: None,
+                token_command: None,
+                group: None,
+                section_alerts: None,
+                inbox_backend: InboxBackend::Rest,
+                forge: Forge::GitHub,
+            },
+            reqwest::Client::new(),
+            EventPublisher::detached(),
+        )
     }
 
     fn sample_review_output() -> crate::app::review::ReviewOutputState {
@@ -1140,4 +2320,113 @@ mod tests {
         assert!(review_output.pending_follow_up_prompt.is_none());
         assert!(review_output.follow_up_draft.is_empty());
     }
+
+    #[test]
+    fn account_errors_map_to_recovery_actions() {
+        let reset_at = Utc::now();
+        assert_eq!(
+            AccountError::from(FetchError::Unauthorized).recovery(),
+            Recovery::UpdateToken
+        );
+        assert_eq!(
+            AccountError::from(FetchError::RateLimited { reset_at }).recovery(),
+            Recovery::RetryAt(reset_at)
+        );
+        assert_eq!(
+            AccountError::from(FetchError::NotFound).recovery(),
+            Recovery::Retry
+        );
+        assert_eq!(
+            AccountError::from("Unable to save reminder".to_owned()).recovery(),
+            Recovery::Dismiss
+        );
+    }
+
+    #[test]
+    fn bulk_actions_cap_concurrent_requests() {
+        let mut account = account_state();
+        // Without a token the requests fail locally instead of hitting GitHub.
+        account.profile.token.clear();
+        account.inflight_done.insert(String::from("0"));
+
+        account.request_bulk_action(BulkAction {
+            section: String::from("Mentions"),
+            thread_ids: (0..20).map(|id| id.to_string()).collect(),
+            done: false,
+        });
+
+        assert_eq!(account.pending_actions.len(), MAX_CONCURRENT_ACTIONS);
+        assert_eq!(account.queued_actions.len(), 19 - MAX_CONCURRENT_ACTIONS);
+        let progress = account.bulk_progress.as_ref().expect("bulk progress");
+        assert_eq!((progress.total, progress.completed), (19, 0));
+    }
+
+    #[test]
+    fn failed_replies_roll_back_their_optimistic_updates() {
+        let mut account = account_state();
+        account.profile.token.clear();
+        let item = NotificationItem {
+            repo: String::from("acme/repo"),
+            title: String::from("Fix login"),
+            url: Some(String::from("https://github.com/acme/repo/pull/42")),
+            my_review_status: Some(PullRequestReviewerStatus::Pending),
+            subject_type: String::from("PullRequest"),
+            reason: String::from("review_requested"),
+            ..test_support::notification("thread-1")
+        };
+        account.inbox = Some(InboxSnapshot {
+            notifications: vec![item.clone()],
+            review_requests: Vec::new(),
+            mentions: Vec::new(),
+            recent_reviews: Vec::new(),
+            assigned_issues: Vec::new(),
+            authored_pull_requests: Vec::new(),
+            pull_request_statuses: Vec::new(),
+            fetched_at: Utc::now(),
+            rate_limit: None,
+            search_totals: SearchTotals::default(),
+            unread_threads: None,
+        });
+        account.preview = Some(ThreadPreview {
+            details: Some(Ok(ThreadDetails {
+                title: item.title.clone(),
+                state: String::from("open"),
+                author: None,
+                labels: Vec::new(),
+                body: String::new(),
+                comments: Vec::new(),
+            })),
+            draft: String::from("Looks good"),
+            ..ThreadPreview::new(item.thread_id.clone())
+        });
+
+        account.request_reply(&item, ThreadReply::Comment(String::from("Looks good")));
+        let preview = account.preview.as_mut().expect("preview");
+        assert!(preview.sending && preview.draft.is_empty());
+        assert_eq!(
+            preview.comments_mut().expect("comments")[0].body,
+            "Looks good"
+        );
+        let job = account.reply_jobs.pop().expect("comment job");
+        account.finish_reply(job.pending, Err(FetchError::NotFound));
+        let preview = account.preview.as_mut().expect("preview");
+        assert!(preview.comments_mut().expect("comments").is_empty());
+        assert_eq!(preview.draft, "Looks good");
+        assert!(!preview.sending && preview.reply_error.is_some());
+
+        account.request_reply(
+            &item,
+            ThreadReply::Review {
+                verdict: ReviewVerdict::Approve,
+                body: String::new(),
+            },
+        );
+        let status = |account: &AccountState| {
+            account.inbox.as_ref().expect("inbox").notifications[0].my_review_status
+        };
+        assert_eq!(status(&account), Some(PullRequestReviewerStatus::Approved));
+        let job = account.reply_jobs.pop().expect("review job");
+        account.finish_reply(job.pending, Err(FetchError::NotFound));
+        assert_eq!(status(&account), Some(PullRequestReviewerStatus::Pending));
+    }
 }
@@ -0,0 +1,114 @@
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+
+use crate::{
+    sync::{self, SyncDocument, SyncError, SyncOutcome, SyncRemote},
+    task::Task,
+};
+
+const SYNC_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// Shares triage state with other machines every few minutes while sync is
+/// configured.
+#[derive(Default)]
+pub(super) struct SyncRunner {
+    job: Option<Task<Result<SyncOutcome, SyncError>>>,
+    last_started: Option<Instant>,
+    requested: bool,
+    /// What the last successful sync agreed on; local changes are stamped
+    /// against it.
+    pub(super) baseline: SyncDocument,
+    pub(super) last_synced: Option<DateTime<Utc>>,
+    pub(super) error: Option<String>,
+}
+
+impl SyncRunner {
+    pub(super) fn is_due(&self) -> bool {
+        self.job.is_none()
+            && (self.requested
+                || self
+                    .last_started
+                    .is_none_or(|started| started.elapsed() >= SYNC_INTERVAL))
+    }
+
+    pub(super) fn is_running(&self) -> bool {
+        self.job.is_some()
+    }
+
+    /// Syncs on the next frame.
+    pub(super) fn request(&mut self) {
+        self.requested = true;
+    }
+
+    pub(super) fn start(
+        &mut self,
+        remote: SyncRemote,
+        local: SyncDocument,
+        oldest_done: DateTime<Utc>,
+    ) {
+        self.requested = false;
+        self.last_started = Some(Instant::now());
+        self.job = Some(Task::spawn(move |_| sync::sync(remote, local, oldest_done)));
+    }
+
+    /// Records a configuration problem in place of a sync attempt.
+    pub(super) fn skip(&mut self, error: impl Into<String>) {
+        self.requested = false;
+        self.last_started = Some(Instant::now());
+        self.error = Some(error.into());
+    }
+
+    pub(super) fn poll(&mut self) -> Option<SyncOutcome> {
+        let result = self.job.as_ref()?.try_take()?;
+        self.job = None;
+        match result {
+            Ok(Ok(outcome)) => {
+                self.error = None;
+                self.last_synced = Some(Utc::now());
+                self.baseline = outcome.document.clone();
+                Some(outcome)
+            }
+            Ok(Err(err)) => {
+                self.error = Some(err.to_string());
+                None
+            }
+            Err(_) => {
+                self.error = Some(SyncError::Interrupted.to_string());
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SyncRunner;
+    use crate::{
+        sync::{SyncDocument, SyncError, SyncOutcome},
+        task::Task,
+    };
+
+    #[test]
+    fn poll_adopts_the_synced_document_as_baseline() {
+        let mut runner = SyncRunner::default();
+        assert!(runner.is_due());
+        let mut document = SyncDocument::default();
+        document
+            .done
+            .insert(String::from("neo"), Default::default());
+        runner.job = Some(Task::finished(Ok(SyncOutcome {
+            document: document.clone(),
+            gist_id: None,
+        })));
+        runner.error = Some(String::from("offline"));
+
+        assert!(runner.poll().is_some());
+        assert_eq!(runner.baseline, document);
+        assert!(runner.error.is_none());
+
+        runner.job = Some(Task::finished(Err(SyncError::Interrupted)));
+        assert!(runner.poll().is_none());
+        assert!(runner.error.is_some());
+    }
+}
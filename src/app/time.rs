@@ -1,14 +1,143 @@
-use chrono::{DateTime, Local, Utc};
+use std::time::Duration;
+
+use chrono::{DateTime, Local, NaiveDate, Utc};
+
+use crate::domain::TimestampStyle;
+
+/// Local time with the offset, shown on hover next to relative times.
+pub(in crate::app) const FULL_TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S %:z";
 
 pub(in crate::app) fn format_local_timestamp(datetime: DateTime<Utc>, format: &str) -> String {
     datetime.with_timezone(&Local).format(format).to_string()
 }
 
+/// `datetime` in the user's chosen style; `format` is used when absolute.
+pub(in crate::app) fn format_timestamp(
+    datetime: DateTime<Utc>,
+    style: TimestampStyle,
+    format: &str,
+) -> String {
+    match style {
+        TimestampStyle::Relative => format_relative(datetime, Utc::now()),
+        TimestampStyle::Absolute => format_local_timestamp(datetime, format),
+    }
+}
+
+/// "just now", "8 min ago", "3 h ago", "yesterday", "4 days ago", and the
+/// local date past a week.
+pub(in crate::app) fn format_relative(datetime: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let elapsed = now - datetime;
+    let minutes = elapsed.num_minutes();
+    if minutes < 1 {
+        return String::from("just now");
+    }
+    if minutes < 60 {
+        return format!("{minutes} min ago");
+    }
+    let today = now.with_timezone(&Local).date_naive();
+    match DayBucket::of(datetime, today) {
+        DayBucket::Today => format!("{} h ago", elapsed.num_hours()),
+        DayBucket::Yesterday => String::from("yesterday"),
+        DayBucket::Earlier if elapsed.num_days() < 7 => {
+            format!("{} days ago", elapsed.num_days().max(2))
+        }
+        DayBucket::Earlier => format_local_timestamp(datetime, "%Y-%m-%d"),
+    }
+}
+
+/// Whole seconds, switching to minutes past the first one: `7s`, `2m 05s`.
+pub(in crate::app) fn format_elapsed(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs();
+    if secs < 60 {
+        format!("{secs}s")
+    } else {
+        format!("{}m {:02}s", secs / 60, secs % 60)
+    }
+}
+
+/// Local calendar day a row was last updated, bucketed the way mail clients
+/// split long lists.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(in crate::app) enum DayBucket {
+    Today,
+    Yesterday,
+    Earlier,
+}
+
+impl DayBucket {
+    pub(in crate::app) const ALL: [Self; 3] = [Self::Today, Self::Yesterday, Self::Earlier];
+
+    pub(in crate::app) fn of(datetime: DateTime<Utc>, today: NaiveDate) -> Self {
+        let day = datetime.with_timezone(&Local).date_naive();
+        if day >= today {
+            Self::Today
+        } else if today.pred_opt() == Some(day) {
+            Self::Yesterday
+        } else {
+            Self::Earlier
+        }
+    }
+
+    pub(in crate::app) fn label(self) -> &'static str {
+        match self {
+            Self::Today => "Today",
+            Self::Yesterday => "Yesterday",
+            Self::Earlier => "Earlier",
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use chrono::{Local, TimeZone, Utc};
+    use chrono::{Duration, Local, TimeZone, Utc};
+
+    use super::{DayBucket, format_elapsed, format_local_timestamp, format_relative};
 
-    use super::format_local_timestamp;
+    #[test]
+    fn format_elapsed_switches_to_minutes() {
+        assert_eq!(
+            format_elapsed(std::time::Duration::from_millis(7_900)),
+            "7s"
+        );
+        assert_eq!(
+            format_elapsed(std::time::Duration::from_secs(125)),
+            "2m 05s"
+        );
+    }
+
+    #[test]
+    fn day_bucket_splits_by_local_calendar_day() {
+        let now = Utc::now();
+        let today = now.with_timezone(&Local).date_naive();
+
+        assert_eq!(DayBucket::of(now, today), DayBucket::Today);
+        assert_eq!(
+            DayBucket::of(now - Duration::days(1), today),
+            DayBucket::Yesterday
+        );
+        assert_eq!(
+            DayBucket::of(now - Duration::days(3), today),
+            DayBucket::Earlier
+        );
+    }
+
+    #[test]
+    fn format_relative_counts_minutes_then_days() {
+        let now = Utc::now();
+
+        assert_eq!(format_relative(now, now), "just now");
+        assert_eq!(format_relative(now + Duration::minutes(5), now), "just now");
+        assert_eq!(
+            format_relative(now - Duration::minutes(8), now),
+            "8 min ago"
+        );
+        assert_eq!(format_relative(now - Duration::days(1), now), "yesterday");
+        assert_eq!(format_relative(now - Duration::days(4), now), "4 days ago");
+        assert_eq!(
+            format_relative(now - Duration::days(30), now),
+            format_local_timestamp(now - Duration::days(30), "%Y-%m-%d")
+        );
+    }
 
     #[test]
     fn format_local_timestamp_uses_local_timezone() {
@@ -0,0 +1,31 @@
+use crate::{
+    domain::GitHubAccount,
+    forge,
+    github::{self, FetchError},
+    task::{Task, TaskLost},
+};
+
+pub(super) type TokenCheckOutcome = Result<GitHubAccount, FetchError>;
+
+/// Verifies a manually entered token in the background and hands the profile
+/// back once GitHub accepted it.
+pub(super) struct PendingTokenCheck {
+    task: Task<TokenCheckOutcome>,
+}
+
+impl PendingTokenCheck {
+    pub(super) fn spawn(profile: GitHubAccount) -> Self {
+        let task = Task::spawn(move |_| async move {
+            let client = github::build_client()?;
+            forge::verify_token(&client, &profile).await?;
+            Ok(profile)
+        });
+        Self { task }
+    }
+
+    pub(super) fn try_take(&self) -> Option<TokenCheckOutcome> {
+        self.task
+            .try_take()
+            .map(|result| result.unwrap_or_else(|TaskLost| Err(FetchError::BackgroundWorkerGone)))
+    }
+}
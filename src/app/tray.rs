@@ -0,0 +1,364 @@
+//! System tray icon that badges the unread count across accounts and offers
+//! Open, Refresh all, a do-not-disturb toggle and Quit. Linux publishes a StatusNotifierItem over
+//! D-Bus; Windows and macOS use the native tray. Builds without the `tray`
+//! feature, and desktops without a tray, run without one.
+// Without the feature nothing builds icons or sends commands.
+#![cfg_attr(not(feature = "tray"), allow(dead_code))]
+
+use std::sync::mpsc::{self, Receiver, Sender};
+
+use eframe::egui::Context;
+
+/// Tray icons are square; desktops scale this down as needed.
+const ICON_SIZE: usize = 32;
+const DISC_RGBA: [u8; 4] = [0x3b, 0x82, 0xf6, 0xff];
+const BADGE_RGBA: [u8; 4] = [0xdc, 0x26, 0x26, 0xff];
+const WHITE: [u8; 4] = [0xff, 0xff, 0xff, 0xff];
+
+/// 3×5 glyphs for the badge, one row per entry, high bit on the left.
+const DIGITS: [[u8; 5]; 10] = [
+    [0b111, 0b101, 0b101, 0b101, 0b111],
+    [0b010, 0b110, 0b010, 0b010, 0b111],
+    [0b111, 0b001, 0b111, 0b100, 0b111],
+    [0b111, 0b001, 0b111, 0b001, 0b111],
+    [0b101, 0b101, 0b111, 0b001, 0b001],
+    [0b111, 0b100, 0b111, 0b001, 0b111],
+    [0b111, 0b100, 0b111, 0b101, 0b111],
+    [0b111, 0b001, 0b010, 0b010, 0b010],
+    [0b111, 0b101, 0b111, 0b101, 0b111],
+    [0b111, 0b101, 0b111, 0b001, 0b111],
+];
+const PLUS: [u8; 5] = [0b000, 0b010, 0b111, 0b010, 0b000];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum TrayCommand {
+    Open,
+    RefreshAll,
+    ToggleMute,
+    Quit,
+}
+
+pub(super) struct Tray {
+    backend: platform::TrayBackend,
+    commands: Receiver<TrayCommand>,
+    unread: Option<usize>,
+    muted: Option<bool>,
+}
+
+impl Tray {
+    /// `None` when this build or desktop has no tray to show the icon in.
+    pub(super) fn spawn(ctx: &Context) -> Option<Self> {
+        let (sender, commands) = mpsc::channel();
+        let backend = platform::TrayBackend::spawn(CommandSender {
+            sender,
+            ctx: ctx.clone(),
+        })?;
+        Some(Self {
+            backend,
+            commands,
+            unread: None,
+            muted: None,
+        })
+    }
+
+    /// Redraws the badge when the count changed.
+    pub(super) fn set_unread(&mut self, unread: usize) {
+        if self.unread != Some(unread) {
+            self.unread = Some(unread);
+            self.backend.set_unread(unread);
+        }
+    }
+
+    /// Ticks the do-not-disturb item when the state changed.
+    pub(super) fn set_muted(&mut self, muted: bool) {
+        if self.muted != Some(muted) {
+            self.muted = Some(muted);
+            self.backend.set_muted(muted);
+        }
+    }
+
+    /// Menu picks since the last call, oldest first.
+    pub(super) fn commands(&self) -> Vec<TrayCommand> {
+        self.commands.try_iter().collect()
+    }
+}
+
+/// Hands menu picks to the app and wakes it, since the window may be hidden.
+#[derive(Clone)]
+struct CommandSender {
+    sender: Sender<TrayCommand>,
+    ctx: Context,
+}
+
+impl CommandSender {
+    fn send(&self, command: TrayCommand) {
+        let _ = self.sender.send(command);
+        self.ctx.request_repaint();
+    }
+}
+
+fn tooltip(unread: usize) -> String {
+    match unread {
+        0 => String::from("Reminder: nothing unread"),
+        1 => String::from("Reminder: 1 unread"),
+        _ => format!("Reminder: {unread} unread"),
+    }
+}
+
+/// Up to two glyphs: the count, or `9+` past 99.
+fn badge_label(unread: usize) -> Vec<[u8; 5]> {
+    if unread > 99 {
+        return vec![DIGITS[9], PLUS];
+    }
+    unread
+        .to_string()
+        .bytes()
+        .map(|digit| DIGITS[usize::from(digit - b'0')])
+        .collect()
+}
+
+/// RGBA pixels of a blue disc, with a red badge holding the count once
+/// anything is unread.
+fn badge_icon(unread: usize) -> Vec<u8> {
+    let mut pixels = vec![0; ICON_SIZE * ICON_SIZE * 4];
+    let mut fill_disc = |cx: f32, cy: f32, radius: f32, rgba: [u8; 4]| {
+        for y in 0..ICON_SIZE {
+            for x in 0..ICON_SIZE {
+                let (dx, dy) = (x as f32 + 0.5 - cx, y as f32 + 0.5 - cy);
+                if dx * dx + dy * dy <= radius * radius {
+                    let at = (y * ICON_SIZE + x) * 4;
+                    pixels[at..at + 4].copy_from_slice(&rgba);
+                }
+            }
+        }
+    };
+    fill_disc(14.0, 18.0, 13.0, DISC_RGBA);
+    if unread == 0 {
+        return pixels;
+    }
+    fill_disc(23.0, 9.0, 9.0, BADGE_RGBA);
+
+    let glyphs = badge_label(unread);
+    let width = glyphs.len() * 4 - 1;
+    let (left, top) = (23 - width / 2, 9 - 2);
+    for (index, glyph) in glyphs.iter().enumerate() {
+        for (row, bits) in glyph.iter().enumerate() {
+            for column in 0..3 {
+                if bits & (0b100 >> column) != 0 {
+                    let (x, y) = (left + index * 4 + column, top + row);
+                    let at = (y * ICON_SIZE + x) * 4;
+                    pixels[at..at + 4].copy_from_slice(&WHITE);
+                }
+            }
+        }
+    }
+    pixels
+}
+
+#[cfg(all(feature = "tray", target_os = "linux"))]
+mod platform {
+    use ksni::{
+        blocking::{Handle, TrayMethods},
+        menu::{CheckmarkItem, MenuItem, StandardItem},
+    };
+
+    use super::{CommandSender, ICON_SIZE, TrayCommand, badge_icon, tooltip};
+
+    struct SniTray {
+        unread: usize,
+        muted: bool,
+        commands: CommandSender,
+    }
+
+    impl ksni::Tray for SniTray {
+        fn id(&self) -> String {
+            env!("CARGO_PKG_NAME").into()
+        }
+
+        fn title(&self) -> String {
+            tooltip(self.unread)
+        }
+
+        fn icon_pixmap(&self) -> Vec<ksni::Icon> {
+            let mut data = badge_icon(self.unread);
+            for pixel in data.chunks_exact_mut(4) {
+                pixel.rotate_right(1);
+            }
+            vec![ksni::Icon {
+                width: ICON_SIZE as i32,
+                height: ICON_SIZE as i32,
+                data,
+            }]
+        }
+
+        fn tool_tip(&self) -> ksni::ToolTip {
+            ksni::ToolTip {
+                title: tooltip(self.unread),
+                ..Default::default()
+            }
+        }
+
+        fn activate(&mut self, _x: i32, _y: i32) {
+            self.commands.send(TrayCommand::Open);
+        }
+
+        fn menu(&self) -> Vec<MenuItem<Self>> {
+            let item = |label: &str, command| {
+                StandardItem {
+                    label: label.into(),
+                    activate: Box::new(move |tray: &mut Self| tray.commands.send(command)),
+                    ..Default::default()
+                }
+                .into()
+            };
+            vec![
+                item("Open", TrayCommand::Open),
+                item("Refresh all", TrayCommand::RefreshAll),
+                CheckmarkItem {
+                    label: "Do not disturb".into(),
+                    checked: self.muted,
+                    activate: Box::new(|tray: &mut Self| {
+                        tray.commands.send(TrayCommand::ToggleMute)
+                    }),
+                    ..Default::default()
+                }
+                .into(),
+                MenuItem::Separator,
+                item("Quit", TrayCommand::Quit),
+            ]
+        }
+    }
+
+    pub(super) struct TrayBackend(Handle<SniTray>);
+
+    impl TrayBackend {
+        pub(super) fn spawn(commands: CommandSender) -> Option<Self> {
+            SniTray {
+                unread: 0,
+                muted: false,
+                commands,
+            }
+            .spawn()
+            .ok()
+            .map(Self)
+        }
+
+        pub(super) fn set_unread(&self, unread: usize) {
+            self.0.update(|tray| tray.unread = unread);
+        }
+
+        pub(super) fn set_muted(&self, muted: bool) {
+            self.0.update(|tray| tray.muted = muted);
+        }
+    }
+}
+
+#[cfg(all(feature = "tray", any(windows, target_os = "macos")))]
+mod platform {
+    use tray_icon::{
+        Icon, TrayIcon, TrayIconBuilder,
+        menu::{CheckMenuItem, Menu, MenuEvent, MenuItem, PredefinedMenuItem},
+    };
+
+    use super::{CommandSender, ICON_SIZE, TrayCommand, badge_icon, tooltip};
+
+    fn icon(unread: usize) -> Option<Icon> {
+        Icon::from_rgba(badge_icon(unread), ICON_SIZE as u32, ICON_SIZE as u32).ok()
+    }
+
+    pub(super) struct TrayBackend {
+        icon: TrayIcon,
+        mute: CheckMenuItem,
+    }
+
+    impl TrayBackend {
+        pub(super) fn spawn(commands: CommandSender) -> Option<Self> {
+            let open = MenuItem::new("Open", true, None);
+            let refresh = MenuItem::new("Refresh all", true, None);
+            let mute = CheckMenuItem::new("Do not disturb", true, false, None);
+            let quit = MenuItem::new("Quit", true, None);
+            let menu = Menu::with_items(&[
+                &open,
+                &refresh,
+                &mute,
+                &PredefinedMenuItem::separator(),
+                &quit,
+            ])
+            .ok()?;
+            let targets = [
+                (open.id().clone(), TrayCommand::Open),
+                (refresh.id().clone(), TrayCommand::RefreshAll),
+                (mute.id().clone(), TrayCommand::ToggleMute),
+                (quit.id().clone(), TrayCommand::Quit),
+            ];
+            MenuEvent::set_event_handler(Some(move |event: MenuEvent| {
+                if let Some((_, command)) = targets.iter().find(|(id, _)| *id == event.id) {
+                    commands.send(*command);
+                }
+            }));
+            let icon = TrayIconBuilder::new()
+                .with_menu(Box::new(menu))
+                .with_tooltip(tooltip(0))
+                .with_icon(icon(0)?)
+                .build()
+                .ok()?;
+            Some(Self { icon, mute })
+        }
+
+        pub(super) fn set_unread(&self, unread: usize) {
+            let _ = self.icon.set_icon(icon(unread));
+            let _ = self.icon.set_tooltip(Some(tooltip(unread)));
+        }
+
+        pub(super) fn set_muted(&self, muted: bool) {
+            self.mute.set_checked(muted);
+        }
+    }
+}
+
+#[cfg(not(all(
+    feature = "tray",
+    any(target_os = "linux", windows, target_os = "macos")
+)))]
+mod platform {
+    use super::CommandSender;
+
+    pub(super) struct TrayBackend;
+
+    impl TrayBackend {
+        pub(super) fn spawn(_commands: CommandSender) -> Option<Self> {
+            None
+        }
+
+        pub(super) fn set_unread(&self, _unread: usize) {}
+
+        pub(super) fn set_muted(&self, _muted: bool) {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BADGE_RGBA, DIGITS, ICON_SIZE, PLUS, WHITE, badge_icon, badge_label};
+
+    fn count(pixels: &[u8], rgba: [u8; 4]) -> usize {
+        pixels
+            .chunks_exact(4)
+            .filter(|pixel| *pixel == rgba)
+            .count()
+    }
+
+    #[test]
+    fn badge_appears_with_unread_threads_and_caps_its_label() {
+        let idle = badge_icon(0);
+        assert_eq!(idle.len(), ICON_SIZE * ICON_SIZE * 4);
+        assert_eq!(count(&idle, BADGE_RGBA), 0);
+
+        let busy = badge_icon(7);
+        assert!(count(&busy, BADGE_RGBA) > 0);
+        assert!(count(&busy, WHITE) > 0);
+
+        assert_eq!(badge_label(7).len(), 1);
+        assert_eq!(badge_label(42).len(), 2);
+        assert_eq!(badge_label(150), [DIGITS[9], PLUS]);
+    }
+}
@@ -1,38 +1,78 @@
 use std::collections::BTreeMap;
 
-use eframe::egui::{self, Layout, RichText};
+use chrono::{Local, Utc};
+use eframe::egui::{self, Key, Layout, Modifiers, RichText};
+
+use crate::domain::AppSettings;
 
 use super::{
     super::{
-        AccountAction, AccountViewMode,
+        AccountAction, AccountViewMode, RATE_LIMIT_BACKOFF_REMAINING, SectionKind,
         review::{resolve_review_launch, review_summary_text},
+        rules::RuleSet,
         search::SearchFilter,
-        state::AccountState,
-        time::format_local_timestamp,
+        state::{AccountState, Recovery},
+        time::{FULL_TIMESTAMP_FORMAT, format_elapsed, format_local_timestamp, format_timestamp},
     },
+    board::render_board,
+    chips::render_filter_chips,
     layout::uses_stacked_account_header,
     notifications::{render_bucket_sections, render_unified_inbox_section},
+    search_box::{SEARCH_MODE_TOGGLES_WIDTH, render_search_box},
+    search_results::render_search_sections,
 };
 
+#[allow(clippy::too_many_arguments)]
 pub(in crate::app) fn render_account_card(
     ui: &mut egui::Ui,
     account: &mut AccountState,
     repo_paths: &BTreeMap<String, String>,
     custom_review_command: bool,
+    settings: &AppSettings,
+    rules: &RuleSet,
+    inbox_zero_streak: u32,
+    section_focus: Option<SectionKind>,
 ) {
-    ui.group(|group| {
+    let card = ui.group(|group| {
         render_account_header(group, account);
-        render_account_status(group, account);
-        render_account_body(group, account, repo_paths, custom_review_command);
+        render_account_status(group, account, settings);
+        render_account_body(
+            group,
+            account,
+            repo_paths,
+            custom_review_command,
+            settings,
+            rules,
+            inbox_zero_streak,
+            section_focus,
+        );
     });
+    if card.response.contains_pointer() && consume_refresh_shortcut(ui.ctx()) {
+        account.refresh_requested = true;
+    }
     ui.add_space(12.0);
 }
 
+/// F5, or Cmd+R (Ctrl+R off macOS). The card under the pointer takes it
+/// first; the app falls back to the selected account.
+pub(in crate::app) fn consume_refresh_shortcut(ctx: &egui::Context) -> bool {
+    ctx.input_mut(|input| {
+        input.consume_key(Modifiers::NONE, Key::F5) || input.consume_key(Modifiers::COMMAND, Key::R)
+    })
+}
+
+fn render_enterprise_host(row: &mut egui::Ui, account: &AccountState) {
+    if account.profile.api_base_url.is_some() {
+        row.weak(account.profile.web_base());
+    }
+}
+
 fn render_account_header(group: &mut egui::Ui, account: &mut AccountState) {
     if uses_stacked_account_header(group.available_width()) {
         group.vertical(|column| {
             column.horizontal_wrapped(|row| {
                 row.heading(format!("Account: {}", account.profile.login));
+                render_enterprise_host(row, account);
                 if row
                     .small_button(if account.expanded {
                         "Hide notifications"
@@ -45,16 +85,19 @@ fn render_account_header(group: &mut egui::Ui, account: &mut AccountState) {
                 }
             });
             render_view_mode_toggle(column, account);
-            let search_width = column.available_width();
-            column.add(
-                egui::TextEdit::singleline(&mut account.search_query)
-                    .hint_text("Search…")
-                    .desired_width(search_width),
+            let search_width = column.available_width() - SEARCH_MODE_TOGGLES_WIDTH;
+            render_search_box(
+                column,
+                &mut account.search_query,
+                &mut account.search_mode,
+                "Search…",
+                search_width,
             );
         });
     } else {
         group.horizontal(|row| {
             row.heading(format!("Account: {}", account.profile.login));
+            render_enterprise_host(row, account);
             if row
                 .small_button(if account.expanded {
                     "Hide notifications"
@@ -66,10 +109,12 @@ fn render_account_header(group: &mut egui::Ui, account: &mut AccountState) {
                 account.expanded = !account.expanded;
             }
             row.with_layout(Layout::right_to_left(egui::Align::Center), |lane| {
-                lane.add(
-                    egui::TextEdit::singleline(&mut account.search_query)
-                        .hint_text("Search…")
-                        .desired_width(160.0),
+                render_search_box(
+                    lane,
+                    &mut account.search_query,
+                    &mut account.search_mode,
+                    "Search…",
+                    160.0,
                 );
                 lane.add_space(8.0);
                 render_view_mode_toggle(lane, account);
@@ -87,22 +132,117 @@ fn render_view_mode_toggle(ui: &mut egui::Ui, account: &mut AccountState) {
         "Unified inbox",
     )
     .on_hover_text("Show every GitHub notification in one list, like GitHub's inbox.");
+    ui.selectable_value(&mut account.view_mode, AccountViewMode::Board, "Board")
+        .on_hover_text("Lay threads out by personal stage; drag cards between columns.");
+    ui.checkbox(&mut account.group_by_repo, "By repo")
+        .on_hover_text("Split each section into collapsible per-repository groups.");
+    ui.checkbox(&mut account.group_by_day, "By day")
+        .on_hover_text("Split rows under Today, Yesterday and Earlier.");
 }
 
-fn render_account_status(group: &mut egui::Ui, account: &mut AccountState) {
+fn render_account_status(group: &mut egui::Ui, account: &mut AccountState, settings: &AppSettings) {
+    if let Some(viewer) = &account.viewer.mismatch {
+        group.colored_label(
+            group.visuals().error_fg_color,
+            RichText::new(format!(
+                "This token now belongs to {viewer}, not {}. Auto-refresh is paused; re-add the account with the right token.",
+                account.profile.login
+            ))
+            .strong(),
+        );
+    }
+
     if let Some(inbox) = &account.inbox {
-        group.label(format!(
-            "Last synced {}",
-            format_local_timestamp(inbox.fetched_at, "%Y-%m-%d %H:%M:%S %:z")
-        ));
+        let synced = group
+            .add(
+                egui::Label::new(format!(
+                    "Last synced {}",
+                    format_timestamp(
+                        inbox.fetched_at,
+                        settings.timestamp_style,
+                        FULL_TIMESTAMP_FORMAT
+                    )
+                ))
+                .sense(egui::Sense::click()),
+            )
+            .on_hover_cursor(egui::CursorIcon::PointingHand)
+            .on_hover_text(format!(
+                "{}\nClick, or press F5 / Cmd+R over this card, to refresh.",
+                format_local_timestamp(inbox.fetched_at, FULL_TIMESTAMP_FORMAT)
+            ));
+        if synced.clicked() {
+            account.refresh_requested = true;
+        }
+        if account.inbox_is_cached {
+            group
+                .small(
+                    RichText::new("Offline copy · may be out of date")
+                        .color(group.visuals().warn_fg_color),
+                )
+                .on_hover_text("Shown from the last session until a refresh succeeds.");
+        }
     } else {
         group.label("No data fetched yet.");
     }
 
     if let Some(err) = &account.last_error {
-        group.colored_label(group.visuals().error_fg_color, err);
-    } else if account.pending_job.is_some() {
-        group.label("Fetching latest notifications...");
+        group.colored_label(group.visuals().error_fg_color, err.to_string());
+        render_recovery_actions(group, account, err.recovery(), settings);
+    } else if account.is_cancelling_refresh() {
+        group.horizontal(|row| {
+            row.spinner();
+            row.label("Cancelling…");
+        });
+    } else if let Some(job) = &account.pending_job {
+        let status = job
+            .progress()
+            .unwrap_or_else(|| String::from("Fetching latest notifications…"));
+        let elapsed = format_elapsed(job.elapsed());
+        let mut cancel = false;
+        group.horizontal(|row| {
+            row.spinner();
+            row.label(status);
+            row.weak(elapsed);
+            cancel = row
+                .small_button("Cancel")
+                .on_hover_text("Stop this refresh, for example when the network hangs.")
+                .clicked();
+        });
+        if cancel {
+            account.cancel_refresh();
+        }
+    }
+
+    if let Some(working_hours) = account.profile.working_hours
+        && !working_hours.contains(Local::now().naive_local())
+    {
+        group.small(
+            RichText::new(format!(
+                "Outside working hours ({}) · auto-refresh paused",
+                working_hours.label()
+            ))
+            .color(group.visuals().weak_text_color()),
+        );
+    }
+
+    if let Some(rate_limit) = account.rate_limit {
+        let text = format!(
+            "API quota: {}/{} left, resets {}",
+            rate_limit.remaining,
+            rate_limit.limit,
+            format_local_timestamp(rate_limit.reset_at, "%H:%M")
+        );
+        if account
+            .rate_limit_backoff(RATE_LIMIT_BACKOFF_REMAINING, Utc::now())
+            .is_some()
+        {
+            group.small(
+                RichText::new(format!("{text} · auto-refresh paused"))
+                    .color(group.visuals().warn_fg_color),
+            );
+        } else {
+            group.small(RichText::new(text).color(group.visuals().weak_text_color()));
+        }
     }
 
     for review_output in account.review_outputs.values() {
@@ -123,11 +263,57 @@ fn render_account_status(group: &mut egui::Ui, account: &mut AccountState) {
     }
 }
 
+fn render_recovery_actions(
+    ui: &mut egui::Ui,
+    account: &mut AccountState,
+    recovery: Recovery,
+    settings: &AppSettings,
+) {
+    let mut retry = false;
+    let mut dismiss = false;
+    ui.horizontal(|row| {
+        match recovery {
+            Recovery::UpdateToken => {
+                if row.button("Update token").clicked() {
+                    account.token_update_requested = true;
+                }
+            }
+            Recovery::RetryAt(reset_at) if reset_at > Utc::now() => {
+                row.add_enabled(
+                    false,
+                    egui::Button::new(format!(
+                        "Retry at {}",
+                        format_local_timestamp(reset_at, "%H:%M")
+                    )),
+                )
+                .on_disabled_hover_text("Auto-refresh resumes once the rate limit resets.");
+            }
+            Recovery::RetryAt(_) | Recovery::Retry => {
+                retry = row
+                    .add_enabled(account.pending_job.is_none(), egui::Button::new("Retry"))
+                    .clicked();
+            }
+            Recovery::Dismiss => {}
+        }
+        dismiss = row.small_button("Dismiss").clicked();
+    });
+    if retry {
+        account.start_refresh(settings);
+    } else if dismiss {
+        account.last_error = None;
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 fn render_account_body(
     group: &mut egui::Ui,
     account: &mut AccountState,
     repo_paths: &BTreeMap<String, String>,
     custom_review_command: bool,
+    settings: &AppSettings,
+    rules: &RuleSet,
+    inbox_zero_streak: u32,
+    section_focus: Option<SectionKind>,
 ) {
     if !account.expanded {
         if account.inbox.is_none() {
@@ -137,19 +323,67 @@ fn render_account_body(
         return;
     }
 
+    if account.unread_count() != Some(0) {
+        account.show_read_at_inbox_zero = false;
+    } else if !account.show_read_at_inbox_zero && account.search_query.trim().is_empty() {
+        group.separator();
+        render_inbox_zero_card(group, account, inbox_zero_streak);
+        return;
+    }
+
     if account.inbox.is_some() {
         group.separator();
-        let filter = SearchFilter::new(&account.search_query);
-        let actions = match account.view_mode {
-            AccountViewMode::Inbox => render_unified_inbox_section(
+        let filter = SearchFilter::new(
+            &account.search_query,
+            account.search_mode,
+            settings.transliterate_search,
+        );
+        if let Some(error) = filter.error() {
+            group.colored_label(
+                group.visuals().error_fg_color,
+                format!("Invalid regex: {error}"),
+            );
+        }
+        if let Some(inbox) = &account.inbox {
+            render_filter_chips(
+                group,
+                &account.profile.login,
+                &mut account.chips,
+                &inbox.notifications,
+            );
+        }
+        let actions = match (section_focus, account.view_mode) {
+            (Some(kind), _) => render_unified_inbox_section(
+                group,
+                account,
+                &filter,
+                repo_paths,
+                custom_review_command,
+                settings,
+                rules,
+                kind,
+            ),
+            (None, AccountViewMode::Inbox) => render_unified_inbox_section(
                 group,
                 account,
                 &filter,
                 repo_paths,
                 custom_review_command,
+                settings,
+                rules,
+                SectionKind::Inbox,
             ),
-            AccountViewMode::Grouped => {
-                render_bucket_sections(group, account, &filter, repo_paths, custom_review_command)
+            (None, AccountViewMode::Grouped) => render_bucket_sections(
+                group,
+                account,
+                &filter,
+                repo_paths,
+                custom_review_command,
+                settings,
+                rules,
+            ),
+            (None, AccountViewMode::Board) => {
+                render_board(group, account, &filter, settings, rules)
             }
         };
         for action in actions {
@@ -172,7 +406,9 @@ fn render_account_body(
                         account.request_review(thread_id, launch)
                     } else {
                         account.last_error = Some(
-                            "Custom `review-pr` is unavailable for this repository.".to_owned(),
+                            "Custom `review-pr` is unavailable for this repository."
+                                .to_owned()
+                                .into(),
                         );
                     }
                 }
@@ -183,7 +419,47 @@ fn render_account_body(
                 }
                 AccountAction::Seen(id) => account.mark_notification_seen(&id),
                 AccountAction::Read(id) => account.request_mark_read(id),
+                AccountAction::Unsubscribe(id) => account.request_unsubscribe(id),
+                AccountAction::ToggleRepoGroup(repo) => account.toggle_repo_group(repo),
+                AccountAction::Open { thread_id, url } => {
+                    group.ctx().open_url(egui::OpenUrl::new_tab(url));
+                    account.mark_notification_seen(&thread_id);
+                }
+                AccountAction::ShowDetails(id) => account.detail_thread_id = Some(id),
+                AccountAction::Preview(id) => account.preview_request = Some(id),
+                AccountAction::Remind(id) => account.remind_thread_id = Some(id),
+                AccountAction::Bulk(action) => account.bulk_confirmation = Some(action),
+                AccountAction::SetStage { thread_id, stage } => {
+                    if account.thread_stages.set(thread_id, stage) {
+                        account.thread_stages_dirty = true;
+                    }
+                }
             }
         }
+        if section_focus.is_none()
+            && let Some(kind) = render_search_sections(group, account, &filter, settings)
+        {
+            account.request_more_search_results(kind);
+        }
     }
 }
+
+fn render_inbox_zero_card(group: &mut egui::Ui, account: &mut AccountState, streak_days: u32) {
+    group.vertical_centered(|column| {
+        column.add_space(24.0);
+        column.heading(RichText::new("🎉 Inbox zero").strong());
+        column.label("Nothing unread in review requests, mentions, or notifications.");
+        if streak_days > 0 {
+            column.label(
+                RichText::new(format!("Inbox zero streak: {streak_days} day(s)"))
+                    .strong()
+                    .color(column.visuals().warn_fg_color),
+            );
+        }
+        column.add_space(8.0);
+        if column.small_button("Show read notifications").clicked() {
+            account.show_read_at_inbox_zero = true;
+        }
+        column.add_space(24.0);
+    });
+}
@@ -0,0 +1,167 @@
+use std::cmp::Reverse;
+
+use chrono::Utc;
+use eframe::egui::{self, RichText};
+
+use crate::domain::{AppSettings, NotificationItem, ThreadStage, ThreadStages};
+
+use super::{
+    super::{
+        AccountAction,
+        rules::RuleSet,
+        search::SearchFilter,
+        state::AccountState,
+        time::{FULL_TIMESTAMP_FORMAT, format_local_timestamp, format_timestamp},
+    },
+    notifications::{notification_matches_search, visible_notifications},
+    palette::stage_color,
+};
+
+/// The visible threads as one column per personal stage. Dropping a card on
+/// another column moves the thread to that stage, the same as the list
+/// view's stage picker.
+pub(in crate::app) fn render_board(
+    group: &mut egui::Ui,
+    account: &AccountState,
+    filter: &SearchFilter,
+    settings: &AppSettings,
+    rules: &RuleSet,
+) -> Vec<AccountAction> {
+    let Some(inbox) = &account.inbox else {
+        return Vec::new();
+    };
+    let (visible, _) = visible_notifications(
+        &inbox.notifications,
+        account.profile.hides_bot_notifications(settings),
+        &account.done_threads,
+        settings,
+        rules,
+        Utc::now(),
+    );
+    let visible = visible.into_iter().filter(|item| {
+        account.chips.matches(item)
+            && account.chips.matches_stage(&account.thread_stages, item)
+            && notification_matches_search(item, filter)
+            && !account.inflight_done.contains(&item.thread_id)
+    });
+    let columns = board_columns(visible, &account.thread_stages);
+
+    let mut actions = Vec::new();
+    group.columns(ThreadStage::ALL.len(), |uis| {
+        for ((ui, stage), items) in uis.iter_mut().zip(ThreadStage::ALL).zip(&columns) {
+            ui.label(
+                RichText::new(format!("{} · {}", stage.label(), items.len()))
+                    .strong()
+                    .color(stage_color(stage, ui.visuals())),
+            );
+            let frame = egui::Frame::group(ui.style());
+            let (_, dropped) = ui.dnd_drop_zone::<String, _>(frame, |zone| {
+                zone.set_min_size(egui::vec2(zone.available_width(), 80.0));
+                if items.is_empty() {
+                    zone.weak("Drop threads here.");
+                }
+                for item in items {
+                    let id = egui::Id::new(("board-card", &account.profile.login, &item.thread_id));
+                    zone.dnd_drag_source(id, item.thread_id.clone(), |card| {
+                        actions.extend(render_board_card(card, item, settings));
+                    });
+                }
+            });
+            if let Some(thread_id) = dropped {
+                actions.push(AccountAction::SetStage {
+                    thread_id: thread_id.as_ref().clone(),
+                    stage,
+                });
+            }
+        }
+    });
+    actions
+}
+
+fn render_board_card(
+    ui: &mut egui::Ui,
+    item: &NotificationItem,
+    settings: &AppSettings,
+) -> Option<AccountAction> {
+    let mut action = None;
+    egui::Frame::group(ui.style()).show(ui, |card| {
+        card.set_width(card.available_width());
+        card.small(RichText::new(&item.repo).weak());
+        card.add(egui::Label::new(item.display_title()).wrap());
+        card.horizontal(|row| {
+            row.small(format_timestamp(
+                item.updated_at,
+                settings.timestamp_style,
+                "%m-%d %H:%M",
+            ))
+            .on_hover_text(format_local_timestamp(
+                item.updated_at,
+                FULL_TIMESTAMP_FORMAT,
+            ));
+            if let Some(url) = &item.url
+                && row.small_button("Open").clicked()
+            {
+                action = Some(AccountAction::Open {
+                    thread_id: item.thread_id.clone(),
+                    url: url.clone(),
+                });
+            }
+        });
+    });
+    action
+}
+
+/// Threads under each stage of [`ThreadStage::ALL`], newest first.
+fn board_columns<'a>(
+    items: impl IntoIterator<Item = &'a NotificationItem>,
+    stages: &ThreadStages,
+) -> [Vec<&'a NotificationItem>; 4] {
+    let mut columns: [Vec<_>; 4] = Default::default();
+    for item in items {
+        let stage = stages.stage(&item.thread_id);
+        let index = ThreadStage::ALL
+            .iter()
+            .position(|candidate| *candidate == stage)
+            .unwrap_or_default();
+        columns[index].push(item);
+    }
+    for column in &mut columns {
+        column.sort_by_key(|item| Reverse(item.updated_at));
+    }
+    columns
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+
+    use super::board_columns;
+    use crate::domain::{NotificationItem, ThreadStage, ThreadStages, test_support};
+
+    fn notification(thread_id: &str, minutes_ago: i64) -> NotificationItem {
+        NotificationItem {
+            updated_at: Utc::now() - Duration::minutes(minutes_ago),
+            ..test_support::notification(thread_id)
+        }
+    }
+
+    #[test]
+    fn board_columns_follow_stages_newest_first() {
+        let items = [
+            notification("1", 30),
+            notification("2", 10),
+            notification("3", 20),
+        ];
+        let mut stages = ThreadStages::default();
+        stages.set(String::from("3"), ThreadStage::WaitingOnAuthor);
+
+        let columns = board_columns(&items, &stages);
+
+        let ids = |column: &[&NotificationItem]| -> Vec<String> {
+            column.iter().map(|item| item.thread_id.clone()).collect()
+        };
+        assert_eq!(ids(&columns[0]), ["2", "1"]);
+        assert_eq!(ids(&columns[1]), ["3"]);
+        assert!(columns[2].is_empty() && columns[3].is_empty());
+    }
+}
@@ -0,0 +1,66 @@
+use std::collections::BTreeSet;
+
+use eframe::egui;
+
+use crate::domain::{NotificationChips, NotificationItem, ReadStateChip, ThreadStage};
+
+/// Read-state, stage, reason and repository chips over `items`. Chips stay on
+/// offer while selected even when no thread carries them any more.
+pub(super) fn render_filter_chips<'a>(
+    ui: &mut egui::Ui,
+    id_salt: &str,
+    chips: &mut NotificationChips,
+    items: impl IntoIterator<Item = &'a NotificationItem>,
+) {
+    let mut reasons: BTreeSet<String> = chips.reasons.clone();
+    let mut repos: BTreeSet<String> = chips.repos.clone();
+    for item in items {
+        reasons.insert(item.reason.clone());
+        repos.insert(item.repo.clone());
+    }
+
+    ui.horizontal_wrapped(|row| {
+        for state in ReadStateChip::ALL {
+            row.selectable_value(&mut chips.state, state, state.label());
+        }
+        row.separator();
+        for stage in ThreadStage::ALL {
+            let selected = chips.stage == Some(stage);
+            if row.selectable_label(selected, stage.label()).clicked() {
+                chips.stage = (!selected).then_some(stage);
+            }
+        }
+        row.separator();
+        for reason in reasons {
+            let selected = chips.reasons.contains(&reason);
+            if row.selectable_label(selected, reason.as_str()).clicked() {
+                toggle(&mut chips.reasons, reason);
+            }
+        }
+        row.separator();
+        let summary = match chips.repos.len() {
+            0 => String::from("All repositories"),
+            1 => chips.repos.iter().next().cloned().unwrap_or_default(),
+            count => format!("{count} repositories"),
+        };
+        egui::ComboBox::from_id_salt(("chip-repos", id_salt))
+            .selected_text(summary)
+            .show_ui(row, |menu| {
+                for repo in repos {
+                    let mut selected = chips.repos.contains(&repo);
+                    if menu.checkbox(&mut selected, repo.as_str()).changed() {
+                        toggle(&mut chips.repos, repo);
+                    }
+                }
+            });
+        if chips.is_active() && row.small_button("Clear filters").clicked() {
+            *chips = NotificationChips::default();
+        }
+    });
+}
+
+fn toggle(set: &mut BTreeSet<String>, value: String) {
+    if !set.remove(&value) {
+        set.insert(value);
+    }
+}
@@ -0,0 +1,233 @@
+use chrono::Utc;
+use eframe::egui::{self, RichText};
+use egui_extras::{Column, TableBuilder};
+
+use crate::domain::{AppSettings, NotificationChips, NotificationItem};
+
+use super::{
+    super::{
+        AccountAction,
+        rules::RuleSet,
+        state::AccountState,
+        time::{FULL_TIMESTAMP_FORMAT, format_local_timestamp, format_timestamp},
+    },
+    chips::render_filter_chips,
+    notifications::{owner_avatar, provider_tag, visible_notifications},
+    palette::account_color,
+};
+
+/// One notification of the combined table and the account that owns it.
+struct CombinedRow<'a> {
+    /// Index into the app's accounts, which actions are routed back to.
+    account: usize,
+    item: &'a NotificationItem,
+}
+
+/// Every account's visible notifications in one table, newest first.
+/// Actions go back to the account a row came from.
+pub(in crate::app) fn render_combined_inbox(
+    ui: &mut egui::Ui,
+    accounts: &mut [AccountState],
+    chips: &mut NotificationChips,
+    settings: &AppSettings,
+    rules: &RuleSet,
+) {
+    render_filter_chips(
+        ui,
+        "combined",
+        chips,
+        accounts
+            .iter()
+            .filter_map(|account| account.inbox.as_ref())
+            .flat_map(|inbox| &inbox.notifications),
+    );
+    ui.add_space(4.0);
+    let now = Utc::now();
+    let feeds = accounts.iter().enumerate().map(|(index, account)| {
+        let Some(inbox) = &account.inbox else {
+            return (index, Vec::new());
+        };
+        let (visible, _) = visible_notifications(
+            &inbox.notifications,
+            account.profile.hides_bot_notifications(settings),
+            &account.done_threads,
+            settings,
+            rules,
+            now,
+        );
+        let visible = visible
+            .into_iter()
+            .filter(|item| {
+                chips.matches(item)
+                    && chips.matches_stage(&account.thread_stages, item)
+                    && !account.inflight_done.contains(&item.thread_id)
+                    && !account.unsubscribed_thread_ids.contains(&item.thread_id)
+            })
+            .collect();
+        (index, visible)
+    });
+    let rows = merge_feeds(feeds);
+
+    if rows.is_empty() {
+        ui.weak("You're all caught up 🎉");
+        return;
+    }
+    let logins: Vec<_> = accounts
+        .iter()
+        .map(|account| account.profile.login.as_str())
+        .collect();
+    let actions = draw_combined_table(ui, &rows, &logins, settings);
+    for (index, action) in actions {
+        let account = &mut accounts[index];
+        match action {
+            AccountAction::Open { thread_id, url } => {
+                ui.ctx().open_url(egui::OpenUrl::new_tab(url));
+                account.mark_notification_seen(&thread_id);
+            }
+            AccountAction::Read(id) => account.request_mark_read(id),
+            AccountAction::Done(id) => account.request_mark_done(id),
+            _ => {}
+        }
+    }
+}
+
+fn merge_feeds<'a>(
+    feeds: impl IntoIterator<Item = (usize, Vec<&'a NotificationItem>)>,
+) -> Vec<CombinedRow<'a>> {
+    let mut rows: Vec<_> = feeds
+        .into_iter()
+        .flat_map(|(account, items)| {
+            items
+                .into_iter()
+                .map(move |item| CombinedRow { account, item })
+        })
+        .collect();
+    rows.sort_by_key(|row| std::cmp::Reverse(row.item.updated_at));
+    rows
+}
+
+fn draw_combined_table(
+    ui: &mut egui::Ui,
+    rows: &[CombinedRow<'_>],
+    logins: &[&str],
+    settings: &AppSettings,
+) -> Vec<(usize, AccountAction)> {
+    let mut actions = Vec::new();
+    egui::ScrollArea::horizontal()
+        .id_salt("combined-table-scroll")
+        .auto_shrink([false, true])
+        .show(ui, |ui| {
+            TableBuilder::new(ui)
+                .id_salt("combined-table")
+                .striped(true)
+                .column(Column::initial(100.0).resizable(true))
+                .column(Column::initial(130.0).resizable(true))
+                .column(Column::remainder().at_least(160.0))
+                .column(Column::initial(110.0).resizable(true))
+                .column(Column::initial(120.0))
+                .column(Column::initial(100.0))
+                .header(20.0, |mut header| {
+                    for heading in ["Account", "Repository", "Title", "Reason", "Updated", ""] {
+                        header.col(|ui| {
+                            ui.strong(heading);
+                        });
+                    }
+                })
+                .body(|body| {
+                    body.rows(22.0, rows.len(), |mut table_row| {
+                        let row = &rows[table_row.index()];
+                        let login = logins[row.account];
+                        let item = row.item;
+                        table_row.col(|ui| {
+                            ui.label(RichText::new(login).strong().color(account_color(login)));
+                        });
+                        table_row.col(|ui| {
+                            ui.horizontal(|cell| {
+                                owner_avatar(cell, item, settings);
+                                cell.label(&item.repo);
+                                provider_tag(cell, item);
+                            });
+                        });
+                        table_row.col(|ui| {
+                            let mut title = RichText::new(item.display_title());
+                            if item.unread {
+                                title = title.strong();
+                            }
+                            match &item.url {
+                                Some(url) => {
+                                    if ui.link(title).on_hover_text(url).clicked() {
+                                        actions.push((
+                                            row.account,
+                                            AccountAction::Open {
+                                                thread_id: item.thread_id.clone(),
+                                                url: url.clone(),
+                                            },
+                                        ));
+                                    }
+                                }
+                                None => {
+                                    ui.label(title);
+                                }
+                            }
+                        });
+                        table_row.col(|ui| {
+                            ui.label(&item.reason);
+                        });
+                        table_row.col(|ui| {
+                            ui.label(format_timestamp(
+                                item.updated_at,
+                                settings.timestamp_style,
+                                "%Y-%m-%d %H:%M",
+                            ))
+                            .on_hover_text(format_local_timestamp(
+                                item.updated_at,
+                                FULL_TIMESTAMP_FORMAT,
+                            ));
+                        });
+                        table_row.col(|ui| {
+                            if item.unread && ui.small_button("Read").clicked() {
+                                actions.push((
+                                    row.account,
+                                    AccountAction::Read(item.thread_id.clone()),
+                                ));
+                            }
+                            if ui.small_button("Done").clicked() {
+                                actions.push((
+                                    row.account,
+                                    AccountAction::Done(item.thread_id.clone()),
+                                ));
+                            }
+                        });
+                    });
+                });
+        });
+    actions
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+
+    use super::merge_feeds;
+    use crate::domain::{NotificationItem, test_support};
+
+    fn item(thread_id: &str, minutes_ago: i64) -> NotificationItem {
+        NotificationItem {
+            updated_at: Utc::now() - Duration::minutes(minutes_ago),
+            ..test_support::notification(thread_id)
+        }
+    }
+
+    #[test]
+    fn feeds_merge_newest_first_and_keep_their_account() {
+        let (a, b, c) = (item("a", 30), item("b", 5), item("c", 10));
+
+        let rows = merge_feeds([(0, vec![&a, &c]), (1, vec![&b])]);
+
+        let order: Vec<_> = rows
+            .iter()
+            .map(|row| (row.account, row.item.thread_id.as_str()))
+            .collect();
+        assert_eq!(order, [(1, "b"), (0, "c"), (0, "a")]);
+    }
+}
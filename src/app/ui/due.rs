@@ -0,0 +1,79 @@
+use chrono::{DateTime, Utc};
+use eframe::egui::{self, RichText};
+
+use crate::domain::{Reminder, ReminderList};
+
+use super::super::{reminders::ReminderAction, time::format_local_timestamp};
+
+/// "Due" block shown above the notifications while any reminder exists.
+pub(in crate::app) fn render_due_section(
+    ui: &mut egui::Ui,
+    reminders: &ReminderList,
+    now: DateTime<Utc>,
+) -> Vec<ReminderAction> {
+    let mut actions = Vec::new();
+    if reminders.reminders.is_empty() {
+        return actions;
+    }
+
+    let due: Vec<_> = reminders.due(now).collect();
+    let upcoming: Vec<_> = reminders.upcoming(now).collect();
+    ui.group(|group| {
+        let heading = RichText::new(format!("Due ({})", due.len())).strong();
+        group.heading(if due.is_empty() {
+            heading
+        } else {
+            heading.color(group.visuals().warn_fg_color)
+        });
+        if due.is_empty() {
+            group.weak("Nothing due right now.");
+        }
+        for reminder in &due {
+            group.horizontal_wrapped(|row| {
+                render_reminder_link(row, reminder, &mut actions);
+                row.small(format!(
+                    "due {}",
+                    format_local_timestamp(reminder.due_at, "%a %H:%M")
+                ));
+                if row.small_button("In 1 hour").clicked() {
+                    actions.push(ReminderAction::Snooze(reminder.id, "in 1h"));
+                }
+                if row.small_button("Tomorrow").clicked() {
+                    actions.push(ReminderAction::Snooze(reminder.id, "tomorrow 09:00"));
+                }
+                if row.small_button("Done").clicked() {
+                    actions.push(ReminderAction::Done(reminder.id));
+                }
+            });
+        }
+
+        if !upcoming.is_empty() {
+            egui::CollapsingHeader::new(format!("Upcoming ({})", upcoming.len()))
+                .id_salt("upcoming-reminders")
+                .default_open(false)
+                .show(group, |section| {
+                    for reminder in &upcoming {
+                        section.horizontal_wrapped(|row| {
+                            render_reminder_link(row, reminder, &mut actions);
+                            row.small(format_local_timestamp(reminder.due_at, "%a %Y-%m-%d %H:%M"));
+                            if row.small_button("Remove").clicked() {
+                                actions.push(ReminderAction::Done(reminder.id));
+                            }
+                        });
+                    }
+                });
+        }
+    });
+    ui.add_space(12.0);
+    actions
+}
+
+fn render_reminder_link(ui: &mut egui::Ui, reminder: &Reminder, actions: &mut Vec<ReminderAction>) {
+    let mut label = reminder.title.clone();
+    if let Some(login) = &reminder.login {
+        label.push_str(&format!(" · {login}"));
+    }
+    if ui.link(label).on_hover_text(&reminder.url).clicked() {
+        actions.push(ReminderAction::Open(reminder.id));
+    }
+}
@@ -0,0 +1,253 @@
+use chrono::{Local, NaiveDate};
+use eframe::egui;
+use egui_extras::{Column, TableBuilder};
+
+use crate::domain::{AppSettings, ArchivedThread, NotificationArchive, SearchMode};
+
+use super::{
+    super::{search::SearchFilter, state::AccountState, time::format_local_timestamp},
+    search_box::render_search_box,
+};
+
+/// Query and date range of the open history window.
+#[derive(Default)]
+pub(in crate::app) struct HistoryView {
+    query: String,
+    mode: SearchMode,
+    /// Inclusive bounds on the local day of a thread's last activity, as
+    /// `YYYY-MM-DD`; blank leaves that side open.
+    from: String,
+    to: String,
+}
+
+struct HistoryRow<'a> {
+    login: &'a str,
+    thread: &'a ArchivedThread,
+}
+
+/// Every thread the accounts have seen, including ones marked done or gone
+/// from GitHub's feed. Closing the window clears `view`.
+pub(in crate::app) fn render_history_window(
+    ctx: &egui::Context,
+    view: &mut Option<HistoryView>,
+    accounts: &[AccountState],
+    settings: &AppSettings,
+) {
+    let Some(state) = view.as_mut() else {
+        return;
+    };
+    let mut open = true;
+    egui::Window::new("History")
+        .open(&mut open)
+        .resizable(true)
+        .default_size(egui::vec2(760.0, 460.0))
+        .show(ctx, |ui| {
+            ui.horizontal_wrapped(|row| {
+                render_search_box(
+                    row,
+                    &mut state.query,
+                    &mut state.mode,
+                    "Search history",
+                    200.0,
+                );
+                row.label("From");
+                row.add(
+                    egui::TextEdit::singleline(&mut state.from)
+                        .hint_text("YYYY-MM-DD")
+                        .desired_width(90.0),
+                );
+                row.label("To");
+                row.add(
+                    egui::TextEdit::singleline(&mut state.to)
+                        .hint_text("YYYY-MM-DD")
+                        .desired_width(90.0),
+                );
+            });
+            let filter = SearchFilter::new(&state.query, state.mode, settings.transliterate_search);
+            if let Some(error) = filter.error() {
+                ui.colored_label(ui.visuals().error_fg_color, error);
+            }
+            let (from, to) = match (parse_bound(&state.from), parse_bound(&state.to)) {
+                (Ok(from), Ok(to)) => (from, to),
+                _ => {
+                    ui.colored_label(ui.visuals().error_fg_color, "Dates use YYYY-MM-DD.");
+                    (None, None)
+                }
+            };
+
+            let rows = history_rows(
+                accounts
+                    .iter()
+                    .map(|account| (account.profile.login.as_str(), &account.archive)),
+                &filter,
+                from,
+                to,
+            );
+            ui.small(format!("{} thread(s)", rows.len()));
+            ui.separator();
+            if rows.is_empty() {
+                ui.weak("No threads match.");
+                return;
+            }
+            draw_history_table(ui, &rows);
+        });
+    if !open {
+        *view = None;
+    }
+}
+
+fn parse_bound(raw: &str) -> Result<Option<NaiveDate>, chrono::ParseError> {
+    let raw = raw.trim();
+    if raw.is_empty() {
+        return Ok(None);
+    }
+    NaiveDate::parse_from_str(raw, "%Y-%m-%d").map(Some)
+}
+
+/// Matching threads across `archives`, most recent activity first.
+fn history_rows<'a>(
+    archives: impl IntoIterator<Item = (&'a str, &'a NotificationArchive)>,
+    filter: &SearchFilter,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+) -> Vec<HistoryRow<'a>> {
+    let mut rows: Vec<_> = archives
+        .into_iter()
+        .flat_map(|(login, archive)| {
+            archive
+                .entries
+                .values()
+                .map(move |thread| HistoryRow { login, thread })
+        })
+        .filter(|row| {
+            let day = row
+                .thread
+                .last_activity()
+                .with_timezone(&Local)
+                .date_naive();
+            from.is_none_or(|from| day >= from)
+                && to.is_none_or(|to| day <= to)
+                && filter.matches_any(&[&row.thread.repo, &row.thread.title, &row.thread.reason])
+        })
+        .collect();
+    rows.sort_by_key(|row| std::cmp::Reverse(row.thread.last_activity()));
+    rows
+}
+
+fn draw_history_table(ui: &mut egui::Ui, rows: &[HistoryRow<'_>]) {
+    egui::ScrollArea::horizontal()
+        .id_salt("history-table-scroll")
+        .auto_shrink([false, true])
+        .show(ui, |ui| {
+            TableBuilder::new(ui)
+                .id_salt("history-table")
+                .striped(true)
+                .column(Column::initial(90.0).resizable(true))
+                .column(Column::initial(130.0).resizable(true))
+                .column(Column::remainder().at_least(160.0))
+                .column(Column::initial(110.0).resizable(true))
+                .column(Column::initial(120.0))
+                .column(Column::initial(120.0))
+                .header(20.0, |mut header| {
+                    for heading in [
+                        "Account",
+                        "Repository",
+                        "Title",
+                        "Reason",
+                        "Updated",
+                        "Done",
+                    ] {
+                        header.col(|ui| {
+                            ui.strong(heading);
+                        });
+                    }
+                })
+                .body(|body| {
+                    body.rows(22.0, rows.len(), |mut table_row| {
+                        let row = &rows[table_row.index()];
+                        table_row.col(|ui| {
+                            ui.label(row.login);
+                        });
+                        table_row.col(|ui| {
+                            ui.label(&row.thread.repo);
+                        });
+                        table_row.col(|ui| match &row.thread.url {
+                            Some(url) => {
+                                ui.hyperlink_to(&row.thread.title, url);
+                            }
+                            None => {
+                                ui.label(&row.thread.title);
+                            }
+                        });
+                        table_row.col(|ui| {
+                            ui.label(&row.thread.reason);
+                        });
+                        table_row.col(|ui| {
+                            ui.label(format_local_timestamp(
+                                row.thread.updated_at,
+                                "%Y-%m-%d %H:%M",
+                            ));
+                        });
+                        table_row.col(|ui| {
+                            if let Some(done_at) = row.thread.done_at {
+                                ui.label(format_local_timestamp(done_at, "%Y-%m-%d %H:%M"));
+                            }
+                        });
+                    });
+                });
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Local, Utc};
+
+    use super::{history_rows, parse_bound};
+    use crate::{
+        app::search::SearchFilter,
+        domain::{ArchivedThread, NotificationArchive, SearchMode},
+    };
+
+    #[test]
+    fn history_rows_filter_by_day_and_query_newest_first() {
+        let now = Utc::now();
+        let thread = |title: &str, updated_at| ArchivedThread {
+            repo: String::from("acme/app"),
+            title: title.to_owned(),
+            url: None,
+            reason: String::from("mention"),
+            first_seen: updated_at,
+            updated_at,
+            done_at: None,
+        };
+        let mut archive = NotificationArchive::default();
+        archive.entries.insert(
+            String::from("1"),
+            thread("Fix login", now - Duration::days(14)),
+        );
+        archive
+            .entries
+            .insert(String::from("2"), thread("Fix logout", now));
+        archive
+            .entries
+            .insert(String::from("3"), thread("Crash", now - Duration::days(13)));
+        let archives = [("neo", &archive)];
+        let two_weeks_ago = (now - Duration::days(14))
+            .with_timezone(&Local)
+            .date_naive();
+
+        let everything = SearchFilter::new("", SearchMode::Substring, false);
+        let all = history_rows(archives, &everything, None, None);
+        let titles: Vec<_> = all.iter().map(|row| row.thread.title.as_str()).collect();
+        assert_eq!(titles, ["Fix logout", "Crash", "Fix login"]);
+
+        let fix = SearchFilter::new("fix", SearchMode::Substring, false);
+        let old = history_rows(archives, &fix, Some(two_weeks_ago), Some(two_weeks_ago));
+        assert_eq!(old.len(), 1);
+        assert_eq!(old[0].thread.title, "Fix login");
+        assert_eq!(old[0].login, "neo");
+
+        assert!(parse_bound("2026-13-01").is_err());
+        assert_eq!(parse_bound("  "), Ok(None));
+    }
+}
@@ -0,0 +1,374 @@
+//! Just enough GitHub flavored markdown for issue and comment bodies:
+//! headings, paragraphs, lists, quotes, fenced code, rules and the common
+//! inline styles. Anything else is shown as written.
+
+use eframe::egui::{self, RichText};
+use reqwest::Url;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Block {
+    Heading(usize, String),
+    Paragraph(String),
+    /// Marker as written (`•` for bullets, `3.` for numbered items) and the
+    /// nesting depth.
+    ListItem {
+        marker: String,
+        depth: usize,
+        text: String,
+    },
+    Quote(String),
+    Code(String),
+    Rule,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Span {
+    Text(String),
+    Strong(String),
+    Emphasis(String),
+    Code(String),
+    Link { text: String, url: String },
+}
+
+/// Relative links resolve against `base_url`, the thread's page.
+pub(in crate::app) fn render_markdown(ui: &mut egui::Ui, text: &str, base_url: Option<&str>) {
+    let base_url = base_url.and_then(|url| Url::parse(url).ok());
+    let base_url = base_url.as_ref();
+    for block in parse_blocks(text) {
+        match block {
+            Block::Heading(level, text) => {
+                let size = match level {
+                    1 => 1.4,
+                    2 => 1.25,
+                    _ => 1.1,
+                };
+                let height = ui.text_style_height(&egui::TextStyle::Body) * size;
+                ui.label(RichText::new(text).strong().size(height));
+            }
+            Block::Paragraph(text) => render_inline(ui, &text, base_url),
+            Block::ListItem {
+                marker,
+                depth,
+                text,
+            } => {
+                ui.horizontal_wrapped(|row| {
+                    row.add_space(12.0 * depth as f32);
+                    row.label(marker);
+                    render_spans(row, &text, base_url);
+                });
+            }
+            Block::Quote(text) => {
+                ui.horizontal_wrapped(|row| {
+                    row.label(RichText::new("▎").weak());
+                    row.label(RichText::new(text).weak().italics());
+                });
+            }
+            Block::Code(code) => {
+                egui::Frame::group(ui.style()).show(ui, |frame| {
+                    frame.set_width(frame.available_width());
+                    frame.label(RichText::new(code).monospace());
+                });
+            }
+            Block::Rule => {
+                ui.separator();
+            }
+        }
+    }
+}
+
+fn render_inline(ui: &mut egui::Ui, text: &str, base_url: Option<&Url>) {
+    ui.horizontal_wrapped(|row| render_spans(row, text, base_url));
+}
+
+fn render_spans(ui: &mut egui::Ui, text: &str, base_url: Option<&Url>) {
+    ui.spacing_mut().item_spacing.x = 0.0;
+    for span in parse_spans(text) {
+        match span {
+            Span::Text(text) => {
+                ui.label(text);
+            }
+            Span::Strong(text) => {
+                ui.label(RichText::new(text).strong());
+            }
+            Span::Emphasis(text) => {
+                ui.label(RichText::new(text).italics());
+            }
+            Span::Code(text) => {
+                ui.label(RichText::new(text).code());
+            }
+            Span::Link { text, url } => match link_target(&url, base_url) {
+                Some(url) => {
+                    ui.hyperlink_to(text, url);
+                }
+                None => {
+                    ui.label(text);
+                }
+            },
+        }
+    }
+}
+
+fn parse_blocks(text: &str) -> Vec<Block> {
+    let text = strip_html_comments(text);
+    let mut blocks = Vec::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut code: Option<Vec<&str>> = None;
+    let flush = |paragraph: &mut Vec<&str>, blocks: &mut Vec<Block>| {
+        if !paragraph.is_empty() {
+            blocks.push(Block::Paragraph(paragraph.join(" ")));
+            paragraph.clear();
+        }
+    };
+
+    for line in text.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            match code.take() {
+                Some(lines) => blocks.push(Block::Code(lines.join("\n"))),
+                None => {
+                    flush(&mut paragraph, &mut blocks);
+                    code = Some(Vec::new());
+                }
+            }
+            continue;
+        }
+        if let Some(lines) = &mut code {
+            lines.push(line);
+            continue;
+        }
+        if trimmed.is_empty() {
+            flush(&mut paragraph, &mut blocks);
+            continue;
+        }
+        if let Some(block) = line_block(line) {
+            flush(&mut paragraph, &mut blocks);
+            blocks.push(block);
+        } else {
+            paragraph.push(trimmed);
+        }
+    }
+    // An unterminated fence runs to the end, as on GitHub.
+    if let Some(lines) = code {
+        blocks.push(Block::Code(lines.join("\n")));
+    }
+    flush(&mut paragraph, &mut blocks);
+    blocks
+}
+
+/// The block a line starts on its own, or `None` for paragraph text.
+fn line_block(line: &str) -> Option<Block> {
+    let trimmed = line.trim();
+    if ['-', '*', '_'].iter().any(|&mark| {
+        let mut marks = trimmed.chars().filter(|&ch| ch != ' ');
+        trimmed.chars().filter(|&ch| ch == mark).count() >= 3 && marks.all(|ch| ch == mark)
+    }) {
+        return Some(Block::Rule);
+    }
+    let hashes = trimmed.chars().take_while(|&ch| ch == '#').count();
+    if (1..=6).contains(&hashes) && trimmed[hashes..].starts_with(' ') {
+        return Some(Block::Heading(hashes, trimmed[hashes..].trim().to_owned()));
+    }
+    if let Some(quote) = trimmed.strip_prefix('>') {
+        return Some(Block::Quote(quote.trim().to_owned()));
+    }
+    let indent = line.len() - line.trim_start().len();
+    let depth = indent / 2;
+    for bullet in ["- ", "* ", "+ "] {
+        if let Some(text) = trimmed.strip_prefix(bullet) {
+            return Some(Block::ListItem {
+                marker: String::from("•"),
+                depth,
+                text: text.trim().to_owned(),
+            });
+        }
+    }
+    let digits = trimmed.chars().take_while(char::is_ascii_digit).count();
+    if digits > 0
+        && let Some(text) = trimmed[digits..]
+            .strip_prefix(". ")
+            .or_else(|| trimmed[digits..].strip_prefix(") "))
+    {
+        return Some(Block::ListItem {
+            marker: format!("{}.", &trimmed[..digits]),
+            depth,
+            text: text.trim().to_owned(),
+        });
+    }
+    None
+}
+
+/// Issue templates leave their instructions in `<!-- -->` comments, which
+/// GitHub hides too.
+fn strip_html_comments(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("<!--") {
+        out.push_str(&rest[..start]);
+        match rest[start..].find("-->") {
+            Some(end) => rest = &rest[start + end + 3..],
+            None => return out,
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn parse_spans(text: &str) -> Vec<Span> {
+    let mut spans = Vec::new();
+    let mut plain = String::new();
+    let mut rest = text;
+    while let Some(ch) = rest.chars().next() {
+        let styled = match ch {
+            '`' => delimited(rest, "`").map(|(inner, len)| (Span::Code(inner.to_owned()), len)),
+            // Underscores inside words, as in snake_case, stay literal.
+            '_' if plain.chars().last().is_some_and(char::is_alphanumeric) => None,
+            '*' | '_' => {
+                let double = if ch == '*' { "**" } else { "__" };
+                let single = if ch == '*' { "*" } else { "_" };
+                delimited(rest, double)
+                    .map(|(inner, len)| (Span::Strong(inner.to_owned()), len))
+                    .or_else(|| {
+                        delimited(rest, single)
+                            .map(|(inner, len)| (Span::Emphasis(inner.to_owned()), len))
+                    })
+            }
+            '!' if rest.starts_with("![") => link(&rest[1..]).map(|(span, len)| (span, len + 1)),
+            '[' => link(rest),
+            _ => None,
+        };
+        match styled {
+            Some((span, len)) => {
+                if !plain.is_empty() {
+                    spans.push(Span::Text(std::mem::take(&mut plain)));
+                }
+                spans.push(span);
+                rest = &rest[len..];
+            }
+            None => {
+                plain.push(ch);
+                rest = &rest[ch.len_utf8()..];
+            }
+        }
+    }
+    if !plain.is_empty() {
+        spans.push(Span::Text(plain));
+    }
+    spans
+}
+
+/// Text between a leading and closing `delimiter` and the length consumed.
+fn delimited<'a>(text: &'a str, delimiter: &str) -> Option<(&'a str, usize)> {
+    let body = text.strip_prefix(delimiter)?;
+    let end = body.find(delimiter)?;
+    let inner = &body[..end];
+    (!inner.trim().is_empty()).then_some((inner, delimiter.len() * 2 + end))
+}
+
+/// Where a link in an untrusted body may lead: made absolute against the
+/// thread's page, and only on http(s), since anything else goes straight to
+/// the OS opener.
+fn link_target(url: &str, base_url: Option<&Url>) -> Option<String> {
+    let url = Url::options().base_url(base_url).parse(url.trim()).ok()?;
+    matches!(url.scheme(), "http" | "https").then(|| url.into())
+}
+
+/// `[text](url)` and the length consumed.
+fn link(text: &str) -> Option<(Span, usize)> {
+    let body = text.strip_prefix('[')?;
+    let close = body.find("](")?;
+    let after = &body[close + 2..];
+    let end = after.find(')')?;
+    Some((
+        Span::Link {
+            text: body[..close].to_owned(),
+            url: after[..end].to_owned(),
+        },
+        1 + close + 2 + end + 1,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::Url;
+
+    use super::{Block, Span, link_target, parse_blocks, parse_spans};
+
+    #[test]
+    fn bodies_split_into_blocks() {
+        let blocks = parse_blocks(
+            "## Summary\n<!-- describe your change -->\nFixes the\ncache.\n\n- one\n  - nested\n2. two\n> quoted\n```rust\nlet x = 1;\n```\n---",
+        );
+
+        assert_eq!(
+            blocks,
+            [
+                Block::Heading(2, String::from("Summary")),
+                Block::Paragraph(String::from("Fixes the cache.")),
+                Block::ListItem {
+                    marker: String::from("•"),
+                    depth: 0,
+                    text: String::from("one"),
+                },
+                Block::ListItem {
+                    marker: String::from("•"),
+                    depth: 1,
+                    text: String::from("nested"),
+                },
+                Block::ListItem {
+                    marker: String::from("2."),
+                    depth: 0,
+                    text: String::from("two"),
+                },
+                Block::Quote(String::from("quoted")),
+                Block::Code(String::from("let x = 1;")),
+                Block::Rule,
+            ]
+        );
+    }
+
+    #[test]
+    fn inline_styles_and_links_become_spans() {
+        assert_eq!(
+            parse_spans(
+                "See **this** and `code`, *why* [docs](https://x.y) or 2*3 snake_case_name"
+            ),
+            [
+                Span::Text(String::from("See ")),
+                Span::Strong(String::from("this")),
+                Span::Text(String::from(" and ")),
+                Span::Code(String::from("code")),
+                Span::Text(String::from(", ")),
+                Span::Emphasis(String::from("why")),
+                Span::Text(String::from(" ")),
+                Span::Link {
+                    text: String::from("docs"),
+                    url: String::from("https://x.y"),
+                },
+                Span::Text(String::from(" or 2*3 snake_case_name")),
+            ]
+        );
+    }
+
+    #[test]
+    fn links_resolve_against_the_thread_and_stay_on_the_web() {
+        let base = Url::parse("https://github.com/acme/app/pull/7").unwrap();
+        let target = |url| link_target(url, Some(&base));
+
+        assert_eq!(
+            target("../blob/main/README.md").as_deref(),
+            Some("https://github.com/acme/app/blob/main/README.md")
+        );
+        assert_eq!(
+            target("#12").as_deref(),
+            Some("https://github.com/acme/app/pull/7#12")
+        );
+        assert_eq!(
+            target("https://x.y/docs").as_deref(),
+            Some("https://x.y/docs")
+        );
+        assert_eq!(target("file:///etc/passwd"), None);
+        assert_eq!(target("vscode://open?x=1"), None);
+        assert_eq!(target("javascript:alert(1)"), None);
+        assert_eq!(link_target("docs/setup.md", None), None);
+    }
+}
@@ -1,15 +1,41 @@
 mod account_card;
+mod board;
+mod chips;
+mod combined;
+mod due;
+mod history;
 mod layout;
+mod markdown;
 mod notifications;
+mod palette;
+mod plugins;
+mod preview;
+mod radar;
 mod repository_card;
+mod search_box;
+mod search_results;
 mod sidebar;
+mod stale_branches;
+mod swipe;
+mod timeline;
+mod whats_new;
 
-pub(super) use account_card::render_account_card;
+pub(super) use account_card::{consume_refresh_shortcut, render_account_card};
+pub(super) use combined::render_combined_inbox;
+pub(super) use due::render_due_section;
+pub(super) use history::{HistoryView, render_history_window};
 pub(super) use layout::{responsive_accounts_panel_width, uses_compact_account_rows};
+pub(super) use palette::{StatusColors, apply_accent, status_colors};
+pub(super) use plugins::render_plugin_sections;
+pub(super) use preview::{PreviewAction, render_preview_pane};
+pub(super) use radar::render_release_radar;
 pub(super) use repository_card::render_repository_card;
 pub(super) use sidebar::{
     account_overview, render_tracked_account_badges, tracked_account_heading,
 };
+pub(super) use stale_branches::render_stale_branches;
+pub(super) use timeline::render_thread_timeline;
+pub(super) use whats_new::{WhatsNew, render_whats_new_window};
 
 #[cfg(test)]
 pub(in crate::app) use layout::{uses_compact_notifications, uses_stacked_account_header};
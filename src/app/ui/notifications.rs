@@ -1,45 +1,88 @@
-use std::collections::{BTreeMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 
+use chrono::{DateTime, Local, NaiveDate, Utc};
 use eframe::egui::{self, Color32, RichText};
 use egui_extras::{Column, TableBuilder};
 
-use crate::domain::{NotificationItem, PullRequestReviewerStatus};
+use crate::{
+    domain::{
+        AppSettings, CheckState, DoneThreads, DoubleClickAction, Forge, NotificationItem,
+        PullRequestReviewerStatus, PullRequestStatus, RepoTier, ReviewDecision, SavedSearch,
+        SavedSearchKind, ThreadStage, ThreadStages,
+    },
+    github::PullRequestStatusQueue,
+};
 
-const APPROVED_TITLE_CHECK_COLOR: Color32 = Color32::from_rgb(80, 170, 90);
+const CRITICAL_TIER_STRIPE_COLOR: Color32 = Color32::from_rgb(210, 70, 70);
+const LOW_TIER_STRIPE_COLOR: Color32 = Color32::from_rgb(120, 120, 120);
 
 use super::super::{
-    AccountAction, PENDING_REVIEW_LABEL_COLOR, SectionKind,
+    AccountAction, SectionKind,
+    alerts::storm_summary_text,
+    avatars::avatar_uri,
     notification_state::{
-        NotificationVisualState, base_notification_state, is_mention, is_other_notification,
-        is_review_request, pending_review_request_ids, summarize_counts,
+        NotificationVisualState, base_notification_state, pending_review_request_ids,
+        re_requested_review_ids, summarize_counts,
     },
     review::custom_review_available_for_repo,
-    search::SearchFilter,
-    state::AccountState,
-    time::format_local_timestamp,
+    review_wait::{format_wait, overdue_level, review_wait_starts},
+    rules::RuleSet,
+    saved_searches::{FilterQuery, SavedSearchState},
+    search::{SearchFilter, search_fields},
+    snooze::is_auto_snoozed,
+    state::{AccountState, BulkAction, BulkProgress},
+    time::{DayBucket, FULL_TIMESTAMP_FORMAT, format_local_timestamp, format_timestamp},
+};
+use super::{
+    layout::uses_compact_notifications,
+    palette::{StatusColors, stage_color, status_colors},
+    search_results::{render_saved_search_list, render_work_section},
+    swipe::swipe_action,
 };
-use super::layout::uses_compact_notifications;
 
 pub(in crate::app) struct NotificationRenderState<'a> {
     pub(in crate::app) inflight_done: &'a HashSet<String>,
+    pub(in crate::app) unsubscribed_thread_ids: &'a HashSet<String>,
+    pub(in crate::app) group_by_repo: bool,
+    /// Split rows under "Today", "Yesterday" and "Earlier" sub-headers.
+    pub(in crate::app) group_by_day: bool,
+    pub(in crate::app) collapsed_repos: &'a BTreeSet<String>,
     pub(in crate::app) pending_review_ids: &'a HashSet<String>,
+    /// Threads asking again for a review I already gave; listed first.
+    pub(in crate::app) re_requested_ids: &'a HashSet<String>,
+    /// When each pending review request was made, by thread id.
+    pub(in crate::app) review_waits: &'a HashMap<String, DateTime<Utc>>,
     pub(in crate::app) active_review_thread_ids: &'a HashSet<String>,
     pub(in crate::app) review_output_thread_ids: &'a HashSet<String>,
     pub(in crate::app) open_review_window_thread_ids: &'a HashSet<String>,
     pub(in crate::app) custom_review_command: bool,
     pub(in crate::app) repo_paths: &'a BTreeMap<String, String>,
+    pub(in crate::app) settings: &'a AppSettings,
+    pub(in crate::app) rules: &'a RuleSet,
+    pub(in crate::app) pull_request_statuses: &'a PullRequestStatusQueue,
+    pub(in crate::app) bulk_progress: Option<&'a BulkProgress>,
+    pub(in crate::app) stages: &'a ThreadStages,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(super) fn render_unified_inbox_section(
     group: &mut egui::Ui,
     account: &mut AccountState,
     filter: &SearchFilter,
     repo_paths: &BTreeMap<String, String>,
     custom_review_command: bool,
+    settings: &AppSettings,
+    rules: &RuleSet,
+    kind: SectionKind,
 ) -> Vec<AccountAction> {
     let inflight_done = account.inflight_done.clone();
+    let unsubscribed_thread_ids = account.unsubscribed_thread_ids.clone();
+    let collapsed_repos = account.collapsed_repos.clone();
+    let bulk_progress = account.bulk_progress.clone();
     let inbox = account.inbox.as_ref().expect("checked by caller");
     let pending_review_ids = pending_review_request_ids(inbox);
+    let re_requested_ids = re_requested_review_ids(inbox);
+    let review_waits = review_wait_starts(inbox);
     let active_review_thread_ids = account.active_review_thread_ids();
     let review_output_thread_ids: HashSet<_> = account.review_outputs.keys().cloned().collect();
     let open_review_window_thread_ids: HashSet<_> = account
@@ -49,26 +92,81 @@ pub(super) fn render_unified_inbox_section(
         .collect();
     let render_state = NotificationRenderState {
         inflight_done: &inflight_done,
+        unsubscribed_thread_ids: &unsubscribed_thread_ids,
+        group_by_repo: account.group_by_repo,
+        group_by_day: account.group_by_day,
+        collapsed_repos: &collapsed_repos,
         pending_review_ids: &pending_review_ids,
+        re_requested_ids: &re_requested_ids,
+        review_waits: &review_waits,
         active_review_thread_ids: &active_review_thread_ids,
         review_output_thread_ids: &review_output_thread_ids,
         open_review_window_thread_ids: &open_review_window_thread_ids,
         custom_review_command,
         repo_paths,
+        settings,
+        rules,
+        pull_request_statuses: &account.pull_request_statuses,
+        bulk_progress: bulk_progress.as_ref(),
+        stages: &account.thread_stages,
     };
-    let notifications: Vec<_> = inbox.notifications.iter().collect();
+    let hide_bots = account.profile.hides_bot_notifications(settings);
+    let (notifications, hidden) = visible_notifications(
+        &inbox.notifications,
+        hide_bots,
+        &account.done_threads,
+        settings,
+        rules,
+        Utc::now(),
+    );
+    render_hidden_counts(group, hidden);
+    let notifications: Vec<_> = notifications
+        .into_iter()
+        .filter(|item| {
+            kind.contains(item)
+                && account.chips.matches(item)
+                && account.chips.matches_stage(&account.thread_stages, item)
+        })
+        .collect();
+    let saved_source = notifications.clone();
+    let (notifications, storm_groups) = split_storm_groups(notifications, &account.storms);
+    let (mut actions, ungrouped) = render_storm_groups(group, &storm_groups, filter, &render_state);
 
-    let (actions, cleared_highlight) = render_notification_section(
+    let (section_actions, cleared_highlight) = render_notification_section(
         group,
-        "Inbox",
+        kind.title(),
         notifications,
         "You're all caught up 🎉",
         filter,
         &render_state,
-        account.highlights.contains(&SectionKind::Inbox),
+        account.highlights.contains(&kind),
     );
+    actions.extend(section_actions);
     if cleared_highlight {
-        account.highlights.remove(&SectionKind::Inbox);
+        account.highlights.remove(&kind);
+    }
+    if kind == SectionKind::Inbox {
+        actions.extend(render_saved_sections(
+            group,
+            &settings.saved_searches,
+            &mut account.saved_searches,
+            &saved_source,
+            filter,
+            &render_state,
+        ));
+    }
+    if settings.show_done_section {
+        group.separator();
+        actions.extend(render_done_section(
+            group,
+            &inbox.notifications,
+            &account.done_threads,
+            filter,
+            &render_state,
+        ));
+    }
+    for repo in ungrouped {
+        account.storms.remove(&repo);
     }
     actions
 }
@@ -79,11 +177,17 @@ pub(in crate::app) fn render_bucket_sections(
     filter: &SearchFilter,
     repo_paths: &BTreeMap<String, String>,
     custom_review_command: bool,
+    settings: &AppSettings,
+    rules: &RuleSet,
 ) -> Vec<AccountAction> {
-    let mut actions = Vec::new();
     let inflight_done = account.inflight_done.clone();
+    let unsubscribed_thread_ids = account.unsubscribed_thread_ids.clone();
+    let collapsed_repos = account.collapsed_repos.clone();
+    let bulk_progress = account.bulk_progress.clone();
     let inbox = account.inbox.as_ref().expect("checked by caller");
     let pending_review_ids = pending_review_request_ids(inbox);
+    let re_requested_ids = re_requested_review_ids(inbox);
+    let review_waits = review_wait_starts(inbox);
     let active_review_thread_ids = account.active_review_thread_ids();
     let review_output_thread_ids: HashSet<_> = account.review_outputs.keys().cloned().collect();
     let open_review_window_thread_ids: HashSet<_> = account
@@ -93,74 +197,312 @@ pub(in crate::app) fn render_bucket_sections(
         .collect();
     let render_state = NotificationRenderState {
         inflight_done: &inflight_done,
+        unsubscribed_thread_ids: &unsubscribed_thread_ids,
+        group_by_repo: account.group_by_repo,
+        group_by_day: account.group_by_day,
+        collapsed_repos: &collapsed_repos,
         pending_review_ids: &pending_review_ids,
+        re_requested_ids: &re_requested_ids,
+        review_waits: &review_waits,
         active_review_thread_ids: &active_review_thread_ids,
         review_output_thread_ids: &review_output_thread_ids,
         open_review_window_thread_ids: &open_review_window_thread_ids,
         custom_review_command,
         repo_paths,
+        settings,
+        rules,
+        pull_request_statuses: &account.pull_request_statuses,
+        bulk_progress: bulk_progress.as_ref(),
+        stages: &account.thread_stages,
     };
-
-    let review_requests: Vec<_> = inbox
-        .notifications
-        .iter()
-        .filter(|item| is_review_request(item))
+    let hide_bots = account.profile.hides_bot_notifications(settings);
+    let (visible, hidden) = visible_notifications(
+        &inbox.notifications,
+        hide_bots,
+        &account.done_threads,
+        settings,
+        rules,
+        Utc::now(),
+    );
+    render_hidden_counts(group, hidden);
+    let visible: Vec<_> = visible
+        .into_iter()
+        .filter(|item| {
+            account.chips.matches(item) && account.chips.matches_stage(&account.thread_stages, item)
+        })
         .collect();
+    let saved_source = visible.clone();
+    let (visible, storm_groups) = split_storm_groups(visible, &account.storms);
+    let (mut actions, ungrouped) = render_storm_groups(group, &storm_groups, filter, &render_state);
 
-    let (section_actions, cleared_highlight) = render_notification_section(
+    for (index, (kind, empty_label)) in BUCKET_SECTIONS.into_iter().enumerate() {
+        if index > 0 {
+            group.separator();
+        }
+        let items: Vec<_> = visible
+            .iter()
+            .copied()
+            .filter(|item| in_bucket(kind, item))
+            .collect();
+        let (section_actions, cleared_highlight) = render_notification_section(
+            group,
+            kind.title(),
+            items,
+            empty_label,
+            filter,
+            &render_state,
+            account.highlights.contains(&kind),
+        );
+        actions.extend(section_actions);
+        if cleared_highlight {
+            account.highlights.remove(&kind);
+        }
+    }
+    for kind in [SectionKind::Assigned, SectionKind::AuthoredPullRequests] {
+        group.separator();
+        if render_work_section(
+            group,
+            kind,
+            inbox,
+            &account.pull_request_statuses,
+            filter,
+            settings,
+            account.highlights.contains(&kind),
+        ) {
+            account.highlights.remove(&kind);
+        }
+    }
+    actions.extend(render_saved_sections(
         group,
-        "Review requests",
-        review_requests,
-        "No pending review requests.",
+        &settings.saved_searches,
+        &mut account.saved_searches,
+        &saved_source,
         filter,
         &render_state,
-        account.highlights.contains(&SectionKind::ReviewRequests),
-    );
-    actions.extend(section_actions);
-    if cleared_highlight {
-        account.highlights.remove(&SectionKind::ReviewRequests);
+    ));
+    if settings.show_done_section {
+        group.separator();
+        actions.extend(render_done_section(
+            group,
+            &inbox.notifications,
+            &account.done_threads,
+            filter,
+            &render_state,
+        ));
+    }
+    for repo in ungrouped {
+        account.storms.remove(&repo);
     }
-    group.separator();
 
-    let mentions: Vec<_> = inbox
-        .notifications
-        .iter()
-        .filter(|item| is_mention(item))
-        .collect();
-    let (section_actions, cleared_highlight) = render_notification_section(
-        group,
-        "Mentions",
-        mentions,
-        "No recent mentions.",
-        filter,
-        &render_state,
-        account.highlights.contains(&SectionKind::Mentions),
-    );
-    actions.extend(section_actions);
-    if cleared_highlight {
-        account.highlights.remove(&SectionKind::Mentions);
+    actions
+}
+
+/// The triage view's notification sections, in display order.
+const BUCKET_SECTIONS: [(SectionKind, &str); 4] = [
+    (SectionKind::ReviewRequests, "No pending review requests."),
+    (SectionKind::Mentions, "No recent mentions."),
+    (SectionKind::Discussions, "No discussion activity."),
+    (SectionKind::Notifications, "You're all caught up 🎉"),
+];
+
+/// Discussions get a section of their own, so the catch-all leaves them out.
+fn in_bucket(kind: SectionKind, item: &NotificationItem) -> bool {
+    match kind {
+        SectionKind::Notifications => kind.contains(item) && !item.is_discussion(),
+        _ => kind.contains(item),
     }
-    group.separator();
+}
 
-    let other: Vec<_> = inbox
-        .notifications
+/// The user's saved searches, after the built-in sections. Filter sections
+/// draw from `visible`; GitHub searches show the hits of the last refresh.
+fn render_saved_sections(
+    group: &mut egui::Ui,
+    searches: &[SavedSearch],
+    state: &mut SavedSearchState,
+    visible: &[&NotificationItem],
+    filter: &SearchFilter,
+    render_state: &NotificationRenderState<'_>,
+) -> Vec<AccountAction> {
+    let mut actions = Vec::new();
+    for search in searches {
+        if search.name.trim().is_empty() {
+            continue;
+        }
+        group.separator();
+        let highlight = state.highlights.contains(&search.name);
+        let cleared_highlight = match search.kind {
+            SavedSearchKind::Filter => {
+                let Ok(query) = FilterQuery::parse(&search.query) else {
+                    group.weak(format!("{}: invalid filter", search.name));
+                    continue;
+                };
+                let items: Vec<_> = visible
+                    .iter()
+                    .copied()
+                    .filter(|item| query.matches(item))
+                    .collect();
+                let (section_actions, cleared) = render_notification_section(
+                    group,
+                    &search.name,
+                    items,
+                    "Nothing matches this search.",
+                    filter,
+                    render_state,
+                    highlight,
+                );
+                actions.extend(section_actions);
+                cleared
+            }
+            SavedSearchKind::GitHubSearch => render_saved_search_list(
+                group,
+                &search.name,
+                state.hits.get(&search.name).map_or(&[], Vec::as_slice),
+                state.errors.get(&search.name).map(String::as_str),
+                filter,
+                render_state,
+                highlight,
+            ),
+        };
+        if cleared_highlight {
+            state.highlights.remove(&search.name);
+        }
+    }
+    actions
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(super) struct HiddenCounts {
+    muted: usize,
+    bots: usize,
+    snoozed: usize,
+    done: usize,
+}
+
+pub(super) fn visible_notifications<'a>(
+    notifications: &'a [NotificationItem],
+    hide_bots: bool,
+    done: &DoneThreads,
+    settings: &AppSettings,
+    rules: &RuleSet,
+    now: DateTime<Utc>,
+) -> (Vec<&'a NotificationItem>, HiddenCounts) {
+    let mut hidden = HiddenCounts::default();
+    let visible = notifications
         .iter()
-        .filter(|item| is_other_notification(item))
+        .filter(|item| {
+            if rules.mutes(item) {
+                hidden.muted += 1;
+                false
+            } else if done.hides(item) {
+                hidden.done += 1;
+                false
+            } else if hide_bots && item.is_from_bot() {
+                hidden.bots += 1;
+                false
+            } else if is_auto_snoozed(item, settings, now) {
+                hidden.snoozed += 1;
+                false
+            } else {
+                true
+            }
+        })
         .collect();
-    let (section_actions, cleared_highlight) = render_notification_section(
-        group,
-        "Notifications",
-        other,
-        "You're all caught up 🎉",
-        filter,
-        &render_state,
-        account.highlights.contains(&SectionKind::Notifications),
-    );
-    actions.extend(section_actions);
-    if cleared_highlight {
-        account.highlights.remove(&SectionKind::Notifications);
+    (visible, hidden)
+}
+
+type StormGroup<'a> = (String, Vec<&'a NotificationItem>);
+
+fn split_storm_groups<'a>(
+    notifications: Vec<&'a NotificationItem>,
+    storms: &BTreeMap<String, Vec<String>>,
+) -> (Vec<&'a NotificationItem>, Vec<StormGroup<'a>>) {
+    let mut groups = BTreeMap::<String, Vec<&NotificationItem>>::new();
+    let mut rest = Vec::new();
+    for item in notifications {
+        let in_storm = storms
+            .get(&item.repo)
+            .is_some_and(|thread_ids| thread_ids.contains(&item.thread_id));
+        if in_storm {
+            groups.entry(item.repo.clone()).or_default().push(item);
+        } else {
+            rest.push(item);
+        }
     }
+    (rest, groups.into_iter().collect())
+}
 
+/// Returns the actions raised inside the groups and the repos the user asked
+/// to ungroup.
+fn render_storm_groups(
+    ui: &mut egui::Ui,
+    groups: &[StormGroup<'_>],
+    filter: &SearchFilter,
+    render_state: &NotificationRenderState<'_>,
+) -> (Vec<AccountAction>, Vec<String>) {
+    let mut actions = Vec::new();
+    let mut ungrouped = Vec::new();
+    for (repo, items) in groups {
+        let heading = RichText::new(storm_summary_text(repo, items.len()))
+            .strong()
+            .color(ui.visuals().warn_fg_color);
+        egui::CollapsingHeader::new(heading)
+            .id_salt(("notification-storm", repo))
+            .default_open(false)
+            .show(ui, |section| {
+                if section
+                    .small_button("Ungroup")
+                    .on_hover_text("Show these threads in the regular sections again.")
+                    .clicked()
+                {
+                    ungrouped.push(repo.clone());
+                }
+                actions.extend(draw_notifications(section, items, filter, render_state));
+            });
+    }
+    if !groups.is_empty() {
+        ui.separator();
+    }
+    (actions, ungrouped)
+}
+
+fn render_hidden_counts(ui: &mut egui::Ui, hidden: HiddenCounts) {
+    if hidden.muted > 0 {
+        ui.weak(format!("{} notification(s) muted by rules", hidden.muted));
+    }
+    if hidden.bots > 0 {
+        ui.weak(format!("{} bot notification(s) hidden", hidden.bots));
+    }
+    if hidden.snoozed > 0 {
+        ui.weak(format!("{} notification(s) auto-snoozed", hidden.snoozed));
+    }
+    if hidden.done > 0 {
+        ui.weak(format!("{} thread(s) marked done", hidden.done));
+    }
+}
+
+/// Threads hidden by a done tombstone, collapsed by default.
+fn render_done_section(
+    ui: &mut egui::Ui,
+    notifications: &[NotificationItem],
+    done: &DoneThreads,
+    filter: &SearchFilter,
+    render_state: &NotificationRenderState<'_>,
+) -> Vec<AccountAction> {
+    let items: Vec<_> = notifications
+        .iter()
+        .filter(|item| done.hides(item))
+        .collect();
+    let mut actions = Vec::new();
+    egui::CollapsingHeader::new(RichText::new(format!("Done ({})", items.len())).strong())
+        .id_salt("notification-section-done")
+        .default_open(false)
+        .show(ui, |section| {
+            if items.is_empty() {
+                section.weak("Nothing marked done yet.");
+            } else {
+                actions = draw_notifications(section, &items, filter, render_state);
+            }
+        });
     actions
 }
 
@@ -198,42 +540,409 @@ fn render_notification_section(
 
     let mut actions = Vec::new();
     let response = header.show(group, |section| {
-        actions.extend(draw_notifications(section, &subset, filter, render_state));
+        actions.extend(render_bulk_actions(
+            section,
+            title,
+            &subset,
+            filter,
+            render_state,
+        ));
+        if render_state.group_by_repo {
+            actions.extend(draw_repository_groups(
+                section,
+                title,
+                &subset,
+                filter,
+                render_state,
+            ));
+        } else {
+            actions.extend(draw_notifications(section, &subset, filter, render_state));
+        }
     });
     (actions, response.body_returned.is_some() && highlight)
 }
 
+/// Matching items bucketed by repository: higher tiers first, then the repo
+/// with the most recent update.
+fn group_by_repository<'a>(
+    items: &[&'a NotificationItem],
+    filter: &SearchFilter,
+    settings: &AppSettings,
+) -> Vec<(&'a str, Vec<&'a NotificationItem>)> {
+    let mut groups = BTreeMap::<&str, Vec<&NotificationItem>>::new();
+    for item in items
+        .iter()
+        .copied()
+        .filter(|item| notification_matches_search(item, filter))
+    {
+        groups.entry(item.repo.as_str()).or_default().push(item);
+    }
+    let mut groups: Vec<_> = groups.into_iter().collect();
+    groups.sort_by_key(|(repo, items)| {
+        let latest = items.iter().map(|item| item.updated_at).max();
+        (settings.repo_tier(repo), std::cmp::Reverse(latest))
+    });
+    groups
+}
+
+/// One collapsible group per repository; collapsing is remembered per
+/// account through [`AccountAction::ToggleRepoGroup`].
+fn draw_repository_groups(
+    ui: &mut egui::Ui,
+    title: &str,
+    items: &[&NotificationItem],
+    filter: &SearchFilter,
+    render_state: &NotificationRenderState<'_>,
+) -> Vec<AccountAction> {
+    let groups = group_by_repository(items, filter, render_state.settings);
+    if groups.is_empty() {
+        ui.weak("No matches for current search.");
+        return Vec::new();
+    }
+
+    let mut actions = Vec::new();
+    for (repo, group) in groups {
+        let (unseen_count, _) = summarize_counts(&group);
+        let heading = format!("{repo} ({} unseen, {} total)", unseen_count, group.len());
+        let response = egui::CollapsingHeader::new(heading)
+            .id_salt(("notification-repo-group", title, repo))
+            .open(Some(!render_state.collapsed_repos.contains(repo)))
+            .show(ui, |body| {
+                actions.extend(draw_notifications(body, &group, filter, render_state));
+            });
+        if response.header_response.clicked() {
+            actions.push(AccountAction::ToggleRepoGroup(repo.to_owned()));
+        }
+    }
+    actions
+}
+
+/// The section's "Mark all read" / "Done all" buttons, or the progress of
+/// the bulk action running for it.
+fn render_bulk_actions(
+    ui: &mut egui::Ui,
+    title: &str,
+    subset: &[&NotificationItem],
+    filter: &SearchFilter,
+    render_state: &NotificationRenderState<'_>,
+) -> Option<AccountAction> {
+    if let Some(progress) = render_state.bulk_progress {
+        if progress.section == title {
+            ui.horizontal(|row| {
+                row.spinner();
+                row.label(format!(
+                    "{} {}/{}",
+                    if progress.done {
+                        "Marking done"
+                    } else {
+                        "Marking read"
+                    },
+                    progress.completed,
+                    progress.total
+                ));
+            });
+        }
+        return None;
+    }
+
+    let visible: Vec<_> = subset
+        .iter()
+        .filter(|item| {
+            notification_matches_search(item, filter)
+                && !render_state.inflight_done.contains(&item.thread_id)
+        })
+        .collect();
+    let unread: Vec<_> = visible
+        .iter()
+        .filter(|item| item.unread)
+        .map(|item| item.thread_id.clone())
+        .collect();
+    let mut action = None;
+    ui.horizontal(|row| {
+        if row
+            .add_enabled(
+                !unread.is_empty(),
+                egui::Button::new("Mark all read").small(),
+            )
+            .clicked()
+        {
+            action = Some(BulkAction {
+                section: title.to_owned(),
+                thread_ids: unread.clone(),
+                done: false,
+            });
+        }
+        if row
+            .add_enabled(!visible.is_empty(), egui::Button::new("Done all").small())
+            .clicked()
+        {
+            action = Some(BulkAction {
+                section: title.to_owned(),
+                thread_ids: visible.iter().map(|item| item.thread_id.clone()).collect(),
+                done: true,
+            });
+        }
+    });
+    action.map(AccountAction::Bulk)
+}
+
 pub(in crate::app) fn notification_state(
     item: &NotificationItem,
     render_state: &NotificationRenderState<'_>,
 ) -> NotificationVisualState {
     let mut visual = base_notification_state(item);
     visual.pending_review = render_state.pending_review_ids.contains(&item.thread_id);
+    visual.re_requested = render_state.re_requested_ids.contains(&item.thread_id);
+    visual.highlighted = render_state.rules.highlights(item);
     visual
 }
 
 fn notification_text(
-    ui: &egui::Ui,
     text: impl Into<String>,
     visual: NotificationVisualState,
+    colors: StatusColors,
 ) -> RichText {
     let mut content = RichText::new(text.into());
     if visual.needs_revisit {
-        content = content.color(ui.visuals().warn_fg_color);
+        content = content.color(colors.updated);
     } else if visual.seen {
-        content = content.color(ui.visuals().weak_text_color());
+        content = content.color(colors.seen);
     }
     content
 }
 
-fn pending_review_badge(ui: &mut egui::Ui) {
+/// The title text, prefixed with a state shape when status icons are on so
+/// unread and updated threads do not rely on color alone.
+fn notification_title_text(
+    title: &str,
+    visual: NotificationVisualState,
+    colors: StatusColors,
+    settings: &AppSettings,
+) -> RichText {
+    let icon = if !settings.status_icons {
+        None
+    } else if visual.needs_revisit {
+        Some("↻")
+    } else if !visual.seen {
+        Some("●")
+    } else {
+        None
+    };
+    let title = if visual.highlighted {
+        format!("★ {title}")
+    } else {
+        title.to_owned()
+    };
+    let text = match icon {
+        Some(icon) => notification_text(format!("{icon} {title}"), visual, colors),
+        None => notification_text(title, visual, colors),
+    };
+    if visual.highlighted {
+        text.strong()
+    } else {
+        text
+    }
+}
+
+fn repo_tier_stripe(ui: &mut egui::Ui, tier: RepoTier) {
+    let color = match tier {
+        RepoTier::Critical => CRITICAL_TIER_STRIPE_COLOR,
+        RepoTier::Normal => return,
+        RepoTier::Low => LOW_TIER_STRIPE_COLOR,
+    };
+    let height = ui.text_style_height(&egui::TextStyle::Body);
+    let (rect, response) = ui.allocate_exact_size(egui::vec2(4.0, height), egui::Sense::hover());
+    ui.painter().rect_filled(rect, 1.0, color);
+    response.on_hover_text(format!("{} priority repository", tier.label()));
+}
+
+fn pending_review_badge(ui: &mut egui::Ui, colors: StatusColors) {
     ui.small(
         RichText::new("Pending review")
             .strong()
-            .color(PENDING_REVIEW_LABEL_COLOR),
+            .color(colors.pending_review),
     );
 }
 
+/// The thread's personal stage as a small colored picker.
+fn stage_picker(
+    ui: &mut egui::Ui,
+    item: &NotificationItem,
+    render_state: &NotificationRenderState<'_>,
+) -> Option<AccountAction> {
+    let current = render_state.stages.stage(&item.thread_id);
+    let color = stage_color(current, ui.visuals());
+    let mut picked = current;
+    egui::ComboBox::from_id_salt(("thread-stage", &item.thread_id))
+        .selected_text(RichText::new(current.label()).small().strong().color(color))
+        .width(110.0)
+        .show_ui(ui, |menu| {
+            for stage in ThreadStage::ALL {
+                menu.selectable_value(&mut picked, stage, stage.label());
+            }
+        });
+    (picked != current).then(|| AccountAction::SetStage {
+        thread_id: item.thread_id.clone(),
+        stage: picked,
+    })
+}
+
+/// How long a review request has waited, in the warning color once it is
+/// overdue and the error color once the wait has doubled.
+pub(super) fn review_wait_badge(ui: &mut egui::Ui, since: DateTime<Utc>, threshold_hours: u32) {
+    let waited = Utc::now() - since;
+    let color = match overdue_level(waited, threshold_hours) {
+        0 => ui.visuals().weak_text_color(),
+        1 => ui.visuals().warn_fg_color,
+        _ => ui.visuals().error_fg_color,
+    };
+    ui.small(RichText::new(format!("Waiting {}", format_wait(waited))).color(color))
+        .on_hover_text(format!(
+            "Review requested {}",
+            format_local_timestamp(since, FULL_TIMESTAMP_FORMAT)
+        ));
+}
+
+fn re_requested_badge(ui: &mut egui::Ui) {
+    let color = ui.visuals().warn_fg_color;
+    ui.small(RichText::new("Re-requested").strong().color(color))
+        .on_hover_text("You reviewed this before and were asked to review it again.");
+}
+
+fn pull_request_status_badges(
+    ui: &mut egui::Ui,
+    item: &NotificationItem,
+    render_state: &NotificationRenderState<'_>,
+    colors: StatusColors,
+) {
+    if let Some(status) = item
+        .pull_request_key()
+        .and_then(|key| render_state.pull_request_statuses.status(&key))
+    {
+        pull_request_badges(ui, status, colors);
+    }
+}
+
+/// Names the provider of threads that did not come from GitHub, with its
+/// extra fields on hover.
+/// Drawn at the repository column's text height.
+const AVATAR_SIZE: f32 = 16.0;
+
+/// `login`'s avatar from the host `page_url` lives on. Skipped in low-data
+/// mode so scrolling never starts downloads there.
+pub(super) fn avatar(ui: &mut egui::Ui, page_url: &str, login: &str, settings: &AppSettings) {
+    if settings.low_data_mode {
+        return;
+    }
+    if let Some(uri) = avatar_uri(page_url, login) {
+        ui.add(
+            egui::Image::new(uri)
+                .fit_to_exact_size(egui::vec2(AVATAR_SIZE, AVATAR_SIZE))
+                .corner_radius(3.0),
+        )
+        .on_hover_text(login);
+    }
+}
+
+/// The repository owner's avatar; only GitHub serves them by login.
+pub(super) fn owner_avatar(ui: &mut egui::Ui, item: &NotificationItem, settings: &AppSettings) {
+    if item.provider != Forge::GitHub {
+        return;
+    }
+    if let (Some(url), Some((owner, _))) = (&item.url, item.repo.split_once('/')) {
+        avatar(ui, url, owner, settings);
+    }
+}
+
+pub(super) fn provider_tag(ui: &mut egui::Ui, item: &NotificationItem) {
+    if item.provider == Forge::GitHub {
+        return;
+    }
+    let tag = ui.small(RichText::new(item.provider.label()).weak());
+    if !item.metadata.is_empty() {
+        let details: Vec<_> = item
+            .metadata
+            .iter()
+            .map(|(key, value)| format!("{key}: {value}"))
+            .collect();
+        tag.on_hover_text(details.join("\n"));
+    }
+}
+
+pub(super) fn pull_request_badges(
+    ui: &mut egui::Ui,
+    status: &PullRequestStatus,
+    colors: StatusColors,
+) {
+    let PullRequestStatus {
+        draft,
+        mergeable,
+        checks,
+        review_decision,
+    } = status;
+    let error_color = ui.visuals().error_fg_color;
+    if let Some(checks) = checks {
+        let color = match checks {
+            CheckState::Passing => colors.approved,
+            CheckState::Failing => error_color,
+            CheckState::Pending => colors.updated,
+        };
+        ui.small(RichText::new(checks.symbol()).strong().color(color))
+            .on_hover_text(checks.label());
+    }
+    if *draft {
+        ui.small(RichText::new("Draft").weak());
+    }
+    if *mergeable == Some(false) {
+        ui.small(RichText::new("Conflicts").color(error_color));
+    }
+    if let Some(decision) = review_decision {
+        let text = RichText::new(decision.label());
+        ui.small(match decision {
+            ReviewDecision::Approved => text.color(colors.approved),
+            ReviewDecision::ChangesRequested => text.color(error_color),
+            ReviewDecision::ReviewRequired => text.weak(),
+        });
+    }
+}
+
+/// "Open" and "Copy link" for the thread's page. Copying needs no account
+/// state, so only opening turns into an action.
+fn link_buttons(ui: &mut egui::Ui, item: &NotificationItem) -> Option<AccountAction> {
+    let url = item.url.as_ref()?;
+    let mut action = None;
+    if ui
+        .button("Open")
+        .on_hover_text("Open in the browser and mark seen.")
+        .clicked()
+    {
+        action = Some(AccountAction::Open {
+            thread_id: item.thread_id.clone(),
+            url: url.clone(),
+        });
+    }
+    if ui.button("Copy link").on_hover_text(url).clicked() {
+        ui.ctx().copy_text(url.clone());
+    }
+    action
+}
+
+fn unsubscribe_button(
+    ui: &mut egui::Ui,
+    item: &NotificationItem,
+    busy: bool,
+    render_state: &NotificationRenderState<'_>,
+) -> egui::Response {
+    if render_state
+        .unsubscribed_thread_ids
+        .contains(&item.thread_id)
+    {
+        return ui.add_enabled(false, egui::Button::new("Unsubscribed"));
+    }
+    ui.add_enabled(!busy, egui::Button::new("Unsubscribe"))
+        .on_hover_text("Stop notifications for this thread until you comment or are mentioned.")
+}
+
 fn reviewing_button(ui: &mut egui::Ui) -> egui::Response {
     let response = ui.add(egui::Button::new("    Reviewing"));
     let spinner_size = 10.0;
@@ -253,60 +962,88 @@ fn draw_notifications(
     filter: &SearchFilter,
     render_state: &NotificationRenderState<'_>,
 ) -> Vec<AccountAction> {
-    let rows: Vec<_> = items
+    let mut rows: Vec<_> = items
         .iter()
         .copied()
         .filter(|item| notification_matches_search(item, filter))
         .collect();
+    // Re-requested reviews first, then by tier. Stable, so each group keeps
+    // the feed's recency order; each tier is looked up once rather than per
+    // comparison.
+    rows.sort_by_cached_key(|item| {
+        (
+            !render_state.re_requested_ids.contains(&item.thread_id),
+            render_state.settings.repo_tier(&item.repo),
+        )
+    });
     if rows.is_empty() {
         ui.weak("No matches for current search.");
         return Vec::new();
     }
 
+    if render_state.group_by_day {
+        return draw_day_groups(ui, &rows, render_state);
+    }
+
+    draw_notification_rows(ui, &rows, render_state)
+}
+
+fn draw_notification_rows(
+    ui: &mut egui::Ui,
+    rows: &[&NotificationItem],
+    render_state: &NotificationRenderState<'_>,
+) -> Vec<AccountAction> {
     if uses_compact_notifications(ui.available_width()) {
-        return draw_notification_cards(ui, &rows, render_state);
+        return draw_notification_cards(ui, rows, render_state);
     }
 
-    draw_notification_table(ui, &rows, render_state)
+    draw_notification_table(ui, rows, render_state)
 }
 
-fn notification_matches_search(item: &NotificationItem, filter: &SearchFilter) -> bool {
-    let display_title = item.display_title();
-    let merge_direction = item.merge_direction_text();
-    let number_alias = item.thread_number().map(|number| number.to_string());
-    let hash_alias = item.thread_number().map(|number| format!("#{number}"));
-    let repo_number_alias = item
-        .thread_number()
-        .map(|number| format!("{}#{number}", item.repo));
+/// Rows keep their order within each day; empty days get no sub-header.
+fn group_by_day<'a>(
+    rows: &[&'a NotificationItem],
+    today: NaiveDate,
+) -> Vec<(DayBucket, Vec<&'a NotificationItem>)> {
+    DayBucket::ALL
+        .into_iter()
+        .map(|bucket| {
+            let items: Vec<_> = rows
+                .iter()
+                .copied()
+                .filter(|item| DayBucket::of(item.updated_at, today) == bucket)
+                .collect();
+            (bucket, items)
+        })
+        .filter(|(_, items)| !items.is_empty())
+        .collect()
+}
 
-    let mut fields = vec![
-        item.repo.as_str(),
-        display_title.as_str(),
-        item.reason.as_str(),
-    ];
-    if let Some(head_ref) = item.head_ref.as_deref() {
-        fields.push(head_ref);
-    }
-    if let Some(base_ref) = item.base_ref.as_deref() {
-        fields.push(base_ref);
-    }
-    if let Some(direction) = merge_direction.as_deref() {
-        fields.push(direction);
-    }
-    if let Some(url) = item.url.as_deref() {
-        fields.push(url);
-    }
-    if let Some(alias) = number_alias.as_deref() {
-        fields.push(alias);
-    }
-    if let Some(alias) = hash_alias.as_deref() {
-        fields.push(alias);
-    }
-    if let Some(alias) = repo_number_alias.as_deref() {
-        fields.push(alias);
+fn draw_day_groups(
+    ui: &mut egui::Ui,
+    rows: &[&NotificationItem],
+    render_state: &NotificationRenderState<'_>,
+) -> Vec<AccountAction> {
+    let today = Local::now().date_naive();
+    let mut actions = Vec::new();
+    for (bucket, items) in group_by_day(rows, today) {
+        ui.add_space(4.0);
+        ui.label(RichText::new(bucket.label()).strong());
+        ui.separator();
+        ui.push_id(("notification-day", bucket), |ui| {
+            actions.extend(draw_notification_rows(ui, &items, render_state));
+        });
     }
+    actions
+}
 
-    filter.matches_any(&fields)
+/// Matches against the item's cached search fields, so filtering does not
+/// allocate once the fields are built.
+pub(super) fn notification_matches_search(item: &NotificationItem, filter: &SearchFilter) -> bool {
+    filter.matches_prepared(
+        item.search_cache
+            .get_or_init(|| search_fields(item.search_terms())),
+    )
 }
 
 fn draw_notification_cards(
@@ -315,29 +1052,49 @@ fn draw_notification_cards(
     render_state: &NotificationRenderState<'_>,
 ) -> Vec<AccountAction> {
     let mut actions = Vec::new();
+    let colors = status_colors(render_state.settings.status_palette, ui.visuals())
+        .with_updated(render_state.settings.updated_color);
 
     for item in rows {
         let visual = notification_state(item, render_state);
-        ui.group(|card| {
+        let card = ui.group(|card| {
             card.vertical(|column| {
                 column.horizontal_wrapped(|row| {
-                    row.label(notification_text(row, &item.repo, visual));
+                    repo_tier_stripe(row, render_state.settings.repo_tier(&item.repo));
+                    owner_avatar(row, item, render_state.settings);
+                    row.label(notification_text(&item.repo, visual, colors));
+                    provider_tag(row, item);
                     row.separator();
                     row.label(notification_text(
-                        row,
-                        format_local_timestamp(item.updated_at, "%Y-%m-%d %H:%M"),
+                        format_timestamp(
+                            item.updated_at,
+                            render_state.settings.timestamp_style,
+                            "%Y-%m-%d %H:%M",
+                        ),
                         visual,
+                        colors,
+                    ))
+                    .on_hover_text(format_local_timestamp(
+                        item.updated_at,
+                        FULL_TIMESTAMP_FORMAT,
                     ));
+                    if let Some(actor) = &item.latest_actor {
+                        row.label(notification_text(format!("by @{actor}"), visual, colors));
+                    }
                     if visual.needs_revisit {
-                        row.small(
-                            RichText::new("Updated")
-                                .strong()
-                                .color(row.visuals().warn_fg_color),
-                        );
+                        row.small(RichText::new("Updated").strong().color(colors.updated));
                     }
                     if visual.pending_review {
-                        pending_review_badge(row);
+                        pending_review_badge(row, colors);
+                    }
+                    if let Some(since) = render_state.review_waits.get(&item.thread_id) {
+                        review_wait_badge(row, *since, render_state.settings.review_wait_hours);
+                    }
+                    if visual.re_requested {
+                        re_requested_badge(row);
                     }
+                    pull_request_status_badges(row, item, render_state, colors);
+                    actions.extend(stage_picker(row, item, render_state));
                 });
 
                 let display_title = item.display_title();
@@ -345,29 +1102,41 @@ fn draw_notification_cards(
                     let resp = render_notification_title_link(
                         column,
                         item,
-                        notification_text(column, display_title.as_str(), visual),
+                        notification_title_text(
+                            display_title.as_str(),
+                            visual,
+                            colors,
+                            render_state.settings,
+                        ),
                         url,
+                        colors,
                     );
-                    if resp.clicked() {
+                    if link_click_marks_seen(column, &resp) {
                         actions.push(AccountAction::Seen(item.thread_id.clone()));
                     }
                 } else {
                     let resp = render_notification_title_label(
                         column,
                         item,
-                        notification_text(column, display_title.as_str(), visual),
+                        notification_title_text(
+                            display_title.as_str(),
+                            visual,
+                            colors,
+                            render_state.settings,
+                        ),
+                        colors,
                     );
                     if resp.clicked() {
                         actions.push(AccountAction::Seen(item.thread_id.clone()));
                     }
                 }
 
-                render_notification_branch_direction(column, item, visual);
+                render_notification_branch_direction(column, item, visual, colors);
 
                 column.small(notification_text(
-                    column,
                     format!("Reason: {}", &item.reason),
                     visual,
+                    colors,
                 ));
 
                 column.horizontal_wrapped(|row| {
@@ -377,12 +1146,26 @@ fn draw_notification_cards(
                         .contains(&item.thread_id);
                     let already_read = !item.unread && !visual.needs_revisit;
 
+                    actions.extend(link_buttons(row, item));
                     if row
                         .add_enabled(!busy && !already_read, egui::Button::new("Mark read"))
                         .clicked()
                     {
                         actions.push(AccountAction::Read(item.thread_id.clone()));
                     }
+                    if row
+                        .add_enabled(!busy, egui::Button::new("Done"))
+                        .on_hover_text("Archive on GitHub and hide until there is new activity.")
+                        .clicked()
+                    {
+                        actions.push(AccountAction::Done(item.thread_id.clone()));
+                    }
+                    if unsubscribe_button(row, item, busy, render_state).clicked() {
+                        actions.push(AccountAction::Unsubscribe(item.thread_id.clone()));
+                    }
+                    if row.button("Remind").clicked() {
+                        actions.push(AccountAction::Remind(item.thread_id.clone()));
+                    }
 
                     if let (Some(pr_url), Some(pr_number)) =
                         (item.pull_request_url(), item.pull_request_number())
@@ -443,6 +1226,16 @@ fn draw_notification_cards(
                 });
             });
         });
+        let response = card.response.interact(egui::Sense::click());
+        if let Some(action) = preview_click_action(&response, item, render_state) {
+            actions.push(action);
+        }
+        if response.double_clicked()
+            && let Some(action) = double_click_account_action(item, render_state)
+        {
+            actions.push(action);
+        }
+        actions.extend(swipe_action(&card.response, item, render_state.settings));
         ui.add_space(8.0);
     }
 
@@ -455,14 +1248,18 @@ fn draw_notification_table(
     render_state: &NotificationRenderState<'_>,
 ) -> Vec<AccountAction> {
     let mut actions = Vec::new();
+    let colors = status_colors(render_state.settings.status_palette, ui.visuals())
+        .with_updated(render_state.settings.updated_color);
 
     egui::ScrollArea::horizontal()
         .auto_shrink([false, false])
         .show(ui, |ui| {
             TableBuilder::new(ui)
                 .striped(true)
+                .sense(egui::Sense::click())
                 .column(Column::initial(120.0).resizable(true))
                 .column(Column::remainder().at_least(140.0))
+                .column(Column::initial(110.0).resizable(true))
                 .column(Column::initial(130.0).resizable(true))
                 .column(Column::initial(100.0))
                 .header(20.0, |mut header| {
@@ -472,6 +1269,10 @@ fn draw_notification_table(
                     header.col(|ui| {
                         ui.strong("Subject");
                     });
+                    header.col(|ui| {
+                        ui.strong("Latest actor")
+                            .on_hover_text("Who made the latest comment, review or update");
+                    });
                     header.col(|ui| {
                         ui.strong("Updated");
                     });
@@ -479,143 +1280,240 @@ fn draw_notification_table(
                         ui.strong("Actions");
                     });
                 })
-                .body(|mut body| {
-                    for item in rows {
+                .body(|body| {
+                    // Only rows scrolled into view are laid out.
+                    body.rows(24.0, rows.len(), |mut row| {
+                        let item = rows[row.index()];
                         let visual = notification_state(item, render_state);
-                        body.row(24.0, |mut row| {
-                            row.col(|ui| {
-                                ui.label(notification_text(ui, &item.repo, visual));
+                        row.col(|ui| {
+                            ui.horizontal(|row_ui| {
+                                repo_tier_stripe(
+                                    row_ui,
+                                    render_state.settings.repo_tier(&item.repo),
+                                );
+                                owner_avatar(row_ui, item, render_state.settings);
+                                row_ui.label(notification_text(&item.repo, visual, colors));
+                                provider_tag(row_ui, item);
                             });
-                            row.col(|ui| {
-                                ui.horizontal(|row_ui| {
-                                    let display_title = item.display_title();
-                                    let subject =
-                                        notification_text(row_ui, display_title.as_str(), visual);
-                                    if let Some(url) = &item.url {
-                                        let resp = render_notification_title_link(row_ui, item, subject, url);
-                                        if resp.clicked() {
-                                            actions
-                                                .push(AccountAction::Seen(item.thread_id.clone()));
-                                            }
-                                    } else {
-                                        let resp = render_notification_title_label(row_ui, item, subject);
-                                        if resp.clicked() {
-                                            actions
-                                                .push(AccountAction::Seen(item.thread_id.clone()));
+                        });
+                        row.col(|ui| {
+                            ui.horizontal(|row_ui| {
+                                let display_title = item.display_title();
+                                let subject =
+                                    notification_title_text(display_title.as_str(), visual, colors, render_state.settings);
+                                if let Some(url) = &item.url {
+                                    let resp = render_notification_title_link(row_ui, item, subject, url, colors);
+                                    if link_click_marks_seen(row_ui, &resp) {
+                                        actions
+                                            .push(AccountAction::Seen(item.thread_id.clone()));
                                         }
+                                } else {
+                                    let resp = render_notification_title_label(row_ui, item, subject, colors);
+                                    if resp.clicked() {
+                                        actions
+                                            .push(AccountAction::Seen(item.thread_id.clone()));
                                     }
-                                    if visual.needs_revisit {
-                                        row_ui.small(
-                                            RichText::new("Updated")
-                                                .strong()
-                                                .color(row_ui.visuals().warn_fg_color),
-                                        );
-                                    }
-                                    if visual.pending_review {
-                                        pending_review_badge(row_ui);
-                                    }
-                                });
-                                render_notification_branch_direction(ui, item, visual);
-                                ui.small(notification_text(
-                                    ui,
-                                    format!("Reason: {}", &item.reason),
-                                    visual,
-                                ));
-                            });
-                            row.col(|ui| {
-                                ui.label(notification_text(
-                                    ui,
-                                    format_local_timestamp(item.updated_at, "%Y-%m-%d %H:%M"),
-                                    visual,
-                                ));
+                                }
+                                if visual.needs_revisit {
+                                    row_ui.small(
+                                        RichText::new("Updated")
+                                            .strong()
+                                            .color(colors.updated),
+                                    );
+                                }
+                                if visual.pending_review {
+                                    pending_review_badge(row_ui, colors);
+                                }
+                                if visual.re_requested {
+                                    re_requested_badge(row_ui);
+                                }
+                                pull_request_status_badges(row_ui, item, render_state, colors);
+                                actions.extend(stage_picker(row_ui, item, render_state));
                             });
-                            row.col(|ui| {
-                                let busy = render_state.inflight_done.contains(&item.thread_id);
-                                let review_active = render_state
-                                    .active_review_thread_ids
-                                    .contains(&item.thread_id);
-                                let already_read = !item.unread && !visual.needs_revisit;
-
-                                ui.horizontal_wrapped(|row_ui| {
-                                    if row_ui
-                                        .add_enabled(
-                                            !busy && !already_read,
-                                            egui::Button::new("Mark read"),
-                                        )
-                                        .clicked()
-                                    {
-                                        actions.push(AccountAction::Read(item.thread_id.clone()));
-                                    }
+                            render_notification_branch_direction(ui, item, visual, colors);
+                            ui.small(notification_text(
+                                format!("Reason: {}", &item.reason),
+                                visual,
+                                colors,
+                            ));
+                        });
+                        row.col(|ui| match &item.latest_actor {
+                            Some(actor) => {
+                                ui.label(notification_text(format!("@{actor}"), visual, colors));
+                            }
+                            None => {
+                                ui.weak("—");
+                            }
+                        });
+                        row.col(|ui| {
+                            ui.label(notification_text(
+                                format_timestamp(
+                                    item.updated_at,
+                                    render_state.settings.timestamp_style,
+                                    "%Y-%m-%d %H:%M",
+                                ),
+                                visual,
+                                colors,
+                            ))
+                            .on_hover_text(format_local_timestamp(
+                                item.updated_at,
+                                FULL_TIMESTAMP_FORMAT,
+                            ));
+                        });
+                        row.col(|ui| {
+                            let busy = render_state.inflight_done.contains(&item.thread_id);
+                            let review_active = render_state
+                                .active_review_thread_ids
+                                .contains(&item.thread_id);
+                            let already_read = !item.unread && !visual.needs_revisit;
 
-                                    if let (Some(pr_url), Some(pr_number)) =
-                                        (item.pull_request_url(), item.pull_request_number())
-                                    {
-                                        let custom_review_available =
-                                            custom_review_available_for_repo(
-                                                render_state.repo_paths,
-                                                render_state.custom_review_command,
-                                                &item.repo,
-                                            );
-                                        if review_active {
-                                            if reviewing_button(row_ui)
-                                                .on_hover_text("Click to stop this review.")
-                                                .clicked()
-                                            {
-                                                actions.push(AccountAction::StopReview(
-                                                    item.thread_id.clone(),
-                                                ));
-                                            }
-                                        } else if custom_review_available
-                                            && row_ui
-                                            .add_enabled(!busy, egui::Button::new("Review"))
+                            ui.horizontal_wrapped(|row_ui| {
+                                actions.extend(link_buttons(row_ui, item));
+                                if row_ui
+                                    .add_enabled(
+                                        !busy && !already_read,
+                                        egui::Button::new("Mark read"),
+                                    )
+                                    .clicked()
+                                {
+                                    actions.push(AccountAction::Read(item.thread_id.clone()));
+                                }
+                                if row_ui
+                                    .add_enabled(!busy, egui::Button::new("Done"))
+                                    .on_hover_text(
+                                        "Archive on GitHub and hide until there is new activity.",
+                                    )
+                                    .clicked()
+                                {
+                                    actions.push(AccountAction::Done(item.thread_id.clone()));
+                                }
+                                if unsubscribe_button(row_ui, item, busy, render_state)
+                                    .clicked()
+                                {
+                                    actions.push(AccountAction::Unsubscribe(
+                                        item.thread_id.clone(),
+                                    ));
+                                }
+                                if row_ui.button("Remind").clicked() {
+                                    actions.push(AccountAction::Remind(
+                                        item.thread_id.clone(),
+                                    ));
+                                }
+
+                                if let (Some(pr_url), Some(pr_number)) =
+                                    (item.pull_request_url(), item.pull_request_number())
+                                {
+                                    let custom_review_available =
+                                        custom_review_available_for_repo(
+                                            render_state.repo_paths,
+                                            render_state.custom_review_command,
+                                            &item.repo,
+                                        );
+                                    if review_active {
+                                        if reviewing_button(row_ui)
+                                            .on_hover_text("Click to stop this review.")
                                             .clicked()
                                         {
-                                            actions.push(AccountAction::Review {
-                                                thread_id: item.thread_id.clone(),
-                                                repo: item.repo.clone(),
-                                                pr_number,
-                                                pr_url: pr_url.to_owned(),
-                                            });
-                                        } else if !custom_review_available {
-                                            row_ui
-                                                .add_enabled(false, egui::Button::new("Review"))
-                                                .on_hover_text(
-                                                    "Custom `review-pr` is unavailable for this repository.",
-                                                );
+                                            actions.push(AccountAction::StopReview(
+                                                item.thread_id.clone(),
+                                            ));
                                         }
+                                    } else if custom_review_available
+                                        && row_ui
+                                        .add_enabled(!busy, egui::Button::new("Review"))
+                                        .clicked()
+                                    {
+                                        actions.push(AccountAction::Review {
+                                            thread_id: item.thread_id.clone(),
+                                            repo: item.repo.clone(),
+                                            pr_number,
+                                            pr_url: pr_url.to_owned(),
+                                        });
+                                    } else if !custom_review_available {
+                                        row_ui
+                                            .add_enabled(false, egui::Button::new("Review"))
+                                            .on_hover_text(
+                                                "Custom `review-pr` is unavailable for this repository.",
+                                            );
+                                    }
 
-                                        if render_state
-                                            .review_output_thread_ids
+                                    if render_state
+                                        .review_output_thread_ids
+                                        .contains(&item.thread_id)
+                                    {
+                                        let window_label = if render_state
+                                            .open_review_window_thread_ids
                                             .contains(&item.thread_id)
                                         {
-                                            let window_label = if render_state
-                                                .open_review_window_thread_ids
-                                                .contains(&item.thread_id)
-                                            {
-                                                "Hide review"
-                                            } else {
-                                                "Show review"
-                                            };
-                                            if row_ui.small_button(window_label).clicked() {
-                                                actions.push(AccountAction::ToggleReviewWindow(
-                                                    item.thread_id.clone(),
-                                                ));
-                                            }
+                                            "Hide review"
+                                        } else {
+                                            "Show review"
+                                        };
+                                        if row_ui.small_button(window_label).clicked() {
+                                            actions.push(AccountAction::ToggleReviewWindow(
+                                                item.thread_id.clone(),
+                                            ));
                                         }
                                     }
+                                }
 
-                                    if busy && !review_active {
-                                        row_ui.spinner();
-                                    }
-                                });
+                                if busy && !review_active {
+                                    row_ui.spinner();
+                                }
                             });
                         });
-                    }
+                        let response = row.response();
+                        if let Some(action) = preview_click_action(&response, item, render_state) {
+                            actions.push(action);
+                        }
+                        if response.double_clicked()
+                            && let Some(action) =
+                                double_click_account_action(item, render_state)
+                        {
+                            actions.push(action);
+                        }
+                        actions.extend(swipe_action(&response, item, render_state.settings));
+                    });
                 });
         });
     actions
 }
 
+fn preview_click_action(
+    response: &egui::Response,
+    item: &NotificationItem,
+    render_state: &NotificationRenderState<'_>,
+) -> Option<AccountAction> {
+    (render_state.settings.preview_pane && response.clicked())
+        .then(|| AccountAction::Preview(item.thread_id.clone()))
+}
+
+fn double_click_account_action(
+    item: &NotificationItem,
+    render_state: &NotificationRenderState<'_>,
+) -> Option<AccountAction> {
+    match render_state.settings.double_click_action(&item.reason) {
+        DoubleClickAction::Nothing => None,
+        DoubleClickAction::OpenInBrowser => item.url.as_ref().map(|url| AccountAction::Open {
+            thread_id: item.thread_id.clone(),
+            url: url.clone(),
+        }),
+        DoubleClickAction::MarkRead => Some(AccountAction::Read(item.thread_id.clone())),
+        DoubleClickAction::ShowDetails => Some(AccountAction::ShowDetails(item.thread_id.clone())),
+    }
+}
+
+/// Plain clicks on a subject link mark the thread seen. Middle-click and
+/// Ctrl/Cmd+click still open the link (egui handles that) but leave the local
+/// read state alone so the item can be peeked at without losing its flag.
+fn link_click_marks_seen(ui: &egui::Ui, response: &egui::Response) -> bool {
+    click_marks_seen(response.clicked(), ui.input(|input| input.modifiers))
+}
+
+fn click_marks_seen(primary_clicked: bool, modifiers: egui::Modifiers) -> bool {
+    primary_clicked && !modifiers.command && !modifiers.ctrl
+}
+
 fn notification_has_approved_badge(item: &NotificationItem) -> bool {
     item.my_review_status == Some(PullRequestReviewerStatus::Approved)
 }
@@ -625,15 +1523,12 @@ fn render_notification_title_link(
     item: &NotificationItem,
     text: RichText,
     url: &str,
+    colors: StatusColors,
 ) -> egui::Response {
     let response = ui.hyperlink_to(text, url);
     if notification_has_approved_badge(item) {
-        ui.small(
-            RichText::new("✓")
-                .strong()
-                .color(APPROVED_TITLE_CHECK_COLOR),
-        )
-        .on_hover_text("You approved this pull request.");
+        ui.small(RichText::new("✓").strong().color(colors.approved))
+            .on_hover_text("You approved this pull request.");
     }
     response
 }
@@ -642,15 +1537,12 @@ fn render_notification_title_label(
     ui: &mut egui::Ui,
     item: &NotificationItem,
     text: RichText,
+    colors: StatusColors,
 ) -> egui::Response {
     let response = ui.label(text);
     if notification_has_approved_badge(item) {
-        ui.small(
-            RichText::new("✓")
-                .strong()
-                .color(APPROVED_TITLE_CHECK_COLOR),
-        )
-        .on_hover_text("You approved this pull request.");
+        ui.small(RichText::new("✓").strong().color(colors.approved))
+            .on_hover_text("You approved this pull request.");
     }
     response
 }
@@ -659,45 +1551,187 @@ fn render_notification_branch_direction(
     ui: &mut egui::Ui,
     item: &NotificationItem,
     visual: NotificationVisualState,
+    colors: StatusColors,
 ) {
     let Some(direction) = item.merge_direction_text() else {
         return;
     };
-    ui.small(notification_text(ui, direction, visual));
+    ui.small(notification_text(direction, visual, colors));
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{notification_has_approved_badge, notification_matches_search};
+    use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+    use eframe::egui::Modifiers;
+
+    use super::{
+        NotificationRenderState, click_marks_seen, double_click_account_action,
+        group_by_repository, notification_has_approved_badge, notification_matches_search,
+        visible_notifications,
+    };
     use crate::{
-        app::search::SearchFilter,
-        domain::{NotificationItem, PullRequestReviewerStatus},
+        app::{AccountAction, rules::RuleSet, search::SearchFilter},
+        domain::{
+            AppSettings, DoneThreads, NotificationItem, PullRequestReviewerStatus, RepoTier,
+            SearchMode, ThreadStages, test_support,
+        },
+        github::PullRequestStatusQueue,
     };
-    use chrono::Utc;
+    use chrono::{Duration, Utc};
 
     fn notification_with_url(url: &str) -> NotificationItem {
         NotificationItem {
-            thread_id: String::from("thread-1"),
             repo: String::from("acme/repo"),
             title: String::from("Fix search behavior"),
             url: Some(url.to_owned()),
             head_ref: Some(String::from("feature/search")),
             base_ref: Some(String::from("main")),
-            my_review_status: None,
             reason: String::from("review_requested"),
-            updated_at: Utc::now(),
-            last_read_at: None,
-            unread: true,
+            ..test_support::notification("thread-1")
         }
     }
 
+    #[test]
+    fn group_by_repository_orders_by_tier_then_latest_update() {
+        let now = Utc::now();
+        let mut stale = notification_with_url("https://github.com/acme/stale/pull/1");
+        stale.repo = String::from("acme/stale");
+        stale.updated_at = now - Duration::hours(3);
+        let mut fresh = notification_with_url("https://github.com/acme/fresh/pull/1");
+        fresh.repo = String::from("acme/fresh");
+        fresh.updated_at = now;
+        let mut critical = notification_with_url("https://github.com/acme/core/pull/1");
+        critical.repo = String::from("acme/core");
+        critical.updated_at = now - Duration::days(2);
+        let mut fresh_older = fresh.clone();
+        fresh_older.thread_id = String::from("thread-2");
+        fresh_older.updated_at = now - Duration::days(5);
+        let items = [&stale, &fresh, &critical, &fresh_older];
+        let mut settings = AppSettings::default();
+        settings
+            .repo_tiers
+            .insert(String::from("acme/core"), RepoTier::Critical);
+        let filter = SearchFilter::new("", SearchMode::Substring, false);
+
+        let groups = group_by_repository(&items, &filter, &settings);
+
+        let order: Vec<_> = groups
+            .iter()
+            .map(|(repo, items)| (*repo, items.len()))
+            .collect();
+        assert_eq!(
+            order,
+            vec![("acme/core", 1), ("acme/fresh", 2), ("acme/stale", 1)]
+        );
+    }
+
+    #[test]
+    fn visible_notifications_hides_bot_threads_only_when_enabled() {
+        let human = notification_with_url("https://github.com/acme/repo/pull/1");
+        let mut bot = notification_with_url("https://github.com/acme/repo/pull/2");
+        bot.thread_id = String::from("thread-2");
+        bot.latest_actor = Some(String::from("dependabot[bot]"));
+        let notifications = vec![human, bot];
+
+        let settings = AppSettings::default();
+        let done = DoneThreads::default();
+
+        let (visible, hidden) = visible_notifications(
+            &notifications,
+            false,
+            &done,
+            &settings,
+            &RuleSet::default(),
+            Utc::now(),
+        );
+        assert_eq!((visible.len(), hidden.bots), (2, 0));
+
+        let (visible, hidden) = visible_notifications(
+            &notifications,
+            true,
+            &done,
+            &settings,
+            &RuleSet::default(),
+            Utc::now(),
+        );
+        assert_eq!(hidden.bots, 1);
+        assert_eq!(visible[0].thread_id, "thread-1");
+    }
+
+    #[test]
+    fn visible_notifications_counts_auto_snoozed_threads() {
+        let mut item = notification_with_url("https://github.com/acme/repo/pull/1");
+        item.reason = String::from("subscribed");
+        let notifications = vec![item];
+        let mut settings = AppSettings::default();
+        settings
+            .auto_snooze_hours
+            .insert(String::from("subscribed"), 4);
+        let done = DoneThreads::default();
+
+        let (visible, hidden) = visible_notifications(
+            &notifications,
+            false,
+            &done,
+            &settings,
+            &RuleSet::default(),
+            Utc::now(),
+        );
+        assert!(visible.is_empty());
+        assert_eq!(hidden.snoozed, 1);
+    }
+
+    #[test]
+    fn visible_notifications_hides_done_threads_until_new_activity() {
+        let mut item = notification_with_url("https://github.com/acme/repo/pull/1");
+        let settings = AppSettings::default();
+        let mut done = DoneThreads::default();
+        done.mark(item.thread_id.clone(), item.updated_at);
+
+        let notifications = vec![item.clone()];
+        let (visible, hidden) = visible_notifications(
+            &notifications,
+            false,
+            &done,
+            &settings,
+            &RuleSet::default(),
+            Utc::now(),
+        );
+        assert!(visible.is_empty());
+        assert_eq!(hidden.done, 1);
+
+        item.updated_at += chrono::Duration::minutes(5);
+        let notifications = vec![item];
+        let (visible, hidden) = visible_notifications(
+            &notifications,
+            false,
+            &done,
+            &settings,
+            &RuleSet::default(),
+            Utc::now(),
+        );
+        assert_eq!((visible.len(), hidden.done), (1, 0));
+    }
+
     #[test]
     fn notification_search_matches_pull_request_number_without_hash() {
         let item = notification_with_url("https://github.com/acme/repo/pull/123");
 
         assert!(notification_matches_search(
             &item,
-            &SearchFilter::new("123")
+            &SearchFilter::new("123", SearchMode::Substring, false)
+        ));
+    }
+
+    #[test]
+    fn notification_search_matches_latest_actor() {
+        let mut item = notification_with_url("https://github.com/acme/repo/pull/123");
+        item.latest_actor = Some(String::from("trinity"));
+
+        assert!(notification_matches_search(
+            &item,
+            &SearchFilter::new("trinity", SearchMode::Substring, false)
         ));
     }
 
@@ -707,7 +1741,7 @@ mod tests {
 
         assert!(notification_matches_search(
             &item,
-            &SearchFilter::new("#123")
+            &SearchFilter::new("#123", SearchMode::Substring, false)
         ));
     }
 
@@ -717,7 +1751,7 @@ mod tests {
 
         assert!(!notification_matches_search(
             &item,
-            &SearchFilter::new("456")
+            &SearchFilter::new("456", SearchMode::Substring, false)
         ));
     }
 
@@ -727,7 +1761,7 @@ mod tests {
 
         assert!(notification_matches_search(
             &item,
-            &SearchFilter::new("acme/repo#123")
+            &SearchFilter::new("acme/repo#123", SearchMode::Substring, false)
         ));
     }
 
@@ -737,7 +1771,7 @@ mod tests {
 
         assert!(notification_matches_search(
             &item,
-            &SearchFilter::new("feature/search -> main")
+            &SearchFilter::new("feature/search -> main", SearchMode::Substring, false)
         ));
     }
 
@@ -752,4 +1786,54 @@ mod tests {
         item.my_review_status = Some(PullRequestReviewerStatus::Approved);
         assert!(notification_has_approved_badge(&item));
     }
+
+    #[test]
+    fn double_click_dispatches_reason_specific_action() {
+        let empty = HashSet::new();
+        let repo_paths = BTreeMap::new();
+        let settings = AppSettings::default();
+        let render_state = NotificationRenderState {
+            inflight_done: &empty,
+            unsubscribed_thread_ids: &empty,
+            group_by_repo: false,
+            group_by_day: false,
+            collapsed_repos: &BTreeSet::new(),
+            pending_review_ids: &empty,
+            re_requested_ids: &empty,
+            review_waits: &HashMap::new(),
+            active_review_thread_ids: &empty,
+            review_output_thread_ids: &empty,
+            open_review_window_thread_ids: &empty,
+            custom_review_command: false,
+            repo_paths: &repo_paths,
+            settings: &settings,
+            rules: &RuleSet::default(),
+            pull_request_statuses: &PullRequestStatusQueue::default(),
+            bulk_progress: None,
+            stages: &ThreadStages::default(),
+        };
+        let mut item = notification_with_url("https://github.com/acme/repo/pull/123");
+
+        assert!(matches!(
+            double_click_account_action(&item, &render_state),
+            Some(AccountAction::Open { url, .. }) if url == "https://github.com/acme/repo/pull/123"
+        ));
+
+        item.reason = String::from("ci_activity");
+        assert!(matches!(
+            double_click_account_action(&item, &render_state),
+            Some(AccountAction::Read(_))
+        ));
+
+        item.reason = String::from("subscribed");
+        assert!(double_click_account_action(&item, &render_state).is_none());
+    }
+
+    #[test]
+    fn modified_clicks_open_without_marking_seen() {
+        assert!(click_marks_seen(true, Modifiers::NONE));
+        assert!(!click_marks_seen(true, Modifiers::COMMAND));
+        assert!(!click_marks_seen(true, Modifiers::CTRL));
+        assert!(!click_marks_seen(false, Modifiers::NONE));
+    }
 }
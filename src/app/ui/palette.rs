@@ -0,0 +1,122 @@
+use eframe::egui::{Color32, Context, Theme, Visuals, ecolor::Hsva};
+
+use crate::domain::{StatusPalette, ThreadStage};
+
+use super::super::{APPROVED_REVIEW_LABEL_COLOR, PENDING_REVIEW_LABEL_COLOR};
+
+// Okabe–Ito colors stay apart under deuteranopia and protanopia.
+const COLOR_BLIND_UPDATED: Color32 = Color32::from_rgb(230, 159, 0);
+const COLOR_BLIND_PENDING: Color32 = Color32::from_rgb(86, 180, 233);
+const COLOR_BLIND_APPROVED: Color32 = Color32::from_rgb(0, 114, 178);
+const WAITING_ON_AUTHOR_COLOR: Color32 = Color32::from_rgb(90, 140, 220);
+const DONE_STAGE_COLOR: Color32 = Color32::from_rgb(80, 160, 90);
+
+/// Colors of notification states and review badges under one palette.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(in crate::app) struct StatusColors {
+    pub(in crate::app) updated: Color32,
+    pub(in crate::app) seen: Color32,
+    pub(in crate::app) pending_review: Color32,
+    pub(in crate::app) approved: Color32,
+}
+
+impl StatusColors {
+    /// Swaps in the user's own color for updated threads, if any.
+    pub(in crate::app) fn with_updated(mut self, color: Option<[u8; 3]>) -> Self {
+        if let Some([r, g, b]) = color {
+            self.updated = Color32::from_rgb(r, g, b);
+        }
+        self
+    }
+}
+
+pub(in crate::app) fn status_colors(palette: StatusPalette, visuals: &Visuals) -> StatusColors {
+    match palette {
+        StatusPalette::Standard => StatusColors {
+            updated: visuals.warn_fg_color,
+            seen: visuals.weak_text_color(),
+            pending_review: PENDING_REVIEW_LABEL_COLOR,
+            approved: APPROVED_REVIEW_LABEL_COLOR,
+        },
+        StatusPalette::ColorBlind => StatusColors {
+            updated: COLOR_BLIND_UPDATED,
+            seen: visuals.weak_text_color(),
+            pending_review: COLOR_BLIND_PENDING,
+            approved: COLOR_BLIND_APPROVED,
+        },
+    }
+}
+
+/// Rebuilds the light and dark visuals with `accent` on selections and
+/// links, so switching themes keeps it.
+pub(in crate::app) fn apply_accent(ctx: &Context, accent: Option<[u8; 3]>) {
+    for theme in [Theme::Dark, Theme::Light] {
+        let mut visuals = theme.default_visuals();
+        if let Some([r, g, b]) = accent {
+            let color = Color32::from_rgb(r, g, b);
+            visuals.selection.bg_fill = color;
+            visuals.hyperlink_color = color;
+        }
+        ctx.set_visuals_of(theme, visuals);
+    }
+}
+
+/// Color of a personal stage's picker and board column.
+pub(in crate::app) fn stage_color(stage: ThreadStage, visuals: &Visuals) -> Color32 {
+    match stage {
+        ThreadStage::ToTriage => visuals.weak_text_color(),
+        ThreadStage::WaitingOnAuthor => WAITING_ON_AUTHOR_COLOR,
+        ThreadStage::WaitingOnMe => visuals.warn_fg_color,
+        ThreadStage::Done => DONE_STAGE_COLOR,
+    }
+}
+
+/// A stable hue per login, so rows of the combined inbox can be told apart
+/// at a glance.
+pub(in crate::app) fn account_color(login: &str) -> Color32 {
+    let hash = login.bytes().fold(0x811c_9dc5_u32, |hash, byte| {
+        (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193)
+    });
+    Hsva::new((hash % 360) as f32 / 360.0, 0.6, 0.85, 1.0).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use eframe::egui::{Color32, Context, Theme, Visuals};
+
+    use super::{apply_accent, status_colors};
+    use crate::domain::StatusPalette;
+
+    #[test]
+    fn accent_applies_to_both_themes_and_can_be_reset() {
+        let ctx = Context::default();
+
+        apply_accent(&ctx, Some([200, 40, 120]));
+        for theme in [Theme::Dark, Theme::Light] {
+            let visuals = ctx.style_of(theme).visuals.clone();
+            assert_eq!(visuals.selection.bg_fill, Color32::from_rgb(200, 40, 120));
+            assert_eq!(visuals.hyperlink_color, Color32::from_rgb(200, 40, 120));
+        }
+
+        apply_accent(&ctx, None);
+        assert_eq!(ctx.style_of(Theme::Dark).visuals, Visuals::dark());
+    }
+
+    #[test]
+    fn every_palette_keeps_its_states_apart() {
+        for visuals in [Visuals::dark(), Visuals::light()] {
+            for palette in StatusPalette::ALL {
+                let colors = status_colors(palette, &visuals);
+                let all = [
+                    colors.updated,
+                    colors.seen,
+                    colors.pending_review,
+                    colors.approved,
+                ];
+                for (idx, color) in all.iter().enumerate() {
+                    assert!(!all[idx + 1..].contains(color), "{palette:?}");
+                }
+            }
+        }
+    }
+}
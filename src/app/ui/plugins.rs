@@ -0,0 +1,70 @@
+use eframe::egui::{self, RichText};
+
+use crate::domain::TimestampStyle;
+
+use super::super::{
+    plugin_sections::PluginSectionsState,
+    time::{FULL_TIMESTAMP_FORMAT, format_local_timestamp, format_timestamp},
+};
+
+/// A collapsible section per loaded plugin, listed like notifications.
+pub(in crate::app) fn render_plugin_sections(
+    ui: &mut egui::Ui,
+    state: &PluginSectionsState,
+    timestamp_style: TimestampStyle,
+) {
+    for error in &state.load_errors {
+        ui.colored_label(ui.visuals().warn_fg_color, error);
+    }
+    for section in &state.sections {
+        let unread = section.rows.iter().filter(|row| row.unread).count();
+        egui::CollapsingHeader::new(format!(
+            "{} ({} · {unread} unread)",
+            section.name(),
+            section.rows.len()
+        ))
+        .id_salt(("plugin-section", section.name()))
+        .default_open(true)
+        .show(ui, |body| {
+            if let Some(error) = &section.error {
+                body.colored_label(body.visuals().error_fg_color, error);
+            }
+            if section.rows.is_empty() && section.error.is_none() {
+                body.weak(if section.checked_at.is_some() {
+                    "Nothing here."
+                } else {
+                    "Loading…"
+                });
+            }
+            for row in &section.rows {
+                body.horizontal_wrapped(|line| {
+                    if !row.context.is_empty() {
+                        line.label(RichText::new(&row.context).weak());
+                    }
+                    let title = if row.unread {
+                        RichText::new(&row.title).strong()
+                    } else {
+                        RichText::new(&row.title)
+                    };
+                    match &row.url {
+                        Some(url) => {
+                            line.hyperlink_to(title, url);
+                        }
+                        None => {
+                            line.label(title);
+                        }
+                    }
+                    line.small(format_timestamp(
+                        row.updated_at,
+                        timestamp_style,
+                        "%Y-%m-%d %H:%M",
+                    ))
+                    .on_hover_text(format_local_timestamp(
+                        row.updated_at,
+                        FULL_TIMESTAMP_FORMAT,
+                    ));
+                });
+            }
+        });
+    }
+}
@@ -0,0 +1,190 @@
+use eframe::egui::{self, RichText};
+
+use crate::domain::{NotificationItem, ReviewVerdict, TimestampStyle};
+
+use super::{
+    super::{
+        state::{ThreadPreview, ThreadReply},
+        time::{FULL_TIMESTAMP_FORMAT, format_local_timestamp, format_timestamp},
+    },
+    markdown::render_markdown,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(in crate::app) enum PreviewAction {
+    Open,
+    MarkRead,
+    Reload,
+    Close,
+    Reply(ThreadReply),
+}
+
+/// Title, labels, author, body and latest comments of the previewed thread,
+/// followed by a reply box.
+pub(in crate::app) fn render_preview_pane(
+    ui: &mut egui::Ui,
+    item: &NotificationItem,
+    preview: &mut ThreadPreview,
+    timestamp_style: TimestampStyle,
+) -> Option<PreviewAction> {
+    let mut action = None;
+    ui.horizontal(|row| {
+        if row
+            .add_enabled(item.url.is_some(), egui::Button::new("Open in browser"))
+            .clicked()
+        {
+            action = Some(PreviewAction::Open);
+        }
+        if row
+            .add_enabled(item.unread, egui::Button::new("Mark read"))
+            .clicked()
+        {
+            action = Some(PreviewAction::MarkRead);
+        }
+        if preview.is_loading() {
+            row.spinner();
+        } else if row.button("Reload").clicked() {
+            action = Some(PreviewAction::Reload);
+        }
+        if row.button("Close").clicked() {
+            action = Some(PreviewAction::Close);
+        }
+    });
+    ui.separator();
+
+    let details = match &preview.details {
+        None => {
+            ui.label(RichText::new(item.display_title()).heading());
+            ui.weak("Loading…");
+            return action;
+        }
+        Some(Err(err)) => {
+            ui.label(RichText::new(item.display_title()).heading());
+            ui.colored_label(ui.visuals().error_fg_color, err);
+            return action;
+        }
+        Some(Ok(details)) => details,
+    };
+
+    egui::ScrollArea::vertical()
+        .id_salt(("thread-preview", &preview.thread_id))
+        .auto_shrink([false, false])
+        .show(ui, |scroll| {
+            scroll.label(RichText::new(&details.title).heading());
+            scroll.horizontal_wrapped(|row| {
+                row.label(RichText::new(&details.state).strong());
+                row.label(RichText::new(&item.repo).weak());
+                if let Some(author) = &details.author {
+                    row.label(format!("by @{author}"));
+                }
+                for label in &details.labels {
+                    row.label(
+                        RichText::new(label)
+                            .small()
+                            .background_color(row.visuals().widgets.inactive.weak_bg_fill),
+                    );
+                }
+            });
+            scroll.add_space(8.0);
+            if details.body.trim().is_empty() {
+                scroll.weak("No description provided.");
+            } else {
+                render_markdown(scroll, &details.body, item.url.as_deref());
+            }
+
+            if !details.comments.is_empty() {
+                scroll.add_space(12.0);
+                scroll.label(RichText::new("Latest comments").strong());
+            }
+            for comment in &details.comments {
+                scroll.separator();
+                scroll.horizontal(|row| {
+                    row.label(
+                        RichText::new(format!("@{}", comment.author.as_deref().unwrap_or("ghost")))
+                            .strong(),
+                    );
+                    row.small(format_timestamp(
+                        comment.created_at,
+                        timestamp_style,
+                        "%Y-%m-%d %H:%M",
+                    ))
+                    .on_hover_text(format_local_timestamp(
+                        comment.created_at,
+                        FULL_TIMESTAMP_FORMAT,
+                    ));
+                });
+                render_markdown(scroll, &comment.body, item.url.as_deref());
+            }
+
+            scroll.separator();
+            if let Some(reply) = render_reply_box(
+                scroll,
+                item,
+                &mut preview.draft,
+                preview.sending,
+                preview.reply_error.as_deref(),
+            ) {
+                action = Some(PreviewAction::Reply(reply));
+            }
+        });
+    action
+}
+
+/// Comment box, plus approve and request-changes buttons when the thread is
+/// a pull request waiting on the user's review.
+fn render_reply_box(
+    ui: &mut egui::Ui,
+    item: &NotificationItem,
+    draft: &mut String,
+    sending: bool,
+    error: Option<&str>,
+) -> Option<ThreadReply> {
+    ui.add(
+        egui::TextEdit::multiline(draft)
+            .id_salt(("thread-reply", &item.thread_id))
+            .hint_text("Leave a comment")
+            .desired_rows(3)
+            .desired_width(f32::INFINITY),
+    );
+    if let Some(err) = error {
+        ui.colored_label(ui.visuals().error_fg_color, err);
+    }
+    let body = draft.trim().to_owned();
+    let has_text = !body.is_empty();
+    let idle = !sending;
+    let mut reply = None;
+    ui.horizontal(|row| {
+        if row
+            .add_enabled(idle && has_text, egui::Button::new("Comment"))
+            .clicked()
+        {
+            reply = Some(ThreadReply::Comment(body.clone()));
+        }
+        if item.reason == "review_requested" && item.pull_request_number().is_some() {
+            if row
+                .add_enabled(idle, egui::Button::new("Approve"))
+                .on_hover_text("Approve, with the text above as the review comment")
+                .clicked()
+            {
+                reply = Some(ThreadReply::Review {
+                    verdict: ReviewVerdict::Approve,
+                    body: body.clone(),
+                });
+            }
+            if row
+                .add_enabled(idle && has_text, egui::Button::new("Request changes"))
+                .on_disabled_hover_text("Describe the changes in the box above first")
+                .clicked()
+            {
+                reply = Some(ThreadReply::Review {
+                    verdict: ReviewVerdict::RequestChanges,
+                    body: body.clone(),
+                });
+            }
+        }
+        if sending {
+            row.spinner();
+        }
+    });
+    reply
+}
@@ -0,0 +1,42 @@
+use eframe::egui::{self, RichText};
+
+use super::super::{release_radar::ReleaseRadarState, time::format_local_timestamp};
+
+/// Red banner pinned above the dashboard while the release radar is set up.
+pub(in crate::app) fn render_release_radar(
+    ui: &mut egui::Ui,
+    label: &str,
+    radar: &ReleaseRadarState,
+) {
+    let color = ui.visuals().error_fg_color;
+    egui::Frame::group(ui.style())
+        .stroke(egui::Stroke::new(2.0, color))
+        .show(ui, |banner| {
+            banner.set_width(banner.available_width());
+            let heading = if radar.hits.is_empty() {
+                RichText::new(format!("No open {label} issues"))
+            } else {
+                RichText::new(format!("⚑ {} open {label} issue(s)", radar.hits.len())).color(color)
+            };
+            banner.horizontal_wrapped(|row| {
+                row.label(heading.strong());
+                if let Some(checked_at) = radar.checked_at {
+                    row.small(format!(
+                        "checked {}",
+                        format_local_timestamp(checked_at, "%H:%M")
+                    ));
+                }
+            });
+            if let Some(error) = &radar.error {
+                banner.colored_label(color, error);
+            }
+            for hit in &radar.hits {
+                banner.horizontal_wrapped(|row| {
+                    row.label(RichText::new(&hit.repo).weak());
+                    row.hyperlink_to(&hit.title, &hit.url);
+                    row.small(format_local_timestamp(hit.updated_at, "%Y-%m-%d %H:%M"));
+                });
+            }
+        });
+    ui.add_space(8.0);
+}
@@ -22,6 +22,7 @@ use super::{
         time::format_local_timestamp,
     },
     layout::{uses_compact_notifications, uses_stacked_account_header},
+    search_box::{SEARCH_MODE_TOGGLES_WIDTH, render_search_box},
 };
 
 pub(in crate::app) fn render_repository_card(
@@ -30,6 +31,7 @@ pub(in crate::app) fn render_repository_card(
     repo_state: &mut RepoState,
     repo_paths: &BTreeMap<String, String>,
     custom_review_command: bool,
+    transliterate_search: bool,
 ) {
     ui.group(|group| {
         render_repository_header(group, account, repo_state);
@@ -40,6 +42,7 @@ pub(in crate::app) fn render_repository_card(
             repo_state,
             repo_paths,
             custom_review_command,
+            transliterate_search,
         );
     });
     ui.add_space(12.0);
@@ -60,11 +63,13 @@ fn render_repository_header(
                 }
             });
             column.small(format!("Using account: {}", account.profile.login));
-            let search_width = column.available_width();
-            column.add(
-                egui::TextEdit::singleline(&mut repo_state.search_query)
-                    .hint_text("Search pull requests…")
-                    .desired_width(search_width),
+            let search_width = column.available_width() - SEARCH_MODE_TOGGLES_WIDTH;
+            render_search_box(
+                column,
+                &mut repo_state.search_query,
+                &mut repo_state.search_mode,
+                "Search pull requests…",
+                search_width,
             );
             render_sort_mode_toggle(column, repo_state);
         });
@@ -76,10 +81,12 @@ fn render_repository_header(
                     refresh_requested = true;
                 }
                 row.with_layout(Layout::right_to_left(egui::Align::Center), |lane| {
-                    lane.add(
-                        egui::TextEdit::singleline(&mut repo_state.search_query)
-                            .hint_text("Search pull requests…")
-                            .desired_width(180.0),
+                    render_search_box(
+                        lane,
+                        &mut repo_state.search_query,
+                        &mut repo_state.search_mode,
+                        "Search pull requests…",
+                        180.0,
                     );
                     lane.add_space(8.0);
                     lane.small(format!("Using account: {}", account.profile.login));
@@ -90,7 +97,7 @@ fn render_repository_header(
     }
 
     if refresh_requested {
-        repo_state.start_refresh(account.profile.clone());
+        repo_state.start_refresh(account.client.clone(), account.profile.clone());
     }
 }
 
@@ -156,9 +163,10 @@ fn render_repository_status(group: &mut egui::Ui, account: &AccountState, repo_s
 fn render_repository_body(
     group: &mut egui::Ui,
     account: &mut AccountState,
-    repo_state: &RepoState,
+    repo_state: &mut RepoState,
     repo_paths: &BTreeMap<String, String>,
     custom_review_command: bool,
+    transliterate_search: bool,
 ) {
     group.separator();
 
@@ -175,7 +183,17 @@ fn render_repository_body(
     }
 
     let context = build_repo_context_info(account);
-    let filter = SearchFilter::new(&repo_state.search_query);
+    let filter = SearchFilter::new(
+        &repo_state.search_query,
+        repo_state.search_mode,
+        transliterate_search,
+    );
+    if let Some(error) = filter.error() {
+        group.colored_label(
+            group.visuals().error_fg_color,
+            format!("Invalid regex: {error}"),
+        );
+    }
     let mut matching_rows: Vec<_> = snapshot
         .pull_requests
         .iter()
@@ -205,32 +223,85 @@ fn render_repository_body(
         .collect();
     let pr_description_prompt_available =
         pr_description_command_available(&account.profile.review_settings);
+    let render_rows = |ui: &mut egui::Ui, rows: &[&RepoPullRequest]| {
+        if uses_compact_notifications(ui.available_width()) {
+            render_pull_request_cards(
+                ui,
+                rows,
+                &active_review_thread_ids,
+                &review_output_thread_ids,
+                &open_review_window_thread_ids,
+                &context,
+                repo_paths,
+                custom_review_command,
+                pr_description_prompt_available,
+            )
+        } else {
+            render_pull_request_table(
+                ui,
+                rows,
+                &active_review_thread_ids,
+                &review_output_thread_ids,
+                &open_review_window_thread_ids,
+                &context,
+                repo_paths,
+                custom_review_command,
+                pr_description_prompt_available,
+            )
+        }
+    };
 
-    let actions = if uses_compact_notifications(group.available_width()) {
-        render_pull_request_cards(
-            group,
-            &matching_rows,
-            &active_review_thread_ids,
-            &review_output_thread_ids,
-            &open_review_window_thread_ids,
-            &context,
-            repo_paths,
-            custom_review_command,
-            pr_description_prompt_available,
-        )
+    let (bot_rows, human_rows): (Vec<_>, Vec<_>) = matching_rows
+        .into_iter()
+        .partition(|pull_request| pull_request.is_from_bot());
+    let mut actions = Vec::new();
+    if human_rows.is_empty() {
+        group.weak("Only dependency updates match.");
     } else {
-        render_pull_request_table(
-            group,
-            &matching_rows,
-            &active_review_thread_ids,
-            &review_output_thread_ids,
-            &open_review_window_thread_ids,
-            &context,
-            repo_paths,
-            custom_review_command,
-            pr_description_prompt_available,
+        actions.extend(render_rows(group, &human_rows));
+    }
+
+    let mut approve_requested = false;
+    if !bot_rows.is_empty() {
+        group.add_space(6.0);
+        egui::CollapsingHeader::new(
+            RichText::new(format!("Dependency updates ({})", bot_rows.len())).strong(),
         )
-    };
+        .id_salt(("dependency-updates", &repo_state.repo))
+        .default_open(false)
+        .show(group, |section| {
+            section.horizontal_wrapped(|row| {
+                if row
+                    .button("Open all")
+                    .on_hover_text("Open every dependency update in the browser, in list order.")
+                    .clicked()
+                {
+                    for pull_request in &bot_rows {
+                        row.ctx()
+                            .open_url(egui::OpenUrl::new_tab(pull_request.url.clone()));
+                    }
+                }
+                if repo_state.pending_batch_approve.is_some() {
+                    progress_button(row, "    Approving");
+                } else if row
+                    .button("Approve passing")
+                    .on_hover_text("Approve every non-draft update whose checks all passed.")
+                    .clicked()
+                {
+                    approve_requested = true;
+                }
+            });
+            if let Some(status) = &repo_state.batch_approve_status {
+                section.small(status);
+            }
+            section.add_space(4.0);
+            actions.extend(render_rows(section, &bot_rows));
+        });
+    }
+    if approve_requested {
+        let pr_numbers = batch_approve_candidates(&bot_rows);
+        repo_state.start_batch_approve(account.client.clone(), account.profile.clone(), pr_numbers);
+    }
 
     for action in actions {
         match action {
@@ -250,8 +321,11 @@ fn render_repository_body(
                 ) {
                     account.request_review(thread_id, launch)
                 } else {
-                    account.last_error =
-                        Some("Custom `review-pr` is unavailable for this repository.".to_owned());
+                    account.last_error = Some(
+                        "Custom `review-pr` is unavailable for this repository."
+                            .to_owned()
+                            .into(),
+                    );
                 }
             }
             AccountAction::PrDescription {
@@ -271,7 +345,9 @@ fn render_repository_body(
                     account.request_review(thread_id, launch)
                 } else {
                     account.last_error = Some(
-                        "Custom `pr-description` is unavailable for this repository.".to_owned(),
+                        "Custom `pr-description` is unavailable for this repository."
+                            .to_owned()
+                            .into(),
                     );
                 }
             }
@@ -282,11 +358,32 @@ fn render_repository_body(
             } => account.open_review_request_editor(repo, pr_number, pr_title),
             AccountAction::StopReview(id) => account.cancel_review(&id),
             AccountAction::ToggleReviewWindow(id) => account.toggle_review_window_for_thread(&id),
-            AccountAction::Done(_) | AccountAction::Seen(_) | AccountAction::Read(_) => {}
+            AccountAction::Done(_)
+            | AccountAction::Seen(_)
+            | AccountAction::Read(_)
+            | AccountAction::Open { .. }
+            | AccountAction::ShowDetails(_)
+            | AccountAction::Preview(_)
+            | AccountAction::Remind(_)
+            | AccountAction::Unsubscribe(_)
+            | AccountAction::Bulk(_)
+            | AccountAction::ToggleRepoGroup(_)
+            | AccountAction::SetStage { .. } => {}
         }
     }
 }
 
+fn batch_approve_candidates(pull_requests: &[&RepoPullRequest]) -> Vec<u64> {
+    pull_requests
+        .iter()
+        .filter(|pull_request| {
+            !pull_request.draft
+                && pull_request.my_review_status != Some(PullRequestReviewerStatus::Approved)
+        })
+        .map(|pull_request| pull_request.number)
+        .collect()
+}
+
 fn pull_request_matches_search(pull_request: &RepoPullRequest, filter: &SearchFilter) -> bool {
     let number_alias = pull_request.number.to_string();
     let hash_alias = format!("#{}", pull_request.number);
@@ -542,6 +639,7 @@ fn render_pull_request_branch_direction(ui: &mut egui::Ui, pull_request: &RepoPu
     );
 }
 
+#[allow(clippy::too_many_arguments)]
 fn render_pull_request_cards(
     ui: &mut egui::Ui,
     pull_requests: &[&RepoPullRequest],
@@ -598,6 +696,7 @@ fn render_pull_request_cards(
     actions
 }
 
+#[allow(clippy::too_many_arguments)]
 fn render_pull_request_table(
     ui: &mut egui::Ui,
     pull_requests: &[&RepoPullRequest],
@@ -679,6 +778,7 @@ fn render_pull_request_table(
     actions
 }
 
+#[allow(clippy::too_many_arguments)]
 fn render_pull_request_actions(
     ui: &mut egui::Ui,
     pull_request: &RepoPullRequest,
@@ -807,13 +907,14 @@ fn progress_button(ui: &mut egui::Ui, label: &str) -> egui::Response {
 mod tests {
     use super::{
         PullRequestActionAvailability, RepoContextInfo, RepoSortMode, RepoSortSignals,
-        pr_description_unavailable_hover_text, pull_request_action_availability,
-        pull_request_has_approved_badge, pull_request_matches_search, pull_request_summary_text,
+        batch_approve_candidates, pr_description_unavailable_hover_text,
+        pull_request_action_availability, pull_request_has_approved_badge,
+        pull_request_matches_search, pull_request_summary_text,
         render_pull_request_branch_direction, sort_pull_requests,
     };
     use crate::{
         app::search::SearchFilter,
-        domain::{PullRequestKey, PullRequestReviewerStatus, RepoPullRequest},
+        domain::{PullRequestKey, PullRequestReviewerStatus, RepoPullRequest, SearchMode},
     };
     use chrono::Utc;
     use std::collections::BTreeMap;
@@ -837,7 +938,7 @@ mod tests {
     fn pull_request_search_matches_repo_scoped_number() {
         assert!(pull_request_matches_search(
             &pull_request(),
-            &SearchFilter::new("acme/repo#123")
+            &SearchFilter::new("acme/repo#123", SearchMode::Substring, false)
         ));
     }
 
@@ -845,15 +946,31 @@ mod tests {
     fn pull_request_search_matches_author_login() {
         assert!(pull_request_matches_search(
             &pull_request(),
-            &SearchFilter::new("neo")
+            &SearchFilter::new("neo", SearchMode::Substring, false)
         ));
     }
 
+    #[test]
+    fn batch_approve_candidates_skip_drafts_and_already_approved() {
+        let ready = pull_request();
+        let mut draft = pull_request();
+        draft.number = 124;
+        draft.draft = true;
+        let mut approved = pull_request();
+        approved.number = 125;
+        approved.my_review_status = Some(PullRequestReviewerStatus::Approved);
+
+        assert_eq!(
+            batch_approve_candidates(&[&ready, &draft, &approved]),
+            vec![123]
+        );
+    }
+
     #[test]
     fn pull_request_search_matches_merge_direction() {
         assert!(pull_request_matches_search(
             &pull_request(),
-            &SearchFilter::new("feature/filters -> main")
+            &SearchFilter::new("feature/filters -> main", SearchMode::Substring, false)
         ));
     }
 
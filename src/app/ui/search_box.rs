@@ -0,0 +1,59 @@
+use eframe::egui;
+
+use crate::domain::SearchMode;
+
+/// Room taken by the mode toggles next to the text field.
+pub(super) const SEARCH_MODE_TOGGLES_WIDTH: f32 = 56.0;
+
+/// Search field followed by the regex and fuzzy toggles. Turning one mode on
+/// turns the other off; with both off the query is a plain substring.
+pub(super) fn render_search_box(
+    ui: &mut egui::Ui,
+    query: &mut String,
+    mode: &mut SearchMode,
+    hint: &str,
+    width: f32,
+) {
+    ui.horizontal(|row| {
+        row.add(
+            egui::TextEdit::singleline(query)
+                .hint_text(hint)
+                .desired_width(width),
+        );
+        render_mode_toggle(
+            row,
+            mode,
+            SearchMode::Regex,
+            ".*",
+            "Regular expression (case-insensitive)",
+        );
+        render_mode_toggle(
+            row,
+            mode,
+            SearchMode::Fuzzy,
+            "~",
+            "Fuzzy: letters in order, gaps allowed",
+        );
+    });
+}
+
+fn render_mode_toggle(
+    ui: &mut egui::Ui,
+    mode: &mut SearchMode,
+    toggle: SearchMode,
+    label: &str,
+    hover: &str,
+) {
+    let active = *mode == toggle;
+    if ui
+        .selectable_label(active, egui::RichText::new(label).monospace())
+        .on_hover_text(hover)
+        .clicked()
+    {
+        *mode = if active {
+            SearchMode::Substring
+        } else {
+            toggle
+        };
+    }
+}
@@ -0,0 +1,559 @@
+use std::collections::HashSet;
+
+use chrono::{DateTime, Utc};
+use eframe::egui::{self, RichText};
+use egui_extras::{Column, TableBuilder};
+
+use crate::{
+    domain::{AppSettings, InboxSnapshot, PullRequestKey, SearchHit},
+    github::PullRequestStatusQueue,
+};
+
+use super::{
+    super::{
+        SectionKind,
+        notification_state::requested_reviews,
+        search::SearchFilter,
+        state::AccountState,
+        time::{FULL_TIMESTAMP_FORMAT, format_local_timestamp, format_timestamp},
+    },
+    notifications::{NotificationRenderState, avatar, pull_request_badges, review_wait_badge},
+    palette::status_colors,
+};
+
+/// One row of a search-backed list.
+struct SearchRow<'a> {
+    repo: &'a str,
+    title: &'a str,
+    url: &'a str,
+    updated_at: DateTime<Utc>,
+    /// Requester, thread kind or PR state, depending on the list.
+    detail: String,
+    /// Login whose avatar leads the detail column, e.g. the requester.
+    person: Option<&'a str>,
+    /// When a pending review request was made.
+    waiting_since: Option<DateTime<Utc>>,
+    key: Option<PullRequestKey>,
+}
+
+struct SearchList<'a> {
+    title: &'a str,
+    /// Heading of the `detail` column; lists without one leave it out.
+    detail_heading: Option<&'static str>,
+    empty_label: &'static str,
+    rows: Vec<SearchRow<'a>>,
+    paging: Option<SearchPaging>,
+}
+
+/// How much of its search a list holds, for lists that can load more.
+struct SearchPaging {
+    kind: SectionKind,
+    fetched: usize,
+    /// GitHub's total; zero when it did not say.
+    total: u64,
+    loading: bool,
+}
+
+impl SearchPaging {
+    fn truncated(&self) -> bool {
+        (self.fetched as u64) < self.total
+    }
+}
+
+#[derive(Default)]
+struct SearchListResponse {
+    /// A highlighted section was shown open, which clears the highlight.
+    highlight_seen: bool,
+    load_more: Option<SectionKind>,
+}
+
+/// Review requests, mentions and submitted reviews found through GitHub
+/// search, minus the threads the notification sections already show.
+/// Returns the list whose "Load more" was clicked.
+pub(super) fn render_search_sections(
+    ui: &mut egui::Ui,
+    account: &AccountState,
+    filter: &SearchFilter,
+    settings: &AppSettings,
+) -> Option<SectionKind> {
+    let inbox = account.inbox.as_ref()?;
+    let mut load_more = None;
+    for mut list in search_lists(inbox) {
+        if let Some(paging) = &mut list.paging {
+            paging.loading = account.is_loading_more(paging.kind);
+        }
+        let response = render_search_list(
+            ui,
+            &list,
+            filter,
+            &account.pull_request_statuses,
+            settings,
+            false,
+        );
+        load_more = load_more.or(response.load_more);
+    }
+    load_more
+}
+
+/// The "Assigned to me" or "My open PRs" section. Returns true when a
+/// highlighted section was shown open, which clears the highlight.
+pub(super) fn render_work_section(
+    ui: &mut egui::Ui,
+    kind: SectionKind,
+    inbox: &InboxSnapshot,
+    statuses: &PullRequestStatusQueue,
+    filter: &SearchFilter,
+    settings: &AppSettings,
+    highlight: bool,
+) -> bool {
+    let list = match kind {
+        SectionKind::Assigned => assigned_issue_list(inbox),
+        SectionKind::AuthoredPullRequests => authored_pull_request_list(inbox),
+        _ => return false,
+    };
+    render_search_list(ui, &list, filter, statuses, settings, highlight).highlight_seen
+}
+
+/// A saved GitHub search section, filled by the last refresh.
+pub(super) fn render_saved_search_list(
+    ui: &mut egui::Ui,
+    name: &str,
+    hits: &[SearchHit],
+    error: Option<&str>,
+    filter: &SearchFilter,
+    render_state: &NotificationRenderState<'_>,
+    highlight: bool,
+) -> bool {
+    if let Some(error) = error {
+        ui.colored_label(ui.visuals().error_fg_color, format!("{name}: {error}"));
+    }
+    let list = SearchList {
+        title: name,
+        detail_heading: Some("State"),
+        empty_label: "Nothing matches this search.",
+        rows: hits
+            .iter()
+            .map(|hit| SearchRow {
+                repo: &hit.repo,
+                title: &hit.title,
+                url: &hit.url,
+                updated_at: hit.updated_at,
+                detail: if hit.labels.is_empty() {
+                    hit.state.clone()
+                } else {
+                    format!("{} · {}", hit.state, hit.labels.join(", "))
+                },
+                person: None,
+                waiting_since: None,
+                key: None,
+            })
+            .collect(),
+        paging: None,
+    };
+    render_search_list(
+        ui,
+        &list,
+        filter,
+        render_state.pull_request_statuses,
+        render_state.settings,
+        highlight,
+    )
+    .highlight_seen
+}
+
+fn render_search_list(
+    ui: &mut egui::Ui,
+    list: &SearchList<'_>,
+    filter: &SearchFilter,
+    statuses: &PullRequestStatusQueue,
+    settings: &AppSettings,
+    highlight: bool,
+) -> SearchListResponse {
+    let rows: Vec<_> = list
+        .rows
+        .iter()
+        .filter(|row| filter.matches_any(&[row.repo, row.title, row.detail.as_str()]))
+        .collect();
+    let paging = list.paging.as_ref().filter(|paging| paging.truncated());
+    let count = match paging {
+        Some(paging) => format!(
+            "{}, {} of {} loaded",
+            rows.len(),
+            paging.fetched,
+            paging.total
+        ),
+        None => rows.len().to_string(),
+    };
+    let mut heading = RichText::new(format!("{} ({count})", list.title)).strong();
+    if highlight {
+        heading = heading.color(ui.visuals().warn_fg_color);
+    }
+    let response = egui::CollapsingHeader::new(heading)
+        .id_salt(("search-list", list.title))
+        .default_open(highlight)
+        .show(ui, |section| {
+            if list.rows.is_empty() {
+                section.weak(list.empty_label);
+            } else if rows.is_empty() {
+                section.weak("No matches for current search.");
+            } else {
+                draw_search_table(section, list, &rows, statuses, settings);
+            }
+            let paging = paging?;
+            if paging.loading {
+                section.spinner();
+                None
+            } else {
+                let remaining = paging.total - paging.fetched as u64;
+                section
+                    .button(format!("Load more ({remaining} not loaded)"))
+                    .clicked()
+                    .then_some(paging.kind)
+            }
+        });
+    SearchListResponse {
+        highlight_seen: response.body_returned.is_some() && highlight,
+        load_more: response.body_returned.flatten(),
+    }
+}
+
+fn search_lists(inbox: &InboxSnapshot) -> [SearchList<'_>; 3] {
+    let shown: HashSet<_> = inbox
+        .notifications
+        .iter()
+        .filter_map(|item| Some((item.repo.clone(), item.thread_number()?)))
+        .collect();
+    let unseen = |key: &Option<PullRequestKey>| key.as_ref().is_none_or(|key| !shown.contains(key));
+
+    let re_requested = requested_reviews(inbox, true);
+    let is_re_requested =
+        |key: &Option<PullRequestKey>| key.as_ref().is_some_and(|key| re_requested.contains(key));
+    let mut review_requests: Vec<_> = inbox
+        .review_requests
+        .iter()
+        .map(|request| {
+            let key = request.pull_request_key();
+            let requester = request
+                .requested_by
+                .as_deref()
+                .map(|login| format!("@{login}"))
+                .unwrap_or_default();
+            SearchRow {
+                repo: &request.repo,
+                title: &request.title,
+                url: &request.url,
+                updated_at: request.updated_at,
+                detail: if is_re_requested(&key) {
+                    format!("Re-requested {requester}").trim_end().to_owned()
+                } else {
+                    requester
+                },
+                person: request.requested_by.as_deref(),
+                waiting_since: request.requested_at,
+                key,
+            }
+        })
+        .filter(|row| unseen(&row.key))
+        .collect();
+    review_requests.sort_by_key(|row| !is_re_requested(&row.key));
+    let mentions = inbox
+        .mentions
+        .iter()
+        .map(|mention| SearchRow {
+            repo: &mention.repo,
+            title: &mention.title,
+            url: &mention.url,
+            updated_at: mention.updated_at,
+            detail: mention.kind.label().to_owned(),
+            person: None,
+            waiting_since: None,
+            key: mention.thread_key(),
+        })
+        .filter(|row| unseen(&row.key))
+        .collect();
+    let recent_reviews = inbox
+        .recent_reviews
+        .iter()
+        .map(|review| SearchRow {
+            repo: &review.repo,
+            title: &review.title,
+            url: &review.url,
+            updated_at: review.updated_at,
+            detail: review.state.clone(),
+            person: None,
+            waiting_since: None,
+            key: review.pull_request_key(),
+        })
+        .filter(|row| unseen(&row.key))
+        .collect();
+
+    [
+        SearchList {
+            title: "Requested reviews (search)",
+            detail_heading: Some("Requested by"),
+            empty_label: "Nothing beyond what the notifications show.",
+            rows: review_requests,
+            paging: Some(SearchPaging {
+                kind: SectionKind::ReviewRequests,
+                fetched: inbox.review_requests.len(),
+                total: inbox.search_totals.review_requests,
+                loading: false,
+            }),
+        },
+        SearchList {
+            title: "Mentions (search)",
+            detail_heading: Some("Type"),
+            empty_label: "Nothing beyond what the notifications show.",
+            rows: mentions,
+            paging: Some(SearchPaging {
+                kind: SectionKind::Mentions,
+                fetched: inbox.mentions.len(),
+                total: inbox.search_totals.mentions,
+                loading: false,
+            }),
+        },
+        SearchList {
+            title: "Reviewed by me",
+            detail_heading: Some("State"),
+            empty_label: "Nothing beyond what the notifications show.",
+            rows: recent_reviews,
+            paging: None,
+        },
+    ]
+}
+
+fn assigned_issue_list(inbox: &InboxSnapshot) -> SearchList<'_> {
+    SearchList {
+        title: SectionKind::Assigned.title(),
+        detail_heading: Some("Labels"),
+        empty_label: "No open issues are assigned to you.",
+        rows: inbox
+            .assigned_issues
+            .iter()
+            .map(|issue| SearchRow {
+                repo: &issue.repo,
+                title: &issue.title,
+                url: &issue.url,
+                updated_at: issue.updated_at,
+                detail: issue.labels.join(", "),
+                person: None,
+                waiting_since: None,
+                key: None,
+            })
+            .collect(),
+        paging: None,
+    }
+}
+
+fn authored_pull_request_list(inbox: &InboxSnapshot) -> SearchList<'_> {
+    SearchList {
+        title: SectionKind::AuthoredPullRequests.title(),
+        detail_heading: Some("State"),
+        empty_label: "You have no open pull requests.",
+        rows: inbox
+            .authored_pull_requests
+            .iter()
+            .map(|pull_request| SearchRow {
+                repo: &pull_request.repo,
+                title: &pull_request.title,
+                url: &pull_request.url,
+                updated_at: pull_request.updated_at,
+                detail: {
+                    let state = if pull_request.draft {
+                        "Draft"
+                    } else {
+                        "Ready for review"
+                    };
+                    if pull_request.labels.is_empty() {
+                        state.to_owned()
+                    } else {
+                        format!("{state} · {}", pull_request.labels.join(", "))
+                    }
+                },
+                person: None,
+                waiting_since: None,
+                key: Some(pull_request.pull_request_key()),
+            })
+            .collect(),
+        paging: None,
+    }
+}
+
+fn draw_search_table(
+    ui: &mut egui::Ui,
+    list: &SearchList<'_>,
+    rows: &[&SearchRow<'_>],
+    statuses: &PullRequestStatusQueue,
+    settings: &AppSettings,
+) {
+    let colors =
+        status_colors(settings.status_palette, ui.visuals()).with_updated(settings.updated_color);
+    let pull_requests = rows.iter().any(|row| row.key.is_some());
+    egui::ScrollArea::horizontal()
+        .id_salt(("search-table", list.title))
+        .auto_shrink([false, true])
+        .show(ui, |ui| {
+            let mut table = TableBuilder::new(ui)
+                .id_salt(list.title)
+                .striped(true)
+                .column(Column::initial(120.0).resizable(true))
+                .column(Column::remainder().at_least(140.0));
+            if list.detail_heading.is_some() {
+                table = table.column(Column::initial(110.0).resizable(true));
+            }
+            if pull_requests {
+                table = table.column(Column::initial(130.0).resizable(true));
+            }
+            table
+                .column(Column::initial(130.0))
+                .header(20.0, |mut header| {
+                    header.col(|ui| {
+                        ui.strong("Repository");
+                    });
+                    header.col(|ui| {
+                        ui.strong("Title");
+                    });
+                    if let Some(detail_heading) = list.detail_heading {
+                        header.col(|ui| {
+                            ui.strong(detail_heading);
+                        });
+                    }
+                    if pull_requests {
+                        header.col(|ui| {
+                            ui.strong("Pull request");
+                        });
+                    }
+                    header.col(|ui| {
+                        ui.strong("Updated");
+                    });
+                })
+                .body(|mut body| {
+                    for row in rows {
+                        body.row(22.0, |mut table_row| {
+                            table_row.col(|ui| {
+                                ui.label(row.repo);
+                            });
+                            table_row.col(|ui| {
+                                ui.hyperlink_to(row.title, row.url);
+                            });
+                            if list.detail_heading.is_some() {
+                                table_row.col(|ui| {
+                                    ui.horizontal(|cell| {
+                                        if let Some(person) = row.person {
+                                            avatar(cell, row.url, person, settings);
+                                        }
+                                        cell.label(&row.detail);
+                                        if let Some(since) = row.waiting_since {
+                                            review_wait_badge(
+                                                cell,
+                                                since,
+                                                settings.review_wait_hours,
+                                            );
+                                        }
+                                    });
+                                });
+                            }
+                            if pull_requests {
+                                table_row.col(|ui| {
+                                    ui.horizontal(|badges| {
+                                        if let Some(status) =
+                                            row.key.as_ref().and_then(|key| statuses.status(key))
+                                        {
+                                            pull_request_badges(badges, status, colors);
+                                        }
+                                    });
+                                });
+                            }
+                            table_row.col(|ui| {
+                                ui.label(format_timestamp(
+                                    row.updated_at,
+                                    settings.timestamp_style,
+                                    "%Y-%m-%d %H:%M",
+                                ))
+                                .on_hover_text(
+                                    format_local_timestamp(row.updated_at, FULL_TIMESTAMP_FORMAT),
+                                );
+                            });
+                        });
+                    }
+                });
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::search_lists;
+    use crate::domain::{
+        InboxSnapshot, MentionKind, MentionThread, NotificationItem, ReviewRequest, ReviewSummary,
+        SearchTotals, test_support,
+    };
+
+    #[test]
+    fn search_lists_leave_out_threads_shown_as_notifications() {
+        let now = Utc::now();
+        let inbox = InboxSnapshot {
+            notifications: vec![NotificationItem {
+                title: String::from("Fix login"),
+                url: Some(String::from("https://github.com/acme/app/pull/7")),
+                reason: String::from("review_requested"),
+                updated_at: now,
+                ..test_support::notification("1")
+            }],
+            review_requests: ["7", "8"]
+                .into_iter()
+                .map(|number| ReviewRequest {
+                    _id: 1,
+                    repo: String::from("acme/app"),
+                    title: format!("PR {number}"),
+                    url: format!("https://github.com/acme/app/pull/{number}"),
+                    updated_at: now,
+                    requested_by: Some(String::from("trinity")),
+                    requested_at: None,
+                })
+                .collect(),
+            mentions: vec![MentionThread {
+                _id: 2,
+                repo: String::from("acme/app"),
+                title: String::from("Crash on start"),
+                url: String::from("https://github.com/acme/app/issues/3"),
+                updated_at: now,
+                kind: MentionKind::Issue,
+            }],
+            recent_reviews: vec![ReviewSummary {
+                _id: 3,
+                repo: String::from("acme/app"),
+                title: String::from("Fix login"),
+                url: String::from("https://github.com/acme/app/pull/7"),
+                updated_at: now,
+                state: String::from("open"),
+            }],
+            assigned_issues: Vec::new(),
+            authored_pull_requests: Vec::new(),
+            pull_request_statuses: Vec::new(),
+            fetched_at: now,
+            rate_limit: None,
+            search_totals: SearchTotals {
+                review_requests: 150,
+                mentions: 1,
+            },
+            unread_threads: None,
+        };
+
+        let [review_requests, mentions, recent_reviews] = search_lists(&inbox);
+
+        assert_eq!(review_requests.rows.len(), 1);
+        assert_eq!(review_requests.rows[0].title, "PR 8");
+        assert_eq!(review_requests.rows[0].detail, "@trinity");
+        assert_eq!(mentions.rows.len(), 1);
+        assert_eq!(mentions.rows[0].detail, "Issue");
+        assert!(recent_reviews.rows.is_empty());
+        assert!(
+            review_requests
+                .paging
+                .is_some_and(|paging| paging.truncated())
+        );
+        assert!(!mentions.paging.is_some_and(|paging| paging.truncated()));
+    }
+}
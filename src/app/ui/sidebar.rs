@@ -1,6 +1,6 @@
 use eframe::egui::{self, RichText};
 
-use super::super::{notification_state::section_stats, state::AccountState};
+use super::super::{notification_state::section_stats, rules::RuleSet, state::AccountState};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub(in crate::app) struct AccountOverview {
@@ -64,9 +64,12 @@ pub(in crate::app) fn render_tracked_account_badges(
     }
 }
 
-pub(in crate::app) fn account_overview(account: &AccountState) -> Option<AccountOverview> {
+pub(in crate::app) fn account_overview(
+    account: &AccountState,
+    rules: &RuleSet,
+) -> Option<AccountOverview> {
     account.inbox.as_ref().map(|inbox| {
-        let stats = section_stats(inbox);
+        let stats = section_stats(inbox, rules);
         AccountOverview {
             new_notifications: account.new_notification_ids.len(),
             unseen: stats.inbox.unseen,
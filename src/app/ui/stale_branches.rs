@@ -0,0 +1,45 @@
+use eframe::egui::{self, RichText};
+
+use super::super::{state::AccountState, time::format_local_timestamp};
+
+/// Quiet pull requests and unopened branches of every account, each with a
+/// way to hide it for the rest of the session.
+pub(in crate::app) fn render_stale_branches(ui: &mut egui::Ui, accounts: &mut [AccountState]) {
+    let count: usize = accounts
+        .iter()
+        .map(|account| account.stale_branches.visible().count())
+        .sum();
+    if count == 0 {
+        return;
+    }
+    egui::CollapsingHeader::new(RichText::new(format!("Stale branches ({count})")).strong())
+        .id_salt("stale-branches")
+        .default_open(true)
+        .show(ui, |section| {
+            section.small("Close them, or push to revive them.");
+            for account in accounts.iter_mut() {
+                let state = &mut account.stale_branches;
+                let mut dismissed = Vec::new();
+                for branch in state.visible() {
+                    section.horizontal_wrapped(|row| {
+                        row.label(RichText::new(&branch.repo).weak());
+                        row.hyperlink_to(&branch.title, &branch.url);
+                        row.small(if branch.has_pull_request {
+                            "open pull request"
+                        } else {
+                            "no pull request"
+                        });
+                        row.small(format!(
+                            "last activity {}",
+                            format_local_timestamp(branch.last_activity, "%Y-%m-%d")
+                        ));
+                        if row.small_button("Dismiss").clicked() {
+                            dismissed.push(branch.url.clone());
+                        }
+                    });
+                }
+                state.dismissed.extend(dismissed);
+            }
+        });
+    ui.add_space(8.0);
+}
@@ -0,0 +1,121 @@
+use eframe::egui::{self, Align2, Color32, FontId, Vec2};
+
+use crate::domain::{AppSettings, NotificationItem, SwipeAction};
+
+use super::super::AccountAction;
+
+/// A touch drag must travel this far sideways to count as a swipe.
+const SWIPE_MIN_DISTANCE: f32 = 80.0;
+const SWIPE_HINT_COLOR: Color32 = Color32::from_rgba_premultiplied(60, 110, 200, 60);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum SwipeDirection {
+    Left,
+    Right,
+}
+
+/// A drag that is long enough and mostly horizontal.
+fn swipe_direction(delta: Vec2) -> Option<SwipeDirection> {
+    if delta.x.abs() < SWIPE_MIN_DISTANCE || delta.y.abs() * 2.0 > delta.x.abs() {
+        return None;
+    }
+    Some(if delta.x > 0.0 {
+        SwipeDirection::Right
+    } else {
+        SwipeDirection::Left
+    })
+}
+
+fn swipe_setting(settings: &AppSettings, direction: SwipeDirection) -> SwipeAction {
+    match direction {
+        SwipeDirection::Right => settings.swipe_right,
+        SwipeDirection::Left => settings.swipe_left,
+    }
+}
+
+/// Tracks a touch swipe that started on `row`, painting what it will do
+/// while the finger is down and returning the action once it lifts. Mouse
+/// drags never count, since egui only reports touches from touch screens.
+pub(super) fn swipe_action(
+    row: &egui::Response,
+    item: &NotificationItem,
+    settings: &AppSettings,
+) -> Option<AccountAction> {
+    let id = egui::Id::new(("notification-swipe", &item.thread_id));
+    let (touching, origin, latest) = row.ctx.input(|input| {
+        (
+            input.any_touches(),
+            input.pointer.press_origin(),
+            input.pointer.latest_pos(),
+        )
+    });
+    if touching {
+        // egui forgets the press origin on release, so the last delta is
+        // kept until the finger lifts.
+        if let (Some(origin), Some(latest)) = (origin, latest)
+            && row.rect.contains(origin)
+        {
+            let delta = latest - origin;
+            row.ctx.data_mut(|data| data.insert_temp(id, delta));
+            if let Some(direction) = swipe_direction(delta) {
+                paint_hint(row, direction, swipe_setting(settings, direction));
+            }
+        }
+        return None;
+    }
+    let delta: Vec2 = row.ctx.data_mut(|data| data.remove_temp(id))?;
+    let thread_id = item.thread_id.clone();
+    match swipe_setting(settings, swipe_direction(delta)?) {
+        SwipeAction::Nothing => None,
+        SwipeAction::MarkRead => Some(AccountAction::Read(thread_id)),
+        SwipeAction::MarkDone => Some(AccountAction::Done(thread_id)),
+        SwipeAction::Snooze => Some(AccountAction::Remind(thread_id)),
+    }
+}
+
+fn paint_hint(row: &egui::Response, direction: SwipeDirection, action: SwipeAction) {
+    if action == SwipeAction::Nothing {
+        return;
+    }
+    let rect = row.rect;
+    let painter = egui::Painter::new(row.ctx.clone(), row.layer_id, rect);
+    painter.rect_filled(rect, 4.0, SWIPE_HINT_COLOR);
+    let (anchor, pos) = match direction {
+        SwipeDirection::Right => (
+            Align2::LEFT_CENTER,
+            rect.left_center() + egui::vec2(8.0, 0.0),
+        ),
+        SwipeDirection::Left => (
+            Align2::RIGHT_CENTER,
+            rect.right_center() - egui::vec2(8.0, 0.0),
+        ),
+    };
+    painter.text(
+        pos,
+        anchor,
+        action.label(),
+        FontId::proportional(14.0),
+        row.ctx.style().visuals.strong_text_color(),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use eframe::egui::vec2;
+
+    use super::{SwipeDirection, swipe_direction};
+
+    #[test]
+    fn only_long_horizontal_drags_are_swipes() {
+        assert_eq!(
+            swipe_direction(vec2(120.0, 10.0)),
+            Some(SwipeDirection::Right)
+        );
+        assert_eq!(
+            swipe_direction(vec2(-90.0, -30.0)),
+            Some(SwipeDirection::Left)
+        );
+        assert_eq!(swipe_direction(vec2(40.0, 0.0)), None);
+        assert_eq!(swipe_direction(vec2(100.0, 80.0)), None);
+    }
+}
@@ -0,0 +1,46 @@
+use eframe::egui::{self, RichText};
+
+use super::super::{state::ThreadTimeline, time::format_local_timestamp};
+
+/// Compact list of the thread's latest events, newest first. Returns true
+/// when the user asked for a reload.
+pub(in crate::app) fn render_thread_timeline(ui: &mut egui::Ui, timeline: &ThreadTimeline) -> bool {
+    let mut reload = false;
+    ui.horizontal(|row| {
+        row.label(RichText::new("Recent activity").strong());
+        if timeline.is_loading() {
+            row.spinner();
+        } else if row.small_button("Reload").clicked() {
+            reload = true;
+        }
+    });
+
+    match &timeline.events {
+        None => {
+            ui.weak("Loading timeline…");
+        }
+        Some(Err(err)) => {
+            ui.colored_label(ui.visuals().error_fg_color, err);
+        }
+        Some(Ok(events)) if events.is_empty() => {
+            ui.weak("No comments, reviews, pushes or label changes yet.");
+        }
+        Some(Ok(events)) => {
+            egui::ScrollArea::vertical()
+                .id_salt(("thread-timeline", &timeline.thread_id))
+                .max_height(180.0)
+                .show(ui, |list| {
+                    for event in events {
+                        list.horizontal_wrapped(|row| {
+                            row.small(format_local_timestamp(event.at, "%m-%d %H:%M"));
+                            if let Some(actor) = &event.actor {
+                                row.label(RichText::new(format!("@{actor}")).strong());
+                            }
+                            row.label(&event.summary);
+                        });
+                    }
+                });
+        }
+    }
+    reload
+}
@@ -0,0 +1,160 @@
+use eframe::egui::{self, RichText};
+
+use crate::domain::{AppSettings, Experiment};
+
+const CHANGELOG: &str = include_str!("../../../CHANGELOG.md");
+
+/// One `## <version>` block of the changelog.
+#[derive(Debug, PartialEq, Eq)]
+struct ChangelogSection<'a> {
+    version: &'a str,
+    lines: Vec<&'a str>,
+}
+
+/// Changelog entries released since the version the user last saw.
+pub(in crate::app) struct WhatsNew {
+    sections: Vec<ChangelogSection<'static>>,
+}
+
+impl WhatsNew {
+    /// `None` when there is nothing new since `last_seen`.
+    pub(in crate::app) fn since(last_seen: Option<&str>) -> Option<Self> {
+        let sections = unseen_sections(parse_changelog(CHANGELOG), last_seen);
+        (!sections.is_empty()).then_some(Self { sections })
+    }
+}
+
+/// Changes the user made from the window's toggles.
+#[derive(Default)]
+pub(in crate::app) struct WhatsNewOutcome {
+    pub(in crate::app) settings_changed: bool,
+    pub(in crate::app) closed: bool,
+}
+
+/// Newest sections first, as written in the file.
+fn parse_changelog(raw: &str) -> Vec<ChangelogSection<'_>> {
+    let mut sections: Vec<ChangelogSection<'_>> = Vec::new();
+    for line in raw.lines() {
+        if let Some(version) = line.strip_prefix("## ") {
+            sections.push(ChangelogSection {
+                version: version.trim(),
+                lines: Vec::new(),
+            });
+        } else if let Some(section) = sections.last_mut()
+            && !line.trim().is_empty()
+        {
+            section.lines.push(line.trim_end());
+        }
+    }
+    sections
+}
+
+/// Sections above `last_seen`. A first run only gets the newest one rather
+/// than the whole history.
+fn unseen_sections<'a>(
+    mut sections: Vec<ChangelogSection<'a>>,
+    last_seen: Option<&str>,
+) -> Vec<ChangelogSection<'a>> {
+    match last_seen {
+        None => sections.truncate(1),
+        Some(seen) => {
+            let end = sections
+                .iter()
+                .position(|section| section.version == seen)
+                .unwrap_or(sections.len());
+            sections.truncate(end);
+        }
+    }
+    sections
+}
+
+/// One-time panel after an update. Closing it clears `view`; the caller
+/// records the current version as seen.
+pub(in crate::app) fn render_whats_new_window(
+    ctx: &egui::Context,
+    view: &mut Option<WhatsNew>,
+    settings: &mut AppSettings,
+    combined_inbox: &mut bool,
+) -> WhatsNewOutcome {
+    let mut outcome = WhatsNewOutcome::default();
+    let Some(whats_new) = view.as_ref() else {
+        return outcome;
+    };
+    let mut open = true;
+    let mut dismissed = false;
+    egui::Window::new("What's new")
+        .open(&mut open)
+        .collapsible(false)
+        .resizable(true)
+        .default_width(480.0)
+        .show(ctx, |ui| {
+            egui::ScrollArea::vertical()
+                .max_height(320.0)
+                .show(ui, |ui| {
+                    for section in &whats_new.sections {
+                        ui.heading(section.version);
+                        for line in &section.lines {
+                            render_line(ui, line);
+                        }
+                        ui.add_space(6.0);
+                    }
+                });
+            ui.separator();
+            ui.strong("Try it out");
+            for experiment in Experiment::ALL {
+                let mut enabled = settings.experiment_enabled(experiment);
+                if ui
+                    .checkbox(&mut enabled, experiment.label())
+                    .on_hover_text(experiment.description())
+                    .changed()
+                {
+                    settings.set_experiment(experiment, enabled);
+                    outcome.settings_changed = true;
+                }
+            }
+            ui.checkbox(combined_inbox, "Combined inbox")
+                .on_hover_text("Show every account's notifications in one table.");
+            ui.add_space(6.0);
+            dismissed = ui.button("Got it").clicked();
+        });
+    if !open || dismissed {
+        *view = None;
+        outcome.closed = true;
+    }
+    outcome
+}
+
+fn render_line(ui: &mut egui::Ui, line: &str) {
+    if let Some(heading) = line.strip_prefix("### ") {
+        ui.label(RichText::new(heading).strong());
+    } else if let Some(bullet) = line.trim_start().strip_prefix("- ") {
+        ui.label(format!("• {bullet}"));
+    } else {
+        ui.label(line);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CHANGELOG, parse_changelog, unseen_sections};
+
+    #[test]
+    fn unseen_sections_stop_at_the_last_seen_version() {
+        let raw = "# Changelog\n\n## 0.3.0\n\n- Rules\n\n## 0.2.0\n- Chips\n\n## 0.1.0\n- First\n";
+        let versions = |last_seen| {
+            unseen_sections(parse_changelog(raw), last_seen)
+                .iter()
+                .map(|section| section.version)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(parse_changelog(raw)[0].lines, ["- Rules"]);
+        assert_eq!(versions(Some("0.1.0")), ["0.3.0", "0.2.0"]);
+        assert_eq!(versions(Some("0.3.0")), Vec::<&str>::new());
+        assert_eq!(versions(None), ["0.3.0"]);
+        assert_eq!(
+            parse_changelog(CHANGELOG)[0].version,
+            env!("CARGO_PKG_VERSION")
+        );
+    }
+}
@@ -0,0 +1,92 @@
+use std::time::{Duration, Instant};
+
+use reqwest::Client;
+
+use crate::{
+    domain::{Forge, GitHubAccount},
+    github::{self, FetchError},
+    task::Task,
+};
+
+const VIEWER_CHECK_INTERVAL: Duration = Duration::from_secs(30 * 60);
+
+/// Periodically asks GitHub who the token belongs to, catching tokens that
+/// were rotated or pasted into the wrong account.
+#[derive(Default)]
+pub(super) struct ViewerCheck {
+    job: Option<Task<Result<String, FetchError>>>,
+    last_started: Option<Instant>,
+    /// Login the token actually belongs to, when it is not the account's.
+    pub(super) mismatch: Option<String>,
+}
+
+impl ViewerCheck {
+    pub(super) fn maybe_start(&mut self, client: &Client, profile: &GitHubAccount) {
+        if self.job.is_some()
+            || profile.forge != Forge::GitHub
+            || self
+                .last_started
+                .is_some_and(|started| started.elapsed() < VIEWER_CHECK_INTERVAL)
+        {
+            return;
+        }
+        self.last_started = Some(Instant::now());
+
+        let client = client.clone();
+        let profile = profile.clone();
+        self.job = Some(Task::spawn(move |_| async move {
+            github::fetch_viewer_login(&client, &profile).await
+        }));
+    }
+
+    /// Errors are left to the regular refresh, which reports them already.
+    pub(super) fn poll(&mut self, login: &str) {
+        let Some(job) = &self.job else {
+            return;
+        };
+        match job.try_take() {
+            Some(Ok(Ok(viewer))) => {
+                self.mismatch = (!viewer.eq_ignore_ascii_case(login)).then_some(viewer);
+                self.job = None;
+            }
+            Some(_) => self.job = None,
+            None => {}
+        }
+    }
+
+    /// Checks again on the next frame, e.g. after the token changed.
+    pub(super) fn recheck(&mut self) {
+        self.last_started = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ViewerCheck;
+    use crate::task::Task;
+
+    fn finished_check(viewer: &str) -> ViewerCheck {
+        ViewerCheck {
+            job: Some(Task::finished(Ok(viewer.to_owned()))),
+            ..ViewerCheck::default()
+        }
+    }
+
+    #[test]
+    fn flags_tokens_that_belong_to_someone_else() {
+        let mut check = finished_check("trinity");
+        check.poll("neo");
+
+        assert_eq!(check.mismatch.as_deref(), Some("trinity"));
+        assert!(check.job.is_none());
+    }
+
+    #[test]
+    fn logins_match_case_insensitively() {
+        let mut check = finished_check("Neo");
+        check.mismatch = Some(String::from("trinity"));
+        check.poll("neo");
+
+        assert!(check.mismatch.is_none());
+    }
+}
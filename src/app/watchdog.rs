@@ -0,0 +1,100 @@
+use std::{fmt, time::Duration};
+
+use chrono::{DateTime, Utc};
+
+/// Results lost in a row (the task panicked or its channel closed) before an
+/// account's workers are restarted.
+const LOST_RESULTS_BEFORE_RESTART: u32 = 3;
+/// A job pending for this many fetch timeouts is treated as stuck; the
+/// deadline itself should have ended it long before.
+const STUCK_AFTER_TIMEOUTS: u32 = 2;
+/// Restarts kept per account for the diagnostics list.
+const RESTART_HISTORY: usize = 5;
+
+/// Why an account's workers were restarted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum Stall {
+    /// A job had been pending this long.
+    StuckJob(Duration),
+    /// This many results in a row never arrived.
+    LostResults(u32),
+}
+
+impl fmt::Display for Stall {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::StuckJob(pending) => {
+                write!(f, "a job was stuck for {}s", pending.as_secs())
+            }
+            Self::LostResults(count) => write!(f, "{count} results in a row were lost"),
+        }
+    }
+}
+
+/// Notices an account whose background jobs stopped making progress.
+#[derive(Default)]
+pub(super) struct Watchdog {
+    lost_in_a_row: u32,
+    /// Most recent first.
+    pub(super) restarts: Vec<(DateTime<Utc>, Stall)>,
+}
+
+impl Watchdog {
+    pub(super) fn record_result(&mut self, delivered: bool) {
+        self.lost_in_a_row = if delivered { 0 } else { self.lost_in_a_row + 1 };
+    }
+
+    /// `oldest_job` is how long the longest-running job has been pending.
+    pub(super) fn check(
+        &self,
+        oldest_job: Option<Duration>,
+        fetch_timeout: Duration,
+    ) -> Option<Stall> {
+        if self.lost_in_a_row >= LOST_RESULTS_BEFORE_RESTART {
+            return Some(Stall::LostResults(self.lost_in_a_row));
+        }
+        oldest_job
+            .filter(|pending| *pending > fetch_timeout * STUCK_AFTER_TIMEOUTS)
+            .map(Stall::StuckJob)
+    }
+
+    pub(super) fn record_restart(&mut self, stall: Stall, at: DateTime<Utc>) {
+        self.lost_in_a_row = 0;
+        self.restarts.insert(0, (at, stall));
+        self.restarts.truncate(RESTART_HISTORY);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use chrono::Utc;
+
+    use super::{Stall, Watchdog};
+
+    #[test]
+    fn stuck_jobs_and_repeated_losses_trigger_a_restart() {
+        let timeout = Duration::from_secs(60);
+        let mut watchdog = Watchdog::default();
+
+        assert_eq!(watchdog.check(Some(Duration::from_secs(90)), timeout), None);
+        assert_eq!(
+            watchdog.check(Some(Duration::from_secs(121)), timeout),
+            Some(Stall::StuckJob(Duration::from_secs(121)))
+        );
+
+        watchdog.record_result(false);
+        watchdog.record_result(false);
+        watchdog.record_result(true);
+        watchdog.record_result(false);
+        watchdog.record_result(false);
+        assert_eq!(watchdog.check(None, timeout), None);
+        watchdog.record_result(false);
+        assert_eq!(watchdog.check(None, timeout), Some(Stall::LostResults(3)));
+
+        watchdog.record_restart(Stall::LostResults(3), Utc::now());
+        assert_eq!(watchdog.check(None, timeout), None);
+        assert_eq!(watchdog.restarts.len(), 1);
+    }
+}
@@ -0,0 +1,186 @@
+use std::{
+    fs,
+    io::{self, BufRead, BufReader, Write},
+    net::{Ipv4Addr, TcpListener, TcpStream},
+    path::Path,
+    sync::mpsc::{self, Receiver},
+    thread,
+    time::Duration,
+};
+
+pub const SCHEME: &str = "reminder://";
+const PORT_FILE: &str = "deeplink.port";
+const FORWARD_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Target of a `reminder://` URL handed to the app by the OS or another tool.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DeepLink {
+    /// `reminder://thread/<id>[?account=<login>]`
+    Thread {
+        thread_id: String,
+        login: Option<String>,
+    },
+    /// `reminder://account/<login>`
+    Account { login: String },
+}
+
+impl DeepLink {
+    pub fn parse(url: &str) -> Option<Self> {
+        let rest = url.trim().strip_prefix(SCHEME)?;
+        let (path, query) = rest.split_once('?').unwrap_or((rest, ""));
+        let (kind, target) = path.trim_end_matches('/').split_once('/')?;
+        if target.is_empty() || target.contains('/') {
+            return None;
+        }
+        match kind {
+            "thread" => Some(Self::Thread {
+                thread_id: target.to_owned(),
+                login: query.split('&').find_map(|pair| {
+                    pair.strip_prefix("account=")
+                        .filter(|login| !login.is_empty())
+                        .map(str::to_owned)
+                }),
+            }),
+            "account" => Some(Self::Account {
+                login: target.to_owned(),
+            }),
+            _ => None,
+        }
+    }
+
+    fn to_url(&self) -> String {
+        match self {
+            Self::Thread {
+                thread_id,
+                login: Some(login),
+            } => format!("{SCHEME}thread/{thread_id}?account={login}"),
+            Self::Thread {
+                thread_id,
+                login: None,
+            } => format!("{SCHEME}thread/{thread_id}"),
+            Self::Account { login } => format!("{SCHEME}account/{login}"),
+        }
+    }
+}
+
+/// Hands `link` to an already running instance that uses `dir`. Fails when
+/// no instance is listening, in which case the caller starts the app itself.
+pub fn forward(dir: &Path, link: &DeepLink) -> io::Result<()> {
+    let port: u16 = fs::read_to_string(dir.join(PORT_FILE))?
+        .trim()
+        .parse()
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "invalid deep link port"))?;
+    let mut stream =
+        TcpStream::connect_timeout(&(Ipv4Addr::LOCALHOST, port).into(), FORWARD_TIMEOUT)?;
+    writeln!(stream, "{}", link.to_url())
+}
+
+/// Accepts links forwarded by later launches on a loopback port recorded in
+/// `dir`, so only the most recent instance receives them.
+pub fn listen(dir: &Path) -> io::Result<Receiver<DeepLink>> {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))?;
+    fs::write(
+        dir.join(PORT_FILE),
+        listener.local_addr()?.port().to_string(),
+    )?;
+
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let mut line = String::new();
+            if BufReader::new(stream).read_line(&mut line).is_err() {
+                continue;
+            }
+            if let Some(link) = DeepLink::parse(&line)
+                && tx.send(link).is_err()
+            {
+                break;
+            }
+        }
+    });
+    Ok(rx)
+}
+
+/// Registers this executable as the `reminder://` handler for the current
+/// desktop user via a freedesktop `.desktop` entry.
+#[cfg(all(unix, not(target_os = "macos")))]
+pub fn register_url_scheme() -> io::Result<()> {
+    let home = std::env::var("HOME").map_err(|_| io::Error::other("HOME is not set"))?;
+    let exe = std::env::current_exe()?;
+    let applications = Path::new(&home).join(".local/share/applications");
+    fs::create_dir_all(&applications)?;
+    fs::write(
+        applications.join("reminder-url-handler.desktop"),
+        format!(
+            "[Desktop Entry]\nType=Application\nName=Reminder\nExec=\"{}\" %u\nNoDisplay=true\nMimeType=x-scheme-handler/reminder;\n",
+            exe.display()
+        ),
+    )?;
+    let status = std::process::Command::new("xdg-mime")
+        .args([
+            "default",
+            "reminder-url-handler.desktop",
+            "x-scheme-handler/reminder",
+        ])
+        .status()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other("xdg-mime could not register the handler"))
+    }
+}
+
+#[cfg(not(all(unix, not(target_os = "macos"))))]
+pub fn register_url_scheme() -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "register the reminder:// scheme through the app bundle or installer on this platform",
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DeepLink;
+
+    #[test]
+    fn parse_reads_thread_and_account_links() {
+        assert_eq!(
+            DeepLink::parse("reminder://thread/1234"),
+            Some(DeepLink::Thread {
+                thread_id: String::from("1234"),
+                login: None,
+            })
+        );
+        assert_eq!(
+            DeepLink::parse("reminder://thread/1234/?account=neo\n"),
+            Some(DeepLink::Thread {
+                thread_id: String::from("1234"),
+                login: Some(String::from("neo")),
+            })
+        );
+        assert_eq!(
+            DeepLink::parse("reminder://account/neo"),
+            Some(DeepLink::Account {
+                login: String::from("neo"),
+            })
+        );
+    }
+
+    #[test]
+    fn parse_rejects_unknown_links() {
+        assert_eq!(DeepLink::parse("https://github.com/acme"), None);
+        assert_eq!(DeepLink::parse("reminder://thread/"), None);
+        assert_eq!(DeepLink::parse("reminder://repo/acme"), None);
+        assert_eq!(DeepLink::parse("reminder://thread/1/2"), None);
+    }
+
+    #[test]
+    fn links_round_trip_through_their_url() {
+        let link = DeepLink::Thread {
+            thread_id: String::from("42"),
+            login: Some(String::from("neo")),
+        };
+
+        assert_eq!(DeepLink::parse(&link.to_url()), Some(link));
+    }
+}
@@ -1,20 +1,1035 @@
-use std::collections::BTreeMap;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::OnceLock,
+};
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, Timelike, Utc, Weekday};
 use serde::{Deserialize, Serialize};
 
 // Domain data structures shared across modules.
 
+pub const GITHUB_API_BASE: &str = "https://api.github.com";
+pub const GITHUB_WEB_BASE: &str = "https://github.com";
+pub const ENTERPRISE_API_PATH: &str = "/api/v3";
+const LOW_DATA_INTERVAL_FACTOR: u32 = 4;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReviewCommandSettings {
+    #[serde(default)]
+    pub env_vars: BTreeMap<String, String>,
+    #[serde(default)]
+    pub additional_args: Vec<String>,
+    #[serde(default)]
+    pub review_prompt_md_path: Option<String>,
+    #[serde(default)]
+    pub pr_description_md_path: Option<String>,
+}
+
+/// How the search box interprets its query.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchMode {
+    #[default]
+    Substring,
+    Regex,
+    /// Query characters must appear in order, not necessarily adjacent.
+    Fuzzy,
+}
+
+/// Read-state chip of [`NotificationChips`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReadStateChip {
+    #[default]
+    All,
+    Unread,
+    /// Read before, with activity since.
+    Updated,
+}
+
+impl ReadStateChip {
+    pub const ALL: [Self; 3] = [Self::All, Self::Unread, Self::Updated];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::All => "All",
+            Self::Unread => "Unread",
+            Self::Updated => "Updated",
+        }
+    }
+}
+
+/// Filter chips above a notification list. Empty sets match anything; the
+/// chips narrow the list before the search box does.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotificationChips {
+    pub reasons: BTreeSet<String>,
+    pub repos: BTreeSet<String>,
+    pub state: ReadStateChip,
+    /// Only threads in this personal stage; see [`ThreadStages`].
+    pub stage: Option<ThreadStage>,
+}
+
+impl NotificationChips {
+    pub fn is_active(&self) -> bool {
+        !self.reasons.is_empty()
+            || !self.repos.is_empty()
+            || self.state != ReadStateChip::All
+            || self.stage.is_some()
+    }
+
+    pub fn matches_stage(&self, stages: &ThreadStages, item: &NotificationItem) -> bool {
+        self.stage
+            .is_none_or(|stage| stages.stage(&item.thread_id) == stage)
+    }
+
+    pub fn matches(&self, item: &NotificationItem) -> bool {
+        (self.reasons.is_empty() || self.reasons.contains(&item.reason))
+            && (self.repos.is_empty() || self.repos.contains(&item.repo))
+            && match self.state {
+                ReadStateChip::All => true,
+                ReadStateChip::Unread => item.unread,
+                ReadStateChip::Updated => {
+                    item.last_read_at.is_some_and(|read| item.updated_at > read)
+                }
+            }
+    }
+}
+
+/// A column of the personal kanban a thread sits in. Threads start in
+/// [`ThreadStage::ToTriage`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ThreadStage {
+    #[default]
+    ToTriage,
+    WaitingOnAuthor,
+    WaitingOnMe,
+    Done,
+}
+
+impl ThreadStage {
+    pub const ALL: [Self; 4] = [
+        Self::ToTriage,
+        Self::WaitingOnAuthor,
+        Self::WaitingOnMe,
+        Self::Done,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::ToTriage => "To triage",
+            Self::WaitingOnAuthor => "Waiting on author",
+            Self::WaitingOnMe => "Waiting on me",
+            Self::Done => "Done",
+        }
+    }
+}
+
+/// Stages the user put threads in, keyed by thread id. Kept on this machine
+/// only; threads without an entry are to triage.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ThreadStages {
+    #[serde(default)]
+    pub entries: BTreeMap<String, ThreadStage>,
+}
+
+impl ThreadStages {
+    pub fn stage(&self, thread_id: &str) -> ThreadStage {
+        self.entries.get(thread_id).copied().unwrap_or_default()
+    }
+
+    /// Returns whether the stage changed.
+    pub fn set(&mut self, thread_id: String, stage: ThreadStage) -> bool {
+        let previous = if stage == ThreadStage::ToTriage {
+            self.entries.remove(&thread_id)
+        } else {
+            self.entries.insert(thread_id, stage)
+        };
+        previous.unwrap_or_default() != stage
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DoubleClickAction {
+    #[default]
+    Nothing,
+    OpenInBrowser,
+    MarkRead,
+    ShowDetails,
+}
+
+impl DoubleClickAction {
+    pub const ALL: [Self; 4] = [
+        Self::Nothing,
+        Self::OpenInBrowser,
+        Self::MarkRead,
+        Self::ShowDetails,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Nothing => "Do nothing",
+            Self::OpenInBrowser => "Open in browser",
+            Self::MarkRead => "Mark read",
+            Self::ShowDetails => "Show details",
+        }
+    }
+}
+
+/// What swiping a notification row on a touch screen does.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SwipeAction {
+    #[default]
+    Nothing,
+    MarkRead,
+    MarkDone,
+    /// Opens the reminder editor to bring the thread back later.
+    Snooze,
+}
+
+impl SwipeAction {
+    pub const ALL: [Self; 4] = [Self::Nothing, Self::MarkRead, Self::MarkDone, Self::Snooze];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Nothing => "Do nothing",
+            Self::MarkRead => "Mark read",
+            Self::MarkDone => "Done",
+            Self::Snooze => "Snooze (remind me later)",
+        }
+    }
+}
+
+/// Colors used for notification states and review badges.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StatusPalette {
+    #[default]
+    Standard,
+    /// Okabe–Ito colors that stay distinguishable with red-green color
+    /// blindness.
+    ColorBlind,
+}
+
+impl StatusPalette {
+    pub const ALL: [Self; 2] = [Self::Standard, Self::ColorBlind];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Standard => "Standard",
+            Self::ColorBlind => "Color-blind friendly",
+        }
+    }
+}
+
+/// How update times show up in notification lists.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimestampStyle {
+    /// "8 min ago", "yesterday", with the local time on hover.
+    #[default]
+    Relative,
+    Absolute,
+}
+
+impl TimestampStyle {
+    pub const ALL: [Self; 2] = [Self::Relative, Self::Absolute];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Relative => "Relative (8 min ago)",
+            Self::Absolute => "Absolute (local time)",
+        }
+    }
+}
+
+/// Which way panels and rows run.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LayoutDirection {
+    /// Right to left when the system locale is an RTL language.
+    #[default]
+    Auto,
+    LeftToRight,
+    RightToLeft,
+}
+
+impl LayoutDirection {
+    pub const ALL: [Self; 3] = [Self::Auto, Self::LeftToRight, Self::RightToLeft];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Auto => "Follow system language",
+            Self::LeftToRight => "Left to right",
+            Self::RightToLeft => "Right to left",
+        }
+    }
+}
+
+/// Open issues and pull requests carrying `label` anywhere in `org`, pinned
+/// above the dashboard whatever their notification state.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReleaseRadar {
+    pub org: String,
+    pub label: String,
+    /// Login whose token runs the search; the first GitHub account if empty.
+    pub account: String,
+}
+
+impl ReleaseRadar {
+    /// GitHub search query, or `None` until both org and label are set.
+    pub fn search_query(&self) -> Option<String> {
+        let org = self.org.trim().trim_start_matches('@');
+        let label = self.label.trim();
+        if org.is_empty() || label.is_empty() {
+            return None;
+        }
+        Some(format!(
+            "org:{org} label:\"{}\" is:open",
+            label.replace('"', "")
+        ))
+    }
+}
+
+/// How a [`SavedSearch`] finds its threads.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SavedSearchKind {
+    /// A local filter over the notification feed, e.g.
+    /// `repo:acme/* reason:mention is:unread`.
+    #[default]
+    Filter,
+    /// A raw GitHub issue search query, run on each refresh.
+    GitHubSearch,
+}
+
+impl SavedSearchKind {
+    pub const ALL: [Self; 2] = [Self::Filter, Self::GitHubSearch];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Filter => "Notification filter",
+            Self::GitHubSearch => "GitHub search",
+        }
+    }
+}
+
+/// A user-defined section shown after the built-in ones on every account.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SavedSearch {
+    pub name: String,
+    pub kind: SavedSearchKind,
+    pub query: String,
+}
+
+/// Where non-secret triage state is shared between machines.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncBackend {
+    #[default]
+    Off,
+    /// A secret gist owned by one of the accounts.
+    Gist,
+    /// A local clone of a git repository that is pulled and pushed.
+    GitRepo,
+}
+
+impl SyncBackend {
+    pub const ALL: [Self; 3] = [Self::Off, Self::Gist, Self::GitRepo];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Off => "Off",
+            Self::Gist => "Secret gist",
+            Self::GitRepo => "Git repository",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SyncSettings {
+    pub backend: SyncBackend,
+    /// Login whose token reads and writes the gist.
+    pub account: String,
+    /// Filled in once the first sync has created the gist.
+    pub gist_id: String,
+    /// Local clone of the sync repository, with a configured upstream.
+    pub repo_path: String,
+}
+
+/// How every HTTP client reaches the forges.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NetworkSettings {
+    /// `http://`, `https://` or `socks5://` proxy for all requests. Empty
+    /// falls back to the `HTTPS_PROXY`, `HTTP_PROXY` and `NO_PROXY`
+    /// environment variables.
+    pub proxy: String,
+    /// PEM file of extra certificate authorities to trust, such as a
+    /// corporate root.
+    pub ca_bundle: String,
+    /// Accepts any certificate. Opt-in only, for intercepting proxies that
+    /// cannot be trusted any other way.
+    pub skip_tls_verification: bool,
+    /// Seconds to establish a connection; 0 waits indefinitely.
+    pub connect_timeout_secs: u64,
+    /// Seconds a response may stall between reads; 0 waits indefinitely.
+    pub read_timeout_secs: u64,
+    /// Interval of TCP keep-alive probes; 0 turns them off.
+    pub keep_alive_secs: u64,
+    /// Seconds an idle pooled connection is kept for reuse; 0 keeps it
+    /// until the server closes it.
+    pub pool_idle_timeout_secs: u64,
+}
+
+impl NetworkSettings {
+    pub const DEFAULT: Self = Self {
+        proxy: String::new(),
+        ca_bundle: String::new(),
+        skip_tls_verification: false,
+        connect_timeout_secs: 10,
+        read_timeout_secs: 30,
+        keep_alive_secs: 60,
+        pool_idle_timeout_secs: 90,
+    };
+}
+
+impl Default for NetworkSettings {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// What a [`NotificationRule`] does to the threads it matches.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleAction {
+    /// Hide the thread from sections, counts and desktop alerts.
+    #[default]
+    Mute,
+    /// Keep the thread visible but never raise a desktop alert for it.
+    Silence,
+    /// Emphasize the thread's title.
+    Highlight,
+}
+
+impl RuleAction {
+    pub const ALL: [Self; 3] = [Self::Mute, Self::Silence, Self::Highlight];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Mute => "Mute",
+            Self::Silence => "No alerts",
+            Self::Highlight => "Highlight",
+        }
+    }
+}
+
+/// A user-defined rule. Empty conditions match anything, but a rule needs at
+/// least one condition to take effect.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NotificationRule {
+    /// Glob over `owner/name`, or over the name alone when it has no `/`.
+    #[serde(default)]
+    pub repo: String,
+    /// Exact notification reason, for example `ci_activity`.
+    #[serde(default)]
+    pub reason: String,
+    /// Regular expression searched in the thread title.
+    #[serde(default)]
+    pub title: String,
+    /// Only threads from this service; `None` matches every provider.
+    #[serde(default)]
+    pub provider: Option<Forge>,
+    #[serde(default)]
+    pub action: RuleAction,
+}
+
+/// Experimental subsystem that users can switch on or off under
+/// Preferences → Labs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Experiment {
+    ThreadTimeline,
+}
+
+impl Experiment {
+    pub const ALL: [Self; 1] = [Self::ThreadTimeline];
+
+    /// Stable name in the settings file.
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::ThreadTimeline => "thread_timeline",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::ThreadTimeline => "Thread timeline",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Self::ThreadTimeline => {
+                "Load the latest comments, reviews and pushes into the detail window."
+            }
+        }
+    }
+
+    /// New experiments should ship dark (`false`) until a user opts in; the
+    /// timeline predates Labs and stays on.
+    pub fn enabled_by_default(self) -> bool {
+        match self {
+            Self::ThreadTimeline => true,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RepoTier {
+    Critical,
+    #[default]
+    Normal,
+    Low,
+}
+
+impl RepoTier {
+    pub const ALL: [Self; 3] = [Self::Critical, Self::Normal, Self::Low];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Critical => "Critical",
+            Self::Normal => "Normal",
+            Self::Low => "Low",
+        }
+    }
+
+    /// Low-priority repositories never raise desktop alerts.
+    pub fn alerts(self) -> bool {
+        self != Self::Low
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AppSettings {
+    #[serde(default = "default_double_click_actions")]
+    pub double_click_actions: BTreeMap<String, DoubleClickAction>,
+    #[serde(default)]
+    pub double_click_fallback: DoubleClickAction,
+    /// A single click on a notification row opens it in the preview pane.
+    #[serde(default)]
+    pub preview_pane: bool,
+    #[serde(default = "default_swipe_right")]
+    pub swipe_right: SwipeAction,
+    #[serde(default = "default_swipe_left")]
+    pub swipe_left: SwipeAction,
+    #[serde(default)]
+    pub hide_bot_notifications: bool,
+    /// Shows how many threads were handled today when the window closes.
+    #[serde(default = "default_summary_on_exit")]
+    pub summary_on_exit: bool,
+    /// Hides the window to the tray on close instead of quitting, when a
+    /// tray is available.
+    #[serde(default = "default_close_to_tray")]
+    pub close_to_tray: bool,
+    /// Hours to hold back notifications per reason before they surface.
+    #[serde(default)]
+    pub auto_snooze_hours: BTreeMap<String, u32>,
+    #[serde(default = "default_desktop_alerts")]
+    pub desktop_alerts: bool,
+    /// Desktop alerts and the title badge only update within these local
+    /// hours; refreshing carries on outside them.
+    #[serde(default)]
+    pub alert_hours: Option<WorkingHours>,
+    /// More than this many threads from one repo in a single refresh are
+    /// collapsed into one summarized row and alert. Zero disables grouping.
+    #[serde(default = "default_storm_threshold")]
+    pub storm_threshold: usize,
+    /// A thread that keeps bumping alerts at most once per this many minutes.
+    #[serde(default = "default_alert_dedup_minutes")]
+    pub alert_dedup_minutes: u32,
+    /// Unread counts that raise a desktop alert when a section grows.
+    #[serde(default)]
+    pub section_alerts: SectionAlertThresholds,
+    /// Upper bound on pages followed per paginated GitHub listing.
+    #[serde(default = "default_max_fetch_pages")]
+    pub max_fetch_pages: usize,
+    /// Tries per request when GitHub times out or answers with a 5xx; one
+    /// means no retries.
+    #[serde(default = "default_fetch_attempts")]
+    pub fetch_attempts: u32,
+    /// Overall deadline for one inbox refresh, retries included. A refresh
+    /// that overruns it is abandoned so a hung connection cannot hold up
+    /// auto-refresh.
+    #[serde(default = "default_fetch_timeout_secs")]
+    pub fetch_timeout_secs: u64,
+    /// Seconds after marking a thread read or done during which a refresh
+    /// that still reports it unread is ignored, covering the API's
+    /// propagation lag. Zero turns the guard off.
+    #[serde(default = "default_action_settle_secs")]
+    pub action_settle_secs: u64,
+    /// Hours a review request may wait on me before it is shown as overdue.
+    /// Zero turns the aging colors off.
+    #[serde(default = "default_review_wait_hours")]
+    pub review_wait_hours: u32,
+    /// Sends a desktop reminder when a review request becomes overdue and
+    /// again each time its wait doubles.
+    #[serde(default)]
+    pub review_wait_reminders: bool,
+    /// Hours between automatic backups of the local triage state. Zero
+    /// turns scheduled backups off.
+    #[serde(default = "default_backup_interval_hours")]
+    pub backup_interval_hours: u32,
+    /// Days without activity after which my open pull requests and pushed
+    /// branches without one are listed as stale. Zero turns the scan off.
+    #[serde(default = "default_stale_branch_days")]
+    pub stale_branch_days: u32,
+    /// Repositories without an entry are [`RepoTier::Normal`].
+    #[serde(default)]
+    pub repo_tiers: BTreeMap<String, RepoTier>,
+    /// Stretches refresh intervals, skips enrichment fetches, and only pulls
+    /// unread notifications, for tethered or metered connections.
+    #[serde(default)]
+    pub low_data_mode: bool,
+    /// On battery at or below this percentage, refreshes slow down and skip
+    /// enrichment fetches. Zero disables power saving.
+    #[serde(default = "default_battery_saver_percent")]
+    pub battery_saver_percent: u8,
+    /// Pause polling and alerts after this many idle minutes or while the
+    /// screen is locked. Zero keeps polling regardless.
+    #[serde(default = "default_idle_pause_minutes")]
+    pub idle_pause_minutes: u32,
+    /// Also match search input against romanized titles.
+    #[serde(default)]
+    pub transliterate_search: bool,
+    /// List threads marked done in a collapsed section of their own.
+    #[serde(default)]
+    pub show_done_section: bool,
+    #[serde(default)]
+    pub status_palette: StatusPalette,
+    #[serde(default)]
+    pub timestamp_style: TimestampStyle,
+    /// Prefix notification titles with a shape per state, so unread and
+    /// updated threads do not rely on color alone.
+    #[serde(default)]
+    pub status_icons: bool,
+    /// sRGB accent for selections and links; `None` keeps egui's own.
+    #[serde(default)]
+    pub accent_color: Option<[u8; 3]>,
+    /// sRGB color of updated threads, in place of the palette's.
+    #[serde(default)]
+    pub updated_color: Option<[u8; 3]>,
+    /// Zoom on top of the display's own scale, in percent.
+    #[serde(default = "default_scale_percent")]
+    pub ui_scale_percent: u16,
+    /// Size of every text style relative to egui's defaults, in percent.
+    #[serde(default = "default_scale_percent")]
+    pub text_size_percent: u16,
+    /// Font file tried before the built-in and CJK fonts; empty for none.
+    #[serde(default)]
+    pub custom_font_path: String,
+    #[serde(default)]
+    pub layout_direction: LayoutDirection,
+    /// Labs overrides keyed by [`Experiment::key`]. Keys of retired
+    /// experiments are ignored.
+    #[serde(default)]
+    pub experiments: BTreeMap<String, bool>,
+    /// User rules that mute, silence or highlight matching threads.
+    #[serde(default)]
+    pub rules: Vec<NotificationRule>,
+    #[serde(default)]
+    pub sync: SyncSettings,
+    #[serde(default)]
+    pub network: NetworkSettings,
+    /// Custom sections, in display order.
+    #[serde(default)]
+    pub saved_searches: Vec<SavedSearch>,
+    #[serde(default)]
+    pub release_radar: ReleaseRadar,
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            double_click_actions: default_double_click_actions(),
+            double_click_fallback: DoubleClickAction::default(),
+            preview_pane: false,
+            swipe_right: default_swipe_right(),
+            swipe_left: default_swipe_left(),
+            hide_bot_notifications: false,
+            summary_on_exit: default_summary_on_exit(),
+            close_to_tray: default_close_to_tray(),
+            auto_snooze_hours: BTreeMap::new(),
+            desktop_alerts: default_desktop_alerts(),
+            alert_hours: None,
+            storm_threshold: default_storm_threshold(),
+            alert_dedup_minutes: default_alert_dedup_minutes(),
+            section_alerts: SectionAlertThresholds::default(),
+            max_fetch_pages: default_max_fetch_pages(),
+            fetch_attempts: default_fetch_attempts(),
+            fetch_timeout_secs: default_fetch_timeout_secs(),
+            action_settle_secs: default_action_settle_secs(),
+            review_wait_hours: default_review_wait_hours(),
+            review_wait_reminders: false,
+            backup_interval_hours: default_backup_interval_hours(),
+            stale_branch_days: default_stale_branch_days(),
+            repo_tiers: BTreeMap::new(),
+            low_data_mode: false,
+            battery_saver_percent: default_battery_saver_percent(),
+            idle_pause_minutes: default_idle_pause_minutes(),
+            transliterate_search: false,
+            show_done_section: false,
+            status_palette: StatusPalette::default(),
+            timestamp_style: TimestampStyle::default(),
+            status_icons: false,
+            accent_color: None,
+            updated_color: None,
+            ui_scale_percent: default_scale_percent(),
+            text_size_percent: default_scale_percent(),
+            custom_font_path: String::new(),
+            layout_direction: LayoutDirection::default(),
+            experiments: BTreeMap::new(),
+            rules: Vec::new(),
+            sync: SyncSettings::default(),
+            network: NetworkSettings::default(),
+            saved_searches: Vec::new(),
+            release_radar: ReleaseRadar::default(),
+        }
+    }
+}
+
+impl AppSettings {
+    pub fn double_click_action(&self, reason: &str) -> DoubleClickAction {
+        self.double_click_actions
+            .get(reason)
+            .copied()
+            .unwrap_or(self.double_click_fallback)
+    }
+
+    pub fn experiment_enabled(&self, experiment: Experiment) -> bool {
+        self.experiments
+            .get(experiment.key())
+            .copied()
+            .unwrap_or_else(|| experiment.enabled_by_default())
+    }
+
+    /// Only choices that differ from the default are stored, so changing a
+    /// default later reaches users who never touched the flag.
+    pub fn set_experiment(&mut self, experiment: Experiment, enabled: bool) {
+        if enabled == experiment.enabled_by_default() {
+            self.experiments.remove(experiment.key());
+        } else {
+            self.experiments
+                .insert(experiment.key().to_owned(), enabled);
+        }
+    }
+
+    pub fn repo_tier(&self, repo: &str) -> RepoTier {
+        self.repo_tiers.get(repo).copied().unwrap_or_default()
+    }
+
+    /// `power_saving` is set while the battery is low and stretches the
+    /// interval just like low-data mode does.
+    pub fn refresh_interval(
+        &self,
+        base: std::time::Duration,
+        power_saving: bool,
+    ) -> std::time::Duration {
+        if self.low_data_mode || power_saving {
+            base * LOW_DATA_INTERVAL_FACTOR
+        } else {
+            base
+        }
+    }
+
+    pub fn idle_pause_threshold(&self) -> Option<std::time::Duration> {
+        (self.idle_pause_minutes > 0)
+            .then(|| std::time::Duration::from_secs(u64::from(self.idle_pause_minutes) * 60))
+    }
+
+    pub fn alert_dedup_window(&self) -> chrono::Duration {
+        chrono::Duration::minutes(i64::from(self.alert_dedup_minutes))
+    }
+
+    pub fn auto_snooze_duration(&self, reason: &str) -> Option<chrono::Duration> {
+        self.auto_snooze_hours
+            .get(reason)
+            .copied()
+            .filter(|hours| *hours > 0)
+            .map(|hours| chrono::Duration::hours(i64::from(hours)))
+    }
+}
+
+fn default_desktop_alerts() -> bool {
+    true
+}
+
+fn default_summary_on_exit() -> bool {
+    true
+}
+
+fn default_close_to_tray() -> bool {
+    true
+}
+
+fn default_scale_percent() -> u16 {
+    100
+}
+
+fn default_storm_threshold() -> usize {
+    10
+}
+
+fn default_alert_dedup_minutes() -> u32 {
+    30
+}
+
+fn default_max_fetch_pages() -> usize {
+    10
+}
+
+fn default_fetch_attempts() -> u32 {
+    3
+}
+
+fn default_fetch_timeout_secs() -> u64 {
+    300
+}
+
+fn default_action_settle_secs() -> u64 {
+    60
+}
+
+fn default_review_wait_hours() -> u32 {
+    24
+}
+
+fn default_backup_interval_hours() -> u32 {
+    24
+}
+
+fn default_stale_branch_days() -> u32 {
+    14
+}
+
+fn default_battery_saver_percent() -> u8 {
+    25
+}
+
+fn default_idle_pause_minutes() -> u32 {
+    15
+}
+
+fn default_swipe_right() -> SwipeAction {
+    SwipeAction::MarkRead
+}
+
+fn default_swipe_left() -> SwipeAction {
+    SwipeAction::Snooze
+}
+
+fn default_double_click_actions() -> BTreeMap<String, DoubleClickAction> {
+    BTreeMap::from([
+        (
+            String::from("review_requested"),
+            DoubleClickAction::OpenInBrowser,
+        ),
+        (String::from("ci_activity"), DoubleClickAction::MarkRead),
+        (String::from("mention"), DoubleClickAction::ShowDetails),
+        (String::from("team_mention"), DoubleClickAction::ShowDetails),
+    ])
+}
+
+/// Long-lived activity records kept apart from account configuration.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActivityHistory {
+    #[serde(default)]
+    pub inbox_zero: InboxZeroStreak,
+    #[serde(default)]
+    pub handled: HandledLog,
+}
+
+/// Days of handled counts kept in [`HandledLog`].
+const HANDLED_LOG_DAYS: i64 = 30;
+
+/// What the user did with a thread they handled.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HandledKind {
+    Read,
+    Done,
+}
+
+/// Threads marked read and done on one local day.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HandledCounts {
+    #[serde(default)]
+    pub read: u32,
+    #[serde(default)]
+    pub done: u32,
+}
+
+impl HandledCounts {
+    pub fn total(&self) -> u32 {
+        self.read + self.done
+    }
+}
+
+/// Threads handled per local day over the last month.
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
-pub struct ReviewCommandSettings {
+pub struct HandledLog {
     #[serde(default)]
-    pub env_vars: BTreeMap<String, String>,
+    days: BTreeMap<NaiveDate, HandledCounts>,
+}
+
+impl HandledLog {
+    /// Counts one handled thread on `today`, forgetting days older than a
+    /// month.
+    pub fn record(&mut self, kind: HandledKind, today: NaiveDate) {
+        let counts = self.days.entry(today).or_default();
+        match kind {
+            HandledKind::Read => counts.read += 1,
+            HandledKind::Done => counts.done += 1,
+        }
+        let cutoff = today - chrono::Duration::days(HANDLED_LOG_DAYS);
+        self.days.retain(|day, _| *day > cutoff);
+    }
+
+    pub fn on(&self, day: NaiveDate) -> HandledCounts {
+        self.days.get(&day).copied().unwrap_or_default()
+    }
+}
+
+/// Consecutive local days on which every account reached inbox zero at least
+/// once.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InboxZeroStreak {
     #[serde(default)]
-    pub additional_args: Vec<String>,
+    pub last_zero_on: Option<NaiveDate>,
     #[serde(default)]
-    pub review_prompt_md_path: Option<String>,
+    pub days: u32,
+}
+
+impl InboxZeroStreak {
+    /// Records inbox zero for `today`. Returns true when the streak changed.
+    pub fn record(&mut self, today: NaiveDate) -> bool {
+        if self.last_zero_on == Some(today) {
+            return false;
+        }
+        let continues = self
+            .last_zero_on
+            .is_some_and(|last| last.succ_opt() == Some(today));
+        self.days = if continues { self.days + 1 } else { 1 };
+        self.last_zero_on = Some(today);
+        true
+    }
+
+    /// Streak length as of `today`; a day without inbox zero resets it.
+    pub fn current(&self, today: NaiveDate) -> u32 {
+        match self.last_zero_on {
+            Some(last) if last == today || last.succ_opt() == Some(today) => self.days,
+            _ => 0,
+        }
+    }
+}
+
+/// Follow-up the user scheduled on a notification, pull request or any
+/// pasted GitHub URL.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Reminder {
+    pub id: u64,
+    pub title: String,
+    pub url: String,
+    /// Account the reminder was created from, if any.
     #[serde(default)]
-    pub pr_description_md_path: Option<String>,
+    pub login: Option<String>,
+    #[serde(default)]
+    pub thread_id: Option<String>,
+    pub due_at: DateTime<Utc>,
+    /// Set once the desktop alert for the current `due_at` went out.
+    #[serde(default)]
+    pub alerted: bool,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReminderList {
+    #[serde(default)]
+    pub next_id: u64,
+    #[serde(default)]
+    pub reminders: Vec<Reminder>,
+}
+
+impl ReminderList {
+    pub fn add(
+        &mut self,
+        title: String,
+        url: String,
+        login: Option<String>,
+        thread_id: Option<String>,
+        due_at: DateTime<Utc>,
+    ) -> u64 {
+        self.next_id += 1;
+        self.reminders.push(Reminder {
+            id: self.next_id,
+            title,
+            url,
+            login,
+            thread_id,
+            due_at,
+            alerted: false,
+        });
+        self.reminders.sort_by_key(|reminder| reminder.due_at);
+        self.next_id
+    }
+
+    pub fn get(&self, id: u64) -> Option<&Reminder> {
+        self.reminders.iter().find(|reminder| reminder.id == id)
+    }
+
+    pub fn remove(&mut self, id: u64) {
+        self.reminders.retain(|reminder| reminder.id != id);
+    }
+
+    pub fn reschedule(&mut self, id: u64, due_at: DateTime<Utc>) {
+        if let Some(reminder) = self.reminders.iter_mut().find(|reminder| reminder.id == id) {
+            reminder.due_at = due_at;
+            reminder.alerted = false;
+        }
+        self.reminders.sort_by_key(|reminder| reminder.due_at);
+    }
+
+    /// Reminders whose time has come, earliest first.
+    pub fn due(&self, now: DateTime<Utc>) -> impl Iterator<Item = &Reminder> {
+        self.reminders
+            .iter()
+            .filter(move |reminder| reminder.due_at <= now)
+    }
+
+    pub fn upcoming(&self, now: DateTime<Utc>) -> impl Iterator<Item = &Reminder> {
+        self.reminders
+            .iter()
+            .filter(move |reminder| reminder.due_at > now)
+    }
+
+    /// Marks reminders that just became due as alerted and returns them, so
+    /// each one alerts once per due time.
+    pub fn take_newly_due(&mut self, now: DateTime<Utc>) -> Vec<Reminder> {
+        self.reminders
+            .iter_mut()
+            .filter(|reminder| reminder.due_at <= now && !reminder.alerted)
+            .map(|reminder| {
+                reminder.alerted = true;
+                reminder.clone()
+            })
+            .collect()
+    }
 }
 
 #[derive(Clone)]
@@ -22,21 +1037,339 @@ pub struct GitHubAccount {
     pub login: String,
     pub token: String,
     pub review_settings: ReviewCommandSettings,
+    /// Per-account override for [`AppSettings::hide_bot_notifications`].
+    pub hide_bot_notifications: Option<bool>,
+    /// Which service the account lives on.
+    pub forge: Forge,
+    /// REST API root for self-hosted instances; `None` means the forge's
+    /// public site.
+    pub api_base_url: Option<String>,
+    /// When set, auto-refresh only polls this account inside the window.
+    pub working_hours: Option<WorkingHours>,
+    /// Shell command (for example `pass show github/work`) whose output is
+    /// the token; the app then never stores the token itself.
+    pub token_command: Option<String>,
+    /// Dashboard group, such as "Work" or "Open source".
+    pub group: Option<String>,
+    /// Per-account override for [`AppSettings::section_alerts`].
+    pub section_alerts: Option<SectionAlertThresholds>,
+    pub inbox_backend: InboxBackend,
 }
 
-#[derive(Clone, Debug)]
+/// How the search-backed lists of an inbox are fetched. Notifications have
+/// no GraphQL API, so they always come over REST.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InboxBackend {
+    /// One search request per list, plus per-pull-request lookups.
+    #[default]
+    Rest,
+    /// A single query for every list, pull request badges included. Falls
+    /// back to REST when the query fails.
+    GraphQl,
+}
+
+impl InboxBackend {
+    pub const ALL: [Self; 2] = [Self::Rest, Self::GraphQl];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Rest => "REST",
+            Self::GraphQl => "GraphQL",
+        }
+    }
+}
+
+/// The service an account's notifications come from. Pull request badges,
+/// search lists and review tooling are GitHub-only; the others contribute
+/// their notification feed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Forge {
+    #[default]
+    GitHub,
+    GitLab,
+    Gitea,
+}
+
+impl Forge {
+    pub const ALL: [Self; 3] = [Self::GitHub, Self::GitLab, Self::Gitea];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::GitHub => "GitHub",
+            Self::GitLab => "GitLab",
+            Self::Gitea => "Gitea",
+        }
+    }
+
+    /// Lowercase name used in keys and search terms, matching the settings
+    /// file.
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::GitHub => "github",
+            Self::GitLab => "gitlab",
+            Self::Gitea => "gitea",
+        }
+    }
+
+    fn public_api_base(self) -> &'static str {
+        match self {
+            Self::GitHub => GITHUB_API_BASE,
+            Self::GitLab => "https://gitlab.com/api/v4",
+            Self::Gitea => "https://gitea.com/api/v1",
+        }
+    }
+
+    /// `api_base_url` without a trailing slash, or the public site's API.
+    fn api_base(self, api_base_url: Option<&str>) -> &str {
+        api_base_url
+            .map(|base| base.trim_end_matches('/'))
+            .filter(|base| !base.is_empty())
+            .unwrap_or(self.public_api_base())
+    }
+
+    /// Names an account's keychain entry and saved files, since one login
+    /// can exist on several instances. Accounts on github.com keep the bare
+    /// login so data saved by older versions still matches.
+    pub fn account_key(self, api_base_url: Option<&str>, login: &str) -> String {
+        let api_base = self.api_base(api_base_url);
+        if self == Self::GitHub && api_base == GITHUB_API_BASE {
+            return login.to_owned();
+        }
+        let host: String = api_base
+            .split_once("://")
+            .map_or(api_base, |(_, rest)| rest)
+            .chars()
+            .map(|ch| {
+                if ch.is_ascii_alphanumeric() || matches!(ch, '.' | '-') {
+                    ch
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        format!("{login}@{}_{host}", self.key())
+    }
+
+    /// Path of the REST API below a self-hosted instance's web root.
+    fn api_path(self) -> &'static str {
+        match self {
+            Self::GitHub => ENTERPRISE_API_PATH,
+            Self::GitLab => "/api/v4",
+            Self::Gitea => "/api/v1",
+        }
+    }
+
+    fn is_public_host(self, host: &str) -> bool {
+        let hosts: &[&str] = match self {
+            Self::GitHub => &["github.com", "api.github.com"],
+            Self::GitLab => &["gitlab.com"],
+            Self::Gitea => &["gitea.com"],
+        };
+        hosts.iter().any(|public| host.eq_ignore_ascii_case(public))
+    }
+}
+
+impl GitHubAccount {
+    pub fn polls_at(&self, local_time: NaiveDateTime) -> bool {
+        self.working_hours
+            .is_none_or(|working_hours| working_hours.contains(local_time))
+    }
+
+    pub fn api_base(&self) -> &str {
+        self.forge.api_base(self.api_base_url.as_deref())
+    }
+
+    /// See [`Forge::account_key`].
+    pub fn storage_key(&self) -> String {
+        self.forge
+            .account_key(self.api_base_url.as_deref(), &self.login)
+    }
+
+    /// True for the same user on the same instance; logins ignore case.
+    pub fn same_identity(&self, other: &Self) -> bool {
+        self.forge == other.forge
+            && self.api_base() == other.api_base()
+            && self.login.eq_ignore_ascii_case(&other.login)
+    }
+
+    /// Browser-facing root that matches [`Self::api_base`].
+    pub fn web_base(&self) -> &str {
+        let api_base = self.api_base();
+        if api_base == GITHUB_API_BASE {
+            return GITHUB_WEB_BASE;
+        }
+        api_base
+            .strip_suffix(self.forge.api_path())
+            .unwrap_or(api_base)
+    }
+
+    /// `api.github.com/graphql` on github.com, `<host>/api/graphql` on
+    /// Enterprise Server.
+    pub fn graphql_url(&self) -> String {
+        if self.api_base() == GITHUB_API_BASE {
+            format!("{GITHUB_API_BASE}/graphql")
+        } else {
+            format!("{}/api/graphql", self.web_base())
+        }
+    }
+
+    pub fn hides_bot_notifications(&self, settings: &AppSettings) -> bool {
+        self.hide_bot_notifications
+            .unwrap_or(settings.hide_bot_notifications)
+    }
+
+    pub fn section_alerts(&self, settings: &AppSettings) -> SectionAlertThresholds {
+        self.section_alerts.unwrap_or(settings.section_alerts)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct InboxSnapshot {
     pub notifications: Vec<NotificationItem>,
     pub review_requests: Vec<ReviewRequest>,
-    #[allow(dead_code)]
     pub mentions: Vec<MentionThread>,
     pub recent_reviews: Vec<ReviewSummary>,
+    #[serde(default)]
+    pub assigned_issues: Vec<AssignedIssue>,
+    #[serde(default)]
+    pub authored_pull_requests: Vec<AuthoredPullRequest>,
+    /// Badges answered by the fetch itself, which only the GraphQL backend
+    /// does. Not cached; the status queue keeps its own copy.
+    #[serde(skip)]
+    pub pull_request_statuses: Vec<(PullRequestKey, PullRequestStatus)>,
     pub fetched_at: DateTime<Utc>,
+    pub rate_limit: Option<RateLimitStatus>,
+    #[serde(default)]
+    pub search_totals: SearchTotals,
+    /// The newest unread threads, fetched next to a delta so threads read
+    /// elsewhere can be settled. Not cached.
+    #[serde(skip)]
+    pub unread_threads: Option<UnreadThreads>,
+}
+
+/// One page of unread threads, fetched without `since`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct UnreadThreads {
+    pub thread_ids: BTreeSet<String>,
+    /// Oldest update on a full page; threads before it may still be unread.
+    /// `None` when the page held every unread thread.
+    pub oldest_update: Option<DateTime<Utc>>,
+}
+
+/// How many hits GitHub reports for the search-backed lists, which can be
+/// more than were fetched. Zero when unknown.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchTotals {
+    pub review_requests: u64,
+    pub mentions: u64,
+}
+
+impl InboxSnapshot {
+    /// Marks every thread as coming from `provider`.
+    pub fn tag_provider(&mut self, provider: Forge) {
+        for item in &mut self.notifications {
+            item.provider = provider;
+        }
+    }
+}
+
+/// REST API quota reported by GitHub's `X-RateLimit-*` headers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RateLimitStatus {
+    pub limit: u32,
+    pub remaining: u32,
+    pub reset_at: DateTime<Utc>,
+}
+
+/// Per-section alert thresholds: when a section's counts bump and its unread
+/// count exceeds the threshold, a desktop alert fires. Zero alerts for any
+/// new thread; `None` leaves the section silent.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SectionAlertThresholds {
+    #[serde(default)]
+    pub review_requests: Option<usize>,
+    #[serde(default)]
+    pub mentions: Option<usize>,
+    #[serde(default)]
+    pub notifications: Option<usize>,
+}
+
+/// Local-time polling window. `end_hour` is exclusive, and a window whose end
+/// precedes its start wraps past midnight (for example 22–6).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkingHours {
+    pub start_hour: u32,
+    pub end_hour: u32,
+    #[serde(default)]
+    pub weekdays_only: bool,
+}
+
+impl Default for WorkingHours {
+    fn default() -> Self {
+        Self {
+            start_hour: 9,
+            end_hour: 18,
+            weekdays_only: true,
+        }
+    }
+}
+
+impl WorkingHours {
+    pub fn contains(&self, local_time: NaiveDateTime) -> bool {
+        if self.weekdays_only && matches!(local_time.weekday(), Weekday::Sat | Weekday::Sun) {
+            return false;
+        }
+        let hour = local_time.hour();
+        if self.start_hour <= self.end_hour {
+            (self.start_hour..self.end_hour).contains(&hour)
+        } else {
+            hour >= self.start_hour || hour < self.end_hour
+        }
+    }
+
+    pub fn label(&self) -> String {
+        let days = if self.weekdays_only {
+            "Mon–Fri"
+        } else {
+            "daily"
+        };
+        format!("{:02}:00–{:02}:00 {days}", self.start_hour, self.end_hour)
+    }
 }
 
 pub type PullRequestKey = (String, u64);
 
-#[derive(Clone, Debug)]
+/// Turns what the user typed into the account form's host field into an API
+/// root. A bare host such as `github.mycorp.com` gets the forge's API path
+/// (`/api/v3` for Enterprise Server); empty input and the forge's public
+/// site map to `None`.
+pub fn normalize_api_base_url(forge: Forge, input: &str) -> Option<String> {
+    let input = input.trim().trim_end_matches('/');
+    if input.is_empty() {
+        return None;
+    }
+
+    let with_scheme = if input.contains("://") {
+        input.to_owned()
+    } else {
+        format!("https://{input}")
+    };
+    let (scheme, rest) = with_scheme.split_once("://")?;
+    let (host, path) = rest.split_once('/').unwrap_or((rest, ""));
+    if forge.is_public_host(host) {
+        return None;
+    }
+
+    if path.is_empty() {
+        Some(format!("{scheme}://{host}{}", forge.api_path()))
+    } else {
+        Some(format!("{scheme}://{host}/{path}"))
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct NotificationItem {
     pub thread_id: String,
     pub repo: String,
@@ -45,13 +1378,96 @@ pub struct NotificationItem {
     pub head_ref: Option<String>,
     pub base_ref: Option<String>,
     pub my_review_status: Option<PullRequestReviewerStatus>,
+    pub latest_comment_url: Option<String>,
+    /// API URL of the subject, kept so its real page can be looked up when
+    /// `url` is only a guess.
+    #[serde(default)]
+    pub subject_url: Option<String>,
+    /// GitHub's subject type, such as `PullRequest` or `Discussion`.
+    #[serde(default)]
+    pub subject_type: String,
+    /// Service the thread came from. Caches written before threads were
+    /// tagged load as GitHub and are retagged with the account's forge.
+    #[serde(default)]
+    pub provider: Forge,
+    /// Provider-specific fields with no GitHub counterpart, such as GitLab's
+    /// original todo action.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub metadata: BTreeMap<String, String>,
+    pub latest_actor: Option<String>,
     pub reason: String,
     pub updated_at: DateTime<Utc>,
     pub last_read_at: Option<DateTime<Utc>>,
     pub unread: bool,
+    #[serde(skip)]
+    pub search_cache: SearchCache,
+}
+
+/// One searchable field in the forms a search compares against.
+#[derive(Clone, Debug)]
+pub struct SearchField {
+    pub raw: String,
+    /// Normalized and lowercased for substring and fuzzy matching.
+    pub folded: String,
+    /// Romanized `raw` and its folded form, for fields with non-ASCII text.
+    pub romanized: Option<(String, String)>,
+}
+
+/// Search fields of a [`NotificationItem`], built on first use so that
+/// filtering a large inbox does not allocate every frame. Cleared whenever
+/// a searchable field changes.
+#[derive(Clone, Debug, Default)]
+pub struct SearchCache(OnceLock<Vec<SearchField>>);
+
+impl SearchCache {
+    pub fn get_or_init(&self, init: impl FnOnce() -> Vec<SearchField>) -> &[SearchField] {
+        self.0.get_or_init(init)
+    }
+
+    pub fn clear(&mut self) {
+        self.0.take();
+    }
 }
 
 impl NotificationItem {
+    /// Everything a notification search looks at: repository, title,
+    /// reason, branches, URL, actor and the `123`, `#123` and `repo#123`
+    /// spellings of the number.
+    pub fn search_terms(&self) -> Vec<String> {
+        let mut terms = vec![self.repo.clone(), self.display_title(), self.reason.clone()];
+        terms.extend(self.head_ref.clone());
+        terms.extend(self.base_ref.clone());
+        terms.extend(self.merge_direction_text());
+        terms.extend(self.url.clone());
+        terms.extend(self.latest_actor.clone());
+        if let Some(number) = self.thread_number() {
+            terms.push(number.to_string());
+            terms.push(format!("#{number}"));
+            terms.push(format!("{}#{number}", self.repo));
+        }
+        terms
+    }
+
+    /// Identifies the thread across providers, whose ids may overlap.
+    pub fn source_key(&self) -> String {
+        format!("{}/{}", self.provider.key(), self.thread_id)
+    }
+
+    /// The API resource whose `html_url` gives the subject's real page.
+    /// Pull requests and issues map onto web URLs directly, so they need no
+    /// lookup.
+    pub fn html_url_source(&self) -> Option<&str> {
+        match self.subject_url.as_deref() {
+            Some(url) if url.contains("/pulls/") || url.contains("/issues/") => None,
+            Some(url) => Some(url),
+            None => self.latest_comment_url.as_deref(),
+        }
+    }
+
+    pub fn is_discussion(&self) -> bool {
+        self.subject_type == "Discussion"
+    }
+
     pub fn pull_request_url(&self) -> Option<&str> {
         let url = self.url.as_deref()?;
         pull_request_number_from_url(url).map(|_| url)
@@ -90,6 +1506,254 @@ impl NotificationItem {
             self.base_ref.as_deref()?
         ))
     }
+
+    pub fn is_from_bot(&self) -> bool {
+        self.latest_actor.as_deref().is_some_and(is_bot_login)
+    }
+}
+
+/// Enrichment fetched for one notification thread.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CachedEnrichment {
+    /// The thread's `updated_at` when this was fetched; any later activity
+    /// invalidates the entry.
+    pub updated_at: DateTime<Utc>,
+    pub fetched_at: DateTime<Utc>,
+    /// Last time a refresh reused the entry; eviction drops the least
+    /// recently used entries first.
+    #[serde(default)]
+    pub last_used_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub head_ref: Option<String>,
+    #[serde(default)]
+    pub base_ref: Option<String>,
+    #[serde(default)]
+    pub my_review_status: Option<PullRequestReviewerStatus>,
+    #[serde(default)]
+    pub latest_actor: Option<String>,
+    #[serde(default)]
+    pub html_url: Option<String>,
+}
+
+/// Per-account enrichment keyed by thread id, kept on disk so a restart does
+/// not fetch every pull request again.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EnrichmentCache {
+    #[serde(default)]
+    pub entries: BTreeMap<String, CachedEnrichment>,
+}
+
+impl EnrichmentCache {
+    /// The entry for `item` if the thread has not changed since and the
+    /// entry is younger than `ttl`. A hit counts as a use.
+    pub fn lookup(
+        &mut self,
+        item: &NotificationItem,
+        now: DateTime<Utc>,
+        ttl: chrono::Duration,
+    ) -> Option<&CachedEnrichment> {
+        let entry = self
+            .entries
+            .get_mut(&item.thread_id)
+            .filter(|entry| entry.updated_at == item.updated_at && now - entry.fetched_at < ttl)?;
+        entry.last_used_at = Some(now);
+        Some(entry)
+    }
+
+    pub fn insert(
+        &mut self,
+        thread_id: String,
+        entry: CachedEnrichment,
+        ttl: chrono::Duration,
+        capacity: usize,
+    ) {
+        let now = entry.fetched_at;
+        self.entries.insert(thread_id, entry);
+        self.compact(now, ttl, capacity);
+    }
+
+    /// Drops expired entries, then the least recently used ones until at
+    /// most `capacity` remain. Returns how many were dropped.
+    pub fn compact(&mut self, now: DateTime<Utc>, ttl: chrono::Duration, capacity: usize) -> usize {
+        let before = self.entries.len();
+        self.entries.retain(|_, entry| now - entry.fetched_at < ttl);
+        if self.entries.len() > capacity {
+            let mut by_use: Vec<_> = self
+                .entries
+                .iter()
+                .map(|(thread_id, entry)| {
+                    (
+                        entry.last_used_at.unwrap_or(entry.fetched_at),
+                        thread_id.clone(),
+                    )
+                })
+                .collect();
+            by_use.sort();
+            let excess = self.entries.len() - capacity;
+            for (_, thread_id) in by_use.into_iter().take(excess) {
+                self.entries.remove(&thread_id);
+            }
+        }
+        before - self.entries.len()
+    }
+}
+
+/// Threads the user marked done, keyed by thread id with the time they did.
+/// GitHub's feed keeps returning archived threads, so these tombstones hide
+/// them locally until the thread sees new activity.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DoneThreads {
+    #[serde(default)]
+    pub entries: BTreeMap<String, DateTime<Utc>>,
+}
+
+impl DoneThreads {
+    pub fn mark(&mut self, thread_id: String, at: DateTime<Utc>) {
+        self.entries.insert(thread_id, at);
+    }
+
+    pub fn hides(&self, item: &NotificationItem) -> bool {
+        self.entries
+            .get(&item.thread_id)
+            .is_some_and(|done_at| item.updated_at <= *done_at)
+    }
+
+    /// Drops tombstones of threads updated after they were marked done, and
+    /// ones older than `max_age`, whose threads have left the feed anyway.
+    /// Returns how many were dropped.
+    pub fn prune(
+        &mut self,
+        notifications: &[NotificationItem],
+        now: DateTime<Utc>,
+        max_age: chrono::Duration,
+    ) -> usize {
+        let before = self.entries.len();
+        for item in notifications {
+            if self
+                .entries
+                .get(&item.thread_id)
+                .is_some_and(|done_at| item.updated_at > *done_at)
+            {
+                self.entries.remove(&item.thread_id);
+            }
+        }
+        self.entries.retain(|_, done_at| now - *done_at < max_age);
+        before - self.entries.len()
+    }
+}
+
+/// Every notification thread an account has seen, kept after GitHub drops
+/// it from the feed so the history view can still find it.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NotificationArchive {
+    #[serde(default)]
+    pub entries: BTreeMap<String, ArchivedThread>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchivedThread {
+    pub repo: String,
+    pub title: String,
+    #[serde(default)]
+    pub url: Option<String>,
+    pub reason: String,
+    pub first_seen: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    #[serde(default)]
+    pub done_at: Option<DateTime<Utc>>,
+}
+
+impl ArchivedThread {
+    /// The later of the thread's last update and the time it was marked done.
+    pub fn last_activity(&self) -> DateTime<Utc> {
+        self.done_at
+            .map_or(self.updated_at, |done_at| done_at.max(self.updated_at))
+    }
+}
+
+impl NotificationArchive {
+    /// Adds new threads from a fetched feed and refreshes known ones.
+    /// Returns whether anything changed.
+    pub fn record(&mut self, notifications: &[NotificationItem], now: DateTime<Utc>) -> bool {
+        let mut changed = false;
+        for item in notifications {
+            let Some(entry) = self.entries.get_mut(&item.thread_id) else {
+                self.entries.insert(
+                    item.thread_id.clone(),
+                    ArchivedThread {
+                        repo: item.repo.clone(),
+                        title: item.title.clone(),
+                        url: item.url.clone(),
+                        reason: item.reason.clone(),
+                        first_seen: now,
+                        updated_at: item.updated_at,
+                        done_at: None,
+                    },
+                );
+                changed = true;
+                continue;
+            };
+            if entry.updated_at != item.updated_at
+                || entry.title != item.title
+                || entry.reason != item.reason
+                || (item.url.is_some() && entry.url != item.url)
+            {
+                entry.updated_at = item.updated_at;
+                entry.title.clone_from(&item.title);
+                entry.reason.clone_from(&item.reason);
+                if item.url.is_some() {
+                    entry.url.clone_from(&item.url);
+                }
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    pub fn mark_done(&mut self, thread_id: &str, at: DateTime<Utc>) -> bool {
+        let Some(entry) = self.entries.get_mut(thread_id) else {
+            return false;
+        };
+        entry.done_at = Some(at);
+        true
+    }
+}
+
+const KNOWN_BOT_LOGINS: &[&str] = &["dependabot", "renovate", "github-actions"];
+
+pub fn is_bot_login(login: &str) -> bool {
+    let login = login.to_ascii_lowercase();
+    login.ends_with("[bot]") || KNOWN_BOT_LOGINS.contains(&login.as_str())
+}
+
+/// One entry of an issue or pull request timeline, already phrased for
+/// display (`commented: …`, `added label bug`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TimelineEvent {
+    pub at: DateTime<Utc>,
+    pub actor: Option<String>,
+    pub summary: String,
+}
+
+/// Issue or pull request shown in the preview pane. Bodies are GitHub
+/// flavored markdown, as written.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ThreadDetails {
+    pub title: String,
+    /// `open`, `closed` or, for pull requests, `merged`.
+    pub state: String,
+    pub author: Option<String>,
+    pub labels: Vec<String>,
+    pub body: String,
+    /// The latest comments, oldest first.
+    pub comments: Vec<ThreadComment>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ThreadComment {
+    pub author: Option<String>,
+    pub body: String,
+    pub created_at: DateTime<Utc>,
 }
 
 #[derive(Clone, Debug)]
@@ -111,7 +1775,61 @@ pub struct PullRequestReviewer {
     pub status: PullRequestReviewerStatus,
 }
 
+/// Combined state of the checks on a pull request's head commit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CheckState {
+    Passing,
+    Failing,
+    Pending,
+}
+
+impl CheckState {
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Self::Passing => "✓",
+            Self::Failing => "✗",
+            Self::Pending => "●",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Passing => "Checks passing",
+            Self::Failing => "Checks failing",
+            Self::Pending => "Checks pending",
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReviewDecision {
+    Approved,
+    ChangesRequested,
+    ReviewRequired,
+}
+
+impl ReviewDecision {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Approved => "Approved",
+            Self::ChangesRequested => "Changes requested",
+            Self::ReviewRequired => "Review required",
+        }
+    }
+}
+
+/// Badges shown next to threads that point at a pull request.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PullRequestStatus {
+    pub draft: bool,
+    /// `Some(false)` when the branch conflicts with its base; `None` while
+    /// GitHub is still computing it.
+    pub mergeable: Option<bool>,
+    pub checks: Option<CheckState>,
+    pub review_decision: Option<ReviewDecision>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum PullRequestReviewerStatus {
     Pending,
     Approved,
@@ -143,6 +1861,29 @@ impl PullRequestReviewerStatus {
     }
 }
 
+/// A review submitted from the app.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReviewVerdict {
+    Approve,
+    RequestChanges,
+}
+
+impl ReviewVerdict {
+    pub fn api_event(self) -> &'static str {
+        match self {
+            Self::Approve => "APPROVE",
+            Self::RequestChanges => "REQUEST_CHANGES",
+        }
+    }
+
+    pub fn reviewer_status(self) -> PullRequestReviewerStatus {
+        match self {
+            Self::Approve => PullRequestReviewerStatus::Approved,
+            Self::RequestChanges => PullRequestReviewerStatus::ChangesRequested,
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct RepoPullRequest {
     pub repo: String,
@@ -171,6 +1912,10 @@ impl RepoPullRequest {
         format!("repo-pr:{}#{}", self.repo, self.number)
     }
 
+    pub fn is_from_bot(&self) -> bool {
+        self.author_login.as_deref().is_some_and(is_bot_login)
+    }
+
     pub fn merge_direction_text(&self) -> String {
         format!("{} -> {}", self.head_ref, self.base_ref)
     }
@@ -198,8 +1943,7 @@ fn thread_number_from_known_segment(url: &str, segment: &str) -> Option<u64> {
     number.parse().ok()
 }
 
-#[allow(dead_code)]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ReviewRequest {
     pub _id: u64,
     pub repo: String,
@@ -207,6 +1951,9 @@ pub struct ReviewRequest {
     pub url: String,
     pub updated_at: DateTime<Utc>,
     pub requested_by: Option<String>,
+    /// When the pending request was made, which is when the wait began.
+    #[serde(default)]
+    pub requested_at: Option<DateTime<Utc>>,
 }
 
 impl ReviewRequest {
@@ -219,8 +1966,7 @@ impl ReviewRequest {
     }
 }
 
-#[allow(dead_code)]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct MentionThread {
     pub _id: u64,
     pub repo: String,
@@ -230,14 +1976,20 @@ pub struct MentionThread {
     pub kind: MentionKind,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum MentionKind {
     Issue,
     PullRequest,
 }
 
+impl MentionThread {
+    /// Repo and issue or pull request number.
+    pub fn thread_key(&self) -> Option<PullRequestKey> {
+        Some((self.repo.clone(), thread_number_from_url(&self.url)?))
+    }
+}
+
 impl MentionKind {
-    #[allow(dead_code)]
     pub fn label(&self) -> &'static str {
         match self {
             MentionKind::Issue => "Issue",
@@ -246,8 +1998,63 @@ impl MentionKind {
     }
 }
 
-#[allow(dead_code)]
-#[derive(Clone, Debug)]
+/// Open issue assigned to the user, found through search.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AssignedIssue {
+    pub repo: String,
+    pub number: u64,
+    pub title: String,
+    pub url: String,
+    pub updated_at: DateTime<Utc>,
+    #[serde(default)]
+    pub labels: Vec<String>,
+}
+
+/// Issue or pull request returned by a saved GitHub search.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchHit {
+    pub repo: String,
+    pub title: String,
+    pub url: String,
+    pub updated_at: DateTime<Utc>,
+    pub state: String,
+    #[serde(default)]
+    pub labels: Vec<String>,
+}
+
+/// Open pull request the user authored, found through search.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AuthoredPullRequest {
+    pub repo: String,
+    pub number: u64,
+    pub title: String,
+    pub url: String,
+    pub updated_at: DateTime<Utc>,
+    #[serde(default)]
+    pub draft: bool,
+    #[serde(default)]
+    pub labels: Vec<String>,
+}
+
+/// One of my branches that has gone quiet: an open pull request without
+/// recent activity, or a pushed branch that never got a pull request.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StaleBranch {
+    pub repo: String,
+    /// `#12 Title` for a pull request, the branch name otherwise.
+    pub title: String,
+    pub url: String,
+    pub has_pull_request: bool,
+    pub last_activity: DateTime<Utc>,
+}
+
+impl AuthoredPullRequest {
+    pub fn pull_request_key(&self) -> PullRequestKey {
+        (self.repo.clone(), self.number)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ReviewSummary {
     pub _id: u64,
     pub repo: String,
@@ -267,25 +2074,352 @@ impl ReviewSummary {
     }
 }
 
+/// Fixtures shared by unit tests across the crate.
 #[cfg(test)]
-mod tests {
-    use super::{NotificationItem, RepoPullRequest};
+pub mod test_support {
+    use std::collections::BTreeMap;
+
     use chrono::Utc;
 
-    fn notification(url: Option<&str>) -> NotificationItem {
+    use super::{Forge, NotificationItem, SearchCache};
+
+    /// An unread GitHub notification in `acme/app` updated just now. Tests
+    /// override the fields they care about with struct update syntax.
+    pub fn notification(thread_id: &str) -> NotificationItem {
         NotificationItem {
-            thread_id: "thread-1".into(),
-            repo: "acme/repo".into(),
-            title: "Title".into(),
-            url: url.map(str::to_owned),
+            thread_id: thread_id.to_owned(),
+            repo: String::from("acme/app"),
+            title: format!("Thread {thread_id}"),
+            url: None,
             head_ref: None,
             base_ref: None,
             my_review_status: None,
-            reason: "review_requested".into(),
+            latest_comment_url: None,
+            subject_url: None,
+            subject_type: String::new(),
+            provider: Forge::GitHub,
+            metadata: BTreeMap::new(),
+            latest_actor: None,
+            reason: String::from("subscribed"),
             updated_at: Utc::now(),
             last_read_at: None,
             unread: true,
+            search_cache: SearchCache::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        AppSettings, CachedEnrichment, DoneThreads, DoubleClickAction, EnrichmentCache, Experiment,
+        Forge, GitHubAccount, HandledKind, HandledLog, InboxBackend, InboxZeroStreak,
+        NotificationArchive, NotificationChips, NotificationItem, ReadStateChip, ReleaseRadar,
+        ReminderList, RepoPullRequest, RepoTier, ReviewCommandSettings, ThreadStage, ThreadStages,
+        WorkingHours, is_bot_login, normalize_api_base_url, test_support,
+    };
+    use chrono::NaiveDate;
+    use chrono::Utc;
+
+    fn day(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn reminders_alert_once_per_due_time() {
+        let now = Utc::now();
+        let mut list = ReminderList::default();
+        let later = list.add(
+            String::from("Follow up"),
+            String::from("https://github.com/acme/repo/pull/1"),
+            None,
+            None,
+            now + chrono::Duration::hours(1),
+        );
+        let due = list.add(
+            String::from("Ping reviewer"),
+            String::from("https://github.com/acme/repo/pull/2"),
+            Some(String::from("neo")),
+            None,
+            now - chrono::Duration::minutes(5),
+        );
+
+        assert_eq!(list.reminders[0].id, due);
+        let fired: Vec<_> = list.take_newly_due(now).into_iter().map(|r| r.id).collect();
+        assert_eq!(fired, [due]);
+        assert!(list.take_newly_due(now).is_empty());
+        assert_eq!(list.due(now).count(), 1);
+        assert_eq!(list.upcoming(now).next().map(|r| r.id), Some(later));
+
+        list.reschedule(due, now + chrono::Duration::hours(2));
+        assert_eq!(list.due(now).count(), 0);
+        assert!(!list.get(due).expect("reminder").alerted);
+    }
+
+    #[test]
+    fn inbox_zero_streak_counts_consecutive_days() {
+        let mut streak = InboxZeroStreak::default();
+
+        assert!(streak.record(day("2024-03-01")));
+        assert!(!streak.record(day("2024-03-01")));
+        assert!(streak.record(day("2024-03-02")));
+        assert_eq!(streak.current(day("2024-03-02")), 2);
+        assert_eq!(streak.current(day("2024-03-03")), 2);
+        assert_eq!(streak.current(day("2024-03-04")), 0);
+
+        assert!(streak.record(day("2024-03-05")));
+        assert_eq!(streak.days, 1);
+    }
+
+    #[test]
+    fn handled_log_counts_per_day_and_drops_old_days() {
+        let mut log = HandledLog::default();
+        log.record(HandledKind::Read, day("2024-03-01"));
+        log.record(HandledKind::Read, day("2024-03-02"));
+        log.record(HandledKind::Done, day("2024-03-02"));
+
+        let today = log.on(day("2024-03-02"));
+        assert_eq!((today.read, today.done, today.total()), (1, 1, 2));
+        assert_eq!(log.on(day("2024-03-01")).total(), 1);
+
+        log.record(HandledKind::Done, day("2024-04-15"));
+        assert_eq!(log.on(day("2024-03-02")).total(), 0);
+        assert_eq!(log.on(day("2024-04-15")).done, 1);
+    }
+
+    #[test]
+    fn experiments_store_only_overrides_of_their_default() {
+        let mut settings = AppSettings::default();
+        assert!(settings.experiment_enabled(Experiment::ThreadTimeline));
+
+        settings.set_experiment(Experiment::ThreadTimeline, false);
+        assert!(!settings.experiment_enabled(Experiment::ThreadTimeline));
+        assert_eq!(settings.experiments.get("thread_timeline"), Some(&false));
+
+        settings.set_experiment(Experiment::ThreadTimeline, true);
+        assert!(settings.experiments.is_empty());
+    }
+
+    #[test]
+    fn repo_tier_defaults_to_normal_and_orders_critical_first() {
+        let mut settings = AppSettings::default();
+        settings
+            .repo_tiers
+            .insert(String::from("acme/infra"), RepoTier::Critical);
+
+        assert_eq!(settings.repo_tier("acme/infra"), RepoTier::Critical);
+        assert_eq!(settings.repo_tier("acme/app"), RepoTier::Normal);
+        assert!(RepoTier::Critical < RepoTier::Normal && RepoTier::Normal < RepoTier::Low);
+    }
+
+    #[test]
+    fn is_bot_login_matches_app_suffix_and_known_bots() {
+        assert!(is_bot_login("dependabot[bot]"));
+        assert!(is_bot_login("renovate"));
+        assert!(is_bot_login("GitHub-Actions"));
+        assert!(!is_bot_login("octocat"));
+    }
+
+    #[test]
+    fn notification_is_from_bot_uses_latest_actor() {
+        let mut item = notification(None);
+        assert!(!item.is_from_bot());
+
+        item.latest_actor = Some("renovate[bot]".into());
+        assert!(item.is_from_bot());
+    }
+
+    #[test]
+    fn double_click_action_uses_reason_defaults_and_fallback() {
+        let settings = AppSettings::default();
+
+        assert_eq!(
+            settings.double_click_action("review_requested"),
+            DoubleClickAction::OpenInBrowser
+        );
+        assert_eq!(
+            settings.double_click_action("ci_activity"),
+            DoubleClickAction::MarkRead
+        );
+        assert_eq!(
+            settings.double_click_action("mention"),
+            DoubleClickAction::ShowDetails
+        );
+        assert_eq!(
+            settings.double_click_action("subscribed"),
+            DoubleClickAction::Nothing
+        );
+    }
+
+    fn notification(url: Option<&str>) -> NotificationItem {
+        NotificationItem {
+            repo: "acme/repo".into(),
+            title: "Title".into(),
+            url: url.map(str::to_owned),
+            reason: "review_requested".into(),
+            ..test_support::notification("thread-1")
+        }
+    }
+
+    #[test]
+    fn chips_combine_reason_repo_and_read_state() {
+        let mut item = notification(None);
+        let mut chips = NotificationChips::default();
+        assert!(chips.matches(&item) && !chips.is_active());
+
+        chips.reasons.insert("mention".into());
+        assert!(!chips.matches(&item));
+        chips.reasons.insert("review_requested".into());
+        chips.repos.insert("acme/repo".into());
+        assert!(chips.matches(&item));
+
+        chips.state = ReadStateChip::Updated;
+        assert!(!chips.matches(&item));
+        item.last_read_at = Some(item.updated_at - chrono::Duration::minutes(5));
+        assert!(chips.matches(&item));
+        chips.state = ReadStateChip::Unread;
+        item.unread = false;
+        assert!(!chips.matches(&item));
+    }
+
+    #[test]
+    fn thread_stages_default_to_triage_and_filter_by_chip() {
+        let item = notification(None);
+        let mut stages = ThreadStages::default();
+        let mut chips = NotificationChips {
+            stage: Some(ThreadStage::ToTriage),
+            ..NotificationChips::default()
+        };
+        assert!(chips.is_active() && chips.matches_stage(&stages, &item));
+
+        assert!(stages.set(item.thread_id.clone(), ThreadStage::WaitingOnAuthor));
+        assert!(!stages.set(item.thread_id.clone(), ThreadStage::WaitingOnAuthor));
+        assert!(!chips.matches_stage(&stages, &item));
+        chips.stage = Some(ThreadStage::WaitingOnAuthor);
+        assert!(chips.matches_stage(&stages, &item));
+
+        assert!(stages.set(item.thread_id.clone(), ThreadStage::ToTriage));
+        assert!(stages.entries.is_empty());
+    }
+
+    #[test]
+    fn html_url_source_skips_subjects_with_derivable_pages() {
+        let mut item = notification(Some("https://github.com/acme/repo/pull/7"));
+        item.subject_url = Some("https://api.github.com/repos/acme/repo/pulls/7".into());
+        item.latest_comment_url =
+            Some("https://api.github.com/repos/acme/repo/issues/comments/1".into());
+        assert_eq!(item.html_url_source(), None);
+
+        item.subject_url = Some("https://api.github.com/repos/acme/repo/releases/9".into());
+        assert_eq!(
+            item.html_url_source(),
+            Some("https://api.github.com/repos/acme/repo/releases/9")
+        );
+
+        item.subject_url = None;
+        assert_eq!(
+            item.html_url_source(),
+            Some("https://api.github.com/repos/acme/repo/issues/comments/1")
+        );
+    }
+
+    #[test]
+    fn done_threads_prune_resurfaced_and_stale_tombstones() {
+        let now = Utc::now();
+        let max_age = chrono::Duration::days(30);
+        let mut item = notification(None);
+        item.updated_at = now;
+        let mut done = DoneThreads::default();
+        done.mark(item.thread_id.clone(), now);
+        done.mark(String::from("gone"), now - chrono::Duration::days(31));
+
+        assert_eq!(done.prune(std::slice::from_ref(&item), now, max_age), 1);
+        assert!(done.hides(&item));
+
+        item.updated_at = now + chrono::Duration::minutes(1);
+        assert_eq!(done.prune(&[item], now, max_age), 1);
+        assert!(done.entries.is_empty());
+    }
+
+    #[test]
+    fn notification_archive_keeps_urls_and_done_times_across_refreshes() {
+        let now = Utc::now();
+        let mut item = notification(Some("https://github.com/acme/repo/pull/7"));
+        let mut archive = NotificationArchive::default();
+
+        assert!(archive.record(std::slice::from_ref(&item), now));
+        assert!(!archive.record(std::slice::from_ref(&item), now));
+        assert!(archive.mark_done(&item.thread_id, now));
+
+        item.url = None;
+        item.title = "Renamed".into();
+        assert!(archive.record(&[item], now + chrono::Duration::days(1)));
+        let entry = &archive.entries["thread-1"];
+        assert_eq!(entry.title, "Renamed");
+        assert_eq!(
+            entry.url.as_deref(),
+            Some("https://github.com/acme/repo/pull/7")
+        );
+        assert_eq!(entry.first_seen, now);
+        assert_eq!(entry.done_at, Some(now));
+    }
+
+    #[test]
+    fn enrichment_cache_expires_on_activity_and_age() {
+        let item = notification(None);
+        let ttl = chrono::Duration::hours(6);
+        let mut cache = EnrichmentCache::default();
+        cache.insert(
+            item.thread_id.clone(),
+            CachedEnrichment {
+                updated_at: item.updated_at,
+                fetched_at: item.updated_at,
+                latest_actor: Some("neo".into()),
+                ..CachedEnrichment::default()
+            },
+            ttl,
+            10,
+        );
+
+        assert!(cache.lookup(&item, item.updated_at, ttl).is_some());
+        assert!(
+            cache
+                .lookup(&item, item.updated_at + chrono::Duration::hours(7), ttl)
+                .is_none()
+        );
+        let mut updated = item.clone();
+        updated.updated_at += chrono::Duration::minutes(1);
+        assert!(cache.lookup(&updated, item.updated_at, ttl).is_none());
+    }
+
+    #[test]
+    fn enrichment_cache_evicts_least_recently_used_entries() {
+        let ttl = chrono::Duration::hours(6);
+        let start = Utc::now();
+        let mut cache = EnrichmentCache::default();
+        let mut items = Vec::new();
+        for (offset, thread_id) in ["a", "b", "c"].into_iter().enumerate() {
+            let mut item = notification(None);
+            item.thread_id = thread_id.into();
+            cache.insert(
+                item.thread_id.clone(),
+                CachedEnrichment {
+                    updated_at: item.updated_at,
+                    fetched_at: start + chrono::Duration::minutes(offset as i64),
+                    ..CachedEnrichment::default()
+                },
+                ttl,
+                3,
+            );
+            items.push(item);
         }
+        // Using "a" makes "b" the least recently used entry.
+        let later = start + chrono::Duration::minutes(5);
+        assert!(cache.lookup(&items[0], later, ttl).is_some());
+
+        assert_eq!(cache.compact(later, ttl, 2), 1);
+        assert!(cache.entries.contains_key("a"));
+        assert!(!cache.entries.contains_key("b"));
     }
 
     #[test]
@@ -446,4 +2580,171 @@ mod tests {
             "feature/filtering -> main"
         );
     }
+
+    fn account_with_api_base(api_base_url: Option<&str>) -> GitHubAccount {
+        GitHubAccount {
+            login: String::from("neo"),
+            token: String::from("token"),
+            review_settings: ReviewCommandSettings::default(),
+            hide_bot_notifications: None,
+            api_base_url: api_base_url.map(str::to_owned),
+            working_hours: None,
+            token_command: None,
+            group: None,
+            section_alerts: None,
+            inbox_backend: InboxBackend::Rest,
+            forge: Forge::GitHub,
+        }
+    }
+
+    #[test]
+    fn account_bases_default_to_github_com() {
+        let account = account_with_api_base(None);
+
+        assert_eq!(account.api_base(), "https://api.github.com");
+        assert_eq!(account.web_base(), "https://github.com");
+        assert_eq!(account.graphql_url(), "https://api.github.com/graphql");
+    }
+
+    #[test]
+    fn enterprise_account_bases_share_the_host() {
+        let account = account_with_api_base(Some("https://github.mycorp.com/api/v3/"));
+
+        assert_eq!(account.api_base(), "https://github.mycorp.com/api/v3");
+        assert_eq!(account.web_base(), "https://github.mycorp.com");
+        assert_eq!(
+            account.graphql_url(),
+            "https://github.mycorp.com/api/graphql"
+        );
+    }
+
+    #[test]
+    fn api_base_url_input_is_normalized() {
+        assert_eq!(normalize_api_base_url(Forge::GitHub, "  "), None);
+        assert_eq!(normalize_api_base_url(Forge::GitHub, "github.com"), None);
+        assert_eq!(
+            normalize_api_base_url(Forge::GitHub, "https://api.github.com/"),
+            None
+        );
+        assert_eq!(
+            normalize_api_base_url(Forge::GitHub, "github.mycorp.com"),
+            Some(String::from("https://github.mycorp.com/api/v3"))
+        );
+        assert_eq!(
+            normalize_api_base_url(Forge::GitHub, "http://ghe.local:8080/api/v3/"),
+            Some(String::from("http://ghe.local:8080/api/v3"))
+        );
+        assert_eq!(normalize_api_base_url(Forge::GitLab, "gitlab.com"), None);
+        assert_eq!(
+            normalize_api_base_url(Forge::GitLab, "gitlab.mycorp.com"),
+            Some(String::from("https://gitlab.mycorp.com/api/v4"))
+        );
+    }
+
+    #[test]
+    fn other_forges_default_to_their_public_site() {
+        let mut account = account_with_api_base(None);
+        account.forge = Forge::GitLab;
+        assert_eq!(account.api_base(), "https://gitlab.com/api/v4");
+        assert_eq!(account.web_base(), "https://gitlab.com");
+
+        account.forge = Forge::Gitea;
+        account.api_base_url = Some(String::from("https://git.mycorp.com/api/v1"));
+        assert_eq!(account.web_base(), "https://git.mycorp.com");
+    }
+
+    #[test]
+    fn accounts_are_keyed_by_forge_host_and_login() {
+        let github = account_with_api_base(None);
+        assert_eq!(github.storage_key(), github.login);
+
+        let mut gitlab = github.clone();
+        gitlab.forge = Forge::GitLab;
+        assert_eq!(
+            gitlab.storage_key(),
+            format!("{}@gitlab_gitlab.com_api_v4", github.login)
+        );
+        assert!(!gitlab.same_identity(&github));
+
+        let mut enterprise = github.clone();
+        enterprise.login = github.login.to_uppercase();
+        enterprise.api_base_url = Some(String::from("https://github.mycorp.com/api/v3/"));
+        assert_eq!(
+            enterprise.storage_key(),
+            format!("{}@github_github.mycorp.com_api_v3", enterprise.login)
+        );
+        assert!(!enterprise.same_identity(&github));
+        enterprise.api_base_url = None;
+        assert!(enterprise.same_identity(&github));
+    }
+
+    fn local_time(date: &str, hour: u32) -> chrono::NaiveDateTime {
+        day(date).and_hms_opt(hour, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn working_hours_cover_weekday_office_hours_only() {
+        let hours = WorkingHours::default();
+
+        // 2026-03-02 is a Monday, 2026-03-07 a Saturday.
+        assert!(hours.contains(local_time("2026-03-02", 9)));
+        assert!(hours.contains(local_time("2026-03-02", 17)));
+        assert!(!hours.contains(local_time("2026-03-02", 18)));
+        assert!(!hours.contains(local_time("2026-03-02", 8)));
+        assert!(!hours.contains(local_time("2026-03-07", 10)));
+    }
+
+    #[test]
+    fn working_hours_wrap_past_midnight() {
+        let hours = WorkingHours {
+            start_hour: 22,
+            end_hour: 6,
+            weekdays_only: false,
+        };
+
+        assert!(hours.contains(local_time("2026-03-07", 23)));
+        assert!(hours.contains(local_time("2026-03-07", 2)));
+        assert!(!hours.contains(local_time("2026-03-07", 12)));
+    }
+
+    #[test]
+    fn accounts_without_working_hours_always_poll() {
+        let mut account = account_with_api_base(None);
+        assert!(account.polls_at(local_time("2026-03-07", 3)));
+
+        account.working_hours = Some(WorkingHours::default());
+        assert!(!account.polls_at(local_time("2026-03-07", 3)));
+    }
+
+    #[test]
+    fn release_radar_needs_an_org_and_a_label() {
+        let mut radar = ReleaseRadar {
+            org: String::from("@acme"),
+            ..ReleaseRadar::default()
+        };
+        assert_eq!(radar.search_query(), None);
+
+        radar.label = String::from(" release blocker ");
+        assert_eq!(
+            radar.search_query().as_deref(),
+            Some("org:acme label:\"release blocker\" is:open")
+        );
+    }
+
+    #[test]
+    fn low_data_mode_stretches_refresh_interval() {
+        let base = std::time::Duration::from_secs(180);
+        let mut settings = AppSettings::default();
+        assert_eq!(settings.refresh_interval(base, false), base);
+        assert_eq!(
+            settings.refresh_interval(base, true),
+            std::time::Duration::from_secs(720)
+        );
+
+        settings.low_data_mode = true;
+        assert_eq!(
+            settings.refresh_interval(base, false),
+            std::time::Duration::from_secs(720)
+        );
+    }
 }
@@ -0,0 +1,199 @@
+//! The services an account can pull notifications from. GitHub keeps its
+//! full feature set in [`crate::github`]; GitLab and Gitea contribute their
+//! notification feeds, which fill the same [`InboxSnapshot`] with empty
+//! search lists.
+
+mod gitea;
+mod gitlab;
+
+use chrono::Utc;
+use reqwest::Client;
+
+use crate::{
+    domain::{Forge, GitHubAccount, InboxSnapshot, NotificationItem, SearchTotals},
+    github::{self, FetchError, InboxFetchOptions},
+    task::Progress,
+};
+
+use gitea::GiteaForge;
+use gitlab::GitLabForge;
+
+/// Notification operations every forge supports.
+trait ForgeProvider {
+    async fn fetch_inbox(
+        &self,
+        client: &Client,
+        profile: &GitHubAccount,
+        options: InboxFetchOptions,
+        progress: &Progress,
+    ) -> Result<InboxSnapshot, FetchError>;
+
+    async fn mark_read(
+        &self,
+        client: &Client,
+        profile: &GitHubAccount,
+        thread_id: &str,
+    ) -> Result<(), FetchError>;
+
+    async fn mark_done(
+        &self,
+        client: &Client,
+        profile: &GitHubAccount,
+        thread_id: &str,
+    ) -> Result<(), FetchError>;
+
+    /// Checks a freshly entered token before the account is saved.
+    async fn verify_token(
+        &self,
+        client: &Client,
+        profile: &GitHubAccount,
+    ) -> Result<(), FetchError>;
+
+    /// Page to open for `item` when the feed gave it no link of its own.
+    fn open_url(&self, profile: &GitHubAccount, item: &NotificationItem) -> String;
+}
+
+struct GitHubForge;
+
+impl ForgeProvider for GitHubForge {
+    async fn fetch_inbox(
+        &self,
+        client: &Client,
+        profile: &GitHubAccount,
+        options: InboxFetchOptions,
+        progress: &Progress,
+    ) -> Result<InboxSnapshot, FetchError> {
+        github::fetch_inbox(client, profile, options, progress).await
+    }
+
+    async fn mark_read(
+        &self,
+        client: &Client,
+        profile: &GitHubAccount,
+        thread_id: &str,
+    ) -> Result<(), FetchError> {
+        github::mark_notification_read(client, profile, thread_id).await
+    }
+
+    async fn mark_done(
+        &self,
+        client: &Client,
+        profile: &GitHubAccount,
+        thread_id: &str,
+    ) -> Result<(), FetchError> {
+        github::mark_notification_done(client, profile, thread_id).await
+    }
+
+    async fn verify_token(
+        &self,
+        client: &Client,
+        profile: &GitHubAccount,
+    ) -> Result<(), FetchError> {
+        github::verify_token(client, profile).await
+    }
+
+    fn open_url(&self, profile: &GitHubAccount, item: &NotificationItem) -> String {
+        format!("{}/{}", profile.web_base(), item.repo)
+    }
+}
+
+pub async fn fetch_inbox(
+    client: &Client,
+    profile: &GitHubAccount,
+    options: InboxFetchOptions,
+    progress: &Progress,
+) -> Result<InboxSnapshot, FetchError> {
+    let mut inbox = match profile.forge {
+        Forge::GitHub => {
+            GitHubForge
+                .fetch_inbox(client, profile, options, progress)
+                .await?
+        }
+        Forge::GitLab => {
+            GitLabForge
+                .fetch_inbox(client, profile, options, progress)
+                .await?
+        }
+        Forge::Gitea => {
+            GiteaForge
+                .fetch_inbox(client, profile, options, progress)
+                .await?
+        }
+    };
+    inbox.tag_provider(profile.forge);
+    for item in &mut inbox.notifications {
+        if item.url.is_none() {
+            item.url = Some(open_url(profile, item));
+        }
+    }
+    Ok(inbox)
+}
+
+pub async fn mark_read(
+    client: &Client,
+    profile: &GitHubAccount,
+    thread_id: &str,
+) -> Result<(), FetchError> {
+    match profile.forge {
+        Forge::GitHub => GitHubForge.mark_read(client, profile, thread_id).await,
+        Forge::GitLab => GitLabForge.mark_read(client, profile, thread_id).await,
+        Forge::Gitea => GiteaForge.mark_read(client, profile, thread_id).await,
+    }
+}
+
+pub async fn mark_done(
+    client: &Client,
+    profile: &GitHubAccount,
+    thread_id: &str,
+) -> Result<(), FetchError> {
+    match profile.forge {
+        Forge::GitHub => GitHubForge.mark_done(client, profile, thread_id).await,
+        Forge::GitLab => GitLabForge.mark_done(client, profile, thread_id).await,
+        Forge::Gitea => GiteaForge.mark_done(client, profile, thread_id).await,
+    }
+}
+
+pub async fn verify_token(client: &Client, profile: &GitHubAccount) -> Result<(), FetchError> {
+    match profile.forge {
+        Forge::GitHub => GitHubForge.verify_token(client, profile).await,
+        Forge::GitLab => GitLabForge.verify_token(client, profile).await,
+        Forge::Gitea => GiteaForge.verify_token(client, profile).await,
+    }
+}
+
+pub fn open_url(profile: &GitHubAccount, item: &NotificationItem) -> String {
+    match item.provider {
+        Forge::GitHub => GitHubForge.open_url(profile, item),
+        Forge::GitLab => GitLabForge.open_url(profile, item),
+        Forge::Gitea => GiteaForge.open_url(profile, item),
+    }
+}
+
+/// The login `/user` reports must match the account being added.
+fn check_login(profile: &GitHubAccount, actual: String) -> Result<(), FetchError> {
+    if actual.eq_ignore_ascii_case(&profile.login) {
+        Ok(())
+    } else {
+        Err(FetchError::LoginMismatch {
+            expected: profile.login.clone(),
+            actual,
+        })
+    }
+}
+
+/// A snapshot holding only notifications; the search lists are GitHub's.
+fn notification_inbox(notifications: Vec<NotificationItem>) -> InboxSnapshot {
+    InboxSnapshot {
+        notifications,
+        review_requests: Vec::new(),
+        mentions: Vec::new(),
+        recent_reviews: Vec::new(),
+        assigned_issues: Vec::new(),
+        authored_pull_requests: Vec::new(),
+        pull_request_statuses: Vec::new(),
+        fetched_at: Utc::now(),
+        rate_limit: None,
+        search_totals: SearchTotals::default(),
+        unread_threads: None,
+    }
+}
@@ -0,0 +1,209 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use reqwest::Client;
+use serde::Deserialize;
+
+use super::{ForgeProvider, check_login, notification_inbox};
+use crate::{
+    domain::{Forge, GitHubAccount, InboxSnapshot, NotificationItem, SearchCache},
+    github::{FetchError, InboxFetchOptions, SendChecked, get_paginated, with_retries},
+    task::Progress,
+};
+
+/// Gitea's (and Forgejo's) notification API, which mirrors GitHub's but has
+/// no done state; marking a thread done reads it.
+pub(super) struct GiteaForge;
+
+impl ForgeProvider for GiteaForge {
+    async fn fetch_inbox(
+        &self,
+        client: &Client,
+        profile: &GitHubAccount,
+        options: InboxFetchOptions,
+        progress: &Progress,
+    ) -> Result<InboxSnapshot, FetchError> {
+        if profile.token.is_empty() {
+            return Err(FetchError::MissingToken);
+        }
+        let all = if options.unread_only { "false" } else { "true" };
+        let url = format!("{}/notifications", profile.api_base());
+        let query = [("all", all), ("limit", "50")];
+        let threads = with_retries(
+            "Fetching notifications",
+            options.max_attempts,
+            progress,
+            || {
+                get_paginated(
+                    client,
+                    profile,
+                    &url,
+                    &query,
+                    options.max_pages,
+                    |page: Vec<ThreadResponse>| page,
+                )
+            },
+        )
+        .await?;
+        Ok(notification_inbox(
+            threads.into_iter().map(notification_from_thread).collect(),
+        ))
+    }
+
+    async fn mark_read(
+        &self,
+        client: &Client,
+        profile: &GitHubAccount,
+        thread_id: &str,
+    ) -> Result<(), FetchError> {
+        if profile.token.is_empty() {
+            return Err(FetchError::MissingToken);
+        }
+        client
+            .patch(format!(
+                "{}/notifications/threads/{thread_id}",
+                profile.api_base()
+            ))
+            .query(&[("to-status", "read")])
+            .bearer_auth(&profile.token)
+            .send_checked()
+            .await?;
+        Ok(())
+    }
+
+    async fn mark_done(
+        &self,
+        client: &Client,
+        profile: &GitHubAccount,
+        thread_id: &str,
+    ) -> Result<(), FetchError> {
+        self.mark_read(client, profile, thread_id).await
+    }
+
+    async fn verify_token(
+        &self,
+        client: &Client,
+        profile: &GitHubAccount,
+    ) -> Result<(), FetchError> {
+        if profile.token.is_empty() {
+            return Err(FetchError::MissingToken);
+        }
+        let user: UserResponse = client
+            .get(format!("{}/user", profile.api_base()))
+            .bearer_auth(&profile.token)
+            .send_checked()
+            .await
+            .map_err(|err| match err {
+                FetchError::Unauthorized => FetchError::TokenRejected,
+                err => err,
+            })?
+            .json()
+            .await?;
+        check_login(profile, user.login)
+    }
+
+    fn open_url(&self, profile: &GitHubAccount, _item: &NotificationItem) -> String {
+        format!("{}/notifications", profile.web_base())
+    }
+}
+
+fn notification_from_thread(thread: ThreadResponse) -> NotificationItem {
+    NotificationItem {
+        thread_id: thread.id.to_string(),
+        repo: thread.repository.full_name,
+        title: thread.subject.title,
+        url: thread.subject.html_url.filter(|url| !url.is_empty()),
+        head_ref: None,
+        base_ref: None,
+        my_review_status: None,
+        latest_comment_url: thread
+            .subject
+            .latest_comment_html_url
+            .filter(|url| !url.is_empty()),
+        subject_url: thread.subject.url,
+        provider: Forge::Gitea,
+        metadata: BTreeMap::from([(String::from("subject_type"), thread.subject.kind.clone())]),
+        // Gitea calls pull requests "Pull"; the app matches GitHub's name.
+        subject_type: match thread.subject.kind.as_str() {
+            "Pull" => String::from("PullRequest"),
+            _ => thread.subject.kind,
+        },
+        latest_actor: None,
+        // Gitea does not say why a thread is in the feed.
+        reason: String::from("subscribed"),
+        updated_at: thread.updated_at,
+        last_read_at: None,
+        unread: thread.unread,
+        search_cache: SearchCache::default(),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ThreadResponse {
+    id: u64,
+    repository: ThreadRepository,
+    subject: ThreadSubject,
+    unread: bool,
+    updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ThreadRepository {
+    full_name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ThreadSubject {
+    title: String,
+    #[serde(default)]
+    url: Option<String>,
+    #[serde(default)]
+    html_url: Option<String>,
+    #[serde(default)]
+    latest_comment_html_url: Option<String>,
+    #[serde(rename = "type", default)]
+    kind: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct UserResponse {
+    login: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ThreadResponse, notification_from_thread};
+
+    #[test]
+    fn threads_become_notifications() {
+        let thread: ThreadResponse = serde_json::from_str(
+            r#"{
+                "id": 5,
+                "repository": {"full_name": "acme/app"},
+                "subject": {
+                    "title": "Fix login",
+                    "url": "https://git.mycorp.com/api/v1/repos/acme/app/pulls/7",
+                    "html_url": "https://git.mycorp.com/acme/app/pulls/7",
+                    "latest_comment_html_url": "",
+                    "type": "Pull",
+                    "state": "open"
+                },
+                "unread": true,
+                "pinned": false,
+                "updated_at": "2026-03-02T10:00:00Z"
+            }"#,
+        )
+        .expect("thread");
+
+        let item = notification_from_thread(thread);
+
+        assert_eq!(item.thread_id, "5");
+        assert_eq!(item.repo, "acme/app");
+        assert_eq!(
+            item.url.as_deref(),
+            Some("https://git.mycorp.com/acme/app/pulls/7")
+        );
+        assert_eq!(item.subject_type, "PullRequest");
+        assert!(item.unread);
+    }
+}
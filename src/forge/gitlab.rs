@@ -0,0 +1,216 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use reqwest::Client;
+use serde::Deserialize;
+
+use super::{ForgeProvider, check_login, notification_inbox};
+use crate::{
+    domain::{Forge, GitHubAccount, InboxSnapshot, NotificationItem, SearchCache},
+    github::{FetchError, InboxFetchOptions, SendChecked, get_paginated, with_retries},
+    task::Progress,
+};
+
+/// GitLab's to-do list. To-dos have no read state, so reading one and
+/// marking it done both close it.
+pub(super) struct GitLabForge;
+
+impl ForgeProvider for GitLabForge {
+    async fn fetch_inbox(
+        &self,
+        client: &Client,
+        profile: &GitHubAccount,
+        options: InboxFetchOptions,
+        progress: &Progress,
+    ) -> Result<InboxSnapshot, FetchError> {
+        if profile.token.is_empty() {
+            return Err(FetchError::MissingToken);
+        }
+        let url = format!("{}/todos", profile.api_base());
+        let query = [("state", "pending"), ("per_page", "50")];
+        let todos = with_retries("Fetching to-dos", options.max_attempts, progress, || {
+            get_paginated(
+                client,
+                profile,
+                &url,
+                &query,
+                options.max_pages,
+                |page: Vec<TodoResponse>| page,
+            )
+        })
+        .await?;
+        Ok(notification_inbox(
+            todos.into_iter().map(notification_from_todo).collect(),
+        ))
+    }
+
+    async fn mark_read(
+        &self,
+        client: &Client,
+        profile: &GitHubAccount,
+        thread_id: &str,
+    ) -> Result<(), FetchError> {
+        self.mark_done(client, profile, thread_id).await
+    }
+
+    async fn mark_done(
+        &self,
+        client: &Client,
+        profile: &GitHubAccount,
+        thread_id: &str,
+    ) -> Result<(), FetchError> {
+        if profile.token.is_empty() {
+            return Err(FetchError::MissingToken);
+        }
+        client
+            .post(format!(
+                "{}/todos/{thread_id}/mark_as_done",
+                profile.api_base()
+            ))
+            .bearer_auth(&profile.token)
+            .send_checked()
+            .await?;
+        Ok(())
+    }
+
+    async fn verify_token(
+        &self,
+        client: &Client,
+        profile: &GitHubAccount,
+    ) -> Result<(), FetchError> {
+        if profile.token.is_empty() {
+            return Err(FetchError::MissingToken);
+        }
+        let user: UserResponse = client
+            .get(format!("{}/user", profile.api_base()))
+            .bearer_auth(&profile.token)
+            .send_checked()
+            .await
+            .map_err(|err| match err {
+                FetchError::Unauthorized => FetchError::TokenRejected,
+                err => err,
+            })?
+            .json()
+            .await?;
+        check_login(profile, user.username)
+    }
+
+    fn open_url(&self, profile: &GitHubAccount, _item: &NotificationItem) -> String {
+        format!("{}/dashboard/todos", profile.web_base())
+    }
+}
+
+fn notification_from_todo(todo: TodoResponse) -> NotificationItem {
+    NotificationItem {
+        thread_id: todo.id.to_string(),
+        repo: todo
+            .project
+            .map(|project| project.path_with_namespace)
+            .unwrap_or_default(),
+        title: todo
+            .target
+            .and_then(|target| target.title)
+            .unwrap_or(todo.body),
+        url: todo.target_url,
+        head_ref: None,
+        base_ref: None,
+        my_review_status: None,
+        latest_comment_url: None,
+        subject_url: None,
+        subject_type: todo.target_type,
+        provider: Forge::GitLab,
+        // `reason` folds some actions into GitHub's names; keep the original.
+        metadata: BTreeMap::from([(String::from("action_name"), todo.action_name.clone())]),
+        latest_actor: todo.author.map(|author| author.username),
+        // Reasons the app already knows keep their GitHub names.
+        reason: match todo.action_name.as_str() {
+            "assigned" => String::from("assign"),
+            "mentioned" | "directly_addressed" => String::from("mention"),
+            _ => todo.action_name,
+        },
+        updated_at: todo.updated_at.unwrap_or(todo.created_at),
+        last_read_at: None,
+        unread: true,
+        search_cache: SearchCache::default(),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct TodoResponse {
+    id: u64,
+    #[serde(default)]
+    project: Option<TodoProject>,
+    #[serde(default)]
+    author: Option<TodoAuthor>,
+    action_name: String,
+    #[serde(default)]
+    target_type: String,
+    #[serde(default)]
+    target: Option<TodoTarget>,
+    #[serde(default)]
+    target_url: Option<String>,
+    #[serde(default)]
+    body: String,
+    created_at: DateTime<Utc>,
+    #[serde(default)]
+    updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TodoProject {
+    path_with_namespace: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct TodoAuthor {
+    username: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct TodoTarget {
+    #[serde(default)]
+    title: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct UserResponse {
+    username: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TodoResponse, notification_from_todo};
+    use crate::domain::Forge;
+
+    #[test]
+    fn todos_become_notifications() {
+        let todo: TodoResponse = serde_json::from_str(
+            r#"{
+                "id": 102,
+                "project": {"path_with_namespace": "acme/app"},
+                "author": {"username": "trinity"},
+                "action_name": "directly_addressed",
+                "target_type": "MergeRequest",
+                "target": {"title": "Fix login", "iid": 7},
+                "target_url": "https://gitlab.com/acme/app/-/merge_requests/7",
+                "body": "Fix login",
+                "state": "pending",
+                "created_at": "2026-03-02T09:00:00Z",
+                "updated_at": "2026-03-02T10:00:00Z"
+            }"#,
+        )
+        .expect("todo");
+
+        let item = notification_from_todo(todo);
+
+        assert_eq!(item.thread_id, "102");
+        assert_eq!(item.repo, "acme/app");
+        assert_eq!(item.title, "Fix login");
+        assert_eq!(item.reason, "mention");
+        assert_eq!(item.latest_actor.as_deref(), Some("trinity"));
+        assert_eq!(item.subject_type, "MergeRequest");
+        assert_eq!(item.provider, Forge::GitLab);
+        assert_eq!(item.metadata["action_name"], "directly_addressed");
+        assert!(item.unread);
+    }
+}
@@ -1,57 +1,650 @@
-use std::collections::{BTreeMap, HashSet};
+mod url_map;
 
-use chrono::{DateTime, Utc};
+use std::{
+    cell::Cell,
+    collections::{BTreeMap, BTreeSet, HashSet},
+    fs,
+    hash::{BuildHasher, RandomState},
+    io,
+    sync::{PoisonError, RwLock},
+    time::Duration,
+};
+
+use chrono::{DateTime, SecondsFormat, Utc};
 use reqwest::{
-    blocking::Client,
-    header::{ACCEPT, USER_AGENT},
+    Certificate, Client, ClientBuilder, NoProxy, Proxy, RequestBuilder, Response, StatusCode,
+    header::{ACCEPT, HeaderMap, LINK, RETRY_AFTER, USER_AGENT},
 };
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
 use thiserror::Error;
 
-use crate::domain::{
-    GitHubAccount, InboxSnapshot, MentionKind, MentionThread, NotificationItem, PullRequestKey,
-    PullRequestReviewer, PullRequestReviewerStatus, PullRequestReviewers, RepoPullRequest,
-    RepoPullRequestSnapshot, ReviewRequest, ReviewSummary,
+use self::url_map::{classify_thread, extract_repo_name, pull_request_number_from_html_url};
+use crate::{
+    domain::{
+        AssignedIssue, AuthoredPullRequest, CheckState, Forge, GitHubAccount, InboxBackend,
+        InboxSnapshot, MentionKind, MentionThread, NetworkSettings, NotificationItem,
+        PullRequestKey, PullRequestReviewer, PullRequestReviewerStatus, PullRequestReviewers,
+        PullRequestStatus, RateLimitStatus, RepoPullRequest, RepoPullRequestSnapshot,
+        ReviewDecision, ReviewRequest, ReviewSummary, ReviewVerdict, SearchCache, SearchHit,
+        SearchTotals, StaleBranch, ThreadComment, ThreadDetails, TimelineEvent, UnreadThreads,
+    },
+    task::Progress,
 };
 
-const GH_NOTIFICATIONS: &str = "https://api.github.com/notifications";
-const GH_NOTIFICATION_THREAD: &str = "https://api.github.com/notifications/threads";
-const GH_REPOS: &str = "https://api.github.com/repos";
-const GH_SEARCH_ISSUES: &str = "https://api.github.com/search/issues";
 const USER_AGENT_HEADER: &str = "reminder-egui/0.1";
+const SECONDARY_RATE_LIMIT_FALLBACK_SECS: i64 = 60;
+const OAUTH_SCOPES: &str = "notifications repo";
+const DEVICE_CODE_GRANT_TYPE: &str = "urn:ietf:params:oauth:grant-type:device_code";
+/// Timelines are oldest-first, so long threads need a few pages to reach
+/// the recent end.
+const TIMELINE_MAX_PAGES: usize = 5;
+const TIMELINE_SNIPPET_CHARS: usize = 80;
+const RETRY_BASE_DELAY_SECS: u64 = 5;
+const RETRY_MAX_DELAY_SECS: u64 = 60;
+const NOTIFICATIONS_PAGE_SIZE: usize = 50;
+/// Hits per search request, GitHub's maximum.
+const SEARCH_PAGE_SIZE: usize = 100;
+/// Pages of 100 check runs read per commit before giving up on it.
+const CHECK_RUN_MAX_PAGES: usize = 10;
+
+tokio::task_local! {
+    // Scoped to one inbox fetch, so concurrent fetches sharing the runtime's
+    // worker threads each see their own latest quota.
+    static LATEST_RATE_LIMIT: Cell<Option<RateLimitStatus>>;
+}
+
+pub(crate) trait SendChecked {
+    async fn send_checked(self) -> Result<Response, FetchError>;
+}
+
+impl SendChecked for RequestBuilder {
+    /// Sends the request, records the rate-limit headers, and turns primary
+    /// or secondary rate-limit rejections into [`FetchError::RateLimited`].
+    async fn send_checked(self) -> Result<Response, FetchError> {
+        let response = self.send().await?;
+        let rate_limit = rate_limit_from_headers(response.headers());
+        record_rate_limit(response.headers(), rate_limit);
+        if let Some(reset_at) = rate_limited_until(
+            response.status(),
+            response.headers(),
+            rate_limit,
+            Utc::now(),
+        ) {
+            return Err(FetchError::RateLimited { reset_at });
+        }
+        Ok(response.error_for_status()?)
+    }
+}
+
+/// Keeps only the core REST quota: search and GraphQL answers report their
+/// own, much smaller buckets, which would trip the backoff meant for core.
+fn record_rate_limit(headers: &HeaderMap, rate_limit: Option<RateLimitStatus>) {
+    let resource = headers
+        .get("x-ratelimit-resource")
+        .and_then(|value| value.to_str().ok());
+    if rate_limit.is_some() && resource.is_none_or(|resource| resource == "core") {
+        // Requests outside an inbox fetch have no quota to record.
+        let _ = LATEST_RATE_LIMIT.try_with(|latest| latest.set(rate_limit));
+    }
+}
+
+fn rate_limit_from_headers(headers: &HeaderMap) -> Option<RateLimitStatus> {
+    let number = |name: &str| headers.get(name)?.to_str().ok()?.trim().parse::<i64>().ok();
+    Some(RateLimitStatus {
+        limit: u32::try_from(number("x-ratelimit-limit")?).ok()?,
+        remaining: u32::try_from(number("x-ratelimit-remaining")?).ok()?,
+        reset_at: DateTime::from_timestamp(number("x-ratelimit-reset")?, 0)?,
+    })
+}
+
+fn rate_limited_until(
+    status: StatusCode,
+    headers: &HeaderMap,
+    rate_limit: Option<RateLimitStatus>,
+    now: DateTime<Utc>,
+) -> Option<DateTime<Utc>> {
+    if status != StatusCode::FORBIDDEN && status != StatusCode::TOO_MANY_REQUESTS {
+        return None;
+    }
+    let retry_after = headers
+        .get(RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<i64>().ok());
+    if let Some(seconds) = retry_after {
+        return Some(now + chrono::Duration::seconds(seconds));
+    }
+    match rate_limit {
+        Some(rate_limit) if rate_limit.remaining == 0 => Some(rate_limit.reset_at),
+        // A 429 without quota headers is still a secondary rate limit.
+        _ if status == StatusCode::TOO_MANY_REQUESTS => {
+            Some(now + chrono::Duration::seconds(SECONDARY_RATE_LIMIT_FALLBACK_SECS))
+        }
+        _ => None,
+    }
+}
 
-pub fn build_client() -> Result<Client, FetchError> {
-    Client::builder()
+/// Proxy and TLS settings every client built from now on uses; see
+/// [`configure_network`].
+static NETWORK: RwLock<NetworkSettings> = RwLock::new(NetworkSettings::DEFAULT);
+
+#[derive(Debug, Error)]
+pub enum NetworkError {
+    #[error("Invalid proxy {proxy}: {source}")]
+    Proxy {
+        proxy: String,
+        source: reqwest::Error,
+    },
+    #[error("Cannot read CA bundle {path}: {source}")]
+    ReadCaBundle { path: String, source: io::Error },
+    #[error("CA bundle {path} is not valid PEM: {source}")]
+    InvalidCaBundle {
+        path: String,
+        source: reqwest::Error,
+    },
+    #[error("CA bundle {0} holds no certificates")]
+    EmptyCaBundle(String),
+    #[error("Failed to initialize the HTTP client: {0}")]
+    Client(#[from] reqwest::Error),
+}
+
+/// Checks `settings` by building a client with them, then makes every later
+/// [`build_client`] use them. Existing clients keep their old settings.
+pub fn configure_network(settings: &NetworkSettings) -> Result<(), NetworkError> {
+    client_builder(settings)?.build()?;
+    *NETWORK.write().unwrap_or_else(PoisonError::into_inner) = settings.clone();
+    Ok(())
+}
+
+/// Without an explicit proxy reqwest honors `HTTPS_PROXY`, `ALL_PROXY` and
+/// `NO_PROXY` itself; an explicit one still respects `NO_PROXY`.
+fn client_builder(settings: &NetworkSettings) -> Result<ClientBuilder, NetworkError> {
+    let seconds = |secs: u64| (secs > 0).then(|| Duration::from_secs(secs));
+    let mut builder = Client::builder()
         .user_agent(USER_AGENT_HEADER)
-        .build()
-        .map_err(FetchError::Http)
+        .tcp_keepalive(seconds(settings.keep_alive_secs))
+        .pool_idle_timeout(seconds(settings.pool_idle_timeout_secs));
+    if let Some(timeout) = seconds(settings.connect_timeout_secs) {
+        builder = builder.connect_timeout(timeout);
+    }
+    if let Some(timeout) = seconds(settings.read_timeout_secs) {
+        builder = builder.read_timeout(timeout);
+    }
+    let proxy = settings.proxy.trim();
+    if !proxy.is_empty() {
+        let proxy = Proxy::all(proxy).map_err(|source| NetworkError::Proxy {
+            proxy: proxy.to_owned(),
+            source,
+        })?;
+        builder = builder.proxy(proxy.no_proxy(NoProxy::from_env()));
+    }
+    let path = settings.ca_bundle.trim();
+    if !path.is_empty() {
+        let pem = fs::read(path).map_err(|source| NetworkError::ReadCaBundle {
+            path: path.to_owned(),
+            source,
+        })?;
+        let certificates =
+            Certificate::from_pem_bundle(&pem).map_err(|source| NetworkError::InvalidCaBundle {
+                path: path.to_owned(),
+                source,
+            })?;
+        if certificates.is_empty() {
+            return Err(NetworkError::EmptyCaBundle(path.to_owned()));
+        }
+        for certificate in certificates {
+            builder = builder.add_root_certificate(certificate);
+        }
+    }
+    Ok(builder.danger_accept_invalid_certs(settings.skip_tls_verification))
 }
 
-pub fn fetch_inbox(client: &Client, profile: &GitHubAccount) -> Result<InboxSnapshot, FetchError> {
+/// Each account keeps one client so its requests share pooled connections.
+pub fn build_client() -> Result<Client, NetworkError> {
+    let settings = NETWORK
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone();
+    Ok(client_builder(&settings)?.build()?)
+}
+
+pub async fn fetch_inbox(
+    client: &Client,
+    profile: &GitHubAccount,
+    options: InboxFetchOptions,
+    progress: &Progress,
+) -> Result<InboxSnapshot, FetchError> {
     if profile.token.is_empty() {
         return Err(FetchError::MissingToken);
     }
 
-    let notifications = fetch_notifications(client, profile)?;
-    let review_requests = fetch_review_requests(client, profile)?;
-    let mentions = fetch_mentions(client, profile)?;
-    let recent_reviews = fetch_recent_reviews(client, profile)?;
+    let InboxFetchOptions {
+        max_pages,
+        unread_only,
+        since,
+        max_attempts,
+        ..
+    } = options;
+    LATEST_RATE_LIMIT
+        .scope(Cell::new(None), async {
+            let notifications =
+                with_retries("Fetching notifications", max_attempts, progress, || {
+                    fetch_notifications(client, profile, max_pages, unread_only, since)
+                })
+                .await?;
+            let lists = match profile.inbox_backend {
+                InboxBackend::Rest => {
+                    RestInbox
+                        .fetch_lists(client, profile, options, progress)
+                        .await?
+                }
+                InboxBackend::GraphQl => {
+                    match GraphQlInbox
+                        .fetch_lists(client, profile, options, progress)
+                        .await
+                    {
+                        Ok(lists) => lists,
+                        Err(err) if err.warrants_rest_fallback() => {
+                            RestInbox
+                                .fetch_lists(client, profile, options, progress)
+                                .await?
+                        }
+                        Err(err) => return Err(err),
+                    }
+                }
+            };
+            // A delta misses threads read elsewhere, since reading one does
+            // not bump its update time. Asked last, so a thread unread in
+            // the delta and missing here was read in between.
+            let unread_threads = match since {
+                Some(_) => Some(
+                    with_retries("Checking read threads", max_attempts, progress, || {
+                        fetch_unread_threads(client, profile)
+                    })
+                    .await?,
+                ),
+                None => None,
+            };
 
-    Ok(InboxSnapshot {
-        notifications,
-        review_requests,
-        mentions,
-        recent_reviews,
-        fetched_at: Utc::now(),
+            Ok(InboxSnapshot {
+                notifications,
+                review_requests: lists.review_requests,
+                mentions: lists.mentions,
+                recent_reviews: lists.recent_reviews,
+                assigned_issues: lists.assigned_issues,
+                authored_pull_requests: lists.authored_pull_requests,
+                pull_request_statuses: lists.pull_request_statuses,
+                fetched_at: Utc::now(),
+                rate_limit: LATEST_RATE_LIMIT.with(Cell::get),
+                search_totals: lists.search_totals,
+                unread_threads,
+            })
+        })
+        .await
+}
+
+/// The search-backed part of an inbox.
+#[derive(Debug, Default)]
+struct SearchLists {
+    review_requests: Vec<ReviewRequest>,
+    mentions: Vec<MentionThread>,
+    recent_reviews: Vec<ReviewSummary>,
+    assigned_issues: Vec<AssignedIssue>,
+    authored_pull_requests: Vec<AuthoredPullRequest>,
+    pull_request_statuses: Vec<(PullRequestKey, PullRequestStatus)>,
+    search_totals: SearchTotals,
+}
+
+/// A way of fetching [`SearchLists`], picked per account by
+/// [`InboxBackend`].
+trait InboxProvider {
+    async fn fetch_lists(
+        &self,
+        client: &Client,
+        profile: &GitHubAccount,
+        options: InboxFetchOptions,
+        progress: &Progress,
+    ) -> Result<SearchLists, FetchError>;
+}
+
+/// One search page per list, plus pages the user loaded more of, and an
+/// issue-events request per review request to find who asked. Search allows
+/// 30 requests a minute, so only notifications follow `max_pages`.
+struct RestInbox;
+
+impl InboxProvider for RestInbox {
+    async fn fetch_lists(
+        &self,
+        client: &Client,
+        profile: &GitHubAccount,
+        options: InboxFetchOptions,
+        progress: &Progress,
+    ) -> Result<SearchLists, FetchError> {
+        let InboxFetchOptions {
+            max_attempts,
+            extra_search_pages,
+            ..
+        } = options;
+        let review_requests =
+            with_retries("Fetching review requests", max_attempts, progress, || {
+                fetch_review_requests(client, profile, 1, 1 + extra_search_pages.review_requests)
+            })
+            .await?;
+        let mentions = with_retries("Fetching mentions", max_attempts, progress, || {
+            fetch_mentions(client, profile, 1, 1 + extra_search_pages.mentions)
+        })
+        .await?;
+        let recent_reviews =
+            with_retries("Fetching recent reviews", max_attempts, progress, || {
+                fetch_recent_reviews(client, profile)
+            })
+            .await?;
+        let assigned_issues =
+            with_retries("Fetching assigned issues", max_attempts, progress, || {
+                fetch_assigned_issues(client, profile)
+            })
+            .await?;
+        let authored_pull_requests = with_retries(
+            "Fetching your pull requests",
+            max_attempts,
+            progress,
+            || fetch_authored_pull_requests(client, profile),
+        )
+        .await?;
+        Ok(SearchLists {
+            review_requests: review_requests.items,
+            mentions: mentions.items,
+            recent_reviews,
+            assigned_issues,
+            authored_pull_requests,
+            pull_request_statuses: Vec::new(),
+            search_totals: SearchTotals {
+                review_requests: review_requests.total_count,
+                mentions: mentions.total_count,
+            },
+        })
+    }
+}
+
+/// Every list, with pull request badges and requesters, in one query of up
+/// to 100 hits per list.
+struct GraphQlInbox;
+
+impl InboxProvider for GraphQlInbox {
+    async fn fetch_lists(
+        &self,
+        client: &Client,
+        profile: &GitHubAccount,
+        options: InboxFetchOptions,
+        progress: &Progress,
+    ) -> Result<SearchLists, FetchError> {
+        let mut lists = with_retries(
+            "Fetching lists over GraphQL",
+            options.max_attempts,
+            progress,
+            || fetch_graphql_lists(client, profile),
+        )
+        .await?;
+        // The query stops at the first 100 hits; pages loaded past them
+        // come from REST search, which pages the same way.
+        let extra = options.extra_search_pages;
+        if extra.review_requests > 0 {
+            let more = with_retries(
+                "Fetching more review requests",
+                options.max_attempts,
+                progress,
+                || fetch_review_requests(client, profile, 2, extra.review_requests),
+            )
+            .await?;
+            append_new_hits(&mut lists.review_requests, more.items, |request| {
+                &request.url
+            });
+        }
+        if extra.mentions > 0 {
+            let more = with_retries(
+                "Fetching more mentions",
+                options.max_attempts,
+                progress,
+                || fetch_mentions(client, profile, 2, extra.mentions),
+            )
+            .await?;
+            append_new_hits(&mut lists.mentions, more.items, |mention| &mention.url);
+        }
+        Ok(lists)
+    }
+}
+
+/// Appends the `more` hits whose URL is not in `hits` yet.
+pub fn append_new_hits<T>(hits: &mut Vec<T>, more: Vec<T>, url: impl Fn(&T) -> &String) {
+    let known: HashSet<String> = hits.iter().map(|hit| url(hit).clone()).collect();
+    hits.extend(more.into_iter().filter(|hit| !known.contains(url(hit))));
+}
+
+/// Runs `request` up to `max_attempts` times, waiting a jittered, doubling
+/// delay after each retryable failure and counting it down in `progress`.
+pub(crate) async fn with_retries<T, Fut>(
+    stage: &str,
+    max_attempts: u32,
+    progress: &Progress,
+    mut request: impl FnMut() -> Fut,
+) -> Result<T, FetchError>
+where
+    Fut: Future<Output = Result<T, FetchError>>,
+{
+    let mut attempt = 1;
+    loop {
+        progress.report(format!("{stage}…"));
+        match request().await {
+            Err(err) if err.is_retryable() && attempt < max_attempts => {
+                let jitter = RandomState::new().hash_one(attempt) % 1000;
+                let delay = retry_delay(attempt, jitter as f64 / 1000.0);
+                attempt += 1;
+                for remaining in (1..=delay.as_secs()).rev() {
+                    progress.report(format!(
+                        "{stage} failed; retrying in {remaining}s (attempt {attempt} of {max_attempts})"
+                    ));
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                }
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Doubles from [`RETRY_BASE_DELAY_SECS`] up to [`RETRY_MAX_DELAY_SECS`],
+/// plus up to a quarter extra (`jitter` in `0.0..1.0`) so accounts that
+/// failed together do not retry in lockstep.
+fn retry_delay(failed_attempt: u32, jitter: f64) -> Duration {
+    let base = RETRY_BASE_DELAY_SECS
+        .saturating_mul(1 << failed_attempt.saturating_sub(1).min(10))
+        .min(RETRY_MAX_DELAY_SECS);
+    Duration::from_secs(base + (base as f64 * 0.25 * jitter) as u64)
+}
+
+/// Starts GitHub's OAuth device flow. `web_base` is the browser-facing host,
+/// see [`GitHubAccount::web_base`].
+pub async fn request_device_code(
+    client: &Client,
+    web_base: &str,
+    client_id: &str,
+) -> Result<DeviceCode, FetchError> {
+    client
+        .post(format!("{web_base}/login/device/code"))
+        .header(USER_AGENT, USER_AGENT_HEADER)
+        .header(ACCEPT, "application/json")
+        .form(&[("client_id", client_id), ("scope", OAUTH_SCOPES)])
+        .send_checked()
+        .await?
+        .json()
+        .await
+        .map_err(FetchError::from)
+}
+
+pub async fn poll_device_token(
+    client: &Client,
+    web_base: &str,
+    client_id: &str,
+    device_code: &str,
+) -> Result<DeviceTokenPoll, FetchError> {
+    let response: DeviceTokenResponse = client
+        .post(format!("{web_base}/login/oauth/access_token"))
+        .header(USER_AGENT, USER_AGENT_HEADER)
+        .header(ACCEPT, "application/json")
+        .form(&[
+            ("client_id", client_id),
+            ("device_code", device_code),
+            ("grant_type", DEVICE_CODE_GRANT_TYPE),
+        ])
+        .send_checked()
+        .await?
+        .json()
+        .await?;
+    device_token_poll(response)
+}
+
+fn device_token_poll(response: DeviceTokenResponse) -> Result<DeviceTokenPoll, FetchError> {
+    if let Some(token) = response.access_token {
+        return Ok(DeviceTokenPoll::Granted(token));
+    }
+    match response.error.as_deref() {
+        Some("authorization_pending") => Ok(DeviceTokenPoll::Pending),
+        Some("slow_down") => Ok(DeviceTokenPoll::SlowDown {
+            interval: response.interval,
+        }),
+        _ => Err(FetchError::DeviceFlow(
+            response
+                .error_description
+                .or(response.error)
+                .unwrap_or_else(|| String::from("GitHub returned no access token")),
+        )),
+    }
+}
+
+/// Login of the user that owns `profile.token`.
+pub async fn fetch_authenticated_login(
+    client: &Client,
+    profile: &GitHubAccount,
+) -> Result<String, FetchError> {
+    if profile.token.is_empty() {
+        return Err(FetchError::MissingToken);
+    }
+
+    let user: UserResponse = client
+        .get(format!("{}/user", profile.api_base()))
+        .header(USER_AGENT, USER_AGENT_HEADER)
+        .header(ACCEPT, "application/vnd.github+json")
+        .bearer_auth(&profile.token)
+        .send_checked()
+        .await?
+        .json()
+        .await?;
+    Ok(user.login)
+}
+
+/// Login of the GraphQL `viewer`, i.e. whoever the token currently belongs to.
+pub async fn fetch_viewer_login(
+    client: &Client,
+    profile: &GitHubAccount,
+) -> Result<String, FetchError> {
+    if profile.token.is_empty() {
+        return Err(FetchError::MissingToken);
+    }
+
+    let response: ViewerResponse = client
+        .post(profile.graphql_url())
+        .header(USER_AGENT, USER_AGENT_HEADER)
+        .bearer_auth(&profile.token)
+        .json(&GraphQlQuery {
+            query: "query { viewer { login } }",
+        })
+        .send_checked()
+        .await?
+        .json()
+        .await?;
+    match response.data {
+        Some(data) => Ok(data.viewer.login),
+        None => Err(FetchError::GraphQl(
+            response
+                .errors
+                .into_iter()
+                .map(|error| error.message)
+                .collect::<Vec<_>>()
+                .join("; "),
+        )),
+    }
+}
+
+/// Checks a freshly entered token before the account is saved: it must be
+/// accepted, belong to `profile.login`, and be able to read notifications.
+pub async fn verify_token(client: &Client, profile: &GitHubAccount) -> Result<(), FetchError> {
+    if profile.token.is_empty() {
+        return Err(FetchError::MissingToken);
+    }
+
+    let response = client
+        .get(format!("{}/user", profile.api_base()))
+        .header(USER_AGENT, USER_AGENT_HEADER)
+        .header(ACCEPT, "application/vnd.github+json")
+        .bearer_auth(&profile.token)
+        .send_checked()
+        .await
+        .map_err(|err| token_error(err, FetchError::TokenRejected))?;
+    let scopes = response
+        .headers()
+        .get("x-oauth-scopes")
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_owned);
+    let user: UserResponse = response.json().await?;
+    if !user.login.eq_ignore_ascii_case(&profile.login) {
+        return Err(FetchError::LoginMismatch {
+            expected: profile.login.clone(),
+            actual: user.login,
+        });
+    }
+    if lacks_notifications_scope(scopes.as_deref()) {
+        return Err(FetchError::MissingNotificationsScope);
+    }
+
+    client
+        .get(format!("{}/notifications", profile.api_base()))
+        .query(&[("per_page", "1")])
+        .header(USER_AGENT, USER_AGENT_HEADER)
+        .header(ACCEPT, "application/vnd.github+json")
+        .bearer_auth(&profile.token)
+        .send_checked()
+        .await
+        .map_err(|err| token_error(err, FetchError::MissingNotificationsScope))?;
+    Ok(())
+}
+
+/// Classic tokens list their scopes in `X-OAuth-Scopes`; either `notifications`
+/// or `repo` grants access to the notifications API. Tokens without the header
+/// (fine-grained or app tokens) are judged by the notifications probe instead.
+fn lacks_notifications_scope(scopes: Option<&str>) -> bool {
+    scopes.is_some_and(|scopes| {
+        !scopes
+            .split(',')
+            .map(str::trim)
+            .any(|scope| scope == "notifications" || scope == "repo")
     })
 }
 
-pub fn fetch_notification_metadata_updates(
+/// Replaces auth failures (401/403/404) with a more specific error.
+fn token_error(err: FetchError, auth_error: FetchError) -> FetchError {
+    match err {
+        FetchError::Unauthorized => FetchError::TokenRejected,
+        FetchError::Forbidden | FetchError::NotFound => auth_error,
+        _ => err,
+    }
+}
+
+pub async fn fetch_notification_metadata_updates(
     client: &Client,
     profile: &GitHubAccount,
     notifications: &[NotificationItem],
 ) -> Result<Vec<NotificationMetadataUpdate>, FetchError> {
     let mut metadata_cache = BTreeMap::<PullRequestKey, NotificationPullRequestMetadata>::new();
+    let mut actor_cache = BTreeMap::<String, Option<String>>::new();
+    let mut html_url_cache = BTreeMap::<String, Option<String>>::new();
     let mut updates = Vec::new();
 
     for item in notifications {
@@ -61,8 +654,30 @@ pub fn fetch_notification_metadata_updates(
             &item.repo,
             item.url.as_deref(),
             &mut metadata_cache,
-        )?;
-        if metadata == NotificationPullRequestMetadata::default() {
+        )
+        .await?;
+        let latest_actor = notification_latest_actor(
+            client,
+            profile,
+            item.latest_comment_url.as_deref(),
+            &mut actor_cache,
+        )
+        .await;
+        let html_url = match notification_subject_html_url(
+            client,
+            profile,
+            item.html_url_source(),
+            &mut html_url_cache,
+        )
+        .await
+        {
+            Some(html_url) => Some(html_url),
+            None => discussion_html_url(client, profile, item, &mut html_url_cache).await,
+        };
+        if metadata == NotificationPullRequestMetadata::default()
+            && latest_actor.is_none()
+            && html_url.is_none()
+        {
             continue;
         }
         updates.push(NotificationMetadataUpdate {
@@ -70,37 +685,184 @@ pub fn fetch_notification_metadata_updates(
             head_ref: metadata.head_ref,
             base_ref: metadata.base_ref,
             my_review_status: metadata.my_review_status,
+            latest_actor,
+            html_url,
         });
     }
 
     Ok(updates)
 }
 
-pub fn mark_notification_done(
+/// Pull requests looked up per GraphQL request.
+const PULL_REQUEST_STATUS_BATCH: usize = 20;
+
+/// Pull requests whose status badges are wanted. Lookups are answered in
+/// batches and cached by repo and number until the thread sees newer
+/// activity or the entry ages out, so a refresh rarely costs a request.
+#[derive(Debug, Default)]
+pub struct PullRequestStatusQueue {
+    pending: BTreeSet<PullRequestKey>,
+    cache: BTreeMap<PullRequestKey, (DateTime<Utc>, PullRequestStatus)>,
+}
+
+impl PullRequestStatusQueue {
+    /// Queues `key` unless a lookup made after `updated_at` and within `ttl`
+    /// is cached.
+    pub fn request(
+        &mut self,
+        key: PullRequestKey,
+        updated_at: DateTime<Utc>,
+        now: DateTime<Utc>,
+        ttl: chrono::Duration,
+    ) {
+        let fresh = self
+            .cache
+            .get(&key)
+            .is_some_and(|(fetched_at, _)| *fetched_at >= updated_at && now - *fetched_at < ttl);
+        if !fresh {
+            self.pending.insert(key);
+        }
+    }
+
+    pub fn status(&self, key: &PullRequestKey) -> Option<&PullRequestStatus> {
+        self.cache.get(key).map(|(_, status)| status)
+    }
+
+    pub fn has_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    pub fn next_batch(&mut self) -> Vec<PullRequestKey> {
+        let batch: Vec<_> = self
+            .pending
+            .iter()
+            .take(PULL_REQUEST_STATUS_BATCH)
+            .cloned()
+            .collect();
+        for key in &batch {
+            self.pending.remove(key);
+        }
+        batch
+    }
+
+    /// Caches statuses that arrived with the inbox itself.
+    pub fn seed(&mut self, statuses: &[(PullRequestKey, PullRequestStatus)], now: DateTime<Utc>) {
+        for (key, status) in statuses {
+            self.pending.remove(key);
+            self.cache.insert(key.clone(), (now, status.clone()));
+        }
+    }
+
+    /// Stores the answers for `batch`. Pull requests GitHub did not return
+    /// (deleted, or no longer readable) are cached without badges so they
+    /// are not asked about again right away.
+    pub fn record(
+        &mut self,
+        batch: &[PullRequestKey],
+        mut statuses: BTreeMap<PullRequestKey, PullRequestStatus>,
+        now: DateTime<Utc>,
+        ttl: chrono::Duration,
+    ) {
+        self.cache
+            .retain(|_, (fetched_at, _)| now - *fetched_at < ttl);
+        for key in batch {
+            let status = statuses.remove(key).unwrap_or_default();
+            self.cache.insert(key.clone(), (now, status));
+        }
+    }
+}
+
+pub type PullRequestStatusOutcome = Result<BTreeMap<PullRequestKey, PullRequestStatus>, FetchError>;
+
+/// Draft, mergeable, check and review state for `keys`, in one GraphQL
+/// request.
+pub async fn fetch_pull_request_statuses(
+    client: &Client,
+    profile: &GitHubAccount,
+    keys: &[PullRequestKey],
+) -> PullRequestStatusOutcome {
+    if profile.token.is_empty() {
+        return Err(FetchError::MissingToken);
+    }
+
+    let query = pull_request_status_query(keys);
+    let response: PullRequestStatusResponse = client
+        .post(profile.graphql_url())
+        .header(USER_AGENT, USER_AGENT_HEADER)
+        .bearer_auth(&profile.token)
+        .json(&GraphQlQuery { query: &query })
+        .send_checked()
+        .await?
+        .json()
+        .await?;
+    // Unreadable repositories come back as null next to an error, so errors
+    // only fail the batch when there is no data at all.
+    let Some(data) = response.data else {
+        return Err(FetchError::GraphQl(
+            response
+                .errors
+                .into_iter()
+                .map(|error| error.message)
+                .collect::<Vec<_>>()
+                .join("; "),
+        ));
+    };
+    Ok(keys
+        .iter()
+        .enumerate()
+        .filter_map(|(index, key)| {
+            let pull_request = data
+                .get(&format!("pr{index}"))?
+                .as_ref()?
+                .pull_request
+                .as_ref()?;
+            Some((key.clone(), pull_request.status()))
+        })
+        .collect())
+}
+
+fn pull_request_status_query(keys: &[PullRequestKey]) -> String {
+    let mut query = String::from("query {");
+    for (index, (repo, number)) in keys.iter().enumerate() {
+        let Some((owner, name)) = repo.split_once('/') else {
+            continue;
+        };
+        // JSON string literals are valid GraphQL strings.
+        let owner = serde_json::Value::from(owner);
+        let name = serde_json::Value::from(name);
+        query.push_str(&format!(
+            " pr{index}: repository(owner: {owner}, name: {name}) {{ pullRequest(number: {number}) \
+             {{ isDraft mergeable reviewDecision \
+             commits(last: 1) {{ nodes {{ commit {{ statusCheckRollup {{ state }} }} }} }} }} }}"
+        ));
+    }
+    query.push_str(" }");
+    query
+}
+
+pub async fn mark_notification_done(
     client: &Client,
     profile: &GitHubAccount,
     thread_id: &str,
 ) -> Result<(), FetchError> {
-    // This endpoint remains for future use, but UI-triggered "Done" actions are
-    // currently disabled because GitHub's notifications feed cannot be filtered
-    // to exclude already-archived items. Removing the call entirely would make
-    // re-enabling the workflow harder if GitHub adds proper server-side filtering.
+    // GitHub's feed cannot exclude archived threads, so callers keep a local
+    // tombstone (`DoneThreads`) to hide them after a refresh.
     if profile.token.is_empty() {
         return Err(FetchError::MissingToken);
     }
 
-    let url = format!("{GH_NOTIFICATION_THREAD}/{thread_id}");
+    let url = format!("{}/notifications/threads/{thread_id}", profile.api_base());
     client
         .delete(url)
         .header(USER_AGENT, USER_AGENT_HEADER)
         .header(ACCEPT, "application/vnd.github+json")
         .bearer_auth(&profile.token)
-        .send()?
-        .error_for_status()?;
+        .send_checked()
+        .await?;
     Ok(())
 }
 
-pub fn mark_notification_read(
+pub async fn mark_notification_read(
     client: &Client,
     profile: &GitHubAccount,
     thread_id: &str,
@@ -109,18 +871,112 @@ pub fn mark_notification_read(
         return Err(FetchError::MissingToken);
     }
 
-    let url = format!("{GH_NOTIFICATION_THREAD}/{thread_id}");
+    let url = format!("{}/notifications/threads/{thread_id}", profile.api_base());
     client
         .patch(url)
         .header(USER_AGENT, USER_AGENT_HEADER)
         .header(ACCEPT, "application/vnd.github+json")
         .bearer_auth(&profile.token)
-        .send()?
-        .error_for_status()?;
+        .send_checked()
+        .await?;
+    Ok(())
+}
+
+#[derive(Deserialize)]
+struct GistResponse {
+    id: String,
+    #[serde(default)]
+    files: BTreeMap<String, GistFileResponse>,
+}
+
+#[derive(Deserialize)]
+struct GistFileResponse {
+    content: Option<String>,
+}
+
+/// Contents of `file` in gist `gist_id`, or `None` while the gist lacks it.
+pub async fn fetch_gist_file(
+    client: &Client,
+    profile: &GitHubAccount,
+    gist_id: &str,
+    file: &str,
+) -> Result<Option<String>, FetchError> {
+    if profile.token.is_empty() {
+        return Err(FetchError::MissingToken);
+    }
+    let gist: GistResponse = client
+        .get(format!("{}/gists/{gist_id}", profile.api_base()))
+        .header(USER_AGENT, USER_AGENT_HEADER)
+        .header(ACCEPT, "application/vnd.github+json")
+        .bearer_auth(&profile.token)
+        .send_checked()
+        .await?
+        .json()
+        .await?;
+    Ok(gist.files.get(file).and_then(|file| file.content.clone()))
+}
+
+/// Writes `file` into gist `gist_id`, or into a new secret gist when there
+/// is none yet. Returns the gist's id.
+pub async fn write_gist_file(
+    client: &Client,
+    profile: &GitHubAccount,
+    gist_id: Option<&str>,
+    file: &str,
+    content: String,
+) -> Result<String, FetchError> {
+    if profile.token.is_empty() {
+        return Err(FetchError::MissingToken);
+    }
+    let files = serde_json::json!({ file: { "content": content } });
+    let request = match gist_id {
+        Some(gist_id) => client
+            .patch(format!("{}/gists/{gist_id}", profile.api_base()))
+            .json(&serde_json::json!({ "files": files })),
+        None => client
+            .post(format!("{}/gists", profile.api_base()))
+            .json(&serde_json::json!({
+                "description": "Reminder triage state",
+                "public": false,
+                "files": files,
+            })),
+    };
+    let gist: GistResponse = request
+        .header(USER_AGENT, USER_AGENT_HEADER)
+        .header(ACCEPT, "application/vnd.github+json")
+        .bearer_auth(&profile.token)
+        .send_checked()
+        .await?
+        .json()
+        .await?;
+    Ok(gist.id)
+}
+
+/// Mutes the thread until the user comments on it or is @mentioned again.
+pub async fn unsubscribe_notification_thread(
+    client: &Client,
+    profile: &GitHubAccount,
+    thread_id: &str,
+) -> Result<(), FetchError> {
+    if profile.token.is_empty() {
+        return Err(FetchError::MissingToken);
+    }
+
+    let url = format!(
+        "{}/notifications/threads/{thread_id}/subscription",
+        profile.api_base()
+    );
+    client
+        .delete(url)
+        .header(USER_AGENT, USER_AGENT_HEADER)
+        .header(ACCEPT, "application/vnd.github+json")
+        .bearer_auth(&profile.token)
+        .send_checked()
+        .await?;
     Ok(())
 }
 
-pub fn fetch_repo_pull_requests(
+pub async fn fetch_repo_pull_requests(
     client: &Client,
     profile: &GitHubAccount,
     repo: &str,
@@ -129,7 +985,7 @@ pub fn fetch_repo_pull_requests(
         return Err(FetchError::MissingToken);
     }
 
-    let url = format!("{GH_REPOS}/{repo}/pulls");
+    let url = format!("{}/repos/{repo}/pulls", profile.api_base());
     let response: Vec<PullRequestResponse> = client
         .get(url)
         .query(&[
@@ -141,14 +997,17 @@ pub fn fetch_repo_pull_requests(
         .header(USER_AGENT, USER_AGENT_HEADER)
         .header(ACCEPT, "application/vnd.github+json")
         .bearer_auth(&profile.token)
-        .send()?
-        .error_for_status()?
-        .json()?;
+        .send_checked()
+        .await?
+        .json()
+        .await?;
 
     let mut pull_requests = Vec::with_capacity(response.len());
     for item in response {
         let my_review_status =
-            fetch_latest_review_status_for_user(client, profile, repo, item.number).unwrap_or(None);
+            fetch_latest_review_status_for_user(client, profile, repo, item.number)
+                .await
+                .unwrap_or(None);
         pull_requests.push(RepoPullRequest {
             repo: repo.to_owned(),
             number: item.number,
@@ -169,7 +1028,7 @@ pub fn fetch_repo_pull_requests(
     })
 }
 
-pub fn fetch_pull_request_reviewers(
+pub async fn fetch_pull_request_reviewers(
     client: &Client,
     profile: &GitHubAccount,
     repo: &str,
@@ -179,11 +1038,11 @@ pub fn fetch_pull_request_reviewers(
         return Err(FetchError::MissingToken);
     }
 
-    let requested_reviewers = fetch_requested_reviewers(client, profile, repo, pr_number)?;
-    let issue_events = fetch_issue_events(client, profile, repo, pr_number)?;
-    let latest_review_states = latest_submitted_reviews_by_reviewer(fetch_pull_request_reviews(
-        client, profile, repo, pr_number,
-    )?);
+    let requested_reviewers = fetch_requested_reviewers(client, profile, repo, pr_number).await?;
+    let issue_events = fetch_issue_events(client, profile, repo, pr_number).await?;
+    let latest_review_states = latest_submitted_reviews_by_reviewer(
+        fetch_pull_request_reviews(client, profile, repo, pr_number).await?,
+    );
     let reviewer_history = review_request_history_from_issue_events(&issue_events);
 
     Ok(PullRequestReviewers {
@@ -198,30 +1057,248 @@ pub fn fetch_pull_request_reviewers(
     })
 }
 
-pub fn request_pull_request_reviewer(
+/// The `limit` most recent comments, reviews, pushes and label changes on an
+/// issue or pull request, newest first.
+pub async fn fetch_thread_timeline(
     client: &Client,
     profile: &GitHubAccount,
     repo: &str,
-    pr_number: u64,
-    reviewer_login: &str,
-) -> Result<(), FetchError> {
+    number: u64,
+    limit: usize,
+) -> Result<Vec<TimelineEvent>, FetchError> {
     if profile.token.is_empty() {
         return Err(FetchError::MissingToken);
     }
 
-    let url = format!("{GH_REPOS}/{repo}/pulls/{pr_number}/requested_reviewers");
-    client
+    let url = format!(
+        "{}/repos/{repo}/issues/{number}/timeline",
+        profile.api_base()
+    );
+    let mut events = get_paginated(
+        client,
+        profile,
+        &url,
+        &[("per_page", "100")],
+        TIMELINE_MAX_PAGES,
+        |page: Vec<serde_json::Value>| {
+            // Event shapes vary a lot; skip the ones we cannot read instead
+            // of failing the whole timeline.
+            page.into_iter()
+                .filter_map(|value| serde_json::from_value(value).ok())
+                .filter_map(timeline_event)
+                .collect()
+        },
+    )
+    .await?;
+    events.sort_by_key(|event| std::cmp::Reverse(event.at));
+    events.truncate(limit);
+    Ok(events)
+}
+
+/// Title, author, labels and body of an issue or pull request with its
+/// `comment_limit` latest comments.
+pub async fn fetch_thread_details(
+    client: &Client,
+    profile: &GitHubAccount,
+    repo: &str,
+    number: u64,
+    comment_limit: usize,
+) -> Result<ThreadDetails, FetchError> {
+    if profile.token.is_empty() {
+        return Err(FetchError::MissingToken);
+    }
+
+    let url = format!("{}/repos/{repo}/issues/{number}", profile.api_base());
+    let issue: ThreadDetailsResponse = get_json(client, profile, &url).await?;
+    let comments = if issue.comments == 0 || comment_limit == 0 {
+        Vec::new()
+    } else {
+        // Comments are oldest first, so the newest are on the last page.
+        let last_page = issue.comments.div_ceil(100);
+        get_json(
+            client,
+            profile,
+            &format!("{url}/comments?per_page=100&page={last_page}"),
+        )
+        .await?
+    };
+    Ok(thread_details(issue, comments, comment_limit))
+}
+
+/// Posts `body` as a comment on issue or pull request `number` and returns
+/// it as GitHub stored it.
+pub async fn post_issue_comment(
+    client: &Client,
+    profile: &GitHubAccount,
+    repo: &str,
+    number: u64,
+    body: &str,
+) -> Result<ThreadComment, FetchError> {
+    if profile.token.is_empty() {
+        return Err(FetchError::MissingToken);
+    }
+
+    let url = format!(
+        "{}/repos/{repo}/issues/{number}/comments",
+        profile.api_base()
+    );
+    let comment: ThreadCommentResponse = client
+        .post(url)
+        .header(USER_AGENT, USER_AGENT_HEADER)
+        .header(ACCEPT, "application/vnd.github+json")
+        .bearer_auth(&profile.token)
+        .json(&IssueCommentBody { body })
+        .send_checked()
+        .await?
+        .json()
+        .await?;
+    Ok(ThreadComment {
+        author: comment.user.map(|user| user.login),
+        body: comment.body.unwrap_or_default(),
+        created_at: comment.created_at,
+    })
+}
+
+/// Submits a review of pull request `number`. GitHub rejects a request for
+/// changes without a `body`.
+pub async fn submit_pull_request_review(
+    client: &Client,
+    profile: &GitHubAccount,
+    repo: &str,
+    number: u64,
+    verdict: ReviewVerdict,
+    body: &str,
+) -> Result<(), FetchError> {
+    if profile.token.is_empty() {
+        return Err(FetchError::MissingToken);
+    }
+
+    let url = format!("{}/repos/{repo}/pulls/{number}/reviews", profile.api_base());
+    client
+        .post(url)
+        .header(USER_AGENT, USER_AGENT_HEADER)
+        .header(ACCEPT, "application/vnd.github+json")
+        .bearer_auth(&profile.token)
+        .json(&PullRequestReviewBody {
+            event: verdict.api_event(),
+            body,
+        })
+        .send_checked()
+        .await?;
+    Ok(())
+}
+
+fn thread_details(
+    issue: ThreadDetailsResponse,
+    comments: Vec<ThreadCommentResponse>,
+    comment_limit: usize,
+) -> ThreadDetails {
+    let merged = issue
+        .pull_request
+        .is_some_and(|pull_request| pull_request.merged_at.is_some());
+    let skip = comments.len().saturating_sub(comment_limit);
+    ThreadDetails {
+        title: issue.title,
+        state: if merged {
+            String::from("merged")
+        } else {
+            issue.state
+        },
+        author: issue.user.map(|user| user.login),
+        labels: issue.labels.into_iter().map(|label| label.name).collect(),
+        body: issue.body.unwrap_or_default(),
+        comments: comments
+            .into_iter()
+            .skip(skip)
+            .map(|comment| ThreadComment {
+                author: comment.user.map(|user| user.login),
+                body: comment.body.unwrap_or_default(),
+                created_at: comment.created_at,
+            })
+            .collect(),
+    }
+}
+
+fn timeline_event(response: TimelineEventResponse) -> Option<TimelineEvent> {
+    let label = || response.label.as_ref().map(|label| label.name.as_str());
+    let summary = match response.event.as_deref()? {
+        "commented" => match response.body.as_deref().map(timeline_snippet) {
+            Some(snippet) if !snippet.is_empty() => format!("commented: {snippet}"),
+            _ => String::from("commented"),
+        },
+        "reviewed" => match response.state.as_deref()? {
+            "approved" => String::from("approved"),
+            "changes_requested" => String::from("requested changes"),
+            "dismissed" => String::from("review dismissed"),
+            _ => String::from("reviewed"),
+        },
+        "committed" => {
+            let sha = response.sha.as_deref()?;
+            let short_sha = &sha[..sha.len().min(7)];
+            match response.message.as_deref().map(timeline_snippet) {
+                Some(message) => format!("pushed {short_sha} {message}"),
+                None => format!("pushed {short_sha}"),
+            }
+        }
+        "head_ref_force_pushed" => String::from("force-pushed"),
+        "labeled" => format!("added label {}", label()?),
+        "unlabeled" => format!("removed label {}", label()?),
+        _ => return None,
+    };
+    let commit_author = response.author.as_ref();
+    Some(TimelineEvent {
+        at: response
+            .created_at
+            .or(response.submitted_at)
+            .or_else(|| commit_author.and_then(|author| author.date))?,
+        actor: response
+            .actor
+            .or(response.user)
+            .map(|user| user.login)
+            .or_else(|| commit_author.and_then(|author| author.name.clone())),
+        summary,
+    })
+}
+
+/// First line of a comment or commit message, shortened for one row.
+fn timeline_snippet(text: &str) -> String {
+    let line = text.lines().map(str::trim).find(|line| !line.is_empty());
+    let line = line.unwrap_or_default();
+    if line.chars().count() > TIMELINE_SNIPPET_CHARS {
+        let cut: String = line.chars().take(TIMELINE_SNIPPET_CHARS).collect();
+        format!("{}…", cut.trim_end())
+    } else {
+        line.to_owned()
+    }
+}
+
+pub async fn request_pull_request_reviewer(
+    client: &Client,
+    profile: &GitHubAccount,
+    repo: &str,
+    pr_number: u64,
+    reviewer_login: &str,
+) -> Result<(), FetchError> {
+    if profile.token.is_empty() {
+        return Err(FetchError::MissingToken);
+    }
+
+    let url = format!(
+        "{}/repos/{repo}/pulls/{pr_number}/requested_reviewers",
+        profile.api_base()
+    );
+    client
         .post(url)
         .header(USER_AGENT, USER_AGENT_HEADER)
         .header(ACCEPT, "application/vnd.github+json")
         .bearer_auth(&profile.token)
         .json(&ReviewRequestMutationBody::new(reviewer_login))
-        .send()?
-        .error_for_status()?;
+        .send_checked()
+        .await?;
     Ok(())
 }
 
-pub fn remove_pull_request_reviewer(
+pub async fn remove_pull_request_reviewer(
     client: &Client,
     profile: &GitHubAccount,
     repo: &str,
@@ -232,33 +1309,148 @@ pub fn remove_pull_request_reviewer(
         return Err(FetchError::MissingToken);
     }
 
-    let url = format!("{GH_REPOS}/{repo}/pulls/{pr_number}/requested_reviewers");
+    let url = format!(
+        "{}/repos/{repo}/pulls/{pr_number}/requested_reviewers",
+        profile.api_base()
+    );
     client
         .delete(url)
         .header(USER_AGENT, USER_AGENT_HEADER)
         .header(ACCEPT, "application/vnd.github+json")
         .bearer_auth(&profile.token)
         .json(&ReviewRequestMutationBody::new(reviewer_login))
-        .send()?
-        .error_for_status()?;
+        .send_checked()
+        .await?;
+    Ok(())
+}
+
+/// Approves each pull request whose head commit has only passing checks and
+/// leaves the rest untouched so they can be reviewed by hand.
+pub async fn approve_passing_pull_requests(
+    client: &Client,
+    profile: &GitHubAccount,
+    repo: &str,
+    pr_numbers: &[u64],
+) -> Result<BatchApproveSummary, FetchError> {
+    if profile.token.is_empty() {
+        return Err(FetchError::MissingToken);
+    }
+
+    let mut summary = BatchApproveSummary::default();
+    for &pr_number in pr_numbers {
+        let pull_request = fetch_pull_request(client, profile, repo, pr_number).await?;
+        if !head_checks_pass(client, profile, repo, &pull_request.head.sha).await? {
+            summary.skipped.push(pr_number);
+            continue;
+        }
+        approve_pull_request(client, profile, repo, pr_number).await?;
+        summary.approved.push(pr_number);
+    }
+    Ok(summary)
+}
+
+async fn head_checks_pass(
+    client: &Client,
+    profile: &GitHubAccount,
+    repo: &str,
+    sha: &str,
+) -> Result<bool, FetchError> {
+    let mut total_runs = 0;
+    let check_runs = get_paginated(
+        client,
+        profile,
+        &format!(
+            "{}/repos/{repo}/commits/{sha}/check-runs",
+            profile.api_base()
+        ),
+        &[("per_page", "100")],
+        CHECK_RUN_MAX_PAGES,
+        |page: CheckRunsResponse| {
+            total_runs = page.total_count;
+            page.check_runs
+        },
+    )
+    .await?;
+    let status: CombinedStatusResponse = client
+        .get(format!(
+            "{}/repos/{repo}/commits/{sha}/status",
+            profile.api_base()
+        ))
+        .header(USER_AGENT, USER_AGENT_HEADER)
+        .header(ACCEPT, "application/vnd.github+json")
+        .bearer_auth(&profile.token)
+        .send_checked()
+        .await?
+        .json()
+        .await?;
+
+    Ok(checks_pass(&check_runs, total_runs, &status))
+}
+
+/// Runs past the last page read are unknown, so fewer than `total_runs`
+/// never counts as passing.
+fn checks_pass(
+    check_runs: &[CheckRunResponse],
+    total_runs: usize,
+    status: &CombinedStatusResponse,
+) -> bool {
+    let runs_pass = check_runs.len() >= total_runs
+        && check_runs.iter().all(|run| {
+            run.status == "completed"
+                && matches!(
+                    run.conclusion.as_deref(),
+                    Some("success" | "neutral" | "skipped")
+                )
+        });
+    // The combined status reports "pending" when a commit has no legacy
+    // statuses at all, so only treat it as blocking when statuses exist.
+    let statuses_pass = status.total_count == 0 || status.state == "success";
+    runs_pass && statuses_pass && !(check_runs.is_empty() && status.total_count == 0)
+}
+
+async fn approve_pull_request(
+    client: &Client,
+    profile: &GitHubAccount,
+    repo: &str,
+    pr_number: u64,
+) -> Result<(), FetchError> {
+    let url = format!(
+        "{}/repos/{repo}/pulls/{pr_number}/reviews",
+        profile.api_base()
+    );
+    client
+        .post(url)
+        .header(USER_AGENT, USER_AGENT_HEADER)
+        .header(ACCEPT, "application/vnd.github+json")
+        .bearer_auth(&profile.token)
+        .json(&PullRequestReviewBody {
+            event: ReviewVerdict::Approve.api_event(),
+            body: "",
+        })
+        .send_checked()
+        .await?;
     Ok(())
 }
 
-fn fetch_requested_reviewers(
+async fn fetch_requested_reviewers(
     client: &Client,
     profile: &GitHubAccount,
     repo: &str,
     pr_number: u64,
 ) -> Result<Vec<String>, FetchError> {
-    let url = format!("{GH_REPOS}/{repo}/pulls/{pr_number}/requested_reviewers");
+    let url = format!(
+        "{}/repos/{repo}/pulls/{pr_number}/requested_reviewers",
+        profile.api_base()
+    );
     let response = client
         .get(url)
         .header(USER_AGENT, USER_AGENT_HEADER)
         .header(ACCEPT, "application/vnd.github+json")
         .bearer_auth(&profile.token)
-        .send()?
-        .error_for_status()?
-        .json::<RequestedReviewersResponse>()?;
+        .send_checked()
+        .await?
+        .json::<RequestedReviewersResponse>()
+        .await?;
 
     let mut requested_reviewers: Vec<_> = response
         .users
@@ -276,87 +1468,139 @@ fn fetch_requested_reviewers(
     Ok(requested_reviewers)
 }
 
-fn fetch_issue_events(
+async fn fetch_issue_events(
     client: &Client,
     profile: &GitHubAccount,
     repo: &str,
     issue_number: u64,
 ) -> Result<Vec<IssueEventResponse>, FetchError> {
-    let url = format!("{GH_REPOS}/{repo}/issues/{issue_number}/events");
+    let url = format!(
+        "{}/repos/{repo}/issues/{issue_number}/events",
+        profile.api_base()
+    );
     client
         .get(url)
         .query(&[("per_page", "100")])
         .header(USER_AGENT, USER_AGENT_HEADER)
         .header(ACCEPT, "application/vnd.github+json")
         .bearer_auth(&profile.token)
-        .send()?
-        .error_for_status()?
+        .send_checked()
+        .await?
         .json()
-        .map_err(FetchError::Http)
+        .await
+        .map_err(FetchError::from)
 }
 
-fn fetch_pull_request_reviews(
+async fn fetch_pull_request_reviews(
     client: &Client,
     profile: &GitHubAccount,
     repo: &str,
     pr_number: u64,
 ) -> Result<Vec<PullRequestReviewResponse>, FetchError> {
-    let url = format!("{GH_REPOS}/{repo}/pulls/{pr_number}/reviews");
+    let url = format!(
+        "{}/repos/{repo}/pulls/{pr_number}/reviews",
+        profile.api_base()
+    );
     client
         .get(url)
         .query(&[("per_page", "100")])
         .header(USER_AGENT, USER_AGENT_HEADER)
         .header(ACCEPT, "application/vnd.github+json")
         .bearer_auth(&profile.token)
-        .send()?
-        .error_for_status()?
+        .send_checked()
+        .await?
         .json()
-        .map_err(FetchError::Http)
+        .await
+        .map_err(FetchError::from)
+}
+
+/// The first page of unread threads, newest first.
+async fn fetch_unread_threads(
+    client: &Client,
+    profile: &GitHubAccount,
+) -> Result<UnreadThreads, FetchError> {
+    let page = fetch_notifications(client, profile, 1, true, None).await?;
+    Ok(unread_threads(page))
+}
+
+fn unread_threads(page: Vec<NotificationItem>) -> UnreadThreads {
+    let oldest_update = (page.len() >= NOTIFICATIONS_PAGE_SIZE)
+        .then(|| page.iter().map(|item| item.updated_at).min())
+        .flatten();
+    UnreadThreads {
+        thread_ids: page.into_iter().map(|item| item.thread_id).collect(),
+        oldest_update,
+    }
 }
 
-fn fetch_notifications(
+async fn fetch_notifications(
     client: &Client,
     profile: &GitHubAccount,
+    max_pages: usize,
+    unread_only: bool,
+    since: Option<DateTime<Utc>>,
 ) -> Result<Vec<NotificationItem>, FetchError> {
-    let response: Vec<NotificationResponse> = client
-        .get(GH_NOTIFICATIONS)
-        .query(&[("all", "true")])
-        .header(USER_AGENT, USER_AGENT_HEADER)
-        .header(ACCEPT, "application/vnd.github+json")
-        .bearer_auth(&profile.token)
-        .send()?
-        .error_for_status()?
-        .json()?;
+    let all = if unread_only { "false" } else { "true" };
+    let since = since.map(|since| since.to_rfc3339_opts(SecondsFormat::Secs, true));
+    let per_page = NOTIFICATIONS_PAGE_SIZE.to_string();
+    let mut query = vec![("all", all), ("per_page", per_page.as_str())];
+    if let Some(since) = &since {
+        query.push(("since", since));
+    }
+    let response: Vec<NotificationResponse> = get_paginated(
+        client,
+        profile,
+        &format!("{}/notifications", profile.api_base()),
+        &query,
+        max_pages,
+        |page: Vec<NotificationResponse>| page,
+    )
+    .await?;
 
     Ok(response
         .into_iter()
         .map(|item| NotificationItem {
+            url: Some(notification_html_url(
+                profile,
+                &item.repository.full_name,
+                &item.subject,
+            )),
             thread_id: item.id,
             repo: item.repository.full_name,
             title: item.subject.title,
-            url: item
-                .subject
-                .url
-                .as_deref()
-                .map(normalize_notification_subject_url),
             head_ref: None,
             base_ref: None,
             my_review_status: None,
+            latest_comment_url: item.subject.latest_comment_url,
+            subject_url: item.subject.url,
+            subject_type: item.subject.kind,
+            provider: Forge::GitHub,
+            metadata: BTreeMap::new(),
+            latest_actor: None,
             reason: item.reason,
             updated_at: item.updated_at,
             last_read_at: item.last_read_at,
             unread: item.unread,
+            search_cache: SearchCache::default(),
         })
         .collect())
 }
 
-fn normalize_notification_subject_url(url: &str) -> String {
-    let mut html = url.replace("api.github.com/repos", "github.com");
-    html = html.replace("/pulls/", "/pull/");
-    html
+fn notification_html_url(
+    profile: &GitHubAccount,
+    repo: &str,
+    subject: &NotificationSubject,
+) -> String {
+    url_map::subject_html_url(
+        profile.api_base(),
+        profile.web_base(),
+        repo,
+        &subject.kind,
+        subject.url.as_deref(),
+    )
 }
 
-fn notification_pull_request_metadata(
+async fn notification_pull_request_metadata(
     client: &Client,
     profile: &GitHubAccount,
     repo: &str,
@@ -371,99 +1615,306 @@ fn notification_pull_request_metadata(
         return Ok(metadata.clone());
     }
 
-    let metadata = fetch_notification_pull_request_metadata(client, profile, repo, pr_number)?;
+    let metadata =
+        fetch_notification_pull_request_metadata(client, profile, repo, pr_number).await?;
     cache.insert(key, metadata.clone());
     Ok(metadata)
 }
 
-fn pull_request_number_from_html_url(url: &str) -> Option<u64> {
-    let (_, suffix) = url.split_once("/pull/")?;
-    suffix.split(['/', '?', '#']).next()?.parse().ok()
+// The latest comment may have been deleted or live in a repository the token
+// can no longer read; a missing actor simply leaves the notification visible.
+async fn notification_latest_actor(
+    client: &Client,
+    profile: &GitHubAccount,
+    api_url: Option<&str>,
+    cache: &mut BTreeMap<String, Option<String>>,
+) -> Option<String> {
+    let api_url = api_url?;
+    if let Some(actor) = cache.get(api_url) {
+        return actor.clone();
+    }
+
+    let actor = fetch_latest_actor(client, profile, api_url)
+        .await
+        .unwrap_or(None);
+    cache.insert(api_url.to_owned(), actor.clone());
+    actor
+}
+
+/// Releases, commits and similar subjects have web URLs that cannot be derived
+/// from their API URLs, so the resource itself is asked for its `html_url`.
+async fn notification_subject_html_url(
+    client: &Client,
+    profile: &GitHubAccount,
+    api_url: Option<&str>,
+    cache: &mut BTreeMap<String, Option<String>>,
+) -> Option<String> {
+    let api_url = api_url?;
+    if let Some(html_url) = cache.get(api_url) {
+        return html_url.clone();
+    }
+
+    let html_url = fetch_html_url(client, profile, api_url)
+        .await
+        .unwrap_or(None);
+    cache.insert(api_url.to_owned(), html_url.clone());
+    html_url
+}
+
+/// Discussion notifications carry no subject URL, so the discussion is
+/// looked up by title through GraphQL search.
+async fn discussion_html_url(
+    client: &Client,
+    profile: &GitHubAccount,
+    item: &NotificationItem,
+    cache: &mut BTreeMap<String, Option<String>>,
+) -> Option<String> {
+    if !item.is_discussion() {
+        return None;
+    }
+    let key = format!("discussion:{}:{}", item.repo, item.title);
+    if let Some(html_url) = cache.get(&key) {
+        return html_url.clone();
+    }
+
+    let html_url = fetch_discussion_url(client, profile, &item.repo, &item.title)
+        .await
+        .unwrap_or(None);
+    cache.insert(key, html_url.clone());
+    html_url
+}
+
+async fn fetch_discussion_url(
+    client: &Client,
+    profile: &GitHubAccount,
+    repo: &str,
+    title: &str,
+) -> Result<Option<String>, FetchError> {
+    if profile.token.is_empty() {
+        return Err(FetchError::MissingToken);
+    }
+
+    let query = discussion_search_query(repo, title);
+    let response: DiscussionSearchResponse = client
+        .post(profile.graphql_url())
+        .header(USER_AGENT, USER_AGENT_HEADER)
+        .bearer_auth(&profile.token)
+        .json(&GraphQlQuery { query: &query })
+        .send_checked()
+        .await?
+        .json()
+        .await?;
+    let Some(data) = response.data else {
+        return Err(FetchError::GraphQl(
+            response
+                .errors
+                .into_iter()
+                .map(|error| error.message)
+                .collect::<Vec<_>>()
+                .join("; "),
+        ));
+    };
+    Ok(matching_discussion_url(data.search.nodes, title))
+}
+
+fn discussion_search_query(repo: &str, title: &str) -> String {
+    // A quote inside the title would end the search phrase early.
+    let search = serde_json::Value::from(format!(
+        "repo:{repo} in:title \"{}\"",
+        title.replace('"', " ")
+    ));
+    format!(
+        "query {{ search(query: {search}, type: DISCUSSION, first: 10) \
+         {{ nodes {{ ... on Discussion {{ title url }} }} }} }}"
+    )
+}
+
+/// The discussion titled exactly `title`, or else the best search hit.
+fn matching_discussion_url(nodes: Vec<DiscussionNode>, title: &str) -> Option<String> {
+    let mut fallback = None;
+    for node in nodes {
+        let Some(url) = node.url else {
+            continue;
+        };
+        if node.title.as_deref() == Some(title) {
+            return Some(url);
+        }
+        fallback.get_or_insert(url);
+    }
+    fallback
+}
+
+async fn fetch_html_url(
+    client: &Client,
+    profile: &GitHubAccount,
+    api_url: &str,
+) -> Result<Option<String>, FetchError> {
+    if profile.token.is_empty() {
+        return Err(FetchError::MissingToken);
+    }
+
+    let response: HtmlUrlResponse = client
+        .get(api_url)
+        .header(USER_AGENT, USER_AGENT_HEADER)
+        .header(ACCEPT, "application/vnd.github+json")
+        .bearer_auth(&profile.token)
+        .send_checked()
+        .await?
+        .json()
+        .await?;
+    Ok(response.html_url)
+}
+
+async fn fetch_latest_actor(
+    client: &Client,
+    profile: &GitHubAccount,
+    api_url: &str,
+) -> Result<Option<String>, FetchError> {
+    let response: LatestCommentResponse = client
+        .get(api_url)
+        .header(USER_AGENT, USER_AGENT_HEADER)
+        .header(ACCEPT, "application/vnd.github+json")
+        .bearer_auth(&profile.token)
+        .send_checked()
+        .await?
+        .json()
+        .await?;
+    Ok(response.user.map(|user| user.login))
 }
 
-fn fetch_notification_pull_request_metadata(
+async fn fetch_notification_pull_request_metadata(
     client: &Client,
     profile: &GitHubAccount,
     repo: &str,
     pr_number: u64,
 ) -> Result<NotificationPullRequestMetadata, FetchError> {
-    let pull_request = fetch_pull_request(client, profile, repo, pr_number)?;
+    let pull_request = fetch_pull_request(client, profile, repo, pr_number).await?;
     Ok(NotificationPullRequestMetadata {
         head_ref: Some(pull_request.head.r#ref),
         base_ref: Some(pull_request.base.r#ref),
-        my_review_status: fetch_latest_review_status_for_user(client, profile, repo, pr_number)?,
+        my_review_status: fetch_latest_review_status_for_user(client, profile, repo, pr_number)
+            .await?,
     })
 }
 
-fn fetch_pull_request(
+async fn fetch_pull_request(
     client: &Client,
     profile: &GitHubAccount,
     repo: &str,
     pr_number: u64,
 ) -> Result<PullRequestResponse, FetchError> {
-    let url = format!("{GH_REPOS}/{repo}/pulls/{pr_number}");
+    let url = format!("{}/repos/{repo}/pulls/{pr_number}", profile.api_base());
     client
         .get(url)
         .header(USER_AGENT, USER_AGENT_HEADER)
         .header(ACCEPT, "application/vnd.github+json")
         .bearer_auth(&profile.token)
-        .send()?
-        .error_for_status()?
+        .send_checked()
+        .await?
         .json()
-        .map_err(FetchError::Http)
+        .await
+        .map_err(FetchError::from)
+}
+
+/// Hits of a search-backed list and how many GitHub has in total.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SearchPage<T> {
+    pub items: Vec<T>,
+    pub total_count: u64,
+}
+
+/// The page of review requests after the `loaded` ones already shown.
+pub async fn fetch_more_review_requests(
+    client: &Client,
+    profile: &GitHubAccount,
+    loaded: usize,
+) -> Result<SearchPage<ReviewRequest>, FetchError> {
+    if profile.token.is_empty() {
+        return Err(FetchError::MissingToken);
+    }
+    fetch_review_requests(client, profile, next_search_page(loaded), 1).await
+}
+
+/// The page of mentions after the `loaded` ones already shown.
+pub async fn fetch_more_mentions(
+    client: &Client,
+    profile: &GitHubAccount,
+    loaded: usize,
+) -> Result<SearchPage<MentionThread>, FetchError> {
+    if profile.token.is_empty() {
+        return Err(FetchError::MissingToken);
+    }
+    fetch_mentions(client, profile, next_search_page(loaded), 1).await
+}
+
+/// 1-based search page that follows `loaded` hits.
+fn next_search_page(loaded: usize) -> usize {
+    loaded / SEARCH_PAGE_SIZE + 1
 }
 
-fn fetch_review_requests(
+async fn fetch_review_requests(
     client: &Client,
     profile: &GitHubAccount,
-) -> Result<Vec<ReviewRequest>, FetchError> {
+    first_page: usize,
+    max_pages: usize,
+) -> Result<SearchPage<ReviewRequest>, FetchError> {
     let query = format!("is:pr state:open review-requested:{}", profile.login);
-    let response: SearchResponse = client
-        .get(GH_SEARCH_ISSUES)
-        .query(&[("q", query.as_str())])
-        .header(USER_AGENT, USER_AGENT_HEADER)
-        .header(ACCEPT, "application/vnd.github+json")
-        .bearer_auth(&profile.token)
-        .send()?
-        .error_for_status()?
-        .json()?;
+    let SearchPage { items, total_count } = search_issues_from(
+        client,
+        profile,
+        &[("q", query.as_str())],
+        first_page,
+        max_pages,
+    )
+    .await?;
 
-    let mut review_requests = Vec::with_capacity(response.items.len());
-    for item in response.items {
+    let mut review_requests = Vec::with_capacity(items.len());
+    for item in items {
         let repo = extract_repo_name(&item.repository_url);
-        let requested_by = fetch_review_requester_for_user(client, profile, &repo, item.number)?;
+        let active = fetch_active_review_request(client, profile, &repo, item.number).await?;
         review_requests.push(ReviewRequest {
             _id: item.id,
             repo,
             title: format!("#{} {}", item.number, item.title),
             url: item.html_url,
             updated_at: item.updated_at,
-            requested_by,
+            requested_by: active.as_ref().and_then(|active| active.requester.clone()),
+            requested_at: active.map(|active| active.requested_at),
         });
     }
 
-    Ok(review_requests)
+    Ok(SearchPage {
+        items: review_requests,
+        total_count,
+    })
+}
+
+/// The review request still waiting on a reviewer: who made it and when.
+#[derive(Debug, PartialEq, Eq)]
+struct ActiveReviewRequest {
+    requester: Option<String>,
+    requested_at: DateTime<Utc>,
 }
 
-fn fetch_review_requester_for_user(
+async fn fetch_active_review_request(
     client: &Client,
     profile: &GitHubAccount,
     repo: &str,
     issue_number: u64,
-) -> Result<Option<String>, FetchError> {
-    Ok(review_requester_for_user_from_issue_events(
-        fetch_issue_events(client, profile, repo, issue_number)?,
+) -> Result<Option<ActiveReviewRequest>, FetchError> {
+    Ok(active_review_request_from_issue_events(
+        fetch_issue_events(client, profile, repo, issue_number).await?,
         &profile.login,
     ))
 }
 
-fn review_requester_for_user_from_issue_events(
+fn active_review_request_from_issue_events(
     mut events: Vec<IssueEventResponse>,
     reviewer_login: &str,
-) -> Option<String> {
-    events.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+) -> Option<ActiveReviewRequest> {
+    events.sort_by_key(|event| event.created_at);
 
-    let mut current_requester = None;
+    let mut current = None;
     for event in events {
         let requested_reviewer = event
             .requested_reviewer
@@ -475,17 +1926,20 @@ fn review_requester_for_user_from_issue_events(
 
         match event.event.as_str() {
             "review_requested" => {
-                current_requester = event
-                    .review_requester
-                    .or(event.actor)
-                    .map(|user| user.login);
+                current = Some(ActiveReviewRequest {
+                    requester: event
+                        .review_requester
+                        .or(event.actor)
+                        .map(|user| user.login),
+                    requested_at: event.created_at,
+                });
             }
-            "review_request_removed" => current_requester = None,
+            "review_request_removed" => current = None,
             _ => {}
         }
     }
 
-    current_requester
+    current
 }
 
 fn review_request_history_from_issue_events(events: &[IssueEventResponse]) -> Vec<String> {
@@ -626,23 +2080,23 @@ fn review_is_stale_after_re_request(
         })
 }
 
-fn fetch_latest_review_status_for_user(
+async fn fetch_latest_review_status_for_user(
     client: &Client,
     profile: &GitHubAccount,
     repo: &str,
     pr_number: u64,
 ) -> Result<Option<PullRequestReviewerStatus>, FetchError> {
     let latest_review = latest_submitted_review_for_user(
-        fetch_pull_request_reviews(client, profile, repo, pr_number)?,
+        fetch_pull_request_reviews(client, profile, repo, pr_number).await?,
         &profile.login,
     );
     let Some(review) = latest_review else {
         return Ok(None);
     };
 
-    let latest_request_times = latest_review_request_times_from_issue_events(&fetch_issue_events(
-        client, profile, repo, pr_number,
-    )?);
+    let latest_request_times = latest_review_request_times_from_issue_events(
+        &fetch_issue_events(client, profile, repo, pr_number).await?,
+    );
     if review_is_stale_after_re_request(&review, &latest_request_times, &profile.login) {
         return Ok(None);
     }
@@ -705,27 +2159,27 @@ fn reviewer_status_from_review_state(state: &str) -> Option<PullRequestReviewerS
     }
 }
 
-fn fetch_mentions(
+async fn fetch_mentions(
     client: &Client,
     profile: &GitHubAccount,
-) -> Result<Vec<MentionThread>, FetchError> {
+    first_page: usize,
+    max_pages: usize,
+) -> Result<SearchPage<MentionThread>, FetchError> {
     let query = format!("mentions:{} is:open", profile.login);
-    let response: SearchResponse = client
-        .get(GH_SEARCH_ISSUES)
-        .query(&[
+    let SearchPage { items, total_count } = search_issues_from(
+        client,
+        profile,
+        &[
             ("q", query.as_str()),
             ("sort", "updated"),
             ("order", "desc"),
-        ])
-        .header(USER_AGENT, USER_AGENT_HEADER)
-        .header(ACCEPT, "application/vnd.github+json")
-        .bearer_auth(&profile.token)
-        .send()?
-        .error_for_status()?
-        .json()?;
+        ],
+        first_page,
+        max_pages,
+    )
+    .await?;
 
-    Ok(response
-        .items
+    let items = items
         .into_iter()
         .map(|item| {
             let kind = classify_thread(&item.html_url);
@@ -738,30 +2192,28 @@ fn fetch_mentions(
                 kind,
             }
         })
-        .collect())
+        .collect();
+    Ok(SearchPage { items, total_count })
 }
 
-fn fetch_recent_reviews(
+async fn fetch_recent_reviews(
     client: &Client,
     profile: &GitHubAccount,
 ) -> Result<Vec<ReviewSummary>, FetchError> {
     let query = format!("is:pr reviewed-by:{}", profile.login);
-    let response: SearchResponse = client
-        .get(GH_SEARCH_ISSUES)
-        .query(&[
+    let items = search_issues(
+        client,
+        profile,
+        &[
             ("q", query.as_str()),
             ("sort", "updated"),
             ("order", "desc"),
-        ])
-        .header(USER_AGENT, USER_AGENT_HEADER)
-        .header(ACCEPT, "application/vnd.github+json")
-        .bearer_auth(&profile.token)
-        .send()?
-        .error_for_status()?
-        .json()?;
+        ],
+        1,
+    )
+    .await?;
 
-    Ok(response
-        .items
+    Ok(items
         .into_iter()
         .map(|item| ReviewSummary {
             _id: item.id,
@@ -774,23 +2226,540 @@ fn fetch_recent_reviews(
         .collect())
 }
 
-fn classify_thread(url: &str) -> MentionKind {
-    if url.contains("/pull/") {
-        MentionKind::PullRequest
-    } else {
-        MentionKind::Issue
-    }
-}
+async fn fetch_assigned_issues(
+    client: &Client,
+    profile: &GitHubAccount,
+) -> Result<Vec<AssignedIssue>, FetchError> {
+    let query = format!("is:issue assignee:{} is:open", profile.login);
+    let items = search_issues(
+        client,
+        profile,
+        &[
+            ("q", query.as_str()),
+            ("sort", "updated"),
+            ("order", "desc"),
+        ],
+        1,
+    )
+    .await?;
 
-fn extract_repo_name(api_url: &str) -> String {
-    api_url
-        .trim_start_matches("https://api.github.com/repos/")
-        .to_owned()
-}
+    Ok(items
+        .into_iter()
+        .map(|item| AssignedIssue {
+            repo: extract_repo_name(&item.repository_url),
+            number: item.number,
+            title: format!("#{} {}", item.number, item.title),
+            url: item.html_url,
+            updated_at: item.updated_at,
+            labels: item.labels.into_iter().map(|label| label.name).collect(),
+        })
+        .collect())
+}
+
+async fn fetch_authored_pull_requests(
+    client: &Client,
+    profile: &GitHubAccount,
+) -> Result<Vec<AuthoredPullRequest>, FetchError> {
+    let query = format!("is:pr author:{} is:open", profile.login);
+    let items = search_issues(
+        client,
+        profile,
+        &[
+            ("q", query.as_str()),
+            ("sort", "updated"),
+            ("order", "desc"),
+        ],
+        1,
+    )
+    .await?;
+
+    Ok(items
+        .into_iter()
+        .map(|item| AuthoredPullRequest {
+            repo: extract_repo_name(&item.repository_url),
+            number: item.number,
+            title: format!("#{} {}", item.number, item.title),
+            url: item.html_url,
+            updated_at: item.updated_at,
+            draft: item.draft,
+            labels: item.labels.into_iter().map(|label| label.name).collect(),
+        })
+        .collect())
+}
+
+async fn fetch_graphql_lists(
+    client: &Client,
+    profile: &GitHubAccount,
+) -> Result<SearchLists, FetchError> {
+    let query = inbox_lists_query(&profile.login);
+    let response: InboxListsResponse = client
+        .post(profile.graphql_url())
+        .header(USER_AGENT, USER_AGENT_HEADER)
+        .bearer_auth(&profile.token)
+        .json(&GraphQlQuery { query: &query })
+        .send_checked()
+        .await?
+        .json()
+        .await?;
+    let Some(data) = response.data else {
+        return Err(FetchError::GraphQl(
+            response
+                .errors
+                .into_iter()
+                .map(|error| error.message)
+                .collect::<Vec<_>>()
+                .join("; "),
+        ));
+    };
+    Ok(search_lists_from_graphql(data, &profile.login))
+}
+
+/// Fields asked of every search hit. Pull requests also carry their badges
+/// and the review requests made on them, so no follow-up calls are needed.
+const INBOX_LIST_FIELDS: &str = "nodes { __typename \
+     ... on Issue { databaseId number title url updatedAt state \
+     repository { nameWithOwner } labels(first: 20) { nodes { name } } } \
+     ... on PullRequest { databaseId number title url updatedAt state \
+     isDraft mergeable reviewDecision repository { nameWithOwner } \
+     labels(first: 20) { nodes { name } } \
+     commits(last: 1) { nodes { commit { statusCheckRollup { state } } } } \
+     timelineItems(itemTypes: [REVIEW_REQUESTED_EVENT], last: 20) { nodes { \
+     ... on ReviewRequestedEvent { createdAt actor { login } requestedReviewer { ... on User { login } } } } } } }";
+
+fn inbox_lists_query(login: &str) -> String {
+    let searches = [
+        (
+            "reviewRequests",
+            format!("is:pr state:open review-requested:{login}"),
+        ),
+        (
+            "mentions",
+            format!("mentions:{login} is:open sort:updated-desc"),
+        ),
+        (
+            "reviewed",
+            format!("is:pr reviewed-by:{login} sort:updated-desc"),
+        ),
+        (
+            "assigned",
+            format!("is:issue assignee:{login} is:open sort:updated-desc"),
+        ),
+        (
+            "authored",
+            format!("is:pr author:{login} is:open sort:updated-desc"),
+        ),
+    ];
+    let mut query = String::from("query {");
+    for (alias, search) in searches {
+        let search = serde_json::Value::from(search);
+        query.push_str(&format!(
+            " {alias}: search(query: {search}, type: ISSUE, first: 100) {{ issueCount {INBOX_LIST_FIELDS} }}"
+        ));
+    }
+    query.push_str(" }");
+    query
+}
+
+fn search_lists_from_graphql(data: InboxListsData, login: &str) -> SearchLists {
+    let search_totals = SearchTotals {
+        review_requests: data.review_requests.issue_count,
+        mentions: data.mentions.issue_count,
+    };
+    let mut pull_request_statuses = Vec::new();
+    let mut hits = |connection: SearchConnection| -> Vec<SearchNode> {
+        let nodes: Vec<_> = connection.nodes.into_iter().flatten().collect();
+        pull_request_statuses.extend(nodes.iter().filter_map(|node| {
+            Some((
+                (node.repository.name_with_owner.clone(), node.number),
+                node.pull_request_status()?,
+            ))
+        }));
+        nodes
+    };
+    let review_requests = hits(data.review_requests)
+        .into_iter()
+        .map(|node| ReviewRequest {
+            _id: node.database_id.unwrap_or_default(),
+            requested_by: node
+                .review_request_for(login)
+                .and_then(|event| event.actor.as_ref())
+                .map(|actor| actor.login.clone()),
+            requested_at: node
+                .review_request_for(login)
+                .and_then(|event| event.created_at),
+            title: format!("#{} {}", node.number, node.title),
+            repo: node.repository.name_with_owner,
+            url: node.url,
+            updated_at: node.updated_at,
+        })
+        .collect();
+    let mentions = hits(data.mentions)
+        .into_iter()
+        .map(|node| MentionThread {
+            _id: node.database_id.unwrap_or_default(),
+            kind: if node.is_pull_request() {
+                MentionKind::PullRequest
+            } else {
+                MentionKind::Issue
+            },
+            title: format!("#{} {}", node.number, node.title),
+            repo: node.repository.name_with_owner,
+            url: node.url,
+            updated_at: node.updated_at,
+        })
+        .collect();
+    let recent_reviews = hits(data.reviewed)
+        .into_iter()
+        .map(|node| ReviewSummary {
+            _id: node.database_id.unwrap_or_default(),
+            title: format!("#{} {}", node.number, node.title),
+            // REST search reports `open` or `closed`; GraphQL also has `MERGED`.
+            state: node.state.to_ascii_lowercase(),
+            repo: node.repository.name_with_owner,
+            url: node.url,
+            updated_at: node.updated_at,
+        })
+        .collect();
+    let assigned_issues = hits(data.assigned)
+        .into_iter()
+        .map(|node| AssignedIssue {
+            labels: node.label_names(),
+            number: node.number,
+            title: format!("#{} {}", node.number, node.title),
+            repo: node.repository.name_with_owner,
+            url: node.url,
+            updated_at: node.updated_at,
+        })
+        .collect();
+    let authored_pull_requests = hits(data.authored)
+        .into_iter()
+        .map(|node| AuthoredPullRequest {
+            labels: node.label_names(),
+            number: node.number,
+            title: format!("#{} {}", node.number, node.title),
+            draft: node.is_draft,
+            repo: node.repository.name_with_owner,
+            url: node.url,
+            updated_at: node.updated_at,
+        })
+        .collect();
+    SearchLists {
+        review_requests,
+        mentions,
+        recent_reviews,
+        assigned_issues,
+        authored_pull_requests,
+        pull_request_statuses,
+        search_totals,
+    }
+}
+
+/// Open issues and pull requests matching a user's saved search `query`.
+pub async fn fetch_saved_search(
+    client: &Client,
+    profile: &GitHubAccount,
+    query: &str,
+    max_pages: usize,
+) -> Result<Vec<SearchHit>, FetchError> {
+    if profile.token.is_empty() {
+        return Err(FetchError::MissingToken);
+    }
+    let items = search_issues(
+        client,
+        profile,
+        &[("q", query), ("sort", "updated"), ("order", "desc")],
+        max_pages,
+    )
+    .await?;
+
+    Ok(items
+        .into_iter()
+        .map(|item| SearchHit {
+            repo: extract_repo_name(&item.repository_url),
+            title: format!("#{} {}", item.number, item.title),
+            url: item.html_url,
+            updated_at: item.updated_at,
+            state: item.state,
+            labels: item.labels.into_iter().map(|label| label.name).collect(),
+        })
+        .collect())
+}
+
+/// Repositories looked at for branches without a pull request per scan.
+const MAX_STALE_BRANCH_REPOS: usize = 20;
+/// Branch head commits looked up per repository, newest branches first.
+const MAX_BRANCH_COMMIT_LOOKUPS: usize = 30;
+
+/// My branches with no activity since `cutoff`: open pull requests found
+/// through search, and branches in `repos` that never had a pull request
+/// and whose head commit is mine. Repositories that cannot be read are
+/// skipped.
+pub async fn fetch_stale_branches(
+    client: &Client,
+    profile: &GitHubAccount,
+    repos: Vec<String>,
+    cutoff: DateTime<Utc>,
+    max_pages: usize,
+) -> Result<Vec<StaleBranch>, FetchError> {
+    if profile.token.is_empty() {
+        return Err(FetchError::MissingToken);
+    }
+    let query = format!(
+        "is:pr author:{} is:open updated:<{}",
+        profile.login,
+        cutoff.format("%Y-%m-%d")
+    );
+    let items = search_issues(
+        client,
+        profile,
+        &[("q", query.as_str()), ("sort", "updated"), ("order", "asc")],
+        max_pages,
+    )
+    .await?;
+    let mut stale: Vec<_> = items
+        .into_iter()
+        .map(|item| StaleBranch {
+            repo: extract_repo_name(&item.repository_url),
+            title: format!("#{} {}", item.number, item.title),
+            url: item.html_url,
+            has_pull_request: true,
+            last_activity: item.updated_at,
+        })
+        .collect();
+
+    for repo in repos.iter().take(MAX_STALE_BRANCH_REPOS) {
+        if let Ok(branches) = fetch_unopened_branches(client, profile, repo, cutoff).await {
+            stale.extend(branches);
+        }
+    }
+    stale.sort_by_key(|branch| branch.last_activity);
+    Ok(stale)
+}
+
+async fn fetch_unopened_branches(
+    client: &Client,
+    profile: &GitHubAccount,
+    repo: &str,
+    cutoff: DateTime<Utc>,
+) -> Result<Vec<StaleBranch>, FetchError> {
+    let api = format!("{}/repos/{repo}", profile.api_base());
+    let repository: RepositoryResponse = get_json(client, profile, &api).await?;
+    let branches: Vec<BranchResponse> = get_paginated(
+        client,
+        profile,
+        &format!("{api}/branches"),
+        &[("per_page", "100")],
+        1,
+        |page| page,
+    )
+    .await?;
+    let pulls: Vec<PullRequestResponse> = get_paginated(
+        client,
+        profile,
+        &format!("{api}/pulls"),
+        &[("state", "all"), ("per_page", "100")],
+        1,
+        |page| page,
+    )
+    .await?;
+    let with_pull_request: HashSet<_> = pulls.into_iter().map(|pull| pull.head.r#ref).collect();
+
+    let mut stale = Vec::new();
+    for branch in branches
+        .into_iter()
+        .filter(|branch| {
+            !branch.protected
+                && branch.name != repository.default_branch
+                && !with_pull_request.contains(&branch.name)
+        })
+        .take(MAX_BRANCH_COMMIT_LOOKUPS)
+    {
+        let commit: BranchCommitResponse = get_json(
+            client,
+            profile,
+            &format!("{api}/commits/{}", branch.commit.sha),
+        )
+        .await?;
+        let mine = commit
+            .author
+            .is_some_and(|author| author.login.eq_ignore_ascii_case(&profile.login));
+        let pushed_at = commit.commit.committer.date;
+        if mine && pushed_at < cutoff {
+            stale.push(StaleBranch {
+                repo: repo.to_owned(),
+                url: format!("{}/{repo}/tree/{}", profile.web_base(), branch.name),
+                title: branch.name,
+                has_pull_request: false,
+                last_activity: pushed_at,
+            });
+        }
+    }
+    Ok(stale)
+}
+
+async fn get_json<T: DeserializeOwned>(
+    client: &Client,
+    profile: &GitHubAccount,
+    url: &str,
+) -> Result<T, FetchError> {
+    Ok(client
+        .get(url)
+        .header(USER_AGENT, USER_AGENT_HEADER)
+        .header(ACCEPT, "application/vnd.github+json")
+        .bearer_auth(&profile.token)
+        .send_checked()
+        .await?
+        .json()
+        .await?)
+}
+
+async fn search_issues(
+    client: &Client,
+    profile: &GitHubAccount,
+    query: &[(&str, &str)],
+    max_pages: usize,
+) -> Result<Vec<SearchItem>, FetchError> {
+    Ok(search_issues_from(client, profile, query, 1, max_pages)
+        .await?
+        .items)
+}
+
+/// Up to `max_pages` pages of an issue search starting at 1-based
+/// `first_page`, with the total GitHub reports.
+async fn search_issues_from(
+    client: &Client,
+    profile: &GitHubAccount,
+    query: &[(&str, &str)],
+    first_page: usize,
+    max_pages: usize,
+) -> Result<SearchPage<SearchItem>, FetchError> {
+    let per_page = SEARCH_PAGE_SIZE.to_string();
+    let first_page = first_page.to_string();
+    let mut query = query.to_vec();
+    query.push(("per_page", &per_page));
+    query.push(("page", &first_page));
+    let mut total_count = 0;
+    let items = get_paginated(
+        client,
+        profile,
+        &format!("{}/search/issues", profile.api_base()),
+        &query,
+        max_pages,
+        |page: SearchResponse| {
+            total_count = page.total_count;
+            page.items
+        },
+    )
+    .await?;
+    Ok(SearchPage { items, total_count })
+}
+
+/// Follows `rel="next"` links from the `Link` header and concatenates the
+/// items of up to `max_pages` pages. `into_items` unwraps each page payload,
+/// which is a bare array for most endpoints but an object for search.
+pub(crate) async fn get_paginated<P, T>(
+    client: &Client,
+    profile: &GitHubAccount,
+    url: &str,
+    query: &[(&str, &str)],
+    max_pages: usize,
+    mut into_items: impl FnMut(P) -> Vec<T>,
+) -> Result<Vec<T>, FetchError>
+where
+    P: DeserializeOwned,
+{
+    let mut items = Vec::new();
+    let mut request = client.get(url).query(query);
+    for _ in 0..max_pages.max(1) {
+        let response = request
+            .header(USER_AGENT, USER_AGENT_HEADER)
+            .header(ACCEPT, "application/vnd.github+json")
+            .bearer_auth(&profile.token)
+            .send_checked()
+            .await?;
+        let next_url = response
+            .headers()
+            .get(LINK)
+            .and_then(|value| value.to_str().ok())
+            .and_then(next_page_url);
+        items.extend(into_items(response.json().await?));
+
+        let Some(next_url) = next_url else {
+            break;
+        };
+        // The next link already carries the original query parameters.
+        request = client.get(next_url);
+    }
+    Ok(items)
+}
+
+fn next_page_url(link_header: &str) -> Option<String> {
+    link_header.split(',').find_map(|link| {
+        let (target, params) = link.split_once(';')?;
+        params
+            .split(';')
+            .any(|param| param.trim() == "rel=\"next\"")
+            .then(|| {
+                target
+                    .trim()
+                    .trim_start_matches('<')
+                    .trim_end_matches('>')
+                    .to_owned()
+            })
+    })
+}
 
 pub type FetchOutcome = Result<InboxSnapshot, FetchError>;
 pub type RepoFetchOutcome = Result<RepoPullRequestSnapshot, FetchError>;
 pub type NotificationMetadataOutcome = Result<Vec<NotificationMetadataUpdate>, FetchError>;
+pub type BatchApproveOutcome = Result<BatchApproveSummary, FetchError>;
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct DeviceCode {
+    pub device_code: String,
+    pub user_code: String,
+    pub verification_uri: String,
+    pub expires_in: u64,
+    pub interval: u64,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum DeviceTokenPoll {
+    Pending,
+    /// GitHub asked to poll less often, optionally naming the new interval.
+    SlowDown {
+        interval: Option<u64>,
+    },
+    Granted(String),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InboxFetchOptions {
+    /// Pages of notifications followed; search lists read one page.
+    pub max_pages: usize,
+    /// Skips already-read threads, which keeps low-data refreshes small.
+    pub unread_only: bool,
+    /// Asks only for threads updated after this, for merging into the
+    /// snapshot on hand.
+    pub since: Option<DateTime<Utc>>,
+    /// Tries per request before a transient failure is reported.
+    pub max_attempts: u32,
+    /// Search pages fetched past the first because the user loaded more.
+    pub extra_search_pages: ExtraSearchPages,
+}
+
+/// "Load more" clicks per search-backed list, kept for later refreshes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ExtraSearchPages {
+    pub review_requests: usize,
+    pub mentions: usize,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BatchApproveSummary {
+    pub approved: Vec<u64>,
+    pub skipped: Vec<u64>,
+}
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct NotificationMetadataUpdate {
@@ -798,20 +2767,366 @@ pub struct NotificationMetadataUpdate {
     pub head_ref: Option<String>,
     pub base_ref: Option<String>,
     pub my_review_status: Option<PullRequestReviewerStatus>,
+    pub latest_actor: Option<String>,
+    pub html_url: Option<String>,
 }
 
 #[derive(Error, Debug)]
 pub enum FetchError {
     #[error("GitHub API request failed: {0}")]
-    Http(#[from] reqwest::Error),
+    Http(reqwest::Error),
+    #[error("GitHub rejected the token (401); it may have expired or been revoked")]
+    Unauthorized,
+    #[error("GitHub denied access (403); the token may lack a scope or SSO authorization")]
+    Forbidden,
+    #[error("GitHub could not find the requested resource (404)")]
+    NotFound,
+    #[error("Could not reach GitHub: {0}")]
+    Network(reqwest::Error),
+    #[error("Could not read GitHub's response: {0}")]
+    Decode(reqwest::Error),
     #[error("Account token is missing")]
     MissingToken,
     #[error("Background worker disconnected before returning a result")]
     BackgroundWorkerGone,
+    #[error("GitHub rate limit reached; retrying after {reset_at}")]
+    RateLimited { reset_at: DateTime<Utc> },
+    #[error("GitHub sign-in failed: {0}")]
+    DeviceFlow(String),
+    #[error("GitHub rejected the token; check that it was copied completely and has not expired")]
+    TokenRejected,
+    #[error("This token belongs to {actual}, not {expected}")]
+    LoginMismatch { expected: String, actual: String },
+    #[error(
+        "Token lacks the notifications scope; create a classic token with `notifications` (or `repo`) selected"
+    )]
+    MissingNotificationsScope,
+    #[error("GitHub GraphQL query failed: {0}")]
+    GraphQl(String),
+    /// A request outlived the network timeouts (`seconds` is `None`), or a
+    /// whole refresh outlived its deadline.
+    #[error("{}", timeout_message(*.seconds))]
+    Timeout { seconds: Option<u64> },
+    #[error("{0}")]
+    ClientSetup(#[from] NetworkError),
+}
+
+fn timeout_message(seconds: Option<u64>) -> String {
+    match seconds {
+        Some(seconds) => format!("Refresh did not finish within {seconds}s; it will be retried"),
+        None => {
+            String::from("GitHub did not answer in time; check the network timeouts in Preferences")
+        }
+    }
+}
+
+impl From<reqwest::Error> for FetchError {
+    fn from(err: reqwest::Error) -> Self {
+        match err.status() {
+            Some(StatusCode::UNAUTHORIZED) => Self::Unauthorized,
+            Some(StatusCode::FORBIDDEN) => Self::Forbidden,
+            Some(StatusCode::NOT_FOUND) => Self::NotFound,
+            Some(_) => Self::Http(err),
+            None if err.is_decode() => Self::Decode(err),
+            None if err.is_timeout() => Self::Timeout { seconds: None },
+            None if err.is_connect() || err.is_request() || err.is_body() => Self::Network(err),
+            None => Self::Http(err),
+        }
+    }
+}
+
+impl FetchError {
+    /// Failures of the GraphQL inbox query that the REST path may not hit,
+    /// such as a host without GraphQL or a query it rejects.
+    fn warrants_rest_fallback(&self) -> bool {
+        matches!(
+            self,
+            Self::GraphQl(_) | Self::Http(_) | Self::Decode(_) | Self::NotFound | Self::Forbidden
+        )
+    }
+
+    /// Network failures and 5xx answers may pass on a retry; auth failures,
+    /// other 4xx answers and rate limits will not.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::Network(_) | Self::Timeout { .. } => true,
+            Self::Http(err) => err.status().is_some_and(|status| status.is_server_error()),
+            _ => false,
+        }
+    }
 }
 
 // Response payloads ---------------------------------------------------------
 
+#[derive(Debug, Deserialize)]
+struct DeviceTokenResponse {
+    access_token: Option<String>,
+    error: Option<String>,
+    error_description: Option<String>,
+    interval: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct UserResponse {
+    login: String,
+}
+
+#[derive(Serialize)]
+struct GraphQlQuery<'a> {
+    query: &'a str,
+}
+
+#[derive(Debug, Deserialize)]
+struct ViewerResponse {
+    data: Option<ViewerData>,
+    #[serde(default)]
+    errors: Vec<GraphQlError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ViewerData {
+    viewer: UserResponse,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQlError {
+    message: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct DiscussionSearchResponse {
+    data: Option<DiscussionSearchData>,
+    #[serde(default)]
+    errors: Vec<GraphQlError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DiscussionSearchData {
+    search: DiscussionSearch,
+}
+
+#[derive(Debug, Deserialize)]
+struct DiscussionSearch {
+    #[serde(default)]
+    nodes: Vec<DiscussionNode>,
+}
+
+/// Search hits of other types come back as empty objects.
+#[derive(Debug, Deserialize)]
+struct DiscussionNode {
+    title: Option<String>,
+    url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct InboxListsResponse {
+    data: Option<InboxListsData>,
+    #[serde(default)]
+    errors: Vec<GraphQlError>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct InboxListsData {
+    review_requests: SearchConnection,
+    mentions: SearchConnection,
+    reviewed: SearchConnection,
+    assigned: SearchConnection,
+    authored: SearchConnection,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchConnection {
+    #[serde(default, rename = "issueCount")]
+    issue_count: u64,
+    #[serde(default)]
+    nodes: Vec<Option<SearchNode>>,
+}
+
+/// An issue or pull request search hit; pull request fields stay empty on
+/// issues.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SearchNode {
+    #[serde(rename = "__typename")]
+    typename: String,
+    database_id: Option<u64>,
+    number: u64,
+    title: String,
+    url: String,
+    updated_at: DateTime<Utc>,
+    state: String,
+    repository: SearchNodeRepository,
+    labels: Option<SearchNodeLabels>,
+    #[serde(default)]
+    is_draft: bool,
+    mergeable: Option<String>,
+    review_decision: Option<String>,
+    commits: Option<StatusCommits>,
+    timeline_items: Option<ReviewRequestedEvents>,
+}
+
+impl SearchNode {
+    fn is_pull_request(&self) -> bool {
+        self.typename == "PullRequest"
+    }
+
+    fn label_names(&self) -> Vec<String> {
+        self.labels
+            .iter()
+            .flat_map(|labels| labels.nodes.iter().map(|label| label.name.clone()))
+            .collect()
+    }
+
+    fn pull_request_status(&self) -> Option<PullRequestStatus> {
+        if !self.is_pull_request() {
+            return None;
+        }
+        Some(pull_request_status(
+            self.is_draft,
+            self.mergeable.as_deref().unwrap_or_default(),
+            self.review_decision.as_deref(),
+            self.commits.as_ref(),
+        ))
+    }
+
+    /// The latest request for a review from `login`.
+    fn review_request_for(&self, login: &str) -> Option<&ReviewRequestedEvent> {
+        self.timeline_items
+            .as_ref()?
+            .nodes
+            .iter()
+            .rev()
+            .find(|event| {
+                event
+                    .requested_reviewer
+                    .as_ref()
+                    .and_then(|reviewer| reviewer.login.as_deref())
+                    .is_some_and(|reviewer| reviewer.eq_ignore_ascii_case(login))
+            })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SearchNodeRepository {
+    name_with_owner: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchNodeLabels {
+    #[serde(default)]
+    nodes: Vec<SearchLabel>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReviewRequestedEvents {
+    #[serde(default)]
+    nodes: Vec<ReviewRequestedEvent>,
+}
+
+/// Team reviewers come back without a login.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ReviewRequestedEvent {
+    actor: Option<GitHubUser>,
+    requested_reviewer: Option<RequestedReviewerNode>,
+    created_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RequestedReviewerNode {
+    login: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PullRequestStatusResponse {
+    data: Option<BTreeMap<String, Option<StatusRepository>>>,
+    #[serde(default)]
+    errors: Vec<GraphQlError>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StatusRepository {
+    pull_request: Option<StatusPullRequest>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StatusPullRequest {
+    is_draft: bool,
+    mergeable: String,
+    review_decision: Option<String>,
+    commits: StatusCommits,
+}
+
+impl StatusPullRequest {
+    fn status(&self) -> PullRequestStatus {
+        pull_request_status(
+            self.is_draft,
+            &self.mergeable,
+            self.review_decision.as_deref(),
+            Some(&self.commits),
+        )
+    }
+}
+
+/// Badges from the fields both the status lookup and the GraphQL inbox ask
+/// pull requests for.
+fn pull_request_status(
+    is_draft: bool,
+    mergeable: &str,
+    review_decision: Option<&str>,
+    commits: Option<&StatusCommits>,
+) -> PullRequestStatus {
+    let rollup = commits
+        .and_then(|commits| commits.nodes.last())
+        .and_then(|node| node.commit.status_check_rollup.as_ref());
+    PullRequestStatus {
+        draft: is_draft,
+        mergeable: match mergeable {
+            "MERGEABLE" => Some(true),
+            "CONFLICTING" => Some(false),
+            _ => None,
+        },
+        checks: rollup.and_then(|rollup| match rollup.state.as_str() {
+            "SUCCESS" => Some(CheckState::Passing),
+            "FAILURE" | "ERROR" => Some(CheckState::Failing),
+            "PENDING" | "EXPECTED" => Some(CheckState::Pending),
+            _ => None,
+        }),
+        review_decision: review_decision.and_then(|decision| match decision {
+            "APPROVED" => Some(ReviewDecision::Approved),
+            "CHANGES_REQUESTED" => Some(ReviewDecision::ChangesRequested),
+            "REVIEW_REQUIRED" => Some(ReviewDecision::ReviewRequired),
+            _ => None,
+        }),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct StatusCommits {
+    #[serde(default)]
+    nodes: Vec<StatusCommitNode>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StatusCommitNode {
+    commit: StatusCommit,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StatusCommit {
+    status_check_rollup: Option<StatusCheckRollup>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StatusCheckRollup {
+    state: String,
+}
+
 #[derive(Debug, Deserialize)]
 struct NotificationResponse {
     id: String,
@@ -826,30 +3141,90 @@ struct NotificationResponse {
 #[derive(Debug, Deserialize)]
 struct NotificationSubject {
     title: String,
+    #[serde(default, rename = "type")]
+    kind: String,
     url: Option<String>,
+    latest_comment_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NotificationRepository {
+    full_name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct PullRequestResponse {
+    html_url: String,
+    number: u64,
+    title: String,
+    head: PullRequestBranchRef,
+    base: PullRequestBranchRef,
+    updated_at: DateTime<Utc>,
+    user: Option<GitHubUser>,
+    #[serde(default)]
+    draft: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct RepositoryResponse {
+    default_branch: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct BranchResponse {
+    name: String,
+    commit: BranchHead,
+    #[serde(default)]
+    protected: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct BranchHead {
+    sha: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct BranchCommitResponse {
+    author: Option<GitHubUser>,
+    commit: BranchCommitDetail,
+}
+
+#[derive(Debug, Deserialize)]
+struct BranchCommitDetail {
+    committer: BranchCommitSignature,
+}
+
+#[derive(Debug, Deserialize)]
+struct BranchCommitSignature {
+    date: DateTime<Utc>,
 }
 
 #[derive(Debug, Deserialize)]
-struct NotificationRepository {
-    full_name: String,
+struct PullRequestBranchRef {
+    r#ref: String,
+    #[serde(default)]
+    sha: String,
 }
 
 #[derive(Debug, Deserialize)]
-struct PullRequestResponse {
-    html_url: String,
-    number: u64,
-    title: String,
-    head: PullRequestBranchRef,
-    base: PullRequestBranchRef,
-    updated_at: DateTime<Utc>,
-    user: Option<GitHubUser>,
+struct CheckRunsResponse {
     #[serde(default)]
-    draft: bool,
+    total_count: usize,
+    #[serde(default)]
+    check_runs: Vec<CheckRunResponse>,
 }
 
 #[derive(Debug, Deserialize)]
-struct PullRequestBranchRef {
-    r#ref: String,
+struct CheckRunResponse {
+    status: String,
+    conclusion: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CombinedStatusResponse {
+    state: String,
+    #[serde(default)]
+    total_count: u64,
 }
 
 #[derive(Debug, Deserialize)]
@@ -869,6 +3244,57 @@ struct IssueEventResponse {
     review_requester: Option<GitHubUser>,
 }
 
+#[derive(Debug, Deserialize)]
+struct TimelineEventResponse {
+    event: Option<String>,
+    created_at: Option<DateTime<Utc>>,
+    submitted_at: Option<DateTime<Utc>>,
+    actor: Option<GitHubUser>,
+    user: Option<GitHubUser>,
+    state: Option<String>,
+    body: Option<String>,
+    label: Option<TimelineLabel>,
+    sha: Option<String>,
+    message: Option<String>,
+    author: Option<TimelineCommitAuthor>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ThreadDetailsResponse {
+    title: String,
+    state: String,
+    user: Option<GitHubUser>,
+    #[serde(default)]
+    labels: Vec<SearchLabel>,
+    body: Option<String>,
+    #[serde(default)]
+    comments: u64,
+    pull_request: Option<ThreadPullRequestLink>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ThreadPullRequestLink {
+    merged_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ThreadCommentResponse {
+    user: Option<GitHubUser>,
+    body: Option<String>,
+    created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TimelineLabel {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct TimelineCommitAuthor {
+    name: Option<String>,
+    date: Option<DateTime<Utc>>,
+}
+
 #[derive(Debug, Deserialize)]
 struct PullRequestReviewResponse {
     id: u64,
@@ -877,6 +3303,16 @@ struct PullRequestReviewResponse {
     submitted_at: Option<DateTime<Utc>>,
 }
 
+#[derive(Debug, Deserialize)]
+struct LatestCommentResponse {
+    user: Option<GitHubUser>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HtmlUrlResponse {
+    html_url: Option<String>,
+}
+
 #[derive(Clone, Copy, Debug)]
 struct SubmittedReviewState {
     review_id: u64,
@@ -898,6 +3334,18 @@ struct ReviewRequestMutationBody {
     team_reviewers: Vec<String>,
 }
 
+#[derive(Debug, Serialize)]
+struct PullRequestReviewBody<'a> {
+    event: &'static str,
+    #[serde(skip_serializing_if = "str::is_empty")]
+    body: &'a str,
+}
+
+#[derive(Debug, Serialize)]
+struct IssueCommentBody<'a> {
+    body: &'a str,
+}
+
 impl ReviewRequestMutationBody {
     fn new(reviewer_login: &str) -> Self {
         if let Some((_, team_slug)) = reviewer_login.split_once('/') {
@@ -921,23 +3369,480 @@ impl ReviewRequestMutationBody {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::Forge;
+    use reqwest::header::HeaderValue;
+
+    fn quota_headers(remaining: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ratelimit-limit", HeaderValue::from_static("5000"));
+        headers.insert(
+            "x-ratelimit-remaining",
+            HeaderValue::from_str(remaining).unwrap(),
+        );
+        headers.insert("x-ratelimit-reset", HeaderValue::from_static("1700000000"));
+        headers
+    }
+
+    #[test]
+    fn rate_limit_from_headers_parses_quota() {
+        let rate_limit = rate_limit_from_headers(&quota_headers("42")).unwrap();
+
+        assert_eq!(rate_limit.limit, 5000);
+        assert_eq!(rate_limit.remaining, 42);
+        assert_eq!(rate_limit.reset_at.timestamp(), 1_700_000_000);
+        assert!(rate_limit_from_headers(&HeaderMap::new()).is_none());
+    }
+
+    #[test]
+    fn only_the_core_quota_is_recorded() {
+        let with_resource = |remaining: &str, resource: &'static str| {
+            let mut headers = quota_headers(remaining);
+            headers.insert("x-ratelimit-resource", HeaderValue::from_static(resource));
+            headers
+        };
+        let latest = LATEST_RATE_LIMIT.sync_scope(Cell::new(None), || {
+            for headers in [
+                with_resource("4200", "core"),
+                with_resource("29", "search"),
+                with_resource("4900", "graphql"),
+            ] {
+                record_rate_limit(&headers, rate_limit_from_headers(&headers));
+            }
+            LATEST_RATE_LIMIT.with(Cell::get)
+        });
+        assert_eq!(latest.map(|rate_limit| rate_limit.remaining), Some(4200));
+
+        let latest = LATEST_RATE_LIMIT.sync_scope(Cell::new(None), || {
+            let headers = quota_headers("4100");
+            record_rate_limit(&headers, rate_limit_from_headers(&headers));
+            LATEST_RATE_LIMIT.with(Cell::get)
+        });
+        assert_eq!(latest.map(|rate_limit| rate_limit.remaining), Some(4100));
+    }
 
     #[test]
-    fn classify_thread_distinguishes_pr_and_issue() {
+    fn network_settings_reject_bad_proxies_and_ca_bundles() {
+        let settings = |proxy: &str, ca_bundle: &str| NetworkSettings {
+            proxy: proxy.to_owned(),
+            ca_bundle: ca_bundle.to_owned(),
+            ..NetworkSettings::default()
+        };
+        assert!(client_builder(&settings("http://proxy.corp:3128", "")).is_ok());
+        assert!(client_builder(&settings("socks5://127.0.0.1:1080", "")).is_ok());
         assert!(matches!(
-            classify_thread("https://api.github.com/repos/acme/r/pull/1"),
-            MentionKind::PullRequest
+            client_builder(&settings("http://[::1", "")),
+            Err(NetworkError::Proxy { .. })
         ));
         assert!(matches!(
-            classify_thread("https://api.github.com/repos/acme/r/issues/2"),
-            MentionKind::Issue
+            client_builder(&settings("", "/nonexistent/corp-root.pem")),
+            Err(NetworkError::ReadCaBundle { .. })
+        ));
+
+        let path = std::env::temp_dir().join(format!("reminder-ca-{}.pem", std::process::id()));
+        fs::write(&path, "not a certificate").unwrap();
+        let empty = client_builder(&settings("", &path.to_string_lossy()));
+        fs::remove_file(&path).unwrap();
+        assert!(matches!(empty, Err(NetworkError::EmptyCaBundle(_))));
+    }
+
+    #[test]
+    fn rate_limited_until_detects_primary_and_secondary_limits() {
+        let now = Utc::now();
+        let exhausted = quota_headers("0");
+        let primary = rate_limited_until(
+            StatusCode::FORBIDDEN,
+            &exhausted,
+            rate_limit_from_headers(&exhausted),
+            now,
+        );
+        assert_eq!(primary.map(|reset| reset.timestamp()), Some(1_700_000_000));
+
+        let mut retry_after = HeaderMap::new();
+        retry_after.insert(RETRY_AFTER, HeaderValue::from_static("30"));
+        assert_eq!(
+            rate_limited_until(StatusCode::FORBIDDEN, &retry_after, None, now),
+            Some(now + chrono::Duration::seconds(30))
+        );
+
+        let plenty = quota_headers("100");
+        assert_eq!(
+            rate_limited_until(
+                StatusCode::FORBIDDEN,
+                &plenty,
+                rate_limit_from_headers(&plenty),
+                now
+            ),
+            None
+        );
+        assert_eq!(
+            rate_limited_until(StatusCode::OK, &exhausted, None, now),
+            None
+        );
+    }
+
+    #[test]
+    fn lacks_notifications_scope_checks_classic_scope_list() {
+        assert!(lacks_notifications_scope(Some("read:org, gist")));
+        assert!(lacks_notifications_scope(Some("")));
+        assert!(!lacks_notifications_scope(Some("read:org, notifications")));
+        assert!(!lacks_notifications_scope(Some("repo")));
+        assert!(!lacks_notifications_scope(None));
+    }
+
+    #[test]
+    fn device_token_poll_maps_pending_and_errors() {
+        let response = |body: &str| serde_json::from_str::<DeviceTokenResponse>(body).unwrap();
+
+        assert_eq!(
+            device_token_poll(response(r#"{"error":"authorization_pending"}"#)).unwrap(),
+            DeviceTokenPoll::Pending
+        );
+        assert_eq!(
+            device_token_poll(response(r#"{"error":"slow_down","interval":10}"#)).unwrap(),
+            DeviceTokenPoll::SlowDown { interval: Some(10) }
+        );
+        assert_eq!(
+            device_token_poll(response(
+                r#"{"access_token":"gho_abc","token_type":"bearer"}"#
+            ))
+            .unwrap(),
+            DeviceTokenPoll::Granted(String::from("gho_abc"))
+        );
+        let err = device_token_poll(response(
+            r#"{"error":"expired_token","error_description":"The device code has expired."}"#,
+        ))
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "GitHub sign-in failed: The device code has expired."
+        );
+    }
+
+    #[test]
+    fn next_page_url_follows_rel_next_only() {
+        let header = concat!(
+            "<https://api.github.com/notifications?all=true&page=2>; rel=\"next\", ",
+            "<https://api.github.com/notifications?all=true&page=5>; rel=\"last\""
+        );
+
+        assert_eq!(
+            next_page_url(header).as_deref(),
+            Some("https://api.github.com/notifications?all=true&page=2")
+        );
+        assert_eq!(
+            next_page_url("<https://api.github.com/notifications?page=1>; rel=\"prev\""),
+            None
+        );
+    }
+
+    fn check_run(status: &str, conclusion: Option<&str>) -> CheckRunResponse {
+        CheckRunResponse {
+            status: status.to_owned(),
+            conclusion: conclusion.map(str::to_owned),
+        }
+    }
+
+    fn combined_status(state: &str, total_count: u64) -> CombinedStatusResponse {
+        CombinedStatusResponse {
+            state: state.to_owned(),
+            total_count,
+        }
+    }
+
+    #[test]
+    fn checks_pass_requires_completed_successful_runs() {
+        let no_statuses = combined_status("pending", 0);
+
+        assert!(checks_pass(
+            &[
+                check_run("completed", Some("success")),
+                check_run("completed", Some("skipped"))
+            ],
+            2,
+            &no_statuses
+        ));
+        assert!(!checks_pass(
+            &[check_run("in_progress", None)],
+            1,
+            &no_statuses
+        ));
+        assert!(!checks_pass(
+            &[check_run("completed", Some("failure"))],
+            1,
+            &no_statuses
+        ));
+        assert!(!checks_pass(&[], 0, &no_statuses));
+        // A second page that was never read may hold a failing run.
+        assert!(!checks_pass(
+            &[check_run("completed", Some("success"))],
+            101,
+            &no_statuses
+        ));
+    }
+
+    #[test]
+    fn checks_pass_respects_legacy_commit_statuses() {
+        let runs = [check_run("completed", Some("success"))];
+
+        assert!(checks_pass(&runs, 1, &combined_status("success", 2)));
+        assert!(!checks_pass(&runs, 1, &combined_status("failure", 2)));
+        assert!(checks_pass(&[], 0, &combined_status("success", 1)));
+    }
+
+    #[test]
+    fn notification_subject_urls_point_at_the_account_host() {
+        let mut profile = GitHubAccount {
+            login: String::from("neo"),
+            token: String::from("token"),
+            review_settings: crate::domain::ReviewCommandSettings::default(),
+            hide_bot_notifications: None,
+            api_base_url: None,
+            working_hours: None,
+            token_command: None,
+            group: None,
+            section_alerts: None,
+            inbox_backend: InboxBackend::Rest,
+            forge: Forge::GitHub,
+        };
+        let issue = |url: &str| NotificationSubject {
+            title: String::from("Bug"),
+            kind: String::from("Issue"),
+            url: Some(url.to_owned()),
+            latest_comment_url: None,
+        };
+        assert_eq!(
+            notification_html_url(
+                &profile,
+                "acme/app",
+                &issue("https://api.github.com/repos/acme/app/issues/3")
+            ),
+            "https://github.com/acme/app/issues/3"
+        );
+
+        profile.api_base_url = Some(String::from("https://github.mycorp.com/api/v3"));
+        assert_eq!(
+            notification_html_url(
+                &profile,
+                "acme/app",
+                &issue("https://github.mycorp.com/api/v3/repos/acme/app/issues/3")
+            ),
+            "https://github.mycorp.com/acme/app/issues/3"
+        );
+    }
+
+    #[test]
+    fn notifications_without_a_subject_url_link_to_the_repository() {
+        let profile = GitHubAccount {
+            login: String::from("neo"),
+            token: String::from("token"),
+            review_settings: crate::domain::ReviewCommandSettings::default(),
+            hide_bot_notifications: None,
+            api_base_url: None,
+            working_hours: None,
+            token_command: None,
+            group: None,
+            section_alerts: None,
+            inbox_backend: InboxBackend::Rest,
+            forge: Forge::GitHub,
+        };
+        let subject = |kind: &str, url: Option<&str>| NotificationSubject {
+            title: String::from("CI"),
+            kind: kind.to_owned(),
+            url: url.map(str::to_owned),
+            latest_comment_url: None,
+        };
+
+        assert_eq!(
+            notification_html_url(&profile, "acme/app", &subject("CheckSuite", None)),
+            "https://github.com/acme/app/actions"
+        );
+        assert_eq!(
+            notification_html_url(&profile, "acme/app", &subject("RepositoryInvitation", None)),
+            "https://github.com/acme/app"
+        );
+        assert_eq!(
+            notification_html_url(
+                &profile,
+                "acme/app",
+                &subject(
+                    "PullRequest",
+                    Some("https://api.github.com/repos/acme/app/pulls/7")
+                )
+            ),
+            "https://github.com/acme/app/pull/7"
+        );
+    }
+
+    #[test]
+    fn status_queue_skips_fresh_lookups_and_batches_the_rest() {
+        let now = Utc::now();
+        let ttl = chrono::Duration::minutes(10);
+        let fresh = (String::from("acme/app"), 1);
+        let bumped = (String::from("acme/app"), 2);
+        let mut queue = PullRequestStatusQueue::default();
+        queue.request(fresh.clone(), now, now, ttl);
+        queue.request(bumped.clone(), now, now, ttl);
+        let batch = queue.next_batch();
+        assert_eq!(batch.len(), 2);
+        assert!(!queue.has_pending());
+
+        let approved = PullRequestStatus {
+            review_decision: Some(ReviewDecision::Approved),
+            ..PullRequestStatus::default()
+        };
+        queue.record(
+            &batch,
+            BTreeMap::from([(fresh.clone(), approved.clone())]),
+            now,
+            ttl,
+        );
+        assert_eq!(queue.status(&fresh), Some(&approved));
+        assert_eq!(queue.status(&bumped), Some(&PullRequestStatus::default()));
+
+        let later = now + chrono::Duration::minutes(1);
+        queue.request(fresh, now, later, ttl);
+        queue.request(bumped.clone(), later, later, ttl);
+        assert_eq!(queue.next_batch(), vec![bumped]);
+    }
+
+    #[test]
+    fn status_response_maps_graphql_states() {
+        let response: PullRequestStatusResponse = serde_json::from_str(
+            r#"{"data": {
+                "pr0": {"pullRequest": {
+                    "isDraft": true,
+                    "mergeable": "CONFLICTING",
+                    "reviewDecision": "CHANGES_REQUESTED",
+                    "commits": {"nodes": [{"commit": {"statusCheckRollup": {"state": "FAILURE"}}}]}
+                }},
+                "pr1": null
+            }}"#,
+        )
+        .expect("response");
+        let data = response.data.expect("data");
+        let status = data["pr0"]
+            .as_ref()
+            .and_then(|repository| repository.pull_request.as_ref())
+            .expect("pull request")
+            .status();
+
+        assert_eq!(
+            status,
+            PullRequestStatus {
+                draft: true,
+                mergeable: Some(false),
+                checks: Some(CheckState::Failing),
+                review_decision: Some(ReviewDecision::ChangesRequested),
+            }
+        );
+        assert!(data["pr1"].is_none());
+        assert!(
+            pull_request_status_query(&[(String::from("acme/app"), 7)]).contains(
+                r#"pr0: repository(owner: "acme", name: "app") { pullRequest(number: 7)"#
+            )
+        );
+    }
+
+    #[test]
+    fn discussion_lookup_prefers_exact_titles_and_quotes_the_search() {
+        let nodes: Vec<DiscussionNode> = serde_json::from_str(
+            r#"[
+                {},
+                {"title": "Roadmap 2027", "url": "https://github.com/acme/app/discussions/9"},
+                {"title": "Roadmap", "url": "https://github.com/acme/app/discussions/4"}
+            ]"#,
+        )
+        .unwrap();
+        assert_eq!(
+            matching_discussion_url(nodes, "Roadmap").as_deref(),
+            Some("https://github.com/acme/app/discussions/4")
+        );
+        assert_eq!(matching_discussion_url(Vec::new(), "Roadmap"), None);
+
+        let query = discussion_search_query("acme/app", r#"Say "hi""#);
+        assert!(query.contains(r#"search(query: "repo:acme/app in:title \"Say  hi \"""#));
+        assert!(query.contains("type: DISCUSSION"));
+    }
+
+    #[test]
+    fn graphql_lists_carry_requesters_labels_and_badges() {
+        let data: InboxListsData = serde_json::from_str(
+            r#"{
+                "reviewRequests": {"issueCount": 140, "nodes": [{
+                    "__typename": "PullRequest", "databaseId": 11, "number": 7,
+                    "title": "Fix login", "url": "https://github.com/acme/app/pull/7",
+                    "updatedAt": "2026-10-01T09:00:00Z", "state": "OPEN",
+                    "repository": {"nameWithOwner": "acme/app"},
+                    "labels": {"nodes": [{"name": "bug"}]},
+                    "isDraft": true, "mergeable": "CONFLICTING", "reviewDecision": "REVIEW_REQUIRED",
+                    "commits": {"nodes": [{"commit": {"statusCheckRollup": {"state": "FAILURE"}}}]},
+                    "timelineItems": {"nodes": [
+                        {"actor": {"login": "morpheus"}, "requestedReviewer": {"login": "neo"}},
+                        {"actor": {"login": "trinity"}, "requestedReviewer": {}},
+                        {"actor": {"login": "trinity"}, "requestedReviewer": {"login": "NEO"},
+                         "createdAt": "2026-09-30T12:00:00Z"}
+                    ]}
+                }, null]},
+                "mentions": {"nodes": []},
+                "reviewed": {"nodes": []},
+                "assigned": {"nodes": [{
+                    "__typename": "Issue", "databaseId": 12, "number": 3,
+                    "title": "Crash", "url": "https://github.com/acme/app/issues/3",
+                    "updatedAt": "2026-10-01T09:00:00Z", "state": "OPEN",
+                    "repository": {"nameWithOwner": "acme/app"},
+                    "labels": {"nodes": [{"name": "p1"}, {"name": "ux"}]}
+                }]},
+                "authored": {"nodes": []}
+            }"#,
+        )
+        .unwrap();
+
+        let lists = search_lists_from_graphql(data, "neo");
+
+        assert_eq!(lists.review_requests.len(), 1);
+        assert_eq!(lists.review_requests[0].title, "#7 Fix login");
+        assert_eq!(
+            lists.review_requests[0].requested_by.as_deref(),
+            Some("trinity")
+        );
+        assert_eq!(
+            lists.review_requests[0].requested_at,
+            Some("2026-09-30T12:00:00Z".parse().unwrap())
+        );
+        assert_eq!(
+            lists.search_totals,
+            SearchTotals {
+                review_requests: 140,
+                mentions: 0,
+            }
+        );
+        assert_eq!(lists.assigned_issues[0].labels, ["p1", "ux"]);
+        let [(key, status)] = lists.pull_request_statuses.as_slice() else {
+            panic!("expected one status");
+        };
+        assert_eq!(key, &(String::from("acme/app"), 7));
+        assert!(status.draft);
+        assert_eq!(status.mergeable, Some(false));
+        assert_eq!(status.checks, Some(CheckState::Failing));
+        assert_eq!(status.review_decision, Some(ReviewDecision::ReviewRequired));
+        assert!(inbox_lists_query("neo").contains(
+            r#"assigned: search(query: "is:issue assignee:neo is:open sort:updated-desc""#
         ));
     }
 
     #[test]
-    fn extract_repo_name_trims_prefix() {
-        let repo = extract_repo_name("https://api.github.com/repos/acme/widgets");
-        assert_eq!(repo, "acme/widgets");
+    fn loading_more_continues_after_the_pages_already_shown() {
+        assert_eq!(next_search_page(0), 1);
+        assert_eq!(next_search_page(100), 2);
+        assert_eq!(next_search_page(187), 2);
+
+        let mut urls = vec![String::from("a"), String::from("b")];
+        append_new_hits(
+            &mut urls,
+            vec![String::from("b"), String::from("c")],
+            |url| url,
+        );
+        assert_eq!(urls, ["a", "b", "c"]);
     }
 
     #[test]
@@ -947,13 +3852,25 @@ mod tests {
             login: "user".into(),
             token: String::new(),
             review_settings: crate::domain::ReviewCommandSettings::default(),
+            hide_bot_notifications: None,
+            api_base_url: None,
+            working_hours: None,
+            token_command: None,
+            group: None,
+            section_alerts: None,
+            inbox_backend: InboxBackend::Rest,
+            forge: Forge::GitHub,
         };
-        let result = mark_notification_read(&client, &profile, "thread123");
+        let result = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("runtime")
+            .block_on(mark_notification_read(&client, &profile, "thread123"));
         assert!(matches!(result, Err(FetchError::MissingToken)));
     }
 
     #[test]
-    fn review_requester_for_user_tracks_latest_active_request() {
+    fn active_review_request_tracks_latest_request_and_its_time() {
         let events = vec![
             IssueEventResponse {
                 event: String::from("review_requested"),
@@ -997,8 +3914,11 @@ mod tests {
         ];
 
         assert_eq!(
-            review_requester_for_user_from_issue_events(events, "neo"),
-            Some(String::from("bob"))
+            active_review_request_from_issue_events(events, "neo"),
+            Some(ActiveReviewRequest {
+                requester: Some(String::from("bob")),
+                requested_at: "2026-04-03T00:00:00Z".parse().unwrap(),
+            })
         );
     }
 
@@ -1198,10 +4118,136 @@ mod tests {
         assert!(body.reviewers.is_empty());
         assert_eq!(body.team_reviewers, vec![String::from("platform")]);
     }
+
+    #[test]
+    fn retry_delay_doubles_up_to_the_cap_with_bounded_jitter() {
+        assert_eq!(retry_delay(1, 0.0), Duration::from_secs(5));
+        assert_eq!(retry_delay(3, 0.0), Duration::from_secs(20));
+        assert_eq!(retry_delay(3, 0.99), Duration::from_secs(24));
+        assert_eq!(
+            retry_delay(30, 0.0),
+            Duration::from_secs(RETRY_MAX_DELAY_SECS)
+        );
+    }
+
+    #[test]
+    fn stalled_responses_surface_as_request_timeouts() {
+        // Accepts the connection but never answers.
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let client = client_builder(&NetworkSettings::default())
+            .unwrap()
+            .read_timeout(Duration::from_millis(200))
+            .build()
+            .unwrap();
+
+        let err = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("runtime")
+            .block_on(async { client.get(url).send().await })
+            .unwrap_err();
+        drop(listener);
+
+        let err = FetchError::from(err);
+        assert!(matches!(err, FetchError::Timeout { seconds: None }));
+        assert!(err.is_retryable());
+    }
+
+    #[test]
+    fn only_transient_errors_are_retried() {
+        assert!(!FetchError::TokenRejected.is_retryable());
+        assert!(!FetchError::MissingToken.is_retryable());
+        assert!(
+            !FetchError::RateLimited {
+                reset_at: Utc::now()
+            }
+            .is_retryable()
+        );
+    }
+
+    fn parse_timeline_event(json: &str) -> Option<TimelineEvent> {
+        timeline_event(serde_json::from_str(json).expect("timeline json"))
+    }
+
+    #[test]
+    fn timeline_events_are_summarized_per_kind() {
+        let comment = parse_timeline_event(
+            r#"{"event":"commented","created_at":"2024-01-02T00:00:00Z",
+                "actor":{"login":"neo"},"body":"\nLooks good to me\nsecond line"}"#,
+        )
+        .unwrap();
+        assert_eq!(comment.actor.as_deref(), Some("neo"));
+        assert_eq!(comment.summary, "commented: Looks good to me");
+
+        let review = parse_timeline_event(
+            r#"{"event":"reviewed","submitted_at":"2024-01-03T00:00:00Z",
+                "user":{"login":"trinity"},"state":"changes_requested"}"#,
+        )
+        .unwrap();
+        assert_eq!(review.actor.as_deref(), Some("trinity"));
+        assert_eq!(review.summary, "requested changes");
+
+        let commit = parse_timeline_event(
+            r#"{"event":"committed","sha":"0123456789abcdef","message":"Fix cache",
+                "author":{"name":"Morpheus","date":"2024-01-04T00:00:00Z"}}"#,
+        )
+        .unwrap();
+        assert_eq!(commit.actor.as_deref(), Some("Morpheus"));
+        assert_eq!(commit.summary, "pushed 0123456 Fix cache");
+
+        let label = parse_timeline_event(
+            r#"{"event":"unlabeled","created_at":"2024-01-05T00:00:00Z",
+                "actor":{"login":"neo"},"label":{"name":"bug"}}"#,
+        )
+        .unwrap();
+        assert_eq!(label.summary, "removed label bug");
+
+        assert!(
+            parse_timeline_event(r#"{"event":"subscribed","created_at":"2024-01-05T00:00:00Z"}"#)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn timeline_snippet_shortens_long_lines() {
+        let snippet = timeline_snippet(&"a".repeat(100));
+
+        assert_eq!(snippet.chars().count(), TIMELINE_SNIPPET_CHARS + 1);
+        assert!(snippet.ends_with('…'));
+    }
+
+    #[test]
+    fn thread_details_keep_the_latest_comments_and_report_merges() {
+        let issue = serde_json::from_str(
+            r#"{"title":"Add cache","state":"closed","user":{"login":"neo"},
+                "labels":[{"name":"perf"}],"body":null,"comments":3,
+                "pull_request":{"merged_at":"2024-01-02T00:00:00Z"}}"#,
+        )
+        .expect("issue json");
+        let comments = serde_json::from_str(
+            r#"[{"user":{"login":"a"},"body":"first","created_at":"2024-01-01T00:00:00Z"},
+                {"user":{"login":"b"},"body":"second","created_at":"2024-01-01T01:00:00Z"},
+                {"user":null,"body":"third","created_at":"2024-01-01T02:00:00Z"}]"#,
+        )
+        .expect("comments json");
+
+        let details = thread_details(issue, comments, 2);
+
+        assert_eq!(details.state, "merged");
+        assert_eq!(details.author.as_deref(), Some("neo"));
+        assert_eq!(details.labels, ["perf"]);
+        assert!(details.body.is_empty());
+        let bodies: Vec<_> = details.comments.iter().map(|c| c.body.as_str()).collect();
+        assert_eq!(bodies, ["second", "third"]);
+        assert_eq!(details.comments[1].author, None);
+    }
 }
 
 #[derive(Debug, Deserialize)]
 struct SearchResponse {
+    #[serde(default)]
+    total_count: u64,
     items: Vec<SearchItem>,
 }
 
@@ -1214,6 +4260,15 @@ struct SearchItem {
     number: u64,
     updated_at: DateTime<Utc>,
     state: String,
+    #[serde(default)]
+    draft: bool,
+    #[serde(default)]
+    labels: Vec<SearchLabel>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchLabel {
+    name: String,
 }
 
 #[derive(Clone, Debug, Deserialize)]
@@ -1235,7 +4290,7 @@ impl GitHubTeam {
 }
 
 fn team_identifier_from_html_url(html_url: &str) -> Option<String> {
-    let (_, suffix) = html_url.split_once("github.com/orgs/")?;
+    let (_, suffix) = html_url.split_once("/orgs/")?;
     let (org, rest) = suffix.split_once("/teams/")?;
     let slug = rest.split(['/', '?', '#']).next()?;
     Some(format!("{org}/{slug}"))
@@ -0,0 +1,279 @@
+//! Maps the REST API URLs that notifications and searches carry to the
+//! pages a browser should open, on github.com and Enterprise Server alike.
+
+use crate::domain::{ENTERPRISE_API_PATH, GITHUB_API_BASE, GITHUB_WEB_BASE, MentionKind};
+
+/// Page of a notification subject. Subjects such as check suites carry no
+/// API URL, so those fall back to the repository page that lists them.
+pub(super) fn subject_html_url(
+    api_base: &str,
+    web_base: &str,
+    repo: &str,
+    kind: &str,
+    api_url: Option<&str>,
+) -> String {
+    if let Some(url) = api_url {
+        return api_url_to_web(api_base, web_base, url);
+    }
+    let repo_url = format!("{web_base}/{repo}");
+    match kind {
+        "CheckSuite" | "WorkflowRun" => format!("{repo_url}/actions"),
+        "Discussion" => format!("{repo_url}/discussions"),
+        "Release" => format!("{repo_url}/releases"),
+        _ => repo_url,
+    }
+}
+
+/// Web page for a `/repos/...` API URL. The account's own API root is tried
+/// first, then api.github.com and any `<host>/api/v3` Enterprise Server, so
+/// links from another instance still open on their own host. Anything else
+/// is returned unchanged.
+pub(super) fn api_url_to_web(api_base: &str, web_base: &str, url: &str) -> String {
+    match split_api_url(api_base, web_base, url) {
+        Some((root, path)) => format!("{root}/{}", web_path(path)),
+        None => url.to_owned(),
+    }
+}
+
+/// The web root and the `owner/name/...` path of an API URL.
+fn split_api_url<'a>(api_base: &str, web_base: &str, url: &'a str) -> Option<(String, &'a str)> {
+    let repos = |root: &str| strip_prefix_ignore_case(url, &format!("{root}/repos/"));
+    if let Some(path) = repos(api_base.trim_end_matches('/')) {
+        return Some((web_base.trim_end_matches('/').to_owned(), path));
+    }
+    if let Some(path) = repos(GITHUB_API_BASE) {
+        return Some((GITHUB_WEB_BASE.to_owned(), path));
+    }
+    let marker = format!("{ENTERPRISE_API_PATH}/repos/");
+    let start = url.to_ascii_lowercase().find(&marker)?;
+    let root = &url[..start];
+    root.contains("://")
+        .then(|| (root.to_owned(), &url[start + marker.len()..]))
+}
+
+fn strip_prefix_ignore_case<'a>(text: &'a str, prefix: &str) -> Option<&'a str> {
+    let head = text.get(..prefix.len())?;
+    head.eq_ignore_ascii_case(prefix)
+        .then(|| &text[prefix.len()..])
+}
+
+/// `owner/name/<kind>/<id>` in the API's spelling to the web's.
+fn web_path(path: &str) -> String {
+    let trimmed = path.trim_end_matches('/');
+    let segments: Vec<&str> = trimmed.split('/').collect();
+    match segments.as_slice() {
+        [owner, name] => format!("{owner}/{name}"),
+        [owner, name, "pulls", number] => format!("{owner}/{name}/pull/{number}"),
+        [owner, name, kind @ ("issues" | "discussions"), number] => {
+            format!("{owner}/{name}/{kind}/{number}")
+        }
+        [owner, name, "commits", sha] => format!("{owner}/{name}/commit/{sha}"),
+        // Release ids are not tags; the real page is looked up afterwards.
+        [owner, name, "releases", _] => format!("{owner}/{name}/releases"),
+        [owner, name, "check-suites", _] => format!("{owner}/{name}/actions"),
+        [owner, name, "check-runs", id] => format!("{owner}/{name}/runs/{id}"),
+        _ => trimmed.replace("/pulls/", "/pull/"),
+    }
+}
+
+/// Works for github.com and Enterprise Server alike because both place the
+/// `owner/name` pair right after the first `/repos/` segment.
+pub(super) fn extract_repo_name(api_url: &str) -> String {
+    api_url
+        .split_once("/repos/")
+        .map_or(api_url, |(_, repo)| repo)
+        .to_owned()
+}
+
+pub(super) fn classify_thread(url: &str) -> MentionKind {
+    if url.contains("/pull/") {
+        MentionKind::PullRequest
+    } else {
+        MentionKind::Issue
+    }
+}
+
+pub(super) fn pull_request_number_from_html_url(url: &str) -> Option<u64> {
+    let (_, suffix) = url.split_once("/pull/")?;
+    suffix.split(['/', '?', '#']).next()?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        api_url_to_web, classify_thread, extract_repo_name, pull_request_number_from_html_url,
+        subject_html_url,
+    };
+    use crate::domain::MentionKind;
+
+    const GITHUB: (&str, &str) = ("https://api.github.com", "https://github.com");
+    const ENTERPRISE: (&str, &str) = (
+        "https://github.mycorp.com/api/v3",
+        "https://github.mycorp.com",
+    );
+    const PROXY: (&str, &str) = ("http://127.0.0.1:8080", "http://127.0.0.1:8080");
+
+    /// Account roots, API URL and the page it should open.
+    const SUBJECT_URLS: &[((&str, &str), &str, &str)] = &[
+        (
+            GITHUB,
+            "https://api.github.com/repos/acme/app/pulls/7",
+            "https://github.com/acme/app/pull/7",
+        ),
+        (
+            GITHUB,
+            "https://api.github.com/repos/acme/app/issues/3",
+            "https://github.com/acme/app/issues/3",
+        ),
+        (
+            GITHUB,
+            "https://api.github.com/repos/acme/app/commits/9f1c2ab",
+            "https://github.com/acme/app/commit/9f1c2ab",
+        ),
+        (
+            GITHUB,
+            "https://api.github.com/repos/acme/app/releases/123456",
+            "https://github.com/acme/app/releases",
+        ),
+        (
+            GITHUB,
+            "https://api.github.com/repos/acme/app/check-suites/42",
+            "https://github.com/acme/app/actions",
+        ),
+        (
+            GITHUB,
+            "https://api.github.com/repos/acme/app/check-runs/77",
+            "https://github.com/acme/app/runs/77",
+        ),
+        (
+            GITHUB,
+            "https://api.github.com/repos/acme/app/discussions/12",
+            "https://github.com/acme/app/discussions/12",
+        ),
+        (
+            GITHUB,
+            "https://api.github.com/repos/acme/app/",
+            "https://github.com/acme/app",
+        ),
+        (
+            GITHUB,
+            "https://api.github.com/repos/acme/app/pulls/7/comments",
+            "https://github.com/acme/app/pull/7/comments",
+        ),
+        (
+            GITHUB,
+            "https://API.GitHub.com/repos/acme/app/pulls/8",
+            "https://github.com/acme/app/pull/8",
+        ),
+        (
+            ENTERPRISE,
+            "https://github.mycorp.com/api/v3/repos/acme/app/issues/3",
+            "https://github.mycorp.com/acme/app/issues/3",
+        ),
+        (
+            ENTERPRISE,
+            "https://github.mycorp.com/api/v3/repos/acme/app/pulls/5",
+            "https://github.mycorp.com/acme/app/pull/5",
+        ),
+        // Another Enterprise host keeps its own hostname.
+        (
+            ENTERPRISE,
+            "https://ghe.other.example/api/v3/repos/ops/infra/pulls/2",
+            "https://ghe.other.example/ops/infra/pull/2",
+        ),
+        (
+            GITHUB,
+            "http://ghe.internal:8443/api/v3/repos/ops/infra/commits/abc",
+            "http://ghe.internal:8443/ops/infra/commit/abc",
+        ),
+        // A replay or proxy root that is neither shape.
+        (
+            PROXY,
+            "http://127.0.0.1:8080/repos/acme/app/pulls/1",
+            "http://127.0.0.1:8080/acme/app/pull/1",
+        ),
+        (
+            PROXY,
+            "https://api.github.com/repos/acme/app/issues/4",
+            "https://github.com/acme/app/issues/4",
+        ),
+        // Already a page, or not GitHub's API at all.
+        (
+            GITHUB,
+            "https://github.com/acme/app/pull/7",
+            "https://github.com/acme/app/pull/7",
+        ),
+        (
+            GITHUB,
+            "https://example.com/repos/acme/app/pulls/7",
+            "https://example.com/repos/acme/app/pulls/7",
+        ),
+    ];
+
+    #[test]
+    fn subject_api_urls_map_to_web_pages() {
+        for ((api_base, web_base), url, expected) in SUBJECT_URLS {
+            assert_eq!(api_url_to_web(api_base, web_base, url), *expected, "{url}");
+        }
+    }
+
+    #[test]
+    fn subjects_without_an_api_url_link_to_the_listing_page() {
+        let cases = [
+            ("CheckSuite", "https://github.mycorp.com/acme/app/actions"),
+            ("WorkflowRun", "https://github.mycorp.com/acme/app/actions"),
+            (
+                "Discussion",
+                "https://github.mycorp.com/acme/app/discussions",
+            ),
+            ("Release", "https://github.mycorp.com/acme/app/releases"),
+            ("RepositoryInvitation", "https://github.mycorp.com/acme/app"),
+        ];
+        let (api_base, web_base) = ENTERPRISE;
+        for (kind, expected) in cases {
+            assert_eq!(
+                subject_html_url(api_base, web_base, "acme/app", kind, None),
+                expected,
+                "{kind}"
+            );
+        }
+        assert_eq!(
+            subject_html_url(
+                api_base,
+                web_base,
+                "acme/app",
+                "PullRequest",
+                Some("https://github.mycorp.com/api/v3/repos/acme/app/pulls/7")
+            ),
+            "https://github.mycorp.com/acme/app/pull/7"
+        );
+    }
+
+    #[test]
+    fn repo_names_numbers_and_kinds_come_from_urls() {
+        assert_eq!(
+            extract_repo_name("https://api.github.com/repos/acme/widgets"),
+            "acme/widgets"
+        );
+        assert_eq!(
+            extract_repo_name("https://github.mycorp.com/api/v3/repos/acme/widgets"),
+            "acme/widgets"
+        );
+        assert!(matches!(
+            classify_thread("https://api.github.com/repos/acme/r/pull/1"),
+            MentionKind::PullRequest
+        ));
+        assert!(matches!(
+            classify_thread("https://api.github.com/repos/acme/r/issues/2"),
+            MentionKind::Issue
+        ));
+        for (url, expected) in [
+            ("https://github.com/acme/app/pull/7", Some(7)),
+            ("https://github.com/acme/app/pull/7/files", Some(7)),
+            ("https://github.com/acme/app/pull/7#discussion_r1", Some(7)),
+            ("https://github.com/acme/app/issues/7", None),
+        ] {
+            assert_eq!(pull_request_number_from_html_url(url), expected, "{url}");
+        }
+    }
+}
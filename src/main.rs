@@ -1,16 +1,197 @@
 mod app;
+mod deep_link;
 mod domain;
+mod forge;
 mod github;
+mod plugins;
+mod replay;
+mod settings;
 mod storage;
+mod sync;
+mod task;
 
-use app::{APP_NAME, ReminderApp};
+use std::{env, path::PathBuf, process};
+
+use app::{ReminderApp, window_title};
+use deep_link::DeepLink;
 use eframe::NativeOptions;
+use replay::{RecordedSession, ReplayServer};
+
+#[derive(Debug, Default, PartialEq, Eq)]
+struct LaunchArgs {
+    profile: Option<String>,
+    deep_link: Option<DeepLink>,
+    register_url_scheme: bool,
+    /// Directory holding a recorded session to serve instead of GitHub.
+    replay: Option<PathBuf>,
+}
 
 fn main() -> eframe::Result<()> {
-    let options = NativeOptions::default();
+    let LaunchArgs {
+        profile,
+        deep_link,
+        register_url_scheme,
+        replay,
+    } = match parse_launch_args(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("{err}");
+            eprintln!(
+                "Usage: reminder [--profile <name>] [--register-url-scheme] [--replay <dir>] [reminder://thread/<id>]"
+            );
+            process::exit(2);
+        }
+    };
+
+    if register_url_scheme {
+        match deep_link::register_url_scheme() {
+            Ok(()) => println!("Registered reminder:// links with this executable."),
+            Err(err) => {
+                eprintln!("Could not register reminder:// links: {err}");
+                process::exit(1);
+            }
+        }
+        return Ok(());
+    }
+
+    // A second launch for a link hands it to the running instance and exits.
+    if let Some(link) = &deep_link
+        && let Ok(dir) = storage::data_dir(profile.as_deref())
+        && deep_link::forward(&dir, link).is_ok()
+    {
+        return Ok(());
+    }
+
+    let replay = match replay
+        .map(|dir| RecordedSession::load(&dir).and_then(ReplayServer::start))
+        .transpose()
+    {
+        Ok(replay) => replay,
+        Err(err) => {
+            eprintln!("Could not start the replay: {err}");
+            process::exit(1);
+        }
+    };
+
+    let title = window_title(profile.as_deref(), 0);
+    let options = NativeOptions {
+        // A replay never touches the window state of real sessions.
+        persistence_path: storage::data_dir(profile.as_deref())
+            .ok()
+            .filter(|_| replay.is_none())
+            .map(|dir| dir.join(settings::UI_STATE_FILE)),
+        ..NativeOptions::default()
+    };
     eframe::run_native(
-        APP_NAME,
+        &title,
         options,
-        Box::new(|cc| Ok(Box::new(ReminderApp::new(cc)))),
+        Box::new(move |cc| Ok(Box::new(ReminderApp::new(cc, profile, deep_link, replay)))),
     )
 }
+
+fn parse_launch_args(mut args: impl Iterator<Item = String>) -> Result<LaunchArgs, String> {
+    let mut launch = LaunchArgs::default();
+    while let Some(arg) = args.next() {
+        if arg == "--register-url-scheme" {
+            launch.register_url_scheme = true;
+            continue;
+        }
+        if arg.starts_with(deep_link::SCHEME) {
+            launch.deep_link =
+                Some(DeepLink::parse(&arg).ok_or_else(|| format!("Unsupported link: {arg}"))?);
+            continue;
+        }
+        if arg == "--replay" {
+            let dir = args
+                .next()
+                .ok_or_else(|| "--replay requires a session directory".to_owned())?;
+            launch.replay = Some(PathBuf::from(dir));
+            continue;
+        }
+        if let Some(dir) = arg.strip_prefix("--replay=") {
+            launch.replay = Some(PathBuf::from(dir));
+            continue;
+        }
+        let value = if arg == "--profile" {
+            args.next()
+                .ok_or_else(|| "--profile requires a profile name".to_owned())?
+        } else if let Some(value) = arg.strip_prefix("--profile=") {
+            value.to_owned()
+        } else {
+            return Err(format!("Unknown argument: {arg}"));
+        };
+        storage::validate_profile_name(&value).map_err(|err| err.to_string())?;
+        launch.profile = Some(value);
+    }
+    Ok(launch)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::{DeepLink, LaunchArgs, parse_launch_args};
+
+    fn parse_profile_arg(args: impl Iterator<Item = String>) -> Result<Option<String>, String> {
+        parse_launch_args(args).map(|launch| launch.profile)
+    }
+
+    fn args(values: &[&str]) -> impl Iterator<Item = String> {
+        values
+            .iter()
+            .map(|value| (*value).to_owned())
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    #[test]
+    fn parse_profile_arg_defaults_to_no_profile() {
+        assert_eq!(parse_profile_arg(args(&[])), Ok(None));
+    }
+
+    #[test]
+    fn parse_profile_arg_accepts_separate_and_inline_values() {
+        assert_eq!(
+            parse_profile_arg(args(&["--profile", "work"])),
+            Ok(Some(String::from("work")))
+        );
+        assert_eq!(
+            parse_profile_arg(args(&["--profile=home"])),
+            Ok(Some(String::from("home")))
+        );
+    }
+
+    #[test]
+    fn parse_profile_arg_rejects_missing_or_invalid_names() {
+        assert!(parse_profile_arg(args(&["--profile"])).is_err());
+        assert!(parse_profile_arg(args(&["--profile", "../work"])).is_err());
+        assert!(parse_profile_arg(args(&["--verbose"])).is_err());
+    }
+
+    #[test]
+    fn parse_launch_args_accepts_deep_links() {
+        assert_eq!(
+            parse_launch_args(args(&["--profile", "work", "reminder://thread/7"])),
+            Ok(LaunchArgs {
+                profile: Some(String::from("work")),
+                deep_link: Some(DeepLink::Thread {
+                    thread_id: String::from("7"),
+                    login: None,
+                }),
+                register_url_scheme: false,
+                replay: None,
+            })
+        );
+        assert!(parse_launch_args(args(&["reminder://bogus"])).is_err());
+    }
+
+    #[test]
+    fn parse_launch_args_accepts_a_replay_directory() {
+        assert_eq!(
+            parse_launch_args(args(&["--replay", "sessions/highlight"]))
+                .map(|launch| launch.replay),
+            Ok(Some(PathBuf::from("sessions/highlight")))
+        );
+        assert!(parse_launch_args(args(&["--replay"])).is_err());
+    }
+}
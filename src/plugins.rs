@@ -0,0 +1,283 @@
+//! Third-party data sources compiled to WebAssembly. Each plugin is a pair
+//! of files in the plugins folder: `<name>.toml` says where to fetch from and
+//! `<name>.wasm` turns the fetched body into rows.
+//!
+//! The host does all networking. A module gets no imports at all, so it can
+//! only read the bytes it is handed and write rows back; fuel and a memory
+//! cap stop runaway plugins. The module must export:
+//!
+//! - `memory`
+//! - `alloc(len: i32) -> i32`, returning where the host may write `len` bytes
+//! - `map(ptr: i32, len: i32) -> i64`, returning `(ptr << 32) | len` of a
+//!   UTF-8 JSON array of [`PluginRow`]s
+
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use chrono::{DateTime, Utc};
+use reqwest::Client;
+use serde::Deserialize;
+use thiserror::Error;
+use wasmi::{Config, Engine, Linker, Module, Store, StoreLimits, StoreLimitsBuilder};
+
+/// Folder under the data directory that plugins are loaded from.
+pub const PLUGINS_DIR: &str = "plugins";
+
+/// Instructions a single `map` call may run before it is stopped.
+const PLUGIN_FUEL: u64 = 500_000_000;
+const PLUGIN_MEMORY_LIMIT: usize = 64 * 1024 * 1024;
+/// Fetched bodies above this are refused rather than handed to a plugin.
+const PLUGIN_INPUT_LIMIT: usize = 8 * 1024 * 1024;
+
+/// `<name>.toml` next to the module.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub struct PluginManifest {
+    /// Section heading; defaults to the file name.
+    #[serde(default)]
+    pub name: String,
+    /// Fetched with GET on every refresh and passed to `map` as is.
+    pub url: String,
+    /// Extra request headers, e.g. an `Authorization` token.
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+}
+
+/// A row a plugin returns; shaped like a notification so it reads the same.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub struct PluginRow {
+    pub id: String,
+    pub title: String,
+    #[serde(default)]
+    pub url: Option<String>,
+    /// Project, queue or board the row belongs to, shown where repos are.
+    #[serde(default)]
+    pub context: String,
+    pub updated_at: DateTime<Utc>,
+    #[serde(default)]
+    pub unread: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct Plugin {
+    pub manifest: PluginManifest,
+    pub wasm: Vec<u8>,
+}
+
+#[derive(Debug, Error)]
+pub enum PluginError {
+    #[error("unable to read {path}: {source}")]
+    Read { path: PathBuf, source: io::Error },
+    #[error("invalid plugin manifest {path}: {source}")]
+    Manifest {
+        path: PathBuf,
+        source: toml::de::Error,
+    },
+    #[error("plugin module is not valid WebAssembly: {0}")]
+    Module(wasmi::Error),
+    #[error("plugin module cannot run: {0}")]
+    Instantiate(wasmi::Error),
+    #[error("plugin does not export `{0}` with the expected signature")]
+    MissingExport(&'static str),
+    #[error("plugin failed while mapping rows: {0}")]
+    Trap(wasmi::Error),
+    #[error("plugin returned a range outside its memory")]
+    OutOfBounds,
+    #[error("plugin returned invalid rows: {0}")]
+    Rows(serde_json::Error),
+    #[error("fetched body is larger than {PLUGIN_INPUT_LIMIT} bytes")]
+    InputTooLarge,
+    #[error("unable to fetch {url}: {source}")]
+    Fetch { url: String, source: reqwest::Error },
+    #[error("plugin stopped unexpectedly")]
+    Interrupted,
+}
+
+/// Every `<name>.toml` with a matching `<name>.wasm` in `dir`, sorted by
+/// file name. A missing folder means no plugins; broken pairs are reported
+/// alongside the ones that loaded.
+pub fn load_plugins(dir: &Path) -> (Vec<Plugin>, Vec<PluginError>) {
+    let mut plugins = Vec::new();
+    let mut errors = Vec::new();
+    let Ok(entries) = fs::read_dir(dir) else {
+        return (plugins, errors);
+    };
+    let mut manifests: Vec<_> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+        .collect();
+    manifests.sort();
+    for path in manifests {
+        match load_plugin(&path) {
+            Ok(plugin) => plugins.push(plugin),
+            Err(err) => errors.push(err),
+        }
+    }
+    (plugins, errors)
+}
+
+fn load_plugin(manifest_path: &Path) -> Result<Plugin, PluginError> {
+    let read = |path: &Path| {
+        fs::read(path).map_err(|source| PluginError::Read {
+            path: path.to_owned(),
+            source,
+        })
+    };
+    let text = String::from_utf8_lossy(&read(manifest_path)?).into_owned();
+    let mut manifest: PluginManifest =
+        toml::from_str(&text).map_err(|source| PluginError::Manifest {
+            path: manifest_path.to_owned(),
+            source,
+        })?;
+    if manifest.name.trim().is_empty() {
+        manifest.name = manifest_path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+    }
+    let wasm = read(&manifest_path.with_extension("wasm"))?;
+    Ok(Plugin { manifest, wasm })
+}
+
+/// Fetches the plugin's URL and maps the body into rows on a blocking
+/// thread, since a plugin may run for as long as its fuel lasts. Bodies past
+/// [`PLUGIN_INPUT_LIMIT`] are dropped while streaming rather than buffered.
+pub async fn fetch_rows(
+    client: &Client,
+    plugin: Arc<Plugin>,
+) -> Result<Vec<PluginRow>, PluginError> {
+    let url = &plugin.manifest.url;
+    let fetch_error = |source| PluginError::Fetch {
+        url: url.clone(),
+        source,
+    };
+    let mut request = client.get(url);
+    for (name, value) in &plugin.manifest.headers {
+        request = request.header(name, value);
+    }
+    let mut response = request
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .map_err(fetch_error)?;
+    if response
+        .content_length()
+        .is_some_and(|len| len > PLUGIN_INPUT_LIMIT as u64)
+    {
+        return Err(PluginError::InputTooLarge);
+    }
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await.map_err(fetch_error)? {
+        if body.len() + chunk.len() > PLUGIN_INPUT_LIMIT {
+            return Err(PluginError::InputTooLarge);
+        }
+        body.extend_from_slice(&chunk);
+    }
+    tokio::task::spawn_blocking(move || map_rows(&plugin.wasm, &body))
+        .await
+        .map_err(|_| PluginError::Interrupted)?
+}
+
+/// Runs the module's `map` export over `input` in a fresh, import-free
+/// instance.
+pub fn map_rows(wasm: &[u8], input: &[u8]) -> Result<Vec<PluginRow>, PluginError> {
+    if input.len() > PLUGIN_INPUT_LIMIT {
+        return Err(PluginError::InputTooLarge);
+    }
+    let mut config = Config::default();
+    config.consume_fuel(true);
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, wasm).map_err(PluginError::Module)?;
+    let limits = StoreLimitsBuilder::new()
+        .memory_size(PLUGIN_MEMORY_LIMIT)
+        .instances(1)
+        .build();
+    let mut store: Store<StoreLimits> = Store::new(&engine, limits);
+    store.limiter(|limits| limits);
+    store
+        .set_fuel(PLUGIN_FUEL)
+        .map_err(|err| PluginError::Instantiate(err.into()))?;
+    let instance = Linker::new(&engine)
+        .instantiate(&mut store, &module)
+        .and_then(|pre| pre.start(&mut store))
+        .map_err(PluginError::Instantiate)?;
+
+    let memory = instance
+        .get_memory(&store, "memory")
+        .ok_or(PluginError::MissingExport("memory"))?;
+    let alloc = instance
+        .get_typed_func::<i32, i32>(&store, "alloc")
+        .map_err(|_| PluginError::MissingExport("alloc"))?;
+    let map = instance
+        .get_typed_func::<(i32, i32), i64>(&store, "map")
+        .map_err(|_| PluginError::MissingExport("map"))?;
+
+    let len = i32::try_from(input.len()).map_err(|_| PluginError::InputTooLarge)?;
+    let ptr = alloc.call(&mut store, len).map_err(PluginError::Trap)?;
+    memory
+        .write(&mut store, ptr as u32 as usize, input)
+        .map_err(|_| PluginError::OutOfBounds)?;
+    let packed = map
+        .call(&mut store, (ptr, len))
+        .map_err(PluginError::Trap)? as u64;
+    let (out_ptr, out_len) = ((packed >> 32) as usize, (packed & 0xffff_ffff) as usize);
+    let output = memory
+        .data(&store)
+        .get(out_ptr..out_ptr.saturating_add(out_len))
+        .ok_or(PluginError::OutOfBounds)?;
+    serde_json::from_slice(output).map_err(PluginError::Rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PluginError, map_rows};
+
+    /// Ignores its input and returns two rows from a data segment.
+    const FIXED_ROWS: &str = r#"(module
+        (memory (export "memory") 1)
+        (data (i32.const 0) "[{\"id\":\"T-1\",\"title\":\"Fix login\",\"context\":\"OPS\",\"updated_at\":\"2026-01-02T03:04:05Z\",\"unread\":true},{\"id\":\"T-2\",\"title\":\"Old\",\"updated_at\":\"2026-01-01T00:00:00Z\"}]")
+        (func (export "alloc") (param i32) (result i32) (i32.const 4096))
+        (func (export "map") (param i32 i32) (result i64)
+            (i64.const 163)))"#;
+
+    #[test]
+    fn map_rows_reads_json_rows_from_the_module() {
+        let wasm = wat::parse_str(FIXED_ROWS).expect("valid module");
+
+        let rows = map_rows(&wasm, b"{}").expect("rows");
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].id, "T-1");
+        assert_eq!(rows[0].context, "OPS");
+        assert!(rows[0].unread && !rows[1].unread);
+    }
+
+    #[test]
+    fn plugins_cannot_import_or_loop_forever() {
+        let importing = wat::parse_str(
+            r#"(module (import "env" "http_get" (func)) (memory (export "memory") 1))"#,
+        )
+        .expect("valid module");
+        assert!(matches!(
+            map_rows(&importing, b""),
+            Err(PluginError::Instantiate(_))
+        ));
+
+        let spinning = wat::parse_str(
+            r#"(module
+                (memory (export "memory") 1)
+                (func (export "alloc") (param i32) (result i32) (i32.const 0))
+                (func (export "map") (param i32 i32) (result i64)
+                    (loop $spin (br $spin))
+                    (i64.const 0)))"#,
+        )
+        .expect("valid module");
+        assert!(matches!(
+            map_rows(&spinning, b""),
+            Err(PluginError::Trap(_))
+        ));
+    }
+}
@@ -0,0 +1,311 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{Ipv4Addr, TcpListener, TcpStream},
+    path::Path,
+    sync::Arc,
+    thread,
+    time::{Duration, Instant},
+};
+
+use reqwest::StatusCode;
+use serde::Deserialize;
+
+use crate::domain::GITHUB_API_BASE;
+
+pub const SESSION_FILE: &str = "session.json";
+const DEFAULT_SPEED: f64 = 10.0;
+
+/// API traffic captured from a real session, served back by [`ReplayServer`].
+#[derive(Debug, Deserialize)]
+pub struct RecordedSession {
+    /// Login of the account the traffic was captured for.
+    pub login: String,
+    /// How many recorded milliseconds pass per wall-clock millisecond.
+    #[serde(default = "default_speed")]
+    pub speed: f64,
+    #[serde(default)]
+    pub exchanges: Vec<RecordedExchange>,
+}
+
+/// One response, available to matching requests from `at_ms` into the
+/// recording until a later exchange for the same request replaces it.
+#[derive(Debug, Deserialize)]
+pub struct RecordedExchange {
+    #[serde(default)]
+    pub at_ms: u64,
+    #[serde(default = "default_method")]
+    pub method: String,
+    /// Path and query below the API root, for example `/notifications?all=true`.
+    pub path: String,
+    #[serde(default = "default_status")]
+    pub status: u16,
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    #[serde(default)]
+    pub body: serde_json::Value,
+}
+
+fn default_speed() -> f64 {
+    DEFAULT_SPEED
+}
+
+fn default_method() -> String {
+    String::from("GET")
+}
+
+fn default_status() -> u16 {
+    200
+}
+
+impl RecordedSession {
+    pub fn load(dir: &Path) -> io::Result<Self> {
+        let raw = fs::read_to_string(dir.join(SESSION_FILE))?;
+        let session: Self = serde_json::from_str(&raw)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        if !(session.speed.is_finite() && session.speed > 0.0) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "replay speed must be a positive number",
+            ));
+        }
+        Ok(session)
+    }
+
+    /// The latest exchange recorded at or before `elapsed_ms`, falling back
+    /// to the first one so requests made before it still get an answer. An
+    /// exact path-and-query match wins over a path-only match, since queries
+    /// such as `since=` differ between runs.
+    fn response_for(
+        &self,
+        method: &str,
+        target: &str,
+        elapsed_ms: u64,
+    ) -> Option<&RecordedExchange> {
+        self.latest_matching(method, elapsed_ms, |path| path == target)
+            .or_else(|| {
+                self.latest_matching(method, elapsed_ms, |path| {
+                    path_without_query(path) == path_without_query(target)
+                })
+            })
+    }
+
+    fn latest_matching(
+        &self,
+        method: &str,
+        elapsed_ms: u64,
+        matches: impl Fn(&str) -> bool,
+    ) -> Option<&RecordedExchange> {
+        let candidates: Vec<_> = self
+            .exchanges
+            .iter()
+            .filter(|exchange| {
+                exchange.method.eq_ignore_ascii_case(method) && matches(&exchange.path)
+            })
+            .collect();
+        candidates
+            .iter()
+            .filter(|exchange| exchange.at_ms <= elapsed_ms)
+            .max_by_key(|exchange| exchange.at_ms)
+            .or(candidates.first())
+            .copied()
+    }
+}
+
+fn path_without_query(target: &str) -> &str {
+    target.split_once('?').map_or(target, |(path, _)| path)
+}
+
+/// Serves a [`RecordedSession`] on a loopback port that stands in for the
+/// GitHub API, so the regular fetch jobs run against recorded responses.
+pub struct ReplayServer {
+    base_url: String,
+    login: String,
+    speed: f64,
+}
+
+impl ReplayServer {
+    pub fn start(session: RecordedSession) -> io::Result<Self> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))?;
+        let base_url = format!("http://{}", listener.local_addr()?);
+        let login = session.login.clone();
+        let speed = session.speed;
+        let session = Arc::new(session);
+        let started = Instant::now();
+        let served_base = base_url.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let session = Arc::clone(&session);
+                let base_url = served_base.clone();
+                thread::spawn(move || {
+                    let elapsed_ms = (started.elapsed().as_millis() as f64 * session.speed) as u64;
+                    let _ = serve(stream, &session, &base_url, elapsed_ms);
+                });
+            }
+        });
+        Ok(Self {
+            base_url,
+            login,
+            speed,
+        })
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    pub fn login(&self) -> &str {
+        &self.login
+    }
+
+    /// Shortens a wall-clock interval so polling keeps pace with the replay.
+    pub fn accelerate(&self, interval: Duration) -> Duration {
+        interval.div_f64(self.speed)
+    }
+}
+
+fn serve(
+    stream: TcpStream,
+    session: &RecordedSession,
+    base_url: &str,
+    elapsed_ms: u64,
+) -> io::Result<()> {
+    let mut reader = BufReader::new(stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return Ok(());
+    };
+
+    // Drain headers and any body so the client sees a clean response.
+    let mut content_length = 0usize;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':')
+            && name.trim().eq_ignore_ascii_case("content-length")
+        {
+            content_length = value.trim().parse().unwrap_or(0);
+        }
+    }
+    io::copy(
+        &mut reader.by_ref().take(content_length as u64),
+        &mut io::sink(),
+    )?;
+
+    let (status, headers, body) = match session.response_for(method, target, elapsed_ms) {
+        Some(exchange) => (
+            exchange.status,
+            exchange.headers.iter().collect::<Vec<_>>(),
+            match &exchange.body {
+                serde_json::Value::Null => String::new(),
+                body => body.to_string(),
+            },
+        ),
+        None => (
+            404,
+            Vec::new(),
+            format!(r#"{{"message":"No recorded response for {method} {target}"}}"#),
+        ),
+    };
+    let reason = StatusCode::from_u16(status)
+        .ok()
+        .and_then(|status| status.canonical_reason())
+        .unwrap_or("");
+    // Recorded URLs point at github.com; keep follow-up requests local.
+    let body = body.replace(GITHUB_API_BASE, base_url);
+    let mut response = format!(
+        "HTTP/1.1 {status} {reason}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n",
+        body.len()
+    );
+    for (name, value) in headers {
+        response.push_str(&format!(
+            "{name}: {}\r\n",
+            value.replace(GITHUB_API_BASE, base_url)
+        ));
+    }
+    response.push_str("\r\n");
+    response.push_str(&body);
+
+    let mut stream = reader.into_inner();
+    stream.write_all(response.as_bytes())?;
+    stream.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RecordedSession, ReplayServer};
+
+    fn session() -> RecordedSession {
+        serde_json::from_str(
+            r#"{
+                "login": "neo",
+                "exchanges": [
+                    {"at_ms": 0, "path": "/notifications?all=true", "body": [1]},
+                    {"at_ms": 5000, "path": "/notifications?all=true", "body": [1, 2]},
+                    {"at_ms": 0, "method": "PATCH", "path": "/notifications/threads/1", "status": 205}
+                ]
+            }"#,
+        )
+        .expect("session")
+    }
+
+    #[test]
+    fn response_for_follows_the_recording_clock() {
+        let session = session();
+
+        let early = session
+            .response_for("GET", "/notifications?all=true", 1000)
+            .expect("early response");
+        let late = session
+            .response_for("GET", "/notifications?all=true", 9000)
+            .expect("late response");
+
+        assert_eq!(early.body, serde_json::json!([1]));
+        assert_eq!(late.body, serde_json::json!([1, 2]));
+    }
+
+    #[test]
+    fn response_for_falls_back_to_the_path_and_matches_methods() {
+        let session = session();
+
+        let by_path = session
+            .response_for("GET", "/notifications?all=true&per_page=50", 0)
+            .expect("path match");
+        assert_eq!(by_path.body, serde_json::json!([1]));
+        assert_eq!(
+            session
+                .response_for("patch", "/notifications/threads/1", 0)
+                .map(|exchange| exchange.status),
+            Some(205)
+        );
+        assert!(
+            session
+                .response_for("DELETE", "/notifications/threads/1", 0)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn server_answers_with_recorded_responses() {
+        let server = ReplayServer::start(session()).expect("server");
+        let client = reqwest::blocking::Client::new();
+
+        let found = client
+            .get(format!("{}/notifications?all=true", server.base_url()))
+            .send()
+            .expect("recorded response");
+        let missing = client
+            .get(format!("{}/user", server.base_url()))
+            .send()
+            .expect("fallback response");
+
+        assert_eq!(found.status(), 200);
+        assert_eq!(found.text().expect("body"), "[1]");
+        assert_eq!(missing.status(), 404);
+    }
+}
@@ -0,0 +1,121 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use eframe::egui::ThemePreference;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::{NotificationChips, SearchMode};
+
+/// eframe keeps the window geometry, egui memory (open/closed sections) and
+/// [`UiState`] in this file next to `accounts.json`.
+pub const UI_STATE_FILE: &str = "ui_state.ron";
+const UI_STATE_KEY: &str = "reminder_ui_state";
+
+/// Dashboard layout restored on the next launch.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UiState {
+    pub theme: ThemePreference,
+    pub selected_account: Option<String>,
+    pub show_all_accounts: bool,
+    pub combined_inbox: bool,
+    /// Filter chips of the combined inbox.
+    pub combined_chips: NotificationChips,
+    pub accounts: BTreeMap<String, AccountUiState>,
+    pub repo_search_queries: BTreeMap<String, String>,
+    pub repo_search_modes: BTreeMap<String, SearchMode>,
+    /// App version whose "What's new" panel was last dismissed.
+    pub last_seen_version: Option<String>,
+    /// Quick mute of desktop alerts and the title badge.
+    pub do_not_disturb: bool,
+}
+
+impl Default for UiState {
+    fn default() -> Self {
+        Self {
+            theme: ThemePreference::System,
+            selected_account: None,
+            show_all_accounts: true,
+            combined_inbox: false,
+            combined_chips: NotificationChips::default(),
+            accounts: BTreeMap::new(),
+            repo_search_queries: BTreeMap::new(),
+            repo_search_modes: BTreeMap::new(),
+            last_seen_version: None,
+            do_not_disturb: false,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AccountUiState {
+    pub expanded: bool,
+    pub grouped_view: bool,
+    /// Show the personal stage board; wins over `grouped_view`.
+    pub board_view: bool,
+    pub search_query: String,
+    pub search_mode: SearchMode,
+    pub chips: NotificationChips,
+    /// Split each section into per-repository groups.
+    pub group_by_repo: bool,
+    /// Split each section under "Today", "Yesterday" and "Earlier".
+    pub group_by_day: bool,
+    pub collapsed_repos: BTreeSet<String>,
+}
+
+impl Default for AccountUiState {
+    fn default() -> Self {
+        Self {
+            expanded: true,
+            grouped_view: false,
+            board_view: false,
+            search_query: String::new(),
+            search_mode: SearchMode::Substring,
+            chips: NotificationChips::default(),
+            group_by_repo: false,
+            group_by_day: false,
+            collapsed_repos: BTreeSet::new(),
+        }
+    }
+}
+
+impl UiState {
+    pub fn load(storage: Option<&dyn eframe::Storage>) -> Self {
+        storage
+            .and_then(|storage| eframe::get_value(storage, UI_STATE_KEY))
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, storage: &mut dyn eframe::Storage) {
+        eframe::set_value(storage, UI_STATE_KEY, self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AccountUiState, UiState};
+    use crate::domain::{NotificationChips, SearchMode};
+
+    #[test]
+    fn missing_fields_fall_back_to_defaults() {
+        let state: UiState =
+            serde_json::from_str(r#"{"accounts": {"neo": {"search_query": "infra"}}}"#)
+                .expect("ui state");
+
+        assert!(state.show_all_accounts);
+        assert_eq!(
+            state.accounts["neo"],
+            AccountUiState {
+                expanded: true,
+                grouped_view: false,
+                board_view: false,
+                search_query: String::from("infra"),
+                search_mode: SearchMode::Substring,
+                chips: NotificationChips::default(),
+                group_by_repo: false,
+                group_by_day: false,
+                collapsed_repos: Default::default(),
+            }
+        );
+    }
+}
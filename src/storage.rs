@@ -1,12 +1,39 @@
-use std::{collections::BTreeMap, env, fs, io, path::PathBuf};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    env, fs, io,
+    path::{Path, PathBuf},
+};
 
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use crate::domain::{GitHubAccount, ReviewCommandSettings};
+use crate::{
+    domain::{
+        ActivityHistory, AppSettings, DoneThreads, EnrichmentCache, Forge, GitHubAccount,
+        InboxBackend, InboxSnapshot, NotificationArchive, ReminderList, ReviewCommandSettings,
+        SectionAlertThresholds, ThreadStages, WorkingHours,
+    },
+    sync::SyncDocument,
+};
+
+mod backup;
+mod provisioning;
+mod secrets;
+
+pub use backup::Backup;
+use secrets::{EncryptedBackend, KeychainBackend, SecretBackend};
+pub use secrets::{EncryptedToken, VaultHeader, run_token_command};
 
 const STORAGE_DIR_NAME: &str = ".reminder";
+const PROFILES_DIR_NAME: &str = "profiles";
 const REGISTRY_FILE: &str = "accounts.json";
+const HISTORY_FILE: &str = "history.json";
+const REMINDERS_FILE: &str = "reminders.json";
+const SYNC_STATE_FILE: &str = "sync.json";
+const INBOX_CACHE_DIR: &str = "inbox-cache";
+const DONE_THREADS_DIR: &str = "done";
+const ARCHIVE_DIR: &str = "archive";
+const KEYRING_SERVICE: &str = "reminder";
 
 #[derive(Default, Serialize, Deserialize, Clone)]
 pub struct StoredAccounts {
@@ -16,37 +43,106 @@ pub struct StoredAccounts {
     pub repo_paths: BTreeMap<String, String>,
     #[serde(default)]
     pub repo_path_accounts: BTreeMap<String, String>,
+    #[serde(default)]
+    pub settings: AppSettings,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vault: Option<VaultHeader>,
+}
+
+/// The parts of the registry a backup keeps. Accounts, tokens and the vault
+/// stay with the live registry, so a restore can't undo a passphrase change
+/// or bring back a token. Parses whole registries too, which older backups
+/// copied.
+#[derive(Default, Serialize, Deserialize)]
+struct BackedUpSettings {
+    #[serde(default)]
+    repo_paths: BTreeMap<String, String>,
+    #[serde(default)]
+    repo_path_accounts: BTreeMap<String, String>,
+    #[serde(default)]
+    settings: AppSettings,
 }
 
 #[derive(Clone, Serialize, Deserialize)]
 pub struct StoredAccount {
     pub login: String,
+    /// Only populated by registries written before tokens moved to the
+    /// keychain, until they can be moved.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub token: String,
+    /// Set instead of `token` once the registry is protected by a passphrase.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encrypted_token: Option<EncryptedToken>,
     #[serde(default)]
     pub review_settings: ReviewCommandSettings,
+    #[serde(default)]
+    pub hide_bot_notifications: Option<bool>,
+    #[serde(default)]
+    pub api_base_url: Option<String>,
+    #[serde(default)]
+    pub working_hours: Option<WorkingHours>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_command: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub section_alerts: Option<SectionAlertThresholds>,
+    #[serde(default)]
+    pub inbox_backend: InboxBackend,
+    #[serde(default)]
+    pub forge: Forge,
+}
+
+impl StoredAccount {
+    /// See [`GitHubAccount::storage_key`].
+    pub(super) fn key(&self) -> String {
+        self.forge
+            .account_key(self.api_base_url.as_deref(), &self.login)
+    }
 }
 
 impl StoredAccounts {
-    fn upsert(&mut self, profile: &GitHubAccount) {
-        if let Some(existing) = self
-            .accounts
-            .iter_mut()
-            .find(|entry| entry.login == profile.login)
-        {
-            existing.token = profile.token.clone();
+    /// Updates everything but the token, which the secret backend records.
+    fn upsert(&mut self, profile: &GitHubAccount) -> &mut StoredAccount {
+        let key = profile.storage_key();
+        let index = if let Some(index) = self.accounts.iter().position(|entry| entry.key() == key) {
+            let existing = &mut self.accounts[index];
             existing.review_settings = profile.review_settings.clone();
+            existing.hide_bot_notifications = profile.hide_bot_notifications;
+            existing.api_base_url = profile.api_base_url.clone();
+            existing.working_hours = profile.working_hours;
+            existing.token_command = profile.token_command.clone();
+            existing.group = profile.group.clone();
+            existing.section_alerts = profile.section_alerts;
+            existing.inbox_backend = profile.inbox_backend;
+            existing.forge = profile.forge;
+            index
         } else {
             self.accounts.push(StoredAccount {
                 login: profile.login.clone(),
-                token: profile.token.clone(),
+                token: String::new(),
+                encrypted_token: None,
                 review_settings: profile.review_settings.clone(),
+                hide_bot_notifications: profile.hide_bot_notifications,
+                api_base_url: profile.api_base_url.clone(),
+                working_hours: profile.working_hours,
+                token_command: profile.token_command.clone(),
+                group: profile.group.clone(),
+                section_alerts: profile.section_alerts,
+                inbox_backend: profile.inbox_backend,
+                forge: profile.forge,
             });
             self.accounts.sort_by(|a, b| a.login.cmp(&b.login));
-        }
+            self.accounts
+                .iter()
+                .position(|entry| entry.key() == key)
+                .expect("account was just inserted")
+        };
+        &mut self.accounts[index]
     }
 
-    fn remove(&mut self, login: &str) {
-        self.accounts.retain(|entry| entry.login != login);
+    fn remove(&mut self, key: &str) {
+        self.accounts.retain(|entry| entry.key() != key);
     }
 
     fn upsert_repo_path(&mut self, repo: &str, path: &str) {
@@ -69,57 +165,440 @@ impl StoredAccounts {
 }
 
 pub struct AccountStore {
+    dir: PathBuf,
     registry_path: PathBuf,
+    history_path: PathBuf,
+    reminders_path: PathBuf,
+    inbox_cache_dir: PathBuf,
+    done_threads_dir: PathBuf,
+    archive_dir: PathBuf,
+    backend: Box<dyn SecretBackend>,
+    /// True while the registry holds a vault that has not been unlocked yet.
+    locked: bool,
+    encrypted: bool,
+    provisioning_path: PathBuf,
+    /// Keys of the accounts declared in `accounts.toml`, which the app must
+    /// not rewrite.
+    provisioned_keys: BTreeSet<String>,
 }
 
 pub struct HydrationOutcome {
     pub profiles: Vec<GitHubAccount>,
     pub repo_paths: BTreeMap<String, String>,
     pub repo_path_accounts: BTreeMap<String, String>,
+    pub settings: AppSettings,
+    /// Set when a token could not be read from or moved into the secret
+    /// backend.
+    pub secret_warning: Option<String>,
+    /// Set when `accounts.toml` is invalid or names an unset token variable.
+    pub provisioning_warning: Option<String>,
 }
 
 impl AccountStore {
-    pub fn initialize() -> Result<Self, SecretStoreError> {
-        let home = env::var("HOME").map_err(|_| SecretStoreError::HomeDirMissing)?;
-        let dir = PathBuf::from(home).join(STORAGE_DIR_NAME);
+    pub fn initialize(profile: Option<&str>) -> Result<Self, SecretStoreError> {
+        let dir = data_dir(profile)?;
         if !dir.exists() {
             fs::create_dir_all(&dir)?;
         }
-        Ok(Self {
+        let mut store = Self {
+            dir: dir.clone(),
             registry_path: dir.join(REGISTRY_FILE),
-        })
+            history_path: dir.join(HISTORY_FILE),
+            reminders_path: dir.join(REMINDERS_FILE),
+            inbox_cache_dir: dir.join(INBOX_CACHE_DIR),
+            done_threads_dir: dir.join(DONE_THREADS_DIR),
+            archive_dir: dir.join(ARCHIVE_DIR),
+            backend: Box::new(KeychainBackend {
+                service: keyring_service(profile),
+            }),
+            locked: false,
+            encrypted: false,
+            provisioning_path: dir.join(provisioning::PROVISIONING_FILE),
+            provisioned_keys: BTreeSet::new(),
+        };
+        store.locked = store.read_registry()?.vault.is_some();
+        store.encrypted = store.locked;
+        Ok(store)
     }
 
-    pub fn hydrate(&self) -> Result<HydrationOutcome, SecretStoreError> {
-        let registry = self.read_registry()?;
-        let profiles = registry
-            .accounts
-            .into_iter()
-            .map(|entry| GitHubAccount {
-                login: entry.login,
-                token: entry.token,
-                review_settings: entry.review_settings,
-            })
+    /// True until [`Self::unlock`] succeeds for a passphrase-protected
+    /// registry; accounts cannot be loaded or saved in the meantime.
+    pub fn is_locked(&self) -> bool {
+        self.locked
+    }
+
+    pub fn is_encrypted(&self) -> bool {
+        self.encrypted
+    }
+
+    pub fn unlock(&mut self, passphrase: &str) -> Result<(), SecretStoreError> {
+        let Some(vault) = self.read_registry()?.vault else {
+            self.locked = false;
+            self.encrypted = false;
+            return Ok(());
+        };
+        self.backend = Box::new(EncryptedBackend::unlock(passphrase, &vault)?);
+        self.locked = false;
+        Ok(())
+    }
+
+    /// Re-encrypts every token under a new passphrase, moving them out of the
+    /// keychain when that was the previous backend.
+    pub fn enable_encryption(
+        &mut self,
+        passphrase: &str,
+        profiles: &[GitHubAccount],
+    ) -> Result<(), SecretStoreError> {
+        self.ensure_unlocked()?;
+        let (backend, vault) = EncryptedBackend::create(passphrase)?;
+        let mut registry = self.read_registry()?;
+        let profiles: Vec<_> = profiles
+            .iter()
+            .filter(|profile| !self.provisioned_keys.contains(&profile.storage_key()))
+            .collect();
+        for profile in profiles
+            .iter()
+            .filter(|profile| profile.token_command.is_none())
+        {
+            let entry = registry.upsert(profile);
+            backend.store(entry, &profile.token)?;
+        }
+        registry.vault = Some(vault);
+        self.write_registry(&registry)?;
+        for profile in profiles {
+            let _ = self.backend.delete(&profile.storage_key());
+        }
+        self.backend = Box::new(backend);
+        self.encrypted = true;
+        Ok(())
+    }
+
+    /// Loads accounts and their tokens. Plaintext tokens left by older
+    /// versions are moved into the secret backend and dropped from the file.
+    /// Accounts declared in `accounts.toml` replace saved ones of the same
+    /// login on the same instance.
+    pub fn hydrate(&mut self) -> Result<HydrationOutcome, SecretStoreError> {
+        self.ensure_unlocked()?;
+        let mut provisioning_warning = None;
+        let provisioned = provisioning::load(&self.provisioning_path, |name| env::var(name).ok())
+            .unwrap_or_else(|err| {
+                provisioning_warning = Some(format!(
+                    "Ignoring {}: {err}",
+                    self.provisioning_path.display()
+                ));
+                Default::default()
+            });
+        self.provisioned_keys = provisioned
+            .profiles
+            .iter()
+            .map(GitHubAccount::storage_key)
+            .chain(
+                provisioned
+                    .missing_tokens
+                    .iter()
+                    .map(|missing| missing.key.clone()),
+            )
             .collect();
+        if let Some(missing) = provisioned.missing_tokens.first() {
+            provisioning_warning = Some(format!(
+                "{} from accounts.toml was skipped because ${} is not set.",
+                missing.login, missing.variable
+            ));
+        }
+
+        let mut registry = self.read_registry()?;
+        let mut profiles = Vec::with_capacity(registry.accounts.len());
+        let mut entry_indices = Vec::with_capacity(registry.accounts.len());
+        let mut secret_warning = None;
+        let mut migrated = false;
+        let keys: BTreeSet<_> = registry.accounts.iter().map(StoredAccount::key).collect();
+        for (index, entry) in registry.accounts.iter_mut().enumerate() {
+            let key = entry.key();
+            if self.provisioned_keys.contains(&key) {
+                continue;
+            }
+            // Accounts off github.com were once saved under their login
+            // alone, unless an account on github.com owns that name now.
+            if key != entry.login
+                && !keys.contains(&entry.login)
+                && let Err(err) = self.adopt_login_data(entry)
+            {
+                secret_warning = Some(format!(
+                    "Could not move the saved data of {} to its new name: {err}",
+                    entry.login
+                ));
+            }
+            let token = if let Some(command) = &entry.token_command {
+                run_token_command(command).unwrap_or_else(|err| {
+                    secret_warning = Some(format!(
+                        "Could not get the token for {} from its command: {err}",
+                        entry.login
+                    ));
+                    String::new()
+                })
+            } else if entry.token.is_empty() {
+                match self.backend.load(entry) {
+                    Ok(token) => token.unwrap_or_default(),
+                    Err(err) => {
+                        secret_warning = Some(format!(
+                            "Could not read the token for {} from {}: {err}",
+                            entry.login,
+                            self.backend.describe()
+                        ));
+                        String::new()
+                    }
+                }
+            } else {
+                let token = entry.token.clone();
+                match self.backend.store(entry, &token) {
+                    Ok(()) => migrated = true,
+                    Err(err) => {
+                        secret_warning = Some(format!(
+                            "Tokens could not be moved into {}, so they stay in plaintext in {} ({err}).",
+                            self.backend.describe(),
+                            self.registry_path.display()
+                        ));
+                    }
+                }
+                token
+            };
+            profiles.push(GitHubAccount {
+                login: entry.login.clone(),
+                token,
+                review_settings: entry.review_settings.clone(),
+                hide_bot_notifications: entry.hide_bot_notifications,
+                api_base_url: entry.api_base_url.clone(),
+                working_hours: entry.working_hours,
+                token_command: entry.token_command.clone(),
+                group: entry.group.clone(),
+                section_alerts: entry.section_alerts,
+                inbox_backend: entry.inbox_backend,
+                forge: entry.forge,
+            });
+            entry_indices.push(index);
+        }
+        let duplicates = duplicate_profile_indices(&profiles);
+        for &index in duplicates.iter().rev() {
+            let dropped = profiles.remove(index);
+            registry.accounts.remove(entry_indices[index]);
+            // Logins differing only in case keep their own keychain entry.
+            let key = dropped.storage_key();
+            if !profiles.iter().any(|profile| profile.storage_key() == key) {
+                let _ = self.backend.delete(&key);
+            }
+        }
+        if migrated || !duplicates.is_empty() {
+            self.write_registry(&registry)?;
+        }
+        profiles.extend(provisioned.profiles);
+        profiles.sort_by(|a, b| a.login.cmp(&b.login));
 
         Ok(HydrationOutcome {
             profiles,
             repo_paths: registry.repo_paths,
             repo_path_accounts: registry.repo_path_accounts,
+            settings: registry.settings,
+            secret_warning,
+            provisioning_warning,
         })
     }
 
+    pub fn persist_settings(&self, settings: &AppSettings) -> Result<(), SecretStoreError> {
+        // A locked app only knows default settings; don't overwrite the real ones.
+        self.ensure_unlocked()?;
+        let mut registry = self.read_registry()?;
+        registry.settings = settings.clone();
+        self.write_registry(&registry)?;
+        Ok(())
+    }
+
+    /// Saves the account, handing its token to the secret backend. When the
+    /// backend fails nothing is saved; the token never goes to the registry
+    /// file in plaintext.
     pub fn persist_profile(&self, profile: &GitHubAccount) -> Result<(), SecretStoreError> {
+        self.ensure_unlocked()?;
+        self.ensure_not_provisioned(profile)?;
         let mut registry = self.read_registry()?;
-        registry.upsert(profile);
+        let entry = registry.upsert(profile);
+        if profile.token_command.is_some() {
+            // The command stays the source of truth; drop any saved copy.
+            entry.token.clear();
+            entry.encrypted_token = None;
+            self.backend.delete(&profile.storage_key())?;
+        } else if let Err(err) = self.backend.store(entry, &profile.token) {
+            return Err(SecretStoreError::TokenNotSaved {
+                backend: self.backend.describe(),
+                reason: err.to_string(),
+            });
+        }
         self.write_registry(&registry)?;
         Ok(())
     }
 
-    pub fn forget(&self, login: &str) -> Result<(), SecretStoreError> {
+    pub fn forget(&self, profile: &GitHubAccount) -> Result<(), SecretStoreError> {
+        self.ensure_not_provisioned(profile)?;
+        let key = profile.storage_key();
         let mut registry = self.read_registry()?;
-        registry.remove(login);
+        registry.remove(&key);
         self.write_registry(&registry)?;
+        self.backend.delete(&key)?;
+        for path in self.account_paths(&key) {
+            match fs::remove_file(path) {
+                Ok(()) => {}
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+                Err(err) => return Err(err.into()),
+            }
+        }
+        Ok(())
+    }
+
+    /// Last inbox fetched for the account with this
+    /// [`GitHubAccount::storage_key`], shown until the first refresh of a
+    /// session succeeds.
+    pub fn load_inbox_cache(&self, key: &str) -> Result<Option<InboxSnapshot>, SecretStoreError> {
+        match fs::read_to_string(self.inbox_cache_path(key)) {
+            Ok(contents) => Ok(Some(serde_json::from_str(&contents)?)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    pub fn persist_inbox_cache(
+        &self,
+        key: &str,
+        inbox: &InboxSnapshot,
+    ) -> Result<(), SecretStoreError> {
+        fs::create_dir_all(&self.inbox_cache_dir)?;
+        fs::write(self.inbox_cache_path(key), serde_json::to_string(inbox)?)?;
+        Ok(())
+    }
+
+    fn inbox_cache_path(&self, key: &str) -> PathBuf {
+        self.inbox_cache_dir.join(format!("{key}.json"))
+    }
+
+    pub fn load_enrichment_cache(&self, key: &str) -> Result<EnrichmentCache, SecretStoreError> {
+        match fs::read_to_string(self.enrichment_cache_path(key)) {
+            Ok(contents) => Ok(serde_json::from_str(&contents)?),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(EnrichmentCache::default()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    pub fn persist_enrichment_cache(
+        &self,
+        key: &str,
+        cache: &EnrichmentCache,
+    ) -> Result<(), SecretStoreError> {
+        fs::create_dir_all(&self.inbox_cache_dir)?;
+        fs::write(
+            self.enrichment_cache_path(key),
+            serde_json::to_string(cache)?,
+        )?;
+        Ok(())
+    }
+
+    fn enrichment_cache_path(&self, key: &str) -> PathBuf {
+        self.inbox_cache_dir.join(format!("{key}.enrichment.json"))
+    }
+
+    /// Threads marked done for the account `key`. Unlike the caches these
+    /// are user state, so they live outside the cache directory.
+    pub fn load_done_threads(&self, key: &str) -> Result<DoneThreads, SecretStoreError> {
+        match fs::read_to_string(self.done_threads_path(key)) {
+            Ok(contents) => Ok(serde_json::from_str(&contents)?),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(DoneThreads::default()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    pub fn persist_done_threads(
+        &self,
+        key: &str,
+        done: &DoneThreads,
+    ) -> Result<(), SecretStoreError> {
+        fs::create_dir_all(&self.done_threads_dir)?;
+        fs::write(self.done_threads_path(key), serde_json::to_string(done)?)?;
+        Ok(())
+    }
+
+    fn done_threads_path(&self, key: &str) -> PathBuf {
+        self.done_threads_dir.join(format!("{key}.json"))
+    }
+
+    /// The personal kanban stages of the account `key`'s threads, kept with
+    /// the done marks.
+    pub fn load_thread_stages(&self, key: &str) -> Result<ThreadStages, SecretStoreError> {
+        match fs::read_to_string(self.thread_stages_path(key)) {
+            Ok(contents) => Ok(serde_json::from_str(&contents)?),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(ThreadStages::default()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    pub fn persist_thread_stages(
+        &self,
+        key: &str,
+        stages: &ThreadStages,
+    ) -> Result<(), SecretStoreError> {
+        fs::create_dir_all(&self.done_threads_dir)?;
+        fs::write(self.thread_stages_path(key), serde_json::to_string(stages)?)?;
+        Ok(())
+    }
+
+    fn thread_stages_path(&self, key: &str) -> PathBuf {
+        self.done_threads_dir.join(format!("{key}.stages.json"))
+    }
+
+    /// Every thread the account `key` has seen, for the history view.
+    pub fn load_archive(&self, key: &str) -> Result<NotificationArchive, SecretStoreError> {
+        match fs::read_to_string(self.archive_path(key)) {
+            Ok(contents) => Ok(serde_json::from_str(&contents)?),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(NotificationArchive::default()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    pub fn persist_archive(
+        &self,
+        key: &str,
+        archive: &NotificationArchive,
+    ) -> Result<(), SecretStoreError> {
+        fs::create_dir_all(&self.archive_dir)?;
+        fs::write(self.archive_path(key), serde_json::to_string(archive)?)?;
+        Ok(())
+    }
+
+    fn archive_path(&self, key: &str) -> PathBuf {
+        self.archive_dir.join(format!("{key}.json"))
+    }
+
+    fn account_paths(&self, key: &str) -> [PathBuf; 5] {
+        [
+            self.inbox_cache_path(key),
+            self.enrichment_cache_path(key),
+            self.done_threads_path(key),
+            self.thread_stages_path(key),
+            self.archive_path(key),
+        ]
+    }
+
+    /// Moves the token and files saved under the entry's bare login to its
+    /// key, leaving any already saved under the key alone.
+    fn adopt_login_data(&self, entry: &StoredAccount) -> Result<(), SecretStoreError> {
+        let key = entry.key();
+        for (old, new) in self
+            .account_paths(&entry.login)
+            .into_iter()
+            .zip(self.account_paths(&key))
+        {
+            if old.exists() && !new.exists() {
+                fs::rename(old, new)?;
+            }
+        }
+        if entry.token.is_empty() && entry.token_command.is_none() {
+            self.backend.adopt(entry, &entry.login)?;
+        }
         Ok(())
     }
 
@@ -165,6 +644,111 @@ impl AccountStore {
         Ok(())
     }
 
+    pub fn load_reminders(&self) -> Result<ReminderList, SecretStoreError> {
+        match fs::read_to_string(&self.reminders_path) {
+            Ok(contents) => Ok(serde_json::from_str(&contents)?),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(ReminderList::default()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    pub fn persist_reminders(&self, reminders: &ReminderList) -> Result<(), SecretStoreError> {
+        let data = serde_json::to_string_pretty(reminders)?;
+        fs::write(&self.reminders_path, data)?;
+        Ok(())
+    }
+
+    pub fn load_history(&self) -> Result<ActivityHistory, SecretStoreError> {
+        match fs::read_to_string(&self.history_path) {
+            Ok(contents) => Ok(serde_json::from_str(&contents)?),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(ActivityHistory::default()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    pub fn persist_history(&self, history: &ActivityHistory) -> Result<(), SecretStoreError> {
+        let data = serde_json::to_string_pretty(history)?;
+        fs::write(&self.history_path, data)?;
+        Ok(())
+    }
+
+    /// The document the last successful sync produced.
+    pub fn load_sync_state(&self) -> Result<SyncDocument, SecretStoreError> {
+        match fs::read_to_string(self.dir.join(SYNC_STATE_FILE)) {
+            Ok(contents) => Ok(serde_json::from_str(&contents)?),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(SyncDocument::default()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    pub fn persist_sync_state(&self, document: &SyncDocument) -> Result<(), SecretStoreError> {
+        let data = serde_json::to_string(document)?;
+        fs::write(self.dir.join(SYNC_STATE_FILE), data)?;
+        Ok(())
+    }
+
+    /// Copies the local triage state into a new timestamped backup.
+    pub fn create_backup(&self) -> Result<Backup, SecretStoreError> {
+        let created = backup::create(&self.dir, &self.backed_up_settings()?, chrono::Local::now())?;
+        self.prune_backups()?;
+        Ok(created)
+    }
+
+    /// Saved backups, newest first.
+    pub fn backups(&self) -> Result<Vec<Backup>, SecretStoreError> {
+        Ok(backup::list(&self.dir)?)
+    }
+
+    /// Replaces the local triage state and settings with backup `name`,
+    /// backing up the current state first so the restore itself can be
+    /// undone. Accounts and the vault are left as they are.
+    pub fn restore_backup(&self, name: &str) -> Result<(), SecretStoreError> {
+        self.ensure_unlocked()?;
+        backup::create(&self.dir, &self.backed_up_settings()?, chrono::Local::now())?;
+        if let Some(contents) = backup::restore(&self.dir, name)? {
+            let saved: BackedUpSettings = serde_json::from_str(&contents)?;
+            let mut registry = self.read_registry()?;
+            registry.repo_paths = saved.repo_paths;
+            registry.repo_path_accounts = saved.repo_path_accounts;
+            registry.settings = saved.settings;
+            self.write_registry(&registry)?;
+        }
+        self.prune_backups()?;
+        Ok(())
+    }
+
+    fn backed_up_settings(&self) -> Result<String, SecretStoreError> {
+        let registry = self.read_registry()?;
+        Ok(serde_json::to_string_pretty(&BackedUpSettings {
+            repo_paths: registry.repo_paths,
+            repo_path_accounts: registry.repo_path_accounts,
+            settings: registry.settings,
+        })?)
+    }
+
+    fn prune_backups(&self) -> Result<(), SecretStoreError> {
+        Ok(backup::prune(&self.dir, |contents| {
+            let saved: BackedUpSettings = serde_json::from_str(contents).ok()?;
+            serde_json::to_string_pretty(&saved).ok()
+        })?)
+    }
+
+    fn ensure_not_provisioned(&self, profile: &GitHubAccount) -> Result<(), SecretStoreError> {
+        if self.provisioned_keys.contains(&profile.storage_key()) {
+            Err(SecretStoreError::Provisioned(profile.login.clone()))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn ensure_unlocked(&self) -> Result<(), SecretStoreError> {
+        if self.locked {
+            Err(SecretStoreError::Locked)
+        } else {
+            Ok(())
+        }
+    }
+
     fn read_registry(&self) -> Result<StoredAccounts, SecretStoreError> {
         match fs::read_to_string(&self.registry_path) {
             Ok(contents) => Ok(serde_json::from_str(&contents)?),
@@ -180,19 +764,175 @@ impl AccountStore {
     }
 }
 
+/// Directory holding the registry and other per-profile files.
+pub fn data_dir(profile: Option<&str>) -> Result<PathBuf, SecretStoreError> {
+    let home = env::var("HOME").map_err(|_| SecretStoreError::HomeDirMissing)?;
+    storage_dir(&PathBuf::from(home), profile)
+}
+
+/// Keychain service name; named profiles get their own so isolated
+/// instances never share tokens.
+fn keyring_service(profile: Option<&str>) -> String {
+    match profile {
+        None => KEYRING_SERVICE.to_owned(),
+        Some(name) => format!("{KEYRING_SERVICE}:{name}"),
+    }
+}
+
+/// Resolves the storage directory for a profile. The default profile keeps
+/// using `~/.reminder` so existing installs are unaffected; named profiles live
+/// under `~/.reminder/profiles/<name>`.
+fn storage_dir(home: &Path, profile: Option<&str>) -> Result<PathBuf, SecretStoreError> {
+    let base = home.join(STORAGE_DIR_NAME);
+    match profile {
+        None => Ok(base),
+        Some(name) => {
+            validate_profile_name(name)?;
+            Ok(base.join(PROFILES_DIR_NAME).join(name))
+        }
+    }
+}
+
+pub fn validate_profile_name(name: &str) -> Result<(), SecretStoreError> {
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(SecretStoreError::InvalidProfile(name.to_owned()))
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum SecretStoreError {
     #[error("HOME environment variable is not set; cannot store tokens under ~/.reminder")]
     HomeDirMissing,
+    #[error("Profile name {0:?} is invalid; use only letters, digits, '-' or '_'")]
+    InvalidProfile(String),
     #[error("I/O error while handling stored accounts: {0}")]
     Io(#[from] io::Error),
     #[error("Failed to serialize stored accounts: {0}")]
     Serialization(#[from] serde_json::Error),
+    #[error("OS keychain error: {0}")]
+    Keychain(#[from] keyring::Error),
+    #[error("Stored tokens are locked; enter the passphrase first")]
+    Locked,
+    #[error("Wrong passphrase")]
+    WrongPassphrase,
+    #[error("Token encryption failed: {0}")]
+    Encryption(String),
+    #[error("Encrypted token is malformed: {0}")]
+    Encoding(#[from] base64::DecodeError),
+    #[error("Invalid account config: {0}")]
+    Provisioning(#[from] toml::de::Error),
+    #[error("{0} is managed by accounts.toml; edit that file instead")]
+    Provisioned(String),
+    #[error("Token command failed: {0}")]
+    TokenCommand(String),
+    #[error(
+        "Could not save the token in {backend} ({reason}). Set a passphrase under Preferences → Security to encrypt tokens in the registry file instead."
+    )]
+    TokenNotSaved {
+        backend: &'static str,
+        reason: String,
+    },
+}
+
+/// Positions of saved accounts that repeat an earlier login on the same
+/// instance, ignoring case. Of each group the last entry with a token
+/// survives, or the last entry when none has one.
+fn duplicate_profile_indices(profiles: &[GitHubAccount]) -> Vec<usize> {
+    let mut keep = BTreeMap::<String, usize>::new();
+    for (index, profile) in profiles.iter().enumerate() {
+        let key = profile.storage_key().to_ascii_lowercase();
+        let replaces = keep
+            .get(&key)
+            .is_none_or(|kept| !profile.token.is_empty() || profiles[*kept].token.is_empty());
+        if replaces {
+            keep.insert(key, index);
+        }
+    }
+    (0..profiles.len())
+        .filter(|index| !keep.values().any(|kept| kept == index))
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
-    use super::StoredAccounts;
+    use std::path::Path;
+
+    use super::{
+        BackedUpSettings, SecretStoreError, StoredAccount, StoredAccounts,
+        duplicate_profile_indices, keyring_service, storage_dir,
+    };
+    use crate::domain::{AppSettings, Forge, GitHubAccount, InboxBackend, ReviewCommandSettings};
+
+    fn profile(login: &str, token: &str) -> GitHubAccount {
+        GitHubAccount {
+            login: login.to_owned(),
+            token: token.to_owned(),
+            review_settings: ReviewCommandSettings::default(),
+            hide_bot_notifications: None,
+            api_base_url: None,
+            working_hours: None,
+            token_command: None,
+            group: None,
+            section_alerts: None,
+            inbox_backend: InboxBackend::Rest,
+            forge: Forge::GitHub,
+        }
+    }
+
+    #[test]
+    fn duplicate_logins_keep_the_entry_with_a_token() {
+        let profiles = [
+            profile("neo", "ghp_old"),
+            profile("Neo", ""),
+            profile("trinity", ""),
+            profile("NEO", "ghp_new"),
+            profile("trinity", ""),
+        ];
+
+        assert_eq!(duplicate_profile_indices(&profiles), vec![0, 1, 2]);
+        assert!(duplicate_profile_indices(&profiles[..1]).is_empty());
+    }
+
+    #[test]
+    fn the_same_login_on_other_instances_is_not_a_duplicate() {
+        let mut gitlab = profile("neo", "glpat_x");
+        gitlab.forge = Forge::GitLab;
+        let mut enterprise = profile("neo", "ghp_corp");
+        enterprise.api_base_url = Some(String::from("https://github.mycorp.com/api/v3"));
+        let profiles = [profile("neo", "ghp_x"), gitlab, enterprise];
+
+        assert!(duplicate_profile_indices(&profiles).is_empty());
+    }
+
+    #[test]
+    fn storage_dir_keeps_default_location_without_profile() {
+        let dir = storage_dir(Path::new("/home/neo"), None).expect("dir");
+
+        assert_eq!(dir, Path::new("/home/neo/.reminder"));
+    }
+
+    #[test]
+    fn storage_dir_isolates_named_profiles() {
+        let dir = storage_dir(Path::new("/home/neo"), Some("work")).expect("dir");
+
+        assert_eq!(dir, Path::new("/home/neo/.reminder/profiles/work"));
+    }
+
+    #[test]
+    fn storage_dir_rejects_path_like_profile_names() {
+        for name in ["", "../escape", "a/b", "."] {
+            assert!(matches!(
+                storage_dir(Path::new("/home/neo"), Some(name)),
+                Err(SecretStoreError::InvalidProfile(_))
+            ));
+        }
+    }
 
     #[test]
     fn stored_accounts_defaults_missing_review_settings() {
@@ -225,5 +965,38 @@ mod tests {
                 .is_none()
         );
         assert!(stored.repo_path_accounts.is_empty());
+        assert_eq!(stored.settings, AppSettings::default());
+    }
+
+    #[test]
+    fn keyring_service_is_scoped_per_profile() {
+        assert_eq!(keyring_service(None), "reminder");
+        assert_eq!(keyring_service(Some("work")), "reminder:work");
+    }
+
+    #[test]
+    fn backups_keep_settings_but_not_accounts_or_tokens() {
+        let legacy = r#"{
+            "accounts": [{"login": "neo", "token": "ghp_x"}],
+            "repo_paths": {"acme/repo": "/tmp/acme-repo"},
+            "vault": {"salt": "c2FsdA"}
+        }"#;
+        let saved: BackedUpSettings = serde_json::from_str(legacy).expect("settings");
+        let json = serde_json::to_string(&saved).expect("json");
+
+        assert_eq!(saved.repo_paths["acme/repo"], "/tmp/acme-repo");
+        assert!(!json.contains("ghp_x") && !json.contains("vault") && !json.contains("neo"));
+    }
+
+    #[test]
+    fn keychain_tokens_are_left_out_of_the_registry_file() {
+        let account: StoredAccount =
+            serde_json::from_str(r#"{"login":"neo","token":""}"#).expect("account");
+        let json = serde_json::to_string(&account).expect("json");
+        assert!(!json.contains("token"));
+
+        let legacy: StoredAccount =
+            serde_json::from_str(r#"{"login":"neo","token":"ghp_x"}"#).expect("legacy account");
+        assert_eq!(legacy.token, "ghp_x");
     }
 }
@@ -0,0 +1,232 @@
+use std::{fs, io, path::Path};
+
+use chrono::{DateTime, Local, NaiveDateTime};
+
+use super::{ARCHIVE_DIR, DONE_THREADS_DIR, HISTORY_FILE, REGISTRY_FILE, REMINDERS_FILE};
+
+pub const BACKUPS_DIR: &str = "backups";
+/// Older backups beyond this many are deleted after each new one.
+const KEEP_BACKUPS: usize = 10;
+const NAME_FORMAT: &str = "%Y%m%d-%H%M%S";
+const PARTIAL_SUFFIX: &str = ".partial";
+
+/// Settings, rules and repo paths from the registry, without accounts,
+/// tokens or the vault.
+const SETTINGS_FILE: &str = "settings.json";
+/// Triage state worth keeping besides [`SETTINGS_FILE`]: the activity
+/// history, reminders, and the per-account done and seen threads. Offline
+/// inboxes and caches are rebuilt by the next refresh.
+const FILES: [&str; 2] = [HISTORY_FILE, REMINDERS_FILE];
+const DIRS: [&str; 2] = [DONE_THREADS_DIR, ARCHIVE_DIR];
+
+/// One timestamped copy under `backups/`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Backup {
+    pub name: String,
+    pub created_at: DateTime<Local>,
+}
+
+/// Copies the triage state of `data_dir` and `settings` into a new backup.
+/// The copy is written under a temporary name first, so an interrupted
+/// backup never shows up in [`list`].
+pub fn create(data_dir: &Path, settings: &str, now: DateTime<Local>) -> io::Result<Backup> {
+    let root = data_dir.join(BACKUPS_DIR);
+    let name = now.format(NAME_FORMAT).to_string();
+    let partial = root.join(format!("{name}{PARTIAL_SUFFIX}"));
+    if partial.exists() {
+        fs::remove_dir_all(&partial)?;
+    }
+    fs::create_dir_all(&partial)?;
+    fs::write(partial.join(SETTINGS_FILE), settings)?;
+    copy_state(data_dir, &partial)?;
+    let target = root.join(&name);
+    if target.exists() {
+        fs::remove_dir_all(&target)?;
+    }
+    fs::rename(&partial, &target)?;
+    Ok(Backup {
+        name,
+        created_at: now,
+    })
+}
+
+/// Deletes all but the newest [`KEEP_BACKUPS`] backups, and swaps the whole
+/// registry older backups copied for the settings `settings_of` picks from
+/// it, so no token outlives the registry it came from.
+pub fn prune(data_dir: &Path, settings_of: impl Fn(&str) -> Option<String>) -> io::Result<()> {
+    let root = data_dir.join(BACKUPS_DIR);
+    for (index, backup) in list(data_dir)?.into_iter().enumerate() {
+        let dir = root.join(backup.name);
+        if index >= KEEP_BACKUPS {
+            fs::remove_dir_all(dir)?;
+            continue;
+        }
+        let registry = dir.join(REGISTRY_FILE);
+        let Ok(contents) = fs::read_to_string(&registry) else {
+            continue;
+        };
+        if let Some(settings) = settings_of(&contents) {
+            fs::write(dir.join(SETTINGS_FILE), settings)?;
+        }
+        remove_file(&registry)?;
+    }
+    Ok(())
+}
+
+/// Complete backups, newest first.
+pub fn list(data_dir: &Path) -> io::Result<Vec<Backup>> {
+    let entries = match fs::read_dir(data_dir.join(BACKUPS_DIR)) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+    let mut backups = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        if !entry.file_type()?.is_dir() {
+            continue;
+        }
+        let Some(name) = entry.file_name().to_str().map(str::to_owned) else {
+            continue;
+        };
+        if let Some(created_at) = NaiveDateTime::parse_from_str(&name, NAME_FORMAT)
+            .ok()
+            .and_then(|time| time.and_local_timezone(Local).earliest())
+        {
+            backups.push(Backup { name, created_at });
+        }
+    }
+    backups.sort_by(|a, b| b.name.cmp(&a.name));
+    Ok(backups)
+}
+
+/// Replaces the triage state of `data_dir` with backup `name` and returns
+/// its settings for the caller to merge into the registry. Files the backup
+/// lacks are removed, so the result matches the moment it was taken.
+pub fn restore(data_dir: &Path, name: &str) -> io::Result<Option<String>> {
+    let source = data_dir.join(BACKUPS_DIR).join(name);
+    if name.contains(['/', '\\']) || name.ends_with(PARTIAL_SUFFIX) || !source.is_dir() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("no backup named {name:?}"),
+        ));
+    }
+    for file in FILES {
+        let target = data_dir.join(file);
+        match fs::copy(source.join(file), &target) {
+            Ok(_) => {}
+            Err(err) if err.kind() == io::ErrorKind::NotFound => remove_file(&target)?,
+            Err(err) => return Err(err),
+        }
+    }
+    for dir in DIRS {
+        let target = data_dir.join(dir);
+        if target.exists() {
+            fs::remove_dir_all(&target)?;
+        }
+        if source.join(dir).is_dir() {
+            copy_dir(&source.join(dir), &target)?;
+        }
+    }
+    // Backups from before settings were split out hold the whole registry.
+    for file in [SETTINGS_FILE, REGISTRY_FILE] {
+        match fs::read_to_string(source.join(file)) {
+            Ok(settings) => return Ok(Some(settings)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(None)
+}
+
+fn copy_state(data_dir: &Path, target: &Path) -> io::Result<()> {
+    for file in FILES {
+        match fs::copy(data_dir.join(file), target.join(file)) {
+            Ok(_) => {}
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err),
+        }
+    }
+    for dir in DIRS {
+        let source = data_dir.join(dir);
+        if source.is_dir() {
+            copy_dir(&source, &target.join(dir))?;
+        }
+    }
+    Ok(())
+}
+
+fn copy_dir(source: &Path, target: &Path) -> io::Result<()> {
+    fs::create_dir_all(target)?;
+    for entry in fs::read_dir(source)? {
+        let entry = entry?;
+        if entry.file_type()?.is_file() {
+            fs::copy(entry.path(), target.join(entry.file_name()))?;
+        }
+    }
+    Ok(())
+}
+
+fn remove_file(path: &Path) -> io::Result<()> {
+    match fs::remove_file(path) {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use chrono::{Duration, Local, TimeZone};
+
+    use super::{
+        BACKUPS_DIR, KEEP_BACKUPS, REGISTRY_FILE, SETTINGS_FILE, create, list, prune, restore,
+    };
+
+    #[test]
+    fn restore_brings_back_the_state_of_a_backup() {
+        let dir = std::env::temp_dir().join(format!("reminder-backup-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("archive")).expect("data dir");
+        fs::write(dir.join("history.json"), "old history").expect("history");
+        fs::write(dir.join("archive/neo.json"), "old archive").expect("archive");
+        let start = Local.with_ymd_and_hms(2026, 3, 2, 9, 0, 0).unwrap();
+
+        let first = create(&dir, "old settings", start).expect("backup");
+        assert_eq!(first.name, "20260302-090000");
+        fs::write(dir.join("history.json"), "new history").expect("history");
+        fs::write(dir.join("reminders.json"), "reminders").expect("reminders");
+        fs::write(dir.join("archive/trinity.json"), "new archive").expect("archive");
+        assert_eq!(
+            restore(&dir, &first.name).expect("restore").as_deref(),
+            Some("old settings")
+        );
+
+        assert_eq!(
+            fs::read_to_string(dir.join("history.json")).unwrap(),
+            "old history"
+        );
+        assert!(!dir.join("reminders.json").exists());
+        assert!(!dir.join("archive/trinity.json").exists());
+        assert!(restore(&dir, "../archive").is_err());
+
+        for hour in 1..=KEEP_BACKUPS as i64 {
+            create(&dir, "settings", start + Duration::hours(hour)).expect("backup");
+        }
+        let legacy = dir.join(BACKUPS_DIR).join("20260302-100000");
+        fs::write(legacy.join(REGISTRY_FILE), r#"{"token":"ghp_x"}"#).unwrap();
+        prune(&dir, |_| Some(String::from("stripped"))).expect("prune");
+        assert!(!legacy.join(REGISTRY_FILE).exists());
+        assert_eq!(
+            fs::read_to_string(legacy.join(SETTINGS_FILE)).unwrap(),
+            "stripped"
+        );
+        fs::create_dir_all(dir.join(BACKUPS_DIR).join("20260302-230000.partial")).unwrap();
+        let backups = list(&dir).expect("list");
+        assert_eq!(backups.len(), KEEP_BACKUPS);
+        assert_eq!(backups[0].created_at, start + Duration::hours(10));
+        assert!(backups.iter().all(|backup| backup.name != first.name));
+        fs::remove_dir_all(&dir).expect("cleanup");
+    }
+}
@@ -0,0 +1,194 @@
+use std::{fs, io, path::Path};
+
+use serde::Deserialize;
+
+use super::SecretStoreError;
+use crate::domain::{
+    Forge, GitHubAccount, InboxBackend, ReviewCommandSettings, SectionAlertThresholds,
+    WorkingHours, normalize_api_base_url,
+};
+
+/// Accounts declared here are loaded on every start with their token read
+/// from an environment variable; neither ends up in `accounts.json`.
+pub(super) const PROVISIONING_FILE: &str = "accounts.toml";
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ProvisioningFile {
+    #[serde(default)]
+    accounts: Vec<ProvisionedAccount>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ProvisionedAccount {
+    login: String,
+    token_env: String,
+    #[serde(default)]
+    api_base_url: Option<String>,
+    #[serde(default)]
+    hide_bot_notifications: Option<bool>,
+    #[serde(default)]
+    working_hours: Option<WorkingHours>,
+    #[serde(default)]
+    review_settings: ReviewCommandSettings,
+    #[serde(default)]
+    group: Option<String>,
+    #[serde(default)]
+    section_alerts: Option<SectionAlertThresholds>,
+    #[serde(default)]
+    inbox_backend: InboxBackend,
+    #[serde(default)]
+    forge: Forge,
+}
+
+#[derive(Default)]
+pub(super) struct Provisioned {
+    pub(super) profiles: Vec<GitHubAccount>,
+    /// Accounts skipped because the variable is unset or empty.
+    pub(super) missing_tokens: Vec<MissingToken>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(super) struct MissingToken {
+    pub(super) login: String,
+    /// See [`GitHubAccount::storage_key`].
+    pub(super) key: String,
+    pub(super) variable: String,
+}
+
+/// Reads the provisioning file, if any. `token_for` resolves a variable
+/// name to its value so tests don't touch the real environment.
+pub(super) fn load(
+    path: &Path,
+    token_for: impl Fn(&str) -> Option<String>,
+) -> Result<Provisioned, SecretStoreError> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Provisioned::default()),
+        Err(err) => return Err(err.into()),
+    };
+    let file: ProvisioningFile = toml::from_str(&contents)?;
+
+    let mut provisioned = Provisioned {
+        profiles: Vec::with_capacity(file.accounts.len()),
+        missing_tokens: Vec::new(),
+    };
+    for account in file.accounts {
+        let api_base_url = account
+            .api_base_url
+            .as_deref()
+            .and_then(|url| normalize_api_base_url(account.forge, url));
+        let Some(token) = token_for(&account.token_env).filter(|token| !token.trim().is_empty())
+        else {
+            provisioned.missing_tokens.push(MissingToken {
+                key: account
+                    .forge
+                    .account_key(api_base_url.as_deref(), &account.login),
+                login: account.login,
+                variable: account.token_env,
+            });
+            continue;
+        };
+        provisioned.profiles.push(GitHubAccount {
+            login: account.login,
+            token: token.trim().to_owned(),
+            review_settings: account.review_settings,
+            hide_bot_notifications: account.hide_bot_notifications,
+            api_base_url,
+            working_hours: account.working_hours,
+            token_command: None,
+            group: account.group,
+            section_alerts: account.section_alerts,
+            inbox_backend: account.inbox_backend,
+            forge: account.forge,
+        });
+    }
+    Ok(provisioned)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use super::{MissingToken, load};
+    use crate::storage::SecretStoreError;
+
+    fn write_config(name: &str, contents: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "reminder-provisioning-{}-{name}.toml",
+            std::process::id()
+        ));
+        fs::write(&path, contents).expect("write config");
+        path
+    }
+
+    #[test]
+    fn provisioned_accounts_take_tokens_from_named_variables() {
+        let path = write_config(
+            "tokens",
+            r#"
+                [[accounts]]
+                login = "neo"
+                token_env = "NEO_TOKEN"
+                api_base_url = "github.acme.com"
+
+                [accounts.working_hours]
+                start_hour = 8
+                end_hour = 17
+                weekdays_only = true
+
+                [[accounts]]
+                login = "trinity"
+                token_env = "TRINITY_TOKEN"
+            "#,
+        );
+
+        let provisioned = load(&path, |name| {
+            (name == "NEO_TOKEN").then(|| String::from("ghp_neo\n"))
+        })
+        .expect("provisioned");
+        fs::remove_file(&path).ok();
+
+        assert_eq!(provisioned.profiles.len(), 1);
+        let neo = &provisioned.profiles[0];
+        assert_eq!(neo.token, "ghp_neo");
+        assert_eq!(
+            neo.api_base_url.as_deref(),
+            Some("https://github.acme.com/api/v3")
+        );
+        assert_eq!(neo.working_hours.map(|hours| hours.start_hour), Some(8));
+        assert_eq!(
+            provisioned.missing_tokens,
+            [MissingToken {
+                login: String::from("trinity"),
+                key: String::from("trinity"),
+                variable: String::from("TRINITY_TOKEN"),
+            }]
+        );
+    }
+
+    #[test]
+    fn plaintext_tokens_are_rejected() {
+        let path = write_config(
+            "plaintext",
+            "[[accounts]]\nlogin = \"neo\"\ntoken_env = \"NEO_TOKEN\"\ntoken = \"ghp_x\"\n",
+        );
+
+        let result = load(&path, |_| None);
+        fs::remove_file(&path).ok();
+
+        assert!(matches!(result, Err(SecretStoreError::Provisioning(_))));
+    }
+
+    #[test]
+    fn missing_file_provisions_nothing() {
+        let provisioned = load(
+            &std::env::temp_dir().join("reminder-no-such-file.toml"),
+            |_| None,
+        )
+        .expect("provisioned");
+
+        assert!(provisioned.profiles.is_empty());
+    }
+}
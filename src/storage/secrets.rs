@@ -0,0 +1,326 @@
+use std::process::{Command, Stdio};
+
+use argon2::Argon2;
+use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
+use chacha20poly1305::{
+    ChaCha20Poly1305, Key, Nonce,
+    aead::{Aead, AeadCore, KeyInit, OsRng, Payload, rand_core::RngCore},
+};
+use serde::{Deserialize, Serialize};
+
+use super::{SecretStoreError, StoredAccount};
+
+const SALT_LEN: usize = 16;
+/// Encrypted under the vault key so a wrong passphrase is caught before any
+/// token is touched.
+const VAULT_CHECK: &str = "reminder-vault";
+
+/// Where account tokens live. The registry entry is handed over so backends
+/// that keep secrets in the file itself can record them there.
+pub(super) trait SecretBackend {
+    fn store(&self, entry: &mut StoredAccount, token: &str) -> Result<(), SecretStoreError>;
+    fn load(&self, entry: &StoredAccount) -> Result<Option<String>, SecretStoreError>;
+    /// Drops the token saved under a [`StoredAccount::key`].
+    fn delete(&self, key: &str) -> Result<(), SecretStoreError>;
+    /// Moves a token saved under `from` by an older version to the entry's
+    /// key.
+    fn adopt(&self, entry: &StoredAccount, from: &str) -> Result<(), SecretStoreError>;
+    /// Human-readable name used in storage warnings.
+    fn describe(&self) -> &'static str;
+}
+
+pub(super) struct KeychainBackend {
+    pub(super) service: String,
+}
+
+impl KeychainBackend {
+    fn entry(&self, key: &str) -> keyring::Result<keyring::Entry> {
+        keyring::Entry::new(&self.service, key)
+    }
+}
+
+impl SecretBackend for KeychainBackend {
+    fn store(&self, entry: &mut StoredAccount, token: &str) -> Result<(), SecretStoreError> {
+        self.entry(&entry.key())?.set_password(token)?;
+        entry.token.clear();
+        entry.encrypted_token = None;
+        Ok(())
+    }
+
+    fn load(&self, entry: &StoredAccount) -> Result<Option<String>, SecretStoreError> {
+        match self.entry(&entry.key())?.get_password() {
+            Ok(token) => Ok(Some(token)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    fn adopt(&self, entry: &StoredAccount, from: &str) -> Result<(), SecretStoreError> {
+        let new = self.entry(&entry.key())?;
+        match new.get_password() {
+            Err(keyring::Error::NoEntry) => {}
+            Ok(_) => return Ok(()),
+            Err(err) => return Err(err.into()),
+        }
+        let old = self.entry(from)?;
+        let token = match old.get_password() {
+            Ok(token) => token,
+            Err(keyring::Error::NoEntry) => return Ok(()),
+            Err(err) => return Err(err.into()),
+        };
+        new.set_password(&token)?;
+        old.delete_credential()?;
+        Ok(())
+    }
+
+    fn delete(&self, key: &str) -> Result<(), SecretStoreError> {
+        match self.entry(key)?.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            // Nothing to delete when the keychain never held the token.
+            Err(keyring::Error::PlatformFailure(_) | keyring::Error::NoStorageAccess(_)) => Ok(()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    fn describe(&self) -> &'static str {
+        "the OS keychain"
+    }
+}
+
+/// Keeps tokens in the registry file, sealed with ChaCha20-Poly1305 under a
+/// key derived from the user's passphrase.
+pub(super) struct EncryptedBackend {
+    key: [u8; 32],
+}
+
+impl EncryptedBackend {
+    /// Derives the key for an existing vault and checks the passphrase.
+    pub(super) fn unlock(passphrase: &str, vault: &VaultHeader) -> Result<Self, SecretStoreError> {
+        let salt = BASE64.decode(&vault.salt)?;
+        let backend = Self {
+            key: derive_key(passphrase, &salt)?,
+        };
+        match backend.open(&vault.check, VAULT_CHECK) {
+            Ok(check) if check == VAULT_CHECK => Ok(backend),
+            _ => Err(SecretStoreError::WrongPassphrase),
+        }
+    }
+
+    /// Starts a new vault with a fresh salt.
+    pub(super) fn create(passphrase: &str) -> Result<(Self, VaultHeader), SecretStoreError> {
+        let mut salt = [0u8; SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+        let backend = Self {
+            key: derive_key(passphrase, &salt)?,
+        };
+        let vault = VaultHeader {
+            salt: BASE64.encode(salt),
+            check: backend.seal(VAULT_CHECK, VAULT_CHECK)?,
+        };
+        Ok((backend, vault))
+    }
+
+    fn cipher(&self) -> ChaCha20Poly1305 {
+        ChaCha20Poly1305::new(Key::from_slice(&self.key))
+    }
+
+    /// `context` is bound as associated data so a token cannot be swapped
+    /// onto another account in the file.
+    fn seal(&self, plaintext: &str, context: &str) -> Result<EncryptedToken, SecretStoreError> {
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .cipher()
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: plaintext.as_bytes(),
+                    aad: context.as_bytes(),
+                },
+            )
+            .map_err(|_| SecretStoreError::Encryption(String::from("could not seal token")))?;
+        Ok(EncryptedToken {
+            nonce: BASE64.encode(nonce),
+            ciphertext: BASE64.encode(ciphertext),
+        })
+    }
+
+    fn open(&self, sealed: &EncryptedToken, context: &str) -> Result<String, SecretStoreError> {
+        let nonce = BASE64.decode(&sealed.nonce)?;
+        if nonce.len() != 12 {
+            return Err(SecretStoreError::Encryption(String::from(
+                "stored nonce has the wrong length",
+            )));
+        }
+        let plaintext = self
+            .cipher()
+            .decrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: &BASE64.decode(&sealed.ciphertext)?,
+                    aad: context.as_bytes(),
+                },
+            )
+            .map_err(|_| SecretStoreError::WrongPassphrase)?;
+        String::from_utf8(plaintext)
+            .map_err(|_| SecretStoreError::Encryption(String::from("token is not valid UTF-8")))
+    }
+}
+
+impl SecretBackend for EncryptedBackend {
+    fn store(&self, entry: &mut StoredAccount, token: &str) -> Result<(), SecretStoreError> {
+        entry.encrypted_token = Some(self.seal(token, &entry.key())?);
+        entry.token.clear();
+        Ok(())
+    }
+
+    fn load(&self, entry: &StoredAccount) -> Result<Option<String>, SecretStoreError> {
+        entry
+            .encrypted_token
+            .as_ref()
+            .map(|sealed| {
+                // Tokens sealed by older versions are bound to the login.
+                self.open(sealed, &entry.key())
+                    .or_else(|_| self.open(sealed, &entry.login))
+            })
+            .transpose()
+    }
+
+    fn delete(&self, _key: &str) -> Result<(), SecretStoreError> {
+        // The sealed token goes away with the registry entry.
+        Ok(())
+    }
+
+    fn adopt(&self, _entry: &StoredAccount, _from: &str) -> Result<(), SecretStoreError> {
+        // Sealed tokens live in their entry; `load` accepts the old binding.
+        Ok(())
+    }
+
+    fn describe(&self) -> &'static str {
+        "the encrypted registry"
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EncryptedToken {
+    pub nonce: String,
+    pub ciphertext: String,
+}
+
+/// Present in the registry once tokens are protected by a passphrase.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct VaultHeader {
+    pub salt: String,
+    pub check: EncryptedToken,
+}
+
+/// Runs a user-configured command such as `op read …` or `pass show …` and
+/// takes the first line of its output as the token.
+pub fn run_token_command(command: &str) -> Result<String, SecretStoreError> {
+    let output = shell(command)
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|err| {
+            SecretStoreError::TokenCommand(format!("could not run `{command}`: {err}"))
+        })?;
+    if !output.status.success() {
+        return Err(SecretStoreError::TokenCommand(format!(
+            "`{command}` exited with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .next()
+        .map(str::trim)
+        .filter(|token| !token.is_empty())
+        .map(str::to_owned)
+        .ok_or_else(|| SecretStoreError::TokenCommand(format!("`{command}` printed no token")))
+}
+
+#[cfg(unix)]
+fn shell(command: &str) -> Command {
+    let mut shell = Command::new("sh");
+    shell.args(["-c", command]);
+    shell
+}
+
+#[cfg(not(unix))]
+fn shell(command: &str) -> Command {
+    let mut shell = Command::new("cmd");
+    shell.args(["/C", command]);
+    shell
+}
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> Result<[u8; 32], SecretStoreError> {
+    let mut key = [0u8; 32];
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|err| SecretStoreError::Encryption(err.to_string()))?;
+    Ok(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EncryptedBackend, SecretBackend, run_token_command};
+    use crate::storage::{SecretStoreError, StoredAccount};
+
+    fn account(login: &str) -> StoredAccount {
+        serde_json::from_str(&format!(r#"{{"login":"{login}"}}"#)).expect("account")
+    }
+
+    #[test]
+    fn encrypted_tokens_round_trip_after_unlock() {
+        let (backend, vault) = EncryptedBackend::create("correct horse").expect("vault");
+        let mut entry = account("neo");
+        backend.store(&mut entry, "ghp_secret").expect("store");
+
+        assert!(entry.token.is_empty());
+        assert!(
+            !serde_json::to_string(&entry)
+                .expect("json")
+                .contains("ghp_secret")
+        );
+
+        let unlocked = EncryptedBackend::unlock("correct horse", &vault).expect("unlock");
+        assert_eq!(
+            unlocked.load(&entry).expect("load"),
+            Some(String::from("ghp_secret"))
+        );
+    }
+
+    #[test]
+    fn wrong_passphrase_is_rejected() {
+        let (_, vault) = EncryptedBackend::create("correct horse").expect("vault");
+
+        assert!(matches!(
+            EncryptedBackend::unlock("battery staple", &vault),
+            Err(SecretStoreError::WrongPassphrase)
+        ));
+    }
+
+    #[test]
+    fn sealed_tokens_are_bound_to_their_account() {
+        let (backend, _) = EncryptedBackend::create("correct horse").expect("vault");
+        let mut neo = account("neo");
+        backend.store(&mut neo, "ghp_secret").expect("store");
+
+        let mut trinity = account("trinity");
+        trinity.encrypted_token = neo.encrypted_token.clone();
+        assert!(backend.load(&trinity).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn token_command_uses_first_line_of_output() {
+        assert_eq!(
+            run_token_command("printf 'ghp_secret\\nurl: github.com\\n'").expect("token"),
+            "ghp_secret"
+        );
+        assert!(matches!(
+            run_token_command("echo locked >&2; exit 1"),
+            Err(SecretStoreError::TokenCommand(message)) if message.contains("locked")
+        ));
+        assert!(run_token_command("true").is_err());
+    }
+}
@@ -0,0 +1,318 @@
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use chrono::{DateTime, Utc};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{
+    domain::{GitHubAccount, NotificationChips, NotificationRule, RepoTier},
+    github::{self, FetchError},
+};
+
+/// Name of the shared file in the gist or repository.
+pub const SYNC_FILE: &str = "reminder-sync.json";
+
+/// A value together with the time it was last changed on any machine.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Stamped<T> {
+    pub value: T,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl<T: PartialEq> Stamped<T> {
+    /// `current` stamped `now` when it differs from what was synced last,
+    /// otherwise the synced stamp.
+    fn observe(baseline: Option<&Self>, current: T, now: DateTime<Utc>) -> Self {
+        match baseline {
+            Some(synced) if synced.value == current => Self {
+                value: current,
+                updated_at: synced.updated_at,
+            },
+            _ => Self {
+                value: current,
+                updated_at: Some(now),
+            },
+        }
+    }
+
+    /// Last write wins; ties keep `self`.
+    fn merge(self, other: Self) -> Self {
+        if other.updated_at > self.updated_at {
+            other
+        } else {
+            self
+        }
+    }
+}
+
+/// Preferences that shape triage, as opposed to ones about this machine.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TriageSettings {
+    pub rules: Vec<NotificationRule>,
+    pub repo_tiers: BTreeMap<String, RepoTier>,
+    pub auto_snooze_hours: BTreeMap<String, u32>,
+}
+
+/// Everything shared between machines. Tokens and machine-specific settings
+/// are never part of it.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SyncDocument {
+    pub settings: Stamped<TriageSettings>,
+    /// Per login, thread id → when the thread was marked done.
+    pub done: BTreeMap<String, BTreeMap<String, DateTime<Utc>>>,
+    /// Per login, the filter chips of its card.
+    pub chips: BTreeMap<String, Stamped<NotificationChips>>,
+}
+
+impl SyncDocument {
+    /// This machine's state, stamped against `baseline`, the document the
+    /// last successful sync produced.
+    pub fn local<'a>(
+        baseline: &Self,
+        now: DateTime<Utc>,
+        settings: TriageSettings,
+        accounts: impl IntoIterator<
+            Item = (
+                &'a str,
+                &'a BTreeMap<String, DateTime<Utc>>,
+                &'a NotificationChips,
+            ),
+        >,
+    ) -> Self {
+        let mut document = Self {
+            settings: Stamped::observe(Some(&baseline.settings), settings, now),
+            ..Self::default()
+        };
+        for (login, done, chips) in accounts {
+            document.done.insert(login.to_owned(), done.clone());
+            document.chips.insert(
+                login.to_owned(),
+                Stamped::observe(baseline.chips.get(login), chips.clone(), now),
+            );
+        }
+        document
+    }
+
+    /// Merges two documents entry by entry: the later write wins for each
+    /// thread id, each account's chips and the triage settings. Done marks
+    /// older than `oldest_done` are dropped.
+    pub fn merge(self, other: Self, oldest_done: DateTime<Utc>) -> Self {
+        let mut done = self.done;
+        for (login, threads) in other.done {
+            let merged = done.entry(login).or_default();
+            for (thread_id, at) in threads {
+                let entry = merged.entry(thread_id).or_insert(at);
+                *entry = (*entry).max(at);
+            }
+        }
+        for threads in done.values_mut() {
+            threads.retain(|_, at| *at >= oldest_done);
+        }
+        let mut chips = self.chips;
+        for (login, theirs) in other.chips {
+            let merged = match chips.remove(&login) {
+                Some(ours) => ours.merge(theirs),
+                None => theirs,
+            };
+            chips.insert(login, merged);
+        }
+        Self {
+            settings: self.settings.merge(other.settings),
+            done,
+            chips,
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum SyncError {
+    #[error("{0}")]
+    Fetch(#[from] FetchError),
+    #[error("Sync file is invalid: {0}")]
+    Format(#[from] serde_json::Error),
+    #[error("I/O error while syncing: {0}")]
+    Io(#[from] io::Error),
+    #[error("git {command} failed: {output}")]
+    Git { command: String, output: String },
+    #[error("Sync background task stopped unexpectedly")]
+    Interrupted,
+}
+
+/// Where [`sync`] reads and writes the shared document.
+pub enum SyncRemote {
+    Gist {
+        client: Client,
+        profile: Box<GitHubAccount>,
+        /// `None` creates a new secret gist.
+        gist_id: Option<String>,
+    },
+    GitRepo(PathBuf),
+}
+
+pub struct SyncOutcome {
+    pub document: SyncDocument,
+    /// Id of the gist written to, which is new after the first sync.
+    pub gist_id: Option<String>,
+}
+
+/// Pulls the shared document, merges `local` into it and pushes the result
+/// when it changed.
+pub async fn sync(
+    remote: SyncRemote,
+    local: SyncDocument,
+    oldest_done: DateTime<Utc>,
+) -> Result<SyncOutcome, SyncError> {
+    match remote {
+        SyncRemote::Gist {
+            client,
+            profile,
+            gist_id,
+        } => {
+            let shared = match &gist_id {
+                Some(gist_id) => {
+                    github::fetch_gist_file(&client, &profile, gist_id, SYNC_FILE).await?
+                }
+                None => None,
+            };
+            let shared: Option<SyncDocument> =
+                shared.as_deref().map(serde_json::from_str).transpose()?;
+            let document = local.merge(shared.clone().unwrap_or_default(), oldest_done);
+            let gist_id = if gist_id.is_some() && shared.as_ref() == Some(&document) {
+                gist_id
+            } else {
+                let content = serde_json::to_string_pretty(&document)?;
+                Some(
+                    github::write_gist_file(
+                        &client,
+                        &profile,
+                        gist_id.as_deref(),
+                        SYNC_FILE,
+                        content,
+                    )
+                    .await?,
+                )
+            };
+            Ok(SyncOutcome { document, gist_id })
+        }
+        SyncRemote::GitRepo(path) => {
+            tokio::task::spawn_blocking(move || sync_git_repo(&path, local, oldest_done))
+                .await
+                .map_err(|_| SyncError::Interrupted)?
+        }
+    }
+}
+
+fn sync_git_repo(
+    path: &Path,
+    local: SyncDocument,
+    oldest_done: DateTime<Utc>,
+) -> Result<SyncOutcome, SyncError> {
+    git(path, &["pull", "--ff-only", "--quiet"])?;
+    let file = path.join(SYNC_FILE);
+    let shared: Option<SyncDocument> = match fs::read_to_string(&file) {
+        Ok(raw) => Some(serde_json::from_str(&raw)?),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => None,
+        Err(err) => return Err(err.into()),
+    };
+    let document = local.merge(shared.clone().unwrap_or_default(), oldest_done);
+    if shared.as_ref() != Some(&document) {
+        fs::write(&file, serde_json::to_string_pretty(&document)?)?;
+        git(path, &["add", SYNC_FILE])?;
+        git(
+            path,
+            &["commit", "--quiet", "-m", "Sync reminder triage state"],
+        )?;
+        git(path, &["push", "--quiet"])?;
+    }
+    Ok(SyncOutcome {
+        document,
+        gist_id: None,
+    })
+}
+
+fn git(path: &Path, args: &[&str]) -> Result<(), SyncError> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(path)
+        .args(args)
+        .output()?;
+    if output.status.success() {
+        return Ok(());
+    }
+    Err(SyncError::Git {
+        command: args[0].to_owned(),
+        output: String::from_utf8_lossy(&output.stderr).trim().to_owned(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use chrono::{Duration, Utc};
+
+    use super::{SyncDocument, TriageSettings};
+    use crate::domain::{NotificationChips, NotificationRule, ReadStateChip};
+
+    #[test]
+    fn merge_keeps_the_latest_write_per_entry() {
+        let now = Utc::now();
+        let baseline = SyncDocument::default();
+        let chips = NotificationChips {
+            state: ReadStateChip::Unread,
+            ..NotificationChips::default()
+        };
+        let desktop_done = BTreeMap::from([
+            (String::from("1"), now - Duration::hours(2)),
+            (String::from("2"), now - Duration::days(40)),
+        ]);
+        let desktop = SyncDocument::local(
+            &baseline,
+            now - Duration::hours(1),
+            TriageSettings {
+                rules: vec![NotificationRule {
+                    repo: String::from("acme/*"),
+                    ..NotificationRule::default()
+                }],
+                ..TriageSettings::default()
+            },
+            [("neo", &desktop_done, &chips)],
+        );
+        let laptop_done = BTreeMap::from([
+            (String::from("1"), now - Duration::hours(5)),
+            (String::from("3"), now),
+        ]);
+        let laptop = SyncDocument::local(
+            &baseline,
+            now,
+            TriageSettings::default(),
+            [("neo", &laptop_done, &NotificationChips::default())],
+        );
+
+        let merged = desktop.merge(laptop, now - Duration::days(30));
+
+        let done = &merged.done["neo"];
+        assert_eq!(done.len(), 2);
+        assert_eq!(done["1"], now - Duration::hours(2));
+        // The laptop never touched its rules, so the desktop's edit wins.
+        assert_eq!(merged.settings.value.rules.len(), 1);
+        assert_eq!(merged.chips["neo"].value, NotificationChips::default());
+
+        let unchanged = SyncDocument::local(
+            &merged,
+            now + Duration::hours(1),
+            merged.settings.value.clone(),
+            [("neo", &laptop_done, &NotificationChips::default())],
+        );
+        assert_eq!(unchanged.settings.updated_at, merged.settings.updated_at);
+        assert_eq!(unchanged.chips["neo"].updated_at, Some(now));
+    }
+}
@@ -0,0 +1,204 @@
+use std::{
+    future::Future,
+    sync::{
+        Arc, Mutex, OnceLock,
+        mpsc::{self, Receiver, TryRecvError},
+    },
+    time::{Duration, Instant},
+};
+
+use tokio::{
+    runtime::{Builder, Runtime},
+    task::JoinHandle,
+};
+
+const WORKER_THREADS: usize = 2;
+
+/// Shared runtime for all network I/O, started on first use.
+fn runtime() -> &'static Runtime {
+    static RUNTIME: OnceLock<Runtime> = OnceLock::new();
+    RUNTIME.get_or_init(|| {
+        Builder::new_multi_thread()
+            .worker_threads(WORKER_THREADS)
+            .thread_name("reminder-io")
+            .enable_all()
+            .build()
+            .expect("failed to start the async runtime")
+    })
+}
+
+/// Latest status line of a running [`Task`], written by the task and read by
+/// the UI.
+#[derive(Clone, Default)]
+pub struct Progress(Arc<Mutex<Option<String>>>);
+
+impl Progress {
+    pub fn report(&self, message: impl Into<String>) {
+        if let Ok(mut latest) = self.0.lock() {
+            *latest = Some(message.into());
+        }
+    }
+
+    fn latest(&self) -> Option<String> {
+        self.0.lock().ok().and_then(|latest| latest.clone())
+    }
+}
+
+/// The task ended without a result: it panicked or was cancelled.
+#[derive(Debug)]
+pub struct TaskLost;
+
+/// Background work on the shared runtime, polled from the UI thread.
+/// Cancelling or dropping the task aborts it at its next `.await`.
+pub struct Task<T> {
+    receiver: Receiver<T>,
+    handle: JoinHandle<()>,
+    progress: Progress,
+    cancelled: bool,
+    started: Instant,
+}
+
+impl<T: Send + 'static> Task<T> {
+    pub fn spawn<F, Fut>(work: F) -> Self
+    where
+        F: FnOnce(Progress) -> Fut,
+        Fut: Future<Output = T> + Send + 'static,
+    {
+        let (tx, rx) = mpsc::channel();
+        let progress = Progress::default();
+        let work = work(progress.clone());
+        let handle = runtime().spawn(async move {
+            let _ = tx.send(work.await);
+        });
+        Self {
+            receiver: rx,
+            handle,
+            progress,
+            cancelled: false,
+            started: Instant::now(),
+        }
+    }
+
+    /// Like [`Task::spawn`], but abandons `work` once `deadline` passes and
+    /// hands over `timed_out()` instead.
+    pub fn spawn_with_deadline<F, Fut>(
+        deadline: Duration,
+        timed_out: impl FnOnce() -> T + Send + 'static,
+        work: F,
+    ) -> Self
+    where
+        F: FnOnce(Progress) -> Fut,
+        Fut: Future<Output = T> + Send + 'static,
+    {
+        Self::spawn(move |progress| {
+            let work = work(progress);
+            async move {
+                tokio::time::timeout(deadline, work)
+                    .await
+                    .unwrap_or_else(|_| timed_out())
+            }
+        })
+    }
+
+    /// A task that already produced `value`, for tests of the polling code.
+    #[cfg(test)]
+    pub fn finished(value: T) -> Self {
+        let (tx, rx) = mpsc::channel();
+        let _ = tx.send(value);
+        Self {
+            receiver: rx,
+            handle: runtime().spawn(async {}),
+            progress: Progress::default(),
+            cancelled: false,
+            started: Instant::now(),
+        }
+    }
+
+    /// The result once the task has finished, without blocking.
+    pub fn try_take(&self) -> Option<Result<T, TaskLost>> {
+        match self.receiver.try_recv() {
+            Ok(result) => Some(Ok(result)),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => Some(Err(TaskLost)),
+        }
+    }
+
+    pub fn progress(&self) -> Option<String> {
+        self.progress.latest()
+    }
+
+    /// Time since the task was spawned.
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    /// Requests an abort; the task counts as cancelling until
+    /// [`Task::try_take`] reports that it ended.
+    pub fn cancel(&mut self) {
+        self.cancelled = true;
+        self.handle.abort();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled
+    }
+}
+
+impl<T> Drop for Task<T> {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{thread, time::Duration};
+
+    use super::Task;
+
+    fn wait_for<T: Send + 'static>(task: &Task<T>) -> Option<T> {
+        for _ in 0..200 {
+            if let Some(result) = task.try_take() {
+                return result.ok();
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        panic!("task did not finish");
+    }
+
+    #[test]
+    fn finished_tasks_hand_over_their_result_and_progress() {
+        let task = Task::spawn(|progress| async move {
+            progress.report("halfway");
+            21 * 2
+        });
+
+        assert_eq!(wait_for(&task), Some(42));
+        assert_eq!(task.progress().as_deref(), Some("halfway"));
+    }
+
+    #[test]
+    fn overdue_tasks_hand_over_the_timeout_value() {
+        let task = Task::spawn_with_deadline(
+            Duration::from_millis(20),
+            || "timed out",
+            |_| async {
+                tokio::time::sleep(Duration::from_secs(60)).await;
+                "finished"
+            },
+        );
+
+        assert_eq!(wait_for(&task), Some("timed out"));
+    }
+
+    #[test]
+    fn cancelled_tasks_report_no_result() {
+        let mut task = Task::spawn(|_| async {
+            tokio::time::sleep(Duration::from_secs(60)).await;
+        });
+        task.cancel();
+
+        assert!(task.is_cancelled());
+        assert_eq!(wait_for(&task), None);
+    }
+}